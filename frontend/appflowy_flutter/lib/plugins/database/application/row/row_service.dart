@@ -109,11 +109,13 @@ class RowBackendService {
 
   static Future<FlowyResult<void, FlowyError>> duplicateRow(
     String viewId,
-    RowId rowId,
-  ) {
-    final payload = RowIdPB(
+    RowId rowId, {
+    bool copyDocument = false,
+  }) {
+    final payload = DuplicateRowPayloadPB(
       viewId: viewId,
       rowId: rowId,
+      copyDocument: copyDocument,
     );
 
     return DatabaseEventDuplicateRow(payload).send();
@@ -1,5 +1,6 @@
 import {
   CreateRowPayloadPB,
+  DuplicateRowPayloadPB,
   MoveGroupRowPayloadPB,
   MoveRowPayloadPB,
   OrderObjectPositionTypePB,
@@ -38,11 +39,17 @@ export async function createRow(viewId: string, params?: {
   return result.map(pbToRowMeta).unwrap();
 }
 
-export async function duplicateRow(viewId: string, rowId: string, groupId?: string): Promise<void> {
-  const payload = RowIdPB.fromObject({
+export async function duplicateRow(
+  viewId: string,
+  rowId: string,
+  groupId?: string,
+  copyDocument = false
+): Promise<void> {
+  const payload = DuplicateRowPayloadPB.fromObject({
     view_id: viewId,
     row_id: rowId,
     group_id: groupId,
+    copy_document: copyDocument,
   });
 
   const result = await DatabaseEventDuplicateRow(payload);
@@ -23,7 +23,7 @@ use lib_infra::{if_native, if_wasm};
 use parking_lot::{Mutex, RwLock};
 use tracing::{instrument, trace};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CollabPluginProviderType {
   Local,
   AppFlowyCloud,
@@ -103,6 +103,11 @@ impl AppFlowyCollabBuilder {
     *self.rocksdb_backup.lock() = Some(rocksdb_backup);
   }
 
+  /// Returns the type of the server that the collab objects sync with.
+  pub fn provider_type(&self) -> CollabPluginProviderType {
+    self.plugin_provider.read().provider_type()
+  }
+
   pub fn update_network(&self, reachable: bool) {
     if reachable {
       self
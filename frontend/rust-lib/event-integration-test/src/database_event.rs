@@ -11,7 +11,7 @@ use flowy_database2::services::cell::CellBuilder;
 use flowy_database2::services::field::{
   MultiSelectTypeOption, SelectOption, SingleSelectTypeOption,
 };
-use flowy_database2::services::share::csv::CSVFormat;
+use flowy_database2::services::share::csv::{CSVExportOptions, CSVFormat};
 use flowy_folder::entities::*;
 use flowy_folder::event_map::FolderEvent;
 use flowy_user::errors::FlowyError;
@@ -27,7 +27,7 @@ impl EventIntegrationTest {
       .get_database_with_view_id(database_view_id)
       .await
       .unwrap()
-      .export_csv(CSVFormat::Original)
+      .export_csv(CSVFormat::Original, CSVExportOptions::default())
       .await
       .unwrap()
   }
@@ -179,6 +179,7 @@ impl EventIntegrationTest {
         view_id: view_id.to_string(),
         field_id: field_id.to_string(),
         field_type,
+        select_option_mapping: vec![],
       })
       .async_send()
       .await
@@ -284,16 +285,42 @@ impl EventIntegrationTest {
   pub async fn duplicate_row(&self, view_id: &str, row_id: &str) -> Option<FlowyError> {
     EventBuilder::new(self.clone())
       .event(DatabaseEvent::DuplicateRow)
-      .payload(RowIdPB {
+      .payload(DuplicateRowPayloadPB {
         view_id: view_id.to_string(),
         row_id: row_id.to_string(),
         group_id: None,
+        copy_document: false,
       })
       .async_send()
       .await
       .error()
   }
 
+  pub async fn create_row_from_document(&self, view_id: &str, document_id: &str) -> RowMetaPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CreateRowFromDocument)
+      .payload(CreateRowFromDocumentPB {
+        view_id: view_id.to_string(),
+        document_id: document_id.to_string(),
+      })
+      .async_send()
+      .await
+      .parse::<RowMetaPB>()
+  }
+
+  pub async fn create_document_from_row(&self, view_id: &str, row_id: &str) -> CreatedDocumentPB {
+    EventBuilder::new(self.clone())
+      .event(DatabaseEvent::CreateDocumentFromRow)
+      .payload(RowIdPB {
+        view_id: view_id.to_string(),
+        row_id: row_id.to_string(),
+        group_id: None,
+      })
+      .async_send()
+      .await
+      .parse::<CreatedDocumentPB>()
+  }
+
   pub async fn move_row(&self, view_id: &str, row_id: &str, to_row_id: &str) -> Option<FlowyError> {
     EventBuilder::new(self.clone())
       .event(DatabaseEvent::MoveRow)
@@ -496,6 +523,7 @@ impl EventIntegrationTest {
       .event(DatabaseEvent::GetAllCalendarEvents)
       .payload(CalendarEventRequestPB {
         view_id: view_id.to_string(),
+        date_field_id: None,
       })
       .async_send()
       .await
@@ -15,6 +15,7 @@ use tokio::time::sleep;
 
 use flowy_core::config::AppFlowyCoreConfig;
 use flowy_core::AppFlowyCore;
+use flowy_database2::services::database::LowMemoryProfile;
 use flowy_notification::register_notification_sender;
 use flowy_server::AppFlowyServer;
 use flowy_user::entities::AuthenticatorPB;
@@ -51,6 +52,23 @@ impl EventIntegrationTest {
   }
 
   pub async fn new_with_user_data_path(path_buf: PathBuf, name: String) -> Self {
+    Self::new_with_config(path_buf, name, |config| config).await
+  }
+
+  /// Same as [Self::new], but the core runs with the [LowMemoryProfile].
+  pub async fn new_with_low_memory_profile(profile: LowMemoryProfile) -> Self {
+    let temp_dir = temp_dir().join(nanoid!(6));
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    Self::new_with_config(temp_dir, nanoid!(6), |config| {
+      config.low_memory_profile(Some(profile))
+    })
+    .await
+  }
+
+  async fn new_with_config<F>(path_buf: PathBuf, name: String, configure: F) -> Self
+  where
+    F: FnOnce(AppFlowyCoreConfig) -> AppFlowyCoreConfig,
+  {
     let path = path_buf.to_str().unwrap().to_string();
     let device_id = uuid::Uuid::new_v4().to_string();
 
@@ -71,7 +89,7 @@ impl EventIntegrationTest {
       ],
     );
 
-    let inner = init_core(config).await;
+    let inner = init_core(configure(config)).await;
     let notification_sender = TestNotificationSender::new();
     let authenticator = Arc::new(RwLock::new(AuthenticatorPB::Local));
     register_notification_sender(notification_sender.clone());
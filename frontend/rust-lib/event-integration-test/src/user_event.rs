@@ -19,9 +19,9 @@ use flowy_server_pub::af_cloud_config::AFCloudConfiguration;
 use flowy_server_pub::AuthenticatorType;
 use flowy_user::entities::{
   AuthenticatorPB, ChangeWorkspaceIconPB, CloudSettingPB, CreateWorkspacePB, ImportAppFlowyDataPB,
-  OauthSignInPB, RenameWorkspacePB, RepeatedUserWorkspacePB, SignInUrlPB, SignInUrlPayloadPB,
-  SignUpPayloadPB, UpdateCloudConfigPB, UpdateUserProfilePayloadPB, UserProfilePB,
-  UserWorkspaceIdPB, UserWorkspacePB,
+  OauthSignInPB, RenameWorkspacePB, ReorderWorkspacesPB, RepeatedUserWorkspacePB, SignInUrlPB,
+  SignInUrlPayloadPB, SignUpPayloadPB, UpdateCloudConfigPB, UpdateUserProfilePayloadPB,
+  UserProfilePB, UserWorkspaceIdPB, UserWorkspacePB, WorkspaceFeatureFlagsPB,
 };
 use flowy_user::errors::{FlowyError, FlowyResult};
 use flowy_user::event_map::UserEvent;
@@ -218,6 +218,7 @@ impl EventIntegrationTest {
   pub async fn create_workspace(&self, name: &str) -> UserWorkspacePB {
     let payload = CreateWorkspacePB {
       name: name.to_string(),
+      unique_name: false,
     };
     EventBuilder::new(self.clone())
       .event(UserEvent::CreateWorkspace)
@@ -235,6 +236,7 @@ impl EventIntegrationTest {
     let payload = RenameWorkspacePB {
       workspace_id: workspace_id.to_owned(),
       new_name: new_name.to_owned(),
+      strict: false,
     };
     match EventBuilder::new(self.clone())
       .event(UserEvent::RenameWorkspace)
@@ -256,6 +258,7 @@ impl EventIntegrationTest {
     let payload = ChangeWorkspaceIconPB {
       workspace_id: workspace_id.to_owned(),
       new_icon: new_icon.to_owned(),
+      strict: false,
     };
     match EventBuilder::new(self.clone())
       .event(UserEvent::ChangeWorkspaceIcon)
@@ -325,6 +328,30 @@ impl EventIntegrationTest {
       .async_send()
       .await;
   }
+
+  pub async fn get_workspace_feature_flags(&self, workspace_id: &str) -> WorkspaceFeatureFlagsPB {
+    let payload = UserWorkspaceIdPB {
+      workspace_id: workspace_id.to_string(),
+    };
+    EventBuilder::new(self.clone())
+      .event(UserEvent::GetWorkspaceFeatureFlags)
+      .payload(payload)
+      .async_send()
+      .await
+      .parse::<WorkspaceFeatureFlagsPB>()
+  }
+
+  pub async fn reorder_workspaces(&self, ordered_workspace_ids: Vec<String>) -> Option<FlowyError> {
+    let payload = ReorderWorkspacesPB {
+      ordered_workspace_ids,
+    };
+    EventBuilder::new(self.clone())
+      .event(UserEvent::ReorderWorkspaces)
+      .payload(payload)
+      .async_send()
+      .await
+      .error()
+  }
 }
 
 #[derive(Clone)]
@@ -450,6 +477,7 @@ pub async fn user_localhost_af_cloud() {
     base_url,
     ws_base_url,
     gotrue_url,
+    ..Default::default()
   }
   .write_env();
   std::env::set_var("GOTRUE_ADMIN_EMAIL", "admin@example.com");
@@ -900,3 +900,55 @@ async fn get_detailed_relation_cell_data() {
   assert_eq!(rows.len(), 1);
   assert_eq!(rows[0].name, "hello world");
 }
+
+#[tokio::test]
+async fn create_row_from_document_event_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let document = test.create_document("my task").await;
+
+  let row_meta = test
+    .create_row_from_document(&grid_view.id, &document.id)
+    .await;
+  assert_ne!(row_meta.document_id, document.id);
+  // A new document only contains an empty paragraph
+  assert!(row_meta.is_document_empty);
+
+  let primary_field = test.get_primary_field(&grid_view.id).await;
+  let name = test
+    .get_text_cell(&grid_view.id, &row_meta.id, &primary_field.id)
+    .await;
+  assert_eq!(name, "my task");
+  let database = test.get_database(&grid_view.id).await;
+  assert_eq!(database.rows.len(), 4);
+}
+
+#[tokio::test]
+async fn create_document_from_row_event_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid_view = test
+    .create_grid(&current_workspace.id, "my grid view".to_owned(), vec![])
+    .await;
+  let database = test.get_database(&grid_view.id).await;
+  let row_id = database.rows[0].id.clone();
+  let primary_field = test.get_primary_field(&grid_view.id).await;
+  let changeset = CellChangesetPB {
+    view_id: grid_view.id.clone(),
+    row_id: row_id.clone(),
+    field_id: primary_field.id.clone(),
+    cell_changeset: "my task".to_string(),
+  };
+  assert!(test.update_cell(changeset).await.is_none());
+
+  let created = test.create_document_from_row(&grid_view.id, &row_id).await;
+  let view = test.get_view(&created.document_id).await;
+  assert_eq!(view.name, "my task");
+
+  // The row is left as it is
+  let database = test.get_database(&grid_view.id).await;
+  assert_eq!(database.rows.len(), 3);
+}
@@ -0,0 +1,40 @@
+use event_integration_test::EventIntegrationTest;
+use flowy_core::export::{CancellationToken, EXPORT_MANIFEST_FILE};
+use flowy_user::errors::ErrorCode;
+use tempdir::TempDir;
+
+#[tokio::test]
+async fn export_workspace_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let out_dir = TempDir::new("export_workspace").unwrap();
+
+  let root_dir = test
+    .appflowy_core
+    .export_workspace(out_dir.path(), CancellationToken::new())
+    .await
+    .unwrap();
+
+  let manifest = std::fs::read_to_string(root_dir.join(EXPORT_MANIFEST_FILE)).unwrap();
+  let manifest = serde_json::from_str::<serde_json::Value>(&manifest).unwrap();
+  let views = manifest["views"].as_array().unwrap();
+  assert!(!views.is_empty());
+  for view in views {
+    let file = view["file"].as_str().unwrap();
+    assert!(root_dir.join(file).exists(), "{} doesn't exist", file);
+  }
+}
+
+#[tokio::test]
+async fn cancel_export_workspace_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let out_dir = TempDir::new("export_workspace").unwrap();
+  let cancel_token = CancellationToken::new();
+  cancel_token.cancel();
+
+  let error = test
+    .appflowy_core
+    .export_workspace(out_dir.path(), cancel_token)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::Cancelled);
+}
@@ -1,3 +1,4 @@
+mod export_test;
 mod folder_test;
 mod import_test;
 mod script;
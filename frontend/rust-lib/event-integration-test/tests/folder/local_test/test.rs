@@ -174,6 +174,52 @@ async fn put_back_trash_event_test() {
   assert!(error.is_none());
 }
 
+#[tokio::test]
+async fn put_back_trash_whose_parent_is_in_trash_event_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let parent_view = test
+    .create_view(&current_workspace.id, "Parent view".to_string())
+    .await;
+  let child_view = test
+    .create_view(&parent_view.id, "Child view".to_string())
+    .await;
+  test.delete_view(&child_view.id).await;
+  test.delete_view(&parent_view.id).await;
+  assert_eq!(test.get_trash().await.items.len(), 2);
+
+  // The parent stays in the trash, so the child is put back to the root of the workspace
+  let payload = TrashIdPB {
+    id: child_view.id.clone(),
+  };
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder::event_map::FolderEvent::RestoreTrashItem)
+    .payload(payload)
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+
+  let view = test.get_view(&child_view.id).await;
+  assert_eq!(view.parent_view_id, current_workspace.id);
+  let trash = test.get_trash().await.items;
+  assert_eq!(trash.len(), 1);
+  assert_eq!(trash[0].id, parent_view.id);
+
+  // The views that aren't in the trash can't be put back
+  let payload = TrashIdPB {
+    id: child_view.id.clone(),
+  };
+  let error = EventBuilder::new(test.clone())
+    .event(flowy_folder::event_map::FolderEvent::RestoreTrashItem)
+    .payload(payload)
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::RecordNotFound);
+}
+
 #[tokio::test]
 async fn delete_view_permanently_event_test() {
   let test = EventIntegrationTest::new_anon().await;
@@ -558,3 +604,144 @@ async fn move_folder_nested_view(
     .async_send()
     .await;
 }
+
+#[tokio::test]
+async fn add_and_remove_favorite_views_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let grid = test
+    .create_grid(&current_workspace.id, "My grid".to_string(), vec![])
+    .await;
+  let document = test
+    .create_view(&current_workspace.id, "My document".to_string())
+    .await;
+
+  test
+    .folder_manager
+    .add_favorite_view(&grid.id)
+    .await
+    .unwrap();
+  test
+    .folder_manager
+    .add_favorite_view(&document.id)
+    .await
+    .unwrap();
+  // Adding the same view twice keeps its position
+  test
+    .folder_manager
+    .add_favorite_view(&grid.id)
+    .await
+    .unwrap();
+
+  let favorites = test.folder_manager.list_favorite_views().await.unwrap();
+  assert_eq!(favorites.len(), 2);
+  assert_eq!(favorites[0].name, "My grid");
+  assert_eq!(favorites[0].layout, ViewLayoutPB::Grid);
+  assert_eq!(favorites[1].name, "My document");
+  assert_eq!(favorites[1].layout, ViewLayoutPB::Document);
+  // The quick-access list doesn't touch the favorites of the folder
+  assert!(!favorites[0].is_favorite);
+
+  test
+    .folder_manager
+    .remove_favorite_view(&grid.id)
+    .await
+    .unwrap();
+  let favorites = test.folder_manager.list_favorite_views().await.unwrap();
+  assert_eq!(favorites.len(), 1);
+  assert_eq!(favorites[0].id, document.id);
+
+  // The views in the trash are skipped
+  test.delete_view(&document.id).await;
+  let favorites = test.folder_manager.list_favorite_views().await.unwrap();
+  assert!(favorites.is_empty());
+}
+
+#[tokio::test]
+async fn move_view_to_parent_test() {
+  let test = EventIntegrationTest::new_anon().await;
+  let current_workspace = test.get_current_workspace().await;
+  let folder = test
+    .create_view(&current_workspace.id, "Folder".to_string())
+    .await;
+  let document = test
+    .create_view(&folder.id, "My document".to_string())
+    .await;
+  let grid = test
+    .create_grid(&current_workspace.id, "My grid".to_string(), vec![])
+    .await;
+
+  test
+    .folder_manager
+    .move_view_to_parent(&grid.id, &folder.id, 0)
+    .await
+    .unwrap();
+  let child_view_ids = test
+    .get_view(&folder.id)
+    .await
+    .child_views
+    .into_iter()
+    .map(|view| view.id)
+    .collect::<Vec<_>>();
+  assert_eq!(child_view_ids, vec![grid.id.clone(), document.id.clone()]);
+  let moved_grid = test.get_view(&grid.id).await;
+  assert_eq!(moved_grid.parent_view_id, folder.id);
+  assert_eq!(moved_grid.layout, ViewLayoutPB::Grid);
+
+  // The index is clamped to the number of the child views
+  test
+    .folder_manager
+    .move_view_to_parent(&grid.id, &folder.id, 10)
+    .await
+    .unwrap();
+  let child_views = test.get_view(&folder.id).await.child_views;
+  assert_eq!(child_views.last().unwrap().id, grid.id);
+
+  // A view can't be moved into itself or its descendants
+  for new_parent_id in [&folder.id, &document.id] {
+    let error = test
+      .folder_manager
+      .move_view_to_parent(&folder.id, new_parent_id, 0)
+      .await
+      .unwrap_err();
+    assert_eq!(error.code, ErrorCode::MoveViewIntoItself);
+  }
+
+  test
+    .folder_manager
+    .move_view_to_parent(&grid.id, &current_workspace.id, 0)
+    .await
+    .unwrap();
+  let workspace = test.get_current_workspace().await;
+  assert_eq!(workspace.views[0].id, grid.id);
+  assert_eq!(test.get_view(&folder.id).await.child_views.len(), 1);
+
+  // The view joins the section of the new parent and keeps it at the top level
+  let private_folder = test
+    .create_view(&current_workspace.id, "Private folder".to_string())
+    .await;
+  test
+    .folder_manager
+    .set_views_visibility(vec![private_folder.id.clone()], false);
+  for (new_parent_id, is_private) in [(&private_folder.id, true), (&folder.id, false)] {
+    test
+      .folder_manager
+      .move_view_to_parent(&grid.id, new_parent_id, 0)
+      .await
+      .unwrap();
+    test
+      .folder_manager
+      .move_view_to_parent(&grid.id, &current_workspace.id, 0)
+      .await
+      .unwrap();
+    let private_views = test
+      .folder_manager
+      .get_workspace_private_views()
+      .await
+      .unwrap();
+    assert_eq!(
+      private_views.iter().any(|view| view.id == grid.id),
+      is_private
+    );
+  }
+}
@@ -4,10 +4,13 @@ use collab_entity::CollabType;
 use collab_folder::Folder;
 use event_integration_test::user_event::user_localhost_af_cloud;
 use event_integration_test::EventIntegrationTest;
+use flowy_user::entities::RemovedUserWorkspacePB;
+use flowy_user::protobuf::UserNotification;
 use std::time::Duration;
 use tokio::time::sleep;
 
 use crate::user::af_cloud_test::util::get_synced_workspaces;
+use crate::util::receive_with_timeout;
 
 #[tokio::test]
 async fn af_cloud_workspace_delete() {
@@ -30,6 +33,77 @@ async fn af_cloud_workspace_delete() {
   assert_eq!(workspaces.len(), 1);
 }
 
+#[tokio::test]
+async fn af_cloud_workspace_deleted_by_other_admin_test() {
+  user_localhost_af_cloud().await;
+  let owner = EventIntegrationTest::new().await;
+  let owner_profile = owner.af_cloud_sign_up().await;
+  let shared_workspace = owner.create_workspace("shared workspace").await;
+
+  let member = EventIntegrationTest::new().await;
+  let member_profile = member.af_cloud_sign_up().await;
+  owner
+    .add_workspace_member(&shared_workspace.workspace_id, &member_profile.email)
+    .await;
+  let workspaces = get_synced_workspaces(&member, member_profile.id).await;
+  assert_eq!(workspaces.len(), 2);
+  member.open_workspace(&shared_workspace.workspace_id).await;
+
+  // The owner deletes the workspace the member is currently in.
+  owner.delete_workspace(&shared_workspace.workspace_id).await;
+  let rx = member
+    .notification_sender
+    .subscribe::<RemovedUserWorkspacePB>(
+      &member_profile.id.to_string(),
+      UserNotification::DidRemoveUserWorkspace as i32,
+    );
+  let _ = member.get_all_workspaces().await;
+  let removed = receive_with_timeout(rx, Duration::from_secs(60))
+    .await
+    .unwrap();
+  assert_eq!(removed.workspace_id, shared_workspace.workspace_id);
+  assert!(removed.is_current_workspace);
+  assert_eq!(removed.fallback_workspace_id, member_profile.workspace_id);
+
+  let workspaces = get_synced_workspaces(&member, member_profile.id).await;
+  assert_eq!(workspaces.len(), 1);
+  assert_ne!(owner_profile.workspace_id, workspaces[0].workspace_id);
+  // The member is switched to the fallback workspace
+  let current_workspace = member.get_current_workspace().await;
+  assert_eq!(current_workspace.id, member_profile.workspace_id);
+}
+
+#[tokio::test]
+async fn af_cloud_reorder_workspaces_test() {
+  user_localhost_af_cloud().await;
+  let test = EventIntegrationTest::new().await;
+  let user_profile_pb = test.af_cloud_sign_up().await;
+  let second = test.create_workspace("second workspace").await;
+  let third = test.create_workspace("third workspace").await;
+  let workspaces = get_synced_workspaces(&test, user_profile_pb.id).await;
+  assert_eq!(workspaces.len(), 3);
+
+  let error = test
+    .reorder_workspaces(vec![
+      third.workspace_id.clone(),
+      second.workspace_id.clone(),
+    ])
+    .await;
+  assert!(error.is_none());
+
+  // The workspace that is not in the ordered list is appended at the end
+  let workspaces = test.get_all_workspaces().await.items;
+  assert_eq!(workspaces[0].workspace_id, third.workspace_id);
+  assert_eq!(workspaces[1].workspace_id, second.workspace_id);
+  assert_eq!(workspaces[2].workspace_id, user_profile_pb.workspace_id);
+
+  // Unknown workspace ids are rejected
+  let error = test
+    .reorder_workspaces(vec![uuid::Uuid::new_v4().to_string()])
+    .await;
+  assert!(error.is_some());
+}
+
 #[tokio::test]
 async fn af_cloud_workspace_change_name_and_icon() {
   user_localhost_af_cloud().await;
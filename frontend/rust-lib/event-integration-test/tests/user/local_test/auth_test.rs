@@ -1,6 +1,11 @@
+use std::path::Path;
+
 use event_integration_test::user_event::{login_password, unique_email};
 use event_integration_test::{event_builder::EventBuilder, EventIntegrationTest};
-use flowy_user::entities::{AuthenticatorPB, SignInPayloadPB, SignUpPayloadPB};
+use flowy_user::entities::{
+  AuthenticatorPB, ChangePasswordPB, DeleteAccountPB, SignInPayloadPB, SignUpPayloadPB,
+  UserProfilePB, DELETE_ACCOUNT_CONFIRMATION,
+};
 use flowy_user::errors::ErrorCode;
 use flowy_user::event_map::UserEvent::*;
 
@@ -103,3 +108,91 @@ async fn sign_in_with_invalid_password() {
       .is_some())
   }
 }
+
+#[tokio::test]
+async fn delete_anon_user_account() {
+  let sdk = EventIntegrationTest::new().await;
+  let user = sdk.init_anon_user().await;
+  let user_dir = sdk.user_manager.user_dir(user.id);
+  assert!(Path::new(&user_dir).exists());
+
+  let error = EventBuilder::new(sdk.clone())
+    .event(DeleteAccount)
+    .payload(DeleteAccountPB {
+      uid: user.id,
+      confirmation: "delete".to_string(),
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+  assert!(Path::new(&user_dir).exists());
+
+  let error = EventBuilder::new(sdk.clone())
+    .event(DeleteAccount)
+    .payload(DeleteAccountPB {
+      uid: user.id,
+      confirmation: DELETE_ACCOUNT_CONFIRMATION.to_string(),
+    })
+    .async_send()
+    .await
+    .error();
+  assert!(error.is_none());
+  assert!(!Path::new(&user_dir).exists());
+
+  let result = EventBuilder::new(sdk.clone())
+    .event(GetUserProfile)
+    .async_send()
+    .await
+    .try_parse::<UserProfilePB>();
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn sign_in_anonymous_reuses_anon_user() {
+  let sdk = EventIntegrationTest::new().await;
+  let sign_in_anonymous = || async {
+    EventBuilder::new(sdk.clone())
+      .event(SignInAnonymous)
+      .async_send()
+      .await
+      .parse::<UserProfilePB>()
+  };
+
+  let user = sign_in_anonymous().await;
+  assert_eq!(user.authenticator, AuthenticatorPB::Local);
+  let current_user = sdk.get_user_profile().await.unwrap();
+  assert_eq!(current_user.id, user.id);
+  assert_eq!(current_user.workspace_id, user.workspace_id);
+
+  // Signing in again doesn't create another anonymous user
+  let user_again = sign_in_anonymous().await;
+  assert_eq!(user_again.id, user.id);
+  assert_eq!(user_again.workspace_id, user.workspace_id);
+  let anon_user = EventBuilder::new(sdk.clone())
+    .event(GetAnonUser)
+    .async_send()
+    .await
+    .parse::<UserProfilePB>();
+  assert_eq!(anon_user.id, user.id);
+}
+
+#[tokio::test]
+async fn change_password_of_anon_user() {
+  let sdk = EventIntegrationTest::new().await;
+  let _ = sdk.init_anon_user().await;
+
+  // The local users sign in without a password
+  let error = EventBuilder::new(sdk.clone())
+    .event(ChangePassword)
+    .payload(ChangePasswordPB {
+      old_password: login_password(),
+      new_password: "HelloWorld!456".to_string(),
+    })
+    .async_send()
+    .await
+    .error()
+    .unwrap();
+  assert_eq!(error.code, ErrorCode::PasswordAuthNotSupported);
+}
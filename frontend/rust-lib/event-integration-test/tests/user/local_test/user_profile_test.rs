@@ -1,6 +1,8 @@
 use crate::user::local_test::helper::*;
 use event_integration_test::{event_builder::EventBuilder, EventIntegrationTest};
-use flowy_user::entities::{AuthenticatorPB, UpdateUserProfilePayloadPB, UserProfilePB};
+use flowy_user::entities::{
+  AuthenticatorPB, RepeatedUserWorkspacePB, UpdateUserProfilePayloadPB, UserProfilePB,
+};
 use flowy_user::{errors::ErrorCode, event_map::UserEvent::*};
 use nanoid::nanoid;
 #[tokio::test]
@@ -147,3 +149,67 @@ async fn user_update_with_invalid_name() {
     .error()
     .is_some())
 }
+
+#[tokio::test]
+async fn anon_user_get_workspace_feature_flags() {
+  let test = EventIntegrationTest::new().await;
+  let user_profile = test.init_anon_user().await;
+  let flags = test
+    .get_workspace_feature_flags(&user_profile.workspace_id)
+    .await;
+  assert_eq!(flags.workspace_id, user_profile.workspace_id);
+  assert!(!flags.guest_sharing);
+  assert!(!flags.ai);
+  assert_eq!(flags.member_limit, Some(1));
+}
+
+#[tokio::test]
+async fn anon_user_rename_workspace_reverted_when_server_rejects() {
+  let test = EventIntegrationTest::new().await;
+  let user_profile = test.init_anon_user().await;
+  let workspace_name = |workspaces: RepeatedUserWorkspacePB| {
+    workspaces
+      .items
+      .into_iter()
+      .find(|workspace| workspace.workspace_id == user_profile.workspace_id)
+      .unwrap()
+      .name
+  };
+  let old_name = workspace_name(test.get_all_workspaces().await);
+
+  // The local server doesn't support renaming the workspace
+  let result = test
+    .rename_workspace(&user_profile.workspace_id, "new name")
+    .await;
+  assert!(result.is_err());
+  assert_eq!(workspace_name(test.get_all_workspaces().await), old_name);
+}
+
+#[tokio::test]
+async fn anon_user_workspace_kv_store() {
+  let test = EventIntegrationTest::new().await;
+  let user_profile = test.init_anon_user().await;
+  let store = test.appflowy_core.workspace_kv_store().unwrap();
+  assert_eq!(store.workspace_id(), user_profile.workspace_id);
+  assert_eq!(store.get("theme").unwrap(), None);
+
+  store.set("theme", "dark").unwrap();
+  store.set("last_opened_view", "view_1").unwrap();
+  store.set("theme", "light").unwrap();
+  assert_eq!(store.get("theme").unwrap(), Some("light".to_string()));
+  assert_eq!(
+    store.list_keys().unwrap(),
+    vec!["last_opened_view", "theme"]
+  );
+
+  // The settings of the other workspaces are isolated
+  let other_store = test.appflowy_core.user_manager.workspace_kv_store("other");
+  assert_eq!(other_store.get("theme").unwrap(), None);
+  other_store.set("theme", "dark").unwrap();
+  assert_eq!(store.get("theme").unwrap(), Some("light".to_string()));
+
+  assert!(store.delete("theme").unwrap());
+  assert!(!store.delete("theme").unwrap());
+  assert_eq!(store.list_keys().unwrap(), vec!["last_opened_view"]);
+  assert_eq!(other_store.list_keys().unwrap(), vec!["theme"]);
+}
@@ -1,17 +1,25 @@
 use std::fmt;
 use std::path::Path;
+use std::time::Duration;
 
 use base64::Engine;
 use tracing::{error, info};
 
+use flowy_database2::services::database::LowMemoryProfile;
 use flowy_server_pub::af_cloud_config::AFCloudConfiguration;
 use flowy_server_pub::supabase_config::SupabaseConfiguration;
+use flowy_sqlite::DEFAULT_POOL_SIZE;
 use flowy_user::services::entities::URL_SAFE_ENGINE;
 use lib_infra::file_util::copy_dir_recursive;
 use lib_infra::util::Platform;
 
 use crate::integrate::log::create_log_filter;
 
+const DEFAULT_IDLE_DATABASE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+const DEFAULT_CONNECTIVITY_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+/// Any response counts as reachable, so the probe works with the servers that don't have it.
+const DEFAULT_CONNECTIVITY_PROBE_ENDPOINT: &str = "/api/health";
+
 #[derive(Clone)]
 pub struct AppFlowyCoreConfig {
   /// Different `AppFlowyCoreConfig` instance should have different name
@@ -27,6 +35,19 @@ pub struct AppFlowyCoreConfig {
   /// the origin_application_path.
   pub application_path: String,
   pub(crate) log_filter: String,
+  /// Masks emails, tokens and user ids in the logs. Enabled by default in release builds.
+  pub(crate) redact_log: bool,
+  /// The max number of the connections of the user's sqlite database.
+  pub(crate) db_pool_size: u32,
+  /// The database editors that have no opening views are closed after being idle for this long
+  /// when the memory is low. None disables it.
+  pub(crate) idle_database_timeout: Option<Duration>,
+  /// Limits the memory used by the databases on memory-constrained devices. None by default.
+  pub(crate) low_memory_profile: Option<LowMemoryProfile>,
+  /// How often the server is probed to check whether it can be reached. None disables it.
+  pub(crate) connectivity_probe_interval: Option<Duration>,
+  /// The path, relative to the base url of the server, that the connectivity probe requests.
+  pub(crate) connectivity_probe_endpoint: String,
   cloud_config: Option<AFCloudConfiguration>,
 }
 
@@ -36,6 +57,13 @@ impl fmt::Debug for AppFlowyCoreConfig {
     debug.field("app_version", &self.app_version);
     debug.field("storage_path", &self.storage_path);
     debug.field("application_path", &self.application_path);
+    debug.field("db_pool_size", &self.db_pool_size);
+    debug.field("idle_database_timeout", &self.idle_database_timeout);
+    debug.field("low_memory_profile", &self.low_memory_profile);
+    debug.field(
+      "connectivity_probe_interval",
+      &self.connectivity_probe_interval,
+    );
     if let Some(config) = &self.cloud_config {
       debug.field("base_url", &config.base_url);
       debug.field("ws_url", &config.ws_base_url);
@@ -103,6 +131,12 @@ impl AppFlowyCoreConfig {
       device_id,
       platform,
       log_filter,
+      redact_log: !cfg!(debug_assertions),
+      db_pool_size: DEFAULT_POOL_SIZE,
+      idle_database_timeout: Some(DEFAULT_IDLE_DATABASE_TIMEOUT),
+      low_memory_profile: None,
+      connectivity_probe_interval: Some(DEFAULT_CONNECTIVITY_PROBE_INTERVAL),
+      connectivity_probe_endpoint: DEFAULT_CONNECTIVITY_PROBE_ENDPOINT.to_string(),
       cloud_config,
     }
   }
@@ -115,4 +149,54 @@ impl AppFlowyCoreConfig {
     );
     self
   }
+
+  /// Turn off the log redaction to see the full emails and tokens when debugging locally.
+  pub fn redact_log(mut self, enable: bool) -> Self {
+    self.redact_log = enable;
+    self
+  }
+
+  /// Raise the pool size for large workspaces that are edited concurrently. It takes effect the
+  /// next time the user's database is opened.
+  pub fn db_pool_size(mut self, db_pool_size: u32) -> Self {
+    self.db_pool_size = db_pool_size;
+    self
+  }
+
+  /// Set the idle timeout of the database editors, or None to keep the editors until their views
+  /// are closed. The timeout is at least 30 seconds.
+  pub fn idle_database_timeout(mut self, timeout: Option<Duration>) -> Self {
+    self.idle_database_timeout = timeout;
+    self
+  }
+
+  /// Set the profile of the memory-constrained devices, e.g. [LowMemoryProfile::default] on
+  /// low-end phones. It shortens the idle timeout of the database editors if it's enabled.
+  pub fn low_memory_profile(mut self, profile: Option<LowMemoryProfile>) -> Self {
+    self.low_memory_profile = profile;
+    self
+  }
+
+  /// Set how often the server is probed, or None to only find out that the server is down when a
+  /// request fails. The interval is at least 5 seconds.
+  pub fn connectivity_probe_interval(mut self, interval: Option<Duration>) -> Self {
+    self.connectivity_probe_interval = interval;
+    self
+  }
+
+  /// Set the path that the connectivity probe requests, e.g. the health check of a self-hosted
+  /// server that's behind a proxy.
+  pub fn connectivity_probe_endpoint(mut self, endpoint: &str) -> Self {
+    self.connectivity_probe_endpoint = endpoint.to_string();
+    self
+  }
+
+  /// The idle timeout of the database editors, shortened by the [LowMemoryProfile] if it's set.
+  pub(crate) fn effective_idle_database_timeout(&self) -> Option<Duration> {
+    let timeout = self.idle_database_timeout?;
+    match &self.low_memory_profile {
+      Some(profile) => Some(timeout.min(profile.idle_database_timeout)),
+      None => Some(timeout),
+    }
+  }
 }
@@ -1,12 +1,24 @@
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
-use flowy_database2::{DatabaseManager, DatabaseUser};
-use flowy_database_pub::cloud::DatabaseCloudService;
+use flowy_database2::services::database::LowMemoryProfile;
+use flowy_database2::{DatabaseDocumentService, DatabaseManager, DatabaseUser};
+use flowy_document::entities::DocumentDataPB;
+use flowy_document::manager::DocumentManager;
 use flowy_error::FlowyError;
+use flowy_folder::entities::{CreateViewParams, ViewLayoutPB};
+use flowy_folder::manager::FolderManager;
+use flowy_folder_pub::cloud::gen_view_id;
 use flowy_user::services::authenticate_user::AuthenticateUser;
+use flowy_user_pub::cloud::UserCloudServiceProvider;
+use flowy_user_pub::entities::Role;
+use lib_dispatch::prelude::ToBytes;
+use lib_infra::future::FutureResult;
 use lib_infra::priority_task::TaskDispatcher;
 use std::sync::{Arc, Weak};
 use tokio::sync::RwLock;
+
+use crate::integrate::server::{Server, ServerProvider};
+
 pub struct DatabaseDepsResolver();
 
 impl DatabaseDepsResolver {
@@ -14,23 +26,47 @@ impl DatabaseDepsResolver {
     authenticate_user: Weak<AuthenticateUser>,
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
-    cloud_service: Arc<dyn DatabaseCloudService>,
+    server_provider: Arc<ServerProvider>,
+    low_memory_profile: Option<LowMemoryProfile>,
   ) -> Arc<DatabaseManager> {
-    let user = Arc::new(DatabaseUserImpl(authenticate_user));
+    let user = Arc::new(DatabaseUserImpl {
+      authenticate_user,
+      server_provider: server_provider.clone(),
+    });
     Arc::new(DatabaseManager::new(
       user,
       task_scheduler,
       collab_builder,
-      cloud_service,
+      server_provider,
+      low_memory_profile,
     ))
   }
+
+  /// Called once the document and the folder managers are created, because they depend on the
+  /// [DatabaseManager].
+  pub fn resolve_document_service(
+    authenticate_user: Weak<AuthenticateUser>,
+    database_manager: &Arc<DatabaseManager>,
+    document_manager: &Arc<DocumentManager>,
+    folder_manager: &Arc<FolderManager>,
+  ) {
+    database_manager.set_document_service(Arc::new(DatabaseDocumentServiceImpl {
+      authenticate_user,
+      document_manager: Arc::downgrade(document_manager),
+      folder_manager: Arc::downgrade(folder_manager),
+    }));
+  }
+}
+
+struct DatabaseUserImpl {
+  authenticate_user: Weak<AuthenticateUser>,
+  server_provider: Arc<ServerProvider>,
 }
 
-struct DatabaseUserImpl(Weak<AuthenticateUser>);
 impl DatabaseUserImpl {
   fn upgrade_user(&self) -> Result<Arc<AuthenticateUser>, FlowyError> {
     let user = self
-      .0
+      .authenticate_user
       .upgrade()
       .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))?;
     Ok(user)
@@ -53,4 +89,145 @@ impl DatabaseUser for DatabaseUserImpl {
   fn workspace_database_object_id(&self) -> Result<String, FlowyError> {
     self.upgrade_user()?.workspace_database_object_id()
   }
+
+  fn is_read_only_member(&self) -> FutureResult<bool, FlowyError> {
+    let user = self.upgrade_user();
+    let server_type = self.server_provider.get_server_type();
+    let user_service = self.server_provider.get_user_service();
+    FutureResult::new(async move {
+      // Only the AppFlowy Cloud workspaces have members with roles
+      if !matches!(server_type, Server::AppFlowyCloud) {
+        return Ok(false);
+      }
+      let user = user?;
+      let workspace_id = user.workspace_id()?;
+      let email = user.get_user_profile()?.email;
+      let members = user_service?.get_workspace_members(workspace_id).await?;
+      // The roles that aren't known to edit, or a user that isn't a member anymore, are read-only
+      let is_read_only = members
+        .into_iter()
+        .find(|member| member.email == email)
+        .map(|member| !matches!(member.role, Role::Owner | Role::Member))
+        .unwrap_or(true);
+      Ok(is_read_only)
+    })
+  }
+}
+
+struct DatabaseDocumentServiceImpl {
+  authenticate_user: Weak<AuthenticateUser>,
+  document_manager: Weak<DocumentManager>,
+  folder_manager: Weak<FolderManager>,
+}
+
+impl DatabaseDocumentServiceImpl {
+  fn upgrade_document_manager(&self) -> Result<Arc<DocumentManager>, FlowyError> {
+    self
+      .document_manager
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: DocumentManager is None"))
+  }
+
+  fn upgrade_folder_manager(&self) -> Result<Arc<FolderManager>, FlowyError> {
+    self
+      .folder_manager
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: FolderManager is None"))
+  }
+
+  fn upgrade_user(&self) -> Result<Arc<AuthenticateUser>, FlowyError> {
+    self
+      .authenticate_user
+      .upgrade()
+      .ok_or(FlowyError::internal().with_context("Unexpected error: UserSession is None"))
+  }
+}
+
+impl DatabaseDocumentService for DatabaseDocumentServiceImpl {
+  fn get_document_title(&self, document_id: &str) -> FutureResult<String, FlowyError> {
+    let folder_manager = self.upgrade_folder_manager();
+    let document_id = document_id.to_string();
+    FutureResult::new(async move {
+      let view = folder_manager?.get_view_pb(&document_id).await?;
+      Ok(view.name)
+    })
+  }
+
+  fn copy_document(
+    &self,
+    document_id: &str,
+    new_document_id: &str,
+  ) -> FutureResult<bool, FlowyError> {
+    let document_manager = self.upgrade_document_manager();
+    let user = self.upgrade_user();
+    let document_id = document_id.to_string();
+    let new_document_id = new_document_id.to_string();
+    FutureResult::new(async move {
+      let document_manager = document_manager?;
+      let uid = user?.user_id()?;
+      let data = DocumentDataPB::from(document_manager.get_document_data(&document_id).await?);
+      let is_empty = is_document_empty(&data);
+      document_manager
+        .create_document(uid, &new_document_id, Some(data.into()))
+        .await?;
+      Ok(is_empty)
+    })
+  }
+
+  fn create_document(
+    &self,
+    name: &str,
+    copy_from: Option<&str>,
+  ) -> FutureResult<String, FlowyError> {
+    let document_manager = self.upgrade_document_manager();
+    let folder_manager = self.upgrade_folder_manager();
+    let user = self.upgrade_user();
+    let name = name.to_string();
+    let copy_from = copy_from.map(|document_id| document_id.to_string());
+    FutureResult::new(async move {
+      // The folder creates an empty document if the initial data is empty
+      let initial_data = match copy_from {
+        None => vec![],
+        Some(document_id) => {
+          let data = document_manager?.get_document_data(&document_id).await?;
+          DocumentDataPB::from(data)
+            .into_bytes()
+            .map_err(|_| FlowyError::invalid_data())?
+            .to_vec()
+        },
+      };
+      let params = CreateViewParams {
+        parent_view_id: user?.workspace_id()?,
+        name,
+        desc: "".to_string(),
+        layout: ViewLayoutPB::Document,
+        view_id: gen_view_id().to_string(),
+        initial_data,
+        meta: Default::default(),
+        set_as_current: false,
+        index: None,
+        section: None,
+      };
+      let view = folder_manager?.create_view_with_params(params).await?;
+      Ok(view.id)
+    })
+  }
+}
+
+/// Returns true if the document has nothing but empty paragraphs, e.g. a new document.
+fn is_document_empty(data: &DocumentDataPB) -> bool {
+  data
+    .blocks
+    .values()
+    .filter(|block| block.id != data.page_id)
+    .all(|block| {
+      let delta = block
+        .external_id
+        .as_ref()
+        .and_then(|text_id| data.meta.text_map.get(text_id));
+      let is_text_empty = delta
+        .map(|delta| delta.is_empty() || delta == "[]")
+        .unwrap_or(true);
+      block.ty == "paragraph" && is_text_empty
+    })
 }
@@ -2,7 +2,7 @@ use bytes::Bytes;
 use collab_integrate::collab_builder::AppFlowyCollabBuilder;
 use collab_integrate::CollabKVDB;
 use flowy_database2::entities::DatabaseLayoutPB;
-use flowy_database2::services::share::csv::CSVFormat;
+use flowy_database2::services::share::csv::{CSVFormat, CSVImportOptions};
 use flowy_database2::template::{make_default_board, make_default_calendar, make_default_grid};
 use flowy_database2::DatabaseManager;
 use flowy_document::entities::DocumentDataPB;
@@ -11,7 +11,7 @@ use flowy_document::parser::json::parser::JsonToDocumentParser;
 use flowy_error::FlowyError;
 use flowy_folder::entities::ViewLayoutPB;
 use flowy_folder::manager::{FolderManager, FolderUser};
-use flowy_folder::share::ImportType;
+use flowy_folder::share::{ImportCSVOptions, ImportType};
 use flowy_folder::view_operation::{FolderOperationHandler, FolderOperationHandlers, View};
 use flowy_folder::ViewLayout;
 use flowy_folder_pub::folder_builder::NestedViewBuilder;
@@ -97,6 +97,22 @@ impl FolderUser for FolderUserImpl {
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError> {
     self.upgrade_user()?.get_collab_db(uid)
   }
+
+  fn favorite_view_ids(&self, workspace_id: &str) -> Result<Vec<String>, FlowyError> {
+    self.upgrade_user()?.get_favorite_view_ids(workspace_id)
+  }
+
+  fn add_favorite_view_id(&self, workspace_id: &str, view_id: &str) -> Result<(), FlowyError> {
+    self
+      .upgrade_user()?
+      .add_favorite_view_id(workspace_id, view_id)
+  }
+
+  fn remove_favorite_view_id(&self, workspace_id: &str, view_id: &str) -> Result<bool, FlowyError> {
+    self
+      .upgrade_user()?
+      .remove_favorite_view_id(workspace_id, view_id)
+  }
 }
 
 struct DocumentFolderOperation(Arc<DocumentManager>);
@@ -225,6 +241,7 @@ impl FolderOperationHandler for DocumentFolderOperation {
     view_id: &str,
     _name: &str,
     _import_type: ImportType,
+    _csv_options: ImportCSVOptions,
     bytes: Vec<u8>,
   ) -> FutureResult<(), FlowyError> {
     let view_id = view_id.to_string();
@@ -373,6 +390,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
     view_id: &str,
     _name: &str,
     import_type: ImportType,
+    csv_options: ImportCSVOptions,
     bytes: Vec<u8>,
   ) -> FutureResult<(), FlowyError> {
     let database_manager = self.0.clone();
@@ -383,6 +401,10 @@ impl FolderOperationHandler for DatabaseFolderOperation {
       ImportType::RawDatabase => CSVFormat::META,
       _ => CSVFormat::Original,
     };
+    let options = CSVImportOptions {
+      import_sort_by_created_at: csv_options.sort_by_created_at,
+      delimiter: csv_options.delimiter,
+    };
     FutureResult::new(async move {
       let content = tokio::task::spawn_blocking(move || {
         String::from_utf8(bytes).map_err(|err| FlowyError::internal().with_context(err))
@@ -390,7 +412,7 @@ impl FolderOperationHandler for DatabaseFolderOperation {
       .await??;
 
       database_manager
-        .import_csv(view_id, content, format)
+        .import_csv(view_id, content, format, options)
         .await?;
       Ok(())
     })
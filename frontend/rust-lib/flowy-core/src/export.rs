@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Serialize;
+pub use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use flowy_database2::services::share::csv::{CSVExportOptions, CSVFormat};
+use flowy_document::parser::document_data_parser::DocumentDataParser;
+use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
+use flowy_folder::entities::{ViewLayoutPB, ViewPB};
+
+use crate::AppFlowyCore;
+
+/// The name of the file that maps the exported view ids to their files.
+pub const EXPORT_MANIFEST_FILE: &str = "manifest.json";
+
+#[derive(Debug, Serialize)]
+pub struct ExportManifest {
+  pub workspace_id: String,
+  pub workspace_name: String,
+  pub views: Vec<ExportedView>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedView {
+  pub view_id: String,
+  pub parent_view_id: String,
+  pub name: String,
+  /// The path of the exported file relative to the export directory. None if the view failed to
+  /// export.
+  pub file: Option<String>,
+}
+
+impl AppFlowyCore {
+  /// Exports the views of the current workspace into a directory inside `out_dir` that mirrors the
+  /// folder tree. The documents are exported as Markdown and the databases as CSV. Each file is
+  /// written as soon as its view is exported, and a [EXPORT_MANIFEST_FILE] that maps the view ids
+  /// to the files is written at the end.
+  ///
+  /// Returns the path of the export directory. The export stops with [ErrorCode::Cancelled] once
+  /// `cancel_token` is cancelled, the files that are already written are kept.
+  pub async fn export_workspace(
+    &self,
+    out_dir: &Path,
+    cancel_token: CancellationToken,
+  ) -> FlowyResult<PathBuf> {
+    let workspace = self.folder_manager.get_current_workspace().await?;
+    let root_dir = out_dir.join(sanitize_file_name(&workspace.name));
+    tokio::fs::create_dir_all(&root_dir)
+      .await
+      .map_err(internal_error)?;
+    info!("Export workspace {} to {:?}", workspace.id, root_dir);
+
+    let mut views = self.folder_manager.get_workspace_public_views().await?;
+    views.extend(self.folder_manager.get_workspace_private_views().await?);
+
+    let mut exported_views = vec![];
+    let mut stack = vec![(PathBuf::new(), views)];
+    while let Some((dir, views)) = stack.pop() {
+      tokio::fs::create_dir_all(root_dir.join(&dir))
+        .await
+        .map_err(internal_error)?;
+
+      let mut used_names = HashSet::new();
+      for view in views {
+        if cancel_token.is_cancelled() {
+          return Err(FlowyError::new(
+            ErrorCode::Cancelled,
+            "The workspace export is cancelled",
+          ));
+        }
+
+        let mut name = sanitize_file_name(&view.name);
+        if !used_names.insert(name.clone()) {
+          name = format!("{} ({})", name, view.id);
+          used_names.insert(name.clone());
+        }
+
+        let file = match self.export_view(&view, &root_dir, &dir, &name).await {
+          Ok(file) => Some(file),
+          Err(err) => {
+            warn!("Failed to export the view {}: {}", view.id, err);
+            None
+          },
+        };
+
+        // The child views only come with the first level, so fetch them again with the view
+        let child_views = match self.folder_manager.get_view_pb(&view.id).await {
+          Ok(view_pb) => view_pb.child_views,
+          Err(_) => view.child_views.clone(),
+        };
+        if !child_views.is_empty() {
+          stack.push((dir.join(&name), child_views));
+        }
+
+        exported_views.push(ExportedView {
+          view_id: view.id,
+          parent_view_id: view.parent_view_id,
+          name: view.name,
+          file,
+        });
+      }
+    }
+
+    let manifest = ExportManifest {
+      workspace_id: workspace.id,
+      workspace_name: workspace.name,
+      views: exported_views,
+    };
+    let manifest = serde_json::to_vec_pretty(&manifest).map_err(internal_error)?;
+    tokio::fs::write(root_dir.join(EXPORT_MANIFEST_FILE), manifest)
+      .await
+      .map_err(internal_error)?;
+    Ok(root_dir)
+  }
+
+  /// Writes the content of the view to `dir` with the extension of its layout. Returns the path of
+  /// the written file relative to `root_dir`.
+  async fn export_view(
+    &self,
+    view: &ViewPB,
+    root_dir: &Path,
+    dir: &Path,
+    name: &str,
+  ) -> FlowyResult<String> {
+    let (content, extension) = match view.layout {
+      ViewLayoutPB::Document => {
+        let document_data = self.document_manager.get_document_data(&view.id).await?;
+        let parser = DocumentDataParser::new(Arc::new(document_data), None);
+        (parser.to_markdown(), "md")
+      },
+      ViewLayoutPB::Grid | ViewLayoutPB::Board | ViewLayoutPB::Calendar => {
+        let csv = self
+          .database_manager
+          .export_csv(&view.id, CSVFormat::Original, CSVExportOptions::default())
+          .await?;
+        (csv, "csv")
+      },
+    };
+
+    let file = dir.join(format!("{}.{}", name, extension));
+    tokio::fs::write(root_dir.join(&file), content)
+      .await
+      .map_err(internal_error)?;
+    Ok(file.to_string_lossy().to_string())
+  }
+}
+
+/// Replaces the characters that aren't allowed in file names on the common platforms.
+fn sanitize_file_name(name: &str) -> String {
+  let name = name
+    .trim()
+    .chars()
+    .map(|c| match c {
+      '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+      c if c.is_control() => '_',
+      c => c,
+    })
+    .collect::<String>();
+  let name = name.trim_matches('.');
+  if name.is_empty() {
+    "Untitled".to_string()
+  } else {
+    name.to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::sanitize_file_name;
+
+  #[test]
+  fn sanitize_file_name_test() {
+    assert_eq!(sanitize_file_name("Getting started"), "Getting started");
+    assert_eq!(sanitize_file_name(" To do: a/b? "), "To do_ a_b_");
+    assert_eq!(sanitize_file_name(".."), "Untitled");
+    assert_eq!(sanitize_file_name(""), "Untitled");
+  }
+}
@@ -0,0 +1,124 @@
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use flowy_server::connectivity::ConnectivityState;
+use flowy_user::user_manager::UserManager;
+use lib_dispatch::runtime::AFPluginRuntime;
+
+use crate::integrate::server::ServerProvider;
+
+/// Probing more often than this would load the server for little benefit.
+const MIN_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The number of consecutive failed probes after which the server is unreachable, so a single
+/// dropped request doesn't make the app go offline.
+const OFFLINE_AFTER_FAILED_PROBES: usize = 3;
+
+/// Periodically probes the server and tells the [UserManager] when it becomes reachable or
+/// unreachable, so the UI can show that the app is offline before a request fails. Nothing is
+/// probed while the local server is used. The task stops when either of them is dropped.
+pub(crate) fn spawn_connectivity_probe(
+  runtime: &Arc<AFPluginRuntime>,
+  server_provider: Weak<ServerProvider>,
+  user_manager: Weak<UserManager>,
+  interval: Duration,
+) {
+  let interval = interval.max(MIN_PROBE_INTERVAL);
+  runtime.spawn(async move {
+    let mut interval = tokio::time::interval(interval);
+    let mut tracker = ConnectivityTracker::default();
+    loop {
+      interval.tick().await;
+      let server_provider = match server_provider.upgrade() {
+        Some(server_provider) => server_provider,
+        None => break,
+      };
+      if server_provider.get_server_type().is_local() {
+        continue;
+      }
+
+      let state = server_provider.probe_connectivity().await;
+      if let Some(state) = tracker.did_change(state) {
+        match user_manager.upgrade() {
+          Some(user_manager) => {
+            user_manager
+              .did_update_server_connectivity(state.is_online())
+              .await
+          },
+          None => break,
+        }
+      }
+    }
+  });
+}
+
+/// Tracks the reported state from the results of the probes. The server is reachable again as
+/// soon as a probe succeeds, but it's only unreachable after [OFFLINE_AFTER_FAILED_PROBES]
+/// consecutive failed probes.
+struct ConnectivityTracker {
+  last_state: ConnectivityState,
+  num_of_failed_probes: usize,
+}
+
+impl Default for ConnectivityTracker {
+  fn default() -> Self {
+    Self {
+      // The services assume that the server is reachable until they're told otherwise
+      last_state: ConnectivityState::Online,
+      num_of_failed_probes: 0,
+    }
+  }
+}
+
+impl ConnectivityTracker {
+  /// Returns the new state if it differs from the last reported one.
+  fn did_change(&mut self, probed_state: ConnectivityState) -> Option<ConnectivityState> {
+    let state = if probed_state.is_online() {
+      self.num_of_failed_probes = 0;
+      ConnectivityState::Online
+    } else {
+      self.num_of_failed_probes += 1;
+      if self.num_of_failed_probes < OFFLINE_AFTER_FAILED_PROBES {
+        return None;
+      }
+      ConnectivityState::Offline
+    };
+    if self.last_state == state {
+      return None;
+    }
+    self.last_state = state;
+    Some(state)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use flowy_server::connectivity::ConnectivityState::{Offline, Online};
+
+  use super::{ConnectivityTracker, OFFLINE_AFTER_FAILED_PROBES};
+
+  #[test]
+  fn did_change_test() {
+    let mut tracker = ConnectivityTracker::default();
+    assert_eq!(tracker.did_change(Online), None);
+    for _ in 1..OFFLINE_AFTER_FAILED_PROBES {
+      assert_eq!(tracker.did_change(Offline), None);
+    }
+    assert_eq!(tracker.did_change(Offline), Some(Offline));
+    assert_eq!(tracker.did_change(Offline), None);
+    assert_eq!(tracker.did_change(Online), Some(Online));
+    assert_eq!(tracker.last_state, Online);
+  }
+
+  #[test]
+  fn failed_probes_must_be_consecutive_test() {
+    let mut tracker = ConnectivityTracker::default();
+    for _ in 0..3 {
+      for _ in 1..OFFLINE_AFTER_FAILED_PROBES {
+        assert_eq!(tracker.did_change(Offline), None);
+      }
+      assert_eq!(tracker.did_change(Online), None);
+    }
+    assert_eq!(tracker.last_state, Online);
+  }
+}
@@ -0,0 +1,79 @@
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use sysinfo::System;
+use tracing::info;
+
+use flowy_database2::DatabaseManager;
+use lib_dispatch::runtime::AFPluginRuntime;
+
+/// The editors are kept for at least this long after they were last used, so the databases that
+/// are closed and reopened quickly, e.g. when switching between tabs, don't reload their rows.
+const MIN_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The idle editors are only closed when less than this percentage of the memory is available.
+/// Desktops commonly run with most of their memory in use, so only a small share being
+/// available means the system is about to swap.
+const LOW_MEMORY_PERCENTAGE: u64 = 10;
+
+/// Periodically closes the database editors that have no opening views and haven't been used for
+/// `idle_timeout`. It does nothing while the memory is plentiful or when the memory usage can't be
+/// read on the platform. The task stops when the [DatabaseManager] is dropped.
+pub(crate) fn spawn_idle_database_reaper(
+  runtime: &Arc<AFPluginRuntime>,
+  database_manager: Weak<DatabaseManager>,
+  idle_timeout: Duration,
+) {
+  let idle_timeout = idle_timeout.max(MIN_IDLE_TIMEOUT);
+  runtime.spawn(async move {
+    let mut interval = tokio::time::interval(idle_timeout / 2);
+    let mut system = System::new();
+    loop {
+      interval.tick().await;
+      let database_manager = match database_manager.upgrade() {
+        Some(database_manager) => database_manager,
+        None => break,
+      };
+
+      system.refresh_memory();
+      if !is_memory_low(system.available_memory(), system.total_memory()) {
+        continue;
+      }
+
+      let num_of_closed = database_manager.close_idle_databases(idle_timeout).await;
+      if num_of_closed > 0 {
+        info!(
+          "Closed {} idle databases, {} since launch",
+          num_of_closed,
+          database_manager.num_of_closed_idle_databases()
+        );
+      }
+    }
+  });
+}
+
+fn is_memory_low(available_memory: u64, total_memory: u64) -> bool {
+  // The total memory is zero if it can't be read on the platform. The memory usage is unknown
+  // then, so the editors are kept as they would be without the reaper.
+  if total_memory == 0 {
+    return false;
+  }
+  available_memory.saturating_mul(100) / total_memory < LOW_MEMORY_PERCENTAGE
+}
+
+#[cfg(test)]
+mod tests {
+  use super::is_memory_low;
+
+  #[test]
+  fn is_memory_low_test() {
+    let gb = 1024 * 1024 * 1024;
+    assert!(!is_memory_low(8 * gb, 16 * gb));
+    assert!(!is_memory_low(4 * gb, 16 * gb));
+    assert!(!is_memory_low(2 * gb, 16 * gb));
+    assert!(is_memory_low(gb, 16 * gb));
+    assert!(is_memory_low(0, 16 * gb));
+    assert!(!is_memory_low(0, 0));
+    assert!(!is_memory_low(gb, 0));
+  }
+}
@@ -21,6 +21,7 @@ pub(crate) fn init_log(
 
     let _ = lib_log::Builder::new("log", &config.storage_path, platform, stream_log_sender)
       .env_filter(&config.log_filter)
+      .redact_sensitive_info(config.redact_log)
       .build();
   }
 }
@@ -1,4 +1,6 @@
 pub(crate) mod collab_interact;
+pub(crate) mod connectivity;
+pub(crate) mod idle_database;
 pub mod log;
 pub(crate) mod server;
 mod trait_impls;
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::sync::{Arc, Weak};
+use std::time::Duration;
 
 use parking_lot::RwLock;
 use serde_repr::*;
@@ -8,6 +9,7 @@ use serde_repr::*;
 use flowy_error::{FlowyError, FlowyResult};
 use flowy_server::af_cloud::define::ServerUser;
 use flowy_server::af_cloud::AppFlowyCloudServer;
+use flowy_server::connectivity::{ConnectivityProbe, ConnectivityState};
 use flowy_server::local_server::{LocalServer, LocalServerDB};
 use flowy_server::supabase::SupabaseServer;
 use flowy_server::{AppFlowyEncryption, AppFlowyServer, EncryptionImpl};
@@ -19,6 +21,9 @@ use flowy_user_pub::entities::*;
 
 use crate::AppFlowyCoreConfig;
 
+/// The probe counts the server as unreachable if it doesn't respond within this time.
+const CONNECTIVITY_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
 pub enum Server {
@@ -66,6 +71,7 @@ pub struct ServerProvider {
   authenticator: RwLock<Authenticator>,
   user: Arc<dyn ServerUser>,
   pub(crate) uid: Arc<RwLock<Option<i64>>>,
+  connectivity_probe: ConnectivityProbe,
 }
 
 impl ServerProvider {
@@ -86,6 +92,7 @@ impl ServerProvider {
       store_preferences,
       uid: Default::default(),
       user,
+      connectivity_probe: ConnectivityProbe::new(CONNECTIVITY_PROBE_TIMEOUT),
     }
   }
 
@@ -111,6 +118,31 @@ impl ServerProvider {
     self.authenticator.read().clone()
   }
 
+  /// Checks whether the current server can be reached with a lightweight request to the
+  /// configured endpoint. It's a no-op in the local mode, which is always online.
+  pub async fn probe_connectivity(&self) -> ConnectivityState {
+    if self.get_server_type().is_local() {
+      return ConnectivityState::Online;
+    }
+
+    let base_url = match self.get_server() {
+      Ok(server) => server.base_url(),
+      Err(err) => {
+        tracing::warn!("Can't probe the server: {}", err);
+        return ConnectivityState::Offline;
+      },
+    };
+    match base_url {
+      None => ConnectivityState::Online,
+      Some(base_url) => {
+        self
+          .connectivity_probe
+          .probe(&base_url, &self.config.connectivity_probe_endpoint)
+          .await
+      },
+    }
+  }
+
   /// Returns a [AppFlowyServer] trait implementation base on the provider_type.
   pub fn get_server(&self) -> FlowyResult<Arc<dyn AppFlowyServer>> {
     let server_type = self.get_server_type();
@@ -8,6 +8,7 @@ use collab::core::origin::{CollabClient, CollabOrigin};
 use collab::preclude::CollabPlugin;
 use collab_entity::CollabType;
 use collab_plugins::cloud_storage::postgres::SupabaseDBPlugin;
+use tokio::sync::broadcast;
 use tokio_stream::wrappers::WatchStream;
 use tracing::debug;
 
@@ -78,6 +79,11 @@ impl UserCloudServiceProvider for ServerProvider {
     server.subscribe_token_state()
   }
 
+  fn subscribe_token_refresh_failed(&self) -> Option<broadcast::Receiver<String>> {
+    let server = self.get_server().ok()?;
+    server.subscribe_token_refresh_failed()
+  }
+
   fn set_enable_sync(&self, uid: i64, enable_sync: bool) {
     if let Ok(server) = self.get_server() {
       server.set_enable_sync(uid, enable_sync);
@@ -215,4 +215,12 @@ impl UserStatusCallback for UserStatusCallbackImpl {
   fn did_update_network(&self, reachable: bool) {
     self.collab_builder.update_network(reachable);
   }
+
+  fn did_update_workspace_role(&self, _workspace_id: &str) -> Fut<FlowyResult<()>> {
+    let database_manager = self.database_manager.clone();
+    to_fut(async move {
+      database_manager.refresh_workspace_role().await;
+      Ok(())
+    })
+  }
 }
@@ -20,6 +20,7 @@ use flowy_server::af_cloud::define::ServerUser;
 use flowy_sqlite::kv::StorePreferences;
 use flowy_user::services::authenticate_user::AuthenticateUser;
 use flowy_user::services::entities::UserConfig;
+use flowy_user::services::workspace_kv_store::WorkspaceKvStore;
 use flowy_user::user_manager::UserManager;
 
 use lib_dispatch::prelude::*;
@@ -32,12 +33,15 @@ use module::make_plugins;
 use crate::config::AppFlowyCoreConfig;
 use crate::deps_resolve::*;
 use crate::integrate::collab_interact::CollabInteractImpl;
+use crate::integrate::connectivity::spawn_connectivity_probe;
+use crate::integrate::idle_database::spawn_idle_database_reaper;
 use crate::integrate::log::init_log;
 use crate::integrate::server::{current_server_type, Server, ServerProvider};
 use crate::integrate::user::UserStatusCallbackImpl;
 
 pub mod config;
 mod deps_resolve;
+pub mod export;
 pub mod integrate;
 pub mod module;
 
@@ -113,7 +117,8 @@ impl AppFlowyCore {
       &config.application_path,
       &config.device_id,
       app_version,
-    );
+    )
+    .with_db_pool_size(config.db_pool_size);
 
     let authenticate_user = Arc::new(AuthenticateUser::new(
       user_config.clone(),
@@ -155,6 +160,7 @@ impl AppFlowyCore {
         task_dispatcher.clone(),
         collab_builder.clone(),
         server_provider.clone(),
+        config.low_memory_profile,
       )
       .await;
 
@@ -176,6 +182,12 @@ impl AppFlowyCore {
         folder_indexer.clone(),
       )
       .await;
+      DatabaseDepsResolver::resolve_document_service(
+        Arc::downgrade(&authenticate_user),
+        &database_manager,
+        &document_manager,
+        &folder_manager,
+      );
 
       let user_manager = UserDepsResolver::resolve(
         authenticate_user.clone(),
@@ -224,6 +236,18 @@ impl AppFlowyCore {
         error!("Init user failed: {}", err)
       }
     }
+    if let Some(idle_timeout) = config.effective_idle_database_timeout() {
+      spawn_idle_database_reaper(&runtime, Arc::downgrade(&database_manager), idle_timeout);
+    }
+    if let Some(probe_interval) = config.connectivity_probe_interval {
+      spawn_connectivity_probe(
+        &runtime,
+        Arc::downgrade(&server_provider),
+        Arc::downgrade(&user_manager),
+        probe_interval,
+      );
+    }
+
     let event_dispatcher = Arc::new(AFPluginDispatcher::new(
       runtime,
       make_plugins(
@@ -249,6 +273,14 @@ impl AppFlowyCore {
     }
   }
 
+  /// Returns the key/value store of the current workspace, which the plugins can use to persist
+  /// their settings. The store stays bound to the workspace, so it should be got again after the
+  /// workspace is switched.
+  pub fn workspace_kv_store(&self) -> FlowyResult<WorkspaceKvStore> {
+    let workspace_id = self.user_manager.workspace_id()?;
+    Ok(self.user_manager.workspace_kv_store(&workspace_id))
+  }
+
   /// Only expose the dispatcher in test
   pub fn dispatcher(&self) -> Arc<AFPluginDispatcher> {
     self.event_dispatcher.clone()
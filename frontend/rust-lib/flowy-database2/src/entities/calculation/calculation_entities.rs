@@ -119,7 +119,12 @@ impl CalculationType {
       // Exclude some fields from CountNotEmpty & CountEmpty
       CalculationType::CountEmpty | CalculationType::CountNonEmpty => !matches!(
         field_type,
-        FieldType::URL | FieldType::Checkbox | FieldType::CreatedTime | FieldType::LastEditedTime
+        FieldType::URL
+          | FieldType::Checkbox
+          | FieldType::CreatedTime
+          | FieldType::LastEditedTime
+          | FieldType::CreatedBy
+          | FieldType::LastEditedBy
       ),
       // All fields
       CalculationType::Count => true,
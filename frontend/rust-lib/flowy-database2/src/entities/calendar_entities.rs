@@ -81,11 +81,17 @@ impl std::convert::From<CalendarLayout> for CalendarLayoutPB {
 pub struct CalendarEventRequestPB {
   #[pb(index = 1)]
   pub view_id: String,
+
+  /// The date field that the events are placed by. The date field of the calendar layout is used
+  /// if it's None.
+  #[pb(index = 2, one_of)]
+  pub date_field_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct CalendarEventRequestParams {
   pub view_id: String,
+  pub date_field_id: Option<String>,
 }
 
 impl TryInto<CalendarEventRequestParams> for CalendarEventRequestPB {
@@ -93,7 +99,10 @@ impl TryInto<CalendarEventRequestParams> for CalendarEventRequestPB {
 
   fn try_into(self) -> Result<CalendarEventRequestParams, Self::Error> {
     let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::ViewIdIsInvalid)?;
-    Ok(CalendarEventRequestParams { view_id: view_id.0 })
+    Ok(CalendarEventRequestParams {
+      view_id: view_id.0,
+      date_field_id: self.date_field_id,
+    })
   }
 }
 
@@ -113,6 +122,10 @@ pub struct CalendarEventPB {
 
   #[pb(index = 5)]
   pub is_scheduled: bool,
+
+  /// The end of the event if the date cell is a range.
+  #[pb(index = 6, one_of)]
+  pub end_timestamp: Option<i64>,
 }
 
 #[derive(Debug, Clone, Default, ProtoBuf)]
@@ -29,6 +29,11 @@ pub struct DatabasePB {
 
   #[pb(index = 5)]
   pub is_linked: bool,
+
+  /// True if only a part of the rows is returned because of the low-memory profile. The rest
+  /// of the rows are loaded with `DatabaseEvent::GetDatabaseRows`.
+  #[pb(index = 6)]
+  pub has_more_rows: bool,
 }
 
 #[derive(ProtoBuf, Default)]
@@ -297,6 +302,45 @@ impl From<SyncState> for DatabaseSyncStatePB {
   }
 }
 
+#[derive(Debug, Default, ProtoBuf)]
+pub struct UnsyncedEditCountPB {
+  #[pb(index = 1)]
+  pub count: i64,
+}
+
+/// An error that happened in the background, e.g. while the rows of a view were loaded, and that
+/// the user should know about. See [crate::services::database::DatabaseEditor::subscribe_errors].
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct DatabaseErrorPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub phase: DatabaseErrorPhasePB,
+
+  #[pb(index = 3)]
+  pub kind: DatabaseErrorKindPB,
+
+  #[pb(index = 4)]
+  pub msg: String,
+}
+
+#[derive(Debug, Default, ProtoBuf_Enum, PartialEq, Eq, Clone, Copy)]
+pub enum DatabaseErrorPhasePB {
+  #[default]
+  LoadRowOrders = 0,
+  LoadRows = 1,
+}
+
+#[derive(Debug, Default, ProtoBuf_Enum, PartialEq, Eq, Clone, Copy)]
+pub enum DatabaseErrorKindPB {
+  /// The data may not be synced yet. Opening the view again may succeed.
+  #[default]
+  Transient = 0,
+  /// The data can't be read, e.g. it's corrupted. Opening the view again won't help.
+  Fatal = 1,
+}
+
 #[derive(Debug, Default, ProtoBuf)]
 pub struct DatabaseSnapshotStatePB {
   #[pb(index = 1)]
@@ -14,8 +14,12 @@ use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
 use crate::entities::position_entities::OrderObjectPositionPB;
+use crate::entities::SelectOptionPB;
 use crate::impl_into_field_type;
-use crate::services::field::{default_type_option_data_from_type, type_option_to_pb};
+use crate::services::field::{
+  default_type_option_data_from_type, field_description, is_system_field, type_option_to_pb,
+  CellMigrationStrategy,
+};
 
 /// [FieldPB] defines a Field's attributes. Such as the name, field_type, and width. etc.
 #[derive(Debug, Clone, Default, ProtoBuf)]
@@ -34,6 +38,13 @@ pub struct FieldPB {
 
   #[pb(index = 7)]
   pub type_option_data: Vec<u8>,
+
+  #[pb(index = 8, one_of)]
+  pub description: Option<String>,
+
+  /// See [crate::services::field::is_system_field].
+  #[pb(index = 9)]
+  pub is_system: bool,
 }
 
 impl FieldPB {
@@ -43,6 +54,8 @@ impl FieldPB {
       .get_any_type_option(field_type)
       .unwrap_or_else(|| default_type_option_data_from_type(field_type));
     Self {
+      description: field_description(&field),
+      is_system: is_system_field(&field),
       id: field.id,
       name: field.name,
       field_type,
@@ -101,6 +114,20 @@ pub struct DatabaseFieldChangesetPB {
 
   #[pb(index = 4)]
   pub updated_fields: Vec<FieldPB>,
+
+  #[pb(index = 5)]
+  pub origin: FieldChangeOriginPB,
+}
+
+/// Where the changes of a [DatabaseFieldChangesetPB] come from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum FieldChangeOriginPB {
+  /// The fields are changed on this device.
+  #[default]
+  Local = 0,
+  /// The fields are changed by a collaborator and synced to this device.
+  Remote = 1,
 }
 
 impl DatabaseFieldChangesetPB {
@@ -110,6 +137,7 @@ impl DatabaseFieldChangesetPB {
       inserted_fields,
       deleted_fields: vec![],
       updated_fields: vec![],
+      origin: FieldChangeOriginPB::Local,
     }
   }
 
@@ -119,6 +147,7 @@ impl DatabaseFieldChangesetPB {
       inserted_fields: vec![],
       deleted_fields,
       updated_fields: vec![],
+      origin: FieldChangeOriginPB::Local,
     }
   }
 
@@ -128,8 +157,14 @@ impl DatabaseFieldChangesetPB {
       inserted_fields: vec![],
       deleted_fields: vec![],
       updated_fields,
+      origin: FieldChangeOriginPB::Local,
     }
   }
+
+  pub fn with_origin(mut self, origin: FieldChangeOriginPB) -> Self {
+    self.origin = origin;
+    self
+  }
 }
 
 #[derive(Debug, Clone, Default, ProtoBuf)]
@@ -159,6 +194,10 @@ pub struct CreateFieldPayloadPB {
 
   #[pb(index = 5)]
   pub field_position: OrderObjectPositionPB,
+
+  /// Creates a system field, see [crate::services::field::is_system_field].
+  #[pb(index = 6)]
+  pub is_system: bool,
 }
 
 #[derive(Clone)]
@@ -168,6 +207,7 @@ pub struct CreateFieldParams {
   pub field_type: FieldType,
   pub type_option_data: Option<Vec<u8>>,
   pub position: OrderObjectPosition,
+  pub is_system: bool,
 }
 
 impl TryInto<CreateFieldParams> for CreateFieldPayloadPB {
@@ -193,6 +233,7 @@ impl TryInto<CreateFieldParams> for CreateFieldPayloadPB {
       field_type: self.field_type,
       type_option_data: self.type_option_data,
       position,
+      is_system: self.is_system,
     })
   }
 }
@@ -207,12 +248,27 @@ pub struct UpdateFieldTypePayloadPB {
 
   #[pb(index = 3)]
   pub field_type: FieldType,
+
+  /// Maps the text of the cells to the options of the new single or multi select field. The
+  /// cells are kept as they are if it's empty. See [CellMigrationStrategy::SelectOptionMapping].
+  #[pb(index = 4)]
+  pub select_option_mapping: Vec<SelectOptionMappingPB>,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct SelectOptionMappingPB {
+  #[pb(index = 1)]
+  pub text: String,
+
+  #[pb(index = 2)]
+  pub option: SelectOptionPB,
 }
 
 pub struct EditFieldParams {
   pub view_id: String,
   pub field_id: String,
   pub field_type: FieldType,
+  pub migration_strategy: Option<CellMigrationStrategy>,
 }
 
 impl TryInto<EditFieldParams> for UpdateFieldTypePayloadPB {
@@ -221,10 +277,21 @@ impl TryInto<EditFieldParams> for UpdateFieldTypePayloadPB {
   fn try_into(self) -> Result<EditFieldParams, Self::Error> {
     let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseIdIsEmpty)?;
     let field_id = NotEmptyStr::parse(self.field_id).map_err(|_| ErrorCode::FieldIdIsEmpty)?;
+    let migration_strategy = if self.select_option_mapping.is_empty() {
+      None
+    } else {
+      let mapping = self
+        .select_option_mapping
+        .into_iter()
+        .map(|mapping| (mapping.text, mapping.option.into()))
+        .collect();
+      Some(CellMigrationStrategy::SelectOptionMapping(mapping))
+    };
     Ok(EditFieldParams {
       view_id: view_id.0,
       field_id: field_id.0,
       field_type: self.field_type,
+      migration_strategy,
     })
   }
 }
@@ -254,6 +321,20 @@ impl std::convert::From<Vec<FieldPB>> for RepeatedFieldPB {
   }
 }
 
+/// The fields of a view along with the version of the schema, see
+/// [crate::services::database::SchemaVersion].
+#[derive(Debug, Default, ProtoBuf)]
+pub struct DatabaseSchemaPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub version: i64,
+
+  #[pb(index = 3)]
+  pub fields: RepeatedFieldPB,
+}
+
 #[derive(Debug, Clone, Default, ProtoBuf)]
 pub struct RepeatedFieldIdPB {
   #[pb(index = 1)]
@@ -375,6 +456,7 @@ pub struct FieldChangesetPB {
   #[pb(index = 3, one_of)]
   pub name: Option<String>,
 
+  /// The description of the field. An empty description clears it.
   #[pb(index = 4, one_of)]
   pub desc: Option<String>,
 
@@ -449,6 +531,8 @@ pub enum FieldType {
   CreatedTime = 9,
   Relation = 10,
   Summary = 11,
+  LastEditedBy = 12,
+  CreatedBy = 13,
 }
 
 impl Display for FieldType {
@@ -489,6 +573,8 @@ impl FieldType {
       FieldType::CreatedTime => "Created time",
       FieldType::Relation => "Relation",
       FieldType::Summary => "Summarize",
+      FieldType::LastEditedBy => "Last edited by",
+      FieldType::CreatedBy => "Created by",
     };
     s.to_string()
   }
@@ -525,6 +611,20 @@ impl FieldType {
     matches!(self, FieldType::CreatedTime)
   }
 
+  pub fn is_last_edited_by(&self) -> bool {
+    matches!(self, FieldType::LastEditedBy)
+  }
+
+  pub fn is_created_by(&self) -> bool {
+    matches!(self, FieldType::CreatedBy)
+  }
+
+  /// Returns true if the cells of the field are filled with the user who created or last edited
+  /// the row.
+  pub fn is_user(&self) -> bool {
+    self.is_last_edited_by() || self.is_created_by()
+  }
+
   pub fn is_url(&self) -> bool {
     matches!(self, FieldType::URL)
   }
@@ -546,7 +646,7 @@ impl FieldType {
   }
 
   pub fn is_auto_update(&self) -> bool {
-    self.is_last_edited_time()
+    self.is_last_edited_time() || self.is_last_edited_by()
   }
 }
 
@@ -5,7 +5,7 @@ use std::ops::Deref;
 use validator::Validate;
 
 use crate::entities::parser::NotEmptyStr;
-use crate::entities::RepeatedFieldIdPB;
+use crate::entities::{DatabaseLayoutPB, RepeatedFieldIdPB, SortConditionPB};
 use crate::impl_into_field_visibility;
 use crate::services::field_settings::FieldSettings;
 
@@ -23,6 +23,10 @@ pub struct FieldSettingsPB {
 
   #[pb(index = 4)]
   pub wrap_cell_content: bool,
+
+  /// The condition of the sorts that are created for the field.
+  #[pb(index = 5)]
+  pub default_sort_condition: SortConditionPB,
 }
 
 impl From<FieldSettings> for FieldSettingsPB {
@@ -32,6 +36,19 @@ impl From<FieldSettings> for FieldSettingsPB {
       visibility: value.visibility,
       width: value.width,
       wrap_cell_content: value.wrap_cell_content,
+      default_sort_condition: value.default_sort_condition.into(),
+    }
+  }
+}
+
+impl From<FieldSettingsPB> for FieldSettings {
+  fn from(value: FieldSettingsPB) -> Self {
+    Self {
+      field_id: value.field_id,
+      visibility: value.visibility,
+      width: value.width,
+      wrap_cell_content: value.wrap_cell_content,
+      default_sort_condition: value.default_sort_condition.into(),
     }
   }
 }
@@ -117,4 +134,41 @@ pub struct FieldSettingsChangesetPB {
 
   #[pb(index = 5, one_of)]
   pub wrap_cell_content: Option<bool>,
+
+  #[pb(index = 6, one_of)]
+  pub default_sort_condition: Option<SortConditionPB>,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct FieldVisibilityChangePB {
+  #[pb(index = 1)]
+  pub field_id: String,
+
+  #[pb(index = 2)]
+  pub is_visible: bool,
+}
+
+/// Shows or hides many fields of a view at once.
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct SetFieldsVisibilityPayloadPB {
+  #[validate(custom = "required_not_empty_str")]
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub items: Vec<FieldVisibilityChangePB>,
+}
+
+/// The settings that the given fields start with in the new views of `layout`.
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct DefaultFieldSettingsPB {
+  #[validate(custom = "required_not_empty_str")]
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub layout: DatabaseLayoutPB,
+
+  #[pb(index = 3)]
+  pub items: Vec<FieldSettingsPB>,
 }
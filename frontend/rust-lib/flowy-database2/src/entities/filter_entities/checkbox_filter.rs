@@ -14,7 +14,12 @@ pub struct CheckboxFilterPB {
 pub enum CheckboxFilterConditionPB {
   #[default]
   IsChecked = 0,
+  /// Includes the cells that have never been set.
   IsUnChecked = 1,
+  /// Only the cells that have never been set.
+  IsUnset = 2,
+  /// Only the cells that are unchecked explicitly.
+  IsExplicitlyUnChecked = 3,
 }
 
 impl std::convert::From<CheckboxFilterConditionPB> for u32 {
@@ -30,6 +35,8 @@ impl TryFrom<u8> for CheckboxFilterConditionPB {
     match value {
       0 => Ok(CheckboxFilterConditionPB::IsChecked),
       1 => Ok(CheckboxFilterConditionPB::IsUnChecked),
+      2 => Ok(CheckboxFilterConditionPB::IsUnset),
+      3 => Ok(CheckboxFilterConditionPB::IsExplicitlyUnChecked),
       _ => Err(ErrorCode::InvalidParams),
     }
   }
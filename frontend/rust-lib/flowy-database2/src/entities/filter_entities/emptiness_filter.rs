@@ -0,0 +1,55 @@
+use collab_database::{fields::Field, rows::Cell};
+use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
+use flowy_error::ErrorCode;
+
+use crate::services::filter::PreFillCellsWithFilter;
+
+/// Filters the rows by whether their cells are empty. It can be applied to any field type, and
+/// each type option decides what an empty cell is.
+#[derive(Eq, PartialEq, ProtoBuf, Debug, Default, Clone)]
+pub struct EmptinessFilterPB {
+  #[pb(index = 1)]
+  pub condition: EmptinessFilterConditionPB,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum EmptinessFilterConditionPB {
+  #[default]
+  IsEmpty = 0,
+  IsNotEmpty = 1,
+}
+
+impl std::convert::From<EmptinessFilterConditionPB> for u32 {
+  fn from(value: EmptinessFilterConditionPB) -> Self {
+    value as u32
+  }
+}
+
+impl TryFrom<u8> for EmptinessFilterConditionPB {
+  type Error = ErrorCode;
+
+  fn try_from(value: u8) -> Result<Self, Self::Error> {
+    match value {
+      0 => Ok(EmptinessFilterConditionPB::IsEmpty),
+      1 => Ok(EmptinessFilterConditionPB::IsNotEmpty),
+      _ => Err(ErrorCode::InvalidParams),
+    }
+  }
+}
+
+impl EmptinessFilterPB {
+  pub fn is_visible(&self, is_cell_empty: bool) -> bool {
+    match self.condition {
+      EmptinessFilterConditionPB::IsEmpty => is_cell_empty,
+      EmptinessFilterConditionPB::IsNotEmpty => !is_cell_empty,
+    }
+  }
+}
+
+impl PreFillCellsWithFilter for EmptinessFilterPB {
+  fn get_compliant_cell(&self, _field: &Field) -> (Option<Cell>, bool) {
+    let open_after_create = matches!(self.condition, EmptinessFilterConditionPB::IsNotEmpty);
+    (None, open_after_create)
+  }
+}
@@ -1,6 +1,7 @@
 mod checkbox_filter;
 mod checklist_filter;
 mod date_filter;
+mod emptiness_filter;
 mod filter_changeset;
 mod number_filter;
 mod relation_filter;
@@ -11,6 +12,7 @@ mod util;
 pub use checkbox_filter::*;
 pub use checklist_filter::*;
 pub use date_filter::*;
+pub use emptiness_filter::*;
 pub use filter_changeset::*;
 pub use number_filter::*;
 pub use relation_filter::*;
@@ -20,10 +20,16 @@ pub enum SelectOptionFilterConditionPB {
   #[default]
   OptionIs = 0,
   OptionIsNot = 1,
+  /// The cell has any of the options.
   OptionContains = 2,
+  /// The cell has none of the options.
   OptionDoesNotContain = 3,
   OptionIsEmpty = 4,
   OptionIsNotEmpty = 5,
+  /// The cell has all of the options, and maybe others too.
+  OptionContainsAll = 6,
+  /// The cell lacks at least one of the options. Empty cells never match.
+  OptionDoesNotContainAll = 7,
 }
 
 impl From<SelectOptionFilterConditionPB> for u32 {
@@ -43,6 +49,8 @@ impl TryFrom<u8> for SelectOptionFilterConditionPB {
       3 => Ok(SelectOptionFilterConditionPB::OptionDoesNotContain),
       4 => Ok(SelectOptionFilterConditionPB::OptionIsEmpty),
       5 => Ok(SelectOptionFilterConditionPB::OptionIsNotEmpty),
+      6 => Ok(SelectOptionFilterConditionPB::OptionContainsAll),
+      7 => Ok(SelectOptionFilterConditionPB::OptionDoesNotContainAll),
       _ => Err(ErrorCode::InvalidParams),
     }
   }
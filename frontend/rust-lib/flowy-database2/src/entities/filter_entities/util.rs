@@ -9,8 +9,8 @@ use protobuf::ProtobufError;
 use validator::Validate;
 
 use crate::entities::{
-  CheckboxFilterPB, ChecklistFilterPB, DateFilterPB, FieldType, NumberFilterPB, RelationFilterPB,
-  SelectOptionFilterPB, TextFilterPB,
+  CheckboxFilterPB, ChecklistFilterPB, DateFilterPB, EmptinessFilterPB, FieldType, NumberFilterPB,
+  RelationFilterPB, SelectOptionFilterPB, TextFilterPB,
 };
 use crate::services::filter::{Filter, FilterChangeset, FilterInner};
 
@@ -58,6 +58,10 @@ pub struct FilterDataPB {
 
   #[pb(index = 3)]
   pub data: Vec<u8>,
+
+  /// If set, the rows are filtered by whether the cells are empty, and `data` is ignored.
+  #[pb(index = 4, one_of)]
+  pub emptiness: Option<EmptinessFilterPB>,
 }
 
 impl From<&Filter> for FilterPB {
@@ -74,6 +78,20 @@ impl From<&Filter> for FilterPB {
         field_type,
         condition_and_content,
       } => {
+        if let Some(emptiness) = condition_and_content.cloned::<EmptinessFilterPB>() {
+          return Self {
+            id: filter.id.clone(),
+            filter_type: FilterType::Data,
+            children: vec![],
+            data: Some(FilterDataPB {
+              field_id: field_id.clone(),
+              field_type: *field_type,
+              data: vec![],
+              emptiness: Some(emptiness),
+            }),
+          };
+        }
+
         let bytes: Result<Bytes, ProtobufError> = match field_type {
           FieldType::RichText | FieldType::URL => condition_and_content
             .cloned::<TextFilterPB>()
@@ -105,10 +123,12 @@ impl From<&Filter> for FilterPB {
             .cloned::<RelationFilterPB>()
             .unwrap()
             .try_into(),
-          FieldType::Summary => condition_and_content
-            .cloned::<TextFilterPB>()
-            .unwrap()
-            .try_into(),
+          FieldType::Summary | FieldType::LastEditedBy | FieldType::CreatedBy => {
+            condition_and_content
+              .cloned::<TextFilterPB>()
+              .unwrap()
+              .try_into()
+          },
         };
 
         Self {
@@ -119,6 +139,7 @@ impl From<&Filter> for FilterPB {
             field_id: field_id.clone(),
             field_type: *field_type,
             data: bytes.unwrap().to_vec(),
+            emptiness: None,
           }),
         }
       },
@@ -130,6 +151,14 @@ impl TryFrom<FilterDataPB> for FilterInner {
   type Error = ErrorCode;
 
   fn try_from(value: FilterDataPB) -> Result<Self, Self::Error> {
+    if let Some(emptiness) = value.emptiness {
+      return Ok(Self::Data {
+        field_id: value.field_id,
+        field_type: value.field_type,
+        condition_and_content: BoxAny::new(emptiness),
+      });
+    }
+
     let bytes: &[u8] = value.data.as_ref();
     let condition_and_content = match value.field_type {
       FieldType::RichText | FieldType::URL => {
@@ -153,7 +182,7 @@ impl TryFrom<FilterDataPB> for FilterInner {
       FieldType::Relation => {
         BoxAny::new(RelationFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
       },
-      FieldType::Summary => {
+      FieldType::Summary | FieldType::LastEditedBy | FieldType::CreatedBy => {
         BoxAny::new(TextFilterPB::try_from(bytes).map_err(|_| ErrorCode::ProtobufSerde)?)
       },
     };
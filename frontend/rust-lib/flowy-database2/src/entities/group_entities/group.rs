@@ -62,6 +62,28 @@ impl std::ops::Deref for RepeatedGroupPB {
   }
 }
 
+/// A group that the rows would be separated into if the view were grouped by a field.
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct GroupPreviewPB {
+  #[pb(index = 1)]
+  pub group_id: String,
+
+  #[pb(index = 2)]
+  pub group_name: String,
+
+  #[pb(index = 3)]
+  pub row_count: i64,
+
+  #[pb(index = 4)]
+  pub is_default: bool,
+}
+
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct RepeatedGroupPreviewPB {
+  #[pb(index = 1)]
+  pub items: Vec<GroupPreviewPB>,
+}
+
 impl std::ops::DerefMut for RepeatedGroupPB {
   fn deref_mut(&mut self) -> &mut Self::Target {
     &mut self.items
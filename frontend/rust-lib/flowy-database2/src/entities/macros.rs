@@ -16,6 +16,8 @@ macro_rules! impl_into_field_type {
           9 => FieldType::CreatedTime,
           10 => FieldType::Relation,
           11 => FieldType::Summary,
+          12 => FieldType::LastEditedBy,
+          13 => FieldType::CreatedBy,
           _ => {
             tracing::error!("🔴Can't parse FieldType from value: {}", ty);
             FieldType::RichText
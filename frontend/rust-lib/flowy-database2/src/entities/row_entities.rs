@@ -66,8 +66,13 @@ pub struct RowMetaPB {
 
   #[pb(index = 5)]
   pub is_document_empty: bool,
+
+  #[pb(index = 6)]
+  pub is_locked: bool,
 }
 
+/// The locked state of the row is kept by the database rather than the [RowDetail], so
+/// `is_locked` is false. Use `DatabaseEditor::row_meta_pb` to fill it in.
 impl std::convert::From<&RowDetail> for RowMetaPB {
   fn from(row_detail: &RowDetail) -> Self {
     Self {
@@ -76,6 +81,7 @@ impl std::convert::From<&RowDetail> for RowMetaPB {
       icon: row_detail.meta.icon_url.clone(),
       cover: row_detail.meta.cover_url.clone(),
       is_document_empty: row_detail.meta.is_document_empty,
+      is_locked: false,
     }
   }
 }
@@ -87,6 +93,7 @@ impl std::convert::From<RowDetail> for RowMetaPB {
       icon: row_detail.meta.icon_url,
       cover: row_detail.meta.cover_url,
       is_document_empty: row_detail.meta.is_document_empty,
+      is_locked: false,
     }
   }
 }
@@ -140,6 +147,35 @@ impl TryInto<UpdateRowMetaParams> for UpdateRowMetaChangesetPB {
   }
 }
 
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct SetRowLockedPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3)]
+  pub locked: bool,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct CreateRowFromDocumentPB {
+  #[pb(index = 1)]
+  #[validate(custom = "required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "required_not_empty_str")]
+  pub document_id: String,
+}
+
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct CreatedDocumentPB {
+  #[pb(index = 1)]
+  pub document_id: String,
+}
+
 #[derive(Debug, Default, Clone, ProtoBuf)]
 pub struct UpdateRowPayloadPB {
   #[pb(index = 1)]
@@ -225,6 +261,32 @@ impl std::convert::From<Vec<RowPB>> for RepeatedRowPB {
   }
 }
 
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct DatabaseRowsPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "required_not_empty_str")]
+  pub view_id: String,
+
+  /// The index of the first row, in the order that the view displays the rows.
+  #[pb(index = 2)]
+  pub offset: u32,
+
+  #[pb(index = 3)]
+  pub limit: u32,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RepeatedRowMetaPB {
+  #[pb(index = 1)]
+  pub items: Vec<RowMetaPB>,
+}
+
+impl std::convert::From<Vec<RowMetaPB>> for RepeatedRowMetaPB {
+  fn from(items: Vec<RowMetaPB>) -> Self {
+    Self { items }
+  }
+}
+
 #[derive(Debug, Clone, Default, ProtoBuf)]
 pub struct InsertedRowPB {
   #[pb(index = 1)]
@@ -338,6 +400,43 @@ impl TryInto<RowIdParams> for RowIdPB {
   }
 }
 
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct DuplicateRowPayloadPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub row_id: String,
+
+  #[pb(index = 3, one_of)]
+  pub group_id: Option<String>,
+
+  /// Copies the content of the row's document to the document of the duplicate. Otherwise, the
+  /// duplicate's document is empty.
+  #[pb(index = 4)]
+  pub copy_document: bool,
+}
+
+pub struct DuplicateRowParams {
+  pub view_id: String,
+  pub row_id: RowId,
+  pub copy_document: bool,
+}
+
+impl TryInto<DuplicateRowParams> for DuplicateRowPayloadPB {
+  type Error = ErrorCode;
+
+  fn try_into(self) -> Result<DuplicateRowParams, Self::Error> {
+    let view_id = NotEmptyStr::parse(self.view_id).map_err(|_| ErrorCode::DatabaseIdIsEmpty)?;
+    let row_id = NotEmptyStr::parse(self.row_id).map_err(|_| ErrorCode::RowIdIsEmpty)?;
+    Ok(DuplicateRowParams {
+      view_id: view_id.0,
+      row_id: RowId::from(row_id.0),
+      copy_document: self.copy_document,
+    })
+  }
+}
+
 #[derive(Debug, Default, Clone, ProtoBuf)]
 pub struct RepeatedRowIdPB {
   #[pb(index = 1)]
@@ -13,7 +13,7 @@ use crate::entities::{
   RepeatedFieldSettingsPB, RepeatedFilterPB, RepeatedGroupSettingPB, RepeatedSortPB,
   UpdateFilterDataPB, UpdateFilterTypePB, UpdateGroupPB, UpdateSortPayloadPB,
 };
-use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
+use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting, NewRowPosition};
 
 use super::{BoardLayoutSettingPB, ReorderSortPayloadPB};
 
@@ -110,6 +110,42 @@ pub struct DatabaseSettingChangesetPB {
   pub delete_sort: Option<DeleteSortPayloadPB>,
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ProtoBuf_Enum)]
+#[repr(u8)]
+pub enum NewRowPositionPB {
+  #[default]
+  Bottom = 0,
+  Top = 1,
+}
+
+impl std::convert::From<NewRowPosition> for NewRowPositionPB {
+  fn from(position: NewRowPosition) -> Self {
+    match position {
+      NewRowPosition::Bottom => NewRowPositionPB::Bottom,
+      NewRowPosition::Top => NewRowPositionPB::Top,
+    }
+  }
+}
+
+impl std::convert::From<NewRowPositionPB> for NewRowPosition {
+  fn from(position: NewRowPositionPB) -> Self {
+    match position {
+      NewRowPositionPB::Bottom => NewRowPosition::Bottom,
+      NewRowPositionPB::Top => NewRowPosition::Top,
+    }
+  }
+}
+
+/// The payload of [crate::notification::DatabaseNotification::DidUpdateNewRowPosition].
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct NewRowPositionSettingPB {
+  #[pb(index = 1)]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub position: NewRowPositionPB,
+}
+
 #[derive(Debug, Eq, PartialEq, Default, ProtoBuf, Clone)]
 pub struct DatabaseLayoutSettingPB {
   #[pb(index = 1)]
@@ -166,6 +202,33 @@ impl From<LayoutSettingParams> for DatabaseLayoutSettingPB {
   }
 }
 
+impl From<DatabaseLayoutSettingPB> for LayoutSettingParams {
+  fn from(data: DatabaseLayoutSettingPB) -> Self {
+    Self {
+      layout_type: data.layout_type.into(),
+      board: data.board.map(Into::into),
+      calendar: data.calendar.map(Into::into),
+    }
+  }
+}
+
+/// The settings of all the layouts of a view, one item per layout.
+#[derive(Debug, Default, ProtoBuf, Clone)]
+pub struct RepeatedDatabaseLayoutSettingPB {
+  #[pb(index = 1)]
+  pub items: Vec<DatabaseLayoutSettingPB>,
+}
+
+#[derive(Debug, Default, ProtoBuf, Clone, Validate)]
+pub struct AllLayoutSettingsChangesetPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  pub items: Vec<DatabaseLayoutSettingPB>,
+}
+
 #[derive(Debug, Eq, PartialEq, Default, ProtoBuf, Clone)]
 pub struct LayoutSettingChangesetPB {
   #[pb(index = 1)]
@@ -112,6 +112,18 @@ pub struct UpdateSortPayloadPB {
   pub condition: SortConditionPB,
 }
 
+/// Replaces all the sorts of a view. The sorts are applied in the order of the list.
+#[derive(Debug, Default, Clone, Validate, ProtoBuf)]
+pub struct SetSortsPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate]
+  pub sorts: Vec<UpdateSortPayloadPB>,
+}
+
 #[derive(Debug, Default, Clone, Validate, ProtoBuf)]
 pub struct ReorderSortPayloadPB {
   #[pb(index = 1)]
@@ -5,11 +5,18 @@ use flowy_derive::ProtoBuf;
 pub struct CheckboxCellDataPB {
   #[pb(index = 1)]
   pub is_checked: bool,
+
+  /// False if the cell has never been set. An unset cell is treated as unchecked.
+  #[pb(index = 2)]
+  pub is_set: bool,
 }
 
 impl CheckboxCellDataPB {
   pub fn new(is_checked: bool) -> Self {
-    Self { is_checked }
+    Self {
+      is_checked,
+      is_set: true,
+    }
   }
 }
 
@@ -8,6 +8,7 @@ mod summary_entities;
 mod text_entities;
 mod timestamp_entities;
 mod url_entities;
+mod user_entities;
 
 pub use checkbox_entities::*;
 pub use checklist_entities::*;
@@ -19,3 +20,4 @@ pub use summary_entities::*;
 pub use text_entities::*;
 pub use timestamp_entities::*;
 pub use url_entities::*;
+pub use user_entities::*;
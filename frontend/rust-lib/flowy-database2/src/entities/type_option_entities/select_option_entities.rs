@@ -6,6 +6,7 @@ use crate::services::field::{
 };
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
+use validator::Validate;
 
 /// [SelectOptionPB] represents an option for a single select, and multiple select.
 #[derive(Clone, Debug, Default, PartialEq, Eq, ProtoBuf)]
@@ -40,6 +41,29 @@ impl From<SelectOptionPB> for SelectOption {
   }
 }
 
+#[derive(Debug, Default, Clone, ProtoBuf, Validate)]
+pub struct GetSelectOptionsPayloadPB {
+  #[pb(index = 1)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub view_id: String,
+
+  #[pb(index = 2)]
+  #[validate(custom = "lib_infra::validator_fn::required_not_empty_str")]
+  pub field_id: String,
+}
+
+#[derive(Debug, Default, ProtoBuf)]
+pub struct RepeatedSelectOptionPB {
+  #[pb(index = 1)]
+  pub items: Vec<SelectOptionPB>,
+}
+
+impl std::convert::From<Vec<SelectOptionPB>> for RepeatedSelectOptionPB {
+  fn from(items: Vec<SelectOptionPB>) -> Self {
+    Self { items }
+  }
+}
+
 #[derive(Default, ProtoBuf)]
 pub struct RepeatedSelectOptionPayload {
   #[pb(index = 1)]
@@ -5,16 +5,30 @@ use flowy_derive::ProtoBuf;
 pub struct RichTextTypeOptionPB {
   #[pb(index = 1)]
   data: String,
+
+  #[pb(index = 2, one_of)]
+  validation_regex: Option<String>,
+
+  #[pb(index = 3)]
+  required: bool,
 }
 
 impl From<RichTextTypeOption> for RichTextTypeOptionPB {
   fn from(data: RichTextTypeOption) -> Self {
-    Self { data: data.inner }
+    Self {
+      data: data.inner,
+      validation_regex: data.validation_regex,
+      required: data.required,
+    }
   }
 }
 
 impl From<RichTextTypeOptionPB> for RichTextTypeOption {
   fn from(data: RichTextTypeOptionPB) -> Self {
-    Self { inner: data.data }
+    Self {
+      inner: data.data,
+      validation_regex: data.validation_regex,
+      required: data.required,
+    }
   }
 }
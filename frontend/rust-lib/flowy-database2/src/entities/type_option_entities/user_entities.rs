@@ -0,0 +1,26 @@
+use flowy_derive::ProtoBuf;
+
+use crate::entities::FieldType;
+use crate::services::field::UserTypeOption;
+
+#[derive(Clone, Debug, Default, ProtoBuf)]
+pub struct UserTypeOptionPB {
+  #[pb(index = 1)]
+  pub field_type: FieldType,
+}
+
+impl From<UserTypeOption> for UserTypeOptionPB {
+  fn from(data: UserTypeOption) -> Self {
+    Self {
+      field_type: data.field_type,
+    }
+  }
+}
+
+impl From<UserTypeOptionPB> for UserTypeOption {
+  fn from(data: UserTypeOptionPB) -> Self {
+    Self {
+      field_type: data.field_type,
+    }
+  }
+}
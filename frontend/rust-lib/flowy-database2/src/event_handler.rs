@@ -1,7 +1,9 @@
 use std::sync::{Arc, Weak};
 
 use collab_database::rows::RowId;
+use collab_database::views::DatabaseLayout;
 use lib_infra::box_any::BoxAny;
+use strum::IntoEnumIterator;
 use tokio::sync::oneshot;
 use tracing::error;
 
@@ -14,8 +16,9 @@ use crate::services::field::{
   type_option_data_from_pb, ChecklistCellChangeset, DateCellChangeset, RelationCellChangeset,
   SelectOptionCellChangeset,
 };
+use crate::services::field_settings::FieldSettings;
 use crate::services::group::GroupChangeset;
-use crate::services::share::csv::CSVFormat;
+use crate::services::share::csv::{CSVExportOptions, CSVFormat};
 
 fn upgrade_manager(
   database_manager: AFPluginState<Weak<DatabaseManager>>,
@@ -38,6 +41,24 @@ pub(crate) async fn get_database_data_handler(
   data_result_ok(data)
 }
 
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn get_database_rows_handler(
+  data: AFPluginData<DatabaseRowsPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedRowMetaPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let rows = database_editor
+    .get_database_rows(
+      &params.view_id,
+      params.offset as usize,
+      params.limit as usize,
+    )
+    .await?;
+  data_result_ok(rows.into())
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn open_database_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -164,10 +185,22 @@ pub(crate) async fn delete_all_sorts_handler(
   let manager = upgrade_manager(manager)?;
   let view_id: DatabaseViewIdPB = data.into_inner();
   let database_editor = manager.get_database_with_view_id(view_id.as_ref()).await?;
-  database_editor.delete_all_sorts(view_id.as_ref()).await;
+  database_editor.delete_all_sorts(view_id.as_ref()).await?;
   Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip(data, manager), err)]
+pub(crate) async fn set_sorts_handler(
+  data: AFPluginData<SetSortsPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedSortPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let sorts = database_editor.set_sorts(params).await?;
+  data_result_ok(sorts.into())
+}
+
 #[tracing::instrument(level = "trace", skip(data, manager), err)]
 pub(crate) async fn get_fields_handler(
   data: AFPluginData<GetFieldPayloadPB>,
@@ -280,7 +313,11 @@ pub(crate) async fn switch_to_field_handler(
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
   let old_field = database_editor.get_field(&params.field_id);
   database_editor
-    .switch_to_field_type(&params.field_id, params.field_type)
+    .switch_to_field_type(
+      &params.field_id,
+      params.field_type,
+      params.migration_strategy,
+    )
     .await?;
 
   if let Some(new_type_option) = database_editor
@@ -380,7 +417,22 @@ pub(crate) async fn update_row_meta_handler(
   let row_id = RowId::from(params.id.clone());
   database_editor
     .update_row_meta(&row_id.clone(), params)
-    .await;
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn set_row_locked_handler(
+  data: AFPluginData<SetRowLockedPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.into_inner();
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let row_id = RowId::from(params.row_id);
+  database_editor
+    .set_row_locked(&row_id, params.locked)
+    .await?;
   Ok(())
 }
 
@@ -397,24 +449,52 @@ pub(crate) async fn delete_rows_handler(
     .into_iter()
     .map(RowId::from)
     .collect::<Vec<_>>();
-  database_editor.delete_rows(&row_ids).await;
+  database_editor.delete_rows(&row_ids).await?;
   Ok(())
 }
 
 #[tracing::instrument(level = "debug", skip(data, manager), err)]
 pub(crate) async fn duplicate_row_handler(
-  data: AFPluginData<RowIdPB>,
+  data: AFPluginData<DuplicateRowPayloadPB>,
   manager: AFPluginState<Weak<DatabaseManager>>,
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
-  let params: RowIdParams = data.into_inner().try_into()?;
+  let params: DuplicateRowParams = data.into_inner().try_into()?;
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
   database_editor
-    .duplicate_row(&params.view_id, &params.row_id)
+    .duplicate_row(&params.view_id, &params.row_id, params.copy_document)
     .await?;
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn create_row_from_document_handler(
+  data: AFPluginData<CreateRowFromDocumentPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RowMetaPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let row_meta = database_editor
+    .create_row_from_document(&params.view_id, &params.document_id)
+    .await?;
+  data_result_ok(row_meta)
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn create_document_from_row_handler(
+  data: AFPluginData<RowIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<CreatedDocumentPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: RowIdParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let document_id = database_editor
+    .create_document_from_row(&params.view_id, &params.row_id)
+    .await?;
+  data_result_ok(CreatedDocumentPB { document_id })
+}
+
 #[tracing::instrument(level = "debug", skip(data, manager), err)]
 pub(crate) async fn move_row_handler(
   data: AFPluginData<MoveRowPayloadPB>,
@@ -498,6 +578,18 @@ pub(crate) async fn new_select_option_handler(
   }
 }
 
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn get_select_options_handler(
+  data: AFPluginData<GetSelectOptionsPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedSelectOptionPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let options = database_editor.get_select_options(&params.field_id).await?;
+  data_result_ok(options.into())
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn insert_or_update_select_option_handler(
   data: AFPluginData<RepeatedSelectOptionPayload>,
@@ -661,6 +753,20 @@ pub(crate) async fn set_group_by_field_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip_all, err)]
+pub(crate) async fn preview_grouping_handler(
+  data: AFPluginData<GroupByFieldPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedGroupPreviewPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let params: GroupByFieldParams = data.into_inner().try_into()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let groups = database_editor
+    .preview_grouping(&params.view_id, &params.field_id)
+    .await?;
+  data_result_ok(RepeatedGroupPreviewPB { items: groups })
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 pub(crate) async fn update_group_handler(
   data: AFPluginData<UpdateGroupPB>,
@@ -750,7 +856,7 @@ pub(crate) async fn get_database_meta_handler(
 ) -> DataResult<DatabaseMetaPB, FlowyError> {
   let manager = upgrade_manager(manager)?;
   let database_id = data.into_inner().value;
-  let inline_view_id = manager.get_database_inline_view_id(&database_id).await?;
+  let inline_view_id = manager.get_inline_view_id(&database_id).await?;
 
   let data = DatabaseMetaPB {
     database_id,
@@ -768,7 +874,7 @@ pub(crate) async fn get_databases_handler(
 
   let mut items = Vec::with_capacity(metas.len());
   for meta in metas {
-    match manager.get_database_inline_view_id(&meta.database_id).await {
+    match manager.get_inline_view_id(&meta.database_id).await {
       Ok(view_id) => items.push(DatabaseMetaPB {
         database_id: meta.database_id,
         inline_view_id: view_id,
@@ -812,6 +918,42 @@ pub(crate) async fn get_layout_setting_handler(
   data_result_ok(layout_setting_pb)
 }
 
+pub(crate) async fn get_all_layout_settings_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<RepeatedDatabaseLayoutSettingPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner();
+  let database_editor = manager.get_database_with_view_id(view_id.as_ref()).await?;
+  let mut layout_settings = database_editor
+    .get_all_layout_settings(view_id.as_ref())
+    .await?;
+  let items = DatabaseLayoutPB::iter()
+    .flat_map(|layout| layout_settings.remove(&DatabaseLayout::from(layout)))
+    .map(DatabaseLayoutSettingPB::from)
+    .collect();
+  data_result_ok(RepeatedDatabaseLayoutSettingPB { items })
+}
+
+#[tracing::instrument(level = "debug", skip(data, manager), err)]
+pub(crate) async fn set_all_layout_settings_handler(
+  data: AFPluginData<AllLayoutSettingsChangesetPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let layout_settings = params
+    .items
+    .into_iter()
+    .map(LayoutSettingParams::from)
+    .collect();
+  database_editor
+    .set_all_layout_settings(&params.view_id, layout_settings)
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip(data, manager), err)]
 pub(crate) async fn get_calendar_events_handler(
   data: AFPluginData<CalendarEventRequestPB>,
@@ -821,8 +963,8 @@ pub(crate) async fn get_calendar_events_handler(
   let params: CalendarEventRequestParams = data.into_inner().try_into()?;
   let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
   let events = database_editor
-    .get_all_calendar_events(&params.view_id)
-    .await;
+    .get_all_calendar_events(&params.view_id, params.date_field_id.as_deref())
+    .await?;
   data_result_ok(RepeatedCalendarEventPB { items: events })
 }
 
@@ -898,7 +1040,26 @@ pub(crate) async fn export_csv_handler(
   let manager = upgrade_manager(manager)?;
   let view_id = data.into_inner().value;
   let database = manager.get_database_with_view_id(&view_id).await?;
-  let data = database.export_csv(CSVFormat::Original).await?;
+  let data = database
+    .export_csv(CSVFormat::Original, CSVExportOptions::default())
+    .await?;
+  data_result_ok(DatabaseExportDataPB {
+    export_type: DatabaseExportDataType::CSV,
+    data,
+  })
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn export_view_csv_handler(
+  data: AFPluginData<DatabaseViewIdPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> DataResult<DatabaseExportDataPB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let view_id = data.into_inner().value;
+  let database = manager.get_database_with_view_id(&view_id).await?;
+  let data = database
+    .export_view_csv(&view_id, CSVFormat::Original)
+    .await?;
   data_result_ok(DatabaseExportDataPB {
     export_type: DatabaseExportDataType::CSV,
     data,
@@ -972,6 +1133,38 @@ pub(crate) async fn update_field_settings_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn set_default_field_settings_handler(
+  data: AFPluginData<DefaultFieldSettingsPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let settings = params.items.into_iter().map(FieldSettings::from).collect();
+  database_editor.set_default_field_settings(params.layout.into(), settings)?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub(crate) async fn set_fields_visibility_handler(
+  data: AFPluginData<SetFieldsVisibilityPayloadPB>,
+  manager: AFPluginState<Weak<DatabaseManager>>,
+) -> FlowyResult<()> {
+  let manager = upgrade_manager(manager)?;
+  let params = data.try_into_inner()?;
+  let database_editor = manager.get_database_with_view_id(&params.view_id).await?;
+  let visibility = params
+    .items
+    .into_iter()
+    .map(|item| (item.field_id, item.is_visible))
+    .collect();
+  database_editor
+    .set_fields_visibility(&params.view_id, visibility)
+    .await?;
+  Ok(())
+}
+
 #[tracing::instrument(level = "debug", skip_all, err)]
 pub(crate) async fn get_all_calculations_handler(
   data: AFPluginData<DatabaseViewIdPB>,
@@ -21,6 +21,8 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::GetAllFilters, get_all_filters_handler)
         .event(DatabaseEvent::GetAllSorts, get_all_sorts_handler)
         .event(DatabaseEvent::DeleteAllSorts, delete_all_sorts_handler)
+        .event(DatabaseEvent::SetSorts, set_sorts_handler)
+        .event(DatabaseEvent::GetDatabaseRows, get_database_rows_handler)
         // Field
         .event(DatabaseEvent::GetFields, get_fields_handler)
         .event(DatabaseEvent::GetPrimaryField, get_primary_field_handler)
@@ -37,9 +39,12 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::GetRow, get_row_handler)
         .event(DatabaseEvent::GetRowMeta, get_row_meta_handler)
         .event(DatabaseEvent::UpdateRowMeta, update_row_meta_handler)
+        .event(DatabaseEvent::SetRowLocked, set_row_locked_handler)
         .event(DatabaseEvent::DeleteRows, delete_rows_handler)
         .event(DatabaseEvent::DuplicateRow, duplicate_row_handler)
         .event(DatabaseEvent::MoveRow, move_row_handler)
+        .event(DatabaseEvent::CreateRowFromDocument, create_row_from_document_handler)
+        .event(DatabaseEvent::CreateDocumentFromRow, create_document_from_row_handler)
         // Cell
         .event(DatabaseEvent::GetCell, get_cell_handler)
         .event(DatabaseEvent::UpdateCell, update_cell_handler)
@@ -47,6 +52,7 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::CreateSelectOption, new_select_option_handler)
         .event(DatabaseEvent::InsertOrUpdateSelectOption, insert_or_update_select_option_handler)
         .event(DatabaseEvent::DeleteSelectOption, delete_select_option_handler)
+        .event(DatabaseEvent::GetSelectOptions, get_select_options_handler)
         .event(DatabaseEvent::UpdateSelectOptionCell, update_select_option_cell_handler)
         // Checklist
         .event(DatabaseEvent::UpdateChecklistCell, update_checklist_cell_handler)
@@ -54,6 +60,7 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         .event(DatabaseEvent::UpdateDateCell, update_date_cell_handler)
         // Group
         .event(DatabaseEvent::SetGroupByField, set_group_by_field_handler)
+        .event(DatabaseEvent::PreviewGrouping, preview_grouping_handler)
         .event(DatabaseEvent::MoveGroup, move_group_handler)
         .event(DatabaseEvent::MoveGroupRow, move_group_row_handler)
         .event(DatabaseEvent::GetGroups, get_groups_handler)
@@ -72,14 +79,19 @@ pub fn init(database_manager: Weak<DatabaseManager>) -> AFPlugin {
         // Layout setting
         .event(DatabaseEvent::SetLayoutSetting, set_layout_setting_handler)
         .event(DatabaseEvent::GetLayoutSetting, get_layout_setting_handler)
+        .event(DatabaseEvent::GetAllLayoutSettings, get_all_layout_settings_handler)
+        .event(DatabaseEvent::SetAllLayoutSettings, set_all_layout_settings_handler)
         .event(DatabaseEvent::CreateDatabaseView, create_database_view)
         // Export
         .event(DatabaseEvent::ExportCSV, export_csv_handler)
+        .event(DatabaseEvent::ExportViewCSV, export_view_csv_handler)
         .event(DatabaseEvent::GetDatabaseSnapshots, get_snapshots_handler)
         // Field settings
         .event(DatabaseEvent::GetFieldSettings, get_field_settings_handler)
         .event(DatabaseEvent::GetAllFieldSettings, get_all_field_settings_handler)
         .event(DatabaseEvent::UpdateFieldSettings, update_field_settings_handler)
+        .event(DatabaseEvent::SetDefaultFieldSettings, set_default_field_settings_handler)
+        .event(DatabaseEvent::SetFieldsVisibility, set_fields_visibility_handler)
         // Calculations
         .event(DatabaseEvent::GetAllCalculations, get_all_calculations_handler)
         .event(DatabaseEvent::UpdateCalculation, update_calculation_handler)
@@ -129,6 +141,16 @@ pub enum DatabaseEvent {
   #[event(input = "DatabaseViewIdPB")]
   DeleteAllSorts = 6,
 
+  /// Replaces all the sorts of a view at once, so the rows are only re-sorted once. Returns an
+  /// error if a field is sorted more than once.
+  #[event(input = "SetSortsPayloadPB", output = "RepeatedSortPB")]
+  SetSorts = 8,
+
+  /// Returns a page of the rows of a view. It's used to load the rows that aren't returned by
+  /// [DatabaseEvent::GetDatabaseData] under the low-memory profile.
+  #[event(input = "DatabaseRowsPayloadPB", output = "RepeatedRowMetaPB")]
+  GetDatabaseRows = 9,
+
   #[event(input = "DatabaseViewIdPB")]
   GetDatabaseData = 7,
 
@@ -215,6 +237,11 @@ pub enum DatabaseEvent {
   #[event(input = "RepeatedSelectOptionPayload")]
   DeleteSelectOption = 32,
 
+  /// Returns all the options of a single or multi-select field, including the unused ones. Returns
+  /// an error for the other field types.
+  #[event(input = "GetSelectOptionsPayloadPB", output = "RepeatedSelectOptionPB")]
+  GetSelectOptions = 33,
+
   #[event(input = "CreateRowPayloadPB", output = "RowMetaPB")]
   CreateRow = 50,
 
@@ -226,7 +253,7 @@ pub enum DatabaseEvent {
   #[event(input = "RepeatedRowIdPB")]
   DeleteRows = 52,
 
-  #[event(input = "RowIdPB")]
+  #[event(input = "DuplicateRowPayloadPB")]
   DuplicateRow = 53,
 
   #[event(input = "MoveRowPayloadPB")]
@@ -295,6 +322,11 @@ pub enum DatabaseEvent {
   #[event(input = "DeleteGroupPayloadPB")]
   DeleteGroup = 115,
 
+  /// Returns the groups and their row counts if the view were grouped by the field, without
+  /// changing the grouping of the view.
+  #[event(input = "GroupByFieldPayloadPB", output = "RepeatedGroupPreviewPB")]
+  PreviewGrouping = 116,
+
   #[event(input = "DatabaseIdPB", output = "DatabaseMetaPB")]
   GetDatabaseMeta = 119,
 
@@ -323,12 +355,26 @@ pub enum DatabaseEvent {
   #[event(input = "MoveCalendarEventPB")]
   MoveCalendarEvent = 126,
 
+  /// Returns the settings of all the layouts of the view. The layouts without a stored setting
+  /// get the default one.
+  #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseLayoutSettingPB")]
+  GetAllLayoutSettings = 127,
+
+  /// Updates the settings of several layouts at once. None of them is saved if one is invalid.
+  #[event(input = "AllLayoutSettingsChangesetPB")]
+  SetAllLayoutSettings = 128,
+
   #[event(input = "CreateDatabaseViewPayloadPB")]
   CreateDatabaseView = 130,
 
   #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
   ExportCSV = 141,
 
+  /// Exports the rows and the visible fields of the view as they're shown, i.e. filtered and
+  /// sorted.
+  #[event(input = "DatabaseViewIdPB", output = "DatabaseExportDataPB")]
+  ExportViewCSV = 142,
+
   /// Returns all the snapshots of the database view.
   #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseSnapshotPB")]
   GetDatabaseSnapshots = 150,
@@ -353,6 +399,10 @@ pub enum DatabaseEvent {
   #[event(input = "RemoveCalculationChangesetPB")]
   RemoveCalculation = 165,
 
+  /// Shows or hides the given fields of the view at once
+  #[event(input = "SetFieldsVisibilityPayloadPB")]
+  SetFieldsVisibility = 166,
+
   /// Currently unused. Get a list of database ids that this database relates
   /// to.
   #[event(input = "DatabaseViewIdPB", output = "RepeatedDatabaseIdPB")]
@@ -373,4 +423,22 @@ pub enum DatabaseEvent {
 
   #[event(input = "SummaryRowPB")]
   SummarizeRow = 174,
+
+  /// Locks or unlocks a row. The cells of a locked row can't be edited.
+  #[event(input = "SetRowLockedPB")]
+  SetRowLocked = 175,
+
+  /// Creates a row from a document. The document's name becomes the primary cell of the row and
+  /// its content is copied to the row's document.
+  #[event(input = "CreateRowFromDocumentPB", output = "RowMetaPB")]
+  CreateRowFromDocument = 176,
+
+  /// Creates a document in the workspace from the row's document.
+  #[event(input = "RowIdPB", output = "CreatedDocumentPB")]
+  CreateDocumentFromRow = 177,
+
+  /// Sets the settings that the given fields start with in the views of the layout that are
+  /// created from now on. The existing views are unaffected.
+  #[event(input = "DefaultFieldSettingsPB")]
+  SetDefaultFieldSettings = 178,
 }
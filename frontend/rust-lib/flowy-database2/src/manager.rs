@@ -1,8 +1,12 @@
 use anyhow::anyhow;
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Weak};
+use std::time::Duration;
 
 use collab::core::collab::{DataSource, MutexCollab};
+use collab::core::collab_state::SyncState;
 use collab_database::database::DatabaseData;
 use collab_database::error::DatabaseError;
 use collab_database::rows::RowId;
@@ -12,30 +16,71 @@ use collab_database::workspace_database::{
 };
 use collab_entity::CollabType;
 use collab_plugins::local_storage::kv::KVTransactionDB;
+use futures::StreamExt;
 use tokio::sync::{Mutex, RwLock};
-use tracing::{event, instrument, trace};
+use tracing::{error, event, instrument, trace};
 
-use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
+use collab_integrate::collab_builder::{
+  AppFlowyCollabBuilder, CollabBuilderConfig, CollabPluginProviderType,
+};
 use collab_integrate::{CollabKVAction, CollabKVDB, CollabPersistenceConfig};
 use flowy_database_pub::cloud::{DatabaseCloudService, SummaryRowContent};
 use flowy_error::{internal_error, FlowyError, FlowyResult};
+use lib_dispatch::prelude::af_spawn;
 use lib_infra::box_any::BoxAny;
+use lib_infra::future::FutureResult;
 use lib_infra::priority_task::TaskDispatcher;
 
-use crate::entities::{DatabaseLayoutPB, DatabaseSnapshotPB};
+use crate::entities::{DatabaseLayoutPB, DatabaseSnapshotPB, FieldType};
 use crate::services::cell::stringify_cell;
-use crate::services::database::DatabaseEditor;
+use crate::services::database::{
+  Clock, CloneDatabaseParams, CloneIdMap, DatabaseEditor, DatabaseEditorConfig, IdGenerator,
+  ImportViewSettingsResult, LowMemoryProfile, OpenProfile, OpenProfiles, RandomIdGenerator,
+  RemoteRowEditNotifier, RemoteRowEditPlugin, RowSyncNotifier, SystemClock,
+  DEFAULT_SYNC_STATE_LOG_WINDOW,
+};
 use crate::services::database_view::DatabaseLayoutDepsResolver;
+use crate::services::field::RelationTypeOption;
 use crate::services::field_settings::default_field_settings_by_layout_map;
-use crate::services::share::csv::{CSVFormat, CSVImporter, ImportResult};
+use crate::services::share::csv::{
+  CSVExportOptions, CSVFormat, CSVImportOptions, CSVImporter, ImportResult,
+};
 
 pub trait DatabaseUser: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError>;
   fn workspace_id(&self) -> Result<String, FlowyError>;
   fn workspace_database_object_id(&self) -> Result<String, FlowyError>;
+  /// Returns true if the current user's role in the current workspace only allows reading, or if
+  /// the user isn't a member of the workspace. Returns an error if the role can't be fetched.
+  fn is_read_only_member(&self) -> FutureResult<bool, FlowyError>;
+}
+
+/// Gives the database access to the documents of the workspace, which are managed outside of
+/// this crate. It's used to convert the documents to the rows and the other way around.
+pub trait DatabaseDocumentService: Send + Sync {
+  /// Returns the name of the document.
+  fn get_document_title(&self, document_id: &str) -> FutureResult<String, FlowyError>;
+  /// Copies the content of the document to a new document with the given id. Returns true if
+  /// the content is empty.
+  fn copy_document(
+    &self,
+    document_id: &str,
+    new_document_id: &str,
+  ) -> FutureResult<bool, FlowyError>;
+  /// Creates a document in the workspace and returns its id. The content is copied from
+  /// `copy_from` if it's not None. Otherwise, the document is empty.
+  fn create_document(
+    &self,
+    name: &str,
+    copy_from: Option<&str>,
+  ) -> FutureResult<String, FlowyError>;
 }
 
+/// Set after the document service is created, which depends on the [DatabaseManager].
+pub(crate) type DatabaseDocumentServiceCell =
+  Arc<parking_lot::RwLock<Option<Arc<dyn DatabaseDocumentService>>>>;
+
 pub struct DatabaseManager {
   user: Arc<dyn DatabaseUser>,
   workspace_database: Arc<RwLock<Option<Arc<WorkspaceDatabase>>>>,
@@ -43,6 +88,27 @@ pub struct DatabaseManager {
   editors: Mutex<HashMap<String, Arc<DatabaseEditor>>>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
   cloud_service: Arc<dyn DatabaseCloudService>,
+  /// Cached role of the current user in the workspace. It's shared with all the editors.
+  is_read_only: Arc<AtomicBool>,
+  /// Shared with all the editors. See [Self::last_open_profiles].
+  open_profiles: Arc<OpenProfiles>,
+  num_of_closed_idle_databases: AtomicUsize,
+  /// Passed to every editor. See [LowMemoryProfile].
+  low_memory_profile: Option<LowMemoryProfile>,
+  /// Shared with all the editors. See [Self::set_document_service].
+  document_service: DatabaseDocumentServiceCell,
+  /// Passed to every editor. See [Self::set_sync_state_log_window].
+  sync_state_log_window: parking_lot::RwLock<Duration>,
+  /// Passed to every editor. See [Self::set_clock].
+  clock: parking_lot::RwLock<Arc<dyn Clock>>,
+  /// Passed to every editor. See [Self::set_id_generator].
+  id_generator: parking_lot::RwLock<Arc<dyn IdGenerator>>,
+  /// Passed to every editor. See [Self::set_primary_value_index_enabled].
+  is_primary_value_index_enabled: AtomicBool,
+  /// Shared by the collab service that builds the row collabs and all the editors.
+  row_sync_notifier: RowSyncNotifier,
+  /// Shared by the collab service that builds the row collabs and all the editors.
+  remote_row_edit_notifier: RemoteRowEditNotifier,
 }
 
 impl DatabaseManager {
@@ -51,6 +117,7 @@ impl DatabaseManager {
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
     collab_builder: Arc<AppFlowyCollabBuilder>,
     cloud_service: Arc<dyn DatabaseCloudService>,
+    low_memory_profile: Option<LowMemoryProfile>,
   ) -> Self {
     Self {
       user: database_user,
@@ -59,9 +126,55 @@ impl DatabaseManager {
       editors: Default::default(),
       collab_builder,
       cloud_service,
+      is_read_only: Default::default(),
+      open_profiles: Default::default(),
+      num_of_closed_idle_databases: Default::default(),
+      low_memory_profile,
+      document_service: Default::default(),
+      sync_state_log_window: parking_lot::RwLock::new(DEFAULT_SYNC_STATE_LOG_WINDOW),
+      clock: parking_lot::RwLock::new(Arc::new(SystemClock)),
+      id_generator: parking_lot::RwLock::new(Arc::new(RandomIdGenerator)),
+      is_primary_value_index_enabled: AtomicBool::new(false),
+      row_sync_notifier: Default::default(),
+      remote_row_edit_notifier: Default::default(),
     }
   }
 
+  /// The document service can't be passed to [Self::new] because it depends on the managers that
+  /// are created after the [DatabaseManager].
+  pub fn set_document_service(&self, document_service: Arc<dyn DatabaseDocumentService>) {
+    *self.document_service.write() = Some(document_service);
+  }
+
+  /// The identical consecutive sync states of a database are logged once per window, along with
+  /// the number of the skipped ones. It only applies to the databases opened afterwards.
+  pub fn set_sync_state_log_window(&self, window: Duration) {
+    *self.sync_state_log_window.write() = window;
+  }
+
+  /// Replaces the clock that provides the created and last modified times of the rows. It only
+  /// applies to the databases opened afterwards, use [DatabaseEditor::set_clock] for the opened
+  /// ones.
+  pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+    *self.clock.write() = clock;
+  }
+
+  /// Replaces the generator of the ids of the rows, fields and select options. It only applies to
+  /// the databases opened afterwards, use [DatabaseEditor::set_id_generator] for the opened ones.
+  pub fn set_id_generator(&self, id_generator: Arc<dyn IdGenerator>) {
+    *self.id_generator.write() = id_generator;
+  }
+
+  /// Indexes the rows of the databases by their primary values, which speeds up the lookups of the
+  /// rows by title, e.g. the relation fields that link to a large database, at the cost of memory.
+  /// It only applies to the databases opened afterwards, use
+  /// [DatabaseEditor::set_primary_value_index_enabled] for the opened ones.
+  pub fn set_primary_value_index_enabled(&self, is_enabled: bool) {
+    self
+      .is_primary_value_index_enabled
+      .store(is_enabled, Ordering::SeqCst);
+  }
+
   fn is_collab_exist(&self, uid: i64, collab_db: &Weak<CollabKVDB>, object_id: &str) -> bool {
     match collab_db.upgrade() {
       None => false,
@@ -79,6 +192,7 @@ impl DatabaseManager {
     // 2. Release all existing editors
     for (_, editor) in self.editors.lock().await.iter() {
       editor.close_all_views().await;
+      editor.close_observers();
     }
     self.editors.lock().await.clear();
     // 3. Clear the workspace database
@@ -92,6 +206,8 @@ impl DatabaseManager {
       user: self.user.clone(),
       collab_builder: self.collab_builder.clone(),
       cloud_service: self.cloud_service.clone(),
+      row_sync_notifier: self.row_sync_notifier.clone(),
+      remote_row_edit_notifier: self.remote_row_edit_notifier.clone(),
     };
     let config = CollabPersistenceConfig::new().snapshot_per_update(100);
 
@@ -144,9 +260,29 @@ impl DatabaseManager {
     let workspace_database =
       WorkspaceDatabase::open(uid, collab, collab_db, config, collab_builder);
     *self.workspace_database.write().await = Some(Arc::new(workspace_database));
+
+    // The role is fetched from the server, so don't block the initialization on it.
+    let user = self.user.clone();
+    let is_read_only = self.is_read_only.clone();
+    af_spawn(async move {
+      refresh_workspace_role(user.as_ref(), &is_read_only).await;
+    });
     Ok(())
   }
 
+  /// Fetches the role of the current user in the workspace and caches it. It's called when the
+  /// workspace is opened and whenever the members of the workspace or their roles are changed.
+  pub async fn refresh_workspace_role(&self) {
+    refresh_workspace_role(self.user.as_ref(), &self.is_read_only).await;
+  }
+
+  /// Overrides the cached role. The editing methods of the [DatabaseEditor] return
+  /// [ErrorCode::NotEnoughPermissions](flowy_error::ErrorCode::NotEnoughPermissions) if
+  /// `is_read_only` is true.
+  pub fn set_workspace_read_only(&self, is_read_only: bool) {
+    self.is_read_only.store(is_read_only, Ordering::SeqCst);
+  }
+
   #[instrument(
     name = "database_initialize_with_new_user",
     level = "debug",
@@ -158,7 +294,14 @@ impl DatabaseManager {
     Ok(())
   }
 
-  pub async fn get_database_inline_view_id(&self, database_id: &str) -> FlowyResult<String> {
+  /// Returns the id of the inline view of the database, which can be used to open a database
+  /// that is only known by its id, e.g. the related database of a relation field. The database
+  /// doesn't need to be opened.
+  pub async fn get_inline_view_id(&self, database_id: &str) -> FlowyResult<String> {
+    if let Some(editor) = self.editors.lock().await.get(database_id) {
+      return Ok(editor.get_inline_view_id());
+    }
+
     let wdb = self.get_database_indexer().await?;
     let database_collab = wdb.get_database(database_id).await.ok_or_else(|| {
       FlowyError::record_not_found().with_context(format!("The database:{} not found", database_id))
@@ -204,6 +347,7 @@ impl DatabaseManager {
 
   pub async fn get_database(&self, database_id: &str) -> FlowyResult<Arc<DatabaseEditor>> {
     if let Some(editor) = self.editors.lock().await.get(database_id).cloned() {
+      editor.touch();
       return Ok(editor);
     }
     // TODO(nathan): refactor the get_database that split the database creation and database opening.
@@ -212,14 +356,38 @@ impl DatabaseManager {
 
   pub async fn open_database(&self, database_id: &str) -> FlowyResult<Arc<DatabaseEditor>> {
     trace!("open database editor:{}", database_id);
-    let database = self
-      .get_database_indexer()
-      .await?
+    let workspace_database = self.get_database_indexer().await?;
+    let database = workspace_database
       .get_database(database_id)
       .await
       .ok_or_else(|| FlowyError::collab_not_sync().with_context("open database error"))?;
 
-    let editor = Arc::new(DatabaseEditor::new(database, self.task_scheduler.clone()).await?);
+    let uid = self.user.user_id()?;
+    let is_sync_enabled = self.collab_builder.provider_type() != CollabPluginProviderType::Local;
+    let editor = Arc::new(
+      DatabaseEditor::new(
+        database,
+        self.task_scheduler.clone(),
+        DatabaseEditorConfig {
+          uid,
+          is_sync_enabled,
+          row_sync_notifier: self.row_sync_notifier.clone(),
+          remote_row_edit_notifier: self.remote_row_edit_notifier.clone(),
+          is_read_only: self.is_read_only.clone(),
+          open_profiles: self.open_profiles.clone(),
+          workspace_database: Arc::downgrade(&workspace_database),
+          low_memory_profile: self.low_memory_profile,
+          document_service: self.document_service.clone(),
+          sync_state_log_window: *self.sync_state_log_window.read(),
+          clock: self.clock.read().clone(),
+          id_generator: self.id_generator.read().clone(),
+          is_primary_value_index_enabled: self
+            .is_primary_value_index_enabled
+            .load(Ordering::SeqCst),
+        },
+      )
+      .await?,
+    );
     self
       .editors
       .lock()
@@ -232,6 +400,8 @@ impl DatabaseManager {
     let view_id = view_id.as_ref();
     let wdb = self.get_database_indexer().await?;
     if let Some(database_id) = wdb.get_database_id_with_view_id(view_id) {
+      let editor = self.get_database(&database_id).await?;
+      editor.did_open_view(view_id);
       if let Some(database) = wdb.open_database(&database_id) {
         if let Some(lock_database) = database.try_lock() {
           if let Some(lock_collab) = lock_database.get_collab().try_lock() {
@@ -244,6 +414,13 @@ impl DatabaseManager {
     Ok(())
   }
 
+  /// Returns the profiles of the last opened views, from the oldest to the most recent. Each
+  /// profile records the time spent in getting the row orders, loading, filtering and sorting the
+  /// rows of the view when it was opened.
+  pub fn last_open_profiles(&self) -> Vec<OpenProfile> {
+    self.open_profiles.to_vec()
+  }
+
   pub async fn close_database_view<T: AsRef<str>>(&self, view_id: T) -> FlowyResult<()> {
     let view_id = view_id.as_ref();
     let wdb = self.get_database_indexer().await?;
@@ -252,13 +429,16 @@ impl DatabaseManager {
       let mut editors = self.editors.lock().await;
       let mut should_remove = false;
       if let Some(editor) = editors.get(&database_id) {
+        editor.did_close_view(view_id);
         editor.close_view(view_id).await;
         should_remove = editor.num_views().await == 0;
       }
 
       if should_remove {
         trace!("remove database editor:{}", database_id);
-        editors.remove(&database_id);
+        if let Some(editor) = editors.remove(&database_id) {
+          editor.close_observers();
+        }
         wdb.close_database(&database_id);
       }
     }
@@ -266,6 +446,45 @@ impl DatabaseManager {
     Ok(())
   }
 
+  /// Closes the editors of the databases that have no opening views and haven't been used for
+  /// `idle_timeout`, so their rows are released from memory. The editors that are still held
+  /// outside the manager are kept. Returns the number of closed databases.
+  pub async fn close_idle_databases(&self, idle_timeout: Duration) -> usize {
+    let wdb = match self.get_database_indexer().await {
+      Ok(wdb) => wdb,
+      Err(_) => return 0,
+    };
+    let mut editors = self.editors.lock().await;
+    let idle_database_ids = editors
+      .iter()
+      .filter(|(_, editor)| {
+        !editor.has_opened_views()
+          && Arc::strong_count(editor) == 1
+          && editor.idle_duration() >= idle_timeout
+      })
+      .map(|(database_id, _)| database_id.clone())
+      .collect::<Vec<String>>();
+
+    for database_id in idle_database_ids.iter() {
+      if let Some(editor) = editors.remove(database_id) {
+        trace!("close idle database editor:{}", database_id);
+        editor.close_all_views().await;
+        editor.close_observers();
+        wdb.close_database(database_id);
+      }
+    }
+    self
+      .num_of_closed_idle_databases
+      .fetch_add(idle_database_ids.len(), Ordering::SeqCst);
+    idle_database_ids.len()
+  }
+
+  /// Returns the number of the databases closed by [Self::close_idle_databases] since the app was
+  /// launched.
+  pub fn num_of_closed_idle_databases(&self) -> usize {
+    self.num_of_closed_idle_databases.load(Ordering::SeqCst)
+  }
+
   pub async fn delete_database_view(&self, view_id: &str) -> FlowyResult<()> {
     let database = self.get_database_with_view_id(view_id).await?;
     let _ = database.delete_database_view(view_id).await?;
@@ -311,6 +530,70 @@ impl DatabaseManager {
     Ok(())
   }
 
+  /// Creates the clone of a database returned by [DatabaseEditor::clone_structure].
+  pub async fn create_database_with_clone_params(
+    &self,
+    params: CloneDatabaseParams,
+  ) -> FlowyResult<()> {
+    let wdb = self.get_database_indexer().await?;
+    create_cloned_database(&wdb, params)
+  }
+
+  /// Clones the structure of the databases of the given views together, without their rows.
+  /// The relation fields that point at one of the cloned databases are rewritten to point at its
+  /// clone, so the clones relate to each other the same way the originals do. The other relation
+  /// fields keep pointing at the original databases.
+  ///
+  /// Returns the mapping from the original ids to the cloned ones, in the order of the views.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn clone_databases_for_template(
+    &self,
+    view_ids: Vec<String>,
+  ) -> FlowyResult<Vec<CloneIdMap>> {
+    let mut clones = Vec::with_capacity(view_ids.len());
+    for view_id in view_ids.iter() {
+      let editor = self.get_database_with_view_id(view_id).await?;
+      clones.push(editor.clone_structure_with_id_map().await?);
+    }
+
+    let database_ids = clones
+      .iter()
+      .map(|(_, id_map)| {
+        (
+          id_map.old_database_id.clone(),
+          id_map.new_database_id.clone(),
+        )
+      })
+      .collect::<HashMap<String, String>>();
+
+    let wdb = self.get_database_indexer().await?;
+    let mut id_maps = Vec::with_capacity(clones.len());
+    for (mut params, id_map) in clones {
+      params.params.fields = params
+        .params
+        .fields
+        .into_iter()
+        .map(|field| {
+          let type_option = field
+            .get_type_option::<RelationTypeOption>(FieldType::Relation)
+            .and_then(|type_option| database_ids.get(&type_option.database_id));
+          match type_option {
+            Some(database_id) => {
+              let type_option = RelationTypeOption {
+                database_id: database_id.clone(),
+              };
+              field.with_type_option_data(FieldType::Relation, type_option.into())
+            },
+            None => field,
+          }
+        })
+        .collect();
+      create_cloned_database(&wdb, params)?;
+      id_maps.push(id_map);
+    }
+    Ok(id_maps)
+  }
+
   /// A linked view is a view that is linked to existing database.
   #[tracing::instrument(level = "trace", skip(self), err)]
   pub async fn create_linked_view(
@@ -322,6 +605,10 @@ impl DatabaseManager {
   ) -> FlowyResult<()> {
     let wdb = self.get_database_indexer().await?;
     let mut params = CreateViewParams::new(database_id.clone(), database_view_id, name, layout);
+    let editor = self.editors.lock().await.get(&database_id).cloned();
+    if let Some(editor) = editor {
+      params.field_settings = editor.get_field_settings_for_new_view(layout).into();
+    }
     if let Some(database) = wdb.get_database(&database_id).await {
       let (field, layout_setting) = DatabaseLayoutDepsResolver::new(database, layout)
         .resolve_deps_when_create_database_linked_view();
@@ -341,9 +628,10 @@ impl DatabaseManager {
     view_id: String,
     content: String,
     format: CSVFormat,
+    options: CSVImportOptions,
   ) -> FlowyResult<ImportResult> {
     let params = tokio::task::spawn_blocking(move || {
-      CSVImporter.import_csv_from_string(view_id, content, format)
+      CSVImporter.import_csv_from_string(view_id, content, format, options)
     })
     .await
     .map_err(internal_error)??;
@@ -364,9 +652,42 @@ impl DatabaseManager {
     Ok(())
   }
 
-  pub async fn export_csv(&self, view_id: &str, style: CSVFormat) -> FlowyResult<String> {
+  pub async fn export_csv(
+    &self,
+    view_id: &str,
+    style: CSVFormat,
+    options: CSVExportOptions,
+  ) -> FlowyResult<String> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    database.export_csv(style, options).await
+  }
+
+  pub async fn export_csv_to_file(
+    &self,
+    view_id: &str,
+    style: CSVFormat,
+    options: CSVExportOptions,
+    path: PathBuf,
+    write_checksum_file: bool,
+  ) -> FlowyResult<String> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    database
+      .export_csv_to_file(style, options, path, write_checksum_file)
+      .await
+  }
+
+  pub async fn export_view_settings(&self, view_id: &str) -> FlowyResult<String> {
     let database = self.get_database_with_view_id(view_id).await?;
-    database.export_csv(style).await
+    database.export_view_settings(view_id).await
+  }
+
+  pub async fn import_view_settings(
+    &self,
+    view_id: &str,
+    json: &str,
+  ) -> FlowyResult<ImportViewSettingsResult> {
+    let database = self.get_database_with_view_id(view_id).await?;
+    database.import_view_settings(view_id, json).await
   }
 
   pub async fn update_database_layout(
@@ -460,10 +781,36 @@ impl DatabaseManager {
   }
 }
 
+/// Caches the role of the current user. The last known role is kept when the role can't be
+/// fetched, e.g. when the user is offline. A user that the server doesn't list as a member of the
+/// workspace is read-only, see [DatabaseUser::is_read_only_member].
+async fn refresh_workspace_role(user: &dyn DatabaseUser, is_read_only: &AtomicBool) {
+  match user.is_read_only_member().await {
+    Ok(value) => is_read_only.store(value, Ordering::SeqCst),
+    Err(err) => error!(
+      "Failed to get the workspace role, keep the last known one: {}",
+      err
+    ),
+  }
+}
+
+fn create_cloned_database(wdb: &WorkspaceDatabase, params: CloneDatabaseParams) -> FlowyResult<()> {
+  let database = wdb.create_database(params.params)?;
+  let database = database.lock();
+  for (view_id, calculations) in params.calculations {
+    for calculation in calculations {
+      database.update_calculation(&view_id, calculation);
+    }
+  }
+  Ok(())
+}
+
 struct UserDatabaseCollabServiceImpl {
   user: Arc<dyn DatabaseUser>,
   collab_builder: Arc<AppFlowyCollabBuilder>,
   cloud_service: Arc<dyn DatabaseCloudService>,
+  row_sync_notifier: RowSyncNotifier,
+  remote_row_edit_notifier: RemoteRowEditNotifier,
 }
 
 impl DatabaseCollabService for UserDatabaseCollabServiceImpl {
@@ -539,6 +886,41 @@ impl DatabaseCollabService for UserDatabaseCollabServiceImpl {
       collab_raw_data,
       CollabBuilderConfig::default().sync_enable(true),
     )?;
+    if object_type == CollabType::DatabaseRow {
+      subscribe_row_sync_state(object_id, &collab, self.row_sync_notifier.clone());
+      let lock_collab = collab.lock();
+      let plugin = RemoteRowEditPlugin::new(
+        RowId::from(object_id.to_string()),
+        lock_collab.origin().clone(),
+        self.remote_row_edit_notifier.clone(),
+      );
+      lock_collab.add_plugin(Box::new(plugin));
+    }
     Ok(collab)
   }
 }
+
+/// The rows are separate collabs, so the editors can't learn that the edits of a row are synced
+/// from the sync state of the database collab. The collab is kept in the [RowSyncNotifier] until
+/// it's dropped, so the editors can retry its sync.
+fn subscribe_row_sync_state(
+  row_id: &str,
+  collab: &Arc<MutexCollab>,
+  row_sync_notifier: RowSyncNotifier,
+) {
+  let row_id = RowId::from(row_id.to_string());
+  let weak_collab = Arc::downgrade(collab);
+  let mut sync_state_stream = collab.lock().subscribe_sync_state();
+  row_sync_notifier.did_build_row_collab(row_id.clone(), collab);
+  af_spawn(async move {
+    while let Some(sync_state) = sync_state_stream.next().await {
+      if weak_collab.upgrade().is_none() {
+        break;
+      }
+      if matches!(sync_state, SyncState::SyncFinished) {
+        row_sync_notifier.notify_sync_finished(row_id.clone());
+      }
+    }
+    row_sync_notifier.did_drop_row_collab(&row_id, &weak_collab);
+  });
+}
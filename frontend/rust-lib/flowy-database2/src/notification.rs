@@ -52,6 +52,16 @@ pub enum DatabaseNotification {
   DidUpdateFieldSettings = 86,
   // Trigger when Calculation changed
   DidUpdateCalculation = 87,
+  // Trigger when the number of the unsynced edits becomes non-zero or zero
+  DidUpdateUnsyncedEditCount = 88,
+  // Trigger when the settings of many fields are changed at once
+  DidUpdateFieldSettingsBatch = 89,
+  // Trigger when the rows of a view fail to load
+  DidReceiveDatabaseError = 90,
+  // Trigger when the position of the new rows of a view is changed
+  DidUpdateNewRowPosition = 91,
+  // Trigger when the settings and the row order of a view are reset
+  DidResetView = 92,
 }
 
 impl std::convert::From<DatabaseNotification> for i32 {
@@ -84,6 +94,11 @@ impl std::convert::From<i32> for DatabaseNotification {
       84 => DatabaseNotification::DidMoveDatabaseViewToTrash,
       86 => DatabaseNotification::DidUpdateFieldSettings,
       87 => DatabaseNotification::DidUpdateCalculation,
+      88 => DatabaseNotification::DidUpdateUnsyncedEditCount,
+      89 => DatabaseNotification::DidUpdateFieldSettingsBatch,
+      90 => DatabaseNotification::DidReceiveDatabaseError,
+      91 => DatabaseNotification::DidUpdateNewRowPosition,
+      92 => DatabaseNotification::DidResetView,
       _ => DatabaseNotification::Unknown,
     }
   }
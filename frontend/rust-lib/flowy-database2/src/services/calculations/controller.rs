@@ -268,6 +268,32 @@ impl CalculationsController {
     }
   }
 
+  /// Recomputes every calculation of the view over all the rows and overwrites the stored values
+  /// that are stale. Returns the notification of all the recomputed calculations, or None if the
+  /// view has no calculations.
+  pub async fn recompute_all_calculations(&self) -> Option<CalculationChangesetNotificationPB> {
+    let calculations = self.delegate.get_all_calculations(&self.view_id).await;
+    let mut updates = vec![];
+    for calculation in calculations.iter() {
+      match self.get_updated_calculation(calculation.clone()).await {
+        Some(update) => {
+          updates.push(CalculationPB::from(&update));
+          self.delegate.update_calculation(&self.view_id, update);
+        },
+        None => updates.push(CalculationPB::from(calculation.as_ref())),
+      }
+    }
+
+    if updates.is_empty() {
+      None
+    } else {
+      Some(CalculationChangesetNotificationPB::from_update(
+        &self.view_id,
+        updates,
+      ))
+    }
+  }
+
   async fn get_updated_calculation(&self, calculation: Arc<Calculation>) -> Option<Calculation> {
     let field_cells = self
       .delegate
@@ -128,3 +128,16 @@ impl CalculationChangeset {
     }
   }
 }
+
+/// The aggregations that are computed on demand by
+/// [crate::services::database::DatabaseEditor::aggregate_field], without saving a calculation to
+/// the view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregationKind {
+  Sum,
+  Min,
+  Max,
+  Average,
+  /// The number of the cells that have a number.
+  Count,
+}
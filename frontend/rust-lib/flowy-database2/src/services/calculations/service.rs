@@ -4,6 +4,7 @@ use collab_database::fields::Field;
 use collab_database::rows::RowCell;
 
 use crate::entities::CalculationType;
+use crate::services::calculations::AggregationKind;
 use crate::services::field::TypeOptionCellExt;
 
 pub struct CalculationsService {}
@@ -33,6 +34,24 @@ impl CalculationsService {
     }
   }
 
+  /// Aggregates the numbers of the cells. The empty and non-numeric cells are skipped. Returns
+  /// None for the min, max or average of the cells that have no number.
+  pub fn aggregate(
+    &self,
+    field: &Field,
+    kind: AggregationKind,
+    row_cells: Vec<Arc<RowCell>>,
+  ) -> Option<f64> {
+    self.reduce_values_f64(field, row_cells, |values| match kind {
+      AggregationKind::Sum => Some(values.iter().sum()),
+      AggregationKind::Count => Some(values.len() as f64),
+      AggregationKind::Min => values.iter().copied().min_by(|a, b| a.total_cmp(b)),
+      AggregationKind::Max => values.iter().copied().max_by(|a, b| a.total_cmp(b)),
+      AggregationKind::Average if values.is_empty() => None,
+      AggregationKind::Average => Some(values.iter().sum::<f64>() / values.len() as f64),
+    })
+  }
+
   fn calculate_average(&self, field: &Field, row_cells: Vec<Arc<RowCell>>) -> String {
     let mut sum = 0.0;
     let mut len = 0.0;
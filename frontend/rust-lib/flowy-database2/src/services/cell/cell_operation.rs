@@ -127,6 +127,14 @@ pub fn get_cell_protobuf(
 /// * `field`: used to get the corresponding TypeOption for the specified field type.
 ///
 pub fn stringify_cell(cell: &Cell, field: &Field) -> String {
+  // The cells of a custom field type can only be read by the handler of the field
+  if !is_built_in_field_type(field.field_type) {
+    return TypeOptionCellExt::new(field, None)
+      .get_type_option_cell_data_handler()
+      .map(|handler| handler.handle_stringify_cell(cell, field))
+      .unwrap_or_default();
+  }
+
   if let Some(field_type_of_cell) = get_field_type_from_cell::<FieldType>(cell) {
     TypeOptionCellExt::new(field, None)
       .get_type_option_cell_data_handler_with_field_type(field_type_of_cell)
@@ -262,6 +270,11 @@ impl<'a> CellBuilder<'a> {
           FieldType::Summary => {
             cells.insert(field_id, insert_text_cell(cell_str, field));
           },
+          FieldType::LastEditedBy | FieldType::CreatedBy => {
+            tracing::warn!(
+              "Shouldn't insert cell data to cell whose field type is LastEditedBy or CreatedBy"
+            );
+          },
         }
       }
     }
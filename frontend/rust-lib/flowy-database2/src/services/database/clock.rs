@@ -0,0 +1,50 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use lib_infra::util::timestamp;
+
+/// Provides the current time to the [crate::services::database::DatabaseEditor]. The created and
+/// last modified times of the rows are read from it, so the tests can freeze or advance the time
+/// instead of sleeping.
+pub trait Clock: Send + Sync {
+  /// The current time as a timestamp in seconds.
+  fn now(&self) -> i64;
+}
+
+/// The default [Clock], which reads the system time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  fn now(&self) -> i64 {
+    timestamp()
+  }
+}
+
+/// A [Clock] that only moves when it's told to.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+  now: AtomicI64,
+}
+
+impl ManualClock {
+  pub fn new(now: i64) -> Self {
+    Self {
+      now: AtomicI64::new(now),
+    }
+  }
+
+  pub fn set(&self, now: i64) {
+    self.now.store(now, Ordering::SeqCst);
+  }
+
+  /// Moves the time forward by the given number of seconds.
+  pub fn advance(&self, seconds: i64) {
+    self.now.fetch_add(seconds, Ordering::SeqCst);
+  }
+}
+
+impl Clock for ManualClock {
+  fn now(&self) -> i64 {
+    self.now.load(Ordering::SeqCst)
+  }
+}
@@ -1,42 +1,102 @@
 use crate::entities::*;
+use crate::manager::{DatabaseDocumentService, DatabaseDocumentServiceCell};
 use crate::notification::{send_notification, DatabaseNotification};
-use crate::services::calculations::Calculation;
-use crate::services::cell::{apply_cell_changeset, get_cell_protobuf, CellCache};
+use crate::services::calculations::{AggregationKind, Calculation, CalculationsService};
+use crate::services::cell::{
+  apply_cell_changeset, get_cell_protobuf, insert_select_option_cell, stringify_cell, CellCache,
+};
 use crate::services::database::database_observe::*;
-use crate::services::database::util::database_view_setting_pb_from_view;
+use crate::services::database::json_data::{
+  cell_to_json_value, json_select_option_names, json_value_to_cell, match_json_fields,
+  DatabaseJsonData, DatabaseJsonField, ImportJsonResult,
+};
+use crate::services::database::merge_rows::merge_row_cells;
+use crate::services::database::row_metas::{
+  ensure_row_not_locked, get_all_row_users, get_row_users, is_row_locked, remove_row_metas,
+  row_meta_pb, set_row_locked, set_rows_created_by, set_rows_last_edited_by,
+};
+use crate::services::database::util::{
+  create_view_params_from_view, database_view_setting_pb_from_view, move_as_block, replace_text,
+};
+use crate::services::database::view_settings::{
+  FieldIdResolver, ImportViewSettingsResult, ViewSettingsField, ViewSettingsSnapshot,
+};
+use crate::services::database::{
+  Clock, CloneDatabaseParams, CloneIdMap, DatabaseErrorReporter, FieldUsage, IdGenerator, IdKind,
+  LoadState, LoadTracker, LowMemoryProfile, MergeRowsStrategy, OpenProfiles, PrimaryValueIndex,
+  RelatedRowsScan, RemoteRowEditNotifier, RowLoadDelegate, RowSyncNotifier, SchemaVersion,
+  UnsyncedEdits,
+};
 use crate::services::database_view::{
   DatabaseViewChanged, DatabaseViewEditor, DatabaseViewOperation, DatabaseViews, EditorByViewId,
+  GroupMetrics,
 };
 use crate::services::field::{
-  default_type_option_data_from_type, select_type_option_from_field, transform_type_option,
-  type_option_data_from_pb, ChecklistCellChangeset, RelationTypeOption, SelectOptionCellChangeset,
-  StringCellData, TimestampCellData, TimestampCellDataWrapper, TypeOptionCellDataHandler,
-  TypeOptionCellExt,
+  default_type_option_data_from_type, field_description_data, insert_select_options,
+  is_system_field, select_type_option_from_field, system_field_data, timestamp_from_local_datetime,
+  transform_type_option, type_option_data_from_pb, user_cell_of_row, validate_field_cell,
+  validate_field_text, CellMigrationStrategy, ChecklistCellChangeset, DateCellChangeset,
+  RelationTypeOption, RichTextTypeOption, RowUsers, RowUsersByRowId, SelectOption,
+  SelectOptionCellChangeset, StringCellData, TimestampCellData, TimestampCellDataWrapper,
+  TypeOptionCellDataHandler, TypeOptionCellExt, CELL_DATA, FIELD_DESCRIPTION_KEY, FIELD_SYSTEM_KEY,
 };
-use crate::services::field_settings::{default_field_settings_by_layout_map, FieldSettings};
-use crate::services::filter::{Filter, FilterChangeset};
+use crate::services::field_settings::{
+  default_field_settings_by_layout_map, field_settings_for_field, get_default_field_settings,
+  save_default_field_settings, system_field_settings_by_layout_map, DefaultFieldSettingsByLayout,
+  FieldSettings,
+};
+use crate::services::filter::{Filter, FilterChangeset, FilterInner};
 use crate::services::group::{default_group_setting, GroupChangeset, GroupSetting, RowChangeset};
-use crate::services::share::csv::{CSVExport, CSVFormat};
-use crate::services::sort::Sort;
+use crate::services::setting::{CalendarLayoutSetting, GridLayoutSetting, NewRowPosition};
+use crate::services::share::csv::{
+  estimate_total_rows, CSVBatchReader, CSVExport, CSVExportOptions, CSVFormat, CSVImportError,
+  CSVImportProgress, CSVStreamImportOptions, CSVStreamImportResult, ROW_ID_COLUMN,
+  ROW_METADATA_COLUMNS,
+};
+use crate::services::sort::{Sort, SortCondition};
 use crate::utils::cache::AnyTypeCache;
-use collab_database::database::MutexDatabase;
+use collab::core::any_map::AnyMapExtension;
+use collab_database::database::{
+  gen_database_calculation_id, gen_database_id, gen_database_view_id, gen_field_id, Database,
+  MutexDatabase,
+};
 use collab_database::fields::{Field, TypeOptionData};
-use collab_database::rows::{Cell, Cells, Row, RowCell, RowDetail, RowId};
+use collab_database::rows::{new_cell_builder, Cell, Cells, Row, RowCell, RowDetail, RowId};
 use collab_database::views::{
-  DatabaseLayout, DatabaseView, FilterMap, LayoutSetting, OrderObjectPosition,
+  CalculationMap, CreateDatabaseParams, DatabaseLayout, DatabaseView, FieldSettingsMap, FilterMap,
+  GroupSettingMap, LayoutSetting, OrderObjectPosition, RowOrder,
 };
+use collab_database::workspace_database::WorkspaceDatabase;
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use flowy_notification::DebounceNotificationSender;
+use indexmap::IndexMap;
 use lib_infra::box_any::BoxAny;
 use lib_infra::future::{to_fut, Fut, FutureResult};
 use lib_infra::priority_task::TaskDispatcher;
-use lib_infra::util::timestamp;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock};
-use tracing::{event, instrument, warn};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio_util::sync::CancellationToken;
+use tracing::{event, info, instrument, warn};
+
+/// The maximum number of the cells that can be updated by one bulk operation.
+const MAX_BULK_UPDATE_CELLS: usize = 10_000;
+
+/// The maximum number of the rows that are scanned when looking for duplicates.
+const MAX_DUPLICATE_SCAN_ROWS: usize = 10_000;
+
+/// The number of the rows read at a time by [DatabaseEditor::scan_related_rows]. The scan can only
+/// be cancelled between the chunks.
+const RELATED_ROWS_SCAN_CHUNK_SIZE: usize = 100;
+
+/// The number of the rows read at a time when the rows of a view are loaded. Each chunk is read in
+/// a blocking task, so the asynchronous runtime isn't blocked by a large view.
+const ROW_LOAD_CHUNK_SIZE: usize = 10;
 
-#[derive(Clone)]
 pub struct DatabaseEditor {
   database: Arc<MutexDatabase>,
   pub cell_cache: CellCache,
@@ -44,23 +104,111 @@ pub struct DatabaseEditor {
   #[allow(dead_code)]
   /// Used to send notification to the frontend.
   notification_sender: Arc<DebounceNotificationSender>,
+  /// The id of the current user. It's recorded as the creator or the last editor of the rows.
+  uid: i64,
+  unsynced_edits: Arc<UnsyncedEdits>,
+  /// Shared with the `DatabaseManager`. It's true if the current user's role in the workspace
+  /// doesn't allow editing.
+  is_read_only: Arc<AtomicBool>,
+  /// Shared with the `DatabaseManager`. The profiles of loading the rows when a view is opened.
+  open_profiles: Arc<OpenProfiles>,
+  /// Updated every time the `DatabaseManager` hands out the editor. Used to close the editors that
+  /// are idle.
+  last_used_at: parking_lot::Mutex<Instant>,
+  /// Limits the memory used by the editor if it's set. See [LowMemoryProfile].
+  low_memory_profile: Option<LowMemoryProfile>,
+  /// Shared with the views and the field observer. See [Self::get_schema].
+  schema_version: Arc<SchemaVersion>,
+  /// Shared with the `DatabaseManager`. Used to convert the documents to the rows and back.
+  document_service: DatabaseDocumentServiceCell,
+  /// See [Self::load_state].
+  load_tracker: LoadTracker,
+  /// The ids of the views that are opened by the UI. See [Self::has_opened_views].
+  opened_view_ids: parking_lot::Mutex<HashSet<String>>,
+  /// See [Self::set_clock].
+  clock: parking_lot::RwLock<Arc<dyn Clock>>,
+  /// See [Self::set_id_generator].
+  id_generator: parking_lot::RwLock<Arc<dyn IdGenerator>>,
+  /// Shared with the rows observer. See [Self::find_rows_by_primary_value].
+  primary_value_index: Arc<PrimaryValueIndex>,
+  /// Shared with the views. See [Self::subscribe_errors].
+  error_reporter: Arc<DatabaseErrorReporter>,
+  /// Shared with the views. See [Self::set_row_load_delegate].
+  row_load_delegate: RowLoadDelegateCell,
+  /// See [Self::close_observers].
+  observers: Arc<DatabaseObservers>,
+}
+
+/// The state and the settings that the `DatabaseManager` shares with the editors it opens.
+pub struct DatabaseEditorConfig {
+  pub uid: i64,
+  /// False if the database is only stored on the device, so there are no unsynced edits.
+  pub is_sync_enabled: bool,
+  pub row_sync_notifier: RowSyncNotifier,
+  pub remote_row_edit_notifier: RemoteRowEditNotifier,
+  pub is_read_only: Arc<AtomicBool>,
+  pub open_profiles: Arc<OpenProfiles>,
+  pub workspace_database: Weak<WorkspaceDatabase>,
+  pub low_memory_profile: Option<LowMemoryProfile>,
+  pub document_service: DatabaseDocumentServiceCell,
+  pub sync_state_log_window: Duration,
+  pub clock: Arc<dyn Clock>,
+  pub id_generator: Arc<dyn IdGenerator>,
+  pub is_primary_value_index_enabled: bool,
 }
 
 impl DatabaseEditor {
   pub async fn new(
     database: Arc<MutexDatabase>,
     task_scheduler: Arc<RwLock<TaskDispatcher>>,
+    config: DatabaseEditorConfig,
   ) -> FlowyResult<Self> {
+    let DatabaseEditorConfig {
+      uid,
+      is_sync_enabled,
+      row_sync_notifier,
+      remote_row_edit_notifier,
+      is_read_only,
+      open_profiles,
+      workspace_database,
+      low_memory_profile,
+      document_service,
+      sync_state_log_window,
+      clock,
+      id_generator,
+      is_primary_value_index_enabled,
+    } = config;
     let notification_sender = Arc::new(DebounceNotificationSender::new(200));
-    let cell_cache = AnyTypeCache::<u64>::new();
+    let cell_cache = AnyTypeCache::<u64>::with_capacity(
+      low_memory_profile.map(|profile| profile.cell_cache_capacity),
+    );
     let database_id = database.lock().get_database_id();
+    let unsynced_edits = Arc::new(UnsyncedEdits::new(is_sync_enabled));
+    let schema_version = Arc::new(SchemaVersion::default());
+    let primary_value_index = Arc::new(PrimaryValueIndex::new(is_primary_value_index_enabled));
+    let error_reporter = Arc::new(DatabaseErrorReporter::new());
+    let row_load_delegate = RowLoadDelegateCell::default();
+    let observers = Arc::new(DatabaseObservers::default());
 
     // Receive database sync state and send to frontend via the notification
-    observe_sync_state(&database_id, &database).await;
-    // observe_view_change(&database_id, &database).await;
-    // observe_field_change(&database_id, &database).await;
-    observe_rows_change(&database_id, &database, &notification_sender).await;
-    // observe_block_event(&database_id, &database).await;
+    observers.add(
+      observe_sync_state(
+        &database_id,
+        &database,
+        &unsynced_edits,
+        sync_state_log_window,
+      )
+      .await,
+    );
+    observers.add(
+      observe_row_sync_state(&database_id, &database, &unsynced_edits, &row_sync_notifier).await,
+    );
+    observers
+      .add(observe_remote_row_edits(&database_id, &database, &remote_row_edit_notifier).await);
+    // observers.add(observe_view_change(&database_id, &database).await);
+    // Send the field changes made by the collaborators to the frontend
+    observers.add(observe_field_change(&database_id, &database, &schema_version).await);
+    // observers.add(observe_block_event(&database_id, &database).await);
 
     // Used to cache the view of the database for fast access.
     let editor_by_view_id = Arc::new(RwLock::new(EditorByViewId::default()));
@@ -69,6 +217,10 @@ impl DatabaseEditor {
       task_scheduler: task_scheduler.clone(),
       cell_cache: cell_cache.clone(),
       editor_by_view_id: editor_by_view_id.clone(),
+      workspace_database,
+      schema_version: schema_version.clone(),
+      error_reporter: error_reporter.clone(),
+      row_load_delegate: row_load_delegate.clone(),
     });
 
     let database_views = Arc::new(
@@ -80,15 +232,123 @@ impl DatabaseEditor {
       )
       .await?,
     );
+    observers.add(
+      observe_rows_change(
+        &database_id,
+        &database,
+        &database_views,
+        &notification_sender,
+        &primary_value_index,
+      )
+      .await,
+    );
 
     Ok(Self {
       database,
       cell_cache,
       database_views,
       notification_sender,
+      uid,
+      unsynced_edits,
+      is_read_only,
+      open_profiles,
+      last_used_at: parking_lot::Mutex::new(Instant::now()),
+      low_memory_profile,
+      schema_version,
+      document_service,
+      load_tracker: Default::default(),
+      opened_view_ids: Default::default(),
+      clock: parking_lot::RwLock::new(clock),
+      id_generator: parking_lot::RwLock::new(id_generator),
+      primary_value_index,
+      error_reporter,
+      row_load_delegate,
+      observers,
     })
   }
 
+  /// Replaces the clock that provides the created and last modified times of the rows written by
+  /// this editor.
+  pub fn set_clock(&self, clock: Arc<dyn Clock>) {
+    *self.clock.write() = clock;
+  }
+
+  fn now(&self) -> i64 {
+    self.clock.read().now()
+  }
+
+  /// Replaces the generator of the ids of the rows, fields and select options created by this
+  /// editor.
+  pub fn set_id_generator(&self, id_generator: Arc<dyn IdGenerator>) {
+    *self.id_generator.write() = id_generator;
+  }
+
+  /// Returns [ErrorCode::RecordAlreadyExists] if the generated id is used by an existing row or by
+  /// a row generated before it.
+  fn next_row_ids(&self, database: &Database, num_of_rows: usize) -> FlowyResult<Vec<RowId>> {
+    let id_generator = self.id_generator.read().clone();
+    // Every row of the database is in the inline view. Its row orders are checked, so the rows
+    // don't have to be loaded.
+    let inline_view_id = database.get_inline_view_id();
+    let mut row_ids = Vec::with_capacity(num_of_rows);
+    for _ in 0..num_of_rows {
+      let row_id = RowId::from(id_generator.next_id(IdKind::Row));
+      if row_ids.contains(&row_id) || database.views.is_row_exist(&inline_view_id, &row_id) {
+        return Err(id_already_exists_error(row_id.as_str()));
+      }
+      row_ids.push(row_id);
+    }
+    Ok(row_ids)
+  }
+
+  fn next_row_id(&self, database: &Database) -> FlowyResult<RowId> {
+    let mut row_ids = self.next_row_ids(database, 1)?;
+    Ok(row_ids.remove(0))
+  }
+
+  fn next_field_id(&self, database: &Database) -> FlowyResult<String> {
+    let field_id = self.id_generator.read().next_id(IdKind::Field);
+    if database.fields.get_field(&field_id).is_some() {
+      return Err(id_already_exists_error(&field_id));
+    }
+    Ok(field_id)
+  }
+
+  /// Returns [ErrorCode::NotEnoughPermissions] if the current user is a read-only member of the
+  /// workspace. Every method that mutates the database calls it before making any change.
+  pub fn ensure_can_edit(&self) -> FlowyResult<()> {
+    if self.is_read_only.load(Ordering::SeqCst) {
+      return Err(FlowyError::new(
+        ErrorCode::NotEnoughPermissions,
+        "Read-only members can't edit the database",
+      ));
+    }
+    Ok(())
+  }
+
+  /// Returns the number of the cell writes that haven't been synced with the server yet. It's
+  /// always zero if the database doesn't sync with a server.
+  pub fn unsynced_edit_count(&self) -> usize {
+    self.unsynced_edits.count()
+  }
+
+  fn record_unsynced_row_edits<'a>(&self, row_ids: impl IntoIterator<Item = &'a RowId>) {
+    if self.unsynced_edits.record_row_edits(row_ids) {
+      self.notify_unsynced_edit_count();
+    }
+  }
+
+  fn record_unsynced_database_edits(&self, num_of_edits: usize) {
+    if self.unsynced_edits.record_database_edits(num_of_edits) {
+      self.notify_unsynced_edit_count();
+    }
+  }
+
+  fn notify_unsynced_edit_count(&self) {
+    let database_id = self.database.lock().get_database_id();
+    notify_unsynced_edit_count(&database_id, self.unsynced_edits.count());
+  }
+
   pub async fn close_view(&self, view_id: &str) {
     self.database_views.close_view(view_id).await;
   }
@@ -97,13 +357,71 @@ impl DatabaseEditor {
     self.database_views.num_editors().await
   }
 
+  pub(crate) fn touch(&self) {
+    *self.last_used_at.lock() = Instant::now();
+  }
+
+  /// Returns how long the editor hasn't been used.
+  pub(crate) fn idle_duration(&self) -> Duration {
+    self.last_used_at.lock().elapsed()
+  }
+
+  /// Stops observing the changes of the database. Called by the `DatabaseManager` when it closes
+  /// the database, the editor doesn't receive the changes made by the collaborators afterwards.
+  pub(crate) fn close_observers(&self) {
+    self.observers.abort_all();
+  }
+
+  /// Returns the number of the observers of the database that are still running. It drops to zero
+  /// shortly after the database is closed.
+  pub fn num_of_live_observers(&self) -> usize {
+    self.observers.num_of_live_tasks()
+  }
+
   #[tracing::instrument(level = "debug", skip_all)]
   pub async fn close_all_views(&self) {
+    self.opened_view_ids.lock().clear();
+    self.load_tracker.did_close();
     for view in self.database_views.editors().await {
       view.close().await;
     }
   }
 
+  /// Returns whether a view of the database is opened by the UI and whether its rows are loaded
+  /// by [Self::get_database_data]. The state goes back to [LoadState::NotOpened] when the views
+  /// are closed, e.g. when the workspace is switched in the middle of the load.
+  pub fn load_state(&self) -> LoadState {
+    self.load_tracker.state()
+  }
+
+  /// Called by the `DatabaseManager` when the UI opens a view of the database.
+  pub(crate) fn did_open_view(&self, view_id: &str) {
+    self.opened_view_ids.lock().insert(view_id.to_string());
+    self.load_tracker.did_open();
+  }
+
+  /// Called by the `DatabaseManager` when the UI closes a view of the database. The database is
+  /// closed once none of its views is opened.
+  pub(crate) fn did_close_view(&self, view_id: &str) {
+    let mut opened_view_ids = self.opened_view_ids.lock();
+    opened_view_ids.remove(view_id);
+    if opened_view_ids.is_empty() {
+      self.load_tracker.did_close();
+    }
+  }
+
+  /// Returns whether a view of the database is opened by the UI. The `DatabaseManager` never
+  /// closes the editor of such a database when it's idle.
+  pub(crate) fn has_opened_views(&self) -> bool {
+    !self.opened_view_ids.lock().is_empty()
+  }
+
+  /// Returns the id of the inline view, the view that is created along with the database. It's
+  /// read from the database itself, so none of the views needs to be opened.
+  pub fn get_inline_view_id(&self) -> String {
+    self.database.lock().get_inline_view_id()
+  }
+
   pub async fn get_layout_type(&self, view_id: &str) -> DatabaseLayout {
     let view = self.database_views.get_view_editor(view_id).await.ok();
     if let Some(editor) = view {
@@ -118,6 +436,7 @@ impl DatabaseEditor {
     view_id: &str,
     layout_type: DatabaseLayout,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(view_id).await?;
     view_editor.v_update_layout_type(layout_type).await?;
 
@@ -132,11 +451,30 @@ impl DatabaseEditor {
     Ok(view_editor.notifier.subscribe())
   }
 
+  /// Returns a receiver of the cell changes of the given field, including the changes made by
+  /// other clients. The receiver is closed when the view is closed.
+  pub async fn subscribe_cell_changed(
+    &self,
+    view_id: &str,
+    field_id: &str,
+  ) -> FlowyResult<broadcast::Receiver<CellChangesetNotifyPB>> {
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    Ok(view_editor.v_subscribe_cell_changed(field_id).await)
+  }
+
+  /// Returns a receiver of the errors that happen in the background, e.g. while the rows of a view
+  /// are loaded. The rows that fail to load are left out of the view instead of failing the whole
+  /// load, so the errors are the only way to tell that the view is incomplete.
+  pub fn subscribe_errors(&self) -> broadcast::Receiver<DatabaseErrorPB> {
+    self.error_reporter.subscribe()
+  }
+
   pub fn get_field(&self, field_id: &str) -> Option<Field> {
     self.database.lock().fields.get_field(field_id)
   }
 
   pub async fn set_group_by_field(&self, view_id: &str, field_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     {
       let database = self.database.lock();
       let field = database.fields.get_field(field_id);
@@ -154,6 +492,7 @@ impl DatabaseEditor {
   }
 
   pub async fn delete_group(&self, params: DeleteGroupParams) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(&params.view_id).await?;
     let changes = view_editor.v_delete_group(&params.group_id).await?;
 
@@ -173,6 +512,7 @@ impl DatabaseEditor {
   /// will be the reference view ids and the inline view id. Otherwise, the return value will
   /// be the view id.
   pub async fn delete_database_view(&self, view_id: &str) -> FlowyResult<Vec<String>> {
+    self.ensure_can_edit()?;
     Ok(self.database.lock().delete_view(view_id))
   }
 
@@ -181,6 +521,7 @@ impl DatabaseEditor {
     view_id: &str,
     changesets: Vec<GroupChangeset>,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(view_id).await?;
     view_editor.v_update_group(changesets).await?;
     Ok(())
@@ -191,24 +532,35 @@ impl DatabaseEditor {
     view_id: &str,
     changeset: FilterChangeset,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(view_id).await?;
     view_editor.v_modify_filters(changeset).await?;
     Ok(())
   }
 
   pub async fn create_or_update_sort(&self, params: UpdateSortPayloadPB) -> FlowyResult<Sort> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(&params.view_id).await?;
     let sort = view_editor.v_create_or_update_sort(params).await?;
     Ok(sort)
   }
 
+  /// Replaces all the sorts of the view, in the given order, and re-sorts the rows once.
+  pub async fn set_sorts(&self, params: SetSortsPayloadPB) -> FlowyResult<Vec<Sort>> {
+    self.ensure_can_edit()?;
+    let view_editor = self.database_views.get_view_editor(&params.view_id).await?;
+    view_editor.v_set_sorts(params.sorts).await
+  }
+
   pub async fn reorder_sort(&self, params: ReorderSortPayloadPB) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(&params.view_id).await?;
     view_editor.v_reorder_sort(params).await?;
     Ok(())
   }
 
   pub async fn delete_sort(&self, params: DeleteSortPayloadPB) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(&params.view_id).await?;
     view_editor.v_delete_sort(params).await?;
     Ok(())
@@ -223,12 +575,22 @@ impl DatabaseEditor {
   }
 
   pub async fn update_calculation(&self, update: UpdateCalculationChangesetPB) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(&update.view_id).await?;
     view_editor.v_update_calculations(update).await?;
     Ok(())
   }
 
+  /// Recomputes every calculation of the view over all its rows and overwrites the stored values.
+  /// The calculations are normally updated incrementally, so it fixes the values that are stale.
+  pub async fn recompute_all_calculations(&self, view_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    view_editor.v_recompute_all_calculations().await
+  }
+
   pub async fn remove_calculation(&self, remove: RemoveCalculationChangesetPB) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(&remove.view_id).await?;
     view_editor.v_remove_calculation(remove).await?;
     Ok(())
@@ -258,16 +620,38 @@ impl DatabaseEditor {
     }
   }
 
-  pub async fn delete_all_sorts(&self, view_id: &str) {
+  pub async fn delete_all_sorts(&self, view_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     if let Ok(view_editor) = self.database_views.get_view_editor(view_id).await {
       let _ = view_editor.v_delete_all_sorts().await;
     }
+    Ok(())
   }
 
   /// Returns a list of fields of the view.
-  /// If `field_ids` is not provided, all the fields will be returned in the order of the field that
-  /// defined in the view. Otherwise, the fields will be returned in the order of the `field_ids`.
+  /// If `field_ids` is not provided, all the fields except the system fields will be returned in
+  /// the order of the field that defined in the view. Otherwise, the fields will be returned in the
+  /// order of the `field_ids`, including the system fields.
   pub fn get_fields(&self, view_id: &str, field_ids: Option<Vec<String>>) -> Vec<Field> {
+    let is_default_listing = field_ids.is_none();
+    let fields = self.get_fields_including_system(view_id, field_ids);
+    if is_default_listing {
+      fields
+        .into_iter()
+        .filter(|field| !is_system_field(field))
+        .collect()
+    } else {
+      fields
+    }
+  }
+
+  /// Like [Self::get_fields], but the system fields are returned when `field_ids` is not provided
+  /// too. See [is_system_field].
+  pub fn get_fields_including_system(
+    &self,
+    view_id: &str,
+    field_ids: Option<Vec<String>>,
+  ) -> Vec<Field> {
     let database = self.database.lock();
     let field_ids = field_ids.unwrap_or_else(|| {
       database
@@ -280,19 +664,188 @@ impl DatabaseEditor {
     database.get_fields_in_view(view_id, Some(field_ids))
   }
 
+  /// Returns the fields of the view and the version of the schema, which is bumped whenever a
+  /// field of the database is created, deleted, moved or changed.
+  pub async fn get_schema(&self, view_id: &str) -> FlowyResult<DatabaseSchemaPB> {
+    let _ = self.database_views.get_view_editor(view_id).await?;
+    // The version is read first, so a change made while the fields are read bumps it again
+    let version = self.schema_version.get();
+    let fields = self
+      .get_fields(view_id, None)
+      .into_iter()
+      .map(FieldPB::new)
+      .collect::<Vec<_>>();
+    Ok(DatabaseSchemaPB {
+      view_id: view_id.to_string(),
+      version,
+      fields: fields.into(),
+    })
+  }
+
+  /// Returns None if the version of the schema is still `known_version`, so the clients that cache
+  /// the fields can poll it cheaply. See [Self::get_schema].
+  pub async fn get_schema_if_changed(
+    &self,
+    view_id: &str,
+    known_version: i64,
+  ) -> FlowyResult<Option<DatabaseSchemaPB>> {
+    let _ = self.database_views.get_view_editor(view_id).await?;
+    if self.schema_version.get() == known_version {
+      return Ok(None);
+    }
+    self.get_schema(view_id).await.map(Some)
+  }
+
+  /// Returns the ids of the views in which the field is visible. Fields are shared by all the
+  /// views of a database, so changing a field affects every view returned here.
+  pub fn views_containing_field(&self, field_id: &str) -> Vec<String> {
+    let database = self.database.lock();
+    database
+      .get_all_database_views_meta()
+      .into_iter()
+      .filter(|view| {
+        let is_in_view = database
+          .get_fields_in_view(&view.id, None)
+          .iter()
+          .any(|field| field.id == field_id);
+        if !is_in_view {
+          return false;
+        }
+
+        let layout = database.views.get_database_view_layout(&view.id);
+        let field_ids = [field_id.to_string()];
+        let visibility = database
+          .get_field_settings(&view.id, Some(field_ids.as_slice()))
+          .get(field_id)
+          .map(|field_settings| {
+            FieldSettings::from_any_map(field_id, layout, field_settings).visibility
+          });
+        !matches!(visibility, Some(FieldVisibility::AlwaysHidden))
+      })
+      .map(|view| view.id)
+      .collect()
+  }
+
+  /// Returns which views use the field in a filter, sort, group or calculation.
+  pub fn field_usage(&self, field_id: &str) -> FieldUsage {
+    let database = self.database.lock();
+    let mut usage = FieldUsage::default();
+    for view in database.get_all_database_views_meta() {
+      let filters: Vec<Filter> = database.get_all_filters(&view.id);
+      let has_filter = filters.iter().any(|filter| {
+        let mut filter_ids = vec![];
+        filter.find_all_filters_with_field_id(field_id, &mut filter_ids);
+        !filter_ids.is_empty()
+      });
+      if has_filter {
+        usage.filter_view_ids.push(view.id.clone());
+      }
+
+      let sorts: Vec<Sort> = database.get_all_sorts(&view.id);
+      if sorts.iter().any(|sort| sort.field_id == field_id) {
+        usage.sort_view_ids.push(view.id.clone());
+      }
+
+      let group_settings: Vec<GroupSetting> = database.get_all_group_setting(&view.id);
+      if group_settings
+        .iter()
+        .any(|setting| setting.field_id == field_id)
+      {
+        usage.group_view_ids.push(view.id.clone());
+      }
+
+      let calculations: Vec<Calculation> = database.get_all_calculations(&view.id);
+      if calculations
+        .iter()
+        .any(|calculation| calculation.field_id == field_id)
+      {
+        usage.calculation_view_ids.push(view.id.clone());
+      }
+    }
+    usage
+  }
+
   pub async fn update_field(&self, params: FieldChangesetParams) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     self
       .database
       .lock()
       .fields
       .update_field(&params.field_id, |update| {
-        update.set_name_if_not_none(params.name);
+        let update = update.set_name_if_not_none(params.name);
+        if let Some(description) = params.desc {
+          update.update_type_options(|type_options_update| {
+            type_options_update.insert(
+              FIELD_DESCRIPTION_KEY,
+              field_description_data(description.trim()),
+            );
+          });
+        }
       });
+    self.schema_version.bump();
     notify_did_update_database_field(&self.database, &params.field_id)?;
     Ok(())
   }
 
+  /// Makes the text field the primary field, whose cells are the titles of the rows, and demotes
+  /// the current primary field to a regular field. Both fields are updated with a single lock of
+  /// the database, so the database never has zero or two primary fields.
+  pub async fn set_primary_field(&self, new_primary_field_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let old_primary_field_id = {
+      let database = self.database.lock();
+      let new_primary_field = database
+        .fields
+        .get_field(new_primary_field_id)
+        .ok_or_else(|| {
+          FlowyError::record_not_found()
+            .with_context(format!("The field {} is not found", new_primary_field_id))
+        })?;
+      if new_primary_field.is_primary {
+        return Ok(());
+      }
+      if FieldType::from(new_primary_field.field_type) != FieldType::RichText {
+        return Err(
+          FlowyError::invalid_data().with_context("The primary field must be a text field"),
+        );
+      }
+      if is_system_field(&new_primary_field) {
+        return Err(
+          FlowyError::invalid_data().with_context("A system field can't be the primary field"),
+        );
+      }
+
+      let old_primary_field_id = database
+        .fields
+        .get_primary_field()
+        .map(|field| field.id.clone());
+      if let Some(old_primary_field_id) = &old_primary_field_id {
+        database
+          .fields
+          .update_field(old_primary_field_id, |update| {
+            update.set_primary(false);
+          });
+      }
+      database
+        .fields
+        .update_field(new_primary_field_id, |update| {
+          update.set_primary(true);
+        });
+      old_primary_field_id
+    };
+    self.schema_version.bump();
+
+    // The titles of the rows are read from the new primary field from now on. The index of the
+    // primary values is rebuilt on the next lookup because its primary field changed.
+    if let Some(old_primary_field_id) = old_primary_field_id {
+      notify_did_update_database_field(&self.database, &old_primary_field_id)?;
+    }
+    notify_did_update_database_field(&self.database, new_primary_field_id)?;
+    Ok(())
+  }
+
   pub async fn delete_field(&self, field_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let is_primary = self
       .database
       .lock()
@@ -313,6 +866,7 @@ impl DatabaseEditor {
       database.delete_field(field_id);
       database.get_database_id()
     };
+    self.schema_version.bump();
     let notified_changeset =
       DatabaseFieldChangesetPB::delete(&database_id, vec![FieldIdPB::from(field_id)]);
     self.notify_did_update_database(notified_changeset).await?;
@@ -324,7 +878,13 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  /// Clears the cells of the field in all the rows of the view. The cells are cleared with a single
+  /// lock of the database, and the views are notified once.
+  ///
+  /// Returns [ErrorCode::RecordLocked] without clearing any cell if one of the rows whose cell isn't
+  /// empty is locked.
   pub async fn clear_field(&self, view_id: &str, field_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let field_type: FieldType = self
       .get_field(field_id)
       .map(|field| field.field_type.into())
@@ -340,14 +900,171 @@ impl DatabaseEditor {
       ));
     }
 
-    let cells: Vec<RowCell> = self.get_cells_for_field(view_id, field_id).await;
-    for row_cell in cells {
-      self.clear_cell(view_id, row_cell.row_id, field_id).await?;
+    if field_type.is_user() {
+      return Err(FlowyError::new(
+        ErrorCode::Internal,
+        "Can not clear the field type of Last Edited By or Created By.",
+      ));
+    }
+
+    // The empty cells are already cleared
+    let row_ids = self
+      .get_cells_for_field(view_id, field_id)
+      .await
+      .into_iter()
+      .filter(|row_cell| row_cell.cell.is_some())
+      .map(|row_cell| row_cell.row_id)
+      .collect::<Vec<RowId>>();
+    if row_ids.is_empty() {
+      return Ok(());
+    }
+
+    let (old_rows, new_rows) = {
+      let database = self.database.lock();
+      let num_of_locked_rows = row_ids
+        .iter()
+        .filter(|row_id| is_row_locked(&database, row_id))
+        .count();
+      if num_of_locked_rows > 0 {
+        return Err(FlowyError::new(
+          ErrorCode::RecordLocked,
+          format!("The field has cells in {} locked rows", num_of_locked_rows),
+        ));
+      }
+      let old_rows = row_ids
+        .iter()
+        .map(|row_id| database.get_row_detail(row_id))
+        .collect::<Vec<_>>();
+      for row_id in row_ids.iter() {
+        database.update_row(row_id, |row_update| {
+          row_update.update_cells(|cell_update| {
+            cell_update.clear(field_id);
+          });
+        });
+      }
+      set_rows_last_edited_by(&database, row_ids.iter(), self.uid);
+      let new_rows = row_ids
+        .iter()
+        .flat_map(|row_id| database.get_row_detail(row_id))
+        .collect::<Vec<_>>();
+      (old_rows, new_rows)
+    };
+    self.record_unsynced_row_edits(row_ids.iter());
+
+    for view in self.database_views.editors().await {
+      view
+        .v_did_update_field_cells(&old_rows, &new_rows, field_id)
+        .await;
     }
 
     Ok(())
   }
 
+  /// Computes the aggregation of a number field over the rows that the view shows, i.e. the rows
+  /// left after filtering. Nothing is saved to the view, unlike the calculations. The empty cells
+  /// are skipped.
+  ///
+  /// Returns an error if the field isn't a number field, or if no cell has a number to compute
+  /// the min, max or average of.
+  pub async fn aggregate_field(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    agg: AggregationKind,
+  ) -> FlowyResult<f64> {
+    let field = self
+      .get_field(field_id)
+      .ok_or_else(FlowyError::field_record_not_found)?;
+    let field_type = FieldType::from(field.field_type);
+    if !field_type.is_number() {
+      return Err(FlowyError::new(
+        ErrorCode::FieldInvalidOperation,
+        format!("Can not aggregate the field type of {:?}", field_type),
+      ));
+    }
+
+    let row_cells = self
+      .get_rows(view_id)
+      .await?
+      .into_iter()
+      .map(|row_detail| {
+        Arc::new(RowCell {
+          row_id: row_detail.row.id.clone(),
+          cell: row_detail.row.cells.get(field_id).cloned(),
+        })
+      })
+      .collect();
+    CalculationsService::new()
+      .aggregate(&field, agg, row_cells)
+      .ok_or_else(|| {
+        FlowyError::record_not_found()
+          .with_context(format!("The field:{} has no number to aggregate", field_id))
+      })
+  }
+
+  /// Replaces `find` with `replace` in all the cells of the text field. Returns the number of the
+  /// cells that are changed, or would be changed if `dry_run` is true. Locked rows are skipped.
+  pub async fn replace_in_field(
+    &self,
+    view_id: &str,
+    field_id: &str,
+    find: &str,
+    replace: &str,
+    case_sensitive: bool,
+    dry_run: bool,
+  ) -> FlowyResult<usize> {
+    let field = self
+      .get_field(field_id)
+      .ok_or_else(FlowyError::field_record_not_found)?;
+    let field_type = FieldType::from(field.field_type);
+    if !matches!(field_type, FieldType::RichText | FieldType::URL) {
+      return Err(FlowyError::new(
+        ErrorCode::FieldInvalidOperation,
+        format!(
+          "Can not replace the text in the field type of {:?}",
+          field_type
+        ),
+      ));
+    }
+
+    if find.is_empty() {
+      return Err(FlowyError::invalid_data().with_context("The text to find is empty"));
+    }
+
+    let new_cells = self
+      .get_cells_for_field(view_id, field_id)
+      .await
+      .into_iter()
+      .filter(|row_cell| !self.is_row_locked(&row_cell.row_id))
+      .filter_map(|row_cell| {
+        let text = row_cell.cell.as_ref()?.get_str_value(CELL_DATA)?;
+        let new_text = replace_text(&text, find, replace, case_sensitive)?;
+        let new_cell = new_cell_builder(field_type)
+          .insert_str_value(CELL_DATA, new_text)
+          .build();
+        Some((row_cell.row_id, new_cell))
+      })
+      .collect::<Vec<(RowId, Cell)>>();
+
+    if new_cells.len() > MAX_BULK_UPDATE_CELLS {
+      return Err(FlowyError::new(
+        ErrorCode::BulkUpdateLimitExceeded,
+        format!(
+          "Can not update {} cells at once, the limit is {}",
+          new_cells.len(),
+          MAX_BULK_UPDATE_CELLS
+        ),
+      ));
+    }
+
+    let count = new_cells.len();
+    if !dry_run {
+      self.ensure_can_edit()?;
+      self.write_cells(view_id, &field, new_cells).await?;
+    }
+    Ok(count)
+  }
+
   /// Update the field type option data.
   /// Do nothing if the [TypeOptionData] is empty.
   pub async fn update_field_type_option(
@@ -356,17 +1073,29 @@ impl DatabaseEditor {
     type_option_data: TypeOptionData,
     old_field: Field,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editors = self.database_views.editors().await;
-    update_field_type_option_fn(&self.database, &view_editors, type_option_data, old_field).await?;
+    update_field_type_option_fn(
+      &self.database,
+      &view_editors,
+      &self.schema_version,
+      type_option_data,
+      old_field,
+    )
+    .await?;
 
     Ok(())
   }
 
+  /// Switches the type of the field. The cells are converted with the `migration_strategy` if
+  /// it's provided, otherwise they're converted when they're read.
   pub async fn switch_to_field_type(
     &self,
     field_id: &str,
     new_field_type: FieldType,
+    migration_strategy: Option<CellMigrationStrategy>,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let field = self.database.lock().fields.get_field(field_id);
     match field {
       None => {},
@@ -378,18 +1107,65 @@ impl DatabaseEditor {
           ));
         }
 
+        let mapping = match migration_strategy {
+          None => None,
+          Some(CellMigrationStrategy::SelectOptionMapping(mapping)) => {
+            if !new_field_type.is_select_option() {
+              return Err(FlowyError::new(
+                ErrorCode::FieldInvalidOperation,
+                format!(
+                  "Can not map the cells to the options of {:?}",
+                  new_field_type
+                ),
+              ));
+            }
+            Some(mapping)
+          },
+        };
+
+        // Read the text of the cells before the field type is switched
+        let old_texts = mapping.as_ref().map(|_| {
+          self
+            .database
+            .lock()
+            .get_database_rows()
+            .into_iter()
+            .filter_map(|row| {
+              let cell = row.cells.get(field_id)?;
+              Some((row.id.clone(), stringify_cell(cell, &field)))
+            })
+            .collect::<Vec<_>>()
+        });
+        // The migrated cells are written like the other cell edits, so the locked rows are kept
+        let num_of_locked_rows = old_texts
+          .iter()
+          .flatten()
+          .filter(|(row_id, _)| self.is_row_locked(row_id))
+          .count();
+        if num_of_locked_rows > 0 {
+          return Err(FlowyError::new(
+            ErrorCode::RecordLocked,
+            format!("The field has cells in {} locked rows", num_of_locked_rows),
+          ));
+        }
+
         let old_field_type = FieldType::from(field.field_type);
         let old_type_option_data = field.get_any_type_option(old_field_type);
         let new_type_option_data = field
           .get_any_type_option(new_field_type)
           .unwrap_or_else(|| default_type_option_data_from_type(new_field_type));
 
-        let transformed_type_option = transform_type_option(
+        let mut transformed_type_option = transform_type_option(
           old_field_type,
           new_field_type,
           old_type_option_data,
           new_type_option_data,
         );
+        if let Some(mapping) = &mapping {
+          let options = mapping.values().cloned().collect();
+          transformed_type_option =
+            insert_select_options(new_field_type, transformed_type_option, options);
+        }
         self
           .database
           .lock()
@@ -399,6 +1175,13 @@ impl DatabaseEditor {
               .set_field_type(new_field_type.into())
               .set_type_option(new_field_type.into(), Some(transformed_type_option));
           });
+        self.schema_version.bump();
+
+        if let (Some(mapping), Some(old_texts)) = (mapping, old_texts) {
+          self
+            .migrate_cells_with_mapping(field_id, &mapping, old_texts)
+            .await;
+        }
 
         for view in self.database_views.editors().await {
           view.v_did_update_field_type(field_id, new_field_type).await;
@@ -410,7 +1193,35 @@ impl DatabaseEditor {
     Ok(())
   }
 
+  async fn migrate_cells_with_mapping(
+    &self,
+    field_id: &str,
+    mapping: &HashMap<String, SelectOption>,
+    old_texts: Vec<(RowId, String)>,
+  ) {
+    let field = match self.get_field(field_id) {
+      Some(field) => field,
+      None => return,
+    };
+    let new_cells = old_texts
+      .into_iter()
+      .map(|(row_id, text)| {
+        let option_ids = mapping
+          .get(text.trim())
+          .map(|option| vec![option.id.clone()])
+          .unwrap_or_default();
+        (row_id, insert_select_option_cell(option_ids, &field))
+      })
+      .collect::<Vec<_>>();
+    // The rows are in every view of the database, so any view can be used to notify the changes
+    let view_id = self.get_inline_view_id();
+    if let Err(err) = self.write_cells(&view_id, &field, new_cells).await {
+      warn!("Failed to migrate the cells of field:{}: {}", field_id, err);
+    }
+  }
+
   pub async fn duplicate_field(&self, view_id: &str, field_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let is_primary = self
       .database
       .lock()
@@ -431,6 +1242,7 @@ impl DatabaseEditor {
       .lock()
       .duplicate_field(view_id, field_id, |field| format!("{} (copy)", field.name));
     if let Some((index, duplicated_field)) = value {
+      self.schema_version.bump();
       let _ = self
         .notify_did_insert_database_field(duplicated_field.clone(), index)
         .await;
@@ -440,7 +1252,7 @@ impl DatabaseEditor {
       for cell in cells {
         if let Some(new_cell) = cell.cell.clone() {
           self
-            .update_cell(view_id, &cell.row_id, &new_field_id, new_cell)
+            .write_cell(view_id, &cell.row_id, &new_field_id, new_cell, true)
             .await?;
         }
       }
@@ -448,13 +1260,29 @@ impl DatabaseEditor {
     Ok(())
   }
 
-  pub async fn duplicate_row(&self, view_id: &str, row_id: &RowId) -> FlowyResult<()> {
-    let (row_detail, index) = {
+  /// Duplicates the row, including its meta. The duplicate gets its own document, whose id is
+  /// derived from the id of the duplicate. If `copy_document` is true, the content of the row's
+  /// document is copied to it. Otherwise, the duplicate's document is empty.
+  ///
+  /// If the content fails to be copied, the error is returned and the duplicate is kept with an
+  /// empty document.
+  pub async fn duplicate_row(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    copy_document: bool,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let (row_detail, index, source_document) = {
       let database = self.database.lock();
+      let source_document = database
+        .get_row_detail(row_id)
+        .map(|row_detail| (row_detail.document_id, row_detail.meta.is_document_empty));
 
-      let params = database
+      let mut params = database
         .duplicate_row(row_id)
         .ok_or_else(|| FlowyError::internal().with_context("error while copying row"))?;
+      params.id = self.next_row_id(&database)?;
 
       let (index, row_order) = database
         .create_row_in_view(view_id, params)
@@ -462,20 +1290,123 @@ impl DatabaseEditor {
           FlowyError::internal().with_context("error while inserting duplicated row")
         })?;
 
+      // The duplicate's document is empty until the content is copied
+      database.update_row_meta(&row_order.id, |meta_update| {
+        meta_update.update_is_document_empty_if_not_none(Some(true));
+      });
+      set_rows_created_by(&database, [&row_order.id], self.uid);
+
       tracing::trace!("duplicated row: {:?} at {}", row_order, index);
       let row_detail = database.get_row_detail(&row_order.id);
 
-      (row_detail, index)
+      (row_detail, index, source_document)
     };
 
-    if let Some(row_detail) = row_detail {
-      for view in self.database_views.editors().await {
-        view.v_did_create_row(&row_detail, index).await;
-      }
+    let row_detail = match row_detail {
+      Some(row_detail) => row_detail,
+      None => return Ok(()),
+    };
+    for view in self.database_views.editors().await {
+      view.v_did_create_row(&row_detail, index).await;
     }
 
-    Ok(())
-  }
+    if !copy_document {
+      return Ok(());
+    }
+    // The row's document isn't created until it's opened, so there's nothing to copy if it's empty
+    if let Some((source_document_id, false)) = source_document {
+      let is_document_empty = self
+        .get_document_service()?
+        .copy_document(&source_document_id, &row_detail.document_id)
+        .await?;
+      let changeset = UpdateRowMetaParams {
+        id: row_detail.row.id.to_string(),
+        view_id: view_id.to_string(),
+        icon_url: None,
+        cover_url: None,
+        is_document_empty: Some(is_document_empty),
+      };
+      self.update_row_meta(&row_detail.row.id, changeset).await?;
+    }
+
+    Ok(())
+  }
+
+  /// Creates a row at the end of the view from the document. The name of the document becomes
+  /// the primary cell of the row, and the content of the document is copied to the row's
+  /// document. The document itself is left as it is.
+  pub async fn create_row_from_document(
+    &self,
+    view_id: &str,
+    document_id: &str,
+  ) -> FlowyResult<RowMetaPB> {
+    self.ensure_can_edit()?;
+    let document_service = self.get_document_service()?;
+    let title = document_service.get_document_title(document_id).await?;
+    let primary_field = self
+      .database
+      .lock()
+      .fields
+      .get_primary_field()
+      .ok_or_else(|| FlowyError::internal().with_context("The primary field is not found"))?;
+    let params = CreateRowPayloadPB {
+      view_id: view_id.to_string(),
+      data: HashMap::from([(primary_field.id, title)]),
+      ..Default::default()
+    };
+    let row_detail = self
+      .create_row(params)
+      .await?
+      .ok_or_else(|| FlowyError::internal().with_context("error while creating row"))?;
+
+    // The id of the row's document is derived from the row id, so the content is copied
+    let row_id = row_detail.row.id;
+    let is_document_empty = document_service
+      .copy_document(document_id, &row_detail.document_id)
+      .await?;
+    let changeset = UpdateRowMetaParams {
+      id: row_id.to_string(),
+      view_id: view_id.to_string(),
+      icon_url: None,
+      cover_url: None,
+      is_document_empty: Some(is_document_empty),
+    };
+    self.update_row_meta(&row_id, changeset).await?;
+    self
+      .get_row_meta(view_id, &row_id)
+      .ok_or_else(FlowyError::record_not_found)
+  }
+
+  /// Creates a document in the workspace from the row's document and returns its id. The
+  /// document is named after the primary cell of the row. The row itself is left as it is.
+  pub async fn create_document_from_row(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+  ) -> FlowyResult<String> {
+    let document_service = self.get_document_service()?;
+    let row_detail = self
+      .get_row_detail(view_id, row_id)
+      .ok_or_else(FlowyError::record_not_found)?;
+    let primary_field = self.database.lock().fields.get_primary_field();
+    let name = primary_field
+      .and_then(|field| {
+        let cell = row_detail.row.cells.get(&field.id)?;
+        Some(stringify_cell(cell, &field))
+      })
+      .unwrap_or_default();
+    // The row's document isn't created until it's opened, so there's nothing to copy if it's empty
+    let copy_from = (!row_detail.meta.is_document_empty).then_some(row_detail.document_id.as_str());
+    document_service.create_document(&name, copy_from).await
+  }
+
+  fn get_document_service(&self) -> FlowyResult<Arc<dyn DatabaseDocumentService>> {
+    self
+      .document_service
+      .read()
+      .clone()
+      .ok_or_else(|| FlowyError::internal().with_context("The document service is not set"))
+  }
 
   pub async fn move_row(
     &self,
@@ -483,7 +1414,9 @@ impl DatabaseEditor {
     from_row_id: RowId,
     to_row_id: RowId,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let database = self.database.lock();
+    ensure_row_not_locked(&database, &from_row_id)?;
 
     let row_detail = database.get_row_detail(&from_row_id).ok_or_else(|| {
       let msg = format!("Cannot find row {}", from_row_id);
@@ -510,18 +1443,91 @@ impl DatabaseEditor {
     Ok(())
   }
 
-  pub async fn create_row(&self, params: CreateRowPayloadPB) -> FlowyResult<Option<RowDetail>> {
+  /// Moves the rows to `to_index` as a contiguous block in one transaction. The rows keep their
+  /// relative order in the view, even if they aren't next to each other before moving. `to_index`
+  /// is the index of the first moved row after moving.
+  pub async fn move_rows(
+    &self,
+    view_id: &str,
+    row_ids: Vec<RowId>,
+    to_index: usize,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let database = self.database.lock();
+    for row_id in row_ids.iter() {
+      ensure_row_not_locked(&database, row_id)?;
+    }
+    let row_orders = database
+      .get_row_orders_for_view(view_id)
+      .into_iter()
+      .map(|row_order| row_order.id)
+      .collect::<Vec<RowId>>();
+    if let Some(row_id) = row_ids.iter().find(|row_id| !row_orders.contains(row_id)) {
+      let msg = format!("Cannot find row {} in view {}", row_id, view_id);
+      return Err(FlowyError::record_not_found().with_context(msg));
+    }
+
+    let new_row_orders = move_as_block(&row_orders, &row_ids, to_index);
+    if new_row_orders == row_orders {
+      return Ok(());
+    }
+    reorder_rows_in_view(&database, view_id, row_orders, &new_row_orders);
+
+    let inserted_rows = new_row_orders
+      .iter()
+      .enumerate()
+      .filter(|(_, row_id)| row_ids.contains(row_id))
+      .flat_map(|(index, row_id)| {
+        let row_detail = database.get_row_detail(row_id)?;
+        Some(InsertedRowPB::new(RowMetaPB::from(row_detail)).with_index(index as i32))
+      })
+      .collect::<Vec<_>>();
+    drop(database);
+
+    let deleted_rows = inserted_rows
+      .iter()
+      .map(|row| row.row_meta.id.clone())
+      .collect();
+    let changes = RowsChangePB::from_move(deleted_rows, inserted_rows);
+    send_notification(view_id, DatabaseNotification::DidUpdateRow)
+      .payload(changes)
+      .send();
+    Ok(())
+  }
+
+  /// Creates a row in the view. A row that's created at the end without a reference row is
+  /// inserted at the view's [NewRowPosition] instead, see [Self::set_new_row_position].
+  pub async fn create_row(&self, mut params: CreateRowPayloadPB) -> FlowyResult<Option<RowDetail>> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(&params.view_id).await?;
+    let is_unspecified_position =
+      matches!(params.row_position.position, OrderObjectPositionTypePB::End)
+        && params.row_position.object_id.is_none();
+    if is_unspecified_position && self.get_new_row_position(&params.view_id) == NewRowPosition::Top
+    {
+      params.row_position = OrderObjectPositionPB::start();
+    }
 
     let CreateRowParams {
-      collab_params,
+      mut collab_params,
       open_after_create: _,
     } = view_editor.v_will_create_row(params).await?;
+    collab_params.id = {
+      let database = self.database.lock();
+      self.next_row_id(&database)?
+    };
+    let now = self.now();
+    collab_params.created_at = now;
+    collab_params.modified_at = now;
 
-    let result = self
-      .database
-      .lock()
-      .create_row_in_view(&view_editor.view_id, collab_params);
+    let result = {
+      let database = self.database.lock();
+      let result = database.create_row_in_view(&view_editor.view_id, collab_params);
+      if let Some((_, row_order)) = &result {
+        set_rows_created_by(&database, [&row_order.id], self.uid);
+      }
+      result
+    };
 
     if let Some((index, row_order)) = result {
       tracing::trace!("created row: {:?} at {}", row_order, index);
@@ -537,10 +1543,62 @@ impl DatabaseEditor {
     Ok(None)
   }
 
+  /// Creates the rows at the end of the view in one go. Unlike [Self::create_row], the rows aren't
+  /// pre-filled according to the filters or the groups of the view. Returns the created rows.
+  pub async fn create_rows_batch(
+    &self,
+    view_id: &str,
+    rows: Vec<Cells>,
+  ) -> FlowyResult<Vec<RowDetail>> {
+    self.ensure_can_edit()?;
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    let now = self.now();
+    let created_rows = {
+      let database = self.database.lock();
+      let database_id = database.get_database_id();
+      // The ids are generated up front, so none of the rows is created if one of them is used
+      let row_ids = self.next_row_ids(&database, rows.len())?;
+      let created_rows = rows
+        .into_iter()
+        .zip(row_ids)
+        .flat_map(|(cells, row_id)| {
+          let mut params = collab_database::rows::CreateRowParams::new(row_id, database_id.clone());
+          params.cells = cells;
+          params.created_at = now;
+          params.modified_at = now;
+          let (index, row_order) = database.create_row_in_view(&view_editor.view_id, params)?;
+          let row_detail = database.get_row_detail(&row_order.id)?;
+          Some((index, row_detail))
+        })
+        .collect::<Vec<(usize, RowDetail)>>();
+      set_rows_created_by(
+        &database,
+        created_rows
+          .iter()
+          .map(|(_, row_detail)| &row_detail.row.id),
+        self.uid,
+      );
+      created_rows
+    };
+
+    for view in self.database_views.editors().await {
+      for (index, row_detail) in created_rows.iter() {
+        view.v_did_create_row(row_detail, *index).await;
+      }
+    }
+    Ok(
+      created_rows
+        .into_iter()
+        .map(|(_, row_detail)| row_detail)
+        .collect(),
+    )
+  }
+
   pub async fn create_field_with_type_option(
     &self,
     params: CreateFieldParams,
   ) -> FlowyResult<FieldPB> {
+    self.ensure_can_edit()?;
     let name = params
       .field_name
       .clone()
@@ -551,27 +1609,62 @@ impl DatabaseEditor {
       .and_then(|data| type_option_data_from_pb(data, &params.field_type).ok())
       .unwrap_or(default_type_option_data_from_type(params.field_type));
 
+    let field_id = {
+      let database = self.database.lock();
+      self.next_field_id(&database)?
+    };
+    // The system fields are hidden in all the layouts
+    let field_settings_by_layout = if params.is_system {
+      system_field_settings_by_layout_map()
+    } else {
+      default_field_settings_by_layout_map()
+    };
     let (index, field) = self.database.lock().create_field_with_mut(
       &params.view_id,
       name,
       params.field_type.into(),
       &params.position,
       |field| {
+        field.id = field_id;
         field
           .type_options
           .insert(params.field_type.to_string(), type_option_data);
+        if params.is_system {
+          field
+            .type_options
+            .insert(FIELD_SYSTEM_KEY.to_string(), system_field_data());
+        }
       },
-      default_field_settings_by_layout_map(),
+      field_settings_by_layout,
     );
+    self.schema_version.bump();
 
+    let is_system = is_system_field(&field);
     let _ = self
       .notify_did_insert_database_field(field.clone(), index)
       .await;
+    if !is_system {
+      self.notify_did_insert_field_settings(&field.id).await;
+    }
 
     Ok(FieldPB::new(field))
   }
 
+  /// Sends the settings that the new field starts with to the opened views, so its width and
+  /// visibility render right away. The views that aren't opened read them when they're opened.
+  async fn notify_did_insert_field_settings(&self, field_id: &str) {
+    let field_ids = vec![field_id.to_string()];
+    for view in self.database_views.editors().await {
+      if let Some(field_settings) = view.v_get_field_settings(&field_ids).await.remove(field_id) {
+        send_notification(&view.view_id, DatabaseNotification::DidUpdateFieldSettings)
+          .payload(FieldSettingsPB::from(field_settings))
+          .send();
+      }
+    }
+  }
+
   pub async fn move_field(&self, params: MoveFieldParams) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let (field, new_index) = {
       let database = self.database.lock();
 
@@ -593,6 +1686,7 @@ impl DatabaseEditor {
 
       (field, new_index)
     };
+    self.schema_version.bump();
 
     if let Some(index) = new_index {
       let delete_field = FieldIdPB::from(params.from_field_id);
@@ -605,6 +1699,7 @@ impl DatabaseEditor {
         inserted_fields: vec![insert_field],
         deleted_fields: vec![delete_field],
         updated_fields: vec![],
+        origin: FieldChangeOriginPB::Local,
       };
 
       send_notification(&params.view_id, DatabaseNotification::DidUpdateFields)
@@ -620,6 +1715,26 @@ impl DatabaseEditor {
     Ok(view_editor.v_get_rows().await)
   }
 
+  /// Returns the ids of the rows in the view that were created or modified after `since`, a unix
+  /// timestamp in seconds, so a client can refresh only those rows after reconnecting instead of
+  /// reloading the whole view. The filters of the view aren't applied.
+  ///
+  /// The deleted rows aren't covered. They can be found by comparing the row ids of the view with
+  /// the ones that the client has.
+  pub async fn rows_changed_since(&self, view_id: &str, since: i64) -> FlowyResult<Vec<RowId>> {
+    // Returns an error if the view doesn't exist
+    self.database_views.get_view_editor(view_id).await?;
+    let row_ids = self
+      .database
+      .lock()
+      .get_rows_for_view(view_id)
+      .into_iter()
+      .filter(|row| row.created_at > since || row.modified_at > since)
+      .map(|row| row.id)
+      .collect();
+    Ok(row_ids)
+  }
+
   pub fn get_row(&self, view_id: &str, row_id: &RowId) -> Option<Row> {
     if self.database.lock().views.is_row_exist(view_id, row_id) {
       Some(self.database.lock().get_row(row_id))
@@ -638,6 +1753,7 @@ impl DatabaseEditor {
         icon: row_meta.icon_url,
         cover: row_meta.cover_url,
         is_document_empty: row_meta.is_document_empty,
+        is_locked: self.is_row_locked(row_id),
       })
     } else {
       warn!("the row:{} is exist in view:{}", row_id.as_str(), view_id);
@@ -654,8 +1770,170 @@ impl DatabaseEditor {
     }
   }
 
-  pub async fn delete_rows(&self, row_ids: &[RowId]) {
-    let rows = self.database.lock().remove_rows(row_ids);
+  /// Returns whether the row is shown in the view according to the view's filters, so the callers
+  /// don't need to reload the view after editing the row. It's true if the view has no filters.
+  pub async fn is_row_visible(&self, view_id: &str, row_id: &RowId) -> FlowyResult<bool> {
+    let row_detail = self
+      .get_row_detail(view_id, row_id)
+      .ok_or_else(FlowyError::record_not_found)?;
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    Ok(view_editor.v_is_row_visible(Arc::new(row_detail)).await)
+  }
+
+  /// Groups the rows of the view by the rendered values of the key fields and returns the groups
+  /// that have more than one row. The rows whose key values are empty are grouped like any other
+  /// value. Each group keeps the order of the rows in the view.
+  pub async fn find_duplicate_rows(
+    &self,
+    view_id: &str,
+    key_field_ids: Vec<String>,
+  ) -> FlowyResult<Vec<Vec<RowId>>> {
+    if key_field_ids.is_empty() {
+      return Err(FlowyError::invalid_data().with_context("The key fields are empty"));
+    }
+    let key_fields = key_field_ids
+      .iter()
+      .map(|field_id| {
+        self
+          .get_field(field_id)
+          .ok_or_else(FlowyError::field_record_not_found)
+      })
+      .collect::<FlowyResult<Vec<Field>>>()?;
+
+    let rows = self.get_rows(view_id).await?;
+    if rows.len() > MAX_DUPLICATE_SCAN_ROWS {
+      return Err(FlowyError::new(
+        ErrorCode::ScanLimitExceeded,
+        format!(
+          "Can not scan {} rows for duplicates, the limit is {}",
+          rows.len(),
+          MAX_DUPLICATE_SCAN_ROWS
+        ),
+      ));
+    }
+
+    let mut row_ids_by_key: IndexMap<Vec<String>, Vec<RowId>> = IndexMap::new();
+    for row_detail in rows {
+      let mut key = Vec::with_capacity(key_fields.len());
+      for field in &key_fields {
+        let value = self
+          .get_cell(&field.id, &row_detail.row.id)
+          .await
+          .map(|cell| stringify_cell(&cell, field))
+          .unwrap_or_default();
+        key.push(value);
+      }
+      row_ids_by_key
+        .entry(key)
+        .or_default()
+        .push(row_detail.row.id.clone());
+    }
+
+    Ok(
+      row_ids_by_key
+        .into_values()
+        .filter(|row_ids| row_ids.len() > 1)
+        .collect(),
+    )
+  }
+
+  /// Merges the duplicates into the primary row and then deletes them. See [MergeRowsStrategy]
+  /// for how the conflicts are resolved. The primary row is updated and the duplicates are deleted
+  /// with a single lock of the database, and the views are notified once for the primary row.
+  ///
+  /// Returns [ErrorCode::RecordLocked] if any of the rows is locked.
+  pub async fn merge_rows(
+    &self,
+    view_id: &str,
+    primary_row_id: &RowId,
+    duplicate_row_ids: Vec<RowId>,
+    strategy: MergeRowsStrategy,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    if duplicate_row_ids.contains(primary_row_id) {
+      return Err(FlowyError::invalid_data().with_context("Can't merge a row into itself"));
+    }
+    if duplicate_row_ids.is_empty() {
+      return Ok(());
+    }
+
+    let old_row = self
+      .get_row_detail(view_id, primary_row_id)
+      .ok_or_else(FlowyError::record_not_found)?;
+    let mut rows = vec![old_row.row.clone()];
+    for row_id in &duplicate_row_ids {
+      let row_detail = self
+        .get_row_detail(view_id, row_id)
+        .ok_or_else(FlowyError::record_not_found)?;
+      rows.push(row_detail.row);
+    }
+
+    let fields = self.get_fields_including_system(view_id, None);
+    let merged_cells = merge_row_cells(&fields, &rows, &strategy, Some(self.cell_cache.clone()));
+    let merged_field_ids = merged_cells
+      .iter()
+      .map(|(field_id, _)| field_id.clone())
+      .collect::<Vec<String>>();
+    let deleted_rows = {
+      let database = self.database.lock();
+      for row in &rows {
+        ensure_row_not_locked(&database, &row.id)?;
+      }
+      if !merged_cells.is_empty() {
+        database.update_row(primary_row_id, |row_update| {
+          row_update.update_cells(|cell_update| {
+            merged_cells
+              .into_iter()
+              .fold(cell_update, |cell_update, (field_id, cell)| {
+                cell_update.insert(&field_id, cell)
+              });
+          });
+        });
+        set_rows_last_edited_by(&database, [primary_row_id], self.uid);
+      }
+      remove_row_metas(&database, &duplicate_row_ids);
+      database.remove_rows(&duplicate_row_ids)
+    };
+    self.primary_value_index.did_delete_rows(&duplicate_row_ids);
+    for field_id in &merged_field_ids {
+      self
+        .primary_value_index
+        .did_update_cell(primary_row_id, field_id);
+    }
+    let is_primary_row_updated = !merged_field_ids.is_empty();
+    if is_primary_row_updated {
+      self.record_unsynced_row_edits([primary_row_id]);
+    }
+    // Removing the rows updates the row orders of the database
+    self.record_unsynced_database_edits(deleted_rows.len());
+
+    let old_row = Some(old_row);
+    let new_row = if is_primary_row_updated {
+      self.get_row_detail(view_id, primary_row_id)
+    } else {
+      None
+    };
+    for view in self.database_views.editors().await {
+      if let Some(new_row) = &new_row {
+        view
+          .v_did_update_row_cells(&old_row, new_row, merged_field_ids.clone())
+          .await;
+      }
+      for row in &deleted_rows {
+        view.v_did_delete_row(row).await;
+      }
+    }
+    Ok(())
+  }
+
+  pub async fn delete_rows(&self, row_ids: &[RowId]) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let rows = {
+      let database = self.database.lock();
+      remove_row_metas(&database, row_ids);
+      database.remove_rows(row_ids)
+    };
+    self.primary_value_index.did_delete_rows(row_ids);
 
     for row in rows {
       tracing::trace!("Did delete row:{:?}", row);
@@ -663,10 +1941,16 @@ impl DatabaseEditor {
         view.v_did_delete_row(&row).await;
       }
     }
+    Ok(())
   }
 
   #[tracing::instrument(level = "trace", skip_all)]
-  pub async fn update_row_meta(&self, row_id: &RowId, changeset: UpdateRowMetaParams) {
+  pub async fn update_row_meta(
+    &self,
+    row_id: &RowId,
+    changeset: UpdateRowMetaParams,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     self.database.lock().update_row_meta(row_id, |meta_update| {
       meta_update
         .insert_cover_if_not_none(changeset.cover_url)
@@ -682,8 +1966,9 @@ impl DatabaseEditor {
       }
 
       // Notifies the client that the row meta has been updated.
+      let row_meta = row_meta_pb(&self.database.lock(), &row_detail);
       send_notification(row_id.as_str(), DatabaseNotification::DidUpdateRowMeta)
-        .payload(RowMetaPB::from(&row_detail))
+        .payload(row_meta)
         .send();
 
       // Update the last modified time of the row
@@ -691,25 +1976,52 @@ impl DatabaseEditor {
         .update_last_modified_time(row_detail.clone(), &changeset.view_id)
         .await;
     }
+    Ok(())
+  }
+
+  /// Locks or unlocks a row. The cells of a locked row can't be edited and the row can't be
+  /// moved, but it can still be read and deleted.
+  pub async fn set_row_locked(&self, row_id: &RowId, locked: bool) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let row_detail = {
+      let database = self.database.lock();
+      if database.get_row_detail(row_id).is_none() {
+        return Err(
+          FlowyError::record_not_found().with_context(format!("Cannot find row {}", row_id)),
+        );
+      }
+      set_row_locked(&database, row_id, locked)?;
+      database
+        .get_row_detail(row_id)
+        .map(|row_detail| (row_meta_pb(&database, &row_detail), row_detail))
+    };
+
+    if let Some((row_meta, row_detail)) = row_detail {
+      for view in self.database_views.editors().await {
+        view.v_did_update_row_meta(row_id, &row_detail).await;
+      }
+      send_notification(row_id.as_str(), DatabaseNotification::DidUpdateRowMeta)
+        .payload(row_meta)
+        .send();
+    }
+    Ok(())
+  }
+
+  pub fn is_row_locked(&self, row_id: &RowId) -> bool {
+    is_row_locked(&self.database.lock(), row_id)
   }
 
   pub async fn get_cell(&self, field_id: &str, row_id: &RowId) -> Option<Cell> {
     let database = self.database.lock();
     let field = database.fields.get_field(field_id)?;
-    let field_type = FieldType::from(field.field_type);
-    // If the cell data is referenced, return the reference data. Otherwise, return an empty cell.
-    match field_type {
-      FieldType::LastEditedTime | FieldType::CreatedTime => {
-        let row = database.get_row(row_id);
-        let wrapped_cell_data = if field_type.is_created_time() {
-          TimestampCellDataWrapper::from((field_type, TimestampCellData::new(row.created_at)))
-        } else {
-          TimestampCellDataWrapper::from((field_type, TimestampCellData::new(row.modified_at)))
-        };
-        Some(Cell::from(wrapped_cell_data))
-      },
-      _ => database.get_cell(field_id, row_id).cell,
-    }
+    let row = database.get_row(row_id);
+    let row_users = get_row_users(&database, row_id);
+    cell_in_row(
+      &row,
+      &row_users,
+      field_id,
+      FieldType::from(field.field_type),
+    )
   }
 
   pub async fn get_cell_pb(&self, field_id: &str, row_id: &RowId) -> Option<CellPB> {
@@ -749,6 +2061,17 @@ impl DatabaseEditor {
             }
           })
           .collect(),
+        FieldType::LastEditedBy | FieldType::CreatedBy => {
+          let row_users = get_all_row_users(&database);
+          database
+            .get_rows_for_view(view_id)
+            .into_iter()
+            .map(|row| RowCell {
+              cell: user_cell_of_row(&row_users, &row.id, field_type),
+              row_id: row.id,
+            })
+            .collect()
+        },
         _ => database.get_cells_for_field(view_id, field_id),
       }
     } else {
@@ -756,6 +2079,59 @@ impl DatabaseEditor {
     }
   }
 
+  /// Returns the cells of the fields in the rows of the view, by row id and field id. All the rows
+  /// of the view are used if `row_ids` is None, and the rows that aren't in the view are skipped.
+  /// Each row is read once for all the fields, so it's faster than calling
+  /// [Self::get_cells_for_field] for each field of a wide grid. The empty cells and the fields that
+  /// don't exist are left out.
+  pub async fn get_cells_for_fields(
+    &self,
+    view_id: &str,
+    field_ids: Vec<String>,
+    row_ids: Option<Vec<RowId>>,
+  ) -> FlowyResult<HashMap<RowId, HashMap<String, Cell>>> {
+    // Returns an error if the view doesn't exist
+    self.database_views.get_view_editor(view_id).await?;
+    let database = self.database.lock();
+    let fields = database
+      .get_fields_in_view(view_id, Some(field_ids))
+      .into_iter()
+      .map(|field| {
+        let field_type = FieldType::from(field.field_type);
+        (field.id, field_type)
+      })
+      .collect::<Vec<_>>();
+    let rows = match row_ids {
+      None => database.get_rows_for_view(view_id),
+      Some(row_ids) => row_ids
+        .into_iter()
+        .filter(|row_id| database.views.is_row_exist(view_id, row_id))
+        .map(|row_id| database.get_row(&row_id))
+        .collect(),
+    };
+    let row_users = if fields.iter().any(|(_, field_type)| field_type.is_user()) {
+      get_all_row_users(&database)
+    } else {
+      RowUsersByRowId::new()
+    };
+
+    let cells_by_row_id = rows
+      .into_iter()
+      .map(|row| {
+        let users = row_users.get(&row.id).cloned().unwrap_or_default();
+        let cells = fields
+          .iter()
+          .flat_map(|(field_id, field_type)| {
+            let cell = cell_in_row(&row, &users, field_id, *field_type)?;
+            Some((field_id.clone(), cell))
+          })
+          .collect();
+        (row.id, cells)
+      })
+      .collect();
+    Ok(cells_by_row_id)
+  }
+
   #[instrument(level = "trace", skip_all)]
   pub async fn update_cell_with_changeset(
     &self,
@@ -781,12 +2157,58 @@ impl DatabaseEditor {
     self.update_cell(view_id, row_id, field_id, new_cell).await
   }
 
+  /// Sets the date cell to `local_datetime`, e.g. `2024-03-10 09:30`, in the timezone with the
+  /// IANA id `timezone_id`, so the clients don't convert the date to a timestamp themselves. If
+  /// `end` is set, it's read in the same timezone and the cell becomes a range. Otherwise, the
+  /// end of a range cell is kept. Returns [ErrorCode::InvalidParams] if the timezone is unknown
+  /// or the end is before the start.
+  #[allow(clippy::too_many_arguments)]
+  pub async fn set_date_cell(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    local_datetime: &str,
+    timezone_id: &str,
+    include_time: bool,
+    end: Option<&str>,
+  ) -> FlowyResult<()> {
+    let field = self.get_field(field_id).ok_or_else(|| {
+      FlowyError::record_not_found().with_context(format!("Field with id:{} not found", field_id))
+    })?;
+    if FieldType::from(field.field_type) != FieldType::DateTime {
+      return Err(
+        FlowyError::invalid_data().with_context(format!("{} isn't a date field", field.name)),
+      );
+    }
+
+    let timestamp = timestamp_from_local_datetime(local_datetime, timezone_id)?;
+    let end_timestamp = end
+      .map(|end| timestamp_from_local_datetime(end, timezone_id))
+      .transpose()?;
+    if matches!(end_timestamp, Some(end_timestamp) if end_timestamp < timestamp) {
+      return Err(FlowyError::invalid_data().with_context("The end date is before the start"));
+    }
+
+    let changeset = DateCellChangeset {
+      date: Some(timestamp),
+      end_date: end_timestamp,
+      include_time: Some(include_time),
+      is_range: end_timestamp.map(|_| true),
+      ..Default::default()
+    };
+    self
+      .update_cell_with_changeset(view_id, row_id, field_id, BoxAny::new(changeset))
+      .await
+  }
+
   async fn update_last_modified_time(&self, row_detail: RowDetail, view_id: &str) {
+    let now = self.now();
     self
       .database
       .lock()
       .update_row(&row_detail.row.id, |row_update| {
-        row_update.set_last_modified(timestamp());
+        row_update.set_last_modified(now);
       });
 
     let editor = self.database_views.get_view_editor(view_id).await;
@@ -799,37 +2221,129 @@ impl DatabaseEditor {
 
   /// Update a cell in the database.
   /// This will notify all views that the cell has been updated.
+  /// Returns [ErrorCode::RecordLocked] if the row is locked.
   pub async fn update_cell(
     &self,
     view_id: &str,
     row_id: &RowId,
     field_id: &str,
     new_cell: Cell,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    if let Some(field) = self.get_field(field_id) {
+      validate_field_cell(&field, &new_cell)?;
+    }
+    self
+      .write_cell(view_id, row_id, field_id, new_cell, false)
+      .await
+  }
+
+  /// Writes the cell. Returns [ErrorCode::RecordLocked] if the row is locked, unless
+  /// `ignore_locked` is true, e.g. when the cells are copied to a new field.
+  async fn write_cell(
+    &self,
+    view_id: &str,
+    row_id: &RowId,
+    field_id: &str,
+    new_cell: Cell,
+    ignore_locked: bool,
   ) -> FlowyResult<()> {
     // Get the old row before updating the cell. It would be better to get the old cell
     let old_row = { self.get_row_detail(view_id, row_id) };
-    self.database.lock().update_row(row_id, |row_update| {
-      row_update.update_cells(|cell_update| {
-        cell_update.insert(field_id, new_cell);
+    let now = self.now();
+    {
+      let database = self.database.lock();
+      if !ignore_locked {
+        ensure_row_not_locked(&database, row_id)?;
+      }
+      database.update_row(row_id, |row_update| {
+        row_update
+          .update_cells(|cell_update| {
+            cell_update.insert(field_id, new_cell);
+          })
+          .set_last_modified(now);
       });
-    });
+      set_rows_last_edited_by(&database, [row_id], self.uid);
+    }
+    self.record_unsynced_row_edits([row_id]);
 
     self
       .did_update_row(view_id, row_id, field_id, old_row)
       .await;
+    Ok(())
+  }
+
+  /// Writes the cells of the field with a single lock of the database. The locked rows are
+  /// skipped. None of the cells is written if one of them breaks the validation of the field.
+  async fn write_cells(
+    &self,
+    view_id: &str,
+    field: &Field,
+    new_cells: Vec<(RowId, Cell)>,
+  ) -> FlowyResult<()> {
+    for (_, new_cell) in new_cells.iter() {
+      validate_field_cell(field, new_cell)?;
+    }
+    let field_id = field.id.as_str();
+    let old_rows = new_cells
+      .iter()
+      .map(|(row_id, _)| self.get_row_detail(view_id, row_id))
+      .collect::<Vec<_>>();
+    let now = self.now();
+    let updated_rows = {
+      let database = self.database.lock();
+      let updated_rows = new_cells
+        .into_iter()
+        .zip(old_rows)
+        .filter(|((row_id, _), _)| !is_row_locked(&database, row_id))
+        .map(|((row_id, new_cell), old_row)| {
+          database.update_row(&row_id, |row_update| {
+            row_update
+              .update_cells(|cell_update| {
+                cell_update.insert(field_id, new_cell);
+              })
+              .set_last_modified(now);
+          });
+          (row_id, old_row)
+        })
+        .collect::<Vec<_>>();
+      set_rows_last_edited_by(
+        &database,
+        updated_rows.iter().map(|(row_id, _)| row_id),
+        self.uid,
+      );
+      updated_rows
+    };
+    self.record_unsynced_row_edits(updated_rows.iter().map(|(row_id, _)| row_id));
 
+    for (row_id, old_row) in updated_rows {
+      self
+        .did_update_row(view_id, &row_id, field_id, old_row)
+        .await;
+    }
     Ok(())
   }
 
+  /// Returns [ErrorCode::RecordLocked] if the row is locked.
   pub async fn clear_cell(&self, view_id: &str, row_id: RowId, field_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    if let Some(field) = self.get_field(field_id) {
+      validate_field_text(&field, "")?;
+    }
     // Get the old row before updating the cell. It would be better to get the old cell
     let old_row = { self.get_row_detail(view_id, &row_id) };
 
-    self.database.lock().update_row(&row_id, |row_update| {
-      row_update.update_cells(|cell_update| {
-        cell_update.clear(field_id);
-      });
-    });
+    {
+      let database = self.database.lock();
+      ensure_row_not_locked(&database, &row_id)?;
+      database.update_row(&row_id, |row_update| {
+        row_update.update_cells(|cell_update| {
+          cell_update.clear(field_id);
+        });
+      });
+      set_rows_last_edited_by(&database, [&row_id], self.uid);
+    }
+    self.record_unsynced_row_edits([&row_id]);
 
     self
       .did_update_row(view_id, &row_id, field_id, old_row)
@@ -845,6 +2359,7 @@ impl DatabaseEditor {
     field_id: &str,
     old_row: Option<RowDetail>,
   ) {
+    self.primary_value_index.did_update_cell(row_id, field_id);
     let option_row = self.get_row_detail(view_id, row_id);
     if let Some(new_row_detail) = option_row {
       for view in self.database_views.editors().await {
@@ -887,10 +2402,40 @@ impl DatabaseEditor {
   ) -> Option<SelectOptionPB> {
     let field = self.database.lock().fields.get_field(field_id)?;
     let type_option = select_type_option_from_field(&field).ok()?;
-    let select_option = type_option.create_option(&option_name);
+    let mut select_option = type_option.create_option(&option_name);
+    let option_id = self.id_generator.read().next_id(IdKind::SelectOption);
+    if type_option
+      .options()
+      .iter()
+      .any(|option| option.id == option_id)
+    {
+      warn!("{}", id_already_exists_error(&option_id));
+      return None;
+    }
+    select_option.id = option_id;
     Some(SelectOptionPB::from(select_option))
   }
 
+  /// Returns all the options of a single or multi-select field in the order of the field,
+  /// including the ones that no cell uses. Returns an error for the other field types.
+  pub async fn get_select_options(&self, field_id: &str) -> FlowyResult<Vec<SelectOptionPB>> {
+    let field = self
+      .database
+      .lock()
+      .fields
+      .get_field(field_id)
+      .ok_or_else(FlowyError::field_record_not_found)?;
+    let type_option = select_type_option_from_field(&field)?;
+    Ok(
+      type_option
+        .options()
+        .iter()
+        .cloned()
+        .map(SelectOptionPB::from)
+        .collect(),
+    )
+  }
+
   /// Insert the options into the field's type option and update the cell content with the new options.
   /// Only used for single select and multiple select.
   pub async fn insert_select_options(
@@ -900,6 +2445,7 @@ impl DatabaseEditor {
     row_id: RowId,
     options: Vec<SelectOptionPB>,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let field = self
       .database
       .lock()
@@ -925,6 +2471,7 @@ impl DatabaseEditor {
     update_field_type_option_fn(
       &self.database,
       &view_editors,
+      &self.schema_version,
       type_option.to_type_option_data(),
       field.clone(),
     )
@@ -944,6 +2491,7 @@ impl DatabaseEditor {
     row_id: RowId,
     options: Vec<SelectOptionPB>,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let field = match self.database.lock().fields.get_field(field_id) {
       Some(field) => Ok(field),
       None => {
@@ -965,6 +2513,7 @@ impl DatabaseEditor {
     update_field_type_option_fn(
       &self.database,
       &view_editors,
+      &self.schema_version,
       type_option.to_type_option_data(),
       field.clone(),
     )
@@ -1007,6 +2556,11 @@ impl DatabaseEditor {
     Ok(RepeatedGroupPB { items: groups })
   }
 
+  pub async fn get_group_metrics(&self, view_id: &str) -> FlowyResult<Arc<GroupMetrics>> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    Ok(view.v_get_group_metrics())
+  }
+
   #[tracing::instrument(level = "trace", skip_all, err)]
   pub async fn get_group(&self, view_id: &str, group_id: &str) -> FlowyResult<GroupPB> {
     let view = self.database_views.get_view_editor(view_id).await?;
@@ -1021,6 +2575,7 @@ impl DatabaseEditor {
     from_group: &str,
     to_group: &str,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     // Do nothing if the group is the same
     if from_group == to_group {
       return Ok(());
@@ -1040,6 +2595,9 @@ impl DatabaseEditor {
     from_row: RowId,
     to_row: Option<RowId>,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    // Checked before the groups are changed, and again when the cells are written
+    ensure_row_not_locked(&self.database.lock(), &from_row)?;
     let row_detail = self.get_row_detail(view_id, &from_row);
     match row_detail {
       None => {
@@ -1072,7 +2630,9 @@ impl DatabaseEditor {
         }
 
         tracing::trace!("Row data changed: {:?}", row_changeset);
-        self.database.lock().update_row(&row_detail.row.id, |row| {
+        let database = self.database.lock();
+        ensure_row_not_locked(&database, &row_detail.row.id)?;
+        database.update_row(&row_detail.row.id, |row| {
           row.set_cells(Cells::from(row_changeset.cell_by_field_id.clone()));
         });
       },
@@ -1082,12 +2642,25 @@ impl DatabaseEditor {
   }
 
   pub async fn group_by_field(&self, view_id: &str, field_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view = self.database_views.get_view_editor(view_id).await?;
     view.v_group_by_field(field_id).await?;
     Ok(())
   }
 
+  /// Returns the groups and their row counts if the view were grouped by `field_id`. The grouping
+  /// of the view is unchanged, so it can be previewed before calling [Self::set_group_by_field].
+  pub async fn preview_grouping(
+    &self,
+    view_id: &str,
+    field_id: &str,
+  ) -> FlowyResult<Vec<GroupPreviewPB>> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    view.v_preview_grouping(field_id).await
+  }
+
   pub async fn create_group(&self, view_id: &str, name: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(view_id).await?;
     view_editor.v_create_group(name).await?;
     Ok(())
@@ -1099,6 +2672,7 @@ impl DatabaseEditor {
     view_id: &str,
     layout_setting: LayoutSettingChangeset,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
     let view_editor = self.database_views.get_view_editor(view_id).await?;
     view_editor.v_set_layout_settings(layout_setting).await?;
     Ok(())
@@ -1114,13 +2688,102 @@ impl DatabaseEditor {
     Some(layout_setting)
   }
 
+  /// Returns the settings of all the layouts of the view, so they can be shown together. The
+  /// layouts without a stored setting get the default one.
+  pub async fn get_all_layout_settings(
+    &self,
+    view_id: &str,
+  ) -> FlowyResult<HashMap<DatabaseLayout, LayoutSettingParams>> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    Ok(view.v_get_all_layout_settings().await)
+  }
+
+  /// Returns where the rows that are created without a position are inserted in the view. The
+  /// position is read from the grid layout setting whatever the layout of the view, see
+  /// [Self::set_new_row_position].
+  pub fn get_new_row_position(&self, view_id: &str) -> NewRowPosition {
+    self
+      .database
+      .lock()
+      .get_layout_setting::<GridLayoutSetting>(view_id, &DatabaseLayout::Grid)
+      .map(|setting| setting.new_row_position)
+      .unwrap_or_default()
+  }
+
+  /// Sets where the rows that are created without a position are inserted in the view. It only
+  /// changes the stored order of the rows: when the view has sorts, the created row is placed
+  /// according to the sorts, and the sorts keep taking precedence when the view is opened again.
+  ///
+  /// The position is shared by all the layouts of the view. It's saved in the grid layout setting
+  /// even if the view is a board or a calendar, so it's kept when the layout of the view changes.
+  pub async fn set_new_row_position(
+    &self,
+    view_id: &str,
+    position: NewRowPosition,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let _ = self.database_views.get_view_editor(view_id).await?;
+    let setting = GridLayoutSetting {
+      new_row_position: position,
+    };
+    self
+      .database
+      .lock()
+      .insert_layout_setting(view_id, &DatabaseLayout::Grid, setting.into());
+
+    let payload = NewRowPositionSettingPB {
+      view_id: view_id.to_string(),
+      position: position.into(),
+    };
+    send_notification(view_id, DatabaseNotification::DidUpdateNewRowPosition)
+      .payload(payload)
+      .send();
+    Ok(())
+  }
+
+  /// Updates the settings of several layouts of the view at once. None of them is saved if one of
+  /// them is invalid.
   #[tracing::instrument(level = "trace", skip_all)]
-  pub async fn get_all_calendar_events(&self, view_id: &str) -> Vec<CalendarEventPB> {
+  pub async fn set_all_layout_settings(
+    &self,
+    view_id: &str,
+    layout_settings: Vec<LayoutSettingParams>,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    view_editor.v_set_all_layout_settings(layout_settings).await
+  }
+
+  /// Returns the events of the calendar placed by `date_field_id`, or by the date field of the
+  /// calendar layout if it's None. Returns an error if the field is not a date field.
+  #[tracing::instrument(level = "trace", skip_all)]
+  pub async fn get_all_calendar_events(
+    &self,
+    view_id: &str,
+    date_field_id: Option<&str>,
+  ) -> FlowyResult<Vec<CalendarEventPB>> {
+    if let Some(date_field_id) = date_field_id {
+      let field = self
+        .get_field(date_field_id)
+        .ok_or_else(FlowyError::field_record_not_found)?;
+      if !FieldType::from(field.field_type).is_date() {
+        return Err(
+          FlowyError::invalid_data()
+            .with_context(format!("The field:{} is not a date field", date_field_id)),
+        );
+      }
+    }
+
     match self.database_views.get_view_editor(view_id).await {
-      Ok(view) => view.v_get_all_calendar_events().await.unwrap_or_default(),
+      Ok(view) => Ok(
+        view
+          .v_get_all_calendar_events(date_field_id)
+          .await
+          .unwrap_or_default(),
+      ),
       Err(_) => {
         warn!("Can not find the view: {}", view_id);
-        vec![]
+        Ok(vec![])
       },
     }
   }
@@ -1141,7 +2804,11 @@ impl DatabaseEditor {
   }
 
   #[tracing::instrument(level = "trace", skip_all, err)]
+  /// The system fields aren't sent to the views, which don't list them.
   async fn notify_did_insert_database_field(&self, field: Field, index: usize) -> FlowyResult<()> {
+    if is_system_field(&field) {
+      return Ok(());
+    }
     let database_id = self.database.lock().get_database_id();
     let index_field = IndexFieldPB {
       field: FieldPB::new(field),
@@ -1177,13 +2844,413 @@ impl DatabaseEditor {
     Ok(database_view_setting_pb_from_view(view))
   }
 
+  /// Serializes the filters, sorts, groups, calculations, field settings and layout of the view to
+  /// JSON. The JSON can be applied to a view of another database with
+  /// [DatabaseEditor::import_view_settings].
+  pub async fn export_view_settings(&self, view_id: &str) -> FlowyResult<String> {
+    let snapshot = {
+      let database = self.database.lock();
+      let view = database.get_view(view_id).ok_or_else(|| {
+        FlowyError::record_not_found().with_context("Can't find the database view")
+      })?;
+      let calculations: Vec<Calculation> = database.get_all_calculations(view_id);
+      let layout_settings = [DatabaseLayout::Board, DatabaseLayout::Calendar]
+        .into_iter()
+        .flat_map(|layout| {
+          let layout_setting = view.layout_settings.get(&layout).cloned();
+          layout_setting.map(|layout_setting| (layout, layout_setting))
+        })
+        .collect();
+      ViewSettingsSnapshot {
+        layout: view.layout,
+        fields: database
+          .get_fields(None)
+          .into_iter()
+          .map(ViewSettingsField::from)
+          .collect(),
+        filters: view.filters,
+        sorts: view.sorts,
+        group_settings: view.group_settings,
+        calculations: calculations.into_iter().map(CalculationMap::from).collect(),
+        field_settings: view.field_settings.into_inner(),
+        layout_settings,
+      }
+    };
+    serde_json::to_string(&snapshot).map_err(internal_error)
+  }
+
+  /// Replaces the settings of the view with the ones exported by
+  /// [DatabaseEditor::export_view_settings], which may come from another database. The field ids
+  /// of the settings are remapped to the fields of this database by name where they differ. The
+  /// settings whose field can't be found are dropped and reported in the returned warnings.
+  pub async fn import_view_settings(
+    &self,
+    view_id: &str,
+    json: &str,
+  ) -> FlowyResult<ImportViewSettingsResult> {
+    self.ensure_can_edit()?;
+    let snapshot = serde_json::from_str::<ViewSettingsSnapshot>(json)
+      .map_err(|err| FlowyError::invalid_data().with_context(err))?;
+
+    // Switch the layout first, so the settings below aren't overridden by the layout change
+    if self.get_layout_type(view_id).await != snapshot.layout {
+      self.update_view_layout(view_id, snapshot.layout).await?;
+    }
+
+    let fields = self.database.lock().get_fields(None);
+    let resolver = FieldIdResolver::new(&snapshot.fields, &fields);
+    let mut warnings = vec![];
+
+    let is_resolved = |field_id: &str| resolver.resolve(field_id).is_some();
+    let mut filters = vec![];
+    for mut filter in snapshot.filters.into_iter().flat_map(Filter::try_from) {
+      if let FilterInner::Data { field_id, .. } = &filter.inner {
+        if !is_resolved(field_id) {
+          warnings.push(resolver.unresolved_warning("filter", field_id));
+          continue;
+        }
+      }
+
+      let mut removed_field_ids = vec![];
+      filter.retain_filters(&is_resolved, &mut removed_field_ids);
+      for field_id in removed_field_ids {
+        warnings.push(resolver.unresolved_warning("filter", &field_id));
+      }
+      let mut removed_options = vec![];
+      filter.remap_select_option_ids(
+        &|field_id, option_id| resolver.resolve_option(field_id, option_id),
+        &mut removed_options,
+      );
+      for (field_id, option_id) in removed_options {
+        warnings.push(resolver.unresolved_option_warning("filter", &field_id, &option_id));
+      }
+      filter.replace_field_ids(resolver.new_field_ids());
+      if !filter.is_empty() {
+        filters.push(filter);
+      }
+    }
+
+    let mut sorts = vec![];
+    for mut sort in snapshot.sorts.into_iter().flat_map(Sort::try_from) {
+      match resolver.resolve(&sort.field_id) {
+        Some(field_id) => {
+          sort.field_id = field_id;
+          sorts.push(sort);
+        },
+        None => warnings.push(resolver.unresolved_warning("sort", &sort.field_id)),
+      }
+    }
+
+    let mut group_settings = vec![];
+    for mut setting in snapshot
+      .group_settings
+      .into_iter()
+      .flat_map(GroupSetting::try_from)
+    {
+      match resolver.resolve(&setting.field_id) {
+        Some(field_id) => {
+          let field_type = FieldType::from(setting.field_type);
+          if matches!(field_type, FieldType::SingleSelect | FieldType::MultiSelect) {
+            // The groups of the select fields are keyed by option id, except the group of the
+            // cells without options, which is keyed by the field id
+            let old_field_id = setting.field_id.clone();
+            setting.groups.retain_mut(|group| {
+              if group.id == old_field_id {
+                group.id = field_id.clone();
+                return true;
+              }
+              match resolver.resolve_option(&old_field_id, &group.id) {
+                Some(option_id) => {
+                  group.id = option_id;
+                  true
+                },
+                None => {
+                  warnings.push(resolver.unresolved_option_warning(
+                    "group",
+                    &old_field_id,
+                    &group.id,
+                  ));
+                  false
+                },
+              }
+            });
+          } else if field_id != setting.field_id {
+            // The groups of another field are built from its own cells, so they can't be reused
+            setting.groups.clear();
+          }
+          setting.field_id = field_id;
+          group_settings.push(GroupSettingMap::from(setting));
+        },
+        None => warnings.push(resolver.unresolved_warning("group", &setting.field_id)),
+      }
+    }
+
+    let mut calculations = vec![];
+    for calculation in snapshot
+      .calculations
+      .into_iter()
+      .flat_map(Calculation::try_from)
+    {
+      match resolver.resolve(&calculation.field_id) {
+        Some(field_id) => calculations.push((field_id, calculation.calculation_type)),
+        None => warnings.push(resolver.unresolved_warning("calculation", &calculation.field_id)),
+      }
+    }
+
+    let mut field_settings = vec![];
+    for (field_id, field_settings_map) in snapshot.field_settings {
+      match resolver.resolve(&field_id) {
+        Some(new_field_id) => field_settings.push(FieldSettings::from_any_map(
+          &new_field_id,
+          snapshot.layout,
+          &field_settings_map,
+        )),
+        None => warnings.push(resolver.unresolved_warning("field setting", &field_id)),
+      }
+    }
+
+    let mut layout_settings = vec![];
+    for (layout, layout_setting) in snapshot.layout_settings {
+      if layout == DatabaseLayout::Calendar {
+        let mut calendar_setting = CalendarLayoutSetting::from(layout_setting);
+        match resolver.resolve(&calendar_setting.field_id) {
+          Some(field_id) => {
+            calendar_setting.field_id = field_id;
+            layout_settings.push((layout, calendar_setting.into()));
+          },
+          None => warnings.push(
+            resolver.unresolved_warning("calendar layout setting", &calendar_setting.field_id),
+          ),
+        }
+      } else {
+        layout_settings.push((layout, layout_setting));
+      }
+    }
+
+    {
+      let database = self.database.lock();
+      database.save_filters::<Filter, FilterMap>(view_id, &filters);
+      database.remove_all_sorts(view_id);
+      for sort in sorts {
+        database.insert_sort(view_id, sort);
+      }
+      database.views.update_database_view(view_id, |view| {
+        view.set_groups(group_settings);
+      });
+      let old_calculations: Vec<Calculation> = database.get_all_calculations(view_id);
+      for calculation in old_calculations {
+        database.remove_calculation(view_id, &calculation.id);
+      }
+      for field_settings in field_settings {
+        let field_ids = vec![field_settings.field_id.clone()];
+        database.update_field_settings(view_id, Some(field_ids), field_settings);
+      }
+      for (layout, layout_setting) in layout_settings {
+        database.insert_layout_setting(view_id, &layout, layout_setting);
+      }
+    }
+
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    view_editor.v_reload_settings().await?;
+    for (field_id, calculation_type) in calculations {
+      view_editor
+        .v_update_calculations(UpdateCalculationChangesetPB {
+          view_id: view_id.to_string(),
+          calculation_id: None,
+          field_id,
+          calculation_type: calculation_type.into(),
+        })
+        .await?;
+    }
+
+    let setting = self.get_database_view_setting(view_id).await?;
+    send_notification(view_id, DatabaseNotification::DidUpdateSettings)
+      .payload(setting)
+      .send();
+    Ok(ImportViewSettingsResult { warnings })
+  }
+
+  /// Restores the view to a clean state: its filters, sorts, groups and calculations are removed,
+  /// the settings of its fields go back to the defaults of its layout and its rows go back to the
+  /// order they were added to the database in, which is the order of the inline view. The fields
+  /// and the layout settings are kept. The settings are written while holding the lock of the
+  /// database, so no other edit lands in between, and the view is refreshed with one
+  /// [DatabaseNotification::DidResetView]. Resetting a view twice is the same as resetting it once.
+  pub async fn reset_view(&self, view_id: &str) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    let overrides = self.get_default_field_settings();
+    {
+      let database = self.database.lock();
+      let layout = database.views.get_database_view_layout(view_id);
+      let filters: Vec<Filter> = vec![];
+      database.save_filters::<Filter, FilterMap>(view_id, &filters);
+      database.remove_all_sorts(view_id);
+      database.views.update_database_view(view_id, |view| {
+        view.set_groups(vec![]);
+      });
+      let calculations: Vec<Calculation> = database.get_all_calculations(view_id);
+      for calculation in calculations {
+        database.remove_calculation(view_id, &calculation.id);
+      }
+      for field in database.get_fields(None) {
+        let field_settings = default_field_settings_for_field(&overrides, layout, &field);
+        database.update_field_settings(view_id, Some(vec![field.id.clone()]), field_settings);
+      }
+
+      let row_orders = database
+        .get_row_orders_for_view(view_id)
+        .into_iter()
+        .map(|row_order| row_order.id)
+        .collect::<Vec<RowId>>();
+      // The inline view has every row of the database in the order they were added
+      let view_row_ids = row_orders.iter().cloned().collect::<HashSet<RowId>>();
+      let new_row_orders = database
+        .get_row_orders_for_view(&database.get_inline_view_id())
+        .into_iter()
+        .map(|row_order| row_order.id)
+        .filter(|row_id| view_row_ids.contains(row_id))
+        .collect::<Vec<RowId>>();
+      if new_row_orders != row_orders {
+        reorder_rows_in_view(&database, view_id, row_orders, &new_row_orders);
+      }
+    }
+
+    view_editor.v_reload_settings().await?;
+    let setting = self.get_database_view_setting(view_id).await?;
+    send_notification(view_id, DatabaseNotification::DidResetView)
+      .payload(setting)
+      .send();
+    Ok(())
+  }
+
+  /// Serializes the fields and the rows of the view to JSON. Unlike the CSV export, the values
+  /// keep their types. The JSON can be imported with [DatabaseEditor::import_json]. The system
+  /// fields are only exported if `include_system_fields` is true.
+  pub async fn export_json(
+    &self,
+    view_id: &str,
+    include_system_fields: bool,
+  ) -> FlowyResult<String> {
+    let fields = if include_system_fields {
+      self.get_fields_including_system(view_id, None)
+    } else {
+      self.get_fields(view_id, None)
+    };
+    let rows = self
+      .get_rows(view_id)
+      .await?
+      .into_iter()
+      .map(|row_detail| {
+        fields
+          .iter()
+          .flat_map(|field| {
+            let cell = row_detail.row.cells.get(&field.id)?;
+            let value = cell_to_json_value(field, cell)?;
+            Some((field.id.clone(), value))
+          })
+          .collect()
+      })
+      .collect();
+    let data = DatabaseJsonData {
+      fields: fields.iter().map(DatabaseJsonField::from).collect(),
+      rows,
+    };
+    serde_json::to_string(&data).map_err(internal_error)
+  }
+
+  /// Appends the rows exported by [DatabaseEditor::export_json], which may come from another
+  /// database, to the view. The fields of the JSON are matched with the fields of this database by
+  /// id and then by name, and the select options that don't exist yet are created. The values
+  /// that can't be imported are reported in the returned result instead of being dropped silently.
+  pub async fn import_json(&self, view_id: &str, json: &str) -> FlowyResult<ImportJsonResult> {
+    self.ensure_can_edit()?;
+    let data = serde_json::from_str::<DatabaseJsonData>(json)
+      .map_err(|err| FlowyError::invalid_data().with_context(err))?;
+
+    let mut result = ImportJsonResult::default();
+    let database_fields = self.get_fields_including_system(view_id, None);
+    let mut fields = match_json_fields(&data.fields, &database_fields, &mut result);
+
+    // Create the select options that are missing before building the cells
+    for (json_field_id, field) in fields.iter_mut() {
+      if !FieldType::from(field.field_type).is_select_option() {
+        continue;
+      }
+      let mut type_option = select_type_option_from_field(field)?;
+      let mut is_changed = false;
+      for value in data.rows.iter().flat_map(|row| row.get(json_field_id)) {
+        for name in json_select_option_names(value) {
+          let is_missing = type_option
+            .options()
+            .iter()
+            .all(|option| option.name != name);
+          if is_missing {
+            let option = type_option.create_option(&name);
+            type_option.insert_option(option);
+            is_changed = true;
+          }
+        }
+      }
+      if is_changed {
+        self
+          .update_field_type_option(&field.id, type_option.to_type_option_data(), field.clone())
+          .await?;
+        if let Some(updated_field) = self.get_field(&field.id) {
+          *field = updated_field;
+        }
+      }
+    }
+
+    let mut rows = vec![];
+    for (index, json_row) in data.rows.iter().enumerate() {
+      let mut cells = Cells::new();
+      for (json_field_id, value) in json_row {
+        let Some(field) = fields.get(json_field_id) else {
+          continue;
+        };
+        if value.is_null() {
+          continue;
+        }
+        match json_value_to_cell(field, value)
+          .and_then(|cell| validate_field_cell(field, &cell).map(|_| cell))
+        {
+          Ok(cell) => {
+            cells.insert(field.id.clone(), cell);
+          },
+          Err(err) => result.errors.push(format!("Row {}: {}", index + 1, err)),
+        }
+      }
+      rows.push(cells);
+    }
+
+    result.rows_imported = self.create_rows_batch(view_id, rows).await?.len();
+    Ok(result)
+  }
+
+  /// Returns the data that is used to open the view. The time spent in loading the rows is
+  /// recorded as an `OpenProfile`.
+  ///
+  /// With a [LowMemoryProfile], only the first [LowMemoryProfile::initial_row_limit] rows are
+  /// returned and the rest are loaded by [Self::get_database_rows].
   pub async fn get_database_data(&self, view_id: &str) -> FlowyResult<DatabasePB> {
+    let start = Instant::now();
     let database_view = self.database_views.get_view_editor(view_id).await?;
+    self.repair_missing_field_settings(view_id)?;
     let view = database_view
       .v_get_view()
       .await
       .ok_or_else(FlowyError::record_not_found)?;
-    let rows = database_view.v_get_rows().await;
+    let row_limit = self
+      .low_memory_profile
+      .map(|profile| profile.initial_row_limit);
+    let load_generation = self.load_tracker.will_load_rows();
+    let (rows, has_more_rows, mut profile) = database_view.v_get_rows_with_profile(row_limit).await;
+    self.load_tracker.did_load_rows(load_generation);
+    // Includes the time spent in opening the view editor
+    profile.total = start.elapsed();
+    tracing::trace!("open database view: {:?}", profile);
+    self.open_profiles.record(profile);
     let (database_id, fields, is_linked) = {
       let database = self.database.lock();
       let database_id = database.get_database_id();
@@ -1197,29 +3264,491 @@ impl DatabaseEditor {
       (database_id, fields, is_linked)
     };
 
-    let rows = rows
+    let rows = {
+      let database = self.database.lock();
+      rows
+        .into_iter()
+        .map(|row_detail| row_meta_pb(&database, &row_detail))
+        .collect::<Vec<RowMetaPB>>()
+    };
+    Ok(DatabasePB {
+      id: database_id,
+      fields,
+      rows,
+      layout_type: view.layout.into(),
+      is_linked,
+      has_more_rows,
+    })
+  }
+
+  /// Returns at most `limit` rows of the view, starting from the `offset`th row in the order that
+  /// the view displays them.
+  pub async fn get_database_rows(
+    &self,
+    view_id: &str,
+    offset: usize,
+    limit: usize,
+  ) -> FlowyResult<Vec<RowMetaPB>> {
+    let view_editor = self.database_views.get_view_editor(view_id).await?;
+    let rows = view_editor.v_get_rows_page(offset, limit).await;
+    let database = self.database.lock();
+    Ok(
+      rows
+        .iter()
+        .map(|row_detail| row_meta_pb(&database, row_detail))
+        .collect(),
+    )
+  }
+
+  pub async fn export_csv(
+    &self,
+    style: CSVFormat,
+    options: CSVExportOptions,
+  ) -> FlowyResult<String> {
+    let database = self.database.clone();
+    let csv = tokio::task::spawn_blocking(move || {
+      let database_guard = database.lock();
+      let csv = CSVExport.export_database(&database_guard, style, &options)?;
+      Ok::<String, FlowyError>(csv)
+    })
+    .await
+    .map_err(internal_error)??;
+    Ok(csv)
+  }
+
+  /// Same as [Self::export_csv], but also returns the SHA-256 checksum of the CSV.
+  pub async fn export_csv_with_checksum(
+    &self,
+    style: CSVFormat,
+    options: CSVExportOptions,
+  ) -> FlowyResult<(String, String)> {
+    let database = self.database.clone();
+    tokio::task::spawn_blocking(move || {
+      let database_guard = database.lock();
+      CSVExport.export_database_with_checksum(&database_guard, style, &options)
+    })
+    .await
+    .map_err(internal_error)?
+  }
+
+  /// Writes the CSV to `path` and returns its SHA-256 checksum. See
+  /// [CSVExport::export_database_to_file].
+  pub async fn export_csv_to_file(
+    &self,
+    style: CSVFormat,
+    options: CSVExportOptions,
+    path: PathBuf,
+    write_checksum_file: bool,
+  ) -> FlowyResult<String> {
+    let database = self.database.clone();
+    tokio::task::spawn_blocking(move || {
+      let database_guard = database.lock();
+      CSVExport.export_database_to_file(
+        &database_guard,
+        style,
+        &options,
+        &path,
+        write_checksum_file,
+      )
+    })
+    .await
+    .map_err(internal_error)?
+  }
+
+  /// Exports what the view shows: the rows that pass its filters in the order of its sorts, with
+  /// its visible fields as the columns. Unlike [Self::export_csv], the filtered out rows and the
+  /// hidden fields are left out. If every row is filtered out, only the header is exported.
+  pub async fn export_view_csv(&self, view_id: &str, style: CSVFormat) -> FlowyResult<String> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    let fields = self.get_visible_fields(&view, view_id).await;
+    let rows = view
+      .v_get_rows()
+      .await
+      .into_iter()
+      .map(|row_detail| row_detail.row.clone())
+      .collect::<Vec<Row>>();
+    let row_users = self.get_row_users(&fields);
+
+    tokio::task::spawn_blocking(move || {
+      CSVExport.export_rows(fields, rows, row_users, style, &CSVExportOptions::default())
+    })
+    .await
+    .map_err(internal_error)?
+  }
+
+  /// Exports the selected rows of the view, like [Self::export_view_csv] does for all of them. The
+  /// rows are exported in the order the view shows them, whatever the order of `row_ids`. The ids
+  /// of the rows that the view doesn't show are skipped.
+  pub async fn export_rows_csv(
+    &self,
+    view_id: &str,
+    row_ids: Vec<RowId>,
+    style: CSVFormat,
+  ) -> FlowyResult<String> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    let fields = self.get_visible_fields(&view, view_id).await;
+    let mut row_ids = row_ids.into_iter().collect::<HashSet<RowId>>();
+    let rows = view
+      .v_get_rows()
+      .await
+      .into_iter()
+      .filter(|row_detail| row_ids.remove(&row_detail.row.id))
+      .map(|row_detail| row_detail.row.clone())
+      .collect::<Vec<Row>>();
+    if !row_ids.is_empty() {
+      warn!(
+        "Skip exporting {} rows that aren't in the view {}: {:?}",
+        row_ids.len(),
+        view_id,
+        row_ids
+      );
+    }
+    let row_users = self.get_row_users(&fields);
+
+    tokio::task::spawn_blocking(move || {
+      CSVExport.export_rows(fields, rows, row_users, style, &CSVExportOptions::default())
+    })
+    .await
+    .map_err(internal_error)?
+  }
+
+  /// Returns the users of the rows if one of the fields is a user field.
+  fn get_row_users(&self, fields: &[Field]) -> RowUsersByRowId {
+    if fields
+      .iter()
+      .any(|field| FieldType::from(field.field_type).is_user())
+    {
+      get_all_row_users(&self.database.lock())
+    } else {
+      RowUsersByRowId::new()
+    }
+  }
+
+  /// Returns the fields of the view in their order, without the ones it hides.
+  async fn get_visible_fields(&self, view: &DatabaseViewEditor, view_id: &str) -> Vec<Field> {
+    let fields = self.get_fields(view_id, None);
+    let field_ids = fields
+      .iter()
+      .map(|field| field.id.clone())
+      .collect::<Vec<String>>();
+    let field_settings = view.v_get_field_settings(&field_ids).await;
+    fields
+      .into_iter()
+      .filter(|field| {
+        field_settings
+          .get(&field.id)
+          .map(|field_settings| field_settings.is_visible())
+          .unwrap_or(true)
+      })
+      .collect()
+  }
+
+  /// Imports the rows of a CSV into the view. The CSV is read incrementally, so it doesn't have to
+  /// fit in memory, and the rows are created in batches by [Self::create_rows_batch]. The progress
+  /// is sent to `progress` after each batch.
+  ///
+  /// `mapping` maps the header of a column to the id of the field that the column is imported
+  /// into. The columns that aren't mapped are imported into the field with the same name, or
+  /// skipped if there is none. The malformed lines are skipped and returned with the result.
+  ///
+  /// Once `cancel_token` is cancelled, the import stops before the next batch and the rows that
+  /// are already imported are kept.
+  ///
+  /// The CSV can be exported with [CSVExportOptions::include_row_metadata] and imported again to
+  /// update the rows in place. A record whose [ROW_ID_COLUMN] matches an existing row updates
+  /// that row instead of creating a new one: the cells of the imported columns are overwritten,
+  /// the cells of the empty values are cleared and the other cells are kept. The records of the
+  /// rows that don't exist in this database create new rows with new ids. The creation and
+  /// modification times are never imported, and the locked rows are skipped and reported as
+  /// errors.
+  pub async fn import_csv_stream<R>(
+    &self,
+    view_id: &str,
+    reader: R,
+    mapping: HashMap<String, String>,
+    options: CSVStreamImportOptions,
+    progress: mpsc::Sender<CSVImportProgress>,
+    cancel_token: CancellationToken,
+  ) -> FlowyResult<CSVStreamImportResult>
+  where
+    R: Read + Send + 'static,
+  {
+    self.ensure_can_edit()?;
+    let fields = self.get_fields(view_id, None);
+    let delimiter = options.delimiter;
+    let mut batch_reader =
+      tokio::task::spawn_blocking(move || CSVBatchReader::new(reader, delimiter))
+        .await
+        .map_err(internal_error)??;
+    let row_id_index = batch_reader
+      .headers()
+      .iter()
+      .position(|header| header == ROW_ID_COLUMN);
+    let columns = batch_reader
+      .headers()
+      .iter()
+      .map(|header| match mapping.get(header) {
+        _ if ROW_METADATA_COLUMNS.contains(&header.as_str()) => None,
+        Some(field_id) => fields.iter().find(|field| &field.id == field_id).cloned(),
+        None => fields.iter().find(|field| &field.name == header).cloned(),
+      })
+      .collect::<Vec<Option<Field>>>();
+
+    // Read the next batch while the current one is being created
+    let batch_size = match &self.low_memory_profile {
+      Some(profile) => options.batch_size.min(profile.import_batch_size),
+      None => options.batch_size,
+    }
+    .max(1);
+    let (tx, mut rx) = mpsc::channel(1);
+    tokio::task::spawn_blocking(move || {
+      while let Some(batch) = batch_reader.next_batch(batch_size) {
+        if tx.blocking_send(batch).is_err() {
+          break;
+        }
+      }
+    });
+
+    let mut result = CSVStreamImportResult::default();
+    let mut rows_read = 0;
+    let mut rows_total_estimate = 0;
+    while let Some(batch) = rx.recv().await {
+      if cancel_token.is_cancelled() {
+        result.is_cancelled = true;
+        break;
+      }
+
+      rows_read += batch.records.len();
+      rows_total_estimate = estimate_total_rows(rows_read, batch.bytes_read, options.total_bytes);
+      let mut new_rows = vec![];
+      let mut updated_rows = vec![];
+      for record in batch.records {
+        let existing_row_id = row_id_index
+          .and_then(|index| record.values.get(index))
+          .filter(|row_id| !row_id.is_empty())
+          .map(|row_id| RowId::from(row_id.clone()))
+          .filter(|row_id| self.get_row_detail(view_id, row_id).is_some());
+        let line = record.line;
+        let (cells, empty_field_ids) = csv_record_to_cells(record.values, &columns);
+        if let Err(err) = validate_csv_cells(&cells, &empty_field_ids, &fields) {
+          result.errors.push(CSVImportError {
+            line,
+            message: err.msg,
+          });
+          continue;
+        }
+        match existing_row_id {
+          Some(row_id) if self.is_row_locked(&row_id) => result.errors.push(CSVImportError {
+            line,
+            message: format!("The row {} is locked", row_id),
+          }),
+          Some(row_id) => updated_rows.push((row_id, cells, empty_field_ids)),
+          None => new_rows.push(cells),
+        }
+      }
+      result.rows_imported += self.create_rows_batch(view_id, new_rows).await?.len();
+      let rows_updated = self.update_rows_cells(view_id, updated_rows).await;
+      result.rows_imported += rows_updated;
+      result.rows_updated += rows_updated;
+      result.errors.extend(batch.errors);
+      let _ = progress
+        .send(CSVImportProgress {
+          rows_imported: result.rows_imported,
+          rows_total_estimate,
+        })
+        .await;
+    }
+
+    // The estimate is exact once the whole CSV is read
+    if !result.is_cancelled && rows_total_estimate != rows_read {
+      let _ = progress
+        .send(CSVImportProgress {
+          rows_imported: result.rows_imported,
+          rows_total_estimate: rows_read,
+        })
+        .await;
+    }
+    Ok(result)
+  }
+
+  /// Writes the cells of the rows and clears the cells of the given fields with a single lock of
+  /// the database. The locked rows are skipped. Returns the number of the updated rows.
+  async fn update_rows_cells(
+    &self,
+    view_id: &str,
+    rows: Vec<(RowId, Cells, Vec<String>)>,
+  ) -> usize {
+    let old_rows = rows
+      .iter()
+      .map(|(row_id, _, _)| self.get_row_detail(view_id, row_id))
+      .collect::<Vec<_>>();
+    let updated_rows = {
+      let database = self.database.lock();
+      let updated_rows = rows
+        .into_iter()
+        .zip(old_rows)
+        .filter(|((row_id, _, _), _)| !is_row_locked(&database, row_id))
+        .map(|((row_id, cells, cleared_field_ids), old_row)| {
+          let field_ids = cells
+            .keys()
+            .chain(cleared_field_ids.iter())
+            .cloned()
+            .collect::<Vec<String>>();
+          database.update_row(&row_id, |row_update| {
+            row_update.update_cells(|cell_update| {
+              let cell_update = cells
+                .iter()
+                .fold(cell_update, |cell_update, (field_id, cell)| {
+                  cell_update.insert(field_id, cell.clone())
+                });
+              cleared_field_ids
+                .iter()
+                .fold(cell_update, |cell_update, field_id| {
+                  cell_update.clear(field_id)
+                });
+            });
+          });
+          (row_id, field_ids, old_row)
+        })
+        .collect::<Vec<_>>();
+      set_rows_last_edited_by(
+        &database,
+        updated_rows.iter().map(|(row_id, _, _)| row_id),
+        self.uid,
+      );
+      updated_rows
+    };
+    self.record_unsynced_row_edits(updated_rows.iter().map(|(row_id, _, _)| row_id));
+
+    for (row_id, field_ids, old_row) in updated_rows.iter() {
+      if let Some(new_row) = self.get_row_detail(view_id, row_id) {
+        for view in self.database_views.editors().await {
+          view
+            .v_did_update_row_cells(old_row, &new_row, field_ids.clone())
+            .await;
+        }
+      }
+    }
+    updated_rows.len()
+  }
+
+  /// Returns the params to create a new database with the same fields, field settings, views and
+  /// calculations as this one, but without any rows. The ids of the database, its views, its
+  /// fields and the filters, sorts, groups and calculations of its views are regenerated, so the
+  /// new database is independent of this one. The relation fields still point at the databases
+  /// they're related to.
+  ///
+  /// Use [crate::manager::DatabaseManager::create_database_with_clone_params] to create the clone.
+  pub async fn clone_structure(&self) -> FlowyResult<CloneDatabaseParams> {
+    let (params, _) = self.clone_structure_with_id_map().await?;
+    Ok(params)
+  }
+
+  /// Same as [Self::clone_structure], but also returns the ids of the clone mapped from the ids
+  /// of this database, so the references to this database can be pointed at the clone.
+  pub async fn clone_structure_with_id_map(
+    &self,
+  ) -> FlowyResult<(CloneDatabaseParams, CloneIdMap)> {
+    let (old_database_id, inline_view_id, fields, views, calculations) = {
+      let database = self.database.lock();
+      let views = database
+        .get_all_database_views_meta()
+        .into_iter()
+        .flat_map(|view_meta| database.get_view(&view_meta.id))
+        .collect::<Vec<DatabaseView>>();
+      let inline_view_id = views
+        .iter()
+        .find(|view| database.is_inline_view(&view.id))
+        .map(|view| view.id.clone())
+        .ok_or_else(|| {
+          FlowyError::record_not_found().with_context("Can't find the inline view of the database")
+        })?;
+      let fields = database.get_fields_in_view(&inline_view_id, None);
+      let calculations = views
+        .iter()
+        .map(|view| {
+          let calculations: Vec<Calculation> = database.get_all_calculations(&view.id);
+          (view.id.clone(), calculations)
+        })
+        .collect::<Vec<_>>();
+      (
+        database.get_database_id(),
+        inline_view_id,
+        fields,
+        views,
+        calculations,
+      )
+    };
+
+    let new_field_ids = fields
+      .iter()
+      .map(|field| (field.id.clone(), gen_field_id()))
+      .collect::<HashMap<String, String>>();
+    let fields = fields
+      .into_iter()
+      .map(|mut field| {
+        field.id = new_field_ids[&field.id].clone();
+        field
+      })
+      .collect::<Vec<Field>>();
+
+    let database_id = gen_database_id();
+    let new_inline_view_id = gen_database_view_id();
+    let mut new_view_ids = HashMap::new();
+    let views = views
       .into_iter()
-      .map(|row_detail| RowMetaPB::from(row_detail.as_ref()))
-      .collect::<Vec<RowMetaPB>>();
-    Ok(DatabasePB {
-      id: database_id,
-      fields,
-      rows,
-      layout_type: view.layout.into(),
-      is_linked,
-    })
-  }
+      .map(|view| {
+        let view_id = if view.id == inline_view_id {
+          new_inline_view_id.clone()
+        } else {
+          gen_database_view_id()
+        };
+        new_view_ids.insert(view.id.clone(), view_id.clone());
+        create_view_params_from_view(view, &database_id, &view_id, &new_field_ids)
+      })
+      .collect();
 
-  pub async fn export_csv(&self, style: CSVFormat) -> FlowyResult<String> {
-    let database = self.database.clone();
-    let csv = tokio::task::spawn_blocking(move || {
-      let database_guard = database.lock();
-      let csv = CSVExport.export_database(&database_guard, style)?;
-      Ok::<String, FlowyError>(csv)
-    })
-    .await
-    .map_err(internal_error)??;
-    Ok(csv)
+    // The clone has no rows, so the values of the calculations are computed again when its views
+    // are opened
+    let calculations = calculations
+      .into_iter()
+      .map(|(view_id, calculations)| {
+        let calculations = calculations
+          .into_iter()
+          .flat_map(|calculation| {
+            let field_id = new_field_ids.get(&calculation.field_id)?;
+            Some(Calculation {
+              id: gen_database_calculation_id(),
+              field_id: field_id.clone(),
+              calculation_type: calculation.calculation_type,
+              value: "".to_string(),
+            })
+          })
+          .collect();
+        (new_view_ids[&view_id].clone(), calculations)
+      })
+      .collect();
+
+    let id_map = CloneIdMap {
+      old_database_id,
+      new_database_id: database_id.clone(),
+      view_ids: new_view_ids,
+      field_ids: new_field_ids,
+    };
+    let params = CreateDatabaseParams {
+      database_id,
+      inline_view_id: new_inline_view_id,
+      views,
+      rows: vec![],
+      fields,
+    };
+    Ok((
+      CloneDatabaseParams {
+        params,
+        calculations,
+      },
+      id_map,
+    ))
   }
 
   pub async fn get_field_settings(
@@ -1252,12 +3781,211 @@ impl DatabaseEditor {
     &self,
     params: FieldSettingsChangesetPB,
   ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    if params.visibility.is_some() {
+      self.ensure_not_system_field(&params.field_id)?;
+    }
+    if params.visibility == Some(FieldVisibility::AlwaysHidden) {
+      let visibility = HashMap::from([(params.field_id.clone(), false)]);
+      self
+        .ensure_any_field_visible(&params.view_id, &visibility)
+        .await?;
+    }
     let view = self.database_views.get_view_editor(&params.view_id).await?;
     view.v_update_field_settings(params).await?;
 
     Ok(())
   }
 
+  /// Shows or hides the given fields of the view at once. A visible field is
+  /// [FieldVisibility::AlwaysShown] and a hidden one is [FieldVisibility::AlwaysHidden]. The
+  /// settings are written under one lock and sent in one
+  /// [DatabaseNotification::DidUpdateFieldSettingsBatch]. Use
+  /// [Self::update_field_settings_with_changeset] to toggle a single field.
+  pub async fn set_fields_visibility(
+    &self,
+    view_id: &str,
+    visibility: HashMap<String, bool>,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    for field_id in visibility.keys() {
+      self.ensure_not_system_field(field_id)?;
+    }
+    let view = self.database_views.get_view_editor(view_id).await?;
+    let field_ids = self
+      .get_fields(view_id, None)
+      .into_iter()
+      .map(|field| field.id)
+      .filter(|field_id| visibility.contains_key(field_id))
+      .collect::<Vec<String>>();
+    if let Some(field_id) = visibility.keys().find(|id| !field_ids.contains(id)) {
+      return Err(FlowyError::field_record_not_found().with_context(format!(
+        "The field {} isn't in the view {}",
+        field_id, view_id
+      )));
+    }
+    self.ensure_any_field_visible(view_id, &visibility).await?;
+
+    let mut field_settings_map = view.v_get_field_settings(&field_ids).await;
+    let field_settings = field_ids
+      .iter()
+      .filter_map(|field_id| {
+        let field_settings = field_settings_map.remove(field_id)?;
+        let visibility = if visibility[field_id] {
+          FieldVisibility::AlwaysShown
+        } else {
+          FieldVisibility::AlwaysHidden
+        };
+        Some(FieldSettings {
+          visibility,
+          ..field_settings
+        })
+      })
+      .collect::<Vec<FieldSettings>>();
+
+    {
+      let database = self.database.lock();
+      for field_settings in &field_settings {
+        database.update_field_settings(
+          view_id,
+          Some(vec![field_settings.field_id.clone()]),
+          field_settings.clone(),
+        );
+      }
+    }
+
+    let items = field_settings
+      .into_iter()
+      .map(FieldSettingsPB::from)
+      .collect::<Vec<_>>();
+    send_notification(view_id, DatabaseNotification::DidUpdateFieldSettingsBatch)
+      .payload(RepeatedFieldSettingsPB::from(items))
+      .send();
+    Ok(())
+  }
+
+  /// The system fields are always hidden, so showing or hiding them is rejected.
+  fn ensure_not_system_field(&self, field_id: &str) -> FlowyResult<()> {
+    let is_system = self
+      .get_field(field_id)
+      .map(|field| is_system_field(&field))
+      .unwrap_or(false);
+    if is_system {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "The field {} is a system field, which is always hidden",
+        field_id
+      )));
+    }
+    Ok(())
+  }
+
+  /// Returns [ErrorCode::InvalidData] if no field of the view would be visible after the fields
+  /// in `visibility` are shown or hidden. A view whose fields are all hidden renders blank.
+  async fn ensure_any_field_visible(
+    &self,
+    view_id: &str,
+    visibility: &HashMap<String, bool>,
+  ) -> FlowyResult<()> {
+    let view = self.database_views.get_view_editor(view_id).await?;
+    let field_ids = self
+      .get_fields(view_id, None)
+      .into_iter()
+      .map(|field| field.id)
+      .collect::<Vec<String>>();
+    let field_settings = view.v_get_field_settings(&field_ids).await;
+    let has_visible_field = field_ids
+      .iter()
+      .any(|field_id| match visibility.get(field_id) {
+        Some(is_visible) => *is_visible,
+        None => field_settings
+          .get(field_id)
+          .map(|field_settings| field_settings.is_visible())
+          .unwrap_or(true),
+      });
+    if !has_visible_field {
+      return Err(
+        FlowyError::invalid_data().with_context("At least one field of the view must be visible"),
+      );
+    }
+    Ok(())
+  }
+
+  /// Overrides the settings of the given fields in the views of `layout` that are created from now
+  /// on, e.g. to hide a field by default in the new grids. It replaces the previous overrides of
+  /// the layout. The other fields fall back to the global defaults, and the existing views are
+  /// unaffected. The overrides are saved in the database, so they're synced to the other devices.
+  pub fn set_default_field_settings(
+    &self,
+    layout: DatabaseLayout,
+    settings: Vec<FieldSettings>,
+  ) -> FlowyResult<()> {
+    self.ensure_can_edit()?;
+    let database = self.database.lock();
+    save_default_field_settings(database.get_collab(), layout, settings)
+  }
+
+  /// Returns the overrides saved by [Self::set_default_field_settings].
+  fn get_default_field_settings(&self) -> DefaultFieldSettingsByLayout {
+    let database = self.database.lock();
+    get_default_field_settings(database.get_collab())
+  }
+
+  /// Saves the settings of the fields that have none in the view, which happens after some sync
+  /// conflicts. Without them, the field falls back to the default settings that aren't saved, so
+  /// it may render differently on the other devices. The settings come from the overrides of
+  /// [Self::set_default_field_settings] or the defaults of the layout. Returns the number of the
+  /// repaired fields, which is zero once the view is repaired. Nothing is saved for the read-only
+  /// members.
+  pub fn repair_missing_field_settings(&self, view_id: &str) -> FlowyResult<usize> {
+    if self.ensure_can_edit().is_err() {
+      return Ok(0);
+    }
+    let overrides = self.get_default_field_settings();
+    let num_of_repaired = {
+      let database = self.database.lock();
+      let layout = database.views.get_database_view_layout(view_id);
+      let fields = database.get_fields(None);
+      let field_ids = fields
+        .iter()
+        .map(|field| field.id.clone())
+        .collect::<Vec<String>>();
+      let field_settings_map = database.get_field_settings(view_id, Some(&field_ids));
+      let missing_fields = fields
+        .iter()
+        .filter(|field| !field_settings_map.contains_key(&field.id))
+        .collect::<Vec<&Field>>();
+      for field in &missing_fields {
+        let field_settings = default_field_settings_for_field(&overrides, layout, field);
+        database.update_field_settings(view_id, Some(vec![field.id.clone()]), field_settings);
+      }
+      missing_fields.len()
+    };
+    if num_of_repaired > 0 {
+      info!(
+        "Repaired the missing settings of {} fields in view: {}",
+        num_of_repaired, view_id
+      );
+    }
+    Ok(num_of_repaired)
+  }
+
+  /// Returns the field settings that a new view of `layout` starts with: the overrides of the
+  /// fields that still exist. The fields without an override use the global defaults.
+  pub fn get_field_settings_for_new_view(
+    &self,
+    layout: DatabaseLayout,
+  ) -> HashMap<String, FieldSettingsMap> {
+    let overrides = self
+      .get_default_field_settings()
+      .remove(&layout)
+      .unwrap_or_default();
+    overrides
+      .into_iter()
+      .filter(|(field_id, _)| self.get_field(field_id).is_some())
+      .map(|(field_id, field_settings)| (field_id, FieldSettingsMap::from(field_settings)))
+      .collect()
+  }
+
   pub async fn get_related_database_id(&self, field_id: &str) -> FlowyResult<String> {
     let mut field = self
       .database
@@ -1276,36 +4004,125 @@ impl DatabaseEditor {
     &self,
     row_ids: Option<&Vec<String>>,
   ) -> FlowyResult<Vec<RelatedRowDataPB>> {
-    let primary_field = self.database.lock().fields.get_primary_field().unwrap();
-    let handler = TypeOptionCellExt::new(&primary_field, Some(self.cell_cache.clone()))
-      .get_type_option_cell_data_handler_with_field_type(FieldType::RichText)
-      .ok_or(FlowyError::internal())?;
+    if let Some(row_ids) = row_ids {
+      if self.primary_value_index.is_enabled() {
+        self.refresh_primary_value_index()?;
+        return Ok(self.primary_value_index.get_row_datas(row_ids));
+      }
+    }
+    get_related_rows_in_database(&self.database, row_ids, Some(self.cell_cache.clone()))
+  }
+
+  /// Enables or disables the index of the primary values of this database. See
+  /// [crate::manager::DatabaseManager::set_primary_value_index_enabled].
+  pub fn set_primary_value_index_enabled(&self, is_enabled: bool) {
+    self.primary_value_index.set_enabled(is_enabled);
+  }
+
+  /// Returns the rows whose primary cell is `title`. All the rows are scanned unless the index of
+  /// the primary values is enabled, in which case the lookup takes constant time once the index
+  /// is built.
+  pub async fn find_rows_by_primary_value(&self, title: &str) -> FlowyResult<Vec<RowId>> {
+    if self.primary_value_index.is_enabled() {
+      self.refresh_primary_value_index()?;
+      return Ok(self.primary_value_index.get_row_ids(title));
+    }
+
+    let row_datas =
+      get_related_rows_in_database(&self.database, None, Some(self.cell_cache.clone()))?;
+    Ok(
+      row_datas
+        .into_iter()
+        .filter(|row_data| row_data.name == title)
+        .map(|row_data| RowId::from(row_data.row_id))
+        .collect(),
+    )
+  }
 
-    let row_data = {
+  /// Builds the index of the primary values if it's missing or out of date, or indexes the rows
+  /// whose primary cell changed since the last lookup.
+  fn refresh_primary_value_index(&self) -> FlowyResult<()> {
+    let (primary_field_id, row_count) = {
       let database = self.database.lock();
-      let mut rows = database.get_database_rows();
-      if let Some(row_ids) = row_ids {
-        rows.retain(|row| row_ids.contains(&row.id));
-      }
-      rows
-        .iter()
-        .map(|row| {
-          let title = database
-            .get_cell(&primary_field.id, &row.id)
-            .cell
-            .and_then(|cell| handler.handle_get_boxed_cell_data(&cell, &primary_field))
-            .and_then(|cell_data| cell_data.unbox_or_none())
-            .unwrap_or_else(|| StringCellData("".to_string()));
-
-          RelatedRowDataPB {
-            row_id: row.id.to_string(),
-            name: title.0,
-          }
-        })
-        .collect::<Vec<_>>()
+      let primary_field = database
+        .fields
+        .get_primary_field()
+        .ok_or_else(|| FlowyError::internal().with_context("The primary field is not found"))?;
+      let inline_view_id = database.get_inline_view_id();
+      let row_count = database.get_row_orders_for_view(&inline_view_id).len();
+      (primary_field.id.clone(), row_count)
     };
+    let cell_cache = Some(self.cell_cache.clone());
+    if self
+      .primary_value_index
+      .needs_rebuild(&primary_field_id, row_count)
+    {
+      let row_datas = get_related_rows_in_database(&self.database, None, cell_cache)?;
+      self
+        .primary_value_index
+        .rebuild(&primary_field_id, row_count, row_datas);
+      return Ok(());
+    }
+
+    let stale_row_ids = self.primary_value_index.take_stale_row_ids();
+    if !stale_row_ids.is_empty() {
+      let rows = {
+        let database = self.database.lock();
+        stale_row_ids
+          .iter()
+          .flat_map(|row_id| database.get_row_detail(row_id))
+          .map(|row_detail| row_detail.row)
+          .collect::<Vec<Row>>()
+      };
+      let row_datas = related_row_datas(&self.database, &rows, cell_cache)?;
+      self.primary_value_index.reindex_rows(row_datas);
+    }
+    Ok(())
+  }
+
+  /// Same as [Self::get_related_rows] with None, but the rows are read in chunks, in the order of
+  /// the inline view, so a relation picker that's closed can stop the scan of a large database
+  /// with `cancel_token`. The rows found until then are returned. If `limit` is set, the scan
+  /// stops once it found that many rows.
+  pub async fn scan_related_rows(
+    &self,
+    limit: Option<usize>,
+    cancel_token: CancellationToken,
+  ) -> FlowyResult<RelatedRowsScan> {
+    let row_orders = {
+      let database = self.database.lock();
+      let inline_view_id = database.get_inline_view_id();
+      database.get_row_orders_for_view(&inline_view_id)
+    };
+    let limit = limit.unwrap_or(usize::MAX);
+    let mut scan = RelatedRowsScan::default();
+    let mut start = 0;
+    while start < row_orders.len() {
+      if cancel_token.is_cancelled() {
+        scan.is_cancelled = true;
+        break;
+      }
+      if scan.rows.len() >= limit {
+        scan.has_more = true;
+        break;
+      }
 
-    Ok(row_data)
+      let end = start
+        .saturating_add(RELATED_ROWS_SCAN_CHUNK_SIZE.min(limit - scan.rows.len()))
+        .min(row_orders.len());
+      let rows = self
+        .database
+        .lock()
+        .get_rows_from_row_orders(&row_orders[start..end]);
+      scan.rows.extend(related_row_datas(
+        &self.database,
+        &rows,
+        Some(self.cell_cache.clone()),
+      )?);
+      start = end;
+      tokio::task::yield_now().await;
+    }
+    Ok(scan)
   }
 
   fn get_auto_updated_fields(&self, view_id: &str) -> Vec<Field> {
@@ -1323,6 +4140,11 @@ impl DatabaseEditor {
   pub fn get_mutex_database(&self) -> &MutexDatabase {
     &self.database
   }
+
+  /// Sets the delegate that's called before each chunk of rows is loaded. Only used by the tests.
+  pub fn set_row_load_delegate(&self, delegate: Arc<dyn RowLoadDelegate>) {
+    *self.row_load_delegate.write() = Some(delegate);
+  }
 }
 
 struct DatabaseViewOperationImpl {
@@ -1330,6 +4152,11 @@ struct DatabaseViewOperationImpl {
   task_scheduler: Arc<RwLock<TaskDispatcher>>,
   cell_cache: CellCache,
   editor_by_view_id: Arc<RwLock<EditorByViewId>>,
+  /// Used to read the rows of the other databases that the relation fields link to.
+  workspace_database: Weak<WorkspaceDatabase>,
+  schema_version: Arc<SchemaVersion>,
+  error_reporter: Arc<DatabaseErrorReporter>,
+  row_load_delegate: RowLoadDelegateCell,
 }
 
 impl DatabaseViewOperation for DatabaseViewOperationImpl {
@@ -1370,6 +4197,7 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
       },
       default_field_settings_by_layout_map(),
     );
+    self.schema_version.bump();
     to_fut(async move { field })
   }
 
@@ -1380,13 +4208,20 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
   ) -> FutureResult<(), FlowyError> {
     let weak_editor_by_view_id = Arc::downgrade(&self.editor_by_view_id);
     let weak_database = Arc::downgrade(&self.database);
+    let schema_version = self.schema_version.clone();
     FutureResult::new(async move {
       if let (Some(database), Some(editor_by_view_id)) =
         (weak_database.upgrade(), weak_editor_by_view_id.upgrade())
       {
         let view_editors = editor_by_view_id.read().await.values().cloned().collect();
-        let _ =
-          update_field_type_option_fn(&database, &view_editors, type_option_data, old_field).await;
+        let _ = update_field_type_option_fn(
+          &database,
+          &view_editors,
+          &schema_version,
+          type_option_data,
+          old_field,
+        )
+        .await;
       }
       Ok(())
     })
@@ -1419,41 +4254,68 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
   }
 
   fn get_rows(&self, view_id: &str) -> Fut<Vec<Arc<RowDetail>>> {
+    let row_orders = self.get_row_orders(view_id);
+    let database = self.database.clone();
+    let error_reporter = self.error_reporter.clone();
+    let row_load_delegate = self.row_load_delegate.read().clone();
+    let view_id = view_id.to_string();
+    to_fut(async move {
+      let row_orders = row_orders.await;
+      load_rows_from_row_orders(
+        database,
+        &view_id,
+        row_orders,
+        error_reporter,
+        row_load_delegate,
+      )
+      .await
+    })
+  }
+
+  fn get_row_orders(&self, view_id: &str) -> Fut<Vec<RowOrder>> {
     let database = self.database.clone();
+    let error_reporter = self.error_reporter.clone();
     let view_id = view_id.to_string();
     to_fut(async move {
-      let cloned_database = database.clone();
       // offloads the blocking operation to a thread where blocking is acceptable. This prevents
       // blocking the main asynchronous runtime
+      let cloned_view_id = view_id.clone();
       let row_orders = tokio::task::spawn_blocking(move || {
-        cloned_database.lock().get_row_orders_for_view(&view_id)
+        database.lock().get_row_orders_for_view(&cloned_view_id)
       })
       .await
-      .unwrap_or_default();
+      .unwrap_or_else(|err| {
+        error_reporter.report(
+          &view_id,
+          DatabaseErrorPhasePB::LoadRowOrders,
+          DatabaseErrorKindPB::Fatal,
+          format!("Failed to read the rows of the view: {}", err),
+        );
+        vec![]
+      });
       tokio::task::yield_now().await;
+      row_orders
+    })
+  }
 
-      let mut all_rows = vec![];
-
-      // Loading the rows in chunks of 10 rows in order to prevent blocking the main asynchronous runtime
-      for chunk in row_orders.chunks(10) {
-        let cloned_database = database.clone();
-        let chunk = chunk.to_vec();
-        let rows = tokio::task::spawn_blocking(move || {
-          let orders = cloned_database.lock().get_rows_from_row_orders(&chunk);
-          let lock_guard = cloned_database.lock();
-          orders
-            .into_iter()
-            .flat_map(|row| lock_guard.get_row_detail(&row.id))
-            .collect::<Vec<RowDetail>>()
-        })
-        .await
-        .unwrap_or_default();
-
-        all_rows.extend(rows);
-        tokio::task::yield_now().await;
-      }
-
-      all_rows.into_iter().map(Arc::new).collect()
+  fn get_rows_from_row_orders(
+    &self,
+    view_id: &str,
+    row_orders: Vec<RowOrder>,
+  ) -> Fut<Vec<Arc<RowDetail>>> {
+    let database = self.database.clone();
+    let error_reporter = self.error_reporter.clone();
+    let row_load_delegate = self.row_load_delegate.read().clone();
+    let view_id = view_id.to_string();
+    to_fut(async move {
+      load_rows_from_row_orders(
+        database,
+        &view_id,
+        row_orders,
+        error_reporter,
+        row_load_delegate,
+      )
+      .await
     })
   }
 
@@ -1510,6 +4372,14 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
     self.database.lock().remove_all_sorts(view_id);
   }
 
+  fn replace_all_sorts(&self, view_id: &str, sorts: Vec<Sort>) {
+    let database = self.database.lock();
+    database.remove_all_sorts(view_id);
+    for sort in sorts {
+      database.insert_sort(view_id, sort);
+    }
+  }
+
   fn get_all_calculations(&self, view_id: &str) -> Vec<Arc<Calculation>> {
     self
       .database
@@ -1581,6 +4451,42 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
       .update_layout_type(view_id, layout_type);
   }
 
+  fn get_related_rows(
+    &self,
+    database_id: &str,
+    row_ids: Vec<String>,
+  ) -> Fut<Vec<RelatedRowDataPB>> {
+    let database = self.database.clone();
+    let cell_cache = self.cell_cache.clone();
+    let workspace_database = self.workspace_database.clone();
+    let database_id = database_id.to_string();
+    to_fut(async move {
+      let (database, cell_cache) = if database.lock().get_database_id() == database_id {
+        (database, Some(cell_cache))
+      } else {
+        let related_database = match workspace_database.upgrade() {
+          Some(workspace_database) => workspace_database.get_database(&database_id).await,
+          None => None,
+        };
+        match related_database {
+          Some(related_database) => (related_database, None),
+          None => {
+            warn!("Can't find the related database: {}", database_id);
+            return vec![];
+          },
+        }
+      };
+      get_related_rows_in_database(&database, Some(&row_ids), cell_cache).unwrap_or_else(|err| {
+        warn!("Failed to get the related rows: {}", err);
+        vec![]
+      })
+    })
+  }
+
+  fn get_row_users(&self) -> RowUsersByRowId {
+    get_all_row_users(&self.database.lock())
+  }
+
   fn get_task_scheduler(&self) -> Arc<RwLock<TaskDispatcher>> {
     self.task_scheduler.clone()
   }
@@ -1652,6 +4558,10 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
       wrap_cell_content: params
         .wrap_cell_content
         .unwrap_or(field_settings.wrap_cell_content),
+      default_sort_condition: params
+        .default_sort_condition
+        .map(SortCondition::from)
+        .unwrap_or(field_settings.default_sort_condition),
       ..field_settings
     };
 
@@ -1681,10 +4591,208 @@ impl DatabaseViewOperation for DatabaseViewOperationImpl {
   }
 }
 
+type RowLoadDelegateCell = Arc<parking_lot::RwLock<Option<Arc<dyn RowLoadDelegate>>>>;
+
+/// Loads the rows in chunks of [ROW_LOAD_CHUNK_SIZE] rows in order to prevent blocking the main
+/// asynchronous runtime. The chunks are loaded one after the other, as reading the rows holds the
+/// lock of the database.
+/// A chunk whose task fails is left out and reported through the [DatabaseErrorReporter] as fatal.
+/// The rows that aren't found are left out and reported as transient, their data may not be synced
+/// yet.
+async fn load_rows_from_row_orders(
+  database: Arc<MutexDatabase>,
+  view_id: &str,
+  row_orders: Vec<RowOrder>,
+  error_reporter: Arc<DatabaseErrorReporter>,
+  row_load_delegate: Option<Arc<dyn RowLoadDelegate>>,
+) -> Vec<Arc<RowDetail>> {
+  let mut all_rows = Vec::with_capacity(row_orders.len());
+  let mut num_of_pending_rows = 0;
+  for chunk in row_orders.chunks(ROW_LOAD_CHUNK_SIZE) {
+    let cloned_database = database.clone();
+    let cloned_row_load_delegate = row_load_delegate.clone();
+    let chunk = chunk.to_vec();
+    let result = tokio::task::spawn_blocking(move || {
+      if let Some(delegate) = cloned_row_load_delegate {
+        let row_ids = chunk
+          .iter()
+          .map(|order| order.id.clone())
+          .collect::<Vec<_>>();
+        delegate.will_load_rows(&row_ids);
+      }
+      let lock_guard = cloned_database.lock();
+      let rows = lock_guard
+        .get_rows_from_row_orders(&chunk)
+        .into_iter()
+        .flat_map(|row| lock_guard.get_row_detail(&row.id))
+        .collect::<Vec<RowDetail>>();
+      let num_of_missing_rows = chunk.len().saturating_sub(rows.len());
+      (rows, num_of_missing_rows)
+    })
+    .await;
+
+    match result {
+      Ok((rows, num_of_missing_rows)) => {
+        num_of_pending_rows += num_of_missing_rows;
+        all_rows.extend(rows.into_iter().map(Arc::new));
+      },
+      Err(err) => error_reporter.report(
+        view_id,
+        DatabaseErrorPhasePB::LoadRows,
+        DatabaseErrorKindPB::Fatal,
+        format!("Failed to load a chunk of rows: {}", err),
+      ),
+    }
+    tokio::task::yield_now().await;
+  }
+  if num_of_pending_rows > 0 {
+    error_reporter.report(
+      view_id,
+      DatabaseErrorPhasePB::LoadRows,
+      DatabaseErrorKindPB::Transient,
+      format!(
+        "{} rows aren't found, they may not be synced yet",
+        num_of_pending_rows
+      ),
+    );
+  }
+  all_rows
+}
+
+fn id_already_exists_error(id: &str) -> FlowyError {
+  FlowyError::new(
+    ErrorCode::RecordAlreadyExists,
+    format!("The generated id {} is already used", id),
+  )
+}
+
+/// Returns the cell of the field in the row. The cells of the timestamp and user fields aren't
+/// stored, they're computed from the row and its users.
+fn cell_in_row(
+  row: &Row,
+  row_users: &RowUsers,
+  field_id: &str,
+  field_type: FieldType,
+) -> Option<Cell> {
+  match field_type {
+    FieldType::LastEditedTime | FieldType::CreatedTime => {
+      let timestamp = if field_type.is_created_time() {
+        row.created_at
+      } else {
+        row.modified_at
+      };
+      let wrapped_cell_data =
+        TimestampCellDataWrapper::from((field_type, TimestampCellData::new(timestamp)));
+      Some(Cell::from(wrapped_cell_data))
+    },
+    FieldType::LastEditedBy | FieldType::CreatedBy => row_users.cell(field_type),
+    _ => row.cells.get(field_id).cloned(),
+  }
+}
+
+/// Makes the cells of a CSV record. The values are applied to the fields as text, e.g. a number is
+/// parsed by the number format of the field, and the values that can't be applied are kept as text.
+/// The empty values don't make cells. The ids of their fields are returned along with the cells.
+fn csv_record_to_cells(record: Vec<String>, columns: &[Option<Field>]) -> (Cells, Vec<String>) {
+  let mut cells = Cells::new();
+  let mut empty_field_ids = vec![];
+  for (value, field) in record.into_iter().zip(columns) {
+    let field = match field {
+      Some(field) if value.is_empty() => {
+        empty_field_ids.push(field.id.clone());
+        continue;
+      },
+      Some(field) => field,
+      None => continue,
+    };
+    let cell =
+      apply_cell_changeset(BoxAny::new(value.clone()), None, field, None).unwrap_or_else(|_| {
+        new_cell_builder(FieldType::RichText)
+          .insert_str_value(CELL_DATA, value)
+          .build()
+      });
+    cells.insert(field.id.clone(), cell);
+  }
+  (cells, empty_field_ids)
+}
+
+/// Validates the cells of a CSV record and the empty values, which clear the cells of the existing
+/// rows. See [validate_field_text].
+fn validate_csv_cells(
+  cells: &Cells,
+  empty_field_ids: &[String],
+  fields: &[Field],
+) -> FlowyResult<()> {
+  for field in fields {
+    if let Some(cell) = cells.get(&field.id) {
+      validate_field_cell(field, cell)?;
+    } else if empty_field_ids.contains(&field.id) {
+      validate_field_text(field, "")?;
+    }
+  }
+  Ok(())
+}
+
+/// Returns the titles of the rows of `database`, which are the contents of their primary field. All
+/// the rows are returned if `row_ids` is None.
 #[tracing::instrument(level = "trace", skip_all, err)]
+fn get_related_rows_in_database(
+  database: &MutexDatabase,
+  row_ids: Option<&Vec<String>>,
+  cell_cache: Option<CellCache>,
+) -> FlowyResult<Vec<RelatedRowDataPB>> {
+  let rows = {
+    let mut rows = database.lock().get_database_rows();
+    if let Some(row_ids) = row_ids {
+      rows.retain(|row| row_ids.contains(&row.id));
+    }
+    rows
+  };
+  related_row_datas(database, &rows, cell_cache)
+}
+
+/// Returns the titles of the rows, which are the contents of their primary field.
+fn related_row_datas(
+  database: &MutexDatabase,
+  rows: &[Row],
+  cell_cache: Option<CellCache>,
+) -> FlowyResult<Vec<RelatedRowDataPB>> {
+  let primary_field = database
+    .lock()
+    .fields
+    .get_primary_field()
+    .ok_or_else(|| FlowyError::internal().with_context("The primary field is not found"))?;
+  let handler = TypeOptionCellExt::new(&primary_field, cell_cache)
+    .get_type_option_cell_data_handler_with_field_type(FieldType::RichText)
+    .ok_or(FlowyError::internal())?;
+
+  let row_data = {
+    let database = database.lock();
+    rows
+      .iter()
+      .map(|row| {
+        let title = database
+          .get_cell(&primary_field.id, &row.id)
+          .cell
+          .and_then(|cell| handler.handle_get_boxed_cell_data(&cell, &primary_field))
+          .and_then(|cell_data| cell_data.unbox_or_none())
+          .unwrap_or_else(|| StringCellData("".to_string()));
+
+        RelatedRowDataPB {
+          row_id: row.id.to_string(),
+          name: title.0,
+        }
+      })
+      .collect::<Vec<_>>()
+  };
+
+  Ok(row_data)
+}
+
 pub async fn update_field_type_option_fn(
   database: &Arc<MutexDatabase>,
   view_editors: &Vec<Arc<DatabaseViewEditor>>,
+  schema_version: &SchemaVersion,
   type_option_data: TypeOptionData,
   old_field: Field,
 ) -> FlowyResult<()> {
@@ -1693,6 +4801,9 @@ pub async fn update_field_type_option_fn(
     return Ok(());
   }
   let field_type = FieldType::from(old_field.field_type);
+  if field_type == FieldType::RichText {
+    RichTextTypeOption::from(type_option_data.clone()).validate_pattern()?;
+  }
   database
     .lock()
     .fields
@@ -1710,6 +4821,7 @@ pub async fn update_field_type_option_fn(
         });
       }
     });
+  schema_version.bump();
 
   let _ = notify_did_update_database_field(database, &old_field.id);
   for view_editor in view_editors {
@@ -1721,6 +4833,46 @@ pub async fn update_field_type_option_fn(
   Ok(())
 }
 
+/// Moves the rows of the view from `row_orders` into the order of `new_row_orders`, which holds
+/// the same rows, with a single update of the view.
+fn reorder_rows_in_view(
+  database: &Database,
+  view_id: &str,
+  row_orders: Vec<RowId>,
+  new_row_orders: &[RowId],
+) {
+  database.views.update_database_view(view_id, |mut view| {
+    // Move the rows one by one into their new positions. The rows before `index` are already
+    // in place, so each row is only moved backwards.
+    let mut row_orders = row_orders;
+    for (index, row_id) in new_row_orders.iter().enumerate() {
+      if row_orders[index] == *row_id {
+        continue;
+      }
+      view = view.move_row_order(row_id, &row_orders[index]);
+      if let Some(from_index) = row_orders.iter().position(|id| id == row_id) {
+        let row_id = row_orders.remove(from_index);
+        row_orders.insert(index, row_id);
+      }
+    }
+  });
+}
+
+/// Returns the settings that the field starts with in a view of `layout`: the override of
+/// [DatabaseEditor::set_default_field_settings] or the default of the layout.
+fn default_field_settings_for_field(
+  overrides: &DefaultFieldSettingsByLayout,
+  layout: DatabaseLayout,
+  field: &Field,
+) -> FieldSettings {
+  overrides
+    .get(&layout)
+    .and_then(|overrides| overrides.get(&field.id).cloned())
+    .unwrap_or_else(|| {
+      FieldSettings::from_any_map(&field.id, layout, &field_settings_for_field(layout, field))
+    })
+}
+
 #[tracing::instrument(level = "trace", skip_all, err)]
 fn notify_did_update_database_field(
   database: &Arc<MutexDatabase>,
@@ -1737,14 +4889,18 @@ fn notify_did_update_database_field(
   };
 
   if let Some(field) = field {
+    // The views don't list the system fields
+    let is_system = is_system_field(&field);
     let updated_field = FieldPB::new(field);
     let notified_changeset =
       DatabaseFieldChangesetPB::update(&database_id, vec![updated_field.clone()]);
 
-    for view in views {
-      send_notification(&view.id, DatabaseNotification::DidUpdateFields)
-        .payload(notified_changeset.clone())
-        .send();
+    if !is_system {
+      for view in views {
+        send_notification(&view.id, DatabaseNotification::DidUpdateFields)
+          .payload(notified_changeset.clone())
+          .send();
+      }
     }
 
     send_notification(field_id, DatabaseNotification::DidUpdateField)
@@ -0,0 +1,48 @@
+use tokio::sync::broadcast;
+use tracing::error;
+
+use crate::entities::{DatabaseErrorKindPB, DatabaseErrorPB, DatabaseErrorPhasePB};
+use crate::notification::{send_notification, DatabaseNotification};
+
+/// Surfaces the errors that happen while the editor works in the background, e.g. a chunk of rows
+/// that can't be loaded, which would otherwise only be logged. The errors are sent to the frontend
+/// with [DatabaseNotification::DidReceiveDatabaseError] and to the receivers returned by
+/// [Self::subscribe].
+pub(crate) struct DatabaseErrorReporter {
+  sender: broadcast::Sender<DatabaseErrorPB>,
+}
+
+impl DatabaseErrorReporter {
+  pub fn new() -> Self {
+    let (sender, _) = broadcast::channel(100);
+    Self { sender }
+  }
+
+  pub fn subscribe(&self) -> broadcast::Receiver<DatabaseErrorPB> {
+    self.sender.subscribe()
+  }
+
+  pub fn report(
+    &self,
+    view_id: &str,
+    phase: DatabaseErrorPhasePB,
+    kind: DatabaseErrorKindPB,
+    msg: String,
+  ) {
+    error!(
+      "[Database]: {:?} of view {} failed: {}",
+      phase, view_id, msg
+    );
+    let error = DatabaseErrorPB {
+      view_id: view_id.to_string(),
+      phase,
+      kind,
+      msg,
+    };
+    send_notification(view_id, DatabaseNotification::DidReceiveDatabaseError)
+      .payload(error.clone())
+      .send();
+    // It's fine if nobody is subscribed
+    let _ = self.sender.send(error);
+  }
+}
@@ -1,50 +1,321 @@
-use crate::entities::{DatabaseSyncStatePB, DidFetchRowPB, RowsChangePB};
+use crate::entities::{
+  DatabaseFieldChangesetPB, DatabaseSyncStatePB, DidFetchRowPB, FieldChangeOriginPB, FieldIdPB,
+  FieldPB, RowsChangePB, UnsyncedEditCountPB,
+};
 use crate::notification::{send_notification, DatabaseNotification, DATABASE_OBSERVABLE_SOURCE};
-use crate::services::database::UpdatedRow;
+use crate::services::database::row_metas::set_rows_last_edited_by;
+use crate::services::database::{
+  PrimaryValueIndex, RemoteRowEditNotifier, RowSyncNotifier, RowSyncRetryQueue, SchemaVersion,
+  SyncStateLogThrottle, UnsyncedEdits, UpdatedRow,
+};
+use crate::services::database_view::DatabaseViews;
+use collab::core::collab_plugin::CollabPluginType;
+use collab::core::collab_state::SyncState;
+use collab::core::origin::CollabOrigin;
+use collab::preclude::{CollabPlugin, TransactionMut};
 use collab_database::blocks::BlockEvent;
 use collab_database::database::MutexDatabase;
 use collab_database::fields::FieldChange;
 use collab_database::rows::{RowChange, RowId};
 use collab_database::views::DatabaseViewChange;
 use flowy_notification::{DebounceNotificationSender, NotificationBuilder};
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use lib_dispatch::prelude::af_spawn;
 use std::sync::Arc;
-use tracing::{trace, warn};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, trace, warn};
 
-pub(crate) async fn observe_sync_state(database_id: &str, database: &Arc<MutexDatabase>) {
+/// How often [observe_row_sync_state] looks for the rows whose sync should be retried.
+const ROW_SYNC_RETRY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The tasks of the observers spawned for a database. A task ends by itself once the database is
+/// dropped, but only when it observes the next change, and the database isn't dropped while its
+/// editor is held elsewhere. So the tasks are aborted when the database is closed, otherwise
+/// opening and closing databases quickly piles up the idle tasks.
+#[derive(Default)]
+pub(crate) struct DatabaseObservers {
+  handles: parking_lot::Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl DatabaseObservers {
+  pub fn add(&self, handle: JoinHandle<()>) {
+    self.handles.lock().push(handle);
+  }
+
+  /// Does nothing to the tasks that have ended already.
+  pub fn abort_all(&self) {
+    for handle in self.handles.lock().iter() {
+      handle.abort();
+    }
+  }
+
+  /// Returns the number of the tasks that haven't ended yet. An aborted task ends the next time
+  /// the runtime handles it.
+  pub fn num_of_live_tasks(&self) -> usize {
+    self
+      .handles
+      .lock()
+      .iter()
+      .filter(|handle| !handle.is_finished())
+      .count()
+  }
+}
+
+/// The sync states are logged through a [SyncStateLogThrottle] with the given window.
+pub(crate) async fn observe_sync_state(
+  database_id: &str,
+  database: &Arc<MutexDatabase>,
+  unsynced_edits: &Arc<UnsyncedEdits>,
+  log_window: Duration,
+) -> JoinHandle<()> {
   let weak_database = Arc::downgrade(database);
+  let unsynced_edits = unsynced_edits.clone();
   let mut sync_state = database.lock().subscribe_sync_state();
   let database_id = database_id.to_string();
   af_spawn(async move {
+    let mut log_throttle = SyncStateLogThrottle::new(log_window);
     while let Some(sync_state) = sync_state.next().await {
       if weak_database.upgrade().is_none() {
         break;
       }
 
+      let is_sync_finished = matches!(sync_state, SyncState::SyncFinished);
+      let payload = DatabaseSyncStatePB::from(sync_state);
+      if let Some(num_of_skipped) = log_throttle.record(payload.value, Instant::now()) {
+        debug!(
+          "[Database Observe]: {} sync state:{:?}, skipped {} repeated states",
+          database_id, payload.value, num_of_skipped
+        );
+      }
+
+      // The edits are acknowledged once the local updates are synced with the server. The rows
+      // are separate collabs, see [observe_row_sync_state].
+      if is_sync_finished && unsynced_edits.acknowledge_database_edits() {
+        notify_unsynced_edit_count(&database_id, 0);
+      }
+
       send_notification(
         &database_id,
         DatabaseNotification::DidUpdateDatabaseSyncUpdate,
       )
-      .payload(DatabaseSyncStatePB::from(sync_state))
+      .payload(payload)
       .send();
     }
-  });
+  })
+}
+
+/// Acknowledges the edits of the rows whose collabs finish syncing with the server. The rows
+/// whose edits stay unsynced for [RowSyncRetryQueue::MIN_DELAY] are queued in a
+/// [RowSyncRetryQueue] to restart their sync.
+pub(crate) async fn observe_row_sync_state(
+  database_id: &str,
+  database: &Arc<MutexDatabase>,
+  unsynced_edits: &Arc<UnsyncedEdits>,
+  row_sync_notifier: &RowSyncNotifier,
+) -> JoinHandle<()> {
+  let weak_database = Arc::downgrade(database);
+  let unsynced_edits = unsynced_edits.clone();
+  let row_sync_notifier = row_sync_notifier.clone();
+  let mut row_sync_rx = row_sync_notifier.subscribe();
+  let database_id = database_id.to_string();
+  af_spawn(async move {
+    let mut retry_queue = RowSyncRetryQueue::default();
+    let mut retry_interval = tokio::time::interval(ROW_SYNC_RETRY_CHECK_INTERVAL);
+    retry_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+      let row_id = tokio::select! {
+        result = row_sync_rx.recv() => match result {
+          Ok(row_id) => Some(row_id),
+          Err(RecvError::Lagged(num_of_skipped)) => {
+            // The skipped broadcasts are lost, so the sync states are re-read from the row collabs
+            warn!(
+              "[Database Observe]: {} skipped {} row sync states",
+              database_id, num_of_skipped
+            );
+            None
+          },
+          Err(RecvError::Closed) => break,
+        },
+        _ = retry_interval.tick() => {
+          if weak_database.upgrade().is_none() {
+            break;
+          }
+          let now = Instant::now();
+          let unsynced_rows = now
+            .checked_sub(RowSyncRetryQueue::MIN_DELAY)
+            .map(|instant| unsynced_edits.rows_edited_before(instant))
+            .unwrap_or_default();
+          // The rows edited again are queued again once their new edits stay unsynced
+          retry_queue.retain(&unsynced_rows);
+          for row_id in unsynced_rows {
+            retry_queue.push(row_id, now);
+          }
+          for row_id in retry_queue.pop_due(now) {
+            if !row_sync_notifier.retry_sync(&row_id) {
+              trace!(
+                "[Database Observe]: {} can't retry the sync of row:{}",
+                database_id, row_id
+              );
+            }
+          }
+          None
+        },
+      };
+      if weak_database.upgrade().is_none() {
+        break;
+      }
+
+      let is_synced = match row_id {
+        Some(row_id) => {
+          retry_queue.remove(&row_id);
+          unsynced_edits.acknowledge_row_edits(&row_id)
+        },
+        None => {
+          unsynced_edits.acknowledge_synced_rows(|row_id| row_sync_notifier.last_synced_at(row_id))
+        },
+      };
+      if is_synced {
+        notify_unsynced_edit_count(&database_id, 0);
+      }
+    }
+  })
+}
+
+/// Records the collaborators as the last editors of the rows of the database that they edit. The
+/// edits made on this device are recorded by the editor when it makes them.
+pub(crate) async fn observe_remote_row_edits(
+  database_id: &str,
+  database: &Arc<MutexDatabase>,
+  remote_row_edit_notifier: &RemoteRowEditNotifier,
+) -> JoinHandle<()> {
+  let weak_database = Arc::downgrade(database);
+  let mut remote_row_edit_rx = remote_row_edit_notifier.subscribe();
+  let database_id = database_id.to_string();
+  af_spawn(async move {
+    loop {
+      let (row_id, uid) = match remote_row_edit_rx.recv().await {
+        Ok(remote_row_edit) => remote_row_edit,
+        Err(RecvError::Lagged(num_of_skipped)) => {
+          warn!(
+            "[Database Observe]: {} skipped {} remote row edits",
+            database_id, num_of_skipped
+          );
+          continue;
+        },
+        Err(RecvError::Closed) => break,
+      };
+      let database = match weak_database.upgrade() {
+        None => break,
+        Some(database) => database,
+      };
+      let database = database.lock();
+      // The row collabs are shared by all the databases of the workspace
+      let inline_view_id = database.get_inline_view_id();
+      if database.views.is_row_exist(&inline_view_id, &row_id) {
+        set_rows_last_edited_by(&database, [&row_id], uid);
+      }
+    }
+  })
+}
+
+/// Tells the [RemoteRowEditNotifier] about the edits of the row made by the collaborators. The
+/// edits are told apart by the origin of the collab transaction that applied them. The edits
+/// whose origin isn't a client, e.g. the ones made by the server, have no editor to record.
+pub(crate) struct RemoteRowEditPlugin {
+  row_id: RowId,
+  local_origin: CollabOrigin,
+  remote_row_edit_notifier: RemoteRowEditNotifier,
+}
+
+impl RemoteRowEditPlugin {
+  pub(crate) fn new(
+    row_id: RowId,
+    local_origin: CollabOrigin,
+    remote_row_edit_notifier: RemoteRowEditNotifier,
+  ) -> Self {
+    Self {
+      row_id,
+      local_origin,
+      remote_row_edit_notifier,
+    }
+  }
+}
+
+impl CollabPlugin for RemoteRowEditPlugin {
+  fn receive_update(&self, _object_id: &str, txn: &TransactionMut, _update: &[u8]) {
+    let origin = CollabOrigin::from(txn);
+    if origin == self.local_origin {
+      return;
+    }
+    if let CollabOrigin::Client(client) = origin {
+      self
+        .remote_row_edit_notifier
+        .notify_remote_edit(self.row_id.clone(), client.uid);
+    }
+  }
+
+  fn plugin_type(&self) -> CollabPluginType {
+    CollabPluginType::Other("RemoteRowEditPlugin".to_string())
+  }
+}
+
+pub(crate) fn notify_unsynced_edit_count(database_id: &str, count: usize) {
+  send_notification(
+    database_id,
+    DatabaseNotification::DidUpdateUnsyncedEditCount,
+  )
+  .payload(UnsyncedEditCountPB {
+    count: count as i64,
+  })
+  .send();
 }
 
 #[allow(dead_code)]
 pub(crate) async fn observe_rows_change(
   database_id: &str,
   database: &Arc<MutexDatabase>,
+  database_views: &Arc<DatabaseViews>,
   notification_sender: &Arc<DebounceNotificationSender>,
-) {
+  primary_value_index: &Arc<PrimaryValueIndex>,
+) -> JoinHandle<()> {
   let notification_sender = notification_sender.clone();
   let database_id = database_id.to_string();
   let weak_database = Arc::downgrade(database);
-  let mut row_change = database.lock().subscribe_row_change();
+  let weak_database_views = Arc::downgrade(database_views);
+  let primary_value_index = primary_value_index.clone();
+  let (row_change, view_change) = {
+    let database = database.lock();
+    (
+      database.subscribe_row_change(),
+      database.subscribe_view_change(),
+    )
+  };
+  // The rows created or deleted by the collaborators are only seen in the row orders of the
+  // views, they're sent as None
+  let row_orders_change = broadcast_stream(view_change).filter_map(|view_change| {
+    let is_row_orders_change = matches!(
+      view_change,
+      DatabaseViewChange::DidInsertRowOrders { .. }
+        | DatabaseViewChange::DidDeleteRowAtIndex { .. }
+    );
+    futures::future::ready(is_row_orders_change.then_some(None))
+  });
+  let mut changes =
+    futures::stream::select(broadcast_stream(row_change).map(Some), row_orders_change);
   af_spawn(async move {
-    while let Ok(row_change) = row_change.recv().await {
+    while let Some(change) = changes.next().await {
       if let Some(database) = weak_database.upgrade() {
+        let row_change = match change {
+          Some(row_change) => row_change,
+          None => {
+            primary_value_index.invalidate();
+            continue;
+          },
+        };
         trace!(
           "[Database Observe]: {} row change:{:?}",
           database_id,
@@ -56,6 +327,8 @@ pub(crate) async fn observe_rows_change(
             row_id,
             value: _,
           } => {
+            // The cells edited by the collaborators don't go through the editor
+            primary_value_index.did_update_cell(&row_id, &field_id);
             let cell_id = format!("{}:{}", row_id, field_id);
             notify_cell(&notification_sender, &cell_id);
 
@@ -63,6 +336,12 @@ pub(crate) async fn observe_rows_change(
             for view in views {
               notify_row(&notification_sender, &view.id, &field_id, &row_id);
             }
+
+            if let Some(database_views) = weak_database_views.upgrade() {
+              for view_editor in database_views.editors().await {
+                view_editor.v_did_update_cell(&row_id, &field_id).await;
+              }
+            }
           },
           _ => {
             warn!("unhandled row change: {:?}", row_change);
@@ -72,35 +351,137 @@ pub(crate) async fn observe_rows_change(
         break;
       }
     }
-  });
+  })
 }
-#[allow(dead_code)]
-pub(crate) async fn observe_field_change(database_id: &str, database: &Arc<MutexDatabase>) {
+/// Returns the values received by `rx`. The stream ends when the channel is closed or lags.
+fn broadcast_stream<T: Clone + Send + 'static>(
+  rx: broadcast::Receiver<T>,
+) -> impl Stream<Item = T> + Send + Unpin {
+  Box::pin(futures::stream::unfold(rx, |mut rx| async move {
+    let value = rx.recv().await.ok()?;
+    Some((value, rx))
+  }))
+}
+
+/// Sends the field changes made by the collaborators to the frontend with the
+/// [FieldChangeOriginPB::Remote] origin. The changes made on this device are skipped, the editor
+/// sends them when it makes them. The remote changes bump the [SchemaVersion] too.
+///
+/// The changes are told apart by the origin of the collab transaction that made them. See
+/// [FieldChangeOriginPlugin].
+pub(crate) async fn observe_field_change(
+  database_id: &str,
+  database: &Arc<MutexDatabase>,
+  schema_version: &Arc<SchemaVersion>,
+) -> JoinHandle<()> {
   let database_id = database_id.to_string();
   let weak_database = Arc::downgrade(database);
-  let mut field_change = database.lock().subscribe_field_change();
+  let schema_version = schema_version.clone();
+  let (tx, mut field_change_rx) = mpsc::unbounded_channel();
+  {
+    let database = database.lock();
+    let field_change = database.subscribe_field_change();
+    let collab = database.get_collab().lock();
+    let plugin = FieldChangeOriginPlugin {
+      local_origin: collab.origin().clone(),
+      field_change: parking_lot::Mutex::new(field_change),
+      tx,
+    };
+    collab.add_plugin(Box::new(plugin));
+  }
   af_spawn(async move {
-    while let Ok(field_change) = field_change.recv().await {
-      if weak_database.upgrade().is_none() {
-        break;
-      }
+    while let Some((field_change, is_local)) = field_change_rx.recv().await {
+      let database = match weak_database.upgrade() {
+        None => break,
+        Some(database) => database,
+      };
 
       trace!(
-        "[Database Observe]: {} field change:{:?}",
+        "[Database Observe]: {} field change:{:?}, is local: {}",
         database_id,
-        field_change
+        field_change,
+        is_local
       );
       match field_change {
-        FieldChange::DidUpdateField { .. } => {},
-        FieldChange::DidCreateField { .. } => {},
-        FieldChange::DidDeleteField { .. } => {},
+        FieldChange::DidUpdateField { field } => {
+          if is_local {
+            continue;
+          }
+          schema_version.bump();
+          let updated_field = FieldPB::new(field);
+          let changeset =
+            DatabaseFieldChangesetPB::update(&database_id, vec![updated_field.clone()])
+              .with_origin(FieldChangeOriginPB::Remote);
+          notify_remote_field_changeset(&database, changeset);
+          send_notification(&updated_field.id, DatabaseNotification::DidUpdateField)
+            .payload(updated_field)
+            .send();
+        },
+        // The fields created on this device aren't all created through the editor, e.g. the
+        // fields that a layout depends on, so every created field bumps the version.
+        FieldChange::DidCreateField { .. } => schema_version.bump(),
+        FieldChange::DidDeleteField { field_id } => {
+          if is_local {
+            continue;
+          }
+          schema_version.bump();
+          let deleted_field = FieldIdPB::from(field_id);
+          let changeset = DatabaseFieldChangesetPB::delete(&database_id, vec![deleted_field])
+            .with_origin(FieldChangeOriginPB::Remote);
+          notify_remote_field_changeset(&database, changeset);
+        },
       }
     }
-  });
+  })
+}
+
+/// Tags the field changes with the origin of the collab transaction that made them. The collab
+/// sends the field changes while the transaction is committed, before the plugins receive the
+/// update of the transaction. So the changes that are pending when the update is received are
+/// the changes of that transaction. The field_test `grid_field_change_is_sent_before_update_test`
+/// pins that order.
+struct FieldChangeOriginPlugin {
+  local_origin: CollabOrigin,
+  field_change: parking_lot::Mutex<broadcast::Receiver<FieldChange>>,
+  tx: mpsc::UnboundedSender<(FieldChange, bool)>,
+}
+
+impl CollabPlugin for FieldChangeOriginPlugin {
+  fn receive_update(&self, object_id: &str, txn: &TransactionMut, _update: &[u8]) {
+    let is_local = CollabOrigin::from(txn) == self.local_origin;
+    let mut field_change = self.field_change.lock();
+    loop {
+      match field_change.try_recv() {
+        Ok(change) => {
+          let _ = self.tx.send((change, is_local));
+        },
+        Err(TryRecvError::Lagged(n)) => {
+          warn!("Missed {} field changes of database: {}", n, object_id);
+        },
+        Err(_) => break,
+      }
+    }
+  }
+
+  fn plugin_type(&self) -> CollabPluginType {
+    CollabPluginType::Other("FieldChangeOriginPlugin".to_string())
+  }
+}
+
+fn notify_remote_field_changeset(database: &MutexDatabase, changeset: DatabaseFieldChangesetPB) {
+  let views = database.lock().get_all_database_views_meta();
+  for view in views {
+    send_notification(&view.id, DatabaseNotification::DidUpdateFields)
+      .payload(changeset.clone())
+      .send();
+  }
 }
 
 #[allow(dead_code)]
-pub(crate) async fn observe_view_change(database_id: &str, database: &Arc<MutexDatabase>) {
+pub(crate) async fn observe_view_change(
+  database_id: &str,
+  database: &Arc<MutexDatabase>,
+) -> JoinHandle<()> {
   let database_id = database_id.to_string();
   let weak_database = Arc::downgrade(database);
   let mut view_change = database.lock().subscribe_view_change();
@@ -132,11 +513,14 @@ pub(crate) async fn observe_view_change(database_id: &str, database: &Arc<MutexD
         DatabaseViewChange::DidDeleteFieldOrder { .. } => {},
       }
     }
-  });
+  })
 }
 
 #[allow(dead_code)]
-pub(crate) async fn observe_block_event(database_id: &str, database: &Arc<MutexDatabase>) {
+pub(crate) async fn observe_block_event(
+  database_id: &str,
+  database: &Arc<MutexDatabase>,
+) -> JoinHandle<()> {
   let database_id = database_id.to_string();
   let weak_database = Arc::downgrade(database);
   let mut block_event_rx = database.lock().subscribe_block_event();
@@ -164,7 +548,7 @@ pub(crate) async fn observe_block_event(database_id: &str, database: &Arc<MutexD
         },
       }
     }
-  });
+  })
 }
 
 #[allow(dead_code)]
@@ -1,5 +1,14 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use collab::core::collab::MutexCollab;
 use collab_database::rows::{RowDetail, RowId};
-use collab_database::views::DatabaseLayout;
+use collab_database::views::{CreateDatabaseParams, DatabaseLayout};
+
+use crate::entities::RelatedRowDataPB;
+use crate::services::calculations::Calculation;
 
 #[derive(Debug, Clone)]
 pub enum DatabaseRowEvent {
@@ -58,9 +67,540 @@ impl UpdatedRow {
   }
 }
 
+/// The params to create the clone of a database made by
+/// [crate::services::database::DatabaseEditor::clone_structure]. The params to create a view can't
+/// hold its calculations, so they're added once the database is created.
+#[derive(Debug, Clone)]
+pub struct CloneDatabaseParams {
+  pub params: CreateDatabaseParams,
+  /// The calculations of the views of the clone, keyed by the ids of the views of the clone.
+  pub calculations: HashMap<String, Vec<Calculation>>,
+}
+
+/// Maps the ids of a database to the ids of its clone. See
+/// [crate::services::database::DatabaseEditor::clone_structure_with_id_map].
+#[derive(Debug, Clone, Default)]
+pub struct CloneIdMap {
+  pub old_database_id: String,
+  pub new_database_id: String,
+  /// The ids of the views, keyed by the ids of the original views.
+  pub view_ids: HashMap<String, String>,
+  /// The ids of the fields, keyed by the ids of the original fields.
+  pub field_ids: HashMap<String, String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct CreateDatabaseViewParams {
   pub name: String,
   pub view_id: String,
   pub layout_type: DatabaseLayout,
 }
+
+/// Describes where a field is referenced by the views of a database.
+#[derive(Debug, Clone, Default)]
+pub struct FieldUsage {
+  /// The ids of the views that have a filter on the field.
+  pub filter_view_ids: Vec<String>,
+  /// The ids of the views that are sorted by the field.
+  pub sort_view_ids: Vec<String>,
+  /// The ids of the views that are grouped by the field.
+  pub group_view_ids: Vec<String>,
+  /// The ids of the views that have a calculation on the field.
+  pub calculation_view_ids: Vec<String>,
+}
+
+impl FieldUsage {
+  pub fn is_used(&self) -> bool {
+    !self.filter_view_ids.is_empty()
+      || !self.sort_view_ids.is_empty()
+      || !self.group_view_ids.is_empty()
+      || !self.calculation_view_ids.is_empty()
+  }
+}
+
+/// Counts the cell writes that haven't been acknowledged by the server. The writes are only
+/// counted when the database syncs with a server.
+///
+/// The rows are separate collabs that sync on their own, so the writes to the rows are tracked
+/// per row and acknowledged when the row collab finishes syncing. The other writes are
+/// acknowledged when the database collab finishes syncing.
+#[derive(Debug, Default)]
+pub struct UnsyncedEdits {
+  is_enabled: bool,
+  pending: parking_lot::Mutex<PendingEdits>,
+}
+
+#[derive(Debug, Default)]
+struct PendingEdits {
+  database_edits: usize,
+  row_edits: HashMap<RowId, PendingRowEdits>,
+}
+
+#[derive(Debug)]
+struct PendingRowEdits {
+  count: usize,
+  last_edited_at: Instant,
+}
+
+impl PendingEdits {
+  fn count(&self) -> usize {
+    self.database_edits
+      + self
+        .row_edits
+        .values()
+        .map(|edits| edits.count)
+        .sum::<usize>()
+  }
+}
+
+impl UnsyncedEdits {
+  pub fn new(is_enabled: bool) -> Self {
+    Self {
+      is_enabled,
+      pending: Default::default(),
+    }
+  }
+
+  pub fn count(&self) -> usize {
+    self.pending.lock().count()
+  }
+
+  /// Records the writes to the database collab. Returns true if the count becomes non-zero.
+  pub fn record_database_edits(&self, num_of_edits: usize) -> bool {
+    if !self.is_enabled || num_of_edits == 0 {
+      return false;
+    }
+    let mut pending = self.pending.lock();
+    let was_empty = pending.count() == 0;
+    pending.database_edits += num_of_edits;
+    was_empty
+  }
+
+  /// Records one write to each of the rows. Returns true if the count becomes non-zero.
+  pub fn record_row_edits<'a>(&self, row_ids: impl IntoIterator<Item = &'a RowId>) -> bool {
+    if !self.is_enabled {
+      return false;
+    }
+    let now = Instant::now();
+    let mut pending = self.pending.lock();
+    let was_empty = pending.count() == 0;
+    for row_id in row_ids {
+      let edits = pending
+        .row_edits
+        .entry(row_id.clone())
+        .or_insert(PendingRowEdits {
+          count: 0,
+          last_edited_at: now,
+        });
+      edits.count += 1;
+      edits.last_edited_at = now;
+    }
+    was_empty && pending.count() > 0
+  }
+
+  /// Acknowledges the writes to the database collab. Returns true if the count becomes zero.
+  pub fn acknowledge_database_edits(&self) -> bool {
+    let mut pending = self.pending.lock();
+    if pending.database_edits == 0 {
+      return false;
+    }
+    pending.database_edits = 0;
+    pending.count() == 0
+  }
+
+  /// Acknowledges the writes to the row. Returns true if the count becomes zero.
+  pub fn acknowledge_row_edits(&self, row_id: &RowId) -> bool {
+    let mut pending = self.pending.lock();
+    pending.row_edits.remove(row_id).is_some() && pending.count() == 0
+  }
+
+  /// Acknowledges the writes to the rows that were made before their collabs last finished
+  /// syncing. Returns true if the count becomes zero.
+  pub fn acknowledge_synced_rows(
+    &self,
+    last_synced_at: impl Fn(&RowId) -> Option<Instant>,
+  ) -> bool {
+    let mut pending = self.pending.lock();
+    let num_of_rows = pending.row_edits.len();
+    pending.row_edits.retain(|row_id, edits| {
+      last_synced_at(row_id).map_or(true, |synced_at| synced_at < edits.last_edited_at)
+    });
+    pending.row_edits.len() < num_of_rows && pending.count() == 0
+  }
+
+  /// Returns the rows whose last write was made before the given time and isn't acknowledged.
+  pub fn rows_edited_before(&self, instant: Instant) -> Vec<RowId> {
+    self
+      .pending
+      .lock()
+      .row_edits
+      .iter()
+      .filter(|(_, edits)| edits.last_edited_at <= instant)
+      .map(|(row_id, _)| row_id.clone())
+      .collect()
+  }
+}
+
+/// Broadcasts the id of a row each time the collab of the row finishes syncing with the server.
+/// It's shared by the collab service that builds the row collabs and all the editors.
+///
+/// It also keeps the sync state of each row collab that is alive, so the editors can re-read the
+/// states after missing some of the broadcasts, and restart the sync of the rows whose edits stay
+/// unsynced.
+#[derive(Clone)]
+pub struct RowSyncNotifier {
+  sender: tokio::sync::broadcast::Sender<RowId>,
+  row_collabs: Arc<parking_lot::Mutex<HashMap<RowId, RowCollabSyncState>>>,
+}
+
+struct RowCollabSyncState {
+  collab: Weak<MutexCollab>,
+  last_synced_at: Option<Instant>,
+}
+
+impl Default for RowSyncNotifier {
+  fn default() -> Self {
+    let (sender, _) = tokio::sync::broadcast::channel(1000);
+    Self {
+      sender,
+      row_collabs: Default::default(),
+    }
+  }
+}
+
+impl RowSyncNotifier {
+  pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<RowId> {
+    self.sender.subscribe()
+  }
+
+  pub fn did_build_row_collab(&self, row_id: RowId, collab: &Arc<MutexCollab>) {
+    self.row_collabs.lock().insert(
+      row_id,
+      RowCollabSyncState {
+        collab: Arc::downgrade(collab),
+        last_synced_at: None,
+      },
+    );
+  }
+
+  /// Does nothing if the row collab is built again in the meantime.
+  pub fn did_drop_row_collab(&self, row_id: &RowId, collab: &Weak<MutexCollab>) {
+    let mut row_collabs = self.row_collabs.lock();
+    if let Some(state) = row_collabs.get(row_id) {
+      if Weak::ptr_eq(&state.collab, collab) {
+        row_collabs.remove(row_id);
+      }
+    }
+  }
+
+  pub fn notify_sync_finished(&self, row_id: RowId) {
+    if let Some(state) = self.row_collabs.lock().get_mut(&row_id) {
+      state.last_synced_at = Some(Instant::now());
+    }
+    // Fails only if there is no receiver
+    let _ = self.sender.send(row_id);
+  }
+
+  /// Returns the last time the collab of the row finished syncing. None if it never finished
+  /// syncing or the collab isn't alive.
+  pub fn last_synced_at(&self, row_id: &RowId) -> Option<Instant> {
+    self
+      .row_collabs
+      .lock()
+      .get(row_id)
+      .and_then(|state| state.last_synced_at)
+  }
+
+  /// Starts the sync of the collab of the row again. Returns false if the collab isn't alive or
+  /// is locked.
+  pub fn retry_sync(&self, row_id: &RowId) -> bool {
+    let collab = match self
+      .row_collabs
+      .lock()
+      .get(row_id)
+      .and_then(|state| state.collab.upgrade())
+    {
+      Some(collab) => collab,
+      None => return false,
+    };
+    if let Some(lock_collab) = collab.try_lock() {
+      lock_collab.start_init_sync();
+      return true;
+    }
+    false
+  }
+}
+
+/// Broadcasts the id of a row and the uid of the collaborator each time the collab of the row
+/// receives an edit made by a collaborator. The editors record the collaborator as the last
+/// editor of the row, since the edit doesn't go through them. It's shared by the collab service
+/// that builds the row collabs and all the editors.
+#[derive(Clone)]
+pub struct RemoteRowEditNotifier {
+  sender: tokio::sync::broadcast::Sender<(RowId, i64)>,
+}
+
+impl Default for RemoteRowEditNotifier {
+  fn default() -> Self {
+    let (sender, _) = tokio::sync::broadcast::channel(1000);
+    Self { sender }
+  }
+}
+
+impl RemoteRowEditNotifier {
+  pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<(RowId, i64)> {
+    self.sender.subscribe()
+  }
+
+  pub fn notify_remote_edit(&self, row_id: RowId, uid: i64) {
+    // Fails only if there is no receiver
+    let _ = self.sender.send((row_id, uid));
+  }
+}
+
+/// The rows whose edits stay unsynced, with the time to restart the sync of each row. The delay
+/// between two retries of a row doubles after each retry, up to [Self::MAX_DELAY].
+#[derive(Debug, Default)]
+pub struct RowSyncRetryQueue {
+  rows: HashMap<RowId, RowSyncRetry>,
+}
+
+#[derive(Debug)]
+struct RowSyncRetry {
+  delay: Duration,
+  retry_at: Instant,
+}
+
+impl RowSyncRetryQueue {
+  pub const MIN_DELAY: Duration = Duration::from_secs(30);
+  pub const MAX_DELAY: Duration = Duration::from_secs(5 * 60);
+
+  /// Does nothing to the rows that are queued already.
+  pub fn push(&mut self, row_id: RowId, now: Instant) {
+    self.rows.entry(row_id).or_insert(RowSyncRetry {
+      delay: Self::MIN_DELAY,
+      retry_at: now + Self::MIN_DELAY,
+    });
+  }
+
+  pub fn remove(&mut self, row_id: &RowId) {
+    self.rows.remove(row_id);
+  }
+
+  /// Removes the rows that aren't in the given rows.
+  pub fn retain(&mut self, row_ids: &[RowId]) {
+    let row_ids = row_ids.iter().collect::<HashSet<_>>();
+    self.rows.retain(|row_id, _| row_ids.contains(row_id));
+  }
+
+  /// Returns the rows to retry now, and schedules their next retries.
+  pub fn pop_due(&mut self, now: Instant) -> Vec<RowId> {
+    let mut row_ids = vec![];
+    for (row_id, retry) in self.rows.iter_mut() {
+      if retry.retry_at <= now {
+        retry.delay = (retry.delay * 2).min(Self::MAX_DELAY);
+        retry.retry_at = now + retry.delay;
+        row_ids.push(row_id.clone());
+      }
+    }
+    row_ids
+  }
+}
+
+/// Whether a database is opened by the UI and whether its rows are loaded. See
+/// [crate::services::database::DatabaseEditor::load_state].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadState {
+  /// None of the views of the database is opened.
+  NotOpened,
+  /// A view is opened, but its rows are not loaded yet.
+  Loading,
+  /// A view is opened and its rows are loaded.
+  Loaded,
+}
+
+/// Tracks the [LoadState] of a database. Each time the database is closed, the loads that are
+/// still running are discarded, so a load that finishes after a workspace switch doesn't mark
+/// the database as loaded.
+#[derive(Debug, Default)]
+pub struct LoadTracker {
+  is_opened: AtomicBool,
+  is_loading_rows: AtomicBool,
+  has_loaded_rows: AtomicBool,
+  /// Increased each time the database is closed.
+  generation: AtomicU64,
+}
+
+impl LoadTracker {
+  pub fn state(&self) -> LoadState {
+    if !self.is_opened.load(Ordering::SeqCst) {
+      LoadState::NotOpened
+    } else if self.is_loading_rows.load(Ordering::SeqCst)
+      || !self.has_loaded_rows.load(Ordering::SeqCst)
+    {
+      LoadState::Loading
+    } else {
+      LoadState::Loaded
+    }
+  }
+
+  pub fn did_open(&self) {
+    self.is_opened.store(true, Ordering::SeqCst);
+  }
+
+  pub fn did_close(&self) {
+    self.generation.fetch_add(1, Ordering::SeqCst);
+    self.is_opened.store(false, Ordering::SeqCst);
+    self.is_loading_rows.store(false, Ordering::SeqCst);
+    self.has_loaded_rows.store(false, Ordering::SeqCst);
+  }
+
+  /// Returns the generation of the load, which is passed to [Self::did_load_rows].
+  pub fn will_load_rows(&self) -> u64 {
+    self.is_loading_rows.store(true, Ordering::SeqCst);
+    self.generation.load(Ordering::SeqCst)
+  }
+
+  /// Does nothing if the database was closed since the load started.
+  pub fn did_load_rows(&self, generation: u64) {
+    if self.generation.load(Ordering::SeqCst) == generation {
+      self.has_loaded_rows.store(true, Ordering::SeqCst);
+      self.is_loading_rows.store(false, Ordering::SeqCst);
+    }
+  }
+}
+
+/// The version of the fields of a database, which is bumped whenever a field is created, deleted,
+/// moved or changed, including the changes made by the collaborators. It lets the clients that
+/// cache the fields tell whether they're outdated without comparing them. The version isn't
+/// stored, it starts from the time the database is opened, so it still increases when the
+/// database is opened again.
+#[derive(Debug)]
+pub struct SchemaVersion {
+  version: AtomicI64,
+}
+
+impl Default for SchemaVersion {
+  fn default() -> Self {
+    Self::new(chrono::Utc::now().timestamp_millis())
+  }
+}
+
+impl SchemaVersion {
+  pub fn new(version: i64) -> Self {
+    Self {
+      version: AtomicI64::new(version),
+    }
+  }
+
+  pub fn get(&self) -> i64 {
+    self.version.load(Ordering::SeqCst)
+  }
+
+  pub fn bump(&self) {
+    self.version.fetch_add(1, Ordering::SeqCst);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::{Duration, Instant};
+
+  use collab_database::rows::RowId;
+
+  use super::{RowSyncRetryQueue, UnsyncedEdits};
+
+  #[test]
+  fn unsynced_edits_test() {
+    let unsynced_edits = UnsyncedEdits::new(true);
+    let row_1 = RowId::from("r1".to_string());
+    let row_2 = RowId::from("r2".to_string());
+    assert!(unsynced_edits.record_row_edits([&row_1, &row_2]));
+    assert!(!unsynced_edits.record_row_edits([&row_1]));
+    assert!(!unsynced_edits.record_database_edits(1));
+    assert_eq!(unsynced_edits.count(), 4);
+
+    // Syncing the database collab doesn't acknowledge the writes to the rows
+    assert!(!unsynced_edits.acknowledge_database_edits());
+    assert_eq!(unsynced_edits.count(), 3);
+    assert!(!unsynced_edits.acknowledge_row_edits(&row_1));
+    assert_eq!(unsynced_edits.count(), 1);
+    assert!(unsynced_edits.acknowledge_row_edits(&row_2));
+    assert!(!unsynced_edits.acknowledge_row_edits(&row_2));
+    assert_eq!(unsynced_edits.count(), 0);
+
+    // The edits aren't counted without a server
+    let unsynced_edits = UnsyncedEdits::new(false);
+    assert!(!unsynced_edits.record_row_edits([&row_1]));
+    assert!(!unsynced_edits.record_database_edits(1));
+    assert_eq!(unsynced_edits.count(), 0);
+  }
+
+  #[test]
+  fn acknowledge_synced_rows_test() {
+    let unsynced_edits = UnsyncedEdits::new(true);
+    let row_1 = RowId::from("r1".to_string());
+    let row_2 = RowId::from("r2".to_string());
+    let before_edits = Instant::now();
+    assert!(unsynced_edits.record_row_edits([&row_1, &row_2]));
+    let after_edits = Instant::now() + Duration::from_millis(1);
+    assert_eq!(unsynced_edits.rows_edited_before(before_edits).len(), 0);
+    assert_eq!(unsynced_edits.rows_edited_before(after_edits).len(), 2);
+
+    // The row 2 finished syncing before it was edited, so its edit is still unsynced
+    let is_synced = unsynced_edits.acknowledge_synced_rows(|row_id| {
+      if row_id == &row_1 {
+        Some(after_edits)
+      } else {
+        Some(before_edits)
+      }
+    });
+    assert!(!is_synced);
+    assert_eq!(unsynced_edits.rows_edited_before(after_edits), vec![row_2]);
+    assert!(unsynced_edits.acknowledge_synced_rows(|_| Some(after_edits)));
+    assert_eq!(unsynced_edits.count(), 0);
+  }
+
+  #[test]
+  fn row_sync_retry_queue_test() {
+    let mut queue = RowSyncRetryQueue::default();
+    let row_1 = RowId::from("r1".to_string());
+    let start = Instant::now();
+    queue.push(row_1.clone(), start);
+    assert!(queue.pop_due(start).is_empty());
+
+    let first_retry = start + RowSyncRetryQueue::MIN_DELAY;
+    assert_eq!(queue.pop_due(first_retry), vec![row_1.clone()]);
+    // Pushing a queued row doesn't reset its delay
+    queue.push(row_1.clone(), first_retry);
+    assert!(queue
+      .pop_due(first_retry + RowSyncRetryQueue::MIN_DELAY)
+      .is_empty());
+    let second_retry = first_retry + RowSyncRetryQueue::MIN_DELAY * 2;
+    assert_eq!(queue.pop_due(second_retry), vec![row_1.clone()]);
+
+    // The delay stops growing at the max delay
+    let mut now = second_retry;
+    for _ in 0..10 {
+      now += RowSyncRetryQueue::MAX_DELAY;
+      assert_eq!(queue.pop_due(now), vec![row_1.clone()]);
+    }
+
+    queue.retain(&[row_1.clone()]);
+    assert_eq!(queue.pop_due(now + RowSyncRetryQueue::MAX_DELAY).len(), 1);
+    queue.retain(&[]);
+    assert!(queue
+      .pop_due(now + RowSyncRetryQueue::MAX_DELAY * 2)
+      .is_empty());
+  }
+}
+
+/// The rows found by [crate::services::database::DatabaseEditor::scan_related_rows].
+#[derive(Debug, Clone, Default)]
+pub struct RelatedRowsScan {
+  pub rows: Vec<RelatedRowDataPB>,
+  /// The scan stopped at the limit before reaching the last row.
+  pub has_more: bool,
+  /// The scan was cancelled. The rows found before are still returned.
+  pub is_cancelled: bool,
+}
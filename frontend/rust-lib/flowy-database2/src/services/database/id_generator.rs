@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use collab_database::database::{gen_field_id, gen_option_id, gen_row_id};
+
+/// The kinds of the objects whose ids are generated by an [IdGenerator].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdKind {
+  Row,
+  Field,
+  SelectOption,
+}
+
+/// Generates the ids of the rows, fields and select options created by the
+/// [crate::services::database::DatabaseEditor], so the integrations can use readable or
+/// deterministic ids. The ids must be unique within the database, the editor fails to create an
+/// object whose generated id is already used.
+pub trait IdGenerator: Send + Sync {
+  fn next_id(&self, kind: IdKind) -> String;
+}
+
+/// The default [IdGenerator], which generates random ids.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RandomIdGenerator;
+
+impl IdGenerator for RandomIdGenerator {
+  fn next_id(&self, kind: IdKind) -> String {
+    match kind {
+      IdKind::Row => gen_row_id().into_inner(),
+      IdKind::Field => gen_field_id(),
+      IdKind::SelectOption => gen_option_id(),
+    }
+  }
+}
+
+/// An [IdGenerator] that generates ids like `row_1`, `field_2` and `option_3`. The counter is
+/// shared by all the kinds. The rows with such ids can't be synced with AppFlowy Cloud, which
+/// expects uuids, so it's meant for the tests and the local databases.
+#[derive(Debug)]
+pub struct SequentialIdGenerator {
+  next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+  pub fn new(start: u64) -> Self {
+    Self {
+      next: AtomicU64::new(start),
+    }
+  }
+}
+
+impl Default for SequentialIdGenerator {
+  fn default() -> Self {
+    Self::new(1)
+  }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+  fn next_id(&self, kind: IdKind) -> String {
+    let prefix = match kind {
+      IdKind::Row => "row",
+      IdKind::Field => "field",
+      IdKind::SelectOption => "option",
+    };
+    format!("{}_{}", prefix, self.next.fetch_add(1, Ordering::SeqCst))
+  }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::Field;
+use collab_database::rows::Cell;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::entities::{CheckboxCellDataPB, FieldType};
+use crate::services::cell::{apply_cell_changeset, stringify_cell};
+use crate::services::field::{
+  select_type_option_from_field, ChecklistCellChangeset, ChecklistCellData, DateCellChangeset,
+  DateCellData, SelectOptionCellChangeset, SelectOptionIds, CELL_DATA, CHECK, UNCHECK,
+};
+use lib_infra::box_any::BoxAny;
+
+/// The fields and the rows of a database, serialized as JSON. Unlike CSV, the values keep their
+/// types, so the data can be copied into a database of another workspace without loss.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct DatabaseJsonData {
+  pub fields: Vec<DatabaseJsonField>,
+  /// The values of the cells of each row, by field id.
+  pub rows: Vec<HashMap<String, Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct DatabaseJsonField {
+  pub id: String,
+  pub name: String,
+  pub field_type: FieldType,
+}
+
+impl From<&Field> for DatabaseJsonField {
+  fn from(field: &Field) -> Self {
+    Self {
+      id: field.id.clone(),
+      name: field.name.clone(),
+      field_type: FieldType::from(field.field_type),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportJsonResult {
+  pub rows_imported: usize,
+  /// Describes the fields of the JSON that can't be found in the database. Their values are
+  /// ignored.
+  pub warnings: Vec<String>,
+  /// Describes the values that aren't imported because they don't fit the field of the database,
+  /// e.g. the field has another type or the value has the wrong type.
+  pub errors: Vec<String>,
+}
+
+/// Returns the fields of the database that the fields of the JSON are imported into, by the ids of
+/// the JSON fields. A field is matched by its id first and then by its name. The fields that can't
+/// be found, or whose type differs, are reported in `result`.
+pub(crate) fn match_json_fields(
+  json_fields: &[DatabaseJsonField],
+  fields: &[Field],
+  result: &mut ImportJsonResult,
+) -> HashMap<String, Field> {
+  let mut matched_fields = HashMap::new();
+  for json_field in json_fields {
+    let field = fields
+      .iter()
+      .find(|field| field.id == json_field.id)
+      .or_else(|| fields.iter().find(|field| field.name == json_field.name));
+    match field {
+      None => result.warnings.push(format!(
+        "The field '{}' can't be found, its values are ignored",
+        json_field.name
+      )),
+      Some(field) if FieldType::from(field.field_type) != json_field.field_type => {
+        result.errors.push(format!(
+          "The field '{}' is {:?} in the JSON but {:?} in the database, its values aren't imported",
+          json_field.name,
+          json_field.field_type,
+          FieldType::from(field.field_type)
+        ))
+      },
+      Some(_) if is_derived(json_field.field_type) => {},
+      Some(field) if json_field.field_type == FieldType::Relation => result.warnings.push(format!(
+        "The values of the relation field '{}' aren't imported, they link to the rows of \
+           another database",
+        field.name
+      )),
+      Some(field) => {
+        matched_fields.insert(json_field.id.clone(), field.clone());
+      },
+    }
+  }
+  matched_fields
+}
+
+/// The values of these fields are read from the rows, so they're neither exported nor imported.
+fn is_derived(field_type: FieldType) -> bool {
+  matches!(
+    field_type,
+    FieldType::LastEditedTime
+      | FieldType::CreatedTime
+      | FieldType::LastEditedBy
+      | FieldType::CreatedBy
+  )
+}
+
+/// Returns the typed value of the cell, or None if the cell is empty or its field isn't exported.
+pub(crate) fn cell_to_json_value(field: &Field, cell: &Cell) -> Option<Value> {
+  let field_type = FieldType::from(field.field_type);
+  let value = match field_type {
+    _ if is_derived(field_type) => return None,
+    FieldType::Relation => return None,
+    FieldType::Number => {
+      let number = cell.get_str_value(CELL_DATA)?;
+      match number.parse::<f64>() {
+        Ok(number) => json!(number),
+        Err(_) => json!(number),
+      }
+    },
+    FieldType::Checkbox => json!(CheckboxCellDataPB::from(cell).is_checked),
+    FieldType::DateTime => {
+      let cell_data = DateCellData::from(cell);
+      json!({
+        "timestamp": cell_data.timestamp?,
+        "end_timestamp": cell_data.end_timestamp,
+        "include_time": cell_data.include_time,
+        "is_range": cell_data.is_range,
+      })
+    },
+    FieldType::SingleSelect | FieldType::MultiSelect => {
+      let type_option = select_type_option_from_field(field).ok()?;
+      let names = SelectOptionIds::from(cell)
+        .iter()
+        .flat_map(|id| type_option.options().iter().find(|option| &option.id == id))
+        .map(|option| option.name.clone())
+        .collect::<Vec<String>>();
+      json!(names)
+    },
+    FieldType::Checklist => {
+      let cell_data = ChecklistCellData::from(cell);
+      let items = cell_data
+        .options
+        .iter()
+        .map(|option| {
+          json!({
+            "name": option.name,
+            "checked": cell_data.selected_option_ids.contains(&option.id),
+          })
+        })
+        .collect::<Vec<Value>>();
+      json!(items)
+    },
+    _ => json!(stringify_cell(cell, field)),
+  };
+  let is_empty = match &value {
+    Value::String(s) => s.is_empty(),
+    Value::Array(items) => items.is_empty(),
+    _ => false,
+  };
+  (!is_empty).then_some(value)
+}
+
+/// Returns the names of the select options in the value of a select field.
+pub(crate) fn json_select_option_names(value: &Value) -> Vec<String> {
+  match value {
+    Value::String(name) => vec![name.clone()],
+    Value::Array(names) => names
+      .iter()
+      .flat_map(|name| name.as_str().map(|name| name.to_string()))
+      .collect(),
+    _ => vec![],
+  }
+}
+
+/// Makes the cell of the value. The options of a select value must already exist in the field.
+/// Returns the reason if the value doesn't fit the field.
+pub(crate) fn json_value_to_cell(field: &Field, value: &Value) -> Result<Cell, String> {
+  let field_type = FieldType::from(field.field_type);
+  let changeset = match (field_type, value) {
+    (FieldType::Number, Value::Number(number)) => BoxAny::new(number.to_string()),
+    (FieldType::Checkbox, Value::Bool(is_checked)) => {
+      let value = if *is_checked { CHECK } else { UNCHECK };
+      BoxAny::new(value.to_string())
+    },
+    (FieldType::DateTime, Value::Number(timestamp)) => BoxAny::new(DateCellChangeset {
+      date: timestamp.as_i64(),
+      ..Default::default()
+    }),
+    (FieldType::DateTime, Value::Object(date)) if date.contains_key("timestamp") => {
+      BoxAny::new(DateCellChangeset {
+        date: date.get("timestamp").and_then(Value::as_i64),
+        end_date: date.get("end_timestamp").and_then(Value::as_i64),
+        include_time: date.get("include_time").and_then(Value::as_bool),
+        is_range: date.get("is_range").and_then(Value::as_bool),
+        ..Default::default()
+      })
+    },
+    (FieldType::SingleSelect | FieldType::MultiSelect, Value::String(_) | Value::Array(_)) => {
+      let type_option = select_type_option_from_field(field).map_err(|err| err.msg)?;
+      let option_ids = json_select_option_names(value)
+        .iter()
+        .flat_map(|name| {
+          type_option
+            .options()
+            .iter()
+            .find(|option| &option.name == name)
+        })
+        .map(|option| option.id.clone())
+        .collect();
+      BoxAny::new(SelectOptionCellChangeset::from_insert_options(option_ids))
+    },
+    (FieldType::Checklist, Value::Array(items)) => {
+      let insert_options = items
+        .iter()
+        .flat_map(|item| {
+          let name = item.get("name")?.as_str()?.to_string();
+          let is_checked = item.get("checked").and_then(Value::as_bool);
+          Some((name, is_checked.unwrap_or(false)))
+        })
+        .collect();
+      BoxAny::new(ChecklistCellChangeset {
+        insert_options,
+        ..Default::default()
+      })
+    },
+    (
+      FieldType::RichText | FieldType::Number | FieldType::URL | FieldType::Summary,
+      Value::String(s),
+    ) => BoxAny::new(s.clone()),
+    _ => {
+      return Err(format!(
+        "{} isn't a valid value of the field '{}' of {:?}",
+        value, field.name, field_type
+      ))
+    },
+  };
+  apply_cell_changeset(changeset, None, field, None).map_err(|err| err.msg)
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+/// Limits the memory that the database editors use on memory-constrained devices, e.g. low-end
+/// phones. Each limit trades speed for memory:
+/// - The decoded cells are cached up to [Self::cell_cache_capacity], so the cells of a large
+///   view are decoded again more often when they're sorted, filtered or displayed.
+/// - Only the first [Self::initial_row_limit] rows are returned when a view is opened. The rest
+///   are loaded page by page, so scrolling to the end of a large view takes more round trips.
+/// - The CSV import creates at most [Self::import_batch_size] rows at once, so a large CSV is
+///   imported in more, smaller writes.
+/// - The editors that have no opening views are closed after [Self::idle_database_timeout], so
+///   reopening a database that was closed has to load it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LowMemoryProfile {
+  /// The max number of the decoded cells cached by an editor. The cache is cleared once it's
+  /// full.
+  pub cell_cache_capacity: usize,
+  /// The max number of the rows returned when a view is opened.
+  pub initial_row_limit: usize,
+  /// The max number of the rows created at once by the CSV import.
+  pub import_batch_size: usize,
+  /// Replaces the idle timeout of the database editors if it's shorter.
+  pub idle_database_timeout: Duration,
+}
+
+impl Default for LowMemoryProfile {
+  fn default() -> Self {
+    Self {
+      cell_cache_capacity: 2_000,
+      initial_row_limit: 200,
+      import_batch_size: 50,
+      idle_database_timeout: Duration::from_secs(60),
+    }
+  }
+}
@@ -0,0 +1,161 @@
+use std::str::FromStr;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::Field;
+use collab_database::rows::{new_cell_builder, Cell, Row};
+use rust_decimal::Decimal;
+
+use crate::entities::FieldType;
+use crate::services::cell::CellCache;
+use crate::services::field::{
+  ChecklistCellData, NumberCellData, NumberCellFormat, NumberFormat, SelectOptionIds,
+  TypeOptionCellExt, CELL_DATA,
+};
+
+/// Decides which row's value is kept when more than one of the merged rows has a value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeRowsPreference {
+  /// The primary row wins, then the duplicates in the given order.
+  #[default]
+  Primary,
+  /// The most recently modified row wins.
+  Latest,
+}
+
+/// Resolves the conflicts when merging duplicate rows into the primary row:
+/// - Multi-select: the options of all the rows are combined.
+/// - Checklist: the items of all the rows are combined. Items with the same name are merged, and
+///   they're checked if any of them is checked.
+/// - Number: the numbers of all the rows are summed if `sum_numbers` is true. Otherwise, it's
+///   resolved like the other field types.
+/// - Created time, last edited time, created by and last edited by: they're kept as they are.
+/// - The other field types: the value of the preferred row according to `prefer`. The empty
+///   values are skipped, so the empty cells of the preferred row are filled by the other rows.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeRowsStrategy {
+  pub prefer: MergeRowsPreference,
+  pub sum_numbers: bool,
+}
+
+/// Returns the cells of the primary row that change after merging the other rows into it. The
+/// primary row is the first one of `rows`.
+pub(crate) fn merge_row_cells(
+  fields: &[Field],
+  rows: &[Row],
+  strategy: &MergeRowsStrategy,
+  cell_cache: Option<CellCache>,
+) -> Vec<(String, Cell)> {
+  let primary_row = match rows.first() {
+    None => return vec![],
+    Some(row) => row,
+  };
+  let mut preferred_rows = rows.iter().collect::<Vec<&Row>>();
+  if strategy.prefer == MergeRowsPreference::Latest {
+    // The sort is stable, so the rows that are modified at the same time keep the given order
+    preferred_rows.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+  }
+
+  let mut merged_cells = vec![];
+  for field in fields {
+    let field_type = FieldType::from(field.field_type);
+    if field_type.is_created_time() || field_type.is_last_edited_time() || field_type.is_user() {
+      continue;
+    }
+
+    let handler =
+      TypeOptionCellExt::new(field, cell_cache.clone()).get_type_option_cell_data_handler();
+    let cells = preferred_rows
+      .iter()
+      .flat_map(|row| row.cells.get(&field.id))
+      .filter(|cell| match &handler {
+        None => true,
+        Some(handler) => !handler.handle_is_cell_empty(cell, field),
+      })
+      .collect::<Vec<&Cell>>();
+
+    let merged_cell = match field_type {
+      FieldType::MultiSelect => merge_select_option_cells(&cells, field_type),
+      FieldType::Checklist => merge_checklist_cells(&cells),
+      FieldType::Number if strategy.sum_numbers => sum_number_cells(&cells),
+      _ => cells.first().copied().cloned(),
+    };
+    if let Some(merged_cell) = merged_cell {
+      if primary_row.cells.get(&field.id) != Some(&merged_cell) {
+        merged_cells.push((field.id.clone(), merged_cell));
+      }
+    }
+  }
+  merged_cells
+}
+
+fn merge_select_option_cells(cells: &[&Cell], field_type: FieldType) -> Option<Cell> {
+  let mut option_ids: Vec<String> = vec![];
+  for cell in cells {
+    for option_id in SelectOptionIds::from(*cell).into_inner() {
+      if !option_ids.contains(&option_id) {
+        option_ids.push(option_id);
+      }
+    }
+  }
+  if option_ids.is_empty() {
+    None
+  } else {
+    Some(SelectOptionIds::from(option_ids).to_cell_data(field_type))
+  }
+}
+
+fn merge_checklist_cells(cells: &[&Cell]) -> Option<Cell> {
+  let mut merged = ChecklistCellData::default();
+  for cell in cells {
+    let cell_data = ChecklistCellData::from(*cell);
+    for option in cell_data.options.iter() {
+      let is_selected = cell_data.selected_option_ids.contains(&option.id);
+      match merged
+        .options
+        .iter()
+        .find(|merged| merged.name == option.name)
+      {
+        Some(merged_option) => {
+          if is_selected && !merged.selected_option_ids.contains(&merged_option.id) {
+            merged.selected_option_ids.push(merged_option.id.clone());
+          }
+        },
+        None => {
+          merged.options.push(option.clone());
+          if is_selected {
+            merged.selected_option_ids.push(option.id.clone());
+          }
+        },
+      }
+    }
+  }
+  if merged.options.is_empty() {
+    None
+  } else {
+    Some(Cell::from(merged))
+  }
+}
+
+fn sum_number_cells(cells: &[&Cell]) -> Option<Cell> {
+  let numbers = cells
+    .iter()
+    .flat_map(|cell| {
+      let NumberCellData(num_str) = NumberCellData::from(*cell);
+      Decimal::from_str(&num_str).ok().or_else(|| {
+        NumberCellFormat::from_format_str(&num_str, &NumberFormat::Num)
+          .ok()
+          .and_then(|format| *format.decimal())
+      })
+    })
+    .collect::<Vec<Decimal>>();
+  if numbers.is_empty() {
+    return None;
+  }
+
+  let sum = numbers.into_iter().sum::<Decimal>();
+  Some(
+    new_cell_builder(FieldType::Number)
+      .insert_str_value(CELL_DATA, sum.to_string())
+      .build(),
+  )
+}
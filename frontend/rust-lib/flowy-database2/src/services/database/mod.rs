@@ -1,8 +1,34 @@
+mod clock;
 mod database_editor;
+mod database_errors;
 mod database_observe;
 mod entities;
+mod id_generator;
+mod json_data;
+mod low_memory;
+mod merge_rows;
+mod open_profile;
+mod primary_value_index;
+mod row_load_delegate;
+mod row_metas;
+mod sync_state_log;
 mod util;
+mod view_settings;
 
+pub use clock::{Clock, ManualClock, SystemClock};
 pub use database_editor::*;
+pub(crate) use database_errors::DatabaseErrorReporter;
+pub(crate) use database_observe::RemoteRowEditPlugin;
 pub use entities::*;
+pub use id_generator::{IdGenerator, IdKind, RandomIdGenerator, SequentialIdGenerator};
+pub use json_data::ImportJsonResult;
+pub use low_memory::LowMemoryProfile;
+pub use merge_rows::{MergeRowsPreference, MergeRowsStrategy};
+pub use open_profile::{OpenProfile, OpenProfiles};
+pub(crate) use primary_value_index::PrimaryValueIndex;
+pub use row_load_delegate::RowLoadDelegate;
+pub(crate) use row_metas::get_all_row_users;
+pub(crate) use sync_state_log::SyncStateLogThrottle;
+pub use sync_state_log::DEFAULT_SYNC_STATE_LOG_WINDOW;
 pub(crate) use util::database_view_setting_pb_from_view;
+pub use view_settings::ImportViewSettingsResult;
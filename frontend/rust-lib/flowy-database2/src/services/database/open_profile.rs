@@ -0,0 +1,45 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+/// The number of the most recent profiles that are kept.
+const MAX_OPEN_PROFILES: usize = 20;
+
+/// The time spent in each phase of loading the rows of a view when it's opened.
+#[derive(Debug, Clone)]
+pub struct OpenProfile {
+  pub view_id: String,
+  pub get_row_orders: Duration,
+  pub load_rows: Duration,
+  pub filter: Duration,
+  pub sort: Duration,
+  pub total: Duration,
+  /// The number of rows in the view before filtering.
+  pub num_of_rows: usize,
+  /// The number of the loaded rows left after filtering. Only a part of the rows is loaded if the
+  /// number of the rows is limited.
+  pub num_of_visible_rows: usize,
+}
+
+/// Keeps the last [MAX_OPEN_PROFILES] profiles. It's shared by all the editors, so the profiles
+/// outlive the editors that recorded them.
+#[derive(Default)]
+pub struct OpenProfiles {
+  profiles: Mutex<VecDeque<OpenProfile>>,
+}
+
+impl OpenProfiles {
+  pub fn record(&self, profile: OpenProfile) {
+    let mut profiles = self.profiles.lock();
+    if profiles.len() == MAX_OPEN_PROFILES {
+      profiles.pop_front();
+    }
+    profiles.push_back(profile);
+  }
+
+  /// Returns the profiles from the oldest to the most recent.
+  pub fn to_vec(&self) -> Vec<OpenProfile> {
+    self.profiles.lock().iter().cloned().collect()
+  }
+}
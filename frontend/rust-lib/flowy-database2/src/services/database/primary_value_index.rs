@@ -0,0 +1,176 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use collab_database::rows::RowId;
+
+use crate::entities::RelatedRowDataPB;
+
+/// Maps the titles of the rows, which are the contents of their primary field, to the rows, so a
+/// database that's used as a lookup table doesn't decode the primary cell of every row on each
+/// lookup. It costs memory proportional to the rows, so it's disabled by default.
+///
+/// The index is built on the first lookup after it's enabled. The rows whose primary cell is
+/// edited are marked stale and indexed again on the next lookup, and the deleted rows are removed.
+/// The index is rebuilt if the number of rows changes, which covers the rows created locally. The
+/// rows created or deleted by the collaborators invalidate the index, see [Self::invalidate], as
+/// a delete and a create leave the number of rows unchanged.
+#[derive(Default)]
+pub(crate) struct PrimaryValueIndex {
+  is_enabled: AtomicBool,
+  state: parking_lot::Mutex<Option<IndexState>>,
+}
+
+struct IndexState {
+  primary_field_id: String,
+  row_count: usize,
+  row_ids_by_title: HashMap<String, Vec<RowId>>,
+  title_by_row_id: HashMap<RowId, String>,
+  stale_row_ids: HashSet<RowId>,
+}
+
+impl IndexState {
+  fn insert(&mut self, row_data: RelatedRowDataPB) {
+    let row_id = RowId::from(row_data.row_id);
+    self
+      .row_ids_by_title
+      .entry(row_data.name.clone())
+      .or_default()
+      .push(row_id.clone());
+    self.title_by_row_id.insert(row_id, row_data.name);
+  }
+
+  fn remove(&mut self, row_id: &RowId) {
+    if let Some(title) = self.title_by_row_id.remove(row_id) {
+      if let Some(row_ids) = self.row_ids_by_title.get_mut(&title) {
+        row_ids.retain(|id| id != row_id);
+        if row_ids.is_empty() {
+          self.row_ids_by_title.remove(&title);
+        }
+      }
+    }
+  }
+}
+
+impl PrimaryValueIndex {
+  pub fn new(is_enabled: bool) -> Self {
+    Self {
+      is_enabled: AtomicBool::new(is_enabled),
+      state: Default::default(),
+    }
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    self.is_enabled.load(Ordering::SeqCst)
+  }
+
+  /// Disabling the index frees its memory.
+  pub fn set_enabled(&self, is_enabled: bool) {
+    self.is_enabled.store(is_enabled, Ordering::SeqCst);
+    if !is_enabled {
+      *self.state.lock() = None;
+    }
+  }
+
+  /// Returns true if the index has to be built from all the rows before the next lookup.
+  pub fn needs_rebuild(&self, primary_field_id: &str, row_count: usize) -> bool {
+    match &*self.state.lock() {
+      None => true,
+      Some(state) => state.primary_field_id != primary_field_id || state.row_count != row_count,
+    }
+  }
+
+  pub fn rebuild(
+    &self,
+    primary_field_id: &str,
+    row_count: usize,
+    row_datas: Vec<RelatedRowDataPB>,
+  ) {
+    if !self.is_enabled() {
+      return;
+    }
+    let mut state = IndexState {
+      primary_field_id: primary_field_id.to_string(),
+      row_count,
+      row_ids_by_title: HashMap::new(),
+      title_by_row_id: HashMap::new(),
+      stale_row_ids: HashSet::new(),
+    };
+    for row_data in row_datas {
+      state.insert(row_data);
+    }
+    *self.state.lock() = Some(state);
+  }
+
+  /// Returns the rows whose primary cell changed since they were indexed.
+  pub fn take_stale_row_ids(&self) -> Vec<RowId> {
+    match &mut *self.state.lock() {
+      None => vec![],
+      Some(state) => state.stale_row_ids.drain().collect(),
+    }
+  }
+
+  /// Indexes the rows returned by [Self::take_stale_row_ids] again.
+  pub fn reindex_rows(&self, row_datas: Vec<RelatedRowDataPB>) {
+    if let Some(state) = &mut *self.state.lock() {
+      for row_data in row_datas {
+        state.remove(&RowId::from(row_data.row_id.clone()));
+        state.insert(row_data);
+      }
+    }
+  }
+
+  /// Marks the row stale if the cell belongs to the primary field.
+  pub fn did_update_cell(&self, row_id: &RowId, field_id: &str) {
+    if let Some(state) = &mut *self.state.lock() {
+      if state.primary_field_id == field_id {
+        state.stale_row_ids.insert(row_id.clone());
+      }
+    }
+  }
+
+  /// Drops the index, so it's built again on the next lookup. Called when the rows of the
+  /// database are created or deleted, including by the collaborators.
+  pub fn invalidate(&self) {
+    *self.state.lock() = None;
+  }
+
+  pub fn did_delete_rows(&self, row_ids: &[RowId]) {
+    if let Some(state) = &mut *self.state.lock() {
+      for row_id in row_ids {
+        if state.title_by_row_id.contains_key(row_id) {
+          state.remove(row_id);
+          state.row_count -= 1;
+        }
+        state.stale_row_ids.remove(row_id);
+      }
+    }
+  }
+
+  pub fn get_row_ids(&self, title: &str) -> Vec<RowId> {
+    match &*self.state.lock() {
+      None => vec![],
+      Some(state) => state
+        .row_ids_by_title
+        .get(title)
+        .cloned()
+        .unwrap_or_default(),
+    }
+  }
+
+  /// Returns the titles of the rows in the given order. The rows that aren't indexed are skipped.
+  pub fn get_row_datas(&self, row_ids: &[String]) -> Vec<RelatedRowDataPB> {
+    match &*self.state.lock() {
+      None => vec![],
+      Some(state) => row_ids
+        .iter()
+        .flat_map(|row_id| {
+          let name = state.title_by_row_id.get(&RowId::from(row_id.clone()))?;
+          Some(RelatedRowDataPB {
+            row_id: row_id.clone(),
+            name: name.clone(),
+          })
+        })
+        .collect(),
+    }
+  }
+}
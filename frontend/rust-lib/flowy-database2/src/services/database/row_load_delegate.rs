@@ -0,0 +1,8 @@
+use collab_database::rows::RowId;
+
+/// Called by the [crate::services::database::DatabaseEditor] before it reads a chunk of rows of a
+/// view. The tests set one with [crate::services::database::DatabaseEditor::set_row_load_delegate]
+/// to make the chunk fail to load, e.g. by panicking, as if its rows were corrupted.
+pub trait RowLoadDelegate: Send + Sync {
+  fn will_load_rows(&self, row_ids: &[RowId]);
+}
@@ -0,0 +1,182 @@
+use collab::preclude::{Map, MapRef, MapRefExtension, ReadTxn, TransactionMut, YrsValue};
+use collab_database::database::Database;
+use collab_database::rows::{RowDetail, RowId};
+use collab_entity::define::DATABASE;
+use tracing::error;
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+use crate::entities::RowMetaPB;
+use crate::services::field::{RowUsers, RowUsersByRowId};
+use crate::services::field_settings::DATABASE_METAS;
+
+/// The map of the database metas that keeps the metas of the rows that the [RowMeta] of
+/// collab_database has no key for. Each row has its own map keyed by the row id, so they're synced
+/// with the database without being mistaken for cells.
+///
+/// [RowMeta]: collab_database::rows::RowMeta
+const ROW_METAS: &str = "row_metas";
+const LOCKED: &str = "locked";
+const CREATED_BY: &str = "created_by";
+const LAST_EDITED_BY: &str = "last_edited_by";
+
+/// Runs `f` with the map of each row's metas in one transaction. The maps that don't exist yet
+/// are created.
+fn update_row_metas<'a, F>(
+  database: &Database,
+  row_ids: impl IntoIterator<Item = &'a RowId>,
+  mut f: F,
+) -> FlowyResult<()>
+where
+  F: FnMut(&mut TransactionMut, &MapRef),
+{
+  let collab = database.get_collab().lock();
+  collab.with_origin_transact_mut(|txn| {
+    let metas = collab
+      .get_map_with_txn(txn, vec![DATABASE, DATABASE_METAS])
+      .ok_or_else(|| FlowyError::internal().with_context("The database has no metas"))?;
+    let all_row_metas = match metas.get_map_with_txn(txn, ROW_METAS) {
+      Some(all_row_metas) => all_row_metas,
+      None => metas.create_map_with_txn(txn, ROW_METAS),
+    };
+    for row_id in row_ids {
+      let row_metas = match all_row_metas.get_map_with_txn(txn, row_id.as_str()) {
+        Some(row_metas) => row_metas,
+        None => all_row_metas.create_map_with_txn(txn, row_id.as_str()),
+      };
+      f(txn, &row_metas);
+    }
+    Ok(())
+  })
+}
+
+/// Removes the metas of the rows, e.g. after the rows are deleted.
+pub(crate) fn remove_row_metas(database: &Database, row_ids: &[RowId]) {
+  let collab = database.get_collab().lock();
+  collab.with_origin_transact_mut(|txn| {
+    if let Some(all_row_metas) =
+      collab.get_map_with_txn(txn, vec![DATABASE, DATABASE_METAS, ROW_METAS])
+    {
+      for row_id in row_ids {
+        all_row_metas.delete_with_txn(txn, row_id.as_str());
+      }
+    }
+  })
+}
+
+pub(crate) fn is_row_locked(database: &Database, row_id: &RowId) -> bool {
+  let collab = database.get_collab().lock();
+  let txn = collab.transact();
+  collab
+    .get_map_with_txn(
+      &txn,
+      vec![DATABASE, DATABASE_METAS, ROW_METAS, row_id.as_str()],
+    )
+    .and_then(|row_metas| row_metas.get_bool_with_txn(&txn, LOCKED))
+    .unwrap_or(false)
+}
+
+/// Returns [ErrorCode::RecordLocked] if the row is locked. The caller must hold the lock of the
+/// database until the row is written, so the row can't be locked in the meantime.
+pub(crate) fn ensure_row_not_locked(database: &Database, row_id: &RowId) -> FlowyResult<()> {
+  if is_row_locked(database, row_id) {
+    return Err(
+      FlowyError::new(ErrorCode::RecordLocked, "The row is locked")
+        .with_context(format!("row_id: {}", row_id)),
+    );
+  }
+  Ok(())
+}
+
+pub(crate) fn set_row_locked(database: &Database, row_id: &RowId, locked: bool) -> FlowyResult<()> {
+  update_row_metas(database, [row_id], |txn, row_metas| {
+    row_metas.insert_bool_with_txn(txn, LOCKED, locked);
+  })
+}
+
+/// Records the user as the creator and the last editor of the new rows. The rows are created
+/// anyway if the users can't be recorded.
+pub(crate) fn set_rows_created_by<'a>(
+  database: &Database,
+  row_ids: impl IntoIterator<Item = &'a RowId>,
+  uid: i64,
+) {
+  let result = update_row_metas(database, row_ids, |txn, row_metas| {
+    row_metas.insert_i64_with_txn(txn, CREATED_BY, uid);
+    row_metas.insert_i64_with_txn(txn, LAST_EDITED_BY, uid);
+  });
+  if let Err(err) = result {
+    error!("Failed to record the creator of the rows: {}", err);
+  }
+}
+
+/// Records the user as the last editor of the rows. The rows that the user edited last already
+/// are left unchanged, so editing a row again doesn't write to the database.
+pub(crate) fn set_rows_last_edited_by<'a>(
+  database: &Database,
+  row_ids: impl IntoIterator<Item = &'a RowId>,
+  uid: i64,
+) {
+  let row_ids = row_ids
+    .into_iter()
+    .filter(|row_id| get_row_users(database, row_id).last_edited_by != Some(uid))
+    .collect::<Vec<_>>();
+  if row_ids.is_empty() {
+    return;
+  }
+  let result = update_row_metas(database, row_ids, |txn, row_metas| {
+    row_metas.insert_i64_with_txn(txn, LAST_EDITED_BY, uid);
+  });
+  if let Err(err) = result {
+    error!("Failed to record the last editor of the rows: {}", err);
+  }
+}
+
+fn read_row_users<T: ReadTxn>(txn: &T, row_metas: &MapRef) -> RowUsers {
+  RowUsers {
+    created_by: row_metas.get_i64_with_txn(txn, CREATED_BY),
+    last_edited_by: row_metas.get_i64_with_txn(txn, LAST_EDITED_BY),
+  }
+}
+
+pub(crate) fn get_row_users(database: &Database, row_id: &RowId) -> RowUsers {
+  let collab = database.get_collab().lock();
+  let txn = collab.transact();
+  collab
+    .get_map_with_txn(
+      &txn,
+      vec![DATABASE, DATABASE_METAS, ROW_METAS, row_id.as_str()],
+    )
+    .map(|row_metas| read_row_users(&txn, &row_metas))
+    .unwrap_or_default()
+}
+
+/// Returns the users of all the rows that have them, read in one transaction.
+pub(crate) fn get_all_row_users(database: &Database) -> RowUsersByRowId {
+  let collab = database.get_collab().lock();
+  let txn = collab.transact();
+  let all_row_metas = match collab.get_map_with_txn(&txn, vec![DATABASE, DATABASE_METAS, ROW_METAS])
+  {
+    Some(all_row_metas) => all_row_metas,
+    None => return RowUsersByRowId::new(),
+  };
+  all_row_metas
+    .iter(&txn)
+    .filter_map(|(row_id, value)| match value {
+      YrsValue::YMap(row_metas) => Some((
+        RowId::from(row_id.to_string()),
+        read_row_users(&txn, &row_metas),
+      )),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Returns the [RowMetaPB] of the row, including its locked state that the [RowDetail] doesn't
+/// have.
+pub(crate) fn row_meta_pb(database: &Database, row_detail: &RowDetail) -> RowMetaPB {
+  RowMetaPB {
+    is_locked: is_row_locked(database, &row_detail.row.id),
+    ..RowMetaPB::from(row_detail)
+  }
+}
@@ -0,0 +1,80 @@
+use std::time::{Duration, Instant};
+
+use crate::entities::DatabaseSyncState;
+
+/// The default window of [SyncStateLogThrottle].
+pub const DEFAULT_SYNC_STATE_LOG_WINDOW: Duration = Duration::from_secs(5);
+
+/// Collapses the identical consecutive sync states into a single log line. A busy database can
+/// go through thousands of sync states while it's edited, so logging each of them fills the log
+/// files quickly. Only the logging is throttled; every state is still sent to the frontend.
+pub(crate) struct SyncStateLogThrottle {
+  window: Duration,
+  /// The last logged state and when it was logged.
+  last_logged: Option<(DatabaseSyncState, Instant)>,
+  /// The number of the states identical to the last logged one that were not logged since.
+  num_of_skipped: usize,
+}
+
+impl SyncStateLogThrottle {
+  pub(crate) fn new(window: Duration) -> Self {
+    Self {
+      window,
+      last_logged: None,
+      num_of_skipped: 0,
+    }
+  }
+
+  /// Returns Some if the state should be logged. The value is the number of the states that were
+  /// skipped since the previous log line, which is logged along with the state.
+  pub(crate) fn record(&mut self, state: DatabaseSyncState, now: Instant) -> Option<usize> {
+    if let Some((last_state, logged_at)) = self.last_logged {
+      if last_state == state && now.saturating_duration_since(logged_at) < self.window {
+        self.num_of_skipped += 1;
+        return None;
+      }
+    }
+
+    self.last_logged = Some((state, now));
+    Some(std::mem::take(&mut self.num_of_skipped))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn identical_states_within_window_are_collapsed_test() {
+    let mut throttle = SyncStateLogThrottle::new(Duration::from_secs(5));
+    let start = Instant::now();
+    assert_eq!(throttle.record(DatabaseSyncState::Syncing, start), Some(0));
+    for i in 1..=10 {
+      let now = start + Duration::from_millis(i * 100);
+      assert_eq!(throttle.record(DatabaseSyncState::Syncing, now), None);
+    }
+
+    // A different state is logged right away, along with the number of the skipped states
+    let now = start + Duration::from_secs(2);
+    assert_eq!(
+      throttle.record(DatabaseSyncState::SyncFinished, now),
+      Some(10)
+    );
+    assert_eq!(throttle.record(DatabaseSyncState::Syncing, now), Some(0));
+  }
+
+  #[test]
+  fn identical_state_is_logged_again_after_window_test() {
+    let mut throttle = SyncStateLogThrottle::new(Duration::from_secs(5));
+    let start = Instant::now();
+    assert_eq!(throttle.record(DatabaseSyncState::Syncing, start), Some(0));
+    assert_eq!(
+      throttle.record(DatabaseSyncState::Syncing, start + Duration::from_secs(1)),
+      None
+    );
+    assert_eq!(
+      throttle.record(DatabaseSyncState::Syncing, start + Duration::from_secs(5)),
+      Some(1)
+    );
+  }
+}
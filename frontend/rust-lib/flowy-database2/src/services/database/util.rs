@@ -1,4 +1,9 @@
-use collab_database::views::{DatabaseLayout, DatabaseView};
+use std::collections::HashMap;
+
+use collab_database::database::{gen_database_group_id, gen_database_sort_id, timestamp};
+use collab_database::views::{
+  CreateViewParams, DatabaseLayout, DatabaseView, FilterMap, GroupSettingMap, SortMap,
+};
 
 use crate::entities::{
   DatabaseLayoutPB, DatabaseLayoutSettingPB, DatabaseViewSettingPB, FieldSettingsPB, FilterPB,
@@ -7,6 +12,7 @@ use crate::entities::{
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::Filter;
 use crate::services::group::GroupSetting;
+use crate::services::setting::CalendarLayoutSetting;
 use crate::services::sort::Sort;
 
 pub(crate) fn database_view_setting_pb_from_view(view: DatabaseView) -> DatabaseViewSettingPB {
@@ -73,3 +79,188 @@ pub(crate) fn database_view_setting_pb_from_view(view: DatabaseView) -> Database
     layout_setting,
   }
 }
+
+/// Builds the params to recreate `view` in another database, without any rows. Every field id
+/// referenced by the view's filters, sorts, groups, field settings and calendar layout setting is
+/// replaced using `new_field_ids`. The filters, sorts and group settings get new ids.
+pub(crate) fn create_view_params_from_view(
+  view: DatabaseView,
+  database_id: &str,
+  view_id: &str,
+  new_field_ids: &HashMap<String, String>,
+) -> CreateViewParams {
+  let new_field_id = |field_id: String| new_field_ids.get(&field_id).cloned().unwrap_or(field_id);
+
+  let filters = view
+    .filters
+    .into_iter()
+    .flat_map(|value| Filter::try_from(value).ok())
+    .map(|mut filter| {
+      filter.regenerate_ids();
+      filter.replace_field_ids(new_field_ids);
+      FilterMap::from(&filter)
+    })
+    .collect();
+
+  let group_settings = view
+    .group_settings
+    .into_iter()
+    .flat_map(|value| GroupSetting::try_from(value).ok())
+    .map(|mut setting| {
+      setting.id = gen_database_group_id();
+      setting.field_id = new_field_id(setting.field_id);
+      GroupSettingMap::from(setting)
+    })
+    .collect();
+
+  let sorts = view
+    .sorts
+    .into_iter()
+    .flat_map(|value| Sort::try_from(value).ok())
+    .map(|mut sort| {
+      sort.id = gen_database_sort_id();
+      sort.field_id = new_field_id(sort.field_id);
+      SortMap::from(sort)
+    })
+    .collect();
+
+  let field_settings = view
+    .field_settings
+    .into_inner()
+    .into_iter()
+    .map(|(field_id, field_settings)| (new_field_id(field_id), field_settings))
+    .collect::<HashMap<_, _>>()
+    .into();
+
+  let mut layout_settings = view.layout_settings.clone();
+  if let Some(layout_setting) = view.layout_settings.get(&DatabaseLayout::Calendar) {
+    let mut calendar_setting = CalendarLayoutSetting::from(layout_setting.clone());
+    calendar_setting.field_id = new_field_id(calendar_setting.field_id);
+    layout_settings.insert(DatabaseLayout::Calendar, calendar_setting.into());
+  }
+
+  let timestamp = timestamp();
+  CreateViewParams {
+    database_id: database_id.to_string(),
+    view_id: view_id.to_string(),
+    name: view.name,
+    layout: view.layout,
+    layout_settings,
+    filters,
+    group_settings,
+    sorts,
+    field_settings,
+    created_at: timestamp,
+    modified_at: timestamp,
+    ..Default::default()
+  }
+}
+
+/// Returns the order of `items` after moving `moving_items` to `to_index` as a contiguous block.
+/// The moving items keep their relative order in `items`, and `to_index` is the index of the
+/// first moving item in the returned order. It's clamped to the end of the order.
+pub(crate) fn move_as_block<T: PartialEq + Clone>(
+  items: &[T],
+  moving_items: &[T],
+  to_index: usize,
+) -> Vec<T> {
+  let (block, mut rest): (Vec<T>, Vec<T>) = items
+    .iter()
+    .cloned()
+    .partition(|item| moving_items.contains(item));
+  let to_index = to_index.min(rest.len());
+  rest.splice(to_index..to_index, block);
+  rest
+}
+
+/// Replaces all the occurrences of `find` in `text`. Returns None if `find` doesn't occur in
+/// `text`.
+pub(crate) fn replace_text(
+  text: &str,
+  find: &str,
+  replace: &str,
+  case_sensitive: bool,
+) -> Option<String> {
+  if find.is_empty() {
+    return None;
+  }
+  if case_sensitive {
+    return text.contains(find).then(|| text.replace(find, replace));
+  }
+
+  let mut output = String::with_capacity(text.len());
+  let mut is_replaced = false;
+  let mut rest = text;
+  while let Some(c) = rest.chars().next() {
+    match prefix_len_ignore_case(rest, find) {
+      Some(len) => {
+        output.push_str(replace);
+        rest = &rest[len..];
+        is_replaced = true;
+      },
+      None => {
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+      },
+    }
+  }
+  is_replaced.then_some(output)
+}
+
+/// Returns the byte length of the prefix of `s` that equals `prefix` ignoring case.
+fn prefix_len_ignore_case(s: &str, prefix: &str) -> Option<usize> {
+  let mut chars = s.char_indices();
+  for p in prefix.chars() {
+    let (_, c) = chars.next()?;
+    if !c.to_lowercase().eq(p.to_lowercase()) {
+      return None;
+    }
+  }
+  Some(chars.next().map(|(index, _)| index).unwrap_or(s.len()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{move_as_block, replace_text};
+
+  #[test]
+  fn move_as_block_test() {
+    let items = vec!["a", "b", "c", "d", "e"];
+    assert_eq!(
+      move_as_block(&items, &["b", "d"], 0),
+      vec!["b", "d", "a", "c", "e"]
+    );
+    // The moving items keep their order in `items`
+    assert_eq!(
+      move_as_block(&items, &["d", "b"], 2),
+      vec!["a", "c", "b", "d", "e"]
+    );
+    assert_eq!(
+      move_as_block(&items, &["a", "c"], 3),
+      vec!["b", "d", "e", "a", "c"]
+    );
+    assert_eq!(
+      move_as_block(&items, &["a"], 10),
+      vec!["b", "c", "d", "e", "a"]
+    );
+    assert_eq!(move_as_block(&items, &[], 1), items);
+  }
+
+  #[test]
+  fn replace_text_test() {
+    assert_eq!(
+      replace_text("AppFlowy app", "app", "note", true),
+      Some("AppFlowy note".to_string())
+    );
+    assert_eq!(
+      replace_text("AppFlowy app", "app", "note", false),
+      Some("noteFlowy note".to_string())
+    );
+    assert_eq!(
+      replace_text("Ärger ärger", "äR", "-", false),
+      Some("-ger -ger".to_string())
+    );
+    assert_eq!(replace_text("AppFlowy", "APP", "note", true), None);
+    assert_eq!(replace_text("AppFlowy", "", "note", false), None);
+  }
+}
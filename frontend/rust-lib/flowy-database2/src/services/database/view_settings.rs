@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use collab_database::fields::Field;
+use collab_database::views::{
+  CalculationMap, DatabaseLayout, FieldSettingsMap, FilterMap, GroupSettingMap, LayoutSetting,
+  SortMap,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::FieldType;
+use crate::services::field::{select_type_option_from_field, SelectOption};
+
+/// A snapshot of the settings of a database view. It's serialized as JSON, so the settings of a
+/// view can be copied onto a view of another database.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ViewSettingsSnapshot {
+  pub layout: DatabaseLayout,
+  /// The fields of the database that the settings were exported from. They are used to match the
+  /// fields by name when the settings are imported into another database.
+  pub fields: Vec<ViewSettingsField>,
+  pub filters: Vec<FilterMap>,
+  pub sorts: Vec<SortMap>,
+  pub group_settings: Vec<GroupSettingMap>,
+  pub calculations: Vec<CalculationMap>,
+  pub field_settings: HashMap<String, FieldSettingsMap>,
+  pub layout_settings: Vec<(DatabaseLayout, LayoutSetting)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ViewSettingsField {
+  pub id: String,
+  pub name: String,
+  pub field_type: i64,
+  /// The names of the select options of the field, by option id. They are used to match the
+  /// options of the filters and the groups by name, like the fields.
+  #[serde(default)]
+  pub select_options: HashMap<String, String>,
+}
+
+impl From<Field> for ViewSettingsField {
+  fn from(field: Field) -> Self {
+    let select_options = select_options_of_field(&field)
+      .into_iter()
+      .map(|option| (option.id, option.name))
+      .collect();
+    Self {
+      id: field.id,
+      name: field.name,
+      field_type: field.field_type,
+      select_options,
+    }
+  }
+}
+
+fn select_options_of_field(field: &Field) -> Vec<SelectOption> {
+  match FieldType::from(field.field_type) {
+    FieldType::SingleSelect | FieldType::MultiSelect => select_type_option_from_field(field)
+      .map(|type_option| type_option.options().clone())
+      .unwrap_or_default(),
+    _ => vec![],
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportViewSettingsResult {
+  /// Describes the settings that were dropped because their field can't be found in the database.
+  pub warnings: Vec<String>,
+}
+
+/// Maps the field ids of a [ViewSettingsSnapshot] to the fields of the database that the settings
+/// are imported into. A field is matched by its id first and then by its name, and the matched
+/// field must have the same field type. The select options of the matched fields are matched the
+/// same way.
+pub(crate) struct FieldIdResolver {
+  new_field_ids: HashMap<String, String>,
+  field_names: HashMap<String, String>,
+  /// The ids of the matched select options, by field id and option id of the snapshot.
+  new_option_ids: HashMap<String, HashMap<String, String>>,
+  option_names: HashMap<String, String>,
+}
+
+impl FieldIdResolver {
+  pub fn new(snapshot_fields: &[ViewSettingsField], fields: &[Field]) -> Self {
+    let mut new_field_ids = HashMap::new();
+    let mut field_names = HashMap::new();
+    let mut new_option_ids = HashMap::new();
+    let mut option_names = HashMap::new();
+    for snapshot_field in snapshot_fields {
+      let is_same_type = |field: &&Field| field.field_type == snapshot_field.field_type;
+      let matched_field = fields
+        .iter()
+        .filter(is_same_type)
+        .find(|field| field.id == snapshot_field.id)
+        .or_else(|| {
+          fields
+            .iter()
+            .filter(is_same_type)
+            .find(|field| field.name == snapshot_field.name)
+        });
+      if let Some(field) = matched_field {
+        new_field_ids.insert(snapshot_field.id.clone(), field.id.clone());
+        new_option_ids.insert(
+          snapshot_field.id.clone(),
+          match_select_options(&snapshot_field.select_options, field),
+        );
+      }
+      field_names.insert(snapshot_field.id.clone(), snapshot_field.name.clone());
+      option_names.extend(snapshot_field.select_options.clone());
+    }
+
+    Self {
+      new_field_ids,
+      field_names,
+      new_option_ids,
+      option_names,
+    }
+  }
+
+  /// Returns the id of the select option of the matched field that replaces the `option_id` of
+  /// the field `field_id` of the snapshot.
+  pub fn resolve_option(&self, field_id: &str, option_id: &str) -> Option<String> {
+    self
+      .new_option_ids
+      .get(field_id)
+      .and_then(|new_option_ids| new_option_ids.get(option_id))
+      .cloned()
+  }
+
+  pub fn resolve(&self, field_id: &str) -> Option<String> {
+    self.new_field_ids.get(field_id).cloned()
+  }
+
+  pub fn new_field_ids(&self) -> &HashMap<String, String> {
+    &self.new_field_ids
+  }
+
+  /// Returns the name of the field in the exported database, or the field id if the name is
+  /// unknown.
+  pub fn field_name<'a>(&'a self, field_id: &'a str) -> &'a str {
+    self
+      .field_names
+      .get(field_id)
+      .map(|name| name.as_str())
+      .unwrap_or(field_id)
+  }
+
+  pub fn unresolved_warning(&self, setting: &str, field_id: &str) -> String {
+    format!(
+      "The {} of the field '{}' is dropped because the field can't be found",
+      setting,
+      self.field_name(field_id)
+    )
+  }
+
+  pub fn unresolved_option_warning(
+    &self,
+    setting: &str,
+    field_id: &str,
+    option_id: &str,
+  ) -> String {
+    format!(
+      "The option '{}' of the {} of the field '{}' is dropped because the option can't be found",
+      self
+        .option_names
+        .get(option_id)
+        .map(|name| name.as_str())
+        .unwrap_or(option_id),
+      setting,
+      self.field_name(field_id)
+    )
+  }
+}
+
+/// Matches the select options of the snapshot to the options of `field`, by id first and then by
+/// name. The snapshots that were exported without the options keep the ids that `field` still has.
+fn match_select_options(
+  snapshot_options: &HashMap<String, String>,
+  field: &Field,
+) -> HashMap<String, String> {
+  let options = select_options_of_field(field);
+  let mut new_option_ids = HashMap::new();
+  for (option_id, option_name) in snapshot_options {
+    let matched_option = options
+      .iter()
+      .find(|option| &option.id == option_id)
+      .or_else(|| options.iter().find(|option| &option.name == option_name));
+    if let Some(option) = matched_option {
+      new_option_ids.insert(option_id.clone(), option.id.clone());
+    }
+  }
+  if snapshot_options.is_empty() {
+    for option in options {
+      new_option_ids.insert(option.id.clone(), option.id);
+    }
+  }
+  new_option_ids
+}
+
+#[cfg(test)]
+mod tests {
+  use collab_database::fields::Field;
+
+  use crate::entities::FieldType;
+  use crate::services::database::view_settings::{FieldIdResolver, ViewSettingsField};
+  use crate::services::field::{FieldBuilder, SelectOption, SingleSelectTypeOption};
+
+  fn field(id: &str, name: &str, field_type: FieldType) -> Field {
+    Field::new(id.to_string(), name.to_string(), field_type.into(), false)
+  }
+
+  fn snapshot_field(id: &str, name: &str, field_type: FieldType) -> ViewSettingsField {
+    ViewSettingsField::from(field(id, name, field_type))
+  }
+
+  #[test]
+  fn resolve_field_id_by_id_then_name_test() {
+    let fields = vec![
+      field("f1", "Name", FieldType::RichText),
+      field("f2", "Status", FieldType::SingleSelect),
+      field("f3", "Due", FieldType::RichText),
+    ];
+    let resolver = FieldIdResolver::new(
+      &[
+        snapshot_field("f1", "Title", FieldType::RichText),
+        snapshot_field("a2", "Status", FieldType::SingleSelect),
+        snapshot_field("a3", "Due", FieldType::DateTime),
+        snapshot_field("a4", "Tags", FieldType::MultiSelect),
+      ],
+      &fields,
+    );
+
+    assert_eq!(resolver.resolve("f1").as_deref(), Some("f1"));
+    assert_eq!(resolver.resolve("a2").as_deref(), Some("f2"));
+    // Fields with the same name but a different type aren't matched
+    assert_eq!(resolver.resolve("a3"), None);
+    assert_eq!(resolver.resolve("a4"), None);
+    assert_eq!(resolver.field_name("a4"), "Tags");
+  }
+
+  #[test]
+  fn resolve_select_option_id_by_id_then_name_test() {
+    let select_field = |options: Vec<SelectOption>| {
+      FieldBuilder::new(
+        FieldType::SingleSelect,
+        SingleSelectTypeOption {
+          options,
+          disable_color: false,
+        },
+      )
+      .name("Status")
+      .build()
+    };
+    let todo = SelectOption::new("Todo");
+    let done = SelectOption::new("Done");
+    let doing = SelectOption::new("Doing");
+    let snapshot_field = ViewSettingsField::from(select_field(vec![
+      todo.clone(),
+      done.clone(),
+      doing.clone(),
+    ]));
+    let new_done = SelectOption::new("Done");
+    let field = select_field(vec![todo.clone(), new_done.clone()]);
+    let resolver = FieldIdResolver::new(&[snapshot_field], &[field]);
+    let field_id = resolver.new_field_ids().keys().next().unwrap().clone();
+
+    assert_eq!(
+      resolver.resolve_option(&field_id, &todo.id).as_deref(),
+      Some(todo.id.as_str())
+    );
+    assert_eq!(
+      resolver.resolve_option(&field_id, &done.id).as_deref(),
+      Some(new_done.id.as_str())
+    );
+    assert_eq!(resolver.resolve_option(&field_id, &doing.id), None);
+    assert!(resolver
+      .unresolved_option_warning("filter", &field_id, &doing.id)
+      .contains("'Doing'"));
+  }
+}
@@ -1,6 +1,7 @@
 pub use layout_deps::*;
 pub use notifier::*;
 pub use view_editor::*;
+pub use view_group::GroupMetrics;
 pub use view_operation::*;
 pub use views::*;
 
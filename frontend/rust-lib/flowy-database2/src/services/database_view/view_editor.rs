@@ -1,12 +1,14 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use collab_database::database::{gen_database_calculation_id, gen_database_sort_id, gen_row_id};
 use collab_database::fields::Field;
 use collab_database::rows::{Cells, Row, RowDetail, RowId};
 use collab_database::views::{DatabaseLayout, DatabaseView};
 use lib_infra::util::timestamp;
+use strum::IntoEnumIterator;
 use tokio::sync::{broadcast, RwLock};
 use tracing::instrument;
 
@@ -14,19 +16,23 @@ use flowy_error::{FlowyError, FlowyResult};
 use lib_dispatch::prelude::af_spawn;
 
 use crate::entities::{
-  CalendarEventPB, CreateRowParams, CreateRowPayloadPB, DatabaseLayoutMetaPB,
-  DatabaseLayoutSettingPB, DeleteSortPayloadPB, FieldSettingsChangesetPB, FieldType,
-  GroupChangesPB, GroupPB, LayoutSettingChangeset, LayoutSettingParams,
-  RemoveCalculationChangesetPB, ReorderSortPayloadPB, RowMetaPB, RowsChangePB,
-  SortChangesetNotificationPB, SortPB, UpdateCalculationChangesetPB, UpdateSortPayloadPB,
+  CalendarEventPB, CellChangesetNotifyPB, CreateRowParams, CreateRowPayloadPB,
+  DatabaseLayoutMetaPB, DatabaseLayoutPB, DatabaseLayoutSettingPB, DeleteSortPayloadPB,
+  FieldSettingsChangesetPB, FieldType, GroupChangesPB, GroupPB, GroupPreviewPB,
+  LayoutSettingChangeset, LayoutSettingParams, RemoveCalculationChangesetPB, ReorderSortPayloadPB,
+  RowMetaPB, RowsChangePB, SortChangesetNotificationPB, SortPB, UpdateCalculationChangesetPB,
+  UpdateSortPayloadPB,
 };
 use crate::notification::{send_notification, DatabaseNotification};
 use crate::services::calculations::{Calculation, CalculationChangeset, CalculationsController};
 use crate::services::cell::{CellBuilder, CellCache};
-use crate::services::database::{database_view_setting_pb_from_view, DatabaseRowEvent, UpdatedRow};
+use crate::services::database::{
+  database_view_setting_pb_from_view, DatabaseRowEvent, OpenProfile, UpdatedRow,
+};
 use crate::services::database_view::view_filter::make_filter_controller;
 use crate::services::database_view::view_group::{
-  get_cell_for_row, get_cells_for_field, new_group_controller,
+  get_cell_for_row, get_cells_for_field, group_name, new_group_controller,
+  preview_group_controller, GroupMetrics,
 };
 use crate::services::database_view::view_operation::DatabaseViewOperation;
 use crate::services::database_view::view_sort::make_sort_controller;
@@ -38,7 +44,7 @@ use crate::services::database_view::{
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::{Filter, FilterChangeset, FilterController};
 use crate::services::group::{GroupChangeset, GroupController, MoveGroupRowContext, RowChangeset};
-use crate::services::setting::CalendarLayoutSetting;
+use crate::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
 use crate::services::sort::{Sort, SortChangeset, SortController};
 
 use super::notify_did_update_calculation;
@@ -49,10 +55,13 @@ pub struct DatabaseViewEditor {
   pub view_id: String,
   delegate: Arc<dyn DatabaseViewOperation>,
   group_controller: Arc<RwLock<Option<Box<dyn GroupController>>>>,
+  group_metrics: Arc<GroupMetrics>,
   filter_controller: Arc<FilterController>,
   sort_controller: Arc<RwLock<SortController>>,
   calculations_controller: Arc<CalculationsController>,
   pub notifier: DatabaseViewChangedNotifier,
+  /// The notifiers of the cell changes, keyed by field id.
+  cell_notifiers: RwLock<HashMap<String, broadcast::Sender<CellChangesetNotifyPB>>>,
 }
 
 impl Drop for DatabaseViewEditor {
@@ -91,12 +100,14 @@ impl DatabaseViewEditor {
     .await;
 
     // Group
+    let group_metrics = Arc::new(GroupMetrics::default());
     let group_controller = Arc::new(RwLock::new(
       new_group_controller(
         view_id.clone(),
         delegate.clone(),
         filter_controller.clone(),
         None,
+        &group_metrics,
       )
       .await?,
     ));
@@ -110,10 +121,12 @@ impl DatabaseViewEditor {
       view_id,
       delegate,
       group_controller,
+      group_metrics,
       filter_controller,
       sort_controller,
       calculations_controller,
       notifier,
+      cell_notifiers: Default::default(),
     })
   }
 
@@ -121,6 +134,44 @@ impl DatabaseViewEditor {
     self.sort_controller.write().await.close().await;
     self.filter_controller.close().await;
     self.calculations_controller.close().await;
+    // Dropping the notifiers closes the receivers of the cell changes
+    self.cell_notifiers.write().await.clear();
+    // Drop the groups, they are built again the next time the view is opened
+    *self.group_controller.write().await = None;
+  }
+
+  pub fn v_get_group_metrics(&self) -> Arc<GroupMetrics> {
+    self.group_metrics.clone()
+  }
+
+  pub async fn v_subscribe_cell_changed(
+    &self,
+    field_id: &str,
+  ) -> broadcast::Receiver<CellChangesetNotifyPB> {
+    self
+      .cell_notifiers
+      .write()
+      .await
+      .entry(field_id.to_string())
+      .or_insert_with(|| broadcast::channel(100).0)
+      .subscribe()
+  }
+
+  /// Notifies the subscribers of the field that one of its cells was changed, either locally or
+  /// by a remote update.
+  pub async fn v_did_update_cell(&self, row_id: &RowId, field_id: &str) {
+    let mut cell_notifiers = self.cell_notifiers.write().await;
+    if let Some(notifier) = cell_notifiers.get(field_id) {
+      let changeset = CellChangesetNotifyPB {
+        view_id: self.view_id.clone(),
+        row_id: row_id.to_string(),
+        field_id: field_id.to_string(),
+      };
+      // The send fails when all the receivers were dropped
+      if notifier.send(changeset).is_err() {
+        cell_notifiers.remove(field_id);
+      }
+    }
   }
 
   pub async fn v_get_view(&self) -> Option<DatabaseView> {
@@ -259,6 +310,7 @@ impl DatabaseViewEditor {
           let result = controller.did_update_group_row(old_row, &row_detail, &field);
 
           if let Ok(result) = result {
+            self.group_metrics.record_incremental_update();
             let mut group_changes = GroupChangesPB {
               view_id: self.view_id.clone(),
               ..Default::default()
@@ -291,15 +343,97 @@ impl DatabaseViewEditor {
     // to block the main thread, so we spawn a new task to do the work.
     if let Some(field_id) = field_id {
       self
-        .gen_did_update_row_view_tasks(row_detail.row.id.clone(), field_id)
+        .gen_did_update_row_view_tasks(row_detail.row.id.clone(), vec![field_id])
         .await;
     }
   }
 
+  /// Same as [Self::v_did_update_row], but for multiple cells of the row that are updated at once.
+  /// The filters and sorts are only updated once.
+  pub async fn v_did_update_row_cells(
+    &self,
+    old_row: &Option<RowDetail>,
+    row_detail: &RowDetail,
+    field_ids: Vec<String>,
+  ) {
+    self.v_did_update_row(old_row, row_detail, None).await;
+    if !field_ids.is_empty() {
+      self
+        .gen_did_update_row_view_tasks(row_detail.row.id.clone(), field_ids)
+        .await;
+    }
+  }
+
+  /// Same as [Self::v_did_update_row], but for the cells of a field that are updated in many rows
+  /// at once. The updated rows are sent in a single [RowsChangePB] notification, and the
+  /// calculations of the field are only updated once.
+  pub async fn v_did_update_field_cells(
+    &self,
+    old_rows: &[Option<RowDetail>],
+    row_details: &[RowDetail],
+    field_id: &str,
+  ) {
+    for (old_row, row_detail) in old_rows.iter().zip(row_details) {
+      self.v_did_update_row(old_row, row_detail, None).await;
+    }
+
+    let updated_rows = row_details
+      .iter()
+      .map(|row_detail| {
+        UpdatedRow::new(row_detail.row.id.as_str())
+          .with_field_ids(vec![field_id.to_string()])
+          .into()
+      })
+      .collect();
+    let changeset = RowsChangePB {
+      updated_rows,
+      ..Default::default()
+    };
+    send_notification(&self.view_id, DatabaseNotification::DidUpdateRow)
+      .payload(changeset)
+      .send();
+
+    let row_ids = row_details
+      .iter()
+      .map(|row_detail| row_detail.row.id.clone())
+      .collect::<Vec<_>>();
+    let field_id = field_id.to_string();
+    let weak_filter_controller = Arc::downgrade(&self.filter_controller);
+    let weak_sort_controller = Arc::downgrade(&self.sort_controller);
+    let weak_calculations_controller = Arc::downgrade(&self.calculations_controller);
+    af_spawn(async move {
+      if let Some(filter_controller) = weak_filter_controller.upgrade() {
+        for row_id in row_ids.iter() {
+          filter_controller
+            .did_receive_row_changed(row_id.clone())
+            .await;
+        }
+      }
+      if let Some(sort_controller) = weak_sort_controller.upgrade() {
+        let sort_controller = sort_controller.read().await;
+        for row_id in row_ids {
+          sort_controller.did_receive_row_changed(row_id).await;
+        }
+      }
+      if let Some(calculations_controller) = weak_calculations_controller.upgrade() {
+        calculations_controller
+          .did_receive_cell_changed(field_id)
+          .await;
+      }
+    });
+  }
+
   pub async fn v_filter_rows(&self, row_details: &mut Vec<Arc<RowDetail>>) {
     self.filter_controller.filter_rows(row_details).await
   }
 
+  /// Returns whether the row passes the filters of the view.
+  pub async fn v_is_row_visible(&self, row_detail: Arc<RowDetail>) -> bool {
+    let mut row_details = vec![row_detail];
+    self.v_filter_rows(&mut row_details).await;
+    !row_details.is_empty()
+  }
+
   pub async fn v_sort_rows(&self, row_details: &mut Vec<Arc<RowDetail>>) {
     self
       .sort_controller
@@ -317,6 +451,117 @@ impl DatabaseViewEditor {
     rows
   }
 
+  /// Same as [Self::v_get_rows], but also measures the time spent in each phase. It's used when
+  /// the view is opened.
+  /// Returns the filtered and sorted rows of the view, and the profile of loading them.
+  ///
+  /// If `limit` is set, at most `limit` rows are returned along with whether the view has more
+  /// rows. Without sorts, the rows are loaded in chunks of the row orders until there are enough
+  /// visible rows. With sorts, every row is loaded because any of them may be sorted first.
+  pub async fn v_get_rows_with_profile(
+    &self,
+    limit: Option<usize>,
+  ) -> (Vec<Arc<RowDetail>>, bool, OpenProfile) {
+    let start = Instant::now();
+    let row_orders = self.delegate.get_row_orders(&self.view_id).await;
+    let get_row_orders = start.elapsed();
+    let num_of_rows = row_orders.len();
+
+    let has_sorts = self.sort_controller.read().await.has_sorts();
+    let chunk_size = match limit {
+      // One more row is loaded to know whether the view has more rows
+      Some(limit) if !has_sorts => limit.saturating_add(1),
+      _ => row_orders.len(),
+    }
+    .max(1);
+    let mut rows = vec![];
+    let mut load_rows = Duration::ZERO;
+    let mut filter = Duration::ZERO;
+    for chunk in row_orders.chunks(chunk_size) {
+      let instant = Instant::now();
+      let mut chunk_rows = self
+        .delegate
+        .get_rows_from_row_orders(&self.view_id, chunk.to_vec())
+        .await;
+      load_rows += instant.elapsed();
+
+      let instant = Instant::now();
+      self.v_filter_rows(&mut chunk_rows).await;
+      filter += instant.elapsed();
+      rows.extend(chunk_rows);
+      if rows.len() >= chunk_size {
+        break;
+      }
+    }
+
+    let instant = Instant::now();
+    self.v_sort_rows(&mut rows).await;
+    let sort = instant.elapsed();
+
+    let num_of_visible_rows = rows.len();
+    let has_more_rows = match limit {
+      Some(limit) if rows.len() > limit => {
+        rows.truncate(limit);
+        true
+      },
+      _ => false,
+    };
+
+    let profile = OpenProfile {
+      view_id: self.view_id.clone(),
+      get_row_orders,
+      load_rows,
+      filter,
+      sort,
+      total: start.elapsed(),
+      num_of_rows,
+      num_of_visible_rows,
+    };
+    (rows, has_more_rows, profile)
+  }
+
+  /// Returns at most `limit` of the filtered and sorted rows, starting from the `offset`th one.
+  ///
+  /// Like [Self::v_get_rows_with_profile], the rows are loaded in chunks of the row orders until
+  /// there are enough visible rows, unless the view has sorts. Without filters, the rows before
+  /// the `offset` aren't loaded at all.
+  pub async fn v_get_rows_page(&self, offset: usize, limit: usize) -> Vec<Arc<RowDetail>> {
+    if limit == 0 {
+      return vec![];
+    }
+    if self.sort_controller.read().await.has_sorts() {
+      let rows = self.v_get_rows().await;
+      return rows.into_iter().skip(offset).take(limit).collect();
+    }
+
+    let row_orders = self.delegate.get_row_orders(&self.view_id).await;
+    let has_filters = self.filter_controller.has_filters().await;
+    // Each row is visible without filters, so the rows before the offset can be skipped
+    let (row_orders, num_of_skipped_rows) = if has_filters {
+      (row_orders.as_slice(), offset)
+    } else {
+      (row_orders.get(offset..).unwrap_or_default(), 0)
+    };
+    let num_of_rows = num_of_skipped_rows.saturating_add(limit);
+    let mut rows = vec![];
+    for chunk in row_orders.chunks(limit) {
+      let mut chunk_rows = self
+        .delegate
+        .get_rows_from_row_orders(&self.view_id, chunk.to_vec())
+        .await;
+      self.v_filter_rows(&mut chunk_rows).await;
+      rows.extend(chunk_rows);
+      if rows.len() >= num_of_rows {
+        break;
+      }
+    }
+    rows
+      .into_iter()
+      .skip(num_of_skipped_rows)
+      .take(limit)
+      .collect()
+  }
+
   pub async fn v_move_group_row(
     &self,
     row_detail: &RowDetail,
@@ -354,7 +599,8 @@ impl DatabaseViewEditor {
     }
   }
 
-  /// Only call once after database view editor initialized
+  /// Returns the groups that are built when the view editor is initialized. They are kept up to
+  /// date by the row and field changes, so loading them doesn't rebuild the groups.
   #[tracing::instrument(level = "trace", skip(self))]
   pub async fn v_load_groups(&self) -> Option<Vec<GroupPB>> {
     let groups = self
@@ -549,6 +795,41 @@ impl DatabaseViewEditor {
     Ok(sort)
   }
 
+  /// Replaces all the sorts of the view with the given sorts. Each field can only be sorted
+  /// once, so the sorts are rejected if two of them sort the same field.
+  pub async fn v_set_sorts(&self, params: Vec<UpdateSortPayloadPB>) -> FlowyResult<Vec<Sort>> {
+    let mut field_ids = HashSet::new();
+    let mut sorts = Vec::with_capacity(params.len());
+    for params in params {
+      if !field_ids.insert(params.field_id.clone()) {
+        return Err(FlowyError::invalid_data().with_context(format!(
+          "The field:{} is sorted more than once",
+          params.field_id
+        )));
+      }
+      if self.delegate.get_field(&params.field_id).is_none() {
+        return Err(FlowyError::field_record_not_found());
+      }
+      sorts.push(Sort {
+        id: params.sort_id.unwrap_or_else(gen_database_sort_id),
+        field_id: params.field_id,
+        condition: params.condition.into(),
+      });
+    }
+
+    self
+      .delegate
+      .replace_all_sorts(&self.view_id, sorts.clone());
+    let notification = self
+      .sort_controller
+      .write()
+      .await
+      .replace_all_sorts(sorts.iter().cloned().map(Arc::new).collect())
+      .await;
+    notify_did_update_sort(notification).await;
+    Ok(sorts)
+  }
+
   pub async fn v_reorder_sort(&self, params: ReorderSortPayloadPB) -> FlowyResult<()> {
     self
       .delegate
@@ -633,6 +914,21 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  /// Recomputes all the calculations of the view instead of updating them incrementally, in case
+  /// their values drifted.
+  pub async fn v_recompute_all_calculations(&self) -> FlowyResult<()> {
+    // Make sure the rows of the view are loaded before calculating over them
+    let _ = self.delegate.get_rows(&self.view_id).await;
+    if let Some(notification) = self
+      .calculations_controller
+      .recompute_all_calculations()
+      .await
+    {
+      notify_did_update_calculation(notification).await;
+    }
+    Ok(())
+  }
+
   pub async fn v_remove_calculation(
     &self,
     params: RemoveCalculationChangesetPB,
@@ -716,6 +1012,84 @@ impl DatabaseViewEditor {
     layout_setting
   }
 
+  /// Returns the settings of all the layouts. The layouts without a stored setting get the default
+  /// one, and the calendar is placed by the first date field of the view by default.
+  pub async fn v_get_all_layout_settings(&self) -> HashMap<DatabaseLayout, LayoutSettingParams> {
+    let mut layout_settings = HashMap::new();
+    for layout_ty in DatabaseLayoutPB::iter().map(DatabaseLayout::from) {
+      let mut layout_setting = self.v_get_layout_settings(&layout_ty).await;
+      layout_setting.layout_type = layout_ty;
+      match layout_ty {
+        DatabaseLayout::Grid => {},
+        DatabaseLayout::Board => {
+          layout_setting
+            .board
+            .get_or_insert_with(BoardLayoutSetting::new);
+        },
+        DatabaseLayout::Calendar => {
+          if layout_setting.calendar.is_none() {
+            layout_setting.calendar = self
+              .delegate
+              .get_fields(&self.view_id, None)
+              .await
+              .into_iter()
+              .find(|field| FieldType::from(field.field_type) == FieldType::DateTime)
+              .map(|field| CalendarLayoutSetting::new(field.id));
+          }
+        },
+      }
+      layout_settings.insert(layout_ty, layout_setting);
+    }
+    layout_settings
+  }
+
+  /// Updates the settings of several layouts at once. Unlike [Self::v_set_layout_settings], the
+  /// layouts don't have to be the current layout. All the settings are checked before saving any
+  /// of them, and only the setting of the current layout is notified.
+  pub async fn v_set_all_layout_settings(
+    &self,
+    layout_settings: Vec<LayoutSettingParams>,
+  ) -> FlowyResult<()> {
+    for layout_setting in layout_settings.iter() {
+      if let Some(calendar) = &layout_setting.calendar {
+        let field = self
+          .delegate
+          .get_field(&calendar.field_id)
+          .ok_or_else(FlowyError::field_record_not_found)?;
+        if FieldType::from(field.field_type) != FieldType::DateTime {
+          return Err(FlowyError::unexpect_calendar_field_type());
+        }
+      }
+    }
+
+    let current_layout = self.v_get_layout_type().await;
+    for layout_setting in layout_settings {
+      let layout_ty = layout_setting.layout_type;
+      let layout_setting_pb = match (layout_ty, layout_setting.board, layout_setting.calendar) {
+        (DatabaseLayout::Board, Some(board), _) => {
+          self
+            .delegate
+            .insert_layout_setting(&self.view_id, &layout_ty, board.clone().into());
+          Some(DatabaseLayoutSettingPB::from_board(board))
+        },
+        (DatabaseLayout::Calendar, _, Some(calendar)) => {
+          self
+            .delegate
+            .insert_layout_setting(&self.view_id, &layout_ty, calendar.clone().into());
+          Some(DatabaseLayoutSettingPB::from_calendar(calendar))
+        },
+        _ => None,
+      };
+
+      if let Some(payload) = layout_setting_pb.filter(|_| layout_ty == current_layout) {
+        send_notification(&self.view_id, DatabaseNotification::DidUpdateLayoutSettings)
+          .payload(payload)
+          .send();
+      }
+    }
+    Ok(())
+  }
+
   /// Update the layout settings and send the notification to refresh the UI
   pub async fn v_set_layout_settings(&self, params: LayoutSettingChangeset) -> FlowyResult<()> {
     if self.v_get_layout_type().await != params.layout_type || !params.is_valid() {
@@ -839,6 +1213,34 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  /// Returns the groups that the rows would be separated into if the view were grouped by
+  /// `field_id`, without changing the grouping of the view.
+  pub async fn v_preview_grouping(&self, field_id: &str) -> FlowyResult<Vec<GroupPreviewPB>> {
+    let field = self
+      .delegate
+      .get_field(field_id)
+      .ok_or_else(FlowyError::field_record_not_found)?;
+    let controller = preview_group_controller(
+      &self.view_id,
+      self.delegate.clone(),
+      self.filter_controller.clone(),
+      field.clone(),
+    )
+    .await?;
+
+    let groups = controller
+      .get_all_groups()
+      .into_iter()
+      .map(|group| GroupPreviewPB {
+        group_id: group.id.clone(),
+        group_name: group_name(&field, group),
+        row_count: group.rows.len() as i64,
+        is_default: group.is_default,
+      })
+      .collect();
+    Ok(groups)
+  }
+
   /// Called when a grouping field is updated.
   #[tracing::instrument(level = "debug", skip_all, err)]
   pub async fn v_group_by_field(&self, field_id: &str) -> FlowyResult<()> {
@@ -850,6 +1252,7 @@ impl DatabaseViewEditor {
         self.delegate.clone(),
         self.filter_controller.clone(),
         Some(field),
+        &self.group_metrics,
       )
       .await?;
 
@@ -901,11 +1304,8 @@ impl DatabaseViewEditor {
       .unwrap_or_default()
       .into();
 
-    let timestamp = date_cell
-      .into_date_field_cell_data()
-      .unwrap_or_default()
-      .timestamp
-      .unwrap_or_default();
+    let date_cell_data = date_cell.into_date_field_cell_data().unwrap_or_default();
+    let timestamp = date_cell_data.timestamp.unwrap_or_default();
 
     let (_, row_detail) = self.delegate.get_row(&self.view_id, &row_id).await?;
     Some(CalendarEventPB {
@@ -914,21 +1314,32 @@ impl DatabaseViewEditor {
       title,
       timestamp,
       is_scheduled: timestamp != 0,
+      end_timestamp: date_cell_data.range_end_timestamp(),
     })
   }
 
-  pub async fn v_get_all_calendar_events(&self) -> Option<Vec<CalendarEventPB>> {
-    let layout_ty = DatabaseLayout::Calendar;
-    let calendar_setting = match self.v_get_layout_settings(&layout_ty).await.calendar {
+  /// Returns the events placed by `date_field_id`, or by the date field of the calendar layout if
+  /// it's None.
+  pub async fn v_get_all_calendar_events(
+    &self,
+    date_field_id: Option<&str>,
+  ) -> Option<Vec<CalendarEventPB>> {
+    let date_field_id = match date_field_id {
+      Some(date_field_id) => date_field_id.to_string(),
       None => {
-        // When create a new calendar view, the calendar setting should be created
-        tracing::error!(
-          "Calendar layout setting not found in database view:{}",
-          self.view_id
-        );
-        return None;
+        let layout_ty = DatabaseLayout::Calendar;
+        match self.v_get_layout_settings(&layout_ty).await.calendar {
+          None => {
+            // When create a new calendar view, the calendar setting should be created
+            tracing::error!(
+              "Calendar layout setting not found in database view:{}",
+              self.view_id
+            );
+            return None;
+          },
+          Some(calendar_setting) => calendar_setting.field_id,
+        }
       },
-      Some(calendar_setting) => calendar_setting,
     };
 
     // Text
@@ -937,30 +1348,24 @@ impl DatabaseViewEditor {
       get_cells_for_field(self.delegate.clone(), &self.view_id, &primary_field.id).await;
 
     // Date
-    let timestamp_by_row_id = get_cells_for_field(
-      self.delegate.clone(),
-      &self.view_id,
-      &calendar_setting.field_id,
-    )
-    .await
-    .into_iter()
-    .map(|date_cell| {
-      let row_id = date_cell.row_id.clone();
-
-      // timestamp
-      let timestamp = date_cell
-        .into_date_field_cell_data()
-        .map(|date_cell_data| date_cell_data.timestamp.unwrap_or_default())
-        .unwrap_or_default();
+    let timestamp_by_row_id =
+      get_cells_for_field(self.delegate.clone(), &self.view_id, &date_field_id)
+        .await
+        .into_iter()
+        .map(|date_cell| {
+          let row_id = date_cell.row_id.clone();
 
-      (row_id, timestamp)
-    })
-    .collect::<HashMap<RowId, i64>>();
+          // timestamp and the end of the range
+          let date_cell_data = date_cell.into_date_field_cell_data().unwrap_or_default();
+          let timestamp = date_cell_data.timestamp.unwrap_or_default();
+          (row_id, (timestamp, date_cell_data.range_end_timestamp()))
+        })
+        .collect::<HashMap<RowId, (i64, Option<i64>)>>();
 
     let mut events: Vec<CalendarEventPB> = vec![];
     for text_cell in text_cells {
       let row_id = text_cell.row_id.clone();
-      let timestamp = timestamp_by_row_id
+      let (timestamp, end_timestamp) = timestamp_by_row_id
         .get(&row_id)
         .cloned()
         .unwrap_or_default();
@@ -973,10 +1378,11 @@ impl DatabaseViewEditor {
       let (_, row_detail) = self.delegate.get_row(&self.view_id, &row_id).await?;
       let event = CalendarEventPB {
         row_meta: RowMetaPB::from(row_detail.as_ref()),
-        date_field_id: calendar_setting.field_id.clone(),
+        date_field_id: date_field_id.clone(),
         title,
         timestamp,
         is_scheduled: timestamp != 0,
+        end_timestamp,
       };
       events.push(event);
     }
@@ -1006,6 +1412,7 @@ impl DatabaseViewEditor {
       self.delegate.clone(),
       self.filter_controller.clone(),
       None,
+      &self.group_metrics,
     )
     .await?;
 
@@ -1020,6 +1427,34 @@ impl DatabaseViewEditor {
     Ok(())
   }
 
+  /// Called after the filters, sorts and groups of the view were replaced in the database, e.g. by
+  /// [crate::services::database::DatabaseEditor::reset_view]. The controllers are rebuilt in place,
+  /// so the subscribers of the view keep receiving its changes.
+  pub async fn v_reload_settings(&self) -> FlowyResult<()> {
+    self.filter_controller.reload_filters().await;
+    let sorts = self
+      .v_get_all_sorts()
+      .await
+      .into_iter()
+      .map(Arc::new)
+      .collect();
+    self
+      .sort_controller
+      .write()
+      .await
+      .replace_all_sorts(sorts)
+      .await;
+    *self.group_controller.write().await = new_group_controller(
+      self.view_id.clone(),
+      self.delegate.clone(),
+      self.filter_controller.clone(),
+      None,
+      &self.group_metrics,
+    )
+    .await?;
+    Ok(())
+  }
+
   pub async fn handle_row_event(&self, event: Cow<'_, DatabaseRowEvent>) {
     let changeset = match event.into_owned() {
       DatabaseRowEvent::InsertRow(row) => RowsChangePB::from_insert(row.into()),
@@ -1065,7 +1500,7 @@ impl DatabaseViewEditor {
     }
   }
 
-  async fn gen_did_update_row_view_tasks(&self, row_id: RowId, field_id: String) {
+  async fn gen_did_update_row_view_tasks(&self, row_id: RowId, field_ids: Vec<String>) {
     let weak_filter_controller = Arc::downgrade(&self.filter_controller);
     let weak_sort_controller = Arc::downgrade(&self.sort_controller);
     let weak_calculations_controller = Arc::downgrade(&self.calculations_controller);
@@ -1083,9 +1518,11 @@ impl DatabaseViewEditor {
           .await;
       }
       if let Some(calculations_controller) = weak_calculations_controller.upgrade() {
-        calculations_controller
-          .did_receive_cell_changed(field_id)
-          .await;
+        for field_id in field_ids {
+          calculations_controller
+            .did_receive_cell_changed(field_id)
+            .await;
+        }
       }
     });
   }
@@ -9,6 +9,7 @@ use crate::services::cell::CellCache;
 use crate::services::database_view::{
   gen_handler_id, DatabaseViewChangedNotifier, DatabaseViewOperation,
 };
+use crate::services::field::RowUsersByRowId;
 use crate::services::filter::{Filter, FilterController, FilterDelegate, FilterTaskHandler};
 
 pub async fn make_filter_controller(
@@ -67,4 +68,8 @@ impl FilterDelegate for DatabaseViewFilterDelegateImpl {
   fn save_filters(&self, view_id: &str, filters: &[Filter]) {
     self.0.save_filters(view_id, filters)
   }
+
+  fn get_row_users(&self) -> RowUsersByRowId {
+    self.0.get_row_users()
+  }
 }
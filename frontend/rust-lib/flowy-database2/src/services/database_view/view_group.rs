@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use collab_database::fields::Field;
@@ -8,18 +9,45 @@ use lib_infra::future::{to_fut, Fut};
 
 use crate::entities::FieldType;
 use crate::services::database_view::DatabaseViewOperation;
-use crate::services::field::RowSingleCellData;
+use crate::services::field::{MultiSelectTypeOption, RowSingleCellData, SingleSelectTypeOption};
 use crate::services::filter::FilterController;
 use crate::services::group::{
-  make_group_controller, GroupContextDelegate, GroupController, GroupControllerDelegate,
+  make_group_controller, GroupContextDelegate, GroupController, GroupControllerDelegate, GroupData,
   GroupSetting,
 };
 
+/// Counts how many times the groups of a view are built from scratch and how many times they are
+/// updated in place after a row changes.
+#[derive(Debug, Default)]
+pub struct GroupMetrics {
+  num_of_full_builds: AtomicUsize,
+  num_of_incremental_updates: AtomicUsize,
+}
+
+impl GroupMetrics {
+  pub fn num_of_full_builds(&self) -> usize {
+    self.num_of_full_builds.load(Ordering::SeqCst)
+  }
+
+  pub fn num_of_incremental_updates(&self) -> usize {
+    self.num_of_incremental_updates.load(Ordering::SeqCst)
+  }
+
+  pub(crate) fn record_incremental_update(&self) {
+    self
+      .num_of_incremental_updates
+      .fetch_add(1, Ordering::SeqCst);
+  }
+}
+
+/// Builds the groups from all the rows of the view. It's expensive, so the returned controller
+/// is kept by the view editor and updated incrementally afterwards.
 pub async fn new_group_controller(
   view_id: String,
   delegate: Arc<dyn DatabaseViewOperation>,
   filter_controller: Arc<FilterController>,
   grouping_field: Option<Field>,
+  metrics: &GroupMetrics,
 ) -> FlowyResult<Option<Box<dyn GroupController>>> {
   if !delegate.get_layout_for_view(&view_id).is_board() {
     return Ok(None);
@@ -49,13 +77,35 @@ pub async fn new_group_controller(
   };
 
   let controller = match grouping_field {
-    Some(field) => Some(make_group_controller(&view_id, field, controller_delegate).await?),
+    Some(field) => {
+      metrics.num_of_full_builds.fetch_add(1, Ordering::SeqCst);
+      Some(make_group_controller(&view_id, field, controller_delegate).await?)
+    },
     None => None,
   };
 
   Ok(controller)
 }
 
+/// Builds the groups that the rows would be separated into if the view were grouped by
+/// `grouping_field`. Unlike [new_group_controller], the view's group setting is never saved, so
+/// the grouping of the view is unchanged.
+pub(crate) async fn preview_group_controller(
+  view_id: &str,
+  delegate: Arc<dyn DatabaseViewOperation>,
+  filter_controller: Arc<FilterController>,
+  grouping_field: Field,
+) -> FlowyResult<Box<dyn GroupController>> {
+  let controller_delegate = PreviewGroupControllerDelegate {
+    grouping_field_id: grouping_field.id.clone(),
+    inner: GroupControllerDelegateImpl {
+      delegate,
+      filter_controller,
+    },
+  };
+  make_group_controller(view_id, grouping_field, controller_delegate).await
+}
+
 pub(crate) struct GroupControllerDelegateImpl {
   delegate: Arc<dyn DatabaseViewOperation>,
   filter_controller: Arc<FilterController>,
@@ -103,6 +153,64 @@ impl GroupControllerDelegate for GroupControllerDelegateImpl {
   }
 }
 
+/// Returns the name of the group that is shown to the user. The groups of the select option
+/// fields are named after their options, and the other groups are named after their values.
+pub(crate) fn group_name(grouping_field: &Field, group: &GroupData) -> String {
+  if group.is_default {
+    return format!("No {}", grouping_field.name);
+  }
+  let field_type = FieldType::from(grouping_field.field_type);
+  let options = match field_type {
+    FieldType::SingleSelect => grouping_field
+      .get_type_option::<SingleSelectTypeOption>(field_type)
+      .map(|type_option| type_option.options),
+    FieldType::MultiSelect => grouping_field
+      .get_type_option::<MultiSelectTypeOption>(field_type)
+      .map(|type_option| type_option.options),
+    _ => None,
+  };
+  options
+    .and_then(|options| options.into_iter().find(|option| option.id == group.id))
+    .map(|option| option.name)
+    .unwrap_or_else(|| group.id.clone())
+}
+
+/// Reads the group setting of the view only if it's for the previewed field, and never saves it.
+struct PreviewGroupControllerDelegate {
+  grouping_field_id: String,
+  inner: GroupControllerDelegateImpl,
+}
+
+impl GroupContextDelegate for PreviewGroupControllerDelegate {
+  fn get_group_setting(&self, view_id: &str) -> Fut<Option<Arc<GroupSetting>>> {
+    let grouping_field_id = self.grouping_field_id.clone();
+    let setting = self.inner.get_group_setting(view_id);
+    to_fut(async move {
+      setting
+        .await
+        .filter(|setting| setting.field_id == grouping_field_id)
+    })
+  }
+
+  fn get_configuration_cells(&self, view_id: &str, field_id: &str) -> Fut<Vec<RowSingleCellData>> {
+    self.inner.get_configuration_cells(view_id, field_id)
+  }
+
+  fn save_configuration(&self, _view_id: &str, _setting: GroupSetting) -> Fut<FlowyResult<()>> {
+    to_fut(async move { Ok(()) })
+  }
+}
+
+impl GroupControllerDelegate for PreviewGroupControllerDelegate {
+  fn get_field(&self, field_id: &str) -> Option<Field> {
+    self.inner.get_field(field_id)
+  }
+
+  fn get_all_rows(&self, view_id: &str) -> Fut<Vec<Arc<RowDetail>>> {
+    self.inner.get_all_rows(view_id)
+  }
+}
+
 pub(crate) async fn get_cell_for_row(
   delegate: Arc<dyn DatabaseViewOperation>,
   field_id: &str,
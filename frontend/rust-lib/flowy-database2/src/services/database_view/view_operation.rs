@@ -4,16 +4,16 @@ use std::sync::Arc;
 use collab_database::database::MutexDatabase;
 use collab_database::fields::{Field, TypeOptionData};
 use collab_database::rows::{Row, RowCell, RowDetail, RowId};
-use collab_database::views::{DatabaseLayout, DatabaseView, LayoutSetting};
+use collab_database::views::{DatabaseLayout, DatabaseView, LayoutSetting, RowOrder};
 use tokio::sync::RwLock;
 
 use flowy_error::FlowyError;
 use lib_infra::future::{Fut, FutureResult};
 use lib_infra::priority_task::TaskDispatcher;
 
-use crate::entities::{FieldSettingsChangesetPB, FieldType};
+use crate::entities::{FieldSettingsChangesetPB, FieldType, RelatedRowDataPB};
 use crate::services::calculations::Calculation;
-use crate::services::field::TypeOptionCellDataHandler;
+use crate::services::field::{RowUsersByRowId, TypeOptionCellDataHandler};
 use crate::services::field_settings::FieldSettings;
 use crate::services::filter::Filter;
 use crate::services::group::GroupSetting;
@@ -57,6 +57,16 @@ pub trait DatabaseViewOperation: Send + Sync + 'static {
   /// Returns all the rows in the view
   fn get_rows(&self, view_id: &str) -> Fut<Vec<Arc<RowDetail>>>;
 
+  /// Returns the orders of all the rows in the view
+  fn get_row_orders(&self, view_id: &str) -> Fut<Vec<RowOrder>>;
+
+  /// Loads the rows of the row orders of the view
+  fn get_rows_from_row_orders(
+    &self,
+    view_id: &str,
+    row_orders: Vec<RowOrder>,
+  ) -> Fut<Vec<Arc<RowDetail>>>;
+
   fn remove_row(&self, row_id: &RowId) -> Option<Row>;
 
   fn get_cells_for_field(&self, view_id: &str, field_id: &str) -> Fut<Vec<Arc<RowCell>>>;
@@ -83,6 +93,9 @@ pub trait DatabaseViewOperation: Send + Sync + 'static {
 
   fn remove_all_sorts(&self, view_id: &str);
 
+  /// Replaces all the sorts of the view with the given sorts in one write.
+  fn replace_all_sorts(&self, view_id: &str, sorts: Vec<Sort>);
+
   fn get_all_calculations(&self, view_id: &str) -> Vec<Arc<Calculation>>;
 
   fn get_calculation(&self, view_id: &str, field_id: &str) -> Option<Calculation>;
@@ -112,6 +125,14 @@ pub trait DatabaseViewOperation: Send + Sync + 'static {
 
   fn update_layout_type(&self, view_id: &str, layout_type: &DatabaseLayout);
 
+  /// Returns the titles of the rows with `row_ids` in the database with `database_id`, which is
+  /// either this database or the one that a relation field links to.
+  fn get_related_rows(&self, database_id: &str, row_ids: Vec<String>)
+    -> Fut<Vec<RelatedRowDataPB>>;
+
+  /// Returns the users who created and last edited the rows of the database
+  fn get_row_users(&self) -> RowUsersByRowId;
+
   /// Returns a `TaskDispatcher` used to poll a `Task`
   fn get_task_scheduler(&self) -> Arc<RwLock<TaskDispatcher>>;
 
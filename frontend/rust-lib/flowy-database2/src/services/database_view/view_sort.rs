@@ -6,10 +6,12 @@ use tokio::sync::RwLock;
 
 use lib_infra::future::{to_fut, Fut};
 
+use crate::entities::RelatedRowDataPB;
 use crate::services::cell::CellCache;
 use crate::services::database_view::{
   gen_handler_id, DatabaseViewChangedNotifier, DatabaseViewOperation,
 };
+use crate::services::field::RowUsersByRowId;
 use crate::services::filter::FilterController;
 use crate::services::sort::{Sort, SortController, SortDelegate, SortTaskHandler};
 
@@ -87,4 +89,16 @@ impl SortDelegate for DatabaseViewSortDelegateImpl {
   fn get_fields(&self, view_id: &str, field_ids: Option<Vec<String>>) -> Fut<Vec<Field>> {
     self.delegate.get_fields(view_id, field_ids)
   }
+
+  fn get_related_rows(
+    &self,
+    database_id: &str,
+    row_ids: Vec<String>,
+  ) -> Fut<Vec<RelatedRowDataPB>> {
+    self.delegate.get_related_rows(database_id, row_ids)
+  }
+
+  fn get_row_users(&self) -> RowUsersByRowId {
+    self.delegate.get_row_users()
+  }
 }
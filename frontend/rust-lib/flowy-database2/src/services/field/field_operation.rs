@@ -1,10 +1,91 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
 use flowy_error::FlowyResult;
 
 use crate::entities::FieldType;
 use crate::services::database::DatabaseEditor;
-use crate::services::field::{MultiSelectTypeOption, SingleSelectTypeOption, TypeOption};
+use crate::services::field::{
+  MultiSelectTypeOption, SelectOption, SelectTypeOptionSharedAction, SingleSelectTypeOption,
+  TypeOption,
+};
+
+/// The description of a field is stored along with its type options. The key can't collide with
+/// the type options, which are keyed by field type.
+pub(crate) const FIELD_DESCRIPTION_KEY: &str = "field_description";
+const DESCRIPTION: &str = "description";
+
+/// Returns the description of the field, or None if it doesn't have one.
+pub fn field_description(field: &Field) -> Option<String> {
+  field
+    .type_options
+    .get(FIELD_DESCRIPTION_KEY)
+    .and_then(|data| data.get_str_value(DESCRIPTION))
+    .filter(|description| !description.is_empty())
+}
+
+/// An empty description clears the field's description.
+pub(crate) fn field_description_data(description: &str) -> TypeOptionData {
+  TypeOptionDataBuilder::new()
+    .insert_str_value(DESCRIPTION, description)
+    .build()
+}
+
+/// Like the description, the system flag is stored along with the type options.
+pub(crate) const FIELD_SYSTEM_KEY: &str = "field_system";
+const IS_SYSTEM: &str = "is_system";
+
+/// A system field holds the data of the integrations, e.g. the id of the row in another service.
+/// It's hidden in all the views and left out of the fields that are listed by default, but its
+/// cells can be read and written like the cells of the other fields.
+pub fn is_system_field(field: &Field) -> bool {
+  field
+    .type_options
+    .get(FIELD_SYSTEM_KEY)
+    .and_then(|data| data.get_bool_value(IS_SYSTEM))
+    .unwrap_or(false)
+}
+
+pub(crate) fn system_field_data() -> TypeOptionData {
+  TypeOptionDataBuilder::new()
+    .insert_bool_value(IS_SYSTEM, true)
+    .build()
+}
+
+/// Converts the cells when the type of a field is switched. Without a strategy, the cells are kept
+/// as they are and converted whenever they're read, which might lose some data.
+#[derive(Debug, Clone)]
+pub enum CellMigrationStrategy {
+  /// Maps the text of the old cells to the options of the new single or multi select field. The
+  /// options are added to the field if they don't exist yet, and the cells whose text isn't mapped
+  /// are cleared.
+  SelectOptionMapping(HashMap<String, SelectOption>),
+}
+
+/// Inserts the `options` into the select type option data of the `field_type`.
+pub(crate) fn insert_select_options(
+  field_type: FieldType,
+  type_option_data: TypeOptionData,
+  options: Vec<SelectOption>,
+) -> TypeOptionData {
+  fn insert<T: SelectTypeOptionSharedAction + Into<TypeOptionData>>(
+    mut type_option: T,
+    options: Vec<SelectOption>,
+  ) -> TypeOptionData {
+    for option in options {
+      type_option.insert_option(option);
+    }
+    type_option.into()
+  }
+
+  match field_type {
+    FieldType::SingleSelect => insert(SingleSelectTypeOption::from(type_option_data), options),
+    FieldType::MultiSelect => insert(MultiSelectTypeOption::from(type_option_data), options),
+    _ => type_option_data,
+  }
+}
 
 pub async fn edit_field_type_option<T: TypeOption>(
   field_id: &str,
@@ -9,6 +9,8 @@ impl CheckboxFilterPB {
     match self.condition {
       CheckboxFilterConditionPB::IsChecked => cell_data.is_checked,
       CheckboxFilterConditionPB::IsUnChecked => !cell_data.is_checked,
+      CheckboxFilterConditionPB::IsUnset => !cell_data.is_set,
+      CheckboxFilterConditionPB::IsExplicitlyUnChecked => cell_data.is_set && !cell_data.is_checked,
     }
   }
 }
@@ -17,7 +19,8 @@ impl PreFillCellsWithFilter for CheckboxFilterPB {
   fn get_compliant_cell(&self, field: &Field) -> (Option<Cell>, bool) {
     let is_checked = match self.condition {
       CheckboxFilterConditionPB::IsChecked => Some(true),
-      CheckboxFilterConditionPB::IsUnChecked => None,
+      CheckboxFilterConditionPB::IsUnChecked | CheckboxFilterConditionPB::IsUnset => None,
+      CheckboxFilterConditionPB::IsExplicitlyUnChecked => Some(false),
     };
 
     (
@@ -65,4 +68,26 @@ mod tests {
       assert_eq!(checkbox_filter.is_visible(&data), visible);
     }
   }
+
+  #[test]
+  fn checkbox_filter_is_unset_test() {
+    let checkbox_filter = CheckboxFilterPB {
+      condition: CheckboxFilterConditionPB::IsUnset,
+    };
+    for (value, visible) in [("", true), ("false", false), ("no", false), ("true", false)] {
+      let data = CheckboxCellDataPB::from_str(value).unwrap();
+      assert_eq!(checkbox_filter.is_visible(&data), visible);
+    }
+  }
+
+  #[test]
+  fn checkbox_filter_is_explicitly_uncheck_test() {
+    let checkbox_filter = CheckboxFilterPB {
+      condition: CheckboxFilterConditionPB::IsExplicitlyUnChecked,
+    };
+    for (value, visible) in [("false", true), ("no", true), ("true", false), ("", false)] {
+      let data = CheckboxCellDataPB::from_str(value).unwrap();
+      assert_eq!(checkbox_filter.is_visible(&data), visible);
+    }
+  }
 }
@@ -38,6 +38,10 @@ impl FromStr for CheckboxCellDataPB {
   type Err = FlowyError;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
+    if s.is_empty() {
+      return Ok(Self::default());
+    }
+
     let lower_case_str: &str = &s.to_lowercase();
     let is_checked = match lower_case_str {
       "1" | "true" | "yes" => true,
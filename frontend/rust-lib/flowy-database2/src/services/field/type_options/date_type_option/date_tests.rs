@@ -4,12 +4,14 @@ mod tests {
   use chrono::{FixedOffset, NaiveDateTime};
   use collab_database::fields::Field;
   use collab_database::rows::Cell;
+  use flowy_error::ErrorCode;
   use strum::IntoEnumIterator;
 
   use crate::entities::FieldType;
   use crate::services::cell::{CellDataChangeset, CellDataDecoder};
   use crate::services::field::{
-    DateCellChangeset, DateFormat, DateTypeOption, FieldBuilder, TimeFormat,
+    timestamp_from_local_datetime, DateCellChangeset, DateFormat, DateTypeOption, FieldBuilder,
+    TimeFormat,
   };
 
   #[test]
@@ -532,6 +534,31 @@ mod tests {
     );
   }
 
+  #[test]
+  fn timestamp_from_local_datetime_test() {
+    // Paris is one hour ahead of UTC in the winter and two hours in the summer
+    assert_eq!(
+      timestamp_from_local_datetime("2024-01-15 09:30", "Europe/Paris").unwrap(),
+      1705307400
+    );
+    assert_eq!(
+      timestamp_from_local_datetime("2024-07-15T09:30:00", "Europe/Paris").unwrap(),
+      1721028600
+    );
+    assert_eq!(
+      timestamp_from_local_datetime("2024-01-15", "Etc/UTC").unwrap(),
+      1705276800
+    );
+
+    // The clocks jump from 02:00 to 03:00
+    let err = timestamp_from_local_datetime("2024-03-31 02:30", "Europe/Paris").unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidDateTimeFormat);
+    let err = timestamp_from_local_datetime("15/01/2024", "Europe/Paris").unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidDateTimeFormat);
+    let err = timestamp_from_local_datetime("2024-01-15 09:30", "Mars/Olympus").unwrap_err();
+    assert_eq!(err.code, ErrorCode::InvalidParams);
+  }
+
   fn assert_date(
     type_option: &DateTypeOption,
     field: &Field,
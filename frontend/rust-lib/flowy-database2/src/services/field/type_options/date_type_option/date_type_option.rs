@@ -1,7 +1,9 @@
 use std::cmp::Ordering;
 use std::str::FromStr;
 
-use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, NaiveTime, Offset, TimeZone};
+use chrono::{
+  DateTime, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone,
+};
 use chrono_tz::Tz;
 use collab::core::any_map::AnyMapExtension;
 use collab_database::fields::{TypeOptionData, TypeOptionDataBuilder};
@@ -183,7 +185,7 @@ impl DateTypeOption {
   }
 
   /// returns offset of Tz timezone if provided or of the local timezone otherwise
-  fn get_timezone_offset(&self, date_time: NaiveDateTime) -> FixedOffset {
+  pub(crate) fn get_timezone_offset(&self, date_time: NaiveDateTime) -> FixedOffset {
     let current_timezone_offset = Local::now().offset().fix();
     if self.timezone_id.is_empty() {
       current_timezone_offset
@@ -196,6 +198,48 @@ impl DateTypeOption {
   }
 }
 
+/// The formats of the local date times accepted by [timestamp_from_local_datetime], besides a
+/// date alone, which is read as the start of the day.
+const LOCAL_DATETIME_FORMATS: [&str; 4] = [
+  "%Y-%m-%dT%H:%M:%S",
+  "%Y-%m-%d %H:%M:%S",
+  "%Y-%m-%dT%H:%M",
+  "%Y-%m-%d %H:%M",
+];
+
+/// Returns the UTC timestamp of `local_datetime`, e.g. `2024-03-10 09:30`, read in the timezone
+/// with the IANA id `timezone_id`, e.g. `Europe/Paris`. A time that is skipped by a daylight
+/// saving change is rejected, and the earlier one of a repeated time is used.
+pub fn timestamp_from_local_datetime(local_datetime: &str, timezone_id: &str) -> FlowyResult<i64> {
+  let timezone = Tz::from_str(timezone_id).map_err(|_| {
+    FlowyError::invalid_data().with_context(format!("Unknown timezone: {}", timezone_id))
+  })?;
+  let local_datetime = local_datetime.trim();
+  let naive = LOCAL_DATETIME_FORMATS
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(local_datetime, format).ok())
+    .or_else(|| {
+      NaiveDate::parse_from_str(local_datetime, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })
+    .ok_or_else(|| {
+      FlowyError::new(
+        ErrorCode::InvalidDateTimeFormat,
+        format!("Parse {} failed", local_datetime),
+      )
+    })?;
+  match timezone.from_local_datetime(&naive) {
+    LocalResult::Single(date_time) | LocalResult::Ambiguous(date_time, _) => {
+      Ok(date_time.timestamp())
+    },
+    LocalResult::None => Err(FlowyError::new(
+      ErrorCode::InvalidDateTimeFormat,
+      format!("{} doesn't exist in {}", local_datetime, timezone_id),
+    )),
+  }
+}
+
 impl TypeOptionTransform for DateTypeOption {}
 
 impl CellDataDecoder for DateTypeOption {
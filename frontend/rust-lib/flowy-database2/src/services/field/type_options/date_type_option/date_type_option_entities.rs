@@ -47,6 +47,16 @@ impl DateCellData {
       reminder_id,
     }
   }
+
+  /// Returns the end of the range if the cell is a range. It's never before the start, so the
+  /// event spans all the days from the start to the end.
+  pub fn range_end_timestamp(&self) -> Option<i64> {
+    if !self.is_range {
+      return None;
+    }
+    let end_timestamp = self.end_timestamp?;
+    Some(end_timestamp.max(self.timestamp.unwrap_or(end_timestamp)))
+  }
 }
 
 impl TypeOptionCellData for DateCellData {
@@ -9,7 +9,9 @@ pub mod text_type_option;
 pub mod timestamp_type_option;
 mod type_option;
 mod type_option_cell;
+mod type_option_registry;
 mod url_type_option;
+pub mod user_type_option;
 mod util;
 
 pub use checkbox_type_option::*;
@@ -22,4 +24,6 @@ pub use text_type_option::*;
 pub use timestamp_type_option::*;
 pub use type_option::*;
 pub use type_option_cell::*;
+pub use type_option_registry::*;
 pub use url_type_option::*;
+pub use user_type_option::*;
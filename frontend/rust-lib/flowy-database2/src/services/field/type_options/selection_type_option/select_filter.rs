@@ -31,6 +31,12 @@ impl SelectOptionFilterPB {
       },
       SelectOptionFilterConditionPB::OptionIsEmpty => SelectOptionFilterStrategy::IsEmpty,
       SelectOptionFilterConditionPB::OptionIsNotEmpty => SelectOptionFilterStrategy::IsNotEmpty,
+      SelectOptionFilterConditionPB::OptionContainsAll => {
+        SelectOptionFilterStrategy::ContainsAll(get_non_empty_expected_options()?)
+      },
+      SelectOptionFilterConditionPB::OptionDoesNotContainAll => {
+        SelectOptionFilterStrategy::DoesNotContainAll(get_non_empty_expected_options()?)
+      },
     };
 
     Some(strategy.filter(&selected_option_ids))
@@ -44,6 +50,8 @@ enum SelectOptionFilterStrategy {
   DoesNotContain(Vec<String>),
   IsEmpty,
   IsNotEmpty,
+  ContainsAll(Vec<String>),
+  DoesNotContainAll(Vec<String>),
 }
 
 impl SelectOptionFilterStrategy {
@@ -91,6 +99,24 @@ impl SelectOptionFilterStrategy {
       },
       SelectOptionFilterStrategy::IsEmpty => selected_option_ids.is_empty(),
       SelectOptionFilterStrategy::IsNotEmpty => !selected_option_ids.is_empty(),
+      SelectOptionFilterStrategy::ContainsAll(option_ids) => {
+        if selected_option_ids.is_empty() {
+          return false;
+        }
+
+        option_ids
+          .iter()
+          .all(|id| selected_option_ids.contains(&id))
+      },
+      SelectOptionFilterStrategy::DoesNotContainAll(option_ids) => {
+        if selected_option_ids.is_empty() {
+          return false;
+        }
+
+        !option_ids
+          .iter()
+          .all(|id| selected_option_ids.contains(&id))
+      },
     }
   }
 }
@@ -107,6 +133,7 @@ impl PreFillCellsWithFilter for SelectOptionFilterPB {
 
     let option_ids = match self.condition {
       SelectOptionFilterConditionPB::OptionIs => get_non_empty_expected_options(),
+      SelectOptionFilterConditionPB::OptionContainsAll => get_non_empty_expected_options(),
       SelectOptionFilterConditionPB::OptionContains => {
         get_non_empty_expected_options().map(|mut options| vec![options.swap_remove(0)])
       },
@@ -374,4 +401,61 @@ mod tests {
       assert_eq!(filter.is_visible(&options), is_visible);
     }
   }
+
+  #[test]
+  fn select_option_filter_contains_all_test() {
+    let option_1 = SelectOption::new("A");
+    let option_2 = SelectOption::new("B");
+    let option_3 = SelectOption::new("C");
+
+    // no expected options
+    let filter = SelectOptionFilterPB {
+      condition: SelectOptionFilterConditionPB::OptionContainsAll,
+      option_ids: vec![],
+    };
+    assert_eq!(filter.is_visible(&[option_1.clone()]), None);
+
+    let filter = SelectOptionFilterPB {
+      condition: SelectOptionFilterConditionPB::OptionContainsAll,
+      option_ids: vec![option_1.id.clone(), option_2.id.clone()],
+    };
+    for (options, is_visible) in [
+      (vec![], Some(false)),
+      (vec![option_1.clone()], Some(false)),
+      (vec![option_3.clone()], Some(false)),
+      (vec![option_2.clone(), option_1.clone()], Some(true)),
+      // a superset of the expected options
+      (
+        vec![option_1.clone(), option_2.clone(), option_3.clone()],
+        Some(true),
+      ),
+    ] {
+      assert_eq!(filter.is_visible(&options), is_visible);
+    }
+  }
+
+  #[test]
+  fn select_option_filter_does_not_contain_all_test() {
+    let option_1 = SelectOption::new("A");
+    let option_2 = SelectOption::new("B");
+    let option_3 = SelectOption::new("C");
+
+    let filter = SelectOptionFilterPB {
+      condition: SelectOptionFilterConditionPB::OptionDoesNotContainAll,
+      option_ids: vec![option_1.id.clone(), option_2.id.clone()],
+    };
+    for (options, is_visible) in [
+      (vec![], Some(false)),
+      (vec![option_1.clone()], Some(true)),
+      (vec![option_3.clone()], Some(true)),
+      (vec![option_1.clone(), option_2.clone()], Some(false)),
+      // a superset of the expected options
+      (
+        vec![option_1.clone(), option_2.clone(), option_3.clone()],
+        Some(false),
+      ),
+    ] {
+      assert_eq!(filter.is_visible(&options), is_visible);
+    }
+  }
 }
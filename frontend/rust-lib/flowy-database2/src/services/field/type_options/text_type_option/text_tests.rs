@@ -5,6 +5,8 @@ mod tests {
   use crate::services::field::FieldBuilder;
   use crate::services::field::*;
 
+  use super::super::text_type_option::RegexCache;
+
   // Test parser the cell data which field's type is FieldType::Date to cell data
   // which field's type is FieldType::Text
   #[test]
@@ -101,4 +103,62 @@ mod tests {
       format!("{},{}", france.name, argentina.name)
     );
   }
+
+  #[test]
+  fn text_validation_regex_test() {
+    let type_option = RichTextTypeOption {
+      validation_regex: Some(r"[A-Z]{3}-\d+".to_string()),
+      ..Default::default()
+    };
+    assert!(type_option.validate_pattern().is_ok());
+    assert!(type_option.validate_text("SKU-42").is_ok());
+    // The pattern must match the whole text
+    assert!(type_option.validate_text("SKU-42x").is_err());
+    assert!(type_option.validate_text("sku-42").is_err());
+    // Empty text is allowed unless it's required
+    assert!(type_option.validate_text("").is_ok());
+
+    let type_option = RichTextTypeOption {
+      required: true,
+      ..type_option
+    };
+    assert!(type_option.validate_text("").is_err());
+
+    let type_option = RichTextTypeOption {
+      validation_regex: Some("(".to_string()),
+      ..Default::default()
+    };
+    assert!(type_option.validate_pattern().is_err());
+  }
+
+  #[test]
+  fn validate_field_text_test() {
+    let type_option = RichTextTypeOption {
+      validation_regex: Some(r"\d+".to_string()),
+      required: true,
+      ..Default::default()
+    };
+    let field = FieldBuilder::new(FieldType::RichText, type_option).build();
+    assert!(validate_field_text(&field, "42").is_ok());
+    assert!(validate_field_text(&field, "4x2").is_err());
+    assert!(validate_field_text(&field, "").is_err());
+
+    // The other field types aren't validated
+    let field = FieldBuilder::from_field_type(FieldType::URL).build();
+    assert!(validate_field_text(&field, "").is_ok());
+  }
+
+  #[test]
+  fn regex_cache_evicts_least_recently_used_test() {
+    let mut cache = RegexCache::new(2);
+    let regex = |pattern: &str| fancy_regex::Regex::new(pattern).unwrap();
+    cache.insert("a".to_string(), regex("a"));
+    cache.insert("b".to_string(), regex("b"));
+    // Using "a" makes "b" the least recently used one
+    assert!(cache.get("a").is_some());
+    cache.insert("c".to_string(), regex("c"));
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("b").is_none());
+    assert!(cache.get("c").is_some());
+  }
 }
@@ -1,8 +1,12 @@
 use std::cmp::Ordering;
+use std::collections::{HashMap, VecDeque};
 
 use collab::core::any_map::AnyMapExtension;
 use collab_database::fields::{Field, TypeOptionData, TypeOptionDataBuilder};
 use collab_database::rows::{new_cell_builder, Cell};
+use fancy_regex::Regex;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 
 use flowy_error::{FlowyError, FlowyResult};
@@ -16,12 +20,143 @@ use crate::services::field::{
 };
 use crate::services::sort::SortCondition;
 
-/// For the moment, the `RichTextTypeOptionPB` is empty. The `data` property is not
-/// used yet.
+const VALIDATION_REGEX: &str = "validation_regex";
+const REQUIRED: &str = "required";
+
+/// The number of the compiled validation patterns that are kept.
+const VALIDATION_REGEX_CACHE_CAPACITY: usize = 32;
+
+lazy_static! {
+  /// The type option is created from the field every time a cell is updated, so the compiled
+  /// validation patterns are cached by their source.
+  static ref VALIDATION_REGEX_CACHE: Mutex<RegexCache> =
+    Mutex::new(RegexCache::new(VALIDATION_REGEX_CACHE_CAPACITY));
+}
+
+/// Keeps the most recently used patterns up to its capacity, so the patterns that users try out
+/// one after another don't pile up.
+pub(super) struct RegexCache {
+  capacity: usize,
+  regexes: HashMap<String, Regex>,
+  /// The patterns from the least to the most recently used.
+  recently_used: VecDeque<String>,
+}
+
+impl RegexCache {
+  pub(super) fn new(capacity: usize) -> Self {
+    Self {
+      capacity,
+      regexes: HashMap::new(),
+      recently_used: VecDeque::new(),
+    }
+  }
+
+  pub(super) fn get(&mut self, pattern: &str) -> Option<Regex> {
+    let regex = self.regexes.get(pattern)?.clone();
+    if let Some(index) = self.recently_used.iter().position(|used| used == pattern) {
+      if let Some(used) = self.recently_used.remove(index) {
+        self.recently_used.push_back(used);
+      }
+    }
+    Some(regex)
+  }
+
+  pub(super) fn insert(&mut self, pattern: String, regex: Regex) {
+    if self.regexes.insert(pattern.clone(), regex).is_some() {
+      return;
+    }
+    self.recently_used.push_back(pattern);
+    while self.recently_used.len() > self.capacity {
+      if let Some(least_used) = self.recently_used.pop_front() {
+        self.regexes.remove(&least_used);
+      }
+    }
+  }
+}
+
+/// The `data` property of the `RichTextTypeOptionPB` is not used yet.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RichTextTypeOption {
   #[serde(default)]
   pub inner: String,
+  /// The pattern that the whole text of the cells must match, e.g. a phone number or a SKU.
+  /// Empty text is allowed unless [Self::required] is true.
+  #[serde(default)]
+  pub validation_regex: Option<String>,
+  /// Rejects empty text.
+  #[serde(default)]
+  pub required: bool,
+}
+
+impl RichTextTypeOption {
+  /// Returns an error if the validation pattern can't be compiled.
+  pub fn validate_pattern(&self) -> FlowyResult<()> {
+    if let Some(pattern) = &self.validation_regex {
+      validation_regex(pattern)?;
+    }
+    Ok(())
+  }
+
+  /// Returns [ErrorCode::InvalidParams] with the pattern if the text doesn't match it, or if the
+  /// text is empty and required.
+  ///
+  /// [ErrorCode::InvalidParams]: flowy_error::ErrorCode::InvalidParams
+  pub fn validate_text(&self, text: &str) -> FlowyResult<()> {
+    if text.is_empty() {
+      if self.required {
+        return Err(FlowyError::invalid_data().with_context("Text is required"));
+      }
+      return Ok(());
+    }
+
+    if let Some(pattern) = &self.validation_regex {
+      let is_match = validation_regex(pattern)?
+        .is_match(text)
+        .map_err(|err| FlowyError::invalid_data().with_context(err))?;
+      if !is_match {
+        let msg = format!("The text doesn't match the pattern: {}", pattern);
+        return Err(FlowyError::invalid_data().with_context(msg));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Returns [ErrorCode::InvalidParams] if the text breaks the validation of the text field. Does
+/// nothing for the other field types.
+///
+/// The cells written without going through [RichTextTypeOption::apply_changeset], e.g. by the
+/// imports and the bulk updates, are validated with it.
+///
+/// [ErrorCode::InvalidParams]: flowy_error::ErrorCode::InvalidParams
+pub fn validate_field_text(field: &Field, text: &str) -> FlowyResult<()> {
+  if FieldType::from(field.field_type) != FieldType::RichText {
+    return Ok(());
+  }
+  match field.get_type_option::<RichTextTypeOption>(FieldType::RichText) {
+    Some(type_option) => type_option.validate_text(text),
+    None => Ok(()),
+  }
+}
+
+/// Same as [validate_field_text] with the text of the cell.
+pub fn validate_field_cell(field: &Field, cell: &Cell) -> FlowyResult<()> {
+  validate_field_text(field, &StringCellData::from(cell).0)
+}
+
+/// Returns the compiled pattern. It's anchored, so it has to match the whole text.
+fn validation_regex(pattern: &str) -> FlowyResult<Regex> {
+  let mut cache = VALIDATION_REGEX_CACHE.lock();
+  if let Some(regex) = cache.get(pattern) {
+    return Ok(regex);
+  }
+
+  let regex = Regex::new(&format!("^(?:{})$", pattern)).map_err(|err| {
+    let msg = format!("Invalid pattern {}: {}", pattern, err);
+    FlowyError::invalid_data().with_context(msg)
+  })?;
+  cache.insert(pattern.to_string(), regex.clone());
+  Ok(regex)
 }
 
 impl TypeOption for RichTextTypeOption {
@@ -34,7 +169,15 @@ impl TypeOption for RichTextTypeOption {
 impl From<TypeOptionData> for RichTextTypeOption {
   fn from(data: TypeOptionData) -> Self {
     let s = data.get_str_value(CELL_DATA).unwrap_or_default();
-    Self { inner: s }
+    let validation_regex = data
+      .get_str_value(VALIDATION_REGEX)
+      .filter(|pattern| !pattern.is_empty());
+    let required = data.get_bool_value(REQUIRED).unwrap_or(false);
+    Self {
+      inner: s,
+      validation_regex,
+      required,
+    }
   }
 }
 
@@ -42,6 +185,8 @@ impl From<RichTextTypeOption> for TypeOptionData {
   fn from(data: RichTextTypeOption) -> Self {
     TypeOptionDataBuilder::new()
       .insert_str_value(CELL_DATA, data.inner)
+      .insert_str_value(VALIDATION_REGEX, data.validation_regex.unwrap_or_default())
+      .insert_bool_value(REQUIRED, data.required)
       .build()
   }
 }
@@ -83,7 +228,9 @@ impl CellDataDecoder for RichTextTypeOption {
       FieldType::Checklist
       | FieldType::LastEditedTime
       | FieldType::CreatedTime
-      | FieldType::Relation => None,
+      | FieldType::Relation
+      | FieldType::LastEditedBy
+      | FieldType::CreatedBy => None,
       FieldType::Summary => Some(StringCellData::from(stringify_cell(cell, field))),
     }
   }
@@ -109,6 +256,7 @@ impl CellDataChangeset for RichTextTypeOption {
           .with_context("The len of the text should not be more than 10000"),
       )
     } else {
+      self.validate_text(&changeset)?;
       let text_cell_data = StringCellData(changeset);
       Ok((text_cell_data.clone().into(), text_cell_data))
     }
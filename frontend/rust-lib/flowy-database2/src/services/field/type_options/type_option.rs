@@ -12,13 +12,14 @@ use crate::entities::{
   CheckboxTypeOptionPB, ChecklistTypeOptionPB, DateTypeOptionPB, FieldType,
   MultiSelectTypeOptionPB, NumberTypeOptionPB, RelationTypeOptionPB, RichTextTypeOptionPB,
   SingleSelectTypeOptionPB, SummarizationTypeOptionPB, TimestampTypeOptionPB, URLTypeOptionPB,
+  UserTypeOptionPB,
 };
 use crate::services::cell::CellDataDecoder;
 use crate::services::field::checklist_type_option::ChecklistTypeOption;
 use crate::services::field::summary_type_option::summary::SummarizationTypeOption;
 use crate::services::field::{
   CheckboxTypeOption, DateTypeOption, MultiSelectTypeOption, NumberTypeOption, RelationTypeOption,
-  RichTextTypeOption, SingleSelectTypeOption, TimestampTypeOption, URLTypeOption,
+  RichTextTypeOption, SingleSelectTypeOption, TimestampTypeOption, URLTypeOption, UserTypeOption,
 };
 use crate::services::filter::{ParseFilterData, PreFillCellsWithFilter};
 use crate::services::sort::SortCondition;
@@ -185,6 +186,9 @@ pub fn type_option_data_from_pb<T: Into<Bytes>>(
     FieldType::Summary => {
       SummarizationTypeOptionPB::try_from(bytes).map(|pb| SummarizationTypeOption::from(pb).into())
     },
+    FieldType::LastEditedBy | FieldType::CreatedBy => {
+      UserTypeOptionPB::try_from(bytes).map(|pb| UserTypeOption::from(pb).into())
+    },
   }
 }
 
@@ -252,6 +256,10 @@ pub fn type_option_to_pb(type_option: TypeOptionData, field_type: &FieldType) ->
         .try_into()
         .unwrap()
     },
+    FieldType::LastEditedBy | FieldType::CreatedBy => {
+      let user_type_option: UserTypeOption = type_option.into();
+      UserTypeOptionPB::from(user_type_option).try_into().unwrap()
+    },
   }
 }
 
@@ -272,5 +280,6 @@ pub fn default_type_option_data_from_type(field_type: FieldType) -> TypeOptionDa
     FieldType::Checklist => ChecklistTypeOption.into(),
     FieldType::Relation => RelationTypeOption::default().into(),
     FieldType::Summary => SummarizationTypeOption::default().into(),
+    FieldType::LastEditedBy | FieldType::CreatedBy => UserTypeOption::new(field_type).into(),
   }
 }
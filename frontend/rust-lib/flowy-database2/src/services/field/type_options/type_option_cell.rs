@@ -12,9 +12,10 @@ use crate::entities::FieldType;
 use crate::services::cell::{CellCache, CellDataChangeset, CellDataDecoder, CellProtobufBlob};
 use crate::services::field::summary_type_option::summary::SummarizationTypeOption;
 use crate::services::field::{
-  CheckboxTypeOption, ChecklistTypeOption, DateTypeOption, MultiSelectTypeOption, NumberTypeOption,
-  RelationTypeOption, RichTextTypeOption, SingleSelectTypeOption, TimestampTypeOption, TypeOption,
-  TypeOptionCellData, TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde,
+  is_built_in_field_type, CheckboxTypeOption, ChecklistTypeOption, DateTypeOption,
+  MultiSelectTypeOption, NumberTypeOption, RelationTypeOption, RichTextTypeOption,
+  SingleSelectTypeOption, TimestampTypeOption, TypeOption, TypeOptionCellData,
+  TypeOptionCellDataCompare, TypeOptionCellDataFilter, TypeOptionCellDataSerde, TypeOptionRegistry,
   TypeOptionTransform, URLTypeOption,
 };
 use crate::services::sort::SortCondition;
@@ -449,10 +450,34 @@ impl<'a> TypeOptionCellExt<'a> {
             self.cell_data_cache.clone(),
           )
         }),
+      FieldType::LastEditedBy | FieldType::CreatedBy => self
+        .field
+        .get_type_option::<UserTypeOption>(field_type)
+        .map(|type_option| {
+          TypeOptionCellDataHandlerImpl::new_with_boxed(
+            type_option,
+            field_type,
+            self.cell_data_cache.clone(),
+          )
+        }),
     }
   }
 
+  /// Resolves the handler of a custom field type from the [TypeOptionRegistry]. The cells of a
+  /// custom field type that isn't registered are handled as text.
   pub fn get_type_option_cell_data_handler(&self) -> Option<Box<dyn TypeOptionCellDataHandler>> {
+    if !is_built_in_field_type(self.field.field_type) {
+      return TypeOptionRegistry::global()
+        .create_handler(self.field, self.cell_data_cache.clone())
+        .or_else(|| {
+          Some(TypeOptionCellDataHandlerImpl::new_with_boxed(
+            RichTextTypeOption::default(),
+            FieldType::RichText,
+            self.cell_data_cache.clone(),
+          ))
+        });
+    }
+
     let field_type = FieldType::from(self.field.field_type);
     self.get_type_option_cell_data_handler_with_field_type(field_type)
   }
@@ -552,6 +577,9 @@ fn get_type_option_transform_handler(
     },
     FieldType::Summary => Box::new(SummarizationTypeOption::from(type_option_data))
       as Box<dyn TypeOptionTransformHandler>,
+    FieldType::LastEditedBy | FieldType::CreatedBy => {
+      Box::new(UserTypeOption::from(type_option_data)) as Box<dyn TypeOptionTransformHandler>
+    },
   }
 }
 
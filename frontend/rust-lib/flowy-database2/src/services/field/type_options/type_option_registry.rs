@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use collab_database::fields::Field;
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use strum::IntoEnumIterator;
+
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::entities::FieldType;
+use crate::services::cell::CellCache;
+use crate::services::field::TypeOptionCellDataHandler;
+
+lazy_static! {
+  static ref TYPE_OPTION_REGISTRY: TypeOptionRegistry = TypeOptionRegistry::default();
+}
+
+/// Creates the [TypeOptionCellDataHandler] of a field whose type is registered in the
+/// [TypeOptionRegistry]. Returning None means the cells of the field can't be read or edited.
+pub type TypeOptionCellDataHandlerFactory = Arc<
+  dyn Fn(&Field, Option<CellCache>) -> Option<Box<dyn TypeOptionCellDataHandler>> + Send + Sync,
+>;
+
+/// Lets the code outside of this crate add its own field types, e.g. a rating or a color field,
+/// without changing [FieldType]. A custom field type is identified by the `field_type` value that's
+/// stored in the [Field], which must not be one of the values of [FieldType].
+///
+/// The registry is consulted by [crate::services::field::TypeOptionCellExt] when it resolves the
+/// handler of a field whose type isn't built in. The cells of a custom field type that isn't
+/// registered, e.g. a database that's opened without the plugin that created it, are read as text.
+#[derive(Default)]
+pub struct TypeOptionRegistry {
+  factories: RwLock<HashMap<i64, TypeOptionCellDataHandlerFactory>>,
+}
+
+impl TypeOptionRegistry {
+  /// The registry that's shared by all the databases of the process.
+  pub fn global() -> &'static TypeOptionRegistry {
+    &TYPE_OPTION_REGISTRY
+  }
+
+  /// Registers the factory of the custom field type, replacing the one that was registered before.
+  pub fn register<F>(&self, field_type: i64, factory: F) -> FlowyResult<()>
+  where
+    F: Fn(&Field, Option<CellCache>) -> Option<Box<dyn TypeOptionCellDataHandler>>
+      + Send
+      + Sync
+      + 'static,
+  {
+    if is_built_in_field_type(field_type) {
+      return Err(FlowyError::invalid_data().with_context(format!(
+        "The field type {} is built in and can't be registered",
+        field_type
+      )));
+    }
+    self.factories.write().insert(field_type, Arc::new(factory));
+    Ok(())
+  }
+
+  pub fn unregister(&self, field_type: i64) {
+    self.factories.write().remove(&field_type);
+  }
+
+  pub fn is_registered(&self, field_type: i64) -> bool {
+    self.factories.read().contains_key(&field_type)
+  }
+
+  /// Returns None if the type of the field isn't registered.
+  pub(crate) fn create_handler(
+    &self,
+    field: &Field,
+    cell_data_cache: Option<CellCache>,
+  ) -> Option<Box<dyn TypeOptionCellDataHandler>> {
+    // The lock isn't held while the factory runs, so the factory may use the registry
+    let factory = self.factories.read().get(&field.field_type).cloned()?;
+    factory(field, cell_data_cache)
+  }
+}
+
+pub fn is_built_in_field_type(field_type: i64) -> bool {
+  FieldType::iter().any(|built_in| built_in.value() == field_type)
+}
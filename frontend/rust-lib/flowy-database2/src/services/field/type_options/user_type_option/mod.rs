@@ -0,0 +1,6 @@
+#![allow(clippy::module_inception)]
+mod user_type_option;
+mod user_type_option_entities;
+
+pub use user_type_option::*;
+pub use user_type_option_entities::*;
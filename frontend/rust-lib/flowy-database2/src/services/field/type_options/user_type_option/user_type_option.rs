@@ -0,0 +1,131 @@
+use std::cmp::Ordering;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::{TypeOptionData, TypeOptionDataBuilder};
+use collab_database::rows::Cell;
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use serde::{Deserialize, Serialize};
+
+use crate::entities::{FieldType, TextFilterPB};
+use crate::services::cell::{CellDataChangeset, CellDataDecoder};
+use crate::services::field::type_options::util::ProtobufStr;
+use crate::services::field::{
+  default_order, TypeOption, TypeOptionCellDataCompare, TypeOptionCellDataFilter,
+  TypeOptionCellDataSerde, TypeOptionTransform, UserCellData,
+};
+use crate::services::sort::SortCondition;
+
+/// The type option of the [FieldType::LastEditedBy] and [FieldType::CreatedBy] fields. The cells
+/// of these fields are filled automatically with the id of the user who edited or created the row.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserTypeOption {
+  pub field_type: FieldType,
+}
+
+impl Default for UserTypeOption {
+  fn default() -> Self {
+    Self {
+      field_type: FieldType::LastEditedBy,
+    }
+  }
+}
+
+impl UserTypeOption {
+  pub fn new(field_type: FieldType) -> Self {
+    Self { field_type }
+  }
+}
+
+impl TypeOption for UserTypeOption {
+  type CellData = UserCellData;
+  type CellChangeset = String;
+  type CellProtobufType = ProtobufStr;
+  type CellFilter = TextFilterPB;
+}
+
+impl From<TypeOptionData> for UserTypeOption {
+  fn from(data: TypeOptionData) -> Self {
+    let field_type = data
+      .get_i64_value("field_type")
+      .map(FieldType::from)
+      .unwrap_or(FieldType::LastEditedBy);
+    Self { field_type }
+  }
+}
+
+impl From<UserTypeOption> for TypeOptionData {
+  fn from(option: UserTypeOption) -> Self {
+    TypeOptionDataBuilder::new()
+      .insert_i64_value("field_type", option.field_type.value())
+      .build()
+  }
+}
+
+impl TypeOptionCellDataSerde for UserTypeOption {
+  fn protobuf_encode(
+    &self,
+    cell_data: <Self as TypeOption>::CellData,
+  ) -> <Self as TypeOption>::CellProtobufType {
+    ProtobufStr::from(self.stringify_cell_data(cell_data))
+  }
+
+  fn parse_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    Ok(UserCellData::from(cell))
+  }
+}
+
+impl TypeOptionTransform for UserTypeOption {}
+
+impl CellDataDecoder for UserTypeOption {
+  fn decode_cell(&self, cell: &Cell) -> FlowyResult<<Self as TypeOption>::CellData> {
+    self.parse_cell(cell)
+  }
+
+  fn stringify_cell_data(&self, cell_data: <Self as TypeOption>::CellData) -> String {
+    cell_data.to_string()
+  }
+
+  fn numeric_cell(&self, _cell: &Cell) -> Option<f64> {
+    None
+  }
+}
+
+impl CellDataChangeset for UserTypeOption {
+  fn apply_changeset(
+    &self,
+    _changeset: <Self as TypeOption>::CellChangeset,
+    _cell: Option<Cell>,
+  ) -> FlowyResult<(Cell, <Self as TypeOption>::CellData)> {
+    Err(FlowyError::new(
+      ErrorCode::FieldInvalidOperation,
+      "Cells of this field type cannot be edited",
+    ))
+  }
+}
+
+impl TypeOptionCellDataFilter for UserTypeOption {
+  /// The content of the filter is the id of the user
+  fn apply_filter(
+    &self,
+    filter: &<Self as TypeOption>::CellFilter,
+    cell_data: &<Self as TypeOption>::CellData,
+  ) -> bool {
+    filter.is_visible(cell_data.to_string())
+  }
+}
+
+impl TypeOptionCellDataCompare for UserTypeOption {
+  fn apply_cmp(
+    &self,
+    cell_data: &<Self as TypeOption>::CellData,
+    other_cell_data: &<Self as TypeOption>::CellData,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    match (cell_data.user_id, other_cell_data.user_id) {
+      (Some(left), Some(right)) => sort_condition.evaluate_order(left.cmp(&right)),
+      (Some(_), None) => Ordering::Less,
+      (None, Some(_)) => Ordering::Greater,
+      (None, None) => default_order(),
+    }
+  }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::rows::{new_cell_builder, Cell, RowId};
+use serde::Serialize;
+
+use crate::entities::FieldType;
+use crate::services::field::{TypeOptionCellData, CELL_DATA};
+
+/// The users who created and last edited a row. They're kept in the metas of the database rather
+/// than in the cells of the row, so every user field of the database reads from them.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RowUsers {
+  pub created_by: Option<i64>,
+  pub last_edited_by: Option<i64>,
+}
+
+/// The [RowUsers] by the id of the row.
+pub type RowUsersByRowId = HashMap<RowId, RowUsers>;
+
+impl RowUsers {
+  /// Returns the cell of the [FieldType::CreatedBy] or [FieldType::LastEditedBy] field. None if
+  /// the user isn't known.
+  pub fn cell(&self, field_type: FieldType) -> Option<Cell> {
+    let user_id = match field_type {
+      FieldType::CreatedBy => self.created_by,
+      FieldType::LastEditedBy => self.last_edited_by,
+      _ => None,
+    }?;
+    Some(UserCellData::new(user_id).into_cell(field_type))
+  }
+}
+
+/// Returns the cell of the [FieldType::CreatedBy] or [FieldType::LastEditedBy] field of the row.
+pub(crate) fn user_cell_of_row(
+  row_users: &RowUsersByRowId,
+  row_id: &RowId,
+  field_type: FieldType,
+) -> Option<Cell> {
+  row_users.get(row_id)?.cell(field_type)
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct UserCellData {
+  pub user_id: Option<i64>,
+}
+
+impl UserCellData {
+  pub fn new(user_id: i64) -> Self {
+    Self {
+      user_id: Some(user_id),
+    }
+  }
+
+  pub fn into_cell(self, field_type: FieldType) -> Cell {
+    new_cell_builder(field_type)
+      .insert_str_value(
+        CELL_DATA,
+        self.user_id.map(|id| id.to_string()).unwrap_or_default(),
+      )
+      .build()
+  }
+}
+
+impl From<&Cell> for UserCellData {
+  fn from(cell: &Cell) -> Self {
+    let user_id = cell
+      .get_str_value(CELL_DATA)
+      .and_then(|data| data.parse::<i64>().ok());
+    Self { user_id }
+  }
+}
+
+impl TypeOptionCellData for UserCellData {
+  fn is_cell_empty(&self) -> bool {
+    self.user_id.is_none()
+  }
+}
+
+impl ToString for UserCellData {
+  fn to_string(&self) -> String {
+    self.user_id.map(|id| id.to_string()).unwrap_or_default()
+  }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use collab::core::collab::MutexCollab;
+use collab::preclude::{Map, MapRef, MapRefExtension, ReadTxn, TransactionMut, YrsValue};
+use collab_database::views::DatabaseLayout;
+use collab_entity::define::DATABASE;
+use serde::Deserialize;
+use tracing::error;
+
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::services::field_settings::{
+  FieldSettings, DEFAULT_SORT_CONDITION, VISIBILITY, WIDTH, WRAP_CELL_CONTENT,
+};
+use crate::services::sort::SortCondition;
+
+/// The map of the database collab that stores the metas of the database, e.g. the id of its
+/// inline view.
+pub(crate) const DATABASE_METAS: &str = "metas";
+
+/// The overrides of the field settings that the new views start with, by layout and field id.
+pub type DefaultFieldSettingsByLayout = HashMap<DatabaseLayout, HashMap<String, FieldSettings>>;
+
+/// The form of the overrides that were stored as one JSON by layout. They're still read until the
+/// overrides of the layout are saved again.
+#[derive(Deserialize)]
+struct LegacyDefaultFieldSettingsData {
+  visibility: i64,
+  width: i32,
+  wrap_cell_content: bool,
+  default_sort_condition: i64,
+}
+
+impl LegacyDefaultFieldSettingsData {
+  fn into_field_settings(self, field_id: String) -> FieldSettings {
+    FieldSettings {
+      field_id,
+      visibility: self.visibility.into(),
+      width: self.width,
+      wrap_cell_content: self.wrap_cell_content,
+      default_sort_condition: SortCondition::from(self.default_sort_condition),
+    }
+  }
+}
+
+fn metas_key(layout: DatabaseLayout) -> &'static str {
+  match layout {
+    DatabaseLayout::Grid => "grid_field_settings_overrides",
+    DatabaseLayout::Board => "board_field_settings_overrides",
+    DatabaseLayout::Calendar => "calendar_field_settings_overrides",
+  }
+}
+
+fn legacy_metas_key(layout: DatabaseLayout) -> &'static str {
+  match layout {
+    DatabaseLayout::Grid => "grid_default_field_settings",
+    DatabaseLayout::Board => "board_default_field_settings",
+    DatabaseLayout::Calendar => "calendar_default_field_settings",
+  }
+}
+
+fn read_field_settings<T: ReadTxn>(txn: &T, field_id: &str, map: &MapRef) -> Option<FieldSettings> {
+  Some(FieldSettings {
+    field_id: field_id.to_string(),
+    visibility: map.get_i64_with_txn(txn, VISIBILITY)?.into(),
+    width: map.get_i64_with_txn(txn, WIDTH)? as i32,
+    wrap_cell_content: map.get_bool_with_txn(txn, WRAP_CELL_CONTENT)?,
+    default_sort_condition: SortCondition::from(map.get_i64_with_txn(txn, DEFAULT_SORT_CONDITION)?),
+  })
+}
+
+/// Only writes the attributes that changed, so the concurrent edits of the other attributes are
+/// kept.
+fn write_field_settings(txn: &mut TransactionMut, map: &MapRef, field_settings: FieldSettings) {
+  let visibility = i64::from(field_settings.visibility);
+  if map.get_i64_with_txn(txn, VISIBILITY) != Some(visibility) {
+    map.insert_i64_with_txn(txn, VISIBILITY, visibility);
+  }
+  let width = field_settings.width as i64;
+  if map.get_i64_with_txn(txn, WIDTH) != Some(width) {
+    map.insert_i64_with_txn(txn, WIDTH, width);
+  }
+  if map.get_bool_with_txn(txn, WRAP_CELL_CONTENT) != Some(field_settings.wrap_cell_content) {
+    map.insert_bool_with_txn(txn, WRAP_CELL_CONTENT, field_settings.wrap_cell_content);
+  }
+  let default_sort_condition = field_settings.default_sort_condition.value();
+  if map.get_i64_with_txn(txn, DEFAULT_SORT_CONDITION) != Some(default_sort_condition) {
+    map.insert_i64_with_txn(txn, DEFAULT_SORT_CONDITION, default_sort_condition);
+  }
+}
+
+/// Returns the overrides saved in the database collab by [save_default_field_settings]. The
+/// overrides that can't be read are skipped.
+pub fn get_default_field_settings(collab: &MutexCollab) -> DefaultFieldSettingsByLayout {
+  let collab = collab.lock();
+  let txn = collab.transact();
+  let metas = match collab.get_map_with_txn(&txn, vec![DATABASE, DATABASE_METAS]) {
+    Some(metas) => metas,
+    None => return HashMap::new(),
+  };
+
+  let mut default_field_settings = HashMap::new();
+  for layout in [
+    DatabaseLayout::Grid,
+    DatabaseLayout::Board,
+    DatabaseLayout::Calendar,
+  ] {
+    if let Some(layout_map) = metas.get_map_with_txn(&txn, metas_key(layout)) {
+      let mut field_settings = HashMap::new();
+      for (field_id, value) in layout_map.iter(&txn) {
+        let settings = match value {
+          YrsValue::YMap(map) => read_field_settings(&txn, field_id, &map),
+          _ => None,
+        };
+        match settings {
+          Some(settings) => {
+            field_settings.insert(field_id.to_string(), settings);
+          },
+          None => error!(
+            "Failed to read the default field settings of {:?} for field:{}",
+            layout, field_id
+          ),
+        }
+      }
+      default_field_settings.insert(layout, field_settings);
+      continue;
+    }
+
+    let json = match metas.get_str_with_txn(&txn, legacy_metas_key(layout)) {
+      Some(json) => json,
+      None => continue,
+    };
+    match serde_json::from_str::<HashMap<String, LegacyDefaultFieldSettingsData>>(&json) {
+      Ok(data) => {
+        let field_settings = data
+          .into_iter()
+          .map(|(field_id, data)| (field_id.clone(), data.into_field_settings(field_id)))
+          .collect();
+        default_field_settings.insert(layout, field_settings);
+      },
+      Err(err) => error!(
+        "Failed to read the default field settings of {:?}: {}",
+        layout, err
+      ),
+    }
+  }
+  default_field_settings
+}
+
+/// Saves the overrides of `layout` in the database collab, replacing the previous ones. They're
+/// synced with the database, so every member of the workspace gets them.
+///
+/// Each override is a map keyed by the field id in the map of the layout, and only the attributes
+/// that changed are written, so the members editing the overrides of different fields don't
+/// overwrite each other.
+pub fn save_default_field_settings(
+  collab: &MutexCollab,
+  layout: DatabaseLayout,
+  settings: Vec<FieldSettings>,
+) -> FlowyResult<()> {
+  let collab = collab.lock();
+  collab.with_origin_transact_mut(|txn| {
+    let metas = collab
+      .get_map_with_txn(txn, vec![DATABASE, DATABASE_METAS])
+      .ok_or_else(|| FlowyError::internal().with_context("The database has no metas"))?;
+    let layout_map = match metas.get_map_with_txn(txn, metas_key(layout)) {
+      Some(layout_map) => layout_map,
+      None => {
+        // The legacy overrides are replaced by the saved ones
+        metas.delete_with_txn(txn, legacy_metas_key(layout));
+        metas.create_map_with_txn(txn, metas_key(layout))
+      },
+    };
+
+    let removed_field_ids = layout_map
+      .keys(txn)
+      .filter(|field_id| {
+        !settings
+          .iter()
+          .any(|field_settings| field_settings.field_id == *field_id)
+      })
+      .map(|field_id| field_id.to_string())
+      .collect::<Vec<_>>();
+    for field_id in removed_field_ids {
+      layout_map.delete_with_txn(txn, &field_id);
+    }
+
+    for field_settings in settings {
+      let map = match layout_map.get_map_with_txn(txn, &field_settings.field_id) {
+        Some(map) => map,
+        None => layout_map.create_map_with_txn(txn, &field_settings.field_id),
+      };
+      write_field_settings(txn, &map, field_settings);
+    }
+    Ok(())
+  })
+}
@@ -3,6 +3,7 @@ use collab_database::views::{DatabaseLayout, FieldSettingsMap, FieldSettingsMapB
 
 use crate::entities::FieldVisibility;
 use crate::services::field_settings::default_field_visibility;
+use crate::services::sort::SortCondition;
 
 /// Stores the field settings for a single field
 #[derive(Debug, Clone)]
@@ -11,14 +12,23 @@ pub struct FieldSettings {
   pub visibility: FieldVisibility,
   pub width: i32,
   pub wrap_cell_content: bool,
+  /// The condition of the sorts that are created for the field.
+  pub default_sort_condition: SortCondition,
 }
 
 pub const VISIBILITY: &str = "visibility";
 pub const WIDTH: &str = "width";
 pub const DEFAULT_WIDTH: i32 = 150;
 pub const WRAP_CELL_CONTENT: &str = "wrap";
+pub const DEFAULT_SORT_CONDITION: &str = "default_sort_condition";
 
 impl FieldSettings {
+  /// Returns false if the field is always hidden. A field that's hidden when it's empty counts as
+  /// visible.
+  pub fn is_visible(&self) -> bool {
+    self.visibility != FieldVisibility::AlwaysHidden
+  }
+
   pub fn from_any_map(
     field_id: &str,
     layout_type: DatabaseLayout,
@@ -35,12 +45,17 @@ impl FieldSettings {
     let wrap_cell_content = field_settings
       .get_bool_value(WRAP_CELL_CONTENT)
       .unwrap_or(true);
+    let default_sort_condition = field_settings
+      .get_i64_value(DEFAULT_SORT_CONDITION)
+      .map(SortCondition::from)
+      .unwrap_or_default();
 
     Self {
       field_id: field_id.to_string(),
       visibility,
       width,
       wrap_cell_content,
+      default_sort_condition,
     }
   }
 }
@@ -51,6 +66,10 @@ impl From<FieldSettings> for FieldSettingsMap {
       .insert_i64_value(VISIBILITY, field_settings.visibility.into())
       .insert_i64_value(WIDTH, field_settings.width as i64)
       .insert_bool_value(WRAP_CELL_CONTENT, field_settings.wrap_cell_content)
+      .insert_i64_value(
+        DEFAULT_SORT_CONDITION,
+        field_settings.default_sort_condition.value(),
+      )
       .build()
   }
 }
@@ -7,7 +7,9 @@ use collab_database::views::{
 use strum::IntoEnumIterator;
 
 use crate::entities::FieldVisibility;
+use crate::services::field::is_system_field;
 use crate::services::field_settings::{FieldSettings, DEFAULT_WIDTH, VISIBILITY};
+use crate::services::sort::SortCondition;
 
 /// Helper struct to create a new field setting
 pub struct FieldSettingsBuilder {
@@ -21,6 +23,7 @@ impl FieldSettingsBuilder {
       visibility: FieldVisibility::AlwaysShown,
       width: DEFAULT_WIDTH,
       wrap_cell_content: true,
+      default_sort_condition: SortCondition::Ascending,
     };
 
     Self {
@@ -72,6 +75,8 @@ pub fn field_settings_for_field(
 ) -> FieldSettingsMap {
   let visibility = if field.is_primary {
     FieldVisibility::AlwaysShown
+  } else if is_system_field(field) {
+    FieldVisibility::AlwaysHidden
   } else {
     default_field_visibility(database_layout)
   };
@@ -94,3 +99,15 @@ pub fn default_field_settings_by_layout_map() -> HashMap<DatabaseLayout, FieldSe
 
   map
 }
+
+/// The settings of a system field, which is hidden in all the layouts.
+pub fn system_field_settings_by_layout_map() -> HashMap<DatabaseLayout, FieldSettingsMap> {
+  DatabaseLayout::iter()
+    .map(|layout_ty| {
+      let field_settings = FieldSettingsMapBuilder::new()
+        .insert_i64_value(VISIBILITY, FieldVisibility::AlwaysHidden.into())
+        .build();
+      (layout_ty, field_settings)
+    })
+    .collect()
+}
@@ -1,5 +1,7 @@
+pub use default_field_settings::*;
 pub use entities::*;
 pub use field_settings_builder::*;
 
+mod default_field_settings;
 mod entities;
 mod field_settings_builder;
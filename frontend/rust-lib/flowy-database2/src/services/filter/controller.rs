@@ -17,7 +17,7 @@ use crate::entities::filter_entities::*;
 use crate::entities::{FieldType, InsertedRowPB, RowMetaPB};
 use crate::services::cell::CellCache;
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
-use crate::services::field::TypeOptionCellExt;
+use crate::services::field::{user_cell_of_row, RowUsersByRowId, TypeOptionCellExt};
 use crate::services::filter::{Filter, FilterChangeset, FilterInner, FilterResultNotification};
 
 pub trait FilterDelegate: Send + Sync + 'static {
@@ -27,6 +27,8 @@ pub trait FilterDelegate: Send + Sync + 'static {
   fn get_row(&self, view_id: &str, rows_id: &RowId) -> Fut<Option<(usize, Arc<RowDetail>)>>;
   fn get_all_filters(&self, view_id: &str) -> Vec<Filter>;
   fn save_filters(&self, view_id: &str, filters: &[Filter]);
+  /// Returns the users who created and last edited the rows
+  fn get_row_users(&self) -> RowUsersByRowId;
 }
 
 pub trait PreFillCellsWithFilter {
@@ -135,6 +137,7 @@ impl FilterController {
       return;
     }
     let field_by_field_id = self.get_field_map().await;
+    let row_users = self.get_row_users(&filters);
     rows.iter().for_each(|row_detail| {
       let _ = filter_row(
         &row_detail.row,
@@ -142,6 +145,7 @@ impl FilterController {
         &field_by_field_id,
         &self.cell_cache,
         &filters,
+        &row_users,
       );
     });
 
@@ -240,6 +244,19 @@ impl FilterController {
     FilterChangesetNotificationPB::from_filters(&self.view_id, &filters)
   }
 
+  pub async fn has_filters(&self) -> bool {
+    !self.filters.read().await.is_empty()
+  }
+
+  /// Reads the filters of the view again, e.g. after they were replaced in the database, and
+  /// filters the rows with them.
+  pub async fn reload_filters(&self) {
+    *self.filters.write().await = self.delegate.get_all_filters(&self.view_id);
+    self
+      .gen_task(FilterEvent::FilterDidChanged, QualityOfService::Background)
+      .await;
+  }
+
   pub async fn fill_cells(&self, cells: &mut Cells) -> bool {
     let filters = self.filters.read().await;
 
@@ -270,6 +287,13 @@ impl FilterController {
         }
 
         if let Some(field) = field_map.get(field_id) {
+          // The empty cells are left as they are, and the non-empty ones can't be guessed
+          if let Some(filter) = condition_and_content.cloned::<EmptinessFilterPB>() {
+            let (_, flag) = filter.get_compliant_cell(field);
+            open_after_create = open_after_create || flag;
+            continue;
+          }
+
           let (cell, flag) = match field_type {
             FieldType::RichText | FieldType::URL => {
               let filter = condition_and_content.cloned::<TextFilterPB>().unwrap();
@@ -341,6 +365,7 @@ impl FilterController {
 
     if let Some((_, row_detail)) = self.delegate.get_row(&self.view_id, &row_id).await {
       let field_by_field_id = self.get_field_map().await;
+      let row_users = self.get_row_users(&filters);
       let mut notification = FilterResultNotification::new(self.view_id.clone());
       if let Some(is_visible) = filter_row(
         &row_detail.row,
@@ -348,6 +373,7 @@ impl FilterController {
         &field_by_field_id,
         &self.cell_cache,
         &filters,
+        &row_users,
       ) {
         if is_visible {
           if let Some((index, _row)) = self.delegate.get_row(&self.view_id, &row_id).await {
@@ -371,6 +397,7 @@ impl FilterController {
     let filters = self.filters.read().await;
 
     let field_by_field_id = self.get_field_map().await;
+    let row_users = self.get_row_users(&filters);
     let mut visible_rows = vec![];
     let mut invisible_rows = vec![];
 
@@ -387,6 +414,7 @@ impl FilterController {
         &field_by_field_id,
         &self.cell_cache,
         &filters,
+        &row_users,
       ) {
         if is_visible {
           let row_meta = RowMetaPB::from(row_detail.as_ref());
@@ -410,6 +438,16 @@ impl FilterController {
     Ok(())
   }
 
+  /// Returns the users of the rows if one of the filters is on a user field. They're read once for
+  /// all the filtered rows.
+  fn get_row_users(&self, filters: &[Filter]) -> RowUsersByRowId {
+    if filters.iter().any(is_user_filter) {
+      self.delegate.get_row_users()
+    } else {
+      RowUsersByRowId::new()
+    }
+  }
+
   async fn get_field_map(&self) -> HashMap<String, Field> {
     self
       .delegate
@@ -454,6 +492,7 @@ fn filter_row(
   field_by_field_id: &HashMap<String, Field>,
   cell_data_cache: &CellCache,
   filters: &Vec<Filter>,
+  row_users: &RowUsersByRowId,
 ) -> Option<bool> {
   // Create a filter result cache if it doesn't exist
   let mut filter_result = result_by_row_id.entry(row.id.clone()).or_insert(true);
@@ -462,7 +501,9 @@ fn filter_row(
   let mut new_is_visible = true;
 
   for filter in filters {
-    if let Some(is_visible) = apply_filter(row, field_by_field_id, cell_data_cache, filter) {
+    if let Some(is_visible) =
+      apply_filter(row, field_by_field_id, cell_data_cache, filter, row_users)
+    {
       new_is_visible = new_is_visible && is_visible;
 
       // short-circuit as soon as one filter tree returns false
@@ -481,12 +522,23 @@ fn filter_row(
   }
 }
 
+/// Returns true if the filter or one of its children is on a user field.
+fn is_user_filter(filter: &Filter) -> bool {
+  match &filter.inner {
+    FilterInner::And { children } | FilterInner::Or { children } => {
+      children.iter().any(is_user_filter)
+    },
+    FilterInner::Data { field_type, .. } => field_type.is_user(),
+  }
+}
+
 /// Recursively applies a `Filter` to a `Row`'s cells.
 fn apply_filter(
   row: &Row,
   field_by_field_id: &HashMap<String, Field>,
   cell_data_cache: &CellCache,
   filter: &Filter,
+  row_users: &RowUsersByRowId,
 ) -> Option<bool> {
   match &filter.inner {
     FilterInner::And { children } => {
@@ -494,7 +546,13 @@ fn apply_filter(
         return None;
       }
       for child_filter in children.iter() {
-        if let Some(false) = apply_filter(row, field_by_field_id, cell_data_cache, child_filter) {
+        if let Some(false) = apply_filter(
+          row,
+          field_by_field_id,
+          cell_data_cache,
+          child_filter,
+          row_users,
+        ) {
           return Some(false);
         }
       }
@@ -505,7 +563,13 @@ fn apply_filter(
         return None;
       }
       for child_filter in children.iter() {
-        if let Some(true) = apply_filter(row, field_by_field_id, cell_data_cache, child_filter) {
+        if let Some(true) = apply_filter(
+          row,
+          field_by_field_id,
+          cell_data_cache,
+          child_filter,
+          row_users,
+        ) {
           return Some(true);
         }
       }
@@ -527,11 +591,19 @@ fn apply_filter(
         tracing::error!("field type of filter doesn't match field type of field");
         return Some(false);
       }
-      let cell = row.cells.get(field_id).cloned();
+      let cell = if field_type.is_user() {
+        user_cell_of_row(row_users, &row.id, *field_type)
+      } else {
+        row.cells.get(field_id).cloned()
+      };
       if let Some(handler) = TypeOptionCellExt::new(field, Some(cell_data_cache.clone()))
         .get_type_option_cell_data_handler()
       {
-        Some(handler.handle_cell_filter(field, &cell.unwrap_or_default(), condition_and_content))
+        let cell = cell.unwrap_or_default();
+        if let Some(emptiness) = condition_and_content.downcast_ref::<EmptinessFilterPB>() {
+          return Some(emptiness.is_visible(handler.handle_is_cell_empty(&cell, field)));
+        }
+        Some(handler.handle_cell_filter(field, &cell, condition_and_content))
       } else {
         Some(true)
       }
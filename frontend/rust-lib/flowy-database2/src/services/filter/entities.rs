@@ -10,8 +10,9 @@ use flowy_error::{FlowyError, FlowyResult};
 use lib_infra::box_any::BoxAny;
 
 use crate::entities::{
-  CheckboxFilterPB, ChecklistFilterPB, DateFilterContent, DateFilterPB, FieldType, FilterType,
-  InsertedRowPB, NumberFilterPB, RelationFilterPB, SelectOptionFilterPB, TextFilterPB,
+  CheckboxFilterPB, ChecklistFilterPB, DateFilterContent, DateFilterPB, EmptinessFilterConditionPB,
+  EmptinessFilterPB, FieldType, FilterType, InsertedRowPB, NumberFilterPB, RelationFilterPB,
+  SelectOptionFilterPB, TextFilterPB,
 };
 use crate::services::field::SelectOptionIds;
 
@@ -226,6 +227,101 @@ impl Filter {
     }
   }
 
+  /// Recursively gives the filter and all of its children new ids, e.g. when the filter is copied
+  /// to another view.
+  pub fn regenerate_ids(&mut self) {
+    self.id = gen_database_filter_id();
+    if let FilterInner::And { children } | FilterInner::Or { children } = &mut self.inner {
+      for child in children.iter_mut() {
+        child.regenerate_ids();
+      }
+    }
+  }
+
+  /// Recursively replace the `field_id` of every Data filter using `new_field_ids`, which maps an
+  /// old field id to its new one. Field ids that aren't in the map are left untouched.
+  pub fn replace_field_ids(&mut self, new_field_ids: &HashMap<String, String>) {
+    match &mut self.inner {
+      FilterInner::And { children } | FilterInner::Or { children } => {
+        for child in children.iter_mut() {
+          child.replace_field_ids(new_field_ids);
+        }
+      },
+      FilterInner::Data { field_id, .. } => {
+        if let Some(new_field_id) = new_field_ids.get(field_id) {
+          *field_id = new_field_id.clone();
+        }
+      },
+    }
+  }
+
+  /// Recursively replaces the option ids of the select option filters with the ids returned by
+  /// `resolve`, which is given the field id and the option id. The options that `resolve` returns
+  /// None for are removed and appended to `removed_options` with the id of their field.
+  pub fn remap_select_option_ids<F>(
+    &mut self,
+    resolve: &F,
+    removed_options: &mut Vec<(String, String)>,
+  ) where
+    F: Fn(&str, &str) -> Option<String>,
+  {
+    match &mut self.inner {
+      FilterInner::And { children } | FilterInner::Or { children } => {
+        for child in children.iter_mut() {
+          child.remap_select_option_ids(resolve, removed_options);
+        }
+      },
+      FilterInner::Data {
+        field_id,
+        condition_and_content,
+        ..
+      } => {
+        if let Some(mut filter) = condition_and_content.cloned::<SelectOptionFilterPB>() {
+          filter.option_ids = filter
+            .option_ids
+            .into_iter()
+            .filter_map(|option_id| {
+              let new_option_id = resolve(field_id, &option_id);
+              if new_option_id.is_none() {
+                removed_options.push((field_id.clone(), option_id));
+              }
+              new_option_id
+            })
+            .collect();
+          *condition_and_content = BoxAny::new(filter);
+        }
+      },
+    }
+  }
+
+  /// Recursively remove the Data filters whose `field_id` doesn't satisfy `predicate`. The field
+  /// ids of the removed filters are appended to the `removed_field_ids` vector. The current filter
+  /// is kept even if it's a Data filter, the caller is expected to check it.
+  pub fn retain_filters<F>(&mut self, predicate: &F, removed_field_ids: &mut Vec<String>)
+  where
+    F: Fn(&str) -> bool,
+  {
+    if let FilterInner::And { children } | FilterInner::Or { children } = &mut self.inner {
+      children.retain_mut(|child| {
+        let field_id = match &child.inner {
+          FilterInner::Data { field_id, .. } => Some(field_id.clone()),
+          _ => None,
+        };
+        match field_id {
+          Some(field_id) if !predicate(&field_id) => {
+            removed_field_ids.push(field_id);
+            false
+          },
+          Some(_) => true,
+          None => {
+            child.retain_filters(predicate, removed_field_ids);
+            true
+          },
+        }
+      });
+    }
+  }
+
   /// Recursively get all of the filtering field ids and the associated filter_ids
   pub fn get_all_filtering_field_ids(&self, field_ids: &mut HashMap<String, Vec<String>>) {
     match &self.inner {
@@ -280,7 +376,9 @@ impl FilterInner {
       FieldType::Checklist => BoxAny::new(ChecklistFilterPB::parse(condition as u8, content)),
       FieldType::Checkbox => BoxAny::new(CheckboxFilterPB::parse(condition as u8, content)),
       FieldType::Relation => BoxAny::new(RelationFilterPB::parse(condition as u8, content)),
-      FieldType::Summary => BoxAny::new(TextFilterPB::parse(condition as u8, content)),
+      FieldType::Summary | FieldType::LastEditedBy | FieldType::CreatedBy => {
+        BoxAny::new(TextFilterPB::parse(condition as u8, content))
+      },
     };
 
     FilterInner::Data {
@@ -290,6 +388,17 @@ impl FilterInner {
     }
   }
 
+  /// Creates a data filter that checks whether the cells of the field are empty, whatever the
+  /// field type is.
+  pub fn new_emptiness(field_id: String, field_type: FieldType, condition: i64) -> Self {
+    let condition = EmptinessFilterConditionPB::try_from(condition as u8).unwrap_or_default();
+    FilterInner::Data {
+      field_id,
+      field_type,
+      condition_and_content: BoxAny::new(EmptinessFilterPB { condition }),
+    }
+  }
+
   pub fn get_int_repr(&self) -> i64 {
     match self {
       FilterInner::And { .. } => FILTER_AND_INDEX,
@@ -306,6 +415,7 @@ const FIELD_TYPE: &str = "ty";
 const FILTER_CONDITION: &str = "condition";
 const FILTER_CONTENT: &str = "content";
 const FILTER_CHILDREN: &str = "children";
+const FILTER_EMPTINESS: &str = "emptiness";
 
 const FILTER_AND_INDEX: i64 = 0;
 const FILTER_OR_INDEX: i64 = 1;
@@ -321,6 +431,20 @@ impl<'a> From<&'a Filter> for FilterMap {
       FilterInner::And { children } | FilterInner::Or { children } => {
         builder.insert_maps(FILTER_CHILDREN, children.iter().collect::<Vec<&Filter>>())
       },
+      FilterInner::Data {
+        field_id,
+        field_type,
+        condition_and_content,
+      } if condition_and_content
+        .downcast_ref::<EmptinessFilterPB>()
+        .is_some() =>
+      {
+        let filter = condition_and_content.cloned::<EmptinessFilterPB>().unwrap();
+        builder
+          .insert_str_value(FIELD_ID, field_id)
+          .insert_i64_value(FIELD_TYPE, field_type.into())
+          .insert_i64_value(FILTER_EMPTINESS, filter.condition as i64)
+      },
       FilterInner::Data {
         field_id,
         field_type,
@@ -363,7 +487,7 @@ impl<'a> From<&'a Filter> for FilterMap {
               let filter = condition_and_content.cloned::<RelationFilterPB>()?;
               (filter.condition as u8, "".to_string())
             },
-            FieldType::Summary => {
+            FieldType::Summary | FieldType::LastEditedBy | FieldType::CreatedBy => {
               let filter = condition_and_content.cloned::<TextFilterPB>()?;
               (filter.condition as u8, filter.content)
             },
@@ -416,10 +540,14 @@ impl TryFrom<FilterMap> for Filter {
             .get_i64_value(FIELD_TYPE)
             .map(FieldType::from)
             .unwrap_or_default();
-          let condition = filter_map.get_i64_value(FILTER_CONDITION).unwrap_or(0);
-          let content = filter_map.get_str_value(FILTER_CONTENT).unwrap_or_default();
+          if let Some(condition) = filter_map.get_i64_value(FILTER_EMPTINESS) {
+            FilterInner::new_emptiness(field_id, field_type, condition)
+          } else {
+            let condition = filter_map.get_i64_value(FILTER_CONDITION).unwrap_or(0);
+            let content = filter_map.get_str_value(FILTER_CONTENT).unwrap_or_default();
 
-          FilterInner::new_data(field_id, field_type, condition, content)
+            FilterInner::new_data(field_id, field_type, condition, content)
+          }
         },
         _ => bail!("Unsupported filter type"),
       },
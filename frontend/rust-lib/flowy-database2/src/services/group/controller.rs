@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::Arc;
 
@@ -41,6 +42,10 @@ pub trait GroupControllerDelegate: Send + Sync + 'static {
 pub struct BaseGroupController<C, G, P> {
   pub grouping_field_id: String,
   pub context: GroupControllerContext<C>,
+  /// The ids of the groups that contain the rows with a given value of the grouping field. The
+  /// rows of a board usually share a few values, so the groups of a value are only matched once.
+  /// It's cleared whenever the groups change.
+  group_ids_by_cell_value: HashMap<String, Vec<String>>,
   group_builder_phantom: PhantomData<G>,
   cell_parser_phantom: PhantomData<P>,
   pub delegate: Arc<dyn GroupControllerDelegate>,
@@ -69,6 +74,7 @@ where
     Ok(Self {
       grouping_field_id: grouping_field.id.clone(),
       context: configuration,
+      group_ids_by_cell_value: HashMap::new(),
       group_builder_phantom: PhantomData,
       cell_parser_phantom: PhantomData,
       delegate,
@@ -154,6 +160,38 @@ where
   }
 }
 
+impl<C, T, G, P> BaseGroupController<C, G, P>
+where
+  C: Serialize + DeserializeOwned,
+  T: TypeOption + Send + Sync,
+  G: GroupsBuilder<Context = GroupControllerContext<C>, GroupTypeOption = T>,
+  Self: GroupCustomize<GroupTypeOption = T>,
+{
+  /// Returns the ids of the groups that contain a row with the cell data.
+  fn group_ids_of_cell_data(&mut self, cell_data: &<T as TypeOption>::CellData) -> Vec<String> {
+    let cell_value = cell_data.to_string();
+    if let Some(group_ids) = self.group_ids_by_cell_value.get(&cell_value) {
+      return group_ids.clone();
+    }
+
+    let group_ids = self
+      .context
+      .groups()
+      .into_iter()
+      .filter(|group| self.can_group(&group.id, cell_data))
+      .map(|group| group.id.clone())
+      .collect::<Vec<_>>();
+    self
+      .group_ids_by_cell_value
+      .insert(cell_value, group_ids.clone());
+    group_ids
+  }
+
+  fn did_change_groups(&mut self) {
+    self.group_ids_by_cell_value.clear();
+  }
+}
+
 impl<C, T, G, P> GroupController for BaseGroupController<C, G, P>
 where
   P: CellProtobufBlobParser<Object = <T as TypeOption>::CellProtobufType>,
@@ -184,21 +222,12 @@ where
       };
 
       if let Some(cell) = cell {
-        let mut grouped_rows: Vec<GroupedRow> = vec![];
         let cell_data = <T as TypeOption>::CellData::from(&cell);
-        for group in self.context.groups() {
-          if self.can_group(&group.id, &cell_data) {
-            grouped_rows.push(GroupedRow {
-              row_detail: (*row_detail).clone(),
-              group_id: group.id.clone(),
-            });
-          }
-        }
-
-        if !grouped_rows.is_empty() {
-          for group_row in grouped_rows {
-            if let Some(group) = self.context.get_mut_group(&group_row.group_id) {
-              group.add_row(group_row.row_detail);
+        let group_ids = self.group_ids_of_cell_data(&cell_data);
+        if !group_ids.is_empty() {
+          for group_id in group_ids {
+            if let Some(group) = self.context.get_mut_group(&group_id) {
+              group.add_row((*row_detail).clone());
             }
           }
           continue;
@@ -219,6 +248,7 @@ where
     &mut self,
     name: String,
   ) -> FlowyResult<(Option<TypeOptionData>, Option<InsertedGroupPB>)> {
+    self.did_change_groups();
     <Self as GroupCustomize>::create_group(self, name)
   }
 
@@ -241,21 +271,17 @@ where
     if let Some(cell) = cell {
       let cell_data = <T as TypeOption>::CellData::from(&cell);
 
-      let mut suitable_group_ids = vec![];
-
-      for group in self.get_all_groups() {
-        if self.can_group(&group.id, &cell_data) {
-          suitable_group_ids.push(group.id.clone());
-          let changeset = GroupRowsNotificationPB::insert(
-            group.id.clone(),
-            vec![InsertedRowPB {
-              row_meta: (*row_detail).clone().into(),
-              index: Some(index as i32),
-              is_new: true,
-            }],
-          );
-          changesets.push(changeset);
-        }
+      let suitable_group_ids = self.group_ids_of_cell_data(&cell_data);
+      for group_id in suitable_group_ids.iter() {
+        let changeset = GroupRowsNotificationPB::insert(
+          group_id.clone(),
+          vec![InsertedRowPB {
+            row_meta: (*row_detail).clone().into(),
+            index: Some(index as i32),
+            is_new: true,
+          }],
+        );
+        changesets.push(changeset);
       }
       if !suitable_group_ids.is_empty() {
         for group_id in suitable_group_ids.iter() {
@@ -299,6 +325,9 @@ where
         old_cell_data.as_ref(),
         &cell_data,
       ) {
+        if insert.is_some() || delete.is_some() {
+          self.did_change_groups();
+        }
         result.inserted_group = insert;
         result.deleted_group = delete;
       }
@@ -325,6 +354,9 @@ where
       let cell_data = <T as TypeOption>::CellData::from(cell);
       if !cell_data.is_cell_empty() {
         (result.deleted_group, result.row_changesets) = self.delete_row(row, &cell_data);
+        if result.deleted_group.is_some() {
+          self.did_change_groups();
+        }
         return Ok(result);
       }
     }
@@ -362,6 +394,9 @@ where
       let cell_bytes = get_cell_protobuf(&cell, context.field, None);
       let cell_data = cell_bytes.parser::<P>()?;
       result.deleted_group = self.delete_group_when_move_row(&context.row_detail.row, &cell_data);
+      if result.deleted_group.is_some() {
+        self.did_change_groups();
+      }
       result.row_changesets = self.move_row(context);
     } else {
       tracing::warn!("Unexpected moving group row, changes should not be empty");
@@ -370,6 +405,7 @@ where
   }
 
   fn did_update_group_field(&mut self, _field: &Field) -> FlowyResult<Option<GroupChangesPB>> {
+    self.did_change_groups();
     Ok(None)
   }
 
@@ -387,6 +423,7 @@ where
           .iter()
           .map(|row| row.row.id.clone())
           .collect();
+        self.did_change_groups();
         let type_option_data = <Self as GroupCustomize>::delete_group(self, group_id)?;
         Ok((row_ids, type_option_data))
       },
@@ -398,6 +435,7 @@ where
     &mut self,
     changeset: &[GroupChangeset],
   ) -> FlowyResult<(Vec<GroupPB>, Option<TypeOptionData>)> {
+    self.did_change_groups();
     // update group visibility
     for group_changeset in changeset.iter() {
       self.context.update_group(group_changeset)?;
@@ -439,11 +477,6 @@ where
   }
 }
 
-struct GroupedRow {
-  row_detail: RowDetail,
-  group_id: String,
-}
-
 fn get_cell_data_from_row<P: CellProtobufBlobParser>(
   row: Option<&Row>,
   field: &Field,
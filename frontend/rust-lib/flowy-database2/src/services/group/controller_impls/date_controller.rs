@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Datelike, Days, Duration, Local, NaiveDateTime};
+use chrono::{DateTime, Datelike, Days, Duration, Local, NaiveDate, NaiveDateTime};
 use collab_database::database::timestamp;
 use collab_database::fields::{Field, TypeOptionData};
 use collab_database::rows::{new_cell_builder, Cell, Cells, Row, RowDetail};
@@ -53,6 +53,15 @@ pub type DateGroupController =
 
 pub type DateGroupControllerContext = GroupControllerContext<DateGroupConfiguration>;
 
+impl DateGroupController {
+  /// Returns the id of the group that the date belongs to. The date is bucketed in the timezone
+  /// of the grouping field.
+  fn group_id_of(&self, cell_data: &DateCellData) -> String {
+    let type_option = self.get_grouping_field_type_option().unwrap_or_default();
+    get_date_group_id(cell_data, &type_option, &self.context.get_setting_content())
+  }
+}
+
 impl GroupCustomize for DateGroupController {
   type GroupTypeOption = DateTypeOption;
 
@@ -69,7 +78,7 @@ impl GroupCustomize for DateGroupController {
     content: &str,
     cell_data: &<Self::GroupTypeOption as TypeOption>::CellData,
   ) -> bool {
-    content == get_date_group_id(cell_data, &self.context.get_setting_content())
+    content == self.group_id_of(cell_data)
   }
 
   fn create_or_delete_group_when_cell_changed(
@@ -78,25 +87,20 @@ impl GroupCustomize for DateGroupController {
     _old_cell_data: Option<&<Self::GroupTypeOption as TypeOption>::CellProtobufType>,
     _cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
   ) -> FlowyResult<(Option<InsertedGroupPB>, Option<GroupPB>)> {
-    let setting_content = self.context.get_setting_content();
     let mut inserted_group = None;
-    if self
-      .context
-      .get_group(&get_date_group_id(&_cell_data.into(), &setting_content))
-      .is_none()
-    {
-      let group = make_group_from_date_cell(&_cell_data.into(), &setting_content);
-      let mut new_group = self.context.add_new_group(group)?;
+    let group_id = self.group_id_of(&_cell_data.into());
+    if self.context.get_group(&group_id).is_none() {
+      let mut new_group = self.context.add_new_group(Group::new(group_id))?;
       new_group.group.rows.push(RowMetaPB::from(_row_detail));
       inserted_group = Some(new_group);
     }
 
     // Delete the old group if there are no rows in that group
-    let deleted_group = match _old_cell_data.and_then(|old_cell_data| {
-      self
-        .context
-        .get_group(&get_date_group_id(&old_cell_data.into(), &setting_content))
-    }) {
+    let old_group_id = _old_cell_data.map(|old_cell_data| self.group_id_of(&old_cell_data.into()));
+    let deleted_group = match old_group_id
+      .as_ref()
+      .and_then(|group_id| self.context.get_group(group_id))
+    {
       None => None,
       Some((_, group)) => {
         if group.rows.len() == 1 {
@@ -124,10 +128,10 @@ impl GroupCustomize for DateGroupController {
     cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
   ) -> Vec<GroupRowsNotificationPB> {
     let mut changesets = vec![];
-    let setting_content = self.context.get_setting_content();
+    let group_id = self.group_id_of(&cell_data.into());
     self.context.iter_mut_status_groups(|group| {
       let mut changeset = GroupRowsNotificationPB::new(group.id.clone());
-      if group.id == get_date_group_id(&cell_data.into(), &setting_content) {
+      if group.id == group_id {
         if !group.contains_row(&row_detail.row.id) {
           changeset
             .inserted_rows
@@ -166,11 +170,8 @@ impl GroupCustomize for DateGroupController {
       }
     });
 
-    let setting_content = self.context.get_setting_content();
-    let deleted_group = match self
-      .context
-      .get_group(&get_date_group_id(cell_data, &setting_content))
-    {
+    let group_id = self.group_id_of(cell_data);
+    let deleted_group = match self.context.get_group(&group_id) {
       Some((_, group)) if group.rows.len() == 1 => Some(group.clone()),
       _ => None,
     };
@@ -199,11 +200,8 @@ impl GroupCustomize for DateGroupController {
     cell_data: &<Self::GroupTypeOption as TypeOption>::CellProtobufType,
   ) -> Option<GroupPB> {
     let mut deleted_group = None;
-    let setting_content = self.context.get_setting_content();
-    if let Some((_, group)) = self
-      .context
-      .get_group(&get_date_group_id(&cell_data.into(), &setting_content))
-    {
+    let group_id = self.group_id_of(&cell_data.into());
+    if let Some((_, group)) = self.context.get_group(&group_id) {
       if group.rows.len() == 1 {
         deleted_group = Some(GroupPB::from(group.clone()));
       }
@@ -222,10 +220,19 @@ impl GroupCustomize for DateGroupController {
   fn will_create_row(&self, cells: &mut Cells, field: &Field, group_id: &str) {
     match self.context.get_group(group_id) {
       None => tracing::warn!("Can not find the group: {}", group_id),
-      Some((_, _)) => {
-        let date = DateTime::parse_from_str(group_id, GROUP_ID_DATE_FORMAT).unwrap();
-        let cell = insert_date_cell(date.timestamp(), None, Some(false), field);
-        cells.insert(field.id.clone(), cell);
+      Some((_, _)) => match NaiveDate::parse_from_str(group_id, GROUP_ID_DATE_FORMAT) {
+        Ok(date) => {
+          // The group id is the first day of the period in the timezone of the field
+          let type_option = field
+            .get_type_option::<DateTypeOption>(FieldType::from(field.field_type))
+            .unwrap_or_default();
+          let naive = date.and_hms_opt(0, 0, 0).unwrap();
+          let offset = type_option.get_timezone_offset(naive);
+          let timestamp = naive.timestamp() - offset.local_minus_utc() as i64;
+          let cell = insert_date_cell(timestamp, None, Some(false), field);
+          cells.insert(field.id.clone(), cell);
+        },
+        Err(err) => tracing::warn!("Invalid date group id: {}, error: {}", group_id, err),
       },
     }
   }
@@ -240,17 +247,18 @@ impl GroupsBuilder for DateGroupBuilder {
   async fn build(
     field: &Field,
     context: &Self::Context,
-    _type_option: &Self::GroupTypeOption,
+    type_option: &Self::GroupTypeOption,
   ) -> GeneratedGroups {
     // Read all the cells for the grouping field
     let cells = context.get_all_cells().await;
 
-    // Generate the groups
+    // Generate the groups. The rows without a date are put into the no status group
+    let setting_content = context.get_setting_content();
     let mut groups: Vec<Group> = cells
       .into_iter()
       .flat_map(|value| value.into_date_field_cell_data())
       .filter(|cell| cell.timestamp.is_some())
-      .map(|cell| make_group_from_date_cell(&cell, &context.get_setting_content()))
+      .map(|cell| Group::new(get_date_group_id(&cell, type_option, &setting_content)))
       .collect();
     groups.sort_by(|a, b| a.id.cmp(&b.id));
 
@@ -263,16 +271,18 @@ impl GroupsBuilder for DateGroupBuilder {
   }
 }
 
-fn make_group_from_date_cell(cell_data: &DateCellData, setting_content: &str) -> Group {
-  let group_id = get_date_group_id(cell_data, setting_content);
-  Group::new(group_id)
-}
-
 const GROUP_ID_DATE_FORMAT: &str = "%Y/%m/%d";
 
-fn get_date_group_id(cell_data: &DateCellData, setting_content: &str) -> String {
+/// Returns the first day of the period that the date belongs to, formatted with
+/// [GROUP_ID_DATE_FORMAT]. The period is computed in the timezone of the `type_option`, so the
+/// dates around midnight are bucketed the same way as they are displayed.
+fn get_date_group_id(
+  cell_data: &DateCellData,
+  type_option: &DateTypeOption,
+  setting_content: &str,
+) -> String {
   let config = DateGroupConfiguration::from_json(setting_content).unwrap_or_default();
-  let date_time = date_time_from_timestamp(cell_data.timestamp);
+  let date_time = date_time_from_timestamp(cell_data.timestamp, type_option);
 
   let date_format = GROUP_ID_DATE_FORMAT;
   let month_format = &date_format.replace("%d", "01");
@@ -287,7 +297,7 @@ fn get_date_group_id(cell_data: &DateCellData, setting_content: &str) -> String
       .unwrap()
       .format(date_format),
     DateCondition::Relative => {
-      let now = date_time_from_timestamp(Some(timestamp())).date_naive();
+      let now = date_time_from_timestamp(Some(timestamp()), type_option).date_naive();
       let date_time = date_time.date_naive();
 
       let diff = date_time.signed_duration_since(now).num_days();
@@ -327,11 +337,14 @@ fn get_date_group_id(cell_data: &DateCellData, setting_content: &str) -> String
   date.to_string()
 }
 
-fn date_time_from_timestamp(timestamp: Option<i64>) -> DateTime<Local> {
+fn date_time_from_timestamp(
+  timestamp: Option<i64>,
+  type_option: &DateTypeOption,
+) -> DateTime<Local> {
   match timestamp {
     Some(timestamp) => {
       let naive = NaiveDateTime::from_timestamp_opt(timestamp, 0).unwrap();
-      let offset = *Local::now().offset();
+      let offset = type_option.get_timezone_offset(naive);
 
       DateTime::<Local>::from_naive_utc_and_offset(naive, offset)
     },
@@ -449,8 +462,58 @@ mod tests {
     ];
 
     for (i, test) in tests.iter().enumerate() {
-      let group_id = get_date_group_id(&test.cell_data, &test.setting_content);
+      let group_id = get_date_group_id(
+        &test.cell_data,
+        &default_date_type_option,
+        &test.setting_content,
+      );
       assert_eq!(test.exp_group_id, group_id, "test {}", i);
     }
   }
+
+  #[test]
+  fn group_id_timezone_boundary_test() {
+    let date_type_option = |timezone_id: &str| DateTypeOption {
+      timezone_id: timezone_id.to_string(),
+      ..Default::default()
+    };
+    let cell_data = |timestamp: i64| DateCellData {
+      timestamp: Some(timestamp),
+      include_time: true,
+      ..Default::default()
+    };
+    let setting_content =
+      |condition: u8| format!(r#"{{"condition": {}, "hide_empty": false}}"#, condition);
+
+    // 2023/12/31 23:30 in UTC, which is already 2024/01/01 in Shanghai
+    let new_year_eve = cell_data(1704065400);
+    let utc = date_type_option("Etc/UTC");
+    let shanghai = date_type_option("Asia/Shanghai");
+    let tests = vec![
+      (&utc, 1, "2023/12/31"),
+      (&utc, 2, "2023/12/25"),
+      (&utc, 3, "2023/12/01"),
+      (&utc, 4, "2023/01/01"),
+      (&shanghai, 1, "2024/01/01"),
+      (&shanghai, 2, "2024/01/01"),
+      (&shanghai, 3, "2024/01/01"),
+      (&shanghai, 4, "2024/01/01"),
+    ];
+    for (i, (type_option, condition, exp_group_id)) in tests.into_iter().enumerate() {
+      let group_id = get_date_group_id(&new_year_eve, type_option, &setting_content(condition));
+      assert_eq!(exp_group_id, group_id, "test {}", i);
+    }
+
+    // 2024/02/01 03:00 in UTC, which is still January in New York
+    let first_of_february = cell_data(1706756400);
+    let new_york = date_type_option("America/New_York");
+    assert_eq!(
+      get_date_group_id(&first_of_february, &utc, &setting_content(3)),
+      "2024/02/01"
+    );
+    assert_eq!(
+      get_date_group_id(&first_of_february, &new_york, &setting_content(3)),
+      "2024/01/01"
+    );
+  }
 }
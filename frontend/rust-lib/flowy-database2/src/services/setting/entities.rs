@@ -123,3 +123,52 @@ impl From<BoardLayoutSetting> for LayoutSetting {
       .build()
   }
 }
+
+/// Where the rows that are created without a position are inserted in a view. It's stored in the
+/// grid layout setting of the view, so it's kept when the layout of the view is changed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum NewRowPosition {
+  #[default]
+  Bottom = 0,
+  Top = 1,
+}
+
+impl From<i64> for NewRowPosition {
+  fn from(value: i64) -> Self {
+    match value {
+      1 => NewRowPosition::Top,
+      _ => NewRowPosition::Bottom,
+    }
+  }
+}
+
+impl NewRowPosition {
+  pub fn value(&self) -> i64 {
+    *self as i64
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GridLayoutSetting {
+  pub new_row_position: NewRowPosition,
+}
+
+impl From<LayoutSetting> for GridLayoutSetting {
+  fn from(setting: LayoutSetting) -> Self {
+    Self {
+      new_row_position: setting
+        .get_i64_value("new_row_position")
+        .map(NewRowPosition::from)
+        .unwrap_or_default(),
+    }
+  }
+}
+
+impl From<GridLayoutSetting> for LayoutSetting {
+  fn from(setting: GridLayoutSetting) -> Self {
+    LayoutSettingBuilder::new()
+      .insert_i64_value("new_row_position", setting.new_row_position.value())
+      .build()
+  }
+}
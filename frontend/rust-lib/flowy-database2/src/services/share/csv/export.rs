@@ -1,13 +1,22 @@
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
 use collab_database::database::Database;
 use collab_database::fields::Field;
-use collab_database::rows::Cell;
+use collab_database::rows::{Cell, Row};
 use indexmap::IndexMap;
+use sha2::{Digest, Sha256};
 
 use flowy_error::{FlowyError, FlowyResult};
 
 use crate::entities::FieldType;
 use crate::services::cell::stringify_cell;
-use crate::services::field::{TimestampCellData, TimestampCellDataWrapper};
+use crate::services::database::get_all_row_users;
+use crate::services::field::{
+  user_cell_of_row, RowUsersByRowId, TimestampCellData, TimestampCellDataWrapper,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum CSVFormat {
@@ -19,21 +28,241 @@ pub enum CSVFormat {
   META,
 }
 
+/// Decides which values are wrapped in quotes when exporting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CSVQuoteStyle {
+  /// Only the values that contain the delimiter, a quote or a line break are quoted.
+  #[default]
+  Necessary,
+  Always,
+  NonNumeric,
+  /// None of the values are quoted. The export fails if a value contains the delimiter.
+  Never,
+}
+
+impl From<CSVQuoteStyle> for csv::QuoteStyle {
+  fn from(style: CSVQuoteStyle) -> Self {
+    match style {
+      CSVQuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+      CSVQuoteStyle::Always => csv::QuoteStyle::Always,
+      CSVQuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+      CSVQuoteStyle::Never => csv::QuoteStyle::Never,
+    }
+  }
+}
+
+/// The headers of the columns that are exported with [CSVExportOptions::include_row_metadata].
+/// The importer recognizes them, so the exported rows can be updated by importing the CSV again.
+pub const ROW_ID_COLUMN: &str = "__row_id";
+pub const CREATED_AT_COLUMN: &str = "__created_at";
+pub const MODIFIED_AT_COLUMN: &str = "__modified_at";
+pub const ROW_METADATA_COLUMNS: [&str; 3] = [ROW_ID_COLUMN, CREATED_AT_COLUMN, MODIFIED_AT_COLUMN];
+
+#[derive(Debug, Clone)]
+pub struct CSVExportOptions {
+  /// Some locales, e.g. most of Europe, use `;` because `,` is their decimal separator.
+  pub delimiter: char,
+  pub quote_style: CSVQuoteStyle,
+  /// Prepends the [ROW_METADATA_COLUMNS], i.e. the id, the creation time and the last modified
+  /// time of the rows. The times are Unix timestamps in seconds.
+  pub include_row_metadata: bool,
+}
+
+impl Default for CSVExportOptions {
+  fn default() -> Self {
+    Self {
+      delimiter: ',',
+      quote_style: CSVQuoteStyle::default(),
+      include_row_metadata: false,
+    }
+  }
+}
+
+/// Returns the delimiter as a byte if it can separate the values: an ASCII character that isn't a
+/// quote or a line break.
+pub(crate) fn validate_delimiter(delimiter: char) -> FlowyResult<u8> {
+  if !delimiter.is_ascii() || matches!(delimiter, '"' | '\n' | '\r') {
+    let msg = format!("Invalid CSV delimiter: {:?}", delimiter);
+    return Err(FlowyError::invalid_data().with_context(msg));
+  }
+  Ok(delimiter as u8)
+}
+
+/// Writes to the inner writer and hashes the written bytes with SHA-256, so the checksum of an
+/// export is computed while it's written, without buffering the whole export.
+pub(crate) struct Sha256Writer<W> {
+  inner: W,
+  hasher: Sha256,
+}
+
+impl<W: Write> Sha256Writer<W> {
+  pub(crate) fn new(inner: W) -> Self {
+    Self {
+      inner,
+      hasher: Sha256::new(),
+    }
+  }
+
+  /// Returns the inner writer and the hex encoded checksum of the written bytes.
+  pub(crate) fn finish(self) -> (W, String) {
+    (self.inner, format!("{:x}", self.hasher.finalize()))
+  }
+}
+
+impl<W: Write> Write for Sha256Writer<W> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    let n = self.inner.write(buf)?;
+    self.hasher.update(&buf[..n]);
+    Ok(n)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+/// Returns the path of the file that stores the checksum of the export at `path`, i.e. the path
+/// with the `.sha256` suffix.
+pub fn checksum_file_path(path: &Path) -> PathBuf {
+  let mut checksum_path = OsString::from(path.as_os_str());
+  checksum_path.push(".sha256");
+  PathBuf::from(checksum_path)
+}
+
 pub struct CSVExport;
 impl CSVExport {
-  pub fn export_database(&self, database: &Database, style: CSVFormat) -> FlowyResult<String> {
-    let mut wtr = csv::Writer::from_writer(vec![]);
+  pub fn export_database(
+    &self,
+    database: &Database,
+    style: CSVFormat,
+    options: &CSVExportOptions,
+  ) -> FlowyResult<String> {
+    let data = self.export_database_to_writer(database, style, options, vec![])?;
+    let csv = String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))?;
+    Ok(csv)
+  }
+
+  /// Same as [Self::export_database], but also returns the hex encoded SHA-256 checksum of the
+  /// CSV.
+  pub fn export_database_with_checksum(
+    &self,
+    database: &Database,
+    style: CSVFormat,
+    options: &CSVExportOptions,
+  ) -> FlowyResult<(String, String)> {
+    let writer = Sha256Writer::new(vec![]);
+    let (data, checksum) = self
+      .export_database_to_writer(database, style, options, writer)?
+      .finish();
+    let csv = String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))?;
+    Ok((csv, checksum))
+  }
+
+  /// Writes the CSV to `path` and returns its hex encoded SHA-256 checksum. The rows are written
+  /// to the file as they're exported. If `write_checksum_file` is true, the checksum is also
+  /// written to [checksum_file_path] in the format of `sha256sum`, so the export can be verified
+  /// with `sha256sum -c`.
+  pub fn export_database_to_file(
+    &self,
+    database: &Database,
+    style: CSVFormat,
+    options: &CSVExportOptions,
+    path: &Path,
+    write_checksum_file: bool,
+  ) -> FlowyResult<String> {
+    let writer = Sha256Writer::new(File::create(path)?);
+    let (file, checksum) = self
+      .export_database_to_writer(database, style, options, writer)?
+      .finish();
+    file.sync_all()?;
+
+    if write_checksum_file {
+      let file_name = path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().to_string())
+        .unwrap_or_default();
+      std::fs::write(
+        checksum_file_path(path),
+        format!("{}  {}\n", checksum, file_name),
+      )?;
+    }
+    Ok(checksum)
+  }
+
+  /// Exports the given rows with the given fields as the columns, in their order. Unlike
+  /// [Self::export_database], it lets the caller decide which rows and fields are exported, e.g.
+  /// the rows and the visible fields of a filtered view. The cells of the user fields are read
+  /// from `row_users`.
+  pub fn export_rows(
+    &self,
+    fields: Vec<Field>,
+    rows: Vec<Row>,
+    row_users: RowUsersByRowId,
+    style: CSVFormat,
+    options: &CSVExportOptions,
+  ) -> FlowyResult<String> {
+    let data = self.write_rows(fields, rows, &row_users, style, options, vec![])?;
+    let csv = String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))?;
+    Ok(csv)
+  }
+
+  fn export_database_to_writer<W: Write>(
+    &self,
+    database: &Database,
+    style: CSVFormat,
+    options: &CSVExportOptions,
+    writer: W,
+  ) -> FlowyResult<W> {
     let inline_view_id = database.get_inline_view_id();
     let fields = database.get_fields_in_view(&inline_view_id, None);
+    let rows = database.get_rows_for_view(&inline_view_id);
+    let row_users = get_all_row_users(database);
+    self.write_rows(fields, rows, &row_users, style, options, writer)
+  }
+
+  /// Writes the CSV to `writer` and returns it after it's flushed. Fails if the writer fails, so
+  /// a truncated export is never reported as complete.
+  fn write_rows<W: Write>(
+    &self,
+    fields: Vec<Field>,
+    rows: Vec<Row>,
+    row_users: &RowUsersByRowId,
+    style: CSVFormat,
+    options: &CSVExportOptions,
+    writer: W,
+  ) -> FlowyResult<W> {
+    let delimiter = validate_delimiter(options.delimiter)?;
+    let mut wtr = csv::WriterBuilder::new()
+      .delimiter(delimiter)
+      .quote_style(options.quote_style.into())
+      .from_writer(writer);
+    // Without quotes, a value that contains the delimiter would be split into two values
+    let ensure_unquoted_values = |values: &[String]| -> FlowyResult<()> {
+      if options.quote_style == CSVQuoteStyle::Never {
+        if let Some(value) = values
+          .iter()
+          .find(|value| value.contains(options.delimiter))
+        {
+          let msg = format!(
+            "The value {:?} contains the delimiter {:?} and can't be exported without quotes",
+            value, options.delimiter
+          );
+          return Err(FlowyError::invalid_data().with_context(msg));
+        }
+      }
+      Ok(())
+    };
 
     // Write fields
-    let field_records = fields
-      .iter()
-      .map(|field| match &style {
-        CSVFormat::Original => field.name.clone(),
-        CSVFormat::META => serde_json::to_string(&field).unwrap(),
-      })
-      .collect::<Vec<String>>();
+    let mut field_records = vec![];
+    if options.include_row_metadata {
+      field_records.extend(ROW_METADATA_COLUMNS.map(String::from));
+    }
+    field_records.extend(fields.iter().map(|field| match &style {
+      CSVFormat::Original => field.name.clone(),
+      CSVFormat::META => serde_json::to_string(&field).unwrap(),
+    }));
+    ensure_unquoted_values(&field_records)?;
     wtr
       .write_record(&field_records)
       .map_err(|e| FlowyError::internal().with_context(e))?;
@@ -43,7 +272,6 @@ impl CSVExport {
     fields.into_iter().for_each(|field| {
       field_by_field_id.insert(field.id.clone(), field);
     });
-    let rows = database.get_rows_for_view(&inline_view_id);
 
     let stringify = |cell: &Cell, field: &Field, style: CSVFormat| match style {
       CSVFormat::Original => stringify_cell(cell, field),
@@ -51,7 +279,15 @@ impl CSVExport {
     };
 
     for row in rows {
-      let cells = field_by_field_id
+      let mut cells = vec![];
+      if options.include_row_metadata {
+        cells.extend([
+          row.id.to_string(),
+          row.created_at.to_string(),
+          row.modified_at.to_string(),
+        ]);
+      }
+      let field_cells = field_by_field_id
         .iter()
         .map(|(field_id, field)| {
           let field_type = FieldType::from(field.field_type);
@@ -65,6 +301,12 @@ impl CSVExport {
               let cell = Cell::from(TimestampCellDataWrapper::from((field_type, cell_data)));
               stringify(&cell, field, style)
             },
+            FieldType::LastEditedBy | FieldType::CreatedBy => {
+              match user_cell_of_row(row_users, &row.id, field_type) {
+                None => "".to_string(),
+                Some(cell) => stringify(&cell, field, style),
+              }
+            },
             _ => match row.cells.get(field_id) {
               None => "".to_string(),
               Some(cell) => stringify(cell, field, style),
@@ -72,16 +314,39 @@ impl CSVExport {
           }
         })
         .collect::<Vec<_>>();
+      cells.extend(field_cells);
 
+      ensure_unquoted_values(&cells)?;
       if let Err(e) = wtr.write_record(&cells) {
+        if e.is_io_error() {
+          return Err(FlowyError::internal().with_context(e));
+        }
         tracing::warn!("CSV failed to write record: {}", e);
       }
     }
 
-    let data = wtr
+    wtr
       .into_inner()
-      .map_err(|e| FlowyError::internal().with_context(e))?;
-    let csv = String::from_utf8(data).map_err(|e| FlowyError::internal().with_context(e))?;
-    Ok(csv)
+      .map_err(|e| FlowyError::internal().with_context(e.error()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::io::Write;
+
+  use super::Sha256Writer;
+
+  #[test]
+  fn sha256_writer_test() {
+    let mut writer = Sha256Writer::new(vec![]);
+    writer.write_all(b"a").unwrap();
+    writer.write_all(b"bc").unwrap();
+    let (data, checksum) = writer.finish();
+    assert_eq!(data, b"abc");
+    assert_eq!(
+      checksum,
+      "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
   }
 }
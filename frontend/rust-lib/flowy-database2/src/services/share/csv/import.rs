@@ -10,23 +10,39 @@ use flowy_error::{FlowyError, FlowyResult};
 use crate::entities::FieldType;
 use crate::services::field::{default_type_option_data_from_type, CELL_DATA};
 use crate::services::field_settings::default_field_settings_for_fields;
-use crate::services::share::csv::CSVFormat;
+use crate::services::share::csv::{
+  validate_delimiter, CSVFormat, CREATED_AT_COLUMN, MODIFIED_AT_COLUMN, ROW_METADATA_COLUMNS,
+};
 
 #[derive(Default)]
 pub struct CSVImporter;
 
+#[derive(Debug, Clone, Default)]
+pub struct CSVImportOptions {
+  /// Orders the rows exported with their [CREATED_AT_COLUMN] by their creation time, i.e. the
+  /// order they had in the exported database regardless of the sort of the exported view.
+  /// Otherwise the rows keep the order of the CSV.
+  pub import_sort_by_created_at: bool,
+  /// The delimiter of the values. It's detected from the header line if it's None.
+  pub delimiter: Option<char>,
+}
+
+/// The delimiters that can be detected. The first one wins if they appear equally often.
+const DELIMITER_CANDIDATES: [char; 4] = [',', ';', '\t', '|'];
+
 impl CSVImporter {
   pub fn import_csv_from_file(
     &self,
     view_id: &str,
     path: &str,
     style: CSVFormat,
+    options: CSVImportOptions,
   ) -> FlowyResult<CreateDatabaseParams> {
     let mut file = File::open(path)?;
     let mut content = String::new();
     file.read_to_string(&mut content)?;
-    let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(view_id, fields_with_rows, &style);
+    let fields_with_rows = self.get_fields_and_rows(content, &options)?;
+    let database_data = database_from_fields_and_rows(view_id, fields_with_rows, &style, &options);
     Ok(database_data)
   }
 
@@ -35,51 +51,120 @@ impl CSVImporter {
     view_id: String,
     content: String,
     format: CSVFormat,
+    options: CSVImportOptions,
   ) -> FlowyResult<CreateDatabaseParams> {
-    let fields_with_rows = self.get_fields_and_rows(content)?;
-    let database_data = database_from_fields_and_rows(&view_id, fields_with_rows, &format);
+    let fields_with_rows = self.get_fields_and_rows(content, &options)?;
+    let database_data =
+      database_from_fields_and_rows(&view_id, fields_with_rows, &format, &options);
     Ok(database_data)
   }
 
-  fn get_fields_and_rows(&self, content: String) -> Result<FieldsRows, FlowyError> {
+  fn get_fields_and_rows(
+    &self,
+    content: String,
+    options: &CSVImportOptions,
+  ) -> Result<FieldsRows, FlowyError> {
     let mut fields: Vec<String> = vec![];
-    if content.is_empty() {
+    // The CSV files saved by Excel start with a BOM that would be a part of the first header
+    let content = content.trim_start_matches('\u{feff}');
+    if content.trim().is_empty() {
       return Err(FlowyError::invalid_data().with_context("Import content is empty"));
     }
 
-    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let delimiter = options
+      .delimiter
+      .unwrap_or_else(|| detect_delimiter(content));
+    let mut reader = csv::ReaderBuilder::new()
+      .delimiter(validate_delimiter(delimiter)?)
+      .from_reader(content.as_bytes());
+    // The row metadata of an export isn't imported as fields, only the times of the rows are kept
+    let mut is_field_column = vec![];
+    let mut created_at_column = None;
+    let mut modified_at_column = None;
     if let Ok(headers) = reader.headers() {
-      for header in headers {
-        fields.push(header.to_string());
+      for (index, header) in headers.iter().enumerate() {
+        match header {
+          CREATED_AT_COLUMN => created_at_column = Some(index),
+          MODIFIED_AT_COLUMN => modified_at_column = Some(index),
+          _ => {},
+        }
+        let is_field = !ROW_METADATA_COLUMNS.contains(&header);
+        if is_field {
+          fields.push(header.to_string());
+        }
+        is_field_column.push(is_field);
       }
     } else {
       return Err(FlowyError::invalid_data().with_context("Header not found"));
     }
 
-    let rows = reader
-      .records()
-      .flat_map(|r| r.ok())
-      .map(|record| {
+    let parse_timestamp = |record: &csv::StringRecord, column: Option<usize>| {
+      column
+        .and_then(|index| record.get(index))
+        .and_then(|value| value.trim().parse::<i64>().ok())
+    };
+    let mut rows = vec![];
+    let mut row_times = vec![];
+    for record in reader.records().flat_map(|r| r.ok()) {
+      row_times.push(RowTimes {
+        created_at: parse_timestamp(&record, created_at_column),
+        modified_at: parse_timestamp(&record, modified_at_column),
+      });
+      rows.push(
         record
           .into_iter()
-          .map(|s| s.to_string())
-          .collect::<Vec<String>>()
-      })
-      .collect();
+          .zip(is_field_column.iter())
+          .filter(|(_, is_field)| **is_field)
+          .map(|(s, _)| s.to_string())
+          .collect::<Vec<String>>(),
+      );
+    }
+
+    Ok(FieldsRows {
+      fields,
+      rows,
+      row_times,
+    })
+  }
+}
+
+/// Guesses the delimiter by counting the [DELIMITER_CANDIDATES] outside the quotes of the header
+/// line. Falls back to `,` if none of them appears.
+pub(crate) fn detect_delimiter(content: &str) -> char {
+  let mut counts = [0; DELIMITER_CANDIDATES.len()];
+  let mut in_quotes = false;
+  for c in content.chars() {
+    match c {
+      '"' => in_quotes = !in_quotes,
+      '\n' | '\r' if !in_quotes => break,
+      _ if !in_quotes => {
+        if let Some(index) = DELIMITER_CANDIDATES.iter().position(|d| *d == c) {
+          counts[index] += 1;
+        }
+      },
+      _ => {},
+    }
+  }
 
-    Ok(FieldsRows { fields, rows })
+  let mut best = 0;
+  for (index, count) in counts.iter().enumerate() {
+    if *count > counts[best] {
+      best = index;
+    }
   }
+  DELIMITER_CANDIDATES[best]
 }
 
 fn database_from_fields_and_rows(
   view_id: &str,
   fields_and_rows: FieldsRows,
   format: &CSVFormat,
+  options: &CSVImportOptions,
 ) -> CreateDatabaseParams {
-  let (fields, rows) = fields_and_rows.split();
+  let (fields, rows, row_times) = fields_and_rows.split();
   let database_id = gen_database_id();
 
-  let fields = fields
+  let mut fields = fields
     .into_iter()
     .enumerate()
     .map(|(index, field_meta)| match format {
@@ -96,13 +181,21 @@ fn database_from_fields_and_rows(
       },
     })
     .collect::<Vec<Field>>();
+  ensure_primary_field(&mut fields);
 
   let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Grid);
 
-  let rows = rows
+  let mut rows = rows
     .iter()
-    .map(|cells| {
+    .zip(row_times)
+    .map(|(cells, row_times)| {
       let mut params = CreateRowParams::new(gen_row_id(), database_id.clone());
+      if let Some(created_at) = row_times.created_at {
+        params.created_at = created_at;
+      }
+      if let Some(modified_at) = row_times.modified_at {
+        params.modified_at = modified_at;
+      }
       for (index, cell_content) in cells.iter().enumerate() {
         if let Some(field) = fields.get(index) {
           let field_type = FieldType::from(field.field_type);
@@ -125,9 +218,12 @@ fn database_from_fields_and_rows(
       params
     })
     .collect::<Vec<CreateRowParams>>();
+  if options.import_sort_by_created_at {
+    // The sort is stable, so the rows that have the same creation time keep the order of the CSV
+    rows.sort_by_key(|row| row.created_at);
+  }
 
   let timestamp = timestamp();
-
   CreateDatabaseParams {
     database_id: database_id.clone(),
     inline_view_id: view_id.to_string(),
@@ -146,6 +242,19 @@ fn database_from_fields_and_rows(
   }
 }
 
+/// Makes sure the database has exactly one primary field. The first column becomes the primary
+/// field if none of the fields is marked as primary, e.g. the field of the META format failed to
+/// parse or was exported from a non-primary field.
+fn ensure_primary_field(fields: &mut [Field]) {
+  let primary_index = fields
+    .iter()
+    .position(|field| field.is_primary)
+    .unwrap_or(0);
+  for (index, field) in fields.iter_mut().enumerate() {
+    field.is_primary = index == primary_index;
+  }
+}
+
 fn default_field(field_str: String, is_primary: bool) -> Field {
   let field_type = FieldType::RichText;
   let type_option_data = default_type_option_data_from_type(field_type);
@@ -153,13 +262,20 @@ fn default_field(field_str: String, is_primary: bool) -> Field {
     .with_type_option_data(field_type, type_option_data)
 }
 
+/// The times of a row read from the [CREATED_AT_COLUMN] and [MODIFIED_AT_COLUMN] of an export.
+struct RowTimes {
+  created_at: Option<i64>,
+  modified_at: Option<i64>,
+}
+
 struct FieldsRows {
   fields: Vec<String>,
   rows: Vec<Vec<String>>,
+  row_times: Vec<RowTimes>,
 }
 impl FieldsRows {
-  fn split(self) -> (Vec<String>, Vec<Vec<String>>) {
-    (self.fields, self.rows)
+  fn split(self) -> (Vec<String>, Vec<Vec<String>>, Vec<RowTimes>) {
+    (self.fields, self.rows, self.row_times)
   }
 }
 
@@ -172,7 +288,9 @@ pub struct ImportResult {
 mod tests {
   use collab_database::database::gen_database_view_id;
 
-  use crate::services::share::csv::{CSVFormat, CSVImporter};
+  use crate::services::share::csv::{CSVFormat, CSVImportOptions, CSVImporter};
+
+  use super::detect_delimiter;
 
   #[test]
   fn test_import_csv_from_str() {
@@ -182,7 +300,12 @@ mod tests {
 ,,,,Yes,"#;
     let importer = CSVImporter;
     let result = importer
-      .import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Original)
+      .import_csv_from_string(
+        gen_database_view_id(),
+        s.to_string(),
+        CSVFormat::Original,
+        CSVImportOptions::default(),
+      )
       .unwrap();
     assert_eq!(result.rows.len(), 3);
     assert_eq!(result.fields.len(), 6);
@@ -205,8 +328,154 @@ mod tests {
   fn import_empty_csv_data_test() {
     let s = r#""#;
     let importer = CSVImporter;
-    let result =
-      importer.import_csv_from_string(gen_database_view_id(), s.to_string(), CSVFormat::Original);
+    let result = importer.import_csv_from_string(
+      gen_database_view_id(),
+      s.to_string(),
+      CSVFormat::Original,
+      CSVImportOptions::default(),
+    );
+    assert!(result.is_err());
+  }
+
+  #[test]
+  fn import_header_only_csv_data_test() {
+    let s = "\u{feff}Title,Tags\n";
+    let result = CSVImporter
+      .import_csv_from_string(
+        gen_database_view_id(),
+        s.to_string(),
+        CSVFormat::Original,
+        CSVImportOptions::default(),
+      )
+      .unwrap();
+    assert!(result.rows.is_empty());
+    assert_eq!(result.fields.len(), 2);
+    assert_eq!(result.fields[0].name, "Title");
+    assert!(result.fields[0].is_primary);
+    assert!(!result.fields[1].is_primary);
+  }
+
+  #[test]
+  fn import_csv_sort_by_created_at_test() {
+    let s = "Name,__created_at\nC,3\nA,1\nB,2\n";
+    let import = |import_sort_by_created_at| {
+      let options = CSVImportOptions {
+        import_sort_by_created_at,
+        ..Default::default()
+      };
+      CSVImporter
+        .import_csv_from_string(
+          gen_database_view_id(),
+          s.to_string(),
+          CSVFormat::Original,
+          options,
+        )
+        .unwrap()
+        .rows
+        .iter()
+        .map(|row| row.created_at)
+        .collect::<Vec<i64>>()
+    };
+    // The creation times are read from the CSV
+    assert_eq!(import(false), vec![3, 1, 2]);
+    assert_eq!(import(true), vec![1, 2, 3]);
+  }
+
+  #[test]
+  fn import_meta_csv_without_primary_field_test() {
+    // None of the fields is marked as primary
+    let s = "{},{}\n";
+    let result = CSVImporter
+      .import_csv_from_string(
+        gen_database_view_id(),
+        s.to_string(),
+        CSVFormat::META,
+        CSVImportOptions::default(),
+      )
+      .unwrap();
+    assert_eq!(result.fields.len(), 2);
+    assert!(result.fields[0].is_primary);
+    assert!(!result.fields[1].is_primary);
+  }
+
+  #[test]
+  fn import_semicolon_csv_with_quoted_commas_test() {
+    let s = r#""Name, full";Price;Note
+"Apple, red";1,5;"a ""fresh"" one"
+Pear;2,25;
+"#;
+    let result = CSVImporter
+      .import_csv_from_string(
+        gen_database_view_id(),
+        s.to_string(),
+        CSVFormat::Original,
+        CSVImportOptions::default(),
+      )
+      .unwrap();
+    assert_eq!(result.fields.len(), 3);
+    assert_eq!(result.fields[0].name, "Name, full");
+    assert_eq!(result.fields[1].name, "Price");
+    assert_eq!(result.rows.len(), 2);
+    assert_eq!(result.rows[0].cells.len(), 3);
+  }
+
+  #[test]
+  fn import_csv_without_row_metadata_columns_test() {
+    let s = "__row_id,Name,__created_at,__modified_at\nr1,Apple,1,2\n";
+    let result = CSVImporter
+      .import_csv_from_string(
+        gen_database_view_id(),
+        s.to_string(),
+        CSVFormat::Original,
+        CSVImportOptions::default(),
+      )
+      .unwrap();
+    assert_eq!(result.fields.len(), 1);
+    assert_eq!(result.fields[0].name, "Name");
+    assert_eq!(result.rows[0].cells.len(), 1);
+    assert_ne!(result.rows[0].id.to_string(), "r1");
+    assert_eq!(result.rows[0].created_at, 1);
+    assert_eq!(result.rows[0].modified_at, 2);
+  }
+
+  #[test]
+  fn detect_delimiter_test() {
+    assert_eq!(detect_delimiter("Name;Price\n1,5;2"), ';');
+    assert_eq!(detect_delimiter("\"a,b\";\"c,d\"\n"), ';');
+    assert_eq!(detect_delimiter("Name\tTags\tDone"), '\t');
+    assert_eq!(detect_delimiter("Name,Tags"), ',');
+    assert_eq!(detect_delimiter("Name"), ',');
+  }
+
+  #[test]
+  fn import_csv_with_explicit_delimiter_test() {
+    // The header has more commas than semicolons, but the delimiter is set explicitly
+    let s = "\"Name, first\";Tags\nLucas;a,b,c\n";
+    let options = CSVImportOptions {
+      delimiter: Some(';'),
+      ..Default::default()
+    };
+    let result = CSVImporter
+      .import_csv_from_string(
+        gen_database_view_id(),
+        s.to_string(),
+        CSVFormat::Original,
+        options,
+      )
+      .unwrap();
+    assert_eq!(result.fields.len(), 2);
+    assert_eq!(result.fields[0].name, "Name, first");
+
+    let options = CSVImportOptions {
+      delimiter: Some('"'),
+      ..Default::default()
+    };
+    let result = CSVImporter.import_csv_from_string(
+      gen_database_view_id(),
+      s.to_string(),
+      CSVFormat::Original,
+      options,
+    );
     assert!(result.is_err());
   }
 }
@@ -0,0 +1,212 @@
+use std::io::{BufRead, BufReader, Read};
+
+use csv::StringRecord;
+
+use flowy_error::{FlowyError, FlowyResult};
+
+use crate::services::share::csv::import::detect_delimiter;
+use crate::services::share::csv::validate_delimiter;
+
+/// The number of the rows that are created at once by default.
+const DEFAULT_IMPORT_BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct CSVStreamImportOptions {
+  /// The delimiter of the values. It's detected from the header line if it's None.
+  pub delimiter: Option<char>,
+  /// The size of the CSV in bytes. It's used to estimate the total number of the rows.
+  pub total_bytes: Option<u64>,
+  /// The number of the rows that are created at once. It's capped by the
+  /// [crate::services::database::LowMemoryProfile] of the editor.
+  pub batch_size: usize,
+}
+
+impl Default for CSVStreamImportOptions {
+  fn default() -> Self {
+    Self {
+      delimiter: None,
+      total_bytes: None,
+      batch_size: DEFAULT_IMPORT_BATCH_SIZE,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CSVImportProgress {
+  pub rows_imported: usize,
+  /// Estimated from the bytes read so far if [CSVStreamImportOptions::total_bytes] is set.
+  /// Otherwise, it's the number of the rows read so far.
+  pub rows_total_estimate: usize,
+}
+
+/// A line of the CSV that can't be imported.
+#[derive(Debug, Clone)]
+pub struct CSVImportError {
+  /// The line number, starting from 1.
+  pub line: u64,
+  pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CSVStreamImportResult {
+  /// The number of the rows that are created or updated.
+  pub rows_imported: usize,
+  /// The number of the existing rows that are updated by their [super::ROW_ID_COLUMN].
+  pub rows_updated: usize,
+  pub errors: Vec<CSVImportError>,
+  pub is_cancelled: bool,
+}
+
+pub(crate) struct CSVRecord {
+  /// The line number, starting from 1.
+  pub(crate) line: u64,
+  pub(crate) values: Vec<String>,
+}
+
+pub(crate) struct CSVBatch {
+  pub(crate) records: Vec<CSVRecord>,
+  pub(crate) errors: Vec<CSVImportError>,
+  /// The number of the bytes read from the start of the CSV.
+  pub(crate) bytes_read: u64,
+}
+
+/// Reads the records of a CSV in batches, so the CSV doesn't have to fit in memory. The malformed
+/// lines are skipped and reported with the batch that they belong to.
+pub(crate) struct CSVBatchReader<R: Read> {
+  reader: csv::Reader<BufReader<R>>,
+  headers: Vec<String>,
+  is_finished: bool,
+}
+
+impl<R: Read> CSVBatchReader<R> {
+  pub(crate) fn new(reader: R, delimiter: Option<char>) -> FlowyResult<Self> {
+    let mut reader = BufReader::new(reader);
+    let delimiter = match delimiter {
+      Some(delimiter) => delimiter,
+      None => detect_delimiter(&String::from_utf8_lossy(reader.fill_buf()?)),
+    };
+    let mut reader = csv::ReaderBuilder::new()
+      .delimiter(validate_delimiter(delimiter)?)
+      .from_reader(reader);
+
+    // The CSV files saved by Excel start with a BOM that would be a part of the first header
+    let headers = reader
+      .headers()
+      .map_err(|_| FlowyError::invalid_data().with_context("Header not found"))?
+      .iter()
+      .map(|header| header.trim_start_matches('\u{feff}').to_string())
+      .collect::<Vec<String>>();
+    if headers.iter().all(|header| header.is_empty()) {
+      return Err(FlowyError::invalid_data().with_context("Import content is empty"));
+    }
+
+    Ok(Self {
+      reader,
+      headers,
+      is_finished: false,
+    })
+  }
+
+  pub(crate) fn headers(&self) -> &[String] {
+    &self.headers
+  }
+
+  /// Returns None once all the records are read.
+  pub(crate) fn next_batch(&mut self, batch_size: usize) -> Option<CSVBatch> {
+    if self.is_finished {
+      return None;
+    }
+
+    let mut records = vec![];
+    let mut errors = vec![];
+    let mut record = StringRecord::new();
+    while records.len() < batch_size {
+      match self.reader.read_record(&mut record) {
+        Ok(true) => records.push(CSVRecord {
+          line: record
+            .position()
+            .map(|position| position.line())
+            .unwrap_or(0),
+          values: record.iter().map(|value| value.to_string()).collect(),
+        }),
+        Ok(false) => {
+          self.is_finished = true;
+          break;
+        },
+        Err(err) => {
+          errors.push(CSVImportError {
+            line: err.position().map(|position| position.line()).unwrap_or(0),
+            message: err.to_string(),
+          });
+          // The reader can't go on after an IO error
+          if err.is_io_error() {
+            self.is_finished = true;
+            break;
+          }
+        },
+      }
+    }
+
+    if records.is_empty() && errors.is_empty() {
+      return None;
+    }
+    Some(CSVBatch {
+      records,
+      errors,
+      bytes_read: self.reader.position().byte(),
+    })
+  }
+}
+
+/// Estimates the total number of the rows by the average size of the rows read so far.
+pub(crate) fn estimate_total_rows(
+  rows_read: usize,
+  bytes_read: u64,
+  total_bytes: Option<u64>,
+) -> usize {
+  match total_bytes {
+    Some(total_bytes) if bytes_read > 0 => {
+      let estimate = (rows_read as u64).saturating_mul(total_bytes) / bytes_read;
+      (estimate as usize).max(rows_read)
+    },
+    _ => rows_read,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{estimate_total_rows, CSVBatchReader};
+
+  #[test]
+  fn read_csv_in_batches_test() {
+    let s = "\u{feff}Name;Price\nApple;1\nPear;2;extra\nPlum;3\nFig;4\n";
+    let mut reader = CSVBatchReader::new(s.as_bytes(), None).unwrap();
+    assert_eq!(reader.headers(), &["Name".to_string(), "Price".to_string()]);
+
+    let batch = reader.next_batch(2).unwrap();
+    assert_eq!(batch.records.len(), 2);
+    assert_eq!(
+      batch.records[1].values,
+      vec!["Plum".to_string(), "3".to_string()]
+    );
+    assert_eq!(batch.records[1].line, 4);
+    // The line with an extra value is skipped
+    assert_eq!(batch.errors.len(), 1);
+    assert_eq!(batch.errors[0].line, 3);
+
+    let mut rows_read = batch.records.len();
+    while let Some(batch) = reader.next_batch(2) {
+      rows_read += batch.records.len();
+    }
+    assert_eq!(rows_read, 3);
+  }
+
+  #[test]
+  fn estimate_total_rows_test() {
+    assert_eq!(estimate_total_rows(10, 100, Some(1000)), 100);
+    assert_eq!(estimate_total_rows(10, 100, None), 10);
+    assert_eq!(estimate_total_rows(10, 0, Some(1000)), 10);
+    // The estimate never goes below the rows that are read
+    assert_eq!(estimate_total_rows(10, 1000, Some(100)), 10);
+  }
+}
@@ -1,5 +1,10 @@
 mod export;
 mod import;
+mod import_stream;
 
 pub use export::*;
 pub use import::*;
+pub(crate) use import_stream::{estimate_total_rows, CSVBatchReader};
+pub use import_stream::{
+  CSVImportError, CSVImportProgress, CSVStreamImportOptions, CSVStreamImportResult,
+};
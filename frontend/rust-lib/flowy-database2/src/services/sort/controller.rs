@@ -14,11 +14,12 @@ use lib_infra::future::Fut;
 use lib_infra::priority_task::{QualityOfService, Task, TaskContent, TaskDispatcher};
 
 use crate::entities::SortChangesetNotificationPB;
-use crate::entities::{FieldType, SortWithIndexPB};
+use crate::entities::{FieldType, RelatedRowDataPB, SortWithIndexPB};
 use crate::services::cell::CellCache;
 use crate::services::database_view::{DatabaseViewChanged, DatabaseViewChangedNotifier};
 use crate::services::field::{
-  default_order, TimestampCellData, TimestampCellDataWrapper, TypeOptionCellExt,
+  default_order, user_cell_of_row, RelationCellData, RelationTypeOption, RowUsersByRowId,
+  TimestampCellData, TimestampCellDataWrapper, TypeOptionCellExt,
 };
 use crate::services::sort::{
   InsertRowResult, ReorderAllRowsResult, ReorderSingleRowResult, Sort, SortChangeset, SortCondition,
@@ -31,8 +32,17 @@ pub trait SortDelegate: Send + Sync {
   fn filter_row(&self, row_detail: &RowDetail) -> Fut<bool>;
   fn get_field(&self, field_id: &str) -> Option<Field>;
   fn get_fields(&self, view_id: &str, field_ids: Option<Vec<String>>) -> Fut<Vec<Field>>;
+  /// Returns the titles of the rows with `row_ids` in the database with `database_id`
+  fn get_related_rows(&self, database_id: &str, row_ids: Vec<String>)
+    -> Fut<Vec<RelatedRowDataPB>>;
+  /// Returns the users who created and last edited the rows
+  fn get_row_users(&self) -> RowUsersByRowId;
 }
 
+/// The sort keys of the relation cells by the id of the relation field and the id of the row. The
+/// titles of the related rows are resolved once before sorting instead of in every comparison.
+type RelationSortKeys = HashMap<String, HashMap<RowId, String>>;
+
 pub struct SortController {
   view_id: String,
   handler_id: String,
@@ -209,16 +219,29 @@ impl SortController {
     self.task_scheduler.write().await.add_task(task);
   }
 
+  pub fn has_sorts(&self) -> bool {
+    !self.sorts.is_empty()
+  }
+
   pub async fn sort_rows(&mut self, rows: &mut Vec<Arc<RowDetail>>) {
     if self.sorts.is_empty() {
       return;
     }
 
     let fields = self.delegate.get_fields(&self.view_id, None).await;
-    for sort in self.sorts.iter().rev() {
-      rows
-        .par_sort_by(|left, right| cmp_row(&left.row, &right.row, sort, &fields, &self.cell_cache));
-    }
+    let relation_sort_keys = self.get_relation_sort_keys(rows, &fields).await;
+    let row_users = self.get_row_users(&fields);
+    rows.par_sort_by(|left, right| {
+      cmp_row_by_sorts(
+        &left.row,
+        &right.row,
+        &self.sorts,
+        &fields,
+        &self.cell_cache,
+        &relation_sort_keys,
+        &row_users,
+      )
+    });
     rows.iter().enumerate().for_each(|(index, row_detail)| {
       self
         .row_index_cache
@@ -226,6 +249,82 @@ impl SortController {
     });
   }
 
+  /// Resolves the titles of the rows that the cells of the sorted relation fields link to.
+  async fn get_relation_sort_keys(
+    &self,
+    rows: &[Arc<RowDetail>],
+    fields: &[Field],
+  ) -> RelationSortKeys {
+    let mut relation_sort_keys = RelationSortKeys::new();
+    for sort in self.sorts.iter() {
+      let field = match fields.iter().find(|field| field.id == sort.field_id) {
+        Some(field) if FieldType::from(field.field_type).is_relation() => field,
+        _ => continue,
+      };
+      if relation_sort_keys.contains_key(&field.id) {
+        continue;
+      }
+      let database_id = match field.get_type_option::<RelationTypeOption>(FieldType::Relation) {
+        Some(type_option) => type_option.database_id,
+        None => continue,
+      };
+
+      let linked_row_ids_by_row = rows
+        .iter()
+        .map(|row_detail| {
+          let linked_row_ids = row_detail
+            .row
+            .cells
+            .get(&field.id)
+            .map(|cell| RelationCellData::from(cell).row_ids)
+            .unwrap_or_default();
+          (row_detail.row.id.clone(), linked_row_ids)
+        })
+        .collect::<Vec<_>>();
+      let mut linked_row_ids = linked_row_ids_by_row
+        .iter()
+        .flat_map(|(_, linked_row_ids)| linked_row_ids.iter().map(|row_id| row_id.to_string()))
+        .collect::<Vec<String>>();
+      linked_row_ids.sort();
+      linked_row_ids.dedup();
+
+      let titles = if linked_row_ids.is_empty() {
+        HashMap::new()
+      } else {
+        self
+          .delegate
+          .get_related_rows(&database_id, linked_row_ids)
+          .await
+          .into_iter()
+          .map(|related_row| (related_row.row_id, related_row.name))
+          .collect::<HashMap<String, String>>()
+      };
+      let sort_keys = linked_row_ids_by_row
+        .into_iter()
+        .flat_map(|(row_id, linked_row_ids)| {
+          relation_sort_key(&linked_row_ids, &titles).map(|sort_key| (row_id, sort_key))
+        })
+        .collect();
+      relation_sort_keys.insert(field.id.clone(), sort_keys);
+    }
+    relation_sort_keys
+  }
+
+  /// Returns the users of the rows if one of the sorted fields is a user field. They're read once
+  /// before sorting instead of in every comparison.
+  fn get_row_users(&self, fields: &[Field]) -> RowUsersByRowId {
+    let sorts_by_user = self.sorts.iter().any(|sort| {
+      fields
+        .iter()
+        .any(|field| field.id == sort.field_id && FieldType::from(field.field_type).is_user())
+    });
+    if sorts_by_user {
+      self.delegate.get_row_users()
+    } else {
+      RowUsersByRowId::new()
+    }
+  }
+
   pub async fn delete_all_sorts(&mut self) {
     self.sorts.clear();
     self
@@ -233,6 +332,31 @@ impl SortController {
       .await;
   }
 
+  /// Replaces all the sorts and re-sorts the rows once.
+  pub async fn replace_all_sorts(&mut self, sorts: Vec<Arc<Sort>>) -> SortChangesetNotificationPB {
+    let mut notification = SortChangesetNotificationPB::new(self.view_id.clone());
+    notification.delete_sorts = self.sorts.iter().map(|sort| sort.as_ref().into()).collect();
+    notification.insert_sorts = sorts
+      .iter()
+      .enumerate()
+      .map(|(index, sort)| SortWithIndexPB {
+        index: index as u32,
+        sort: sort.as_ref().into(),
+      })
+      .collect();
+    self.sorts = sorts;
+
+    let task_type = if self.sorts.is_empty() {
+      SortEvent::DeleteAllSorts
+    } else {
+      SortEvent::SortDidChanged
+    };
+    self
+      .gen_task(task_type, QualityOfService::UserInteractive)
+      .await;
+    notification
+  }
+
   pub async fn did_update_field_type_option(&self, _field: &Field) {
     //
   }
@@ -298,12 +422,42 @@ impl SortController {
   }
 }
 
+/// Compares two rows by applying the sorts in order. Rows that are equal for every sort are
+/// ordered by their row id, so the result doesn't depend on the order the rows were loaded in.
+fn cmp_row_by_sorts(
+  left: &Row,
+  right: &Row,
+  sorts: &[Arc<Sort>],
+  fields: &[Field],
+  cell_data_cache: &CellCache,
+  relation_sort_keys: &RelationSortKeys,
+  row_users: &RowUsersByRowId,
+) -> Ordering {
+  sorts
+    .iter()
+    .map(|sort| {
+      cmp_row(
+        left,
+        right,
+        sort,
+        fields,
+        cell_data_cache,
+        relation_sort_keys,
+        row_users,
+      )
+    })
+    .find(|order| order.is_ne())
+    .unwrap_or_else(|| left.id.as_str().cmp(right.id.as_str()))
+}
+
 fn cmp_row(
   left: &Row,
   right: &Row,
   sort: &Arc<Sort>,
   fields: &[Field],
   cell_data_cache: &CellCache,
+  relation_sort_keys: &RelationSortKeys,
+  row_users: &RowUsersByRowId,
 ) -> Ordering {
   match fields
     .iter()
@@ -311,7 +465,15 @@ fn cmp_row(
   {
     None => default_order(),
     Some(field_rev) => {
-      let field_type = field_rev.field_type.into();
+      let field_type: FieldType = field_rev.field_type.into();
+      if field_type.is_relation() {
+        let sort_keys = relation_sort_keys.get(&field_rev.id);
+        return cmp_relation_sort_keys(
+          sort_keys.and_then(|sort_keys| sort_keys.get(&left.id)),
+          sort_keys.and_then(|sort_keys| sort_keys.get(&right.id)),
+          sort.condition,
+        );
+      }
       let timestamp_cells = match field_type {
         FieldType::LastEditedTime | FieldType::CreatedTime => {
           let (left_cell, right_cell) = if field_type.is_created_time() {
@@ -325,6 +487,10 @@ fn cmp_row(
           );
           Some((Some(left_cell.into()), Some(right_cell.into())))
         },
+        FieldType::LastEditedBy | FieldType::CreatedBy => Some((
+          user_cell_of_row(row_users, &left.id, field_type),
+          user_cell_of_row(row_users, &right.id, field_type),
+        )),
         _ => None,
       };
 
@@ -358,6 +524,37 @@ fn cmp_cell(
   }
 }
 
+/// Returns the key that a relation cell is sorted by. The titles of the linked rows are sorted and
+/// joined, so a cell that links to many rows is ordered by its smallest title first. Returns None
+/// if none of the linked rows has a title.
+fn relation_sort_key(linked_row_ids: &[RowId], titles: &HashMap<String, String>) -> Option<String> {
+  let mut linked_titles = linked_row_ids
+    .iter()
+    .flat_map(|row_id| titles.get(row_id.as_str()))
+    .filter(|title| !title.is_empty())
+    .map(|title| title.as_str())
+    .collect::<Vec<&str>>();
+  if linked_titles.is_empty() {
+    return None;
+  }
+  linked_titles.sort();
+  Some(linked_titles.join(", "))
+}
+
+/// The empty relation cells are always placed at the end, regardless of the sort condition.
+fn cmp_relation_sort_keys(
+  left: Option<&String>,
+  right: Option<&String>,
+  sort_condition: SortCondition,
+) -> Ordering {
+  match (left, right) {
+    (None, None) => Ordering::Equal,
+    (None, Some(_)) => Ordering::Greater,
+    (Some(_), None) => Ordering::Less,
+    (Some(left), Some(right)) => sort_condition.evaluate_order(left.cmp(right)),
+  }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 enum SortEvent {
   SortDidChanged,
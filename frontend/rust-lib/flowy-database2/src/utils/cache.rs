@@ -7,28 +7,44 @@ use std::sync::Arc;
 
 #[derive(Default, Debug)]
 /// The better option is use LRU cache
-pub struct AnyTypeCache<TypeValueKey>(HashMap<TypeValueKey, TypeValue>);
+pub struct AnyTypeCache<TypeValueKey> {
+  values: HashMap<TypeValueKey, TypeValue>,
+  /// The cache is cleared when a new key is inserted into a full cache. None for no limit.
+  capacity: Option<usize>,
+}
 
 impl<TypeValueKey> AnyTypeCache<TypeValueKey>
 where
   TypeValueKey: Clone + Hash + Eq,
 {
   pub fn new() -> Arc<RwLock<AnyTypeCache<TypeValueKey>>> {
-    Arc::new(RwLock::new(AnyTypeCache(HashMap::default())))
+    Self::with_capacity(None)
+  }
+
+  pub fn with_capacity(capacity: Option<usize>) -> Arc<RwLock<AnyTypeCache<TypeValueKey>>> {
+    Arc::new(RwLock::new(AnyTypeCache {
+      values: HashMap::default(),
+      capacity,
+    }))
   }
 
   pub fn insert<T>(&mut self, key: &TypeValueKey, val: T) -> Option<T>
   where
     T: 'static + Send + Sync,
   {
+    if let Some(capacity) = self.capacity {
+      if self.values.len() >= capacity && !self.values.contains_key(key) {
+        self.values.clear();
+      }
+    }
     self
-      .0
+      .values
       .insert(key.clone(), TypeValue::new(val))
       .and_then(downcast_owned)
   }
 
   pub fn remove(&mut self, key: &TypeValueKey) {
-    self.0.remove(key);
+    self.values.remove(key);
   }
 
   pub fn get<T>(&self, key: &TypeValueKey) -> Option<&T>
@@ -36,7 +52,7 @@ where
     T: 'static + Send + Sync,
   {
     self
-      .0
+      .values
       .get(key)
       .and_then(|type_value| type_value.boxed.downcast_ref())
   }
@@ -46,17 +62,21 @@ where
     T: 'static + Send + Sync,
   {
     self
-      .0
+      .values
       .get_mut(key)
       .and_then(|type_value| type_value.boxed.downcast_mut())
   }
 
   pub fn contains(&self, key: &TypeValueKey) -> bool {
-    self.0.contains_key(key)
+    self.values.contains_key(key)
+  }
+
+  pub fn len(&self) -> usize {
+    self.values.len()
   }
 
   pub fn is_empty(&self) -> bool {
-    self.0.is_empty()
+    self.values.is_empty()
   }
 }
 
@@ -1,8 +1,20 @@
-use std::time::Duration;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use flowy_database2::entities::FieldType;
-use flowy_database2::services::field::DateCellData;
+use collab_database::rows::{Cells, CreateRowParams, RowDetail, RowId};
+use collab_database::views::OrderObjectPosition;
+use flowy_database2::entities::{CreateRowPayloadPB, FieldType};
+use flowy_database2::services::cell::{insert_text_cell, stringify_cell};
+use flowy_database2::services::database::{ManualClock, MergeRowsStrategy};
+use flowy_database2::services::field::{
+  DateCellData, SelectOptionIds, StringCellData, TimestampCellData, UserCellData,
+};
+use flowy_database2::services::setting::NewRowPosition;
+use flowy_error::ErrorCode;
+use lib_infra::box_any::BoxAny;
 use lib_infra::util::timestamp;
+use tokio_util::sync::CancellationToken;
 
 use crate::database::block_test::script::DatabaseRowTest;
 use crate::database::block_test::script::RowScript::*;
@@ -62,3 +74,718 @@ async fn update_at_field_test() {
   let new_updated_at = DateCellData::from(&cell).timestamp.unwrap();
   assert!(old_updated_at < new_updated_at);
 }
+
+#[tokio::test]
+async fn find_duplicate_rows_test() {
+  let mut test = DatabaseRowTest::new().await;
+  let rows = test.get_rows().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+
+  // The 5th and 6th rows are both named "AE"
+  let duplicates = test
+    .editor
+    .find_duplicate_rows(&test.view_id, vec![text_field.id.clone()])
+    .await
+    .unwrap();
+  assert_eq!(
+    duplicates,
+    vec![vec![rows[4].row.id.clone(), rows[5].row.id.clone()]]
+  );
+
+  // They have different checkbox values
+  let duplicates = test
+    .editor
+    .find_duplicate_rows(
+      &test.view_id,
+      vec![text_field.id.clone(), checkbox_field.id.clone()],
+    )
+    .await
+    .unwrap();
+  assert!(duplicates.is_empty());
+
+  // The new row has an empty name, same as the 2nd row
+  test.run_scripts(vec![CreateEmptyRow]).await;
+  let new_row = test.get_rows().await.last().cloned().unwrap();
+  let duplicates = test
+    .editor
+    .find_duplicate_rows(&test.view_id, vec![text_field.id.clone()])
+    .await
+    .unwrap();
+  assert_eq!(duplicates.len(), 2);
+  let empty_names = vec![rows[1].row.id.clone(), new_row.row.id.clone()];
+  assert!(duplicates.contains(&empty_names));
+
+  let result = test.editor.find_duplicate_rows(&test.view_id, vec![]).await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn merge_rows_prefer_primary_test() {
+  let test = DatabaseRowTest::new().await;
+  let rows = test.get_rows().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let number_field = test.get_first_field(FieldType::Number);
+  let single_select_field = test.get_first_field(FieldType::SingleSelect);
+  let multi_select_field = test.get_first_field(FieldType::MultiSelect);
+  let multi_select_ids = |row_detail: &RowDetail| {
+    let cell = row_detail.row.cells.get(&multi_select_field.id).unwrap();
+    SelectOptionIds::from(cell).into_inner()
+  };
+
+  // The options of the multi-select are combined
+  let mut expected_option_ids = multi_select_ids(&rows[0]);
+  for option_id in multi_select_ids(&rows[2]) {
+    if !expected_option_ids.contains(&option_id) {
+      expected_option_ids.push(option_id);
+    }
+  }
+  // The first row has no single-select option, so it's filled by the third row
+  assert!(rows[0].row.cells.get(&single_select_field.id).is_none());
+  let expected_single_select = rows[2].row.cells.get(&single_select_field.id).cloned();
+
+  test
+    .editor
+    .merge_rows(
+      &test.view_id,
+      &rows[0].row.id,
+      vec![rows[2].row.id.clone()],
+      MergeRowsStrategy::default(),
+    )
+    .await
+    .unwrap();
+
+  assert_eq!(test.get_rows().await.len(), rows.len() - 1);
+  let primary_row = test
+    .editor
+    .get_row_detail(&test.view_id, &rows[0].row.id)
+    .unwrap();
+  let cell = |field_id: &str| primary_row.row.cells.get(field_id).cloned();
+  // The primary row keeps its own text and number
+  assert_eq!(
+    stringify_cell(&cell(&text_field.id).unwrap(), &text_field),
+    "A"
+  );
+  assert_eq!(
+    stringify_cell(&cell(&number_field.id).unwrap(), &number_field),
+    "1"
+  );
+  assert_eq!(cell(&single_select_field.id), expected_single_select);
+  assert_eq!(multi_select_ids(&primary_row), expected_option_ids);
+}
+
+#[tokio::test]
+async fn merge_rows_sum_numbers_test() {
+  let test = DatabaseRowTest::new().await;
+  let rows = test.get_rows().await;
+  let number_field = test.get_first_field(FieldType::Number);
+  let strategy = MergeRowsStrategy {
+    sum_numbers: true,
+    ..Default::default()
+  };
+
+  // 1 + 2 + 3
+  test
+    .editor
+    .merge_rows(
+      &test.view_id,
+      &rows[0].row.id,
+      vec![rows[1].row.id.clone(), rows[2].row.id.clone()],
+      strategy,
+    )
+    .await
+    .unwrap();
+  let primary_row = test
+    .editor
+    .get_row_detail(&test.view_id, &rows[0].row.id)
+    .unwrap();
+  let cell = primary_row.row.cells.get(&number_field.id).unwrap();
+  assert_eq!(stringify_cell(cell, &number_field), "6");
+  assert_eq!(test.get_rows().await.len(), rows.len() - 2);
+
+  // The locked rows can't be merged
+  test
+    .editor
+    .set_row_locked(&rows[3].row.id, true)
+    .await
+    .unwrap();
+  let err = test
+    .editor
+    .merge_rows(
+      &test.view_id,
+      &rows[0].row.id,
+      vec![rows[3].row.id.clone()],
+      strategy,
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(err.code, ErrorCode::RecordLocked);
+  assert_eq!(test.get_rows().await.len(), rows.len() - 2);
+}
+
+#[tokio::test]
+async fn move_rows_test() {
+  let test = DatabaseRowTest::new().await;
+  let rows = test.get_rows().await;
+  let row_ids = rows
+    .iter()
+    .map(|row_detail| row_detail.row.id.clone())
+    .collect::<Vec<_>>();
+
+  // The rows are gathered before moving, and they keep their order in the view
+  test
+    .editor
+    .move_rows(
+      &test.view_id,
+      vec![row_ids[4].clone(), row_ids[1].clone()],
+      0,
+    )
+    .await
+    .unwrap();
+
+  let mut expected = row_ids.clone();
+  let row_4 = expected.remove(4);
+  let row_1 = expected.remove(1);
+  expected.splice(0..0, vec![row_1, row_4]);
+  let new_row_ids = test
+    .get_rows()
+    .await
+    .iter()
+    .map(|row_detail| row_detail.row.id.clone())
+    .collect::<Vec<_>>();
+  assert_eq!(new_row_ids, expected);
+}
+
+#[tokio::test]
+async fn rows_changed_since_test() {
+  let mut test = DatabaseRowTest::new().await;
+  let since = timestamp();
+  assert!(test
+    .editor
+    .rows_changed_since(&test.view_id, since)
+    .await
+    .unwrap()
+    .is_empty());
+
+  tokio::time::sleep(Duration::from_millis(1000)).await;
+  let updated_row_id = test.get_rows().await.remove(1).row.id.clone();
+  test
+    .run_scripts(vec![
+      UpdateTextCell {
+        row_id: updated_row_id.clone(),
+        content: "changed".to_string(),
+      },
+      CreateEmptyRow,
+    ])
+    .await;
+  let created_row_id = test.row_details.last().unwrap().row.id.clone();
+
+  let changed_row_ids = test
+    .editor
+    .rows_changed_since(&test.view_id, since)
+    .await
+    .unwrap();
+  assert_eq!(changed_row_ids, vec![updated_row_id, created_row_id]);
+
+  // Nothing changed after the last change
+  let changed_row_ids = test
+    .editor
+    .rows_changed_since(&test.view_id, timestamp())
+    .await
+    .unwrap();
+  assert!(changed_row_ids.is_empty());
+
+  let result = test.editor.rows_changed_since("not exist", since).await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn duplicate_row_with_document_test() {
+  let test = DatabaseRowTest::new().await;
+  let document = test.sdk.create_document("my task").await;
+  test
+    .sdk
+    .insert_document_text(&document.id, "hello", 0)
+    .await;
+  let row_meta = test
+    .editor
+    .create_row_from_document(&test.view_id, &document.id)
+    .await
+    .unwrap();
+  assert!(!row_meta.is_document_empty);
+  let row_id = RowId::from(row_meta.id.clone());
+
+  for copy_document in [false, true] {
+    let row_ids = test
+      .get_rows()
+      .await
+      .into_iter()
+      .map(|row_detail| row_detail.row.id.clone())
+      .collect::<HashSet<_>>();
+    test
+      .editor
+      .duplicate_row(&test.view_id, &row_id, copy_document)
+      .await
+      .unwrap();
+    let duplicate = test
+      .get_rows()
+      .await
+      .into_iter()
+      .find(|row_detail| !row_ids.contains(&row_detail.row.id))
+      .unwrap();
+    assert_ne!(duplicate.document_id, row_meta.document_id);
+
+    let duplicate_meta = test
+      .editor
+      .get_row_meta(&test.view_id, &duplicate.row.id)
+      .unwrap();
+    assert_eq!(duplicate_meta.is_document_empty, !copy_document);
+  }
+}
+
+#[tokio::test]
+async fn new_row_position_test() {
+  let test = DatabaseRowTest::new().await;
+  assert_eq!(
+    test.editor.get_new_row_position(&test.view_id),
+    NewRowPosition::Bottom
+  );
+
+  for position in [NewRowPosition::Top, NewRowPosition::Bottom] {
+    test
+      .editor
+      .set_new_row_position(&test.view_id, position)
+      .await
+      .unwrap();
+    assert_eq!(test.editor.get_new_row_position(&test.view_id), position);
+
+    let row_detail = test
+      .editor
+      .create_row(CreateRowPayloadPB {
+        view_id: test.view_id.clone(),
+        ..Default::default()
+      })
+      .await
+      .unwrap()
+      .unwrap();
+    let rows = test.get_rows().await;
+    let expected = match position {
+      NewRowPosition::Top => rows.first(),
+      NewRowPosition::Bottom => rows.last(),
+    };
+    assert_eq!(expected.unwrap().row.id, row_detail.row.id);
+  }
+}
+
+#[tokio::test]
+async fn locked_row_rejects_cell_edits_test() {
+  let test = DatabaseRowTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let row_id = test.get_rows().await[0].row.id.clone();
+
+  test.editor.set_row_locked(&row_id, true).await.unwrap();
+  assert!(test.editor.is_row_locked(&row_id));
+  assert!(
+    test
+      .editor
+      .get_row_meta(&test.view_id, &row_id)
+      .unwrap()
+      .is_locked
+  );
+  let row_metas = test
+    .editor
+    .get_database_rows(&test.view_id, 0, 1)
+    .await
+    .unwrap();
+  assert!(row_metas[0].is_locked);
+
+  // The copy of a locked row can be edited
+  test
+    .editor
+    .duplicate_row(&test.view_id, &row_id, false)
+    .await
+    .unwrap();
+  let num_of_locked_rows = test
+    .get_rows()
+    .await
+    .iter()
+    .filter(|row_detail| test.editor.is_row_locked(&row_detail.row.id))
+    .count();
+  assert_eq!(num_of_locked_rows, 1);
+
+  let error = test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &text_field.id,
+      BoxAny::new("hello".to_string()),
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::RecordLocked);
+  let error = test
+    .editor
+    .clear_cell(&test.view_id, row_id.clone(), &text_field.id)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::RecordLocked);
+
+  // The locked row can still be read
+  let cell = test.editor.get_cell(&text_field.id, &row_id).await.unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "A");
+
+  test.editor.set_row_locked(&row_id, false).await.unwrap();
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &text_field.id,
+      BoxAny::new("hello".to_string()),
+    )
+    .await
+    .unwrap();
+  let cell = test.editor.get_cell(&text_field.id, &row_id).await.unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "hello");
+}
+
+#[tokio::test]
+async fn clear_field_with_locked_row_test() {
+  let test = DatabaseRowTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let row_id = test.get_rows().await[0].row.id.clone();
+  test.editor.set_row_locked(&row_id, true).await.unwrap();
+
+  // Nothing is cleared
+  let error = test
+    .editor
+    .clear_field(&test.view_id, &text_field.id)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::RecordLocked);
+  let cell = test.editor.get_cell(&text_field.id, &row_id).await.unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "A");
+
+  test.editor.set_row_locked(&row_id, false).await.unwrap();
+  test
+    .editor
+    .clear_field(&test.view_id, &text_field.id)
+    .await
+    .unwrap();
+  let text = test
+    .editor
+    .get_cell(&text_field.id, &row_id)
+    .await
+    .map(|cell| StringCellData::from(&cell).as_str().to_string())
+    .unwrap_or_default();
+  assert!(text.is_empty());
+}
+
+#[tokio::test]
+async fn update_last_edited_by_field_on_cell_update() {
+  let test = DatabaseRowTest::new().await;
+  let uid = test.sdk.get_user_profile().await.unwrap().id;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let last_edited_by_field = test.get_first_field(FieldType::LastEditedBy);
+  let created_by_field = test.get_first_field(FieldType::CreatedBy);
+  let row_id = test.get_rows().await[0].row.id.clone();
+
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &text_field.id,
+      BoxAny::new("hello".to_string()),
+    )
+    .await
+    .unwrap();
+  let cell = test
+    .editor
+    .get_cell(&last_edited_by_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(UserCellData::from(&cell).user_id, Some(uid));
+
+  // The users are kept in the row metas instead of the cells of the row
+  let row = test.get_rows().await[0].row.clone();
+  assert!(!row.cells.contains_key(&last_edited_by_field.id));
+  assert!(!row.cells.contains_key(&created_by_field.id));
+
+  // The user fields can't be edited directly
+  assert!(test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &created_by_field.id,
+      BoxAny::new(uid.to_string()),
+    )
+    .await
+    .is_err());
+
+  // The new rows record the user who created them
+  let row = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  let cell = test
+    .editor
+    .get_cell(&created_by_field.id, &row.row.id)
+    .await
+    .unwrap();
+  assert_eq!(UserCellData::from(&cell).user_id, Some(uid));
+}
+
+#[tokio::test]
+async fn row_timestamps_follow_the_editor_clock_test() {
+  let mut test = DatabaseRowTest::new().await;
+  let clock = Arc::new(ManualClock::new(1_700_000_000));
+  test.editor.set_clock(clock.clone());
+  let updated_at_field = test.get_first_field(FieldType::LastEditedTime);
+  let created_at_field = test.get_first_field(FieldType::CreatedTime);
+
+  let row_id = test.row_details[0].row.id.clone();
+  test
+    .run_script(UpdateTextCell {
+      row_id: row_id.clone(),
+      content: "change".to_string(),
+    })
+    .await;
+  let cell = test
+    .editor
+    .get_cell(&updated_at_field.id, &row_id)
+    .await
+    .unwrap();
+  assert_eq!(
+    TimestampCellData::from(&cell).timestamp,
+    Some(1_700_000_000)
+  );
+
+  // The new rows are created at the time of the clock, without waiting for it
+  clock.advance(3600);
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  for field in [&created_at_field, &updated_at_field] {
+    let cell = test
+      .editor
+      .get_cell(&field.id, &row_detail.row.id)
+      .await
+      .unwrap();
+    assert_eq!(
+      TimestampCellData::from(&cell).timestamp,
+      Some(1_700_003_600)
+    );
+  }
+}
+
+#[tokio::test]
+async fn scan_related_rows_test() {
+  let test = DatabaseRowTest::new().await;
+  let primary_field = test.get_first_field(FieldType::RichText);
+  let num_of_rows = test.get_rows().await.len();
+  let new_rows = (num_of_rows..250)
+    .map(|i| {
+      let mut cells = Cells::new();
+      cells.insert(
+        primary_field.id.clone(),
+        insert_text_cell(format!("row {}", i), &primary_field),
+      );
+      cells
+    })
+    .collect::<Vec<_>>();
+  test
+    .editor
+    .create_rows_batch(&test.view_id, new_rows)
+    .await
+    .unwrap();
+
+  // The first rows of the view are returned for the initial display
+  let scan = test
+    .editor
+    .scan_related_rows(Some(5), CancellationToken::new())
+    .await
+    .unwrap();
+  assert_eq!(scan.rows.len(), 5);
+  assert!(scan.has_more);
+  assert!(!scan.is_cancelled);
+  let rows = test.get_rows().await;
+  for (related_row, row_detail) in scan.rows.iter().zip(rows.iter()) {
+    assert_eq!(related_row.row_id, row_detail.row.id.to_string());
+  }
+
+  let scan = test
+    .editor
+    .scan_related_rows(None, CancellationToken::new())
+    .await
+    .unwrap();
+  assert_eq!(scan.rows.len(), 250);
+  assert!(!scan.has_more);
+  assert_eq!(scan.rows[249].name, "row 249");
+
+  // A limit that's larger than the database returns all the rows
+  let scan = test
+    .editor
+    .scan_related_rows(Some(usize::MAX), CancellationToken::new())
+    .await
+    .unwrap();
+  assert_eq!(scan.rows.len(), 250);
+  assert!(!scan.has_more);
+
+  // The scan stops as soon as the picker is closed
+  let cancel_token = CancellationToken::new();
+  cancel_token.cancel();
+  let scan = test
+    .editor
+    .scan_related_rows(None, cancel_token)
+    .await
+    .unwrap();
+  assert!(scan.rows.is_empty());
+  assert!(scan.is_cancelled);
+}
+
+#[tokio::test]
+async fn find_rows_by_primary_value_with_index_test() {
+  let test = DatabaseRowTest::new().await;
+  let primary_field = test.get_first_field(FieldType::RichText);
+  let new_rows = (0..2000)
+    .map(|i| {
+      let mut cells = Cells::new();
+      cells.insert(
+        primary_field.id.clone(),
+        insert_text_cell(format!("row {}", i), &primary_field),
+      );
+      cells
+    })
+    .collect::<Vec<_>>();
+  let new_rows = test
+    .editor
+    .create_rows_batch(&test.view_id, new_rows)
+    .await
+    .unwrap();
+  let titles = (0..2000)
+    .step_by(20)
+    .map(|i| format!("row {}", i))
+    .collect::<Vec<_>>();
+
+  let start = Instant::now();
+  let mut scanned_row_ids = vec![];
+  for title in &titles {
+    scanned_row_ids.push(test.editor.find_rows_by_primary_value(title).await.unwrap());
+  }
+  let scan_elapsed = start.elapsed();
+
+  // The first lookup builds the index, so it's measured as part of the indexed lookups
+  test.editor.set_primary_value_index_enabled(true);
+  let start = Instant::now();
+  let mut indexed_row_ids = vec![];
+  for title in &titles {
+    indexed_row_ids.push(test.editor.find_rows_by_primary_value(title).await.unwrap());
+  }
+  let index_elapsed = start.elapsed();
+  println!(
+    "{} lookups: scan {:?}, index {:?}",
+    titles.len(),
+    scan_elapsed,
+    index_elapsed
+  );
+  assert_eq!(scanned_row_ids, indexed_row_ids);
+  assert_eq!(indexed_row_ids[1], vec![new_rows[20].row.id.clone()]);
+
+  // Editing the primary cell moves the row to its new title
+  let row_id = new_rows[20].row.id.clone();
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &primary_field.id,
+      BoxAny::new("renamed".to_string()),
+    )
+    .await
+    .unwrap();
+  let editor = &test.editor;
+  assert!(editor
+    .find_rows_by_primary_value("row 20")
+    .await
+    .unwrap()
+    .is_empty());
+  assert_eq!(
+    editor.find_rows_by_primary_value("renamed").await.unwrap(),
+    vec![row_id.clone()]
+  );
+  let related_rows = editor
+    .get_related_rows(Some(&vec![row_id.to_string()]))
+    .await
+    .unwrap();
+  assert_eq!(related_rows[0].name, "renamed");
+
+  // The deleted rows are removed and the created rows are found
+  editor.delete_rows(&[row_id]).await.unwrap();
+  assert!(editor
+    .find_rows_by_primary_value("renamed")
+    .await
+    .unwrap()
+    .is_empty());
+  let mut cells = Cells::new();
+  cells.insert(
+    primary_field.id.clone(),
+    insert_text_cell("renamed".to_string(), &primary_field),
+  );
+  let created_rows = editor
+    .create_rows_batch(&test.view_id, vec![cells])
+    .await
+    .unwrap();
+  assert_eq!(
+    editor.find_rows_by_primary_value("renamed").await.unwrap(),
+    vec![created_rows[0].row.id.clone()]
+  );
+
+  // A row deleted and a row created by a collaborator leave the number of rows unchanged
+  let remote_row_id = RowId::from("remote_row".to_string());
+  {
+    let database = editor.get_mutex_database().lock();
+    database.remove_row(&created_rows[0].row.id);
+    let mut cells = Cells::new();
+    cells.insert(
+      primary_field.id.clone(),
+      insert_text_cell("remote".to_string(), &primary_field),
+    );
+    database.create_row_in_view(
+      &test.view_id,
+      CreateRowParams {
+        id: remote_row_id.clone(),
+        database_id: database.get_database_id(),
+        cells,
+        height: 60,
+        visibility: true,
+        row_position: OrderObjectPosition::default(),
+        created_at: timestamp(),
+        modified_at: timestamp(),
+      },
+    );
+  }
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  assert!(editor
+    .find_rows_by_primary_value("renamed")
+    .await
+    .unwrap()
+    .is_empty());
+  assert_eq!(
+    editor.find_rows_by_primary_value("remote").await.unwrap(),
+    vec![remote_row_id]
+  );
+}
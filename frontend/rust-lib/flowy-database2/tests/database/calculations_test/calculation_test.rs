@@ -1,9 +1,15 @@
 use std::sync::Arc;
 
 use crate::database::calculations_test::script::{CalculationScript::*, DatabaseCalculationTest};
+use crate::database::filter_test::script::{DatabaseFilterTest, FilterScript::CreateDataFilter};
 
 use collab_database::fields::Field;
-use flowy_database2::entities::{CalculationType, FieldType, UpdateCalculationChangesetPB};
+use flowy_database2::entities::{
+  CalculationType, FieldType, NumberFilterConditionPB, NumberFilterPB, UpdateCalculationChangesetPB,
+};
+use flowy_database2::services::calculations::{AggregationKind, Calculation};
+use flowy_error::ErrorCode;
+use lib_infra::box_any::BoxAny;
 
 #[tokio::test]
 async fn calculations_test() {
@@ -85,3 +91,98 @@ async fn calculations_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn recompute_all_calculations_test() {
+  let mut test = DatabaseCalculationTest::new().await;
+  let view_id = test.view_id();
+  let field_id = test.get_first_field(FieldType::Number).id;
+  test
+    .run_scripts(vec![
+      InsertCalculation {
+        payload: UpdateCalculationChangesetPB {
+          view_id: view_id.clone(),
+          field_id: field_id.clone(),
+          calculation_id: Some("calc_id".to_owned()),
+          calculation_type: CalculationType::Sum,
+        },
+      },
+      AssertCalculationValue { expected: 25.0 },
+    ])
+    .await;
+
+  // Leave a stale value, as if an incremental update was missed
+  test.editor.get_mutex_database().lock().update_calculation(
+    &view_id,
+    Calculation {
+      id: "calc_id".to_owned(),
+      field_id,
+      calculation_type: CalculationType::Sum.value(),
+      value: "1.00000".to_owned(),
+    },
+  );
+  test
+    .run_scripts(vec![AssertCalculationValue { expected: 1.0 }])
+    .await;
+
+  test
+    .editor
+    .recompute_all_calculations(&view_id)
+    .await
+    .unwrap();
+  test
+    .run_scripts(vec![AssertCalculationValue { expected: 25.0 }])
+    .await;
+}
+
+#[tokio::test]
+async fn aggregate_field_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id.clone();
+  let number_field = test.get_first_field(FieldType::Number);
+  let aggregations = [
+    (AggregationKind::Sum, 25.0),
+    (AggregationKind::Min, 1.0),
+    (AggregationKind::Max, 14.0),
+    (AggregationKind::Average, 5.0),
+    (AggregationKind::Count, 5.0),
+  ];
+  for (kind, expected) in aggregations {
+    let value = test
+      .editor
+      .aggregate_field(&view_id, &number_field.id, kind)
+      .await
+      .unwrap();
+    assert_eq!(value, expected, "{:?}", kind);
+  }
+  // The aggregations aren't saved to the view
+  let calculations = test.editor.get_all_calculations(&view_id).await;
+  assert!(calculations.items.is_empty());
+
+  // Only the rows that pass the filter are aggregated
+  test
+    .run_scripts(vec![CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::Number,
+      data: BoxAny::new(NumberFilterPB {
+        condition: NumberFilterConditionPB::GreaterThan,
+        content: "2".to_string(),
+      }),
+      changed: None,
+    }])
+    .await;
+  let sum = test
+    .editor
+    .aggregate_field(&view_id, &number_field.id, AggregationKind::Sum)
+    .await
+    .unwrap();
+  assert_eq!(sum, 22.0);
+
+  let text_field = test.get_first_field(FieldType::RichText);
+  let error = test
+    .editor
+    .aggregate_field(&view_id, &text_field.id, AggregationKind::Sum)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::FieldInvalidOperation);
+}
@@ -21,7 +21,10 @@ async fn grid_cell_update() {
   for row_detail in rows.iter() {
     for field in &fields {
       let field_type = FieldType::from(field.field_type);
-      if field_type == FieldType::LastEditedTime || field_type == FieldType::CreatedTime {
+      if field_type == FieldType::LastEditedTime
+        || field_type == FieldType::CreatedTime
+        || field_type.is_user()
+      {
         continue;
       }
       let cell_changeset = match field_type {
@@ -19,7 +19,7 @@ use flowy_database2::services::field::{
   CheckboxTypeOption, MultiSelectTypeOption, SelectOption, SelectOptionCellChangeset,
   SingleSelectTypeOption,
 };
-use flowy_database2::services::share::csv::{CSVFormat, ImportResult};
+use flowy_database2::services::share::csv::{CSVFormat, CSVImportOptions, ImportResult};
 use flowy_error::FlowyResult;
 
 use crate::database::mock_data::{
@@ -268,7 +268,12 @@ impl DatabaseEditorTest {
     self
       .sdk
       .database_manager
-      .import_csv(gen_database_view_id(), s, format)
+      .import_csv(
+        gen_database_view_id(),
+        s,
+        format,
+        CSVImportOptions::default(),
+      )
       .await
       .unwrap()
   }
@@ -1,3 +1,5 @@
+use collab_database::database::gen_database_view_id;
+use collab_database::views::DatabaseLayout;
 use flowy_database2::entities::{FieldSettingsChangesetPB, FieldVisibility};
 
 use crate::database::database_editor::DatabaseEditorTest;
@@ -27,10 +29,23 @@ impl FieldSettingsTest {
     field_ids: Vec<String>,
     visibility: FieldVisibility,
     width: i32,
+  ) {
+    let view_id = self.view_id.clone();
+    self
+      .assert_view_field_settings(&view_id, field_ids, visibility, width)
+      .await;
+  }
+
+  pub async fn assert_view_field_settings(
+    &mut self,
+    view_id: &str,
+    field_ids: Vec<String>,
+    visibility: FieldVisibility,
+    width: i32,
   ) {
     let field_settings = self
       .editor
-      .get_field_settings(&self.view_id, field_ids)
+      .get_field_settings(view_id, field_ids)
       .await
       .unwrap();
 
@@ -53,6 +68,21 @@ impl FieldSettingsTest {
     }
   }
 
+  /// Creates a new view of the database and returns its id.
+  pub async fn create_linked_view(&self, layout: DatabaseLayout) -> String {
+    let database_manager = self.sdk.database_manager.clone();
+    let database_id = database_manager
+      .get_database_id_with_view_id(&self.view_id)
+      .await
+      .unwrap();
+    let view_id = gen_database_view_id();
+    database_manager
+      .create_linked_view("linked".to_string(), layout, database_id, view_id.clone())
+      .await
+      .unwrap();
+    view_id
+  }
+
   pub async fn update_field_settings(
     &mut self,
     field_id: String,
@@ -65,6 +95,7 @@ impl FieldSettingsTest {
       visibility,
       width,
       wrap_cell_content: None,
+      default_sort_condition: None,
     };
     let _ = self
       .editor
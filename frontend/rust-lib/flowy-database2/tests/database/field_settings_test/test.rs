@@ -1,6 +1,13 @@
+use std::collections::HashMap;
+
+use collab_database::fields::Field;
+use collab_database::views::DatabaseLayout;
 use flowy_database2::entities::FieldType;
 use flowy_database2::entities::FieldVisibility;
-use flowy_database2::services::field_settings::DEFAULT_WIDTH;
+use flowy_database2::entities::{FieldSettingsChangesetPB, SortConditionPB, UpdateSortPayloadPB};
+use flowy_database2::services::field_settings::{FieldSettingsBuilder, DEFAULT_WIDTH};
+use flowy_database2::services::sort::SortCondition;
+use flowy_error::ErrorCode;
 
 use crate::database::field_settings_test::script::FieldSettingsTest;
 
@@ -117,3 +124,306 @@ async fn update_field_settings_test() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn views_containing_field_and_field_usage_test() {
+  let mut test = FieldSettingsTest::new_grid().await;
+  let checkbox_field_id = test.get_first_field(FieldType::Checkbox).id;
+  let view_id = test.view_id.clone();
+  assert_eq!(
+    test.editor.views_containing_field(&checkbox_field_id),
+    vec![view_id.clone()]
+  );
+  assert!(!test.editor.field_usage(&checkbox_field_id).is_used());
+
+  test
+    .editor
+    .create_or_update_sort(UpdateSortPayloadPB {
+      view_id: view_id.clone(),
+      field_id: checkbox_field_id.clone(),
+      sort_id: None,
+      condition: SortConditionPB::Ascending,
+    })
+    .await
+    .unwrap();
+  let usage = test.editor.field_usage(&checkbox_field_id);
+  assert!(usage.is_used());
+  assert_eq!(usage.sort_view_ids, vec![view_id.clone()]);
+  assert!(usage.filter_view_ids.is_empty());
+  assert!(usage.group_view_ids.is_empty());
+  assert!(usage.calculation_view_ids.is_empty());
+
+  // A hidden field is no longer visible in the view
+  test
+    .update_field_settings(
+      checkbox_field_id.clone(),
+      Some(FieldVisibility::AlwaysHidden),
+      None,
+    )
+    .await;
+  assert!(test
+    .editor
+    .views_containing_field(&checkbox_field_id)
+    .is_empty());
+}
+
+#[tokio::test]
+async fn default_field_settings_override_for_new_views() {
+  let mut test = FieldSettingsTest::new_grid().await;
+  let hidden_field_id = test.get_first_field(FieldType::Checkbox).id;
+  let other_field_ids: Vec<String> = test
+    .get_fields()
+    .into_iter()
+    .filter(|field| field.id != hidden_field_id)
+    .map(|field| field.id)
+    .collect();
+  test
+    .editor
+    .set_default_field_settings(
+      DatabaseLayout::Grid,
+      vec![FieldSettingsBuilder::new(&hidden_field_id)
+        .visibility(FieldVisibility::AlwaysHidden)
+        .width(200)
+        .build()],
+    )
+    .unwrap();
+
+  // The new view picks up the override, and the other fields use the global defaults
+  let view_id = test.create_linked_view(DatabaseLayout::Grid).await;
+  test
+    .assert_view_field_settings(
+      &view_id,
+      vec![hidden_field_id.clone()],
+      FieldVisibility::AlwaysHidden,
+      200,
+    )
+    .await;
+  test
+    .assert_view_field_settings(
+      &view_id,
+      other_field_ids,
+      FieldVisibility::AlwaysShown,
+      DEFAULT_WIDTH,
+    )
+    .await;
+
+  // The existing view is unaffected
+  test
+    .assert_field_settings(
+      vec![hidden_field_id.clone()],
+      FieldVisibility::AlwaysShown,
+      DEFAULT_WIDTH,
+    )
+    .await;
+
+  // The override only applies to the views of its layout
+  let board_view_id = test.create_linked_view(DatabaseLayout::Board).await;
+  test
+    .assert_view_field_settings(
+      &board_view_id,
+      vec![hidden_field_id],
+      FieldVisibility::HideWhenEmpty,
+      DEFAULT_WIDTH,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn update_field_default_sort_condition_test() {
+  let test = FieldSettingsTest::new_grid().await;
+  let field_id = test.get_fields()[0].id.clone();
+  let field_settings = test
+    .editor
+    .get_field_settings(&test.view_id, vec![field_id.clone()])
+    .await
+    .unwrap();
+  assert!(matches!(
+    field_settings[0].default_sort_condition,
+    SortCondition::Ascending
+  ));
+
+  let params = FieldSettingsChangesetPB {
+    view_id: test.view_id.clone(),
+    field_id: field_id.clone(),
+    visibility: None,
+    width: None,
+    wrap_cell_content: None,
+    default_sort_condition: Some(SortConditionPB::Descending),
+  };
+  test
+    .editor
+    .update_field_settings_with_changeset(params)
+    .await
+    .unwrap();
+  let field_settings = test
+    .editor
+    .get_field_settings(&test.view_id, vec![field_id])
+    .await
+    .unwrap();
+  assert!(matches!(
+    field_settings[0].default_sort_condition,
+    SortCondition::Descending
+  ));
+  // The other settings are kept
+  assert_eq!(field_settings[0].width, DEFAULT_WIDTH);
+}
+
+#[tokio::test]
+async fn set_fields_visibility_test() {
+  let test = FieldSettingsTest::new_grid().await;
+  let field_ids = test
+    .get_fields()
+    .into_iter()
+    .map(|field| field.id)
+    .collect::<Vec<String>>();
+  let visibility = HashMap::from([
+    (field_ids[1].clone(), false),
+    (field_ids[2].clone(), false),
+    (field_ids[3].clone(), true),
+  ]);
+  test
+    .editor
+    .set_fields_visibility(&test.view_id, visibility)
+    .await
+    .unwrap();
+
+  let field_settings = test
+    .editor
+    .get_field_settings(&test.view_id, field_ids[0..4].to_vec())
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|field_settings| (field_settings.field_id.clone(), field_settings))
+    .collect::<HashMap<_, _>>();
+  assert_eq!(
+    field_settings[&field_ids[0]].visibility,
+    FieldVisibility::AlwaysShown
+  );
+  assert_eq!(
+    field_settings[&field_ids[1]].visibility,
+    FieldVisibility::AlwaysHidden
+  );
+  assert_eq!(
+    field_settings[&field_ids[2]].visibility,
+    FieldVisibility::AlwaysHidden
+  );
+  assert_eq!(
+    field_settings[&field_ids[3]].visibility,
+    FieldVisibility::AlwaysShown
+  );
+  // The other settings are kept
+  assert_eq!(field_settings[&field_ids[1]].width, DEFAULT_WIDTH);
+
+  // Nothing is changed if one of the fields isn't in the view
+  let visibility = HashMap::from([
+    (field_ids[3].clone(), false),
+    ("unknown field".to_string(), false),
+  ]);
+  assert!(test
+    .editor
+    .set_fields_visibility(&test.view_id, visibility)
+    .await
+    .is_err());
+  let field_settings = test
+    .editor
+    .get_field_settings(&test.view_id, vec![field_ids[3].clone()])
+    .await
+    .unwrap();
+  assert_eq!(field_settings[0].visibility, FieldVisibility::AlwaysShown);
+}
+
+#[tokio::test]
+async fn hide_all_fields_test() {
+  let test = FieldSettingsTest::new_grid().await;
+  let fields = test.get_fields();
+  let primary_field_id = test.get_first_field(FieldType::RichText).id;
+
+  // Hiding every field at once is rejected
+  let visibility = fields
+    .iter()
+    .map(|field| (field.id.clone(), false))
+    .collect::<HashMap<_, _>>();
+  let err = test
+    .editor
+    .set_fields_visibility(&test.view_id, visibility)
+    .await
+    .unwrap_err();
+  assert_eq!(err.code, ErrorCode::InvalidParams);
+
+  // Hiding the last visible field is rejected, so the primary field stays visible
+  let visibility = fields
+    .iter()
+    .filter(|field| field.id != primary_field_id)
+    .map(|field| (field.id.clone(), false))
+    .collect::<HashMap<_, _>>();
+  test
+    .editor
+    .set_fields_visibility(&test.view_id, visibility)
+    .await
+    .unwrap();
+  let params = FieldSettingsChangesetPB {
+    view_id: test.view_id.clone(),
+    field_id: primary_field_id.clone(),
+    visibility: Some(FieldVisibility::AlwaysHidden),
+    width: None,
+    wrap_cell_content: None,
+    default_sort_condition: None,
+  };
+  let err = test
+    .editor
+    .update_field_settings_with_changeset(params)
+    .await
+    .unwrap_err();
+  assert_eq!(err.code, ErrorCode::InvalidParams);
+  let field_settings = test
+    .editor
+    .get_field_settings(&test.view_id, vec![primary_field_id])
+    .await
+    .unwrap();
+  assert_eq!(field_settings[0].visibility, FieldVisibility::AlwaysShown);
+}
+
+#[tokio::test]
+async fn repair_missing_field_settings_on_open_test() {
+  let mut test = FieldSettingsTest::new_board().await;
+  let view_id = test.view_id.clone();
+  // The field is added without settings, as it may be after a sync conflict
+  let field = Field::new(
+    "field_without_settings".to_string(),
+    "Field without settings".to_string(),
+    FieldType::RichText.into(),
+    false,
+  );
+  let field_id = field.id.clone();
+  let field_ids = vec![field_id.clone()];
+  let has_field_settings = |test: &FieldSettingsTest| {
+    test
+      .editor
+      .get_mutex_database()
+      .lock()
+      .get_field_settings(&view_id, Some(field_ids.as_slice()))
+      .contains_key(&field_id)
+  };
+  test
+    .editor
+    .get_mutex_database()
+    .lock()
+    .fields
+    .insert_field(field);
+  assert!(!has_field_settings(&test));
+
+  test.editor.get_database_data(&view_id).await.unwrap();
+  assert!(has_field_settings(&test));
+  test
+    .assert_view_field_settings(
+      &view_id,
+      field_ids.clone(),
+      FieldVisibility::HideWhenEmpty,
+      DEFAULT_WIDTH,
+    )
+    .await;
+
+  // The repair is idempotent
+  let num_of_repaired = test.editor.repair_missing_field_settings(&view_id).unwrap();
+  assert_eq!(num_of_repaired, 0);
+}
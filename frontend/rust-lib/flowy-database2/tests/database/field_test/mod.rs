@@ -1,3 +1,4 @@
 mod script;
 mod test;
+mod type_option_registry_test;
 pub mod util;
@@ -1,7 +1,29 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use collab::core::collab_plugin::CollabPluginType;
+use collab::preclude::{CollabPlugin, TransactionMut};
 use collab_database::database::gen_option_id;
+use collab_database::fields::{Field, FieldChange};
+use collab_database::rows::{new_cell_builder, Cells, RowId};
+use collab_entity::define::DATABASE;
+use tokio::sync::broadcast;
+use tokio::time::timeout;
 
-use flowy_database2::entities::{FieldChangesetParams, FieldType};
-use flowy_database2::services::field::{SelectOption, SingleSelectTypeOption, CHECK, UNCHECK};
+use flowy_database2::entities::{
+  DatabaseFieldChangesetPB, FieldChangeOriginPB, FieldChangesetParams, FieldPB,
+  FieldSettingsChangesetPB, FieldSettingsPB, FieldType, FieldVisibility, RowsChangePB,
+  SelectOptionColorPB, SelectOptionPB,
+};
+use flowy_database2::notification::DatabaseNotification;
+use flowy_database2::services::cell::{insert_text_cell, stringify_cell};
+use flowy_database2::services::field::{
+  CellMigrationStrategy, DateCellData, RichTextTypeOption, SelectOption, SelectOptionColor,
+  SelectOptionIds, SingleSelectTypeOption, StringCellData, CELL_DATA, CHECK, UNCHECK,
+};
+use flowy_error::ErrorCode;
+use lib_infra::box_any::BoxAny;
 
 use crate::database::field_test::script::DatabaseFieldTest;
 use crate::database::field_test::script::FieldScript::*;
@@ -82,6 +104,36 @@ async fn grid_update_field_with_empty_change() {
   test.run_scripts(scripts).await;
 }
 
+#[tokio::test]
+async fn grid_update_field_description() {
+  let test = DatabaseFieldTest::new().await;
+  let field = test.get_first_field(FieldType::RichText);
+  let changeset = FieldChangesetParams {
+    field_id: field.id.clone(),
+    view_id: test.view_id(),
+    desc: Some("The name of the task".to_string()),
+    ..Default::default()
+  };
+  test.editor.update_field(changeset).await.unwrap();
+
+  let field = test.editor.get_field(&field.id).unwrap();
+  assert_eq!(
+    FieldPB::new(field.clone()).description,
+    Some("The name of the task".to_string())
+  );
+
+  // An empty description clears it
+  let changeset = FieldChangesetParams {
+    field_id: field.id.clone(),
+    view_id: test.view_id(),
+    desc: Some("".to_string()),
+    ..Default::default()
+  };
+  test.editor.update_field(changeset).await.unwrap();
+  let field = test.editor.get_field(&field.id).unwrap();
+  assert_eq!(FieldPB::new(field).description, None);
+}
+
 #[tokio::test]
 async fn grid_delete_field() {
   let mut test = DatabaseFieldTest::new().await;
@@ -299,3 +351,858 @@ async fn grid_switch_from_checklist_to_text_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn grid_switch_from_text_to_single_select_with_mapping_test() {
+  let test = DatabaseFieldTest::new().await;
+  let (params, _) = create_text_field(&test.view_id());
+  let field = test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  let rows = test.get_rows().await;
+  for (row, text) in rows.iter().zip(["High", "Med", "Low", "Unknown"]) {
+    test
+      .editor
+      .update_cell_with_changeset(
+        &test.view_id(),
+        &row.row.id,
+        &field.id,
+        BoxAny::new(text.to_string()),
+      )
+      .await
+      .unwrap();
+  }
+
+  let high = SelectOption::new("High");
+  let medium = SelectOption::new("Medium");
+  let low = SelectOption::new("Low");
+  let mapping = HashMap::from([
+    ("High".to_string(), high.clone()),
+    ("Med".to_string(), medium.clone()),
+    ("Low".to_string(), low.clone()),
+  ]);
+  test
+    .editor
+    .switch_to_field_type(
+      &field.id,
+      FieldType::SingleSelect,
+      Some(CellMigrationStrategy::SelectOptionMapping(mapping)),
+    )
+    .await
+    .unwrap();
+
+  let options = test.get_single_select_type_option(&field.id);
+  assert_eq!(options.len(), 3);
+  let expected_option_ids = [Some(&high.id), Some(&medium.id), Some(&low.id), None];
+  for (row, expected_option_id) in rows.iter().zip(expected_option_ids) {
+    let cell = test.editor.get_cell(&field.id, &row.row.id).await.unwrap();
+    let option_ids = SelectOptionIds::from(&cell);
+    // The text that isn't mapped is cleared
+    assert_eq!(option_ids.first(), expected_option_id);
+  }
+}
+
+#[tokio::test]
+async fn grid_switch_field_with_mapping_and_locked_row_test() {
+  let test = DatabaseFieldTest::new().await;
+  let (params, _) = create_text_field(&test.view_id());
+  let field = test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  let row_id = test.get_rows().await[0].row.id.clone();
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id(),
+      &row_id,
+      &field.id,
+      BoxAny::new("High".to_string()),
+    )
+    .await
+    .unwrap();
+  test.editor.set_row_locked(&row_id, true).await.unwrap();
+
+  let mapping = HashMap::from([("High".to_string(), SelectOption::new("High"))]);
+  let error = test
+    .editor
+    .switch_to_field_type(
+      &field.id,
+      FieldType::SingleSelect,
+      Some(CellMigrationStrategy::SelectOptionMapping(mapping)),
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::RecordLocked);
+
+  // Neither the field nor the cell is changed
+  let field = test.editor.get_field(&field.id).unwrap();
+  assert_eq!(FieldType::from(field.field_type), FieldType::RichText);
+  let cell = test.editor.get_cell(&field.id, &row_id).await.unwrap();
+  assert_eq!(stringify_cell(&cell, &field), "High");
+}
+
+#[tokio::test]
+async fn grid_text_field_validation_regex_test() {
+  let test = DatabaseFieldTest::new().await;
+  let (params, _) = create_text_field(&test.view_id());
+  let field = test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  let field = test.editor.get_field(&field.id).unwrap();
+  let row_id = test.get_rows().await[0].row.id.clone();
+
+  // The invalid pattern is rejected when the type option is set
+  let type_option = RichTextTypeOption {
+    validation_regex: Some("[0-9".to_string()),
+    ..Default::default()
+  };
+  let error = test
+    .editor
+    .update_field_type_option(&field.id, type_option.into(), field.clone())
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+
+  let type_option = RichTextTypeOption {
+    validation_regex: Some(r"\d{3}-\d{4}".to_string()),
+    ..Default::default()
+  };
+  test
+    .editor
+    .update_field_type_option(&field.id, type_option.into(), field.clone())
+    .await
+    .unwrap();
+
+  let view_id = test.view_id();
+  let error = test
+    .editor
+    .update_cell_with_changeset(
+      &view_id,
+      &row_id,
+      &field.id,
+      BoxAny::new("555-12345".to_string()),
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+  assert!(error.msg.contains("doesn't match the pattern"));
+
+  test
+    .editor
+    .update_cell_with_changeset(
+      &view_id,
+      &row_id,
+      &field.id,
+      BoxAny::new("555-1234".to_string()),
+    )
+    .await
+    .unwrap();
+  let cell = test.editor.get_cell(&field.id, &row_id).await.unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "555-1234");
+
+  // The cells written without a changeset are validated too
+  let error = test
+    .editor
+    .replace_in_field(&view_id, &field.id, "1234", "12345", true, false)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+  let error = test
+    .editor
+    .update_cell(
+      &view_id,
+      &row_id,
+      &field.id,
+      new_cell_builder(FieldType::RichText)
+        .insert_str_value(CELL_DATA, "555")
+        .build(),
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+  let cell = test.editor.get_cell(&field.id, &row_id).await.unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "555-1234");
+
+  // Empty text is allowed because the field isn't required
+  test
+    .editor
+    .update_cell_with_changeset(&view_id, &row_id, &field.id, BoxAny::new(String::new()))
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn grid_get_select_options_test() {
+  let test = DatabaseFieldTest::new().await;
+  let field = test.get_first_field(FieldType::SingleSelect);
+
+  // Add an option that no cell uses
+  let mut options = test.get_single_select_type_option(&field.id);
+  let unused_option = SelectOption::with_color("Unused", SelectOptionColor::Orange);
+  options.push(unused_option.clone());
+  let type_option = SingleSelectTypeOption {
+    options: options.clone(),
+    disable_color: false,
+  };
+  test
+    .editor
+    .update_field_type_option(&field.id, type_option.into(), field.clone())
+    .await
+    .unwrap();
+
+  let select_options = test.editor.get_select_options(&field.id).await.unwrap();
+  assert_eq!(
+    select_options,
+    options
+      .into_iter()
+      .map(SelectOptionPB::from)
+      .collect::<Vec<_>>()
+  );
+  assert_eq!(
+    select_options.last().unwrap().color,
+    SelectOptionColorPB::Orange
+  );
+
+  let text_field = test.get_first_field(FieldType::RichText);
+  let error = test
+    .editor
+    .get_select_options(&text_field.id)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::FieldInvalidOperation);
+}
+
+#[tokio::test]
+async fn grid_field_change_origin_test() {
+  let test = DatabaseFieldTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let number_field = test.get_first_field(FieldType::Number);
+  let mut rx = test
+    .sdk
+    .notification_sender
+    .subscribe::<DatabaseFieldChangesetPB>(&test.view_id(), DatabaseNotification::DidUpdateFields);
+
+  // The field is renamed on this device
+  let changeset = FieldChangesetParams {
+    field_id: text_field.id.clone(),
+    view_id: test.view_id(),
+    name: Some("Local name".to_string()),
+    ..Default::default()
+  };
+  test.editor.update_field(changeset).await.unwrap();
+  let changeset = timeout(Duration::from_secs(5), rx.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(changeset.origin, FieldChangeOriginPB::Local);
+  assert_eq!(changeset.updated_fields[0].name, "Local name");
+
+  // The field is renamed without the editor but with the local origin, e.g. by a migration
+  test
+    .editor
+    .get_mutex_database()
+    .lock()
+    .fields
+    .update_field(&number_field.id, |update| {
+      update.set_name_if_not_none(Some("Migrated name".to_string()));
+    });
+
+  // The same field is renamed right away by a transaction with another origin, like a change
+  // synced from a collaborator
+  {
+    let database = test.editor.get_mutex_database().lock();
+    let collab = database.get_collab().lock();
+    let mut txn = collab.transact_mut();
+    let field = collab
+      .get_map_with_txn(&txn, vec![DATABASE, "fields", text_field.id.as_str()])
+      .unwrap();
+    field.insert_str_with_txn(&mut txn, "name", "Remote name");
+  }
+  let changeset = timeout(Duration::from_secs(5), rx.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(changeset.origin, FieldChangeOriginPB::Remote);
+  assert_eq!(changeset.updated_fields[0].id, text_field.id);
+  assert_eq!(changeset.updated_fields[0].name, "Remote name");
+}
+
+/// Records whether the field change of the transaction was already sent when the plugin received
+/// the update of the transaction.
+struct FieldChangeOrderPlugin {
+  field_change: parking_lot::Mutex<broadcast::Receiver<FieldChange>>,
+  is_change_sent_first: Arc<parking_lot::Mutex<Vec<bool>>>,
+}
+
+impl CollabPlugin for FieldChangeOrderPlugin {
+  fn receive_update(&self, _object_id: &str, _txn: &TransactionMut, _update: &[u8]) {
+    let is_change_sent = self.field_change.lock().try_recv().is_ok();
+    self.is_change_sent_first.lock().push(is_change_sent);
+  }
+
+  fn plugin_type(&self) -> CollabPluginType {
+    CollabPluginType::Other("FieldChangeOrderPlugin".to_string())
+  }
+}
+
+/// The origin of the field changes is found by relying on the collab to send the field changes
+/// of a transaction before the plugins receive its update. This test fails if a collab upgrade
+/// changes that order.
+#[tokio::test]
+async fn grid_field_change_is_sent_before_update_test() {
+  let test = DatabaseFieldTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let is_change_sent_first = Arc::new(parking_lot::Mutex::new(vec![]));
+  {
+    let database = test.editor.get_mutex_database().lock();
+    let plugin = FieldChangeOrderPlugin {
+      field_change: parking_lot::Mutex::new(database.subscribe_field_change()),
+      is_change_sent_first: is_change_sent_first.clone(),
+    };
+    database.get_collab().lock().add_plugin(Box::new(plugin));
+  }
+
+  {
+    let database = test.editor.get_mutex_database().lock();
+    let collab = database.get_collab().lock();
+    let mut txn = collab.transact_mut();
+    let field = collab
+      .get_map_with_txn(&txn, vec![DATABASE, "fields", text_field.id.as_str()])
+      .unwrap();
+    field.insert_str_with_txn(&mut txn, "name", "Remote name");
+  }
+  assert_eq!(*is_change_sent_first.lock(), vec![true]);
+}
+
+#[tokio::test]
+async fn grid_create_field_notifies_field_settings_test() {
+  let test = DatabaseFieldTest::new().await;
+  let mut rx = test.sdk.notification_sender.subscribe::<FieldSettingsPB>(
+    &test.view_id(),
+    DatabaseNotification::DidUpdateFieldSettings,
+  );
+
+  let (params, _) = create_text_field(&test.view_id());
+  let field = test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  let field_settings = timeout(Duration::from_secs(5), rx.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(field_settings.field_id, field.id);
+
+  // The notification carries the settings stored for the new field
+  let stored_field_settings = test
+    .editor
+    .get_field_settings(&test.view_id(), vec![field.id.clone()])
+    .await
+    .unwrap()
+    .remove(0);
+  assert_eq!(field_settings.width, stored_field_settings.width);
+  assert_eq!(field_settings.visibility, stored_field_settings.visibility);
+}
+
+#[tokio::test]
+async fn set_primary_field_test() {
+  let test = DatabaseFieldTest::new().await;
+  let view_id = test.view_id();
+  let old_primary_field = test.get_first_field(FieldType::RichText);
+  assert!(old_primary_field.is_primary);
+  let (params, _) = create_text_field(&view_id);
+  let new_primary_field = test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  let row_id = test.row_details[0].row.id.clone();
+  test
+    .editor
+    .update_cell_with_changeset(
+      &view_id,
+      &row_id,
+      &new_primary_field.id,
+      BoxAny::new("new title".to_string()),
+    )
+    .await
+    .unwrap();
+
+  // Only a text field can be the primary field
+  let number_field = test.get_first_field(FieldType::Number);
+  let error = test
+    .editor
+    .set_primary_field(&number_field.id)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+
+  test
+    .editor
+    .set_primary_field(&new_primary_field.id)
+    .await
+    .unwrap();
+  let old_primary_field = test.get_field(&old_primary_field.id, FieldType::RichText);
+  let new_primary_field = test.get_field(&new_primary_field.id, FieldType::RichText);
+  assert!(!old_primary_field.is_primary);
+  assert!(new_primary_field.is_primary);
+
+  // The titles of the rows are read from the new primary field
+  assert_eq!(
+    test
+      .editor
+      .find_rows_by_primary_value("new title")
+      .await
+      .unwrap(),
+    vec![row_id.clone()]
+  );
+  let related_rows = test
+    .editor
+    .get_related_rows(Some(&vec![row_id.to_string()]))
+    .await
+    .unwrap();
+  assert_eq!(related_rows[0].name, "new title");
+}
+
+#[tokio::test]
+async fn schema_version_test() {
+  let test = DatabaseFieldTest::new().await;
+  let view_id = test.view_id();
+  let schema = test.editor.get_schema(&view_id).await.unwrap();
+  let num_of_fields = schema.fields.len();
+  assert!(test
+    .editor
+    .get_schema_if_changed(&view_id, schema.version)
+    .await
+    .unwrap()
+    .is_none());
+
+  // Creating a field bumps the version
+  let (params, _) = create_text_field(&view_id);
+  let field = test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  let schema = test
+    .editor
+    .get_schema_if_changed(&view_id, schema.version)
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(schema.fields.len(), num_of_fields + 1);
+  let mut version = schema.version;
+
+  // Updating the type option bumps the version
+  let field = test.editor.get_field(&field.id).unwrap();
+  let type_option = RichTextTypeOption {
+    validation_regex: Some(r"\d+".to_string()),
+    ..Default::default()
+  };
+  test
+    .editor
+    .update_field_type_option(&field.id, type_option.into(), field.clone())
+    .await
+    .unwrap();
+  let schema = test.editor.get_schema(&view_id).await.unwrap();
+  assert!(schema.version > version);
+  version = schema.version;
+
+  // Switching the field type bumps the version
+  test
+    .editor
+    .switch_to_field_type(&field.id, FieldType::Number, None)
+    .await
+    .unwrap();
+  let schema = test
+    .editor
+    .get_schema_if_changed(&view_id, version)
+    .await
+    .unwrap()
+    .unwrap();
+  assert!(schema.version > version);
+  let switched_field = schema
+    .fields
+    .iter()
+    .find(|schema_field| schema_field.id == field.id)
+    .unwrap();
+  assert_eq!(switched_field.field_type, FieldType::Number);
+  version = schema.version;
+
+  // Deleting a field bumps the version
+  test.editor.delete_field(&field.id).await.unwrap();
+  let schema = test.editor.get_schema(&view_id).await.unwrap();
+  assert!(schema.version > version);
+  assert_eq!(schema.fields.len(), num_of_fields);
+}
+
+#[tokio::test]
+async fn system_field_test() {
+  let test = DatabaseFieldTest::new().await;
+  let view_id = test.view_id();
+  let (mut params, _) = create_text_field(&view_id);
+  params.is_system = true;
+  let field = test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  assert!(field.is_system);
+
+  // The system field is only listed when it's asked for
+  let is_listed = |fields: Vec<Field>| fields.iter().any(|listed| listed.id == field.id);
+  assert!(!is_listed(test.editor.get_fields(&view_id, None)));
+  assert!(is_listed(
+    test
+      .editor
+      .get_fields(&view_id, Some(vec![field.id.clone()]))
+  ));
+  assert!(is_listed(
+    test.editor.get_fields_including_system(&view_id, None)
+  ));
+
+  // Its cells can be written and read
+  let row_id = test.get_rows().await[0].row.id.clone();
+  test
+    .editor
+    .update_cell_with_changeset(
+      &view_id,
+      &row_id,
+      &field.id,
+      BoxAny::new("external-1".to_string()),
+    )
+    .await
+    .unwrap();
+  let cell = test.editor.get_cell(&field.id, &row_id).await.unwrap();
+  let system_field = test.editor.get_field(&field.id).unwrap();
+  assert_eq!(stringify_cell(&cell, &system_field), "external-1");
+
+  // It's always hidden
+  let error = test
+    .editor
+    .set_fields_visibility(&view_id, HashMap::from([(field.id.clone(), true)]))
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+  let error = test
+    .editor
+    .update_field_settings_with_changeset(FieldSettingsChangesetPB {
+      view_id: view_id.clone(),
+      field_id: field.id.clone(),
+      visibility: Some(FieldVisibility::AlwaysShown),
+      width: None,
+      wrap_cell_content: None,
+      default_sort_condition: None,
+    })
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+
+  // It's only exported to JSON if it's asked for
+  let json = test.editor.export_json(&view_id, false).await.unwrap();
+  assert!(!json.contains(&field.id));
+  let json = test.editor.export_json(&view_id, true).await.unwrap();
+  assert!(json.contains("external-1"));
+}
+
+#[tokio::test]
+async fn subscribe_cell_changed_of_field_test() {
+  let test = DatabaseFieldTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let url_field = test.get_first_field(FieldType::URL);
+  let row_id = test.get_rows().await[0].row.id.clone();
+  let mut receiver = test
+    .editor
+    .subscribe_cell_changed(&test.view_id, &text_field.id)
+    .await
+    .unwrap();
+
+  // Changes of other fields are not forwarded
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &url_field.id,
+      BoxAny::new("https://appflowy.io".to_string()),
+    )
+    .await
+    .unwrap();
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &text_field.id,
+      BoxAny::new("hello".to_string()),
+    )
+    .await
+    .unwrap();
+
+  let changeset = tokio::time::timeout(Duration::from_secs(2), receiver.recv())
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(changeset.field_id, text_field.id);
+  assert_eq!(changeset.row_id, row_id.to_string());
+
+  // The receiver is closed after the view is closed
+  test.editor.close_view(&test.view_id).await;
+  let result = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await;
+  assert!(matches!(result, Ok(Err(_))));
+}
+
+#[tokio::test]
+async fn replace_text_in_field_test() {
+  let test = DatabaseFieldTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let number_field = test.get_first_field(FieldType::Number);
+  let rows = test.get_rows().await;
+
+  // The dry run only counts the cells: "A", "DA", "AE" and "AE"
+  let count = test
+    .editor
+    .replace_in_field(&test.view_id, &text_field.id, "a", "x", false, true)
+    .await
+    .unwrap();
+  assert_eq!(count, 4);
+  let cell = test
+    .editor
+    .get_cell(&text_field.id, &rows[0].row.id)
+    .await
+    .unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "A");
+
+  let count = test
+    .editor
+    .replace_in_field(&test.view_id, &text_field.id, "a", "x", true, false)
+    .await
+    .unwrap();
+  assert_eq!(count, 0);
+
+  test
+    .editor
+    .set_row_locked(&rows[3].row.id, true)
+    .await
+    .unwrap();
+  let count = test
+    .editor
+    .replace_in_field(&test.view_id, &text_field.id, "a", "x", false, false)
+    .await
+    .unwrap();
+  assert_eq!(count, 3);
+  let cell = test
+    .editor
+    .get_cell(&text_field.id, &rows[0].row.id)
+    .await
+    .unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "x");
+  let cell = test
+    .editor
+    .get_cell(&text_field.id, &rows[3].row.id)
+    .await
+    .unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "DA");
+
+  let error = test
+    .editor
+    .replace_in_field(&test.view_id, &number_field.id, "1", "2", false, false)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::FieldInvalidOperation);
+}
+
+#[tokio::test]
+async fn clear_large_field_test() {
+  let test = DatabaseFieldTest::new().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let num_of_rows = test.get_rows().await.len();
+  let new_rows = (num_of_rows..1000)
+    .map(|i| {
+      let mut cells = Cells::new();
+      cells.insert(
+        text_field.id.clone(),
+        insert_text_cell(format!("row {}", i), &text_field),
+      );
+      cells
+    })
+    .collect::<Vec<_>>();
+  test
+    .editor
+    .create_rows_batch(&test.view_id, new_rows)
+    .await
+    .unwrap();
+  let rows = test.get_rows().await;
+  assert_eq!(rows.len(), 1000);
+  let num_of_cells = test
+    .editor
+    .get_cells_for_field(&test.view_id, &text_field.id)
+    .await
+    .into_iter()
+    .filter(|row_cell| row_cell.cell.is_some())
+    .count();
+
+  let mut rx = test
+    .sdk
+    .notification_sender
+    .subscribe::<RowsChangePB>(&test.view_id, DatabaseNotification::DidUpdateRow);
+  test
+    .editor
+    .clear_field(&test.view_id, &text_field.id)
+    .await
+    .unwrap();
+  for row in rows.iter() {
+    let text = test
+      .editor
+      .get_cell(&text_field.id, &row.row.id)
+      .await
+      .map(|cell| StringCellData::from(&cell).as_str().to_string())
+      .unwrap_or_default();
+    assert!(text.is_empty());
+  }
+
+  // All the cleared rows are sent in a single notification
+  let mut updated_rows = vec![];
+  while let Ok(Some(changeset)) = tokio::time::timeout(Duration::from_secs(2), rx.recv()).await {
+    if changeset.updated_rows.len() > 1 {
+      updated_rows.push(changeset.updated_rows.len());
+    }
+  }
+  assert_eq!(updated_rows, vec![num_of_cells]);
+}
+
+#[tokio::test]
+async fn set_date_cell_with_timezone_test() {
+  let test = DatabaseFieldTest::new().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  let row_id = test.get_rows().await[0].row.id.clone();
+
+  test
+    .editor
+    .set_date_cell(
+      &test.view_id,
+      &row_id,
+      &date_field.id,
+      "2024-01-15 09:30",
+      "Europe/Paris",
+      true,
+      None,
+    )
+    .await
+    .unwrap();
+  let cell = test.editor.get_cell(&date_field.id, &row_id).await.unwrap();
+  let cell_data = DateCellData::from(&cell);
+  assert_eq!(cell_data.timestamp, Some(1705307400));
+  assert!(cell_data.include_time);
+  assert!(!cell_data.is_range);
+
+  test
+    .editor
+    .set_date_cell(
+      &test.view_id,
+      &row_id,
+      &date_field.id,
+      "2024-01-15 09:30",
+      "Europe/Paris",
+      true,
+      Some("2024-01-16 10:00"),
+    )
+    .await
+    .unwrap();
+  let cell = test.editor.get_cell(&date_field.id, &row_id).await.unwrap();
+  let cell_data = DateCellData::from(&cell);
+  assert!(cell_data.is_range);
+  assert_eq!(cell_data.end_timestamp, Some(1705395600));
+
+  // The end must not be before the start
+  let error = test
+    .editor
+    .set_date_cell(
+      &test.view_id,
+      &row_id,
+      &date_field.id,
+      "2024-01-15 09:30",
+      "Europe/Paris",
+      true,
+      Some("2024-01-14 09:30"),
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+
+  let error = test
+    .editor
+    .set_date_cell(
+      &test.view_id,
+      &row_id,
+      &date_field.id,
+      "2024-01-15 09:30",
+      "Mars/Olympus",
+      true,
+      None,
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+}
+
+#[tokio::test]
+async fn get_cells_for_fields_test() {
+  let test = DatabaseFieldTest::new().await;
+  let fields = test.get_fields();
+  let field_ids = fields
+    .iter()
+    .map(|field| field.id.clone())
+    .collect::<Vec<_>>();
+  let cells = test
+    .editor
+    .get_cells_for_fields(&test.view_id, field_ids, None)
+    .await
+    .unwrap();
+  assert_eq!(cells.len(), test.row_details.len());
+
+  // The cells are the same as the ones returned one by one, including the timestamp cells
+  for row_detail in &test.row_details {
+    let row_cells = &cells[&row_detail.row.id];
+    for field in &fields {
+      let expected = test.editor.get_cell(&field.id, &row_detail.row.id).await;
+      assert_eq!(
+        row_cells
+          .get(&field.id)
+          .map(|cell| stringify_cell(cell, field)),
+        expected.map(|cell| stringify_cell(&cell, field)),
+        "{}",
+        field.name
+      );
+    }
+  }
+
+  // The rows that aren't in the view and the fields that don't exist are skipped
+  let row_id = test.row_details[0].row.id.clone();
+  let created_time_field = test.get_first_field(FieldType::CreatedTime);
+  let cells = test
+    .editor
+    .get_cells_for_fields(
+      &test.view_id,
+      vec![created_time_field.id.clone(), "unknown".to_string()],
+      Some(vec![row_id.clone(), RowId::from("unknown".to_string())]),
+    )
+    .await
+    .unwrap();
+  assert_eq!(cells.len(), 1);
+  assert_eq!(cells[&row_id].len(), 1);
+  assert!(cells[&row_id].contains_key(&created_time_field.id));
+
+  let result = test
+    .editor
+    .get_cells_for_fields("unknown", vec![], None)
+    .await;
+  assert!(result.is_err());
+}
@@ -0,0 +1,140 @@
+use std::cmp::Ordering;
+
+use collab::core::any_map::AnyMapExtension;
+use collab_database::fields::Field;
+use collab_database::rows::{new_cell_builder, Cell};
+
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::cell::{
+  apply_cell_changeset, get_cell_protobuf, insert_text_cell, stringify_cell, CellProtobufBlob,
+};
+use flowy_database2::services::field::{
+  BoxCellData, TypeOptionCellDataHandler, TypeOptionCellExt, TypeOptionRegistry, CELL_DATA,
+};
+use flowy_database2::services::sort::SortCondition;
+use flowy_error::{ErrorCode, FlowyResult};
+use lib_infra::box_any::BoxAny;
+
+/// A rating from 0 to 5 stars, stored as the number of stars.
+struct RatingCellDataHandler {
+  field_type: i64,
+}
+
+impl RatingCellDataHandler {
+  fn rating(cell: &Cell) -> i64 {
+    cell
+      .get_str_value(CELL_DATA)
+      .and_then(|value| value.parse::<i64>().ok())
+      .unwrap_or_default()
+  }
+}
+
+impl TypeOptionCellDataHandler for RatingCellDataHandler {
+  fn handle_get_boxed_cell_data(&self, cell: &Cell, _field: &Field) -> Option<BoxCellData> {
+    Some(BoxCellData::new(Self::rating(cell)))
+  }
+
+  fn handle_get_protobuf_cell_data(
+    &self,
+    cell: &Cell,
+    _field_rev: &Field,
+  ) -> FlowyResult<CellProtobufBlob> {
+    Ok(CellProtobufBlob::new(Self::rating(cell).to_string()))
+  }
+
+  fn handle_cell_changeset(
+    &self,
+    cell_changeset: BoxAny,
+    _old_cell: Option<Cell>,
+    _field: &Field,
+  ) -> FlowyResult<Cell> {
+    let rating = cell_changeset.unbox_or_error::<i64>()?.clamp(0, 5);
+    Ok(
+      new_cell_builder(self.field_type)
+        .insert_str_value(CELL_DATA, rating.to_string())
+        .build(),
+    )
+  }
+
+  fn handle_cell_compare(
+    &self,
+    left_cell: Option<&Cell>,
+    right_cell: Option<&Cell>,
+    _field: &Field,
+    sort_condition: SortCondition,
+  ) -> Ordering {
+    let order = left_cell
+      .map(Self::rating)
+      .cmp(&right_cell.map(Self::rating));
+    match sort_condition {
+      SortCondition::Ascending => order,
+      SortCondition::Descending => order.reverse(),
+    }
+  }
+
+  fn handle_cell_filter(&self, _field: &Field, _cell: &Cell, _filter: &BoxAny) -> bool {
+    true
+  }
+
+  fn handle_stringify_cell(&self, cell: &Cell, _field: &Field) -> String {
+    "★".repeat(Self::rating(cell) as usize)
+  }
+
+  fn handle_numeric_cell(&self, cell: &Cell) -> Option<f64> {
+    Some(Self::rating(cell) as f64)
+  }
+
+  fn handle_is_cell_empty(&self, cell: &Cell, _field: &Field) -> bool {
+    Self::rating(cell) == 0
+  }
+}
+
+#[test]
+fn custom_field_type_handler_test() {
+  // Each test uses its own field type, the registry is shared by the tests
+  let rating_field_type = 1000;
+  TypeOptionRegistry::global()
+    .register(rating_field_type, move |_, _| {
+      Some(Box::new(RatingCellDataHandler {
+        field_type: rating_field_type,
+      }) as Box<dyn TypeOptionCellDataHandler>)
+    })
+    .unwrap();
+  let field = Field::new(
+    "rating".to_string(),
+    "Rating".to_string(),
+    rating_field_type,
+    false,
+  );
+
+  let cell = apply_cell_changeset(BoxAny::new(7_i64), None, &field, None).unwrap();
+  assert_eq!(stringify_cell(&cell, &field), "★★★★★");
+  assert_eq!(get_cell_protobuf(&cell, &field, None).to_string(), "5");
+  let handler = TypeOptionCellExt::new(&field, None)
+    .get_type_option_cell_data_handler()
+    .unwrap();
+  assert_eq!(handler.handle_numeric_cell(&cell), Some(5.0));
+
+  TypeOptionRegistry::global().unregister(rating_field_type);
+  assert!(!TypeOptionRegistry::global().is_registered(rating_field_type));
+}
+
+#[test]
+fn register_built_in_field_type_test() {
+  let error = TypeOptionRegistry::global()
+    .register(FieldType::Number.into(), |_, _| None)
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::InvalidParams);
+}
+
+#[test]
+fn unknown_custom_field_type_is_read_as_text_test() {
+  // The field was created by a plugin that isn't registered
+  let field = Field::new("color".to_string(), "Color".to_string(), 1001, false);
+  assert!(TypeOptionCellExt::new(&field, None)
+    .get_type_option_cell_data_handler()
+    .is_some());
+
+  let cell = insert_text_cell("#FF0000".to_string(), &field);
+  assert_eq!(stringify_cell(&cell, &field), "#FF0000");
+}
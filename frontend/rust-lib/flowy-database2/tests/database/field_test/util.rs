@@ -22,6 +22,7 @@ pub fn create_text_field(grid_id: &str) -> (CreateFieldParams, Field) {
     type_option_data: Some(type_option_data),
     field_name: None,
     position: OrderObjectPosition::default(),
+    is_system: false,
   };
   (params, text_field)
 }
@@ -42,6 +43,7 @@ pub fn create_single_select_field(grid_id: &str) -> (CreateFieldParams, Field) {
     type_option_data: Some(type_option_data),
     field_name: None,
     position: OrderObjectPosition::default(),
+    is_system: false,
   };
   (params, single_select_field)
 }
@@ -65,6 +67,7 @@ pub fn create_date_field(grid_id: &str) -> (CreateFieldParams, Field) {
     type_option_data: Some(type_option_data),
     field_name: None,
     position: OrderObjectPosition::default(),
+    is_system: false,
   };
   (params, field)
 }
@@ -95,6 +98,7 @@ pub fn create_timestamp_field(grid_id: &str, field_type: FieldType) -> (CreateFi
     type_option_data: Some(type_option_data),
     field_name: None,
     position: OrderObjectPosition::default(),
+    is_system: false,
   };
   (params, field)
 }
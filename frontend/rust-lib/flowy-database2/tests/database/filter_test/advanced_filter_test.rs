@@ -91,6 +91,7 @@ async fn create_advanced_filter_test() {
               field_id: "".to_string(),
               field_type: FieldType::Checkbox,
               data: checkbox_filter_bytes.clone(),
+              emptiness: None,
             }),
           },
           FilterPB {
@@ -142,6 +143,7 @@ async fn create_advanced_filter_test() {
               field_id: "".to_string(),
               field_type: FieldType::Checkbox,
               data: checkbox_filter_bytes,
+              emptiness: None,
             }),
           },
           FilterPB {
@@ -156,6 +158,7 @@ async fn create_advanced_filter_test() {
                   field_id: "".to_string(),
                   field_type: FieldType::DateTime,
                   data: date_filter_bytes,
+                  emptiness: None,
                 }),
               },
               FilterPB {
@@ -166,6 +169,7 @@ async fn create_advanced_filter_test() {
                   field_id: "".to_string(),
                   field_type: FieldType::Number,
                   data: number_filter_bytes,
+                  emptiness: None,
                 }),
               },
             ],
@@ -274,6 +278,7 @@ async fn create_advanced_filter_with_conversion_test() {
               field_id: "".to_string(),
               field_type: FieldType::Checkbox,
               data: checkbox_filter_bytes,
+              emptiness: None,
             }),
           },
           FilterPB {
@@ -288,6 +293,7 @@ async fn create_advanced_filter_with_conversion_test() {
                   field_id: "".to_string(),
                   field_type: FieldType::DateTime,
                   data: date_filter_bytes,
+                  emptiness: None,
                 }),
               },
               FilterPB {
@@ -298,6 +304,7 @@ async fn create_advanced_filter_with_conversion_test() {
                   field_id: "".to_string(),
                   field_type: FieldType::Number,
                   data: number_filter_bytes,
+                  emptiness: None,
                 }),
               },
             ],
@@ -49,3 +49,72 @@ async fn grid_filter_checkbox_is_uncheck_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn grid_filter_checkbox_is_unset_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  // Only one row has never set the checkbox
+  let expected = 1;
+  let row_count = test.row_details.len();
+  let scripts = vec![
+    CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::Checkbox,
+      data: BoxAny::new(CheckboxFilterPB {
+        condition: CheckboxFilterConditionPB::IsUnset,
+      }),
+      changed: Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    },
+    AssertNumberOfVisibleRows { expected },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_checkbox_is_explicitly_uncheck_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let expected = 3;
+  let row_count = test.row_details.len();
+  let scripts = vec![
+    CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::Checkbox,
+      data: BoxAny::new(CheckboxFilterPB {
+        condition: CheckboxFilterConditionPB::IsExplicitlyUnChecked,
+      }),
+      changed: Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    },
+    AssertNumberOfVisibleRows { expected },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_open_profile_with_checkbox_filter_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.row_details.len();
+  let scripts = vec![CreateDataFilter {
+    parent_filter_id: None,
+    field_type: FieldType::Checkbox,
+    data: BoxAny::new(CheckboxFilterPB {
+      condition: CheckboxFilterConditionPB::IsChecked,
+    }),
+    changed: None,
+  }];
+  test.run_scripts(scripts).await;
+
+  let database = test.editor.get_database_data(&test.view_id).await.unwrap();
+  let profiles = test.sdk.database_manager.last_open_profiles();
+  let profile = profiles.last().unwrap();
+  assert_eq!(profile.view_id, test.view_id);
+  assert_eq!(profile.num_of_rows, row_count);
+  assert_eq!(profile.num_of_visible_rows, 3);
+  assert_eq!(profile.num_of_visible_rows, database.rows.len());
+  assert!(profile.total >= profile.load_rows + profile.filter + profile.sort);
+}
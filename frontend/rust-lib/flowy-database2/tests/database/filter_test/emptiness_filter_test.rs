@@ -0,0 +1,74 @@
+use flowy_database2::entities::EmptinessFilterConditionPB::{IsEmpty, IsNotEmpty};
+use flowy_database2::entities::{EmptinessFilterConditionPB, EmptinessFilterPB, FieldType};
+use lib_infra::box_any::BoxAny;
+
+use crate::database::filter_test::script::FilterScript::*;
+use crate::database::filter_test::script::{DatabaseFilterTest, FilterRowChanged};
+
+#[tokio::test]
+async fn grid_filter_text_is_empty_test() {
+  assert_emptiness_filter(FieldType::RichText, IsEmpty, 1).await;
+}
+
+#[tokio::test]
+async fn grid_filter_text_is_not_empty_test() {
+  assert_emptiness_filter(FieldType::RichText, IsNotEmpty, 6).await;
+}
+
+#[tokio::test]
+async fn grid_filter_number_is_empty_test() {
+  assert_emptiness_filter(FieldType::Number, IsEmpty, 2).await;
+}
+
+#[tokio::test]
+async fn grid_filter_date_is_empty_test() {
+  assert_emptiness_filter(FieldType::DateTime, IsEmpty, 1).await;
+}
+
+#[tokio::test]
+async fn grid_filter_single_select_is_empty_test() {
+  assert_emptiness_filter(FieldType::SingleSelect, IsEmpty, 3).await;
+}
+
+#[tokio::test]
+async fn grid_filter_multi_select_is_not_empty_test() {
+  assert_emptiness_filter(FieldType::MultiSelect, IsNotEmpty, 5).await;
+}
+
+#[tokio::test]
+async fn grid_filter_checklist_is_empty_test() {
+  assert_emptiness_filter(FieldType::Checklist, IsEmpty, 3).await;
+}
+
+#[tokio::test]
+async fn grid_filter_url_is_not_empty_test() {
+  assert_emptiness_filter(FieldType::URL, IsNotEmpty, 1).await;
+}
+
+#[tokio::test]
+async fn grid_filter_checkbox_is_never_empty_test() {
+  // An unchecked checkbox is a value, so none of the checkbox cells are empty
+  assert_emptiness_filter(FieldType::Checkbox, IsEmpty, 0).await;
+}
+
+async fn assert_emptiness_filter(
+  field_type: FieldType,
+  condition: EmptinessFilterConditionPB,
+  expected: usize,
+) {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_count = test.row_details.len();
+  let scripts = vec![
+    CreateDataFilter {
+      parent_filter_id: None,
+      field_type,
+      data: BoxAny::new(EmptinessFilterPB { condition }),
+      changed: Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: row_count - expected,
+      }),
+    },
+    AssertNumberOfVisibleRows { expected },
+  ];
+  test.run_scripts(scripts).await;
+}
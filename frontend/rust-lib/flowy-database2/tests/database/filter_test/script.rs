@@ -68,6 +68,10 @@ pub enum FilterScript {
   AssertNumberOfVisibleRows {
     expected: usize,
   },
+  AssertRowVisible {
+    row_id: RowId,
+    expected: bool,
+  },
   AssertFilters {
     /// 1. assert that the filter type is correct
     /// 2. if the filter is data, assert that the field_type, condition and content are correct
@@ -304,6 +308,14 @@ impl DatabaseFilterTest {
         let grid = self.editor.get_database_data(&self.view_id).await.unwrap();
         assert_eq!(grid.rows.len(), expected);
       },
+      FilterScript::AssertRowVisible { row_id, expected } => {
+        let is_visible = self
+          .editor
+          .is_row_visible(&self.view_id, &row_id)
+          .await
+          .unwrap();
+        assert_eq!(is_visible, expected);
+      },
       FilterScript::Wait { millisecond } => {
         tokio::time::sleep(Duration::from_millis(millisecond)).await;
       },
@@ -190,6 +190,48 @@ async fn grid_filter_multi_select_contains_test() {
   test.run_scripts(scripts).await;
 }
 
+#[tokio::test]
+async fn grid_filter_multi_select_contains_all_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let field = test.get_first_field(FieldType::MultiSelect);
+  let mut options = test.get_multi_select_type_option(&field.id);
+  let scripts = vec![
+    CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::MultiSelect,
+      data: BoxAny::new(SelectOptionFilterPB {
+        condition: SelectOptionFilterConditionPB::OptionContainsAll,
+        option_ids: vec![options.remove(0).id, options.remove(0).id],
+      }),
+      changed: None,
+    },
+    // One of the rows has all the three options
+    AssertNumberOfVisibleRows { expected: 2 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn grid_filter_multi_select_does_not_contain_all_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let field = test.get_first_field(FieldType::MultiSelect);
+  let mut options = test.get_multi_select_type_option(&field.id);
+  let scripts = vec![
+    CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::MultiSelect,
+      data: BoxAny::new(SelectOptionFilterPB {
+        condition: SelectOptionFilterConditionPB::OptionDoesNotContainAll,
+        option_ids: vec![options.remove(0).id, options.remove(0).id],
+      }),
+      changed: None,
+    },
+    // The empty rows are hidden
+    AssertNumberOfVisibleRows { expected: 3 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
 #[tokio::test]
 async fn grid_filter_multi_select_contains_test2() {
   let mut test = DatabaseFilterTest::new().await;
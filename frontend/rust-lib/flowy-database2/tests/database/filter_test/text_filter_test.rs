@@ -283,3 +283,44 @@ async fn grid_filter_update_empty_text_cell_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn grid_filter_is_row_visible_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let row_id = test.row_details[0].row.id.clone();
+  let scripts = vec![
+    AssertRowVisible {
+      row_id: row_id.clone(),
+      expected: true,
+    },
+    CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::RichText,
+      data: BoxAny::new(TextFilterPB {
+        condition: TextFilterConditionPB::TextIsEmpty,
+        content: "".to_string(),
+      }),
+      changed: Some(FilterRowChanged {
+        showing_num_of_rows: 0,
+        hiding_num_of_rows: 5,
+      }),
+    },
+    AssertRowVisible {
+      row_id: row_id.clone(),
+      expected: false,
+    },
+    UpdateTextCell {
+      row_id: row_id.clone(),
+      text: "".to_string(),
+      changed: Some(FilterRowChanged {
+        showing_num_of_rows: 1,
+        hiding_num_of_rows: 0,
+      }),
+    },
+    AssertRowVisible {
+      row_id,
+      expected: true,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -0,0 +1,41 @@
+use flowy_database2::entities::{FieldType, TextFilterConditionPB, TextFilterPB};
+use lib_infra::box_any::BoxAny;
+
+use crate::database::filter_test::script::FilterScript::*;
+use crate::database::filter_test::script::*;
+
+#[tokio::test]
+async fn grid_filter_last_edited_by_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let uid = test.sdk.get_user_profile().await.unwrap().id;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let row_id = test.get_rows().await[0].row.id.clone();
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &text_field.id,
+      BoxAny::new("hello".to_string()),
+    )
+    .await
+    .unwrap();
+
+  let scripts = vec![
+    CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::LastEditedBy,
+      data: BoxAny::new(TextFilterPB {
+        condition: TextFilterConditionPB::TextIs,
+        content: uid.to_string(),
+      }),
+      changed: None,
+    },
+    AssertNumberOfVisibleRows { expected: 1 },
+    AssertRowVisible {
+      row_id,
+      expected: true,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
@@ -149,7 +149,7 @@ impl DatabaseGroupTest {
       } => {
         let row = self.row_at_index(group_index, row_index).await;
         let row_ids = vec![RowId::from(row.id)];
-        self.editor.delete_rows(&row_ids).await;
+        self.editor.delete_rows(&row_ids).await.unwrap();
       },
       GroupScript::UpdateGroupedCell {
         from_group_index,
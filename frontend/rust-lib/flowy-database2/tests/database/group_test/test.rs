@@ -1,3 +1,4 @@
+use flowy_database2::entities::RepeatedGroupPB;
 use flowy_database2::services::field::SelectOption;
 
 use crate::database::group_test::script::DatabaseGroupTest;
@@ -504,3 +505,85 @@ async fn group_manual_create_new_group() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn group_update_row_without_rebuilding_groups_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let metrics = test.editor.get_group_metrics(&test.view_id).await.unwrap();
+  let num_of_full_builds = metrics.num_of_full_builds();
+  assert!(num_of_full_builds > 0);
+
+  // Loading the groups and moving a row between the groups don't rebuild the groups
+  test.editor.load_groups(&test.view_id).await.unwrap();
+  let scripts = vec![
+    UpdateGroupedCell {
+      from_group_index: 1,
+      row_index: 0,
+      to_group_index: 2,
+    },
+    AssertGroupRowCount {
+      group_index: 1,
+      row_count: 1,
+    },
+    AssertGroupRowCount {
+      group_index: 2,
+      row_count: 3,
+    },
+  ];
+  test.run_scripts(scripts).await;
+  assert_eq!(metrics.num_of_full_builds(), num_of_full_builds);
+  assert!(metrics.num_of_incremental_updates() > 0);
+
+  // Grouping by another field builds the groups from scratch
+  let multi_select_field = test.get_multi_select_field().await;
+  test
+    .run_scripts(vec![GroupByField {
+      field_id: multi_select_field.id.clone(),
+    }])
+    .await;
+  assert_eq!(metrics.num_of_full_builds(), num_of_full_builds + 1);
+}
+
+#[tokio::test]
+async fn group_preview_grouping_by_another_field_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let group_ids = |groups: RepeatedGroupPB| {
+    groups
+      .items
+      .into_iter()
+      .map(|group| group.group_id)
+      .collect::<Vec<_>>()
+  };
+  let groups_before = group_ids(test.editor.load_groups(&test.view_id).await.unwrap());
+
+  let multi_select_field = test.get_multi_select_field().await;
+  let previews = test
+    .editor
+    .preview_grouping(&test.view_id, &multi_select_field.id)
+    .await
+    .unwrap();
+
+  // Previewing doesn't change the grouping of the view
+  let groups_after = group_ids(test.editor.load_groups(&test.view_id).await.unwrap());
+  assert_eq!(groups_before, groups_after);
+  let group_field = test.group_at_index(0).await.field_id;
+  assert_ne!(group_field, multi_select_field.id);
+
+  // The preview matches the groups that are built after grouping by the field
+  test
+    .run_scripts(vec![GroupByField {
+      field_id: multi_select_field.id.clone(),
+    }])
+    .await;
+  let groups = test.editor.load_groups(&test.view_id).await.unwrap().items;
+  assert_eq!(previews.len(), groups.len());
+  for (preview, group) in previews.iter().zip(groups.iter()) {
+    assert_eq!(preview.group_id, group.group_id);
+    assert_eq!(preview.row_count, group.rows.len() as i64);
+    assert_eq!(preview.is_default, group.is_default);
+  }
+  assert!(previews
+    .iter()
+    .filter(|preview| !preview.is_default)
+    .all(|preview| !preview.group_name.is_empty() && preview.group_name != preview.group_id));
+}
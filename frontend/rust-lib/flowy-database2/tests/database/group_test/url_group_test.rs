@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use flowy_database2::entities::CreateRowPayloadPB;
+
 use crate::database::group_test::script::DatabaseGroupTest;
 use crate::database::group_test::script::GroupScript::*;
 
@@ -146,3 +150,44 @@ async fn group_move_url_group_row_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn group_create_row_after_url_group_removed_test() {
+  let mut test = DatabaseGroupTest::new().await;
+  let url_field = test.get_url_field().await;
+  let scripts = vec![
+    GroupByField {
+      field_id: url_field.id.clone(),
+    },
+    // Moving the only row of the https://github.com/AppFlowy-IO/AppFlowy group removes the group
+    UpdateGroupedCell {
+      from_group_index: 2,
+      row_index: 0,
+      to_group_index: 1,
+    },
+    AssertGroupCount(2),
+  ];
+  test.run_scripts(scripts).await;
+
+  // The new row with the url of the removed group goes to the no status group
+  test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      data: HashMap::from([(
+        url_field.id.clone(),
+        "https://github.com/AppFlowy-IO/AppFlowy".to_string(),
+      )]),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+  let scripts = vec![
+    AssertGroupCount(2),
+    AssertGroupRowCount {
+      group_index: 0,
+      row_count: 3,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
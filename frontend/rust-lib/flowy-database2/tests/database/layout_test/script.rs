@@ -1,18 +1,53 @@
 use collab_database::fields::Field;
 use collab_database::views::DatabaseLayout;
+use lib_infra::box_any::BoxAny;
 
 use flowy_database2::entities::{FieldType, LayoutSettingChangeset, LayoutSettingParams};
+use flowy_database2::services::field::DateCellChangeset;
 use flowy_database2::services::setting::{BoardLayoutSetting, CalendarLayoutSetting};
 
 use crate::database::database_editor::DatabaseEditorTest;
+use crate::database::field_test::util::create_date_field;
 
 pub enum LayoutScript {
-  AssertBoardLayoutSetting { expected: BoardLayoutSetting },
-  AssertCalendarLayoutSetting { expected: CalendarLayoutSetting },
-  UpdateBoardLayoutSetting { new_setting: BoardLayoutSetting },
+  AssertBoardLayoutSetting {
+    expected: BoardLayoutSetting,
+  },
+  AssertCalendarLayoutSetting {
+    expected: CalendarLayoutSetting,
+  },
+  UpdateBoardLayoutSetting {
+    new_setting: BoardLayoutSetting,
+  },
   AssertDefaultAllCalendarEvents,
-  AssertAllCalendarEventsCount { expected: usize },
-  UpdateDatabaseLayout { layout: DatabaseLayout },
+  AssertAllCalendarEventsCount {
+    expected: usize,
+  },
+  UpdateDatabaseLayout {
+    layout: DatabaseLayout,
+  },
+  UpdateDateCell {
+    field_id: String,
+    row_index: usize,
+    changeset: DateCellChangeset,
+  },
+  AssertCalendarEvent {
+    date_field_id: Option<String>,
+    row_index: usize,
+    timestamp: i64,
+    end_timestamp: Option<i64>,
+  },
+  AssertGetCalendarEventsError {
+    date_field_id: String,
+  },
+  SetAllLayoutSettings {
+    layout_settings: Vec<LayoutSettingParams>,
+    is_err: bool,
+  },
+  AssertAllLayoutSettings {
+    board: BoardLayoutSetting,
+    calendar: Option<CalendarLayoutSetting>,
+  },
 }
 
 pub struct DatabaseLayoutTest {
@@ -39,6 +74,21 @@ impl DatabaseLayoutTest {
     self.database_test.get_first_field(FieldType::DateTime)
   }
 
+  pub async fn get_first_field(&self, field_type: FieldType) -> Field {
+    self.database_test.get_first_field(field_type)
+  }
+
+  pub async fn create_date_field(&self) -> Field {
+    let (params, _) = create_date_field(&self.database_test.view_id);
+    let field = self
+      .database_test
+      .editor
+      .create_field_with_type_option(params)
+      .await
+      .unwrap();
+    self.database_test.get_field(&field.id, FieldType::DateTime)
+  }
+
   async fn get_layout_setting(
     &self,
     view_id: &str,
@@ -72,8 +122,9 @@ impl DatabaseLayoutTest {
         let events = self
           .database_test
           .editor
-          .get_all_calendar_events(&self.database_test.view_id)
-          .await;
+          .get_all_calendar_events(&self.database_test.view_id, None)
+          .await
+          .unwrap();
         assert_eq!(events.len(), expected);
       },
       LayoutScript::AssertBoardLayoutSetting { expected } => {
@@ -122,8 +173,9 @@ impl DatabaseLayoutTest {
         let events = self
           .database_test
           .editor
-          .get_all_calendar_events(&self.database_test.view_id)
-          .await;
+          .get_all_calendar_events(&self.database_test.view_id, None)
+          .await
+          .unwrap();
         assert_eq!(events.len(), 5);
 
         for (index, event) in events.into_iter().enumerate() {
@@ -146,6 +198,85 @@ impl DatabaseLayoutTest {
           }
         }
       },
+      LayoutScript::UpdateDateCell {
+        field_id,
+        row_index,
+        changeset,
+      } => {
+        let row_id = self.database_test.row_details[row_index].row.id.clone();
+        self
+          .database_test
+          .update_cell(&field_id, row_id, BoxAny::new(changeset))
+          .await
+          .unwrap();
+      },
+      LayoutScript::AssertCalendarEvent {
+        date_field_id,
+        row_index,
+        timestamp,
+        end_timestamp,
+      } => {
+        let row_id = self.database_test.row_details[row_index].row.id.to_string();
+        let event = self
+          .database_test
+          .editor
+          .get_all_calendar_events(&self.database_test.view_id, date_field_id.as_deref())
+          .await
+          .unwrap()
+          .into_iter()
+          .find(|event| event.row_meta.id == row_id)
+          .unwrap();
+        assert_eq!(event.timestamp, timestamp);
+        assert_eq!(event.end_timestamp, end_timestamp);
+        assert_eq!(event.is_scheduled, timestamp != 0);
+        if let Some(date_field_id) = date_field_id {
+          assert_eq!(event.date_field_id, date_field_id);
+        }
+      },
+      LayoutScript::AssertGetCalendarEventsError { date_field_id } => {
+        let result = self
+          .database_test
+          .editor
+          .get_all_calendar_events(&self.database_test.view_id, Some(&date_field_id))
+          .await;
+        assert!(result.is_err());
+      },
+      LayoutScript::SetAllLayoutSettings {
+        layout_settings,
+        is_err,
+      } => {
+        let result = self
+          .database_test
+          .editor
+          .set_all_layout_settings(&self.database_test.view_id, layout_settings)
+          .await;
+        assert_eq!(result.is_err(), is_err);
+      },
+      LayoutScript::AssertAllLayoutSettings { board, calendar } => {
+        let layout_settings = self
+          .database_test
+          .editor
+          .get_all_layout_settings(&self.database_test.view_id)
+          .await
+          .unwrap();
+        assert_eq!(layout_settings.len(), 3);
+
+        let grid_setting = &layout_settings[&DatabaseLayout::Grid];
+        assert!(grid_setting.board.is_none());
+        assert!(grid_setting.calendar.is_none());
+
+        let board_setting = layout_settings[&DatabaseLayout::Board].board.clone();
+        assert_eq!(
+          board_setting.unwrap().hide_ungrouped_column,
+          board.hide_ungrouped_column
+        );
+
+        let calendar_setting = layout_settings[&DatabaseLayout::Calendar].calendar.clone();
+        assert_eq!(
+          calendar_setting.map(|setting| (setting.field_id, setting.show_weekends)),
+          calendar.map(|setting| (setting.field_id, setting.show_weekends))
+        );
+      },
     }
   }
 }
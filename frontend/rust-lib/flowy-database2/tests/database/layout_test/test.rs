@@ -1,4 +1,6 @@
 use collab_database::views::DatabaseLayout;
+use flowy_database2::entities::{FieldType, LayoutSettingParams};
+use flowy_database2::services::field::DateCellChangeset;
 use flowy_database2::services::setting::BoardLayoutSetting;
 use flowy_database2::services::setting::CalendarLayoutSetting;
 
@@ -45,6 +47,67 @@ async fn calendar_get_events_test() {
   test.run_scripts(scripts).await;
 }
 
+#[tokio::test]
+async fn calendar_get_events_by_date_field_test() {
+  let mut test = DatabaseLayoutTest::new_calendar().await;
+  let layout_date_field = test.get_first_date_field().await;
+  let date_field = test.create_date_field().await;
+  let scripts = vec![
+    // A range from 2023-03-06 to 2023-03-09
+    UpdateDateCell {
+      field_id: date_field.id.clone(),
+      row_index: 0,
+      changeset: DateCellChangeset {
+        date: Some(1678060800),
+        end_date: Some(1678320000),
+        is_range: Some(true),
+        ..Default::default()
+      },
+    },
+    AssertCalendarEvent {
+      date_field_id: Some(date_field.id.clone()),
+      row_index: 0,
+      timestamp: 1678060800,
+      end_timestamp: Some(1678320000),
+    },
+    AssertCalendarEvent {
+      date_field_id: Some(date_field.id.clone()),
+      row_index: 1,
+      timestamp: 0,
+      end_timestamp: None,
+    },
+    // The date field of the layout is used by default
+    AssertCalendarEvent {
+      date_field_id: None,
+      row_index: 0,
+      timestamp: 1678090778,
+      end_timestamp: None,
+    },
+    AssertCalendarEvent {
+      date_field_id: Some(layout_date_field.id.clone()),
+      row_index: 0,
+      timestamp: 1678090778,
+      end_timestamp: None,
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn calendar_get_events_by_non_date_field_test() {
+  let mut test = DatabaseLayoutTest::new_calendar().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let scripts = vec![
+    AssertGetCalendarEventsError {
+      date_field_id: text_field.id.clone(),
+    },
+    AssertGetCalendarEventsError {
+      date_field_id: "not exist".to_string(),
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
+
 #[tokio::test]
 async fn grid_to_calendar_layout_test() {
   let mut test = DatabaseLayoutTest::new_no_date_grid().await;
@@ -56,3 +119,67 @@ async fn grid_to_calendar_layout_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn get_and_set_all_layout_settings_test() {
+  let mut test = DatabaseLayoutTest::new_board().await;
+  let date_field = test.get_first_date_field().await;
+  let text_field = test.get_first_field(FieldType::RichText).await;
+  let new_board_setting = BoardLayoutSetting {
+    hide_ungrouped_column: true,
+    ..BoardLayoutSetting::new()
+  };
+  let new_calendar_setting = CalendarLayoutSetting {
+    show_weekends: false,
+    ..CalendarLayoutSetting::new(date_field.id.clone())
+  };
+  let invalid_calendar_setting = CalendarLayoutSetting::new(text_field.id.clone());
+
+  let scripts = vec![
+    // The layouts without a stored setting get the default one
+    AssertAllLayoutSettings {
+      board: BoardLayoutSetting::new(),
+      calendar: Some(CalendarLayoutSetting::new(date_field.id.clone())),
+    },
+    SetAllLayoutSettings {
+      layout_settings: vec![
+        LayoutSettingParams {
+          layout_type: DatabaseLayout::Board,
+          board: Some(new_board_setting.clone()),
+          calendar: None,
+        },
+        LayoutSettingParams {
+          layout_type: DatabaseLayout::Calendar,
+          board: None,
+          calendar: Some(new_calendar_setting.clone()),
+        },
+      ],
+      is_err: false,
+    },
+    AssertAllLayoutSettings {
+      board: new_board_setting.clone(),
+      calendar: Some(new_calendar_setting.clone()),
+    },
+    // None of the settings is saved if the calendar is placed by a non-date field
+    SetAllLayoutSettings {
+      layout_settings: vec![
+        LayoutSettingParams {
+          layout_type: DatabaseLayout::Board,
+          board: Some(BoardLayoutSetting::new()),
+          calendar: None,
+        },
+        LayoutSettingParams {
+          layout_type: DatabaseLayout::Calendar,
+          board: None,
+          calendar: Some(invalid_calendar_setting),
+        },
+      ],
+      is_err: true,
+    },
+    AssertAllLayoutSettings {
+      board: new_board_setting,
+      calendar: Some(new_calendar_setting),
+    },
+  ];
+  test.run_scripts(scripts).await;
+}
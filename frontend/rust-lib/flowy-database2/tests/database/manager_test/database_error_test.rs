@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use collab_database::rows::RowId;
+use flowy_database2::entities::{DatabaseErrorKindPB, DatabaseErrorPhasePB};
+use flowy_database2::services::database::RowLoadDelegate;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+/// Makes the chunk that contains the row fail to load, as if the row was corrupted.
+struct FailingRowLoadDelegate {
+  row_id: RowId,
+}
+
+impl RowLoadDelegate for FailingRowLoadDelegate {
+  fn will_load_rows(&self, row_ids: &[RowId]) {
+    if row_ids.contains(&self.row_id) {
+      panic!("Injected failure while loading the rows");
+    }
+  }
+}
+
+#[tokio::test]
+async fn row_load_failure_is_reported_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let mut errors = test.editor.subscribe_errors();
+  let rows = test.get_rows().await;
+  assert!(!rows.is_empty());
+  test
+    .editor
+    .set_row_load_delegate(Arc::new(FailingRowLoadDelegate {
+      row_id: rows[0].row.id.clone(),
+    }));
+
+  // The rows of the grid fit in one chunk, so none of them is loaded
+  let rows = test.get_rows().await;
+  assert!(rows.is_empty());
+
+  let error = errors.try_recv().unwrap();
+  assert_eq!(error.view_id, test.view_id);
+  assert_eq!(error.phase, DatabaseErrorPhasePB::LoadRows);
+  assert_eq!(error.kind, DatabaseErrorKindPB::Fatal);
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use flowy_database2::entities::{CreateRowPayloadPB, FieldType};
+use flowy_database2::services::database::SequentialIdGenerator;
+use flowy_error::ErrorCode;
+
+use crate::database::database_editor::DatabaseEditorTest;
+use crate::database::field_test::util::create_text_field;
+
+#[tokio::test]
+async fn sequential_id_generator_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  test
+    .editor
+    .set_id_generator(Arc::new(SequentialIdGenerator::default()));
+  let num_of_rows = test.get_rows().await.len();
+
+  let row_detail = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap()
+    .unwrap();
+  assert_eq!(row_detail.row.id.as_str(), "row_1");
+
+  let (params, _) = create_text_field(&test.view_id);
+  let field = test
+    .editor
+    .create_field_with_type_option(params)
+    .await
+    .unwrap();
+  assert_eq!(field.id, "field_2");
+
+  let select_field = test.get_first_field(FieldType::SingleSelect);
+  let option = test
+    .editor
+    .create_select_option(&select_field.id, "New".to_string())
+    .await
+    .unwrap();
+  assert_eq!(option.id, "option_3");
+
+  // Restarting the sequence generates an id that's already used
+  test
+    .editor
+    .set_id_generator(Arc::new(SequentialIdGenerator::new(1)));
+  let err = test
+    .editor
+    .create_row(CreateRowPayloadPB {
+      view_id: test.view_id.clone(),
+      ..Default::default()
+    })
+    .await
+    .unwrap_err();
+  assert_eq!(err.code, ErrorCode::RecordAlreadyExists);
+  assert_eq!(test.get_rows().await.len(), num_of_rows + 1);
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn close_idle_databases_test() {
+  let DatabaseEditorTest {
+    sdk,
+    view_id,
+    editor,
+    row_details,
+    ..
+  } = DatabaseEditorTest::new_grid().await;
+  let database_manager = sdk.database_manager.clone();
+
+  // The editor is still held by the test
+  assert_eq!(
+    database_manager.close_idle_databases(Duration::ZERO).await,
+    0
+  );
+  drop(editor);
+
+  // The editor was used less than an hour ago
+  let idle_timeout = Duration::from_secs(60 * 60);
+  assert_eq!(database_manager.close_idle_databases(idle_timeout).await, 0);
+
+  // The editor of a database with opening views is kept
+  database_manager.open_database_view(&view_id).await.unwrap();
+  assert_eq!(
+    database_manager.close_idle_databases(Duration::ZERO).await,
+    0
+  );
+
+  database_manager
+    .close_database_view(&view_id)
+    .await
+    .unwrap();
+  let _ = database_manager
+    .get_database_with_view_id(&view_id)
+    .await
+    .unwrap();
+  assert_eq!(
+    database_manager.close_idle_databases(Duration::ZERO).await,
+    1
+  );
+  assert_eq!(database_manager.num_of_closed_idle_databases(), 1);
+
+  // The database is opened again on demand
+  let editor = database_manager
+    .get_database_with_view_id(&view_id)
+    .await
+    .unwrap();
+  let rows = editor.get_rows(&view_id).await.unwrap();
+  assert_eq!(rows.len(), row_details.len());
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn get_inline_view_id_test() {
+  let DatabaseEditorTest {
+    sdk,
+    view_id,
+    editor,
+    ..
+  } = DatabaseEditorTest::new_grid().await;
+  let database_manager = sdk.database_manager.clone();
+  let database_id = database_manager
+    .get_database_id_with_view_id(&view_id)
+    .await
+    .unwrap();
+
+  // The grid is the inline view of the database created along with it
+  assert_eq!(editor.get_inline_view_id(), view_id);
+  assert_eq!(
+    database_manager
+      .get_inline_view_id(&database_id)
+      .await
+      .unwrap(),
+    view_id
+  );
+
+  // The inline view id is available after the database is closed
+  drop(editor);
+  assert_eq!(
+    database_manager.close_idle_databases(Duration::ZERO).await,
+    1
+  );
+  assert_eq!(
+    database_manager
+      .get_inline_view_id(&database_id)
+      .await
+      .unwrap(),
+    view_id
+  );
+}
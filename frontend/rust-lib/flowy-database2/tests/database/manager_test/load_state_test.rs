@@ -0,0 +1,43 @@
+use flowy_database2::services::database::{LoadState, LoadTracker};
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn database_load_state_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let database_manager = test.sdk.database_manager.clone();
+
+  // The rows are not loaded until the data of the view is fetched
+  database_manager
+    .open_database_view(&test.view_id)
+    .await
+    .unwrap();
+  assert_eq!(test.editor.load_state(), LoadState::Loading);
+
+  test.editor.get_database_data(&test.view_id).await.unwrap();
+  assert_eq!(test.editor.load_state(), LoadState::Loaded);
+
+  // Switching the workspace closes all the views
+  test.editor.close_all_views().await;
+  assert_eq!(test.editor.load_state(), LoadState::NotOpened);
+}
+
+#[test]
+fn load_cancelled_by_close_test() {
+  let tracker = LoadTracker::default();
+  assert_eq!(tracker.state(), LoadState::NotOpened);
+
+  tracker.did_open();
+  let generation = tracker.will_load_rows();
+  assert_eq!(tracker.state(), LoadState::Loading);
+
+  // The load finishes after the database is closed and opened again
+  tracker.did_close();
+  tracker.did_open();
+  tracker.did_load_rows(generation);
+  assert_eq!(tracker.state(), LoadState::Loading);
+
+  let generation = tracker.will_load_rows();
+  tracker.did_load_rows(generation);
+  assert_eq!(tracker.state(), LoadState::Loaded);
+}
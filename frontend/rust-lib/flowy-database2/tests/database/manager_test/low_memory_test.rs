@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use collab_database::rows::RowDetail;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use event_integration_test::folder_event::ViewTest;
+use event_integration_test::EventIntegrationTest;
+use flowy_database2::entities::{CheckboxFilterConditionPB, FieldType};
+use flowy_database2::services::database::LowMemoryProfile;
+use flowy_database2::services::filter::{FilterChangeset, FilterInner};
+use flowy_database2::services::share::csv::CSVStreamImportOptions;
+
+use crate::database::database_editor::DatabaseEditorTest;
+use crate::database::mock_data::make_test_grid;
+
+#[tokio::test]
+async fn open_large_view_with_low_memory_profile_test() {
+  let profile = LowMemoryProfile {
+    cell_cache_capacity: 10,
+    initial_row_limit: 20,
+    import_batch_size: 50,
+    idle_database_timeout: Duration::from_secs(60),
+  };
+  let sdk = EventIntegrationTest::new_with_low_memory_profile(profile).await;
+  let _ = sdk.init_anon_user().await;
+  let params = make_test_grid();
+  let view_test = ViewTest::new_grid_view(&sdk, params.to_json_bytes().unwrap()).await;
+  let test = DatabaseEditorTest::new(sdk, view_test).await;
+
+  // The import asks for one batch, but the rows are created in batches of the profile's size
+  let num_of_imported_rows = 500;
+  let mut csv = "Name\n".to_string();
+  for i in 0..num_of_imported_rows {
+    csv.push_str(&format!("Row {}\n", i));
+  }
+  let (tx, mut rx) = mpsc::channel(100);
+  let result = test
+    .editor
+    .import_csv_stream(
+      &test.view_id,
+      std::io::Cursor::new(csv.into_bytes()),
+      HashMap::new(),
+      CSVStreamImportOptions::default(),
+      tx,
+      CancellationToken::new(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(result.rows_imported, num_of_imported_rows);
+  let mut num_of_batches = 0;
+  while rx.try_recv().is_ok() {
+    num_of_batches += 1;
+  }
+  assert_eq!(
+    num_of_batches,
+    num_of_imported_rows / profile.import_batch_size
+  );
+
+  // Only the first rows are returned when the view is opened
+  let num_of_rows = test.row_details.len() + num_of_imported_rows;
+  let data = test.editor.get_database_data(&test.view_id).await.unwrap();
+  assert_eq!(data.rows.len(), profile.initial_row_limit);
+  assert!(data.has_more_rows);
+  // The view has no sorts, so only the rows up to the limit are loaded
+  let open_profile = test
+    .sdk
+    .database_manager
+    .last_open_profiles()
+    .pop()
+    .unwrap();
+  assert_eq!(open_profile.num_of_rows, num_of_rows);
+  assert_eq!(
+    open_profile.num_of_visible_rows,
+    profile.initial_row_limit + 1
+  );
+  let rest = test
+    .editor
+    .get_database_rows(&test.view_id, profile.initial_row_limit, num_of_rows)
+    .await
+    .unwrap();
+  assert_eq!(rest.len(), num_of_rows - profile.initial_row_limit);
+
+  // Reading every cell doesn't grow the cache past its capacity
+  let text_field = test.get_first_field(FieldType::RichText);
+  for row_detail in test.get_rows().await {
+    let _ = test
+      .editor
+      .get_cell_pb(&text_field.id, &row_detail.row.id)
+      .await;
+  }
+  assert!(test.editor.cell_cache.read().len() <= profile.cell_cache_capacity);
+}
+
+#[tokio::test]
+async fn open_view_without_low_memory_profile_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let data = test.editor.get_database_data(&test.view_id).await.unwrap();
+  assert_eq!(data.rows.len(), test.row_details.len());
+  assert!(!data.has_more_rows);
+}
+
+#[tokio::test]
+async fn get_database_rows_in_pages_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let get_pages = |page_size: usize| {
+    let test = &test;
+    async move {
+      let mut row_ids = vec![];
+      loop {
+        let rows = test
+          .editor
+          .get_database_rows(&test.view_id, row_ids.len(), page_size)
+          .await
+          .unwrap();
+        if rows.is_empty() {
+          break;
+        }
+        row_ids.extend(rows.into_iter().map(|row| row.id));
+        assert!(row_ids.len() < 100);
+      }
+      row_ids
+    }
+  };
+  let row_ids_of = |rows: Vec<Arc<RowDetail>>| {
+    rows
+      .iter()
+      .map(|row_detail| row_detail.row.id.to_string())
+      .collect::<Vec<_>>()
+  };
+  assert_eq!(get_pages(2).await, row_ids_of(test.get_rows().await));
+
+  // The rows hidden by the filters aren't counted by the offset
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  test
+    .editor
+    .modify_view_filters(
+      &test.view_id,
+      FilterChangeset::Insert {
+        parent_filter_id: None,
+        data: FilterInner::new_data(
+          checkbox_field.id.clone(),
+          FieldType::Checkbox,
+          CheckboxFilterConditionPB::IsChecked as i64,
+          "".to_string(),
+        ),
+      },
+    )
+    .await
+    .unwrap();
+  let visible_row_ids = row_ids_of(test.get_rows().await);
+  assert!(visible_row_ids.len() < test.row_details.len());
+  assert_eq!(get_pages(2).await, visible_row_ids);
+  assert!(test
+    .editor
+    .get_database_rows(&test.view_id, 0, 0)
+    .await
+    .unwrap()
+    .is_empty());
+}
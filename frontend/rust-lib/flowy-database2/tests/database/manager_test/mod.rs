@@ -0,0 +1,8 @@
+mod database_error_test;
+mod id_generator_test;
+mod idle_database_test;
+mod inline_view_test;
+mod load_state_test;
+mod low_memory_test;
+mod observer_leak_test;
+mod read_only_member_test;
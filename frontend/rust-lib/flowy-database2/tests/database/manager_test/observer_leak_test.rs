@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn observers_are_aborted_on_close_test() {
+  let DatabaseEditorTest { sdk, view_id, .. } = DatabaseEditorTest::new_grid().await;
+  let database_manager = sdk.database_manager.clone();
+
+  // The editors are held by the test, so only aborting their observers ends the tasks
+  let mut editors = vec![];
+  for _ in 0..10 {
+    database_manager.open_database_view(&view_id).await.unwrap();
+    let editor = database_manager
+      .get_database_with_view_id(&view_id)
+      .await
+      .unwrap();
+    assert!(editor.num_of_live_observers() > 0);
+    database_manager
+      .close_database_view(&view_id)
+      .await
+      .unwrap();
+    editors.push(editor);
+  }
+
+  let mut attempts = 0;
+  while editors
+    .iter()
+    .any(|editor| editor.num_of_live_observers() > 0)
+  {
+    attempts += 1;
+    assert!(
+      attempts < 100,
+      "The observers of the closed databases are still running"
+    );
+    tokio::time::sleep(Duration::from_millis(10)).await;
+  }
+}
@@ -0,0 +1,87 @@
+use collab_database::views::DatabaseLayout;
+use flowy_database2::entities::{FieldType, UpdateRowMetaParams};
+use flowy_database2::services::field::StringCellData;
+use flowy_error::ErrorCode;
+use lib_infra::box_any::BoxAny;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn read_only_member_cannot_edit_database_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let row_id = test.get_rows().await[0].row.id.clone();
+
+  // The owner can edit the database
+  test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &text_field.id,
+      BoxAny::new("hello".to_string()),
+    )
+    .await
+    .unwrap();
+
+  test.sdk.database_manager.set_workspace_read_only(true);
+  let error = test
+    .editor
+    .update_cell_with_changeset(
+      &test.view_id,
+      &row_id,
+      &text_field.id,
+      BoxAny::new("world".to_string()),
+    )
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotEnoughPermissions);
+  let error = test.editor.delete_field(&text_field.id).await.unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotEnoughPermissions);
+  let error = test.editor.set_row_locked(&row_id, true).await.unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotEnoughPermissions);
+  let changeset = UpdateRowMetaParams {
+    id: row_id.to_string(),
+    view_id: test.view_id.clone(),
+    icon_url: Some("icon".to_string()),
+    cover_url: None,
+    is_document_empty: None,
+  };
+  let error = test
+    .editor
+    .update_row_meta(&row_id, changeset)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotEnoughPermissions);
+  let error = test
+    .editor
+    .delete_all_sorts(&test.view_id)
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotEnoughPermissions);
+  let error = test
+    .editor
+    .create_group(&test.view_id, "group")
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotEnoughPermissions);
+  let error = test
+    .editor
+    .set_default_field_settings(DatabaseLayout::Grid, vec![])
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotEnoughPermissions);
+  let error = test
+    .editor
+    .delete_rows(&[row_id.clone()])
+    .await
+    .unwrap_err();
+  assert_eq!(error.code, ErrorCode::NotEnoughPermissions);
+
+  // The read-only member can still read the database
+  let cell = test.editor.get_cell(&text_field.id, &row_id).await.unwrap();
+  assert_eq!(StringCellData::from(&cell).as_str(), "hello");
+  assert!(test.editor.get_field(&text_field.id).is_some());
+
+  test.sdk.database_manager.set_workspace_read_only(false);
+  test.editor.delete_field(&text_field.id).await.unwrap();
+}
@@ -10,6 +10,7 @@ use flowy_database2::services::field::summary_type_option::summary::Summarizatio
 use flowy_database2::services::field::{
   DateFormat, DateTypeOption, FieldBuilder, MultiSelectTypeOption, RelationTypeOption,
   SelectOption, SelectOptionColor, SingleSelectTypeOption, TimeFormat, TimestampTypeOption,
+  UserTypeOption,
 };
 use flowy_database2::services::field_settings::default_field_settings_for_fields;
 use flowy_database2::services::setting::BoardLayoutSetting;
@@ -134,6 +135,16 @@ pub fn make_test_board() -> DatabaseData {
           .build();
         fields.push(relation_field);
       },
+      FieldType::LastEditedBy | FieldType::CreatedBy => {
+        let name = match field_type {
+          FieldType::LastEditedBy => "Last Edited By",
+          _ => "Created By",
+        };
+        let user_field = FieldBuilder::new(field_type, UserTypeOption::new(field_type))
+          .name(name)
+          .build();
+        fields.push(user_field);
+      },
     }
   }
 
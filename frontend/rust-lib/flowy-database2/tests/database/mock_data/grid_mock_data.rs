@@ -1,15 +1,17 @@
-use collab_database::database::{gen_database_id, gen_database_view_id, gen_row_id, DatabaseData};
+use collab_database::database::{gen_database_id, gen_database_view_id, DatabaseData};
 use collab_database::views::{DatabaseLayout, DatabaseView};
 use strum::IntoEnumIterator;
 
-use crate::database::mock_data::{COMPLETED, FACEBOOK, GOOGLE, PAUSED, PLANNED, TWITTER};
+use crate::database::mock_data::{
+  mock_row_id, COMPLETED, FACEBOOK, GOOGLE, PAUSED, PLANNED, TWITTER,
+};
 use event_integration_test::database_event::TestRowBuilder;
 use flowy_database2::entities::FieldType;
 use flowy_database2::services::field::summary_type_option::summary::SummarizationTypeOption;
 use flowy_database2::services::field::{
   ChecklistTypeOption, DateFormat, DateTypeOption, FieldBuilder, MultiSelectTypeOption,
   NumberFormat, NumberTypeOption, RelationTypeOption, SelectOption, SelectOptionColor,
-  SingleSelectTypeOption, TimeFormat, TimestampTypeOption,
+  SingleSelectTypeOption, TimeFormat, TimestampTypeOption, UserTypeOption,
 };
 use flowy_database2::services::field_settings::default_field_settings_for_fields;
 
@@ -132,13 +134,24 @@ pub fn make_test_grid() -> DatabaseData {
           .build();
         fields.push(relation_field);
       },
+      FieldType::LastEditedBy | FieldType::CreatedBy => {
+        let name = match field_type {
+          FieldType::LastEditedBy => "Last Edited By",
+          _ => "Created By",
+        };
+        let user_field = FieldBuilder::new(field_type, UserTypeOption::new(field_type))
+          .name(name)
+          .build();
+        fields.push(user_field);
+      },
     }
   }
 
   let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Grid);
 
   for i in 0..7 {
-    let mut row_builder = TestRowBuilder::new(&database_id, gen_row_id(), &fields);
+    let row_id = mock_row_id(&database_id, i);
+    let mut row_builder = TestRowBuilder::new(&database_id, row_id, &fields);
     match i {
       0 => {
         for field_type in FieldType::iter() {
@@ -325,7 +338,8 @@ pub fn make_no_date_test_grid() -> DatabaseData {
   let field_settings = default_field_settings_for_fields(&fields, DatabaseLayout::Grid);
 
   for i in 0..3 {
-    let mut row_builder = TestRowBuilder::new(&database_id, gen_row_id(), &fields);
+    let row_id = mock_row_id(&database_id, i);
+    let mut row_builder = TestRowBuilder::new(&database_id, row_id, &fields);
     match i {
       0 => {
         for field_type in FieldType::iter() {
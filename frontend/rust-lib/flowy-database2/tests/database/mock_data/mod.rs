@@ -1,3 +1,5 @@
+use collab_database::rows::RowId;
+
 mod board_mock_data;
 mod calendar_mock_data;
 mod grid_mock_data;
@@ -14,6 +16,12 @@ pub const COMPLETED: &str = "Completed";
 pub const PLANNED: &str = "Planned";
 pub const PAUSED: &str = "Paused";
 
+/// Returns a row id that sorts in the same order as the rows are created. Sorting breaks ties by
+/// row id, so the mock rows keep their insertion order when their sort keys are equal.
+pub fn mock_row_id(database_id: &str, index: usize) -> RowId {
+  RowId::from(format!("{}_row_{:02}", database_id, index))
+}
+
 // pub const FIRST_THING: &str = "Wake up at 6:00 am";
 // pub const SECOND_THING: &str = "Get some coffee";
 // pub const THIRD_THING: &str = "Start working";
@@ -7,6 +7,7 @@ mod field_test;
 mod filter_test;
 mod group_test;
 mod layout_test;
+mod manager_test;
 mod mock_data;
 mod pre_fill_cell_test;
 mod share_test;
@@ -30,6 +30,7 @@ async fn according_to_text_contains_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -73,6 +74,7 @@ async fn according_to_empty_text_contains_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -109,6 +111,7 @@ async fn according_to_text_is_not_empty_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -138,6 +141,7 @@ async fn according_to_checkbox_is_unchecked_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -175,6 +179,7 @@ async fn according_to_checkbox_is_checked_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -222,6 +227,7 @@ async fn according_to_date_time_is_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -269,6 +275,7 @@ async fn according_to_invalid_date_time_is_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -316,6 +323,7 @@ async fn according_to_select_option_is_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -365,6 +373,7 @@ async fn according_to_select_option_contains_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -409,6 +418,7 @@ async fn according_to_select_option_is_not_empty_filter_test() {
         }
         .try_into()
         .unwrap(),
+        emptiness: None,
       },
     },
     Wait { milliseconds: 100 },
@@ -0,0 +1,153 @@
+use flowy_database2::entities::{
+  CalculationType, FieldType, UpdateCalculationChangesetPB, UpdateSortPayloadPB,
+};
+use flowy_database2::services::field::RelationTypeOption;
+use flowy_database2::services::sort::SortCondition;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn clone_database_structure_without_rows_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  test
+    .editor
+    .create_or_update_sort(UpdateSortPayloadPB {
+      view_id: test.view_id.clone(),
+      field_id: text_field.id.clone(),
+      sort_id: None,
+      condition: SortCondition::Descending.into(),
+    })
+    .await
+    .unwrap();
+  let number_field = test.get_first_field(FieldType::Number);
+  test
+    .editor
+    .update_calculation(UpdateCalculationChangesetPB {
+      view_id: test.view_id.clone(),
+      field_id: number_field.id.clone(),
+      calculation_id: None,
+      calculation_type: CalculationType::Sum,
+    })
+    .await
+    .unwrap();
+
+  let params = test.editor.clone_structure().await.unwrap();
+  assert!(params.params.rows.is_empty());
+  let new_view_id = params.params.inline_view_id.clone();
+  test
+    .sdk
+    .database_manager
+    .create_database_with_clone_params(params)
+    .await
+    .unwrap();
+
+  let database = test
+    .sdk
+    .database_manager
+    .get_database_with_view_id(&new_view_id)
+    .await
+    .unwrap();
+  let fields = database.get_fields(&new_view_id, None);
+  let original_fields = test.get_fields();
+  assert_eq!(fields.len(), original_fields.len());
+  for (field, original_field) in fields.iter().zip(original_fields.iter()) {
+    assert_ne!(field.id, original_field.id);
+    assert_eq!(field.name, original_field.name);
+    assert_eq!(field.field_type, original_field.field_type);
+  }
+  assert!(database.get_rows(&new_view_id).await.unwrap().is_empty());
+
+  // The sort is copied and points at the cloned field instead of the original one
+  let sorts = database.get_all_sorts(&new_view_id).await.items;
+  assert_eq!(sorts.len(), 1);
+  let original_sorts = test.editor.get_all_sorts(&test.view_id).await.items;
+  assert_ne!(sorts[0].id, original_sorts[0].id);
+  let cloned_text_field = fields
+    .iter()
+    .find(|field| field.name == text_field.name)
+    .unwrap();
+  assert_eq!(sorts[0].field_id, cloned_text_field.id);
+  assert_eq!(sorts[0].condition, SortCondition::Descending.into());
+
+  // So is the calculation, with a new id
+  let calculations = database.get_all_calculations(&new_view_id).await.items;
+  assert_eq!(calculations.len(), 1);
+  let original_calculations = test.editor.get_all_calculations(&test.view_id).await.items;
+  assert_ne!(calculations[0].id, original_calculations[0].id);
+  let cloned_number_field = fields
+    .iter()
+    .find(|field| field.name == number_field.name)
+    .unwrap();
+  assert_eq!(calculations[0].field_id, cloned_number_field.id);
+  assert_eq!(calculations[0].calculation_type, CalculationType::Sum);
+
+  // The original database is left untouched
+  assert_eq!(test.get_rows().await.len(), 7);
+}
+
+#[tokio::test]
+async fn clone_databases_for_template_rewrites_relations_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let relation_field = test.get_first_field(FieldType::Relation);
+  let database_id = test
+    .editor
+    .get_database_data(&test.view_id)
+    .await
+    .unwrap()
+    .id;
+  test
+    .editor
+    .update_field_type_option(
+      &relation_field.id,
+      RelationTypeOption {
+        database_id: database_id.clone(),
+      }
+      .into(),
+      relation_field.clone(),
+    )
+    .await
+    .unwrap();
+
+  // Cloning the database alone keeps the relation pointing at the original database
+  let params = test.editor.clone_structure().await.unwrap();
+  let cloned_relation_field = params
+    .params
+    .fields
+    .iter()
+    .find(|field| field.field_type == relation_field.field_type)
+    .unwrap();
+  let type_option = cloned_relation_field
+    .get_type_option::<RelationTypeOption>(FieldType::Relation)
+    .unwrap();
+  assert_eq!(type_option.database_id, database_id);
+
+  // Cloning it as a set of related databases points the relation at the clone
+  let id_maps = test
+    .sdk
+    .database_manager
+    .clone_databases_for_template(vec![test.view_id.clone()])
+    .await
+    .unwrap();
+  assert_eq!(id_maps.len(), 1);
+  let id_map = &id_maps[0];
+  assert_eq!(id_map.old_database_id, database_id);
+  assert_ne!(id_map.new_database_id, database_id);
+  assert_eq!(id_map.field_ids.len(), test.get_fields().len());
+
+  let new_view_id = id_map.view_ids.get(&test.view_id).unwrap();
+  let database = test
+    .sdk
+    .database_manager
+    .get_database_with_view_id(new_view_id)
+    .await
+    .unwrap();
+  let new_database_id = database.get_database_data(new_view_id).await.unwrap().id;
+  assert_eq!(new_database_id, id_map.new_database_id);
+  let new_relation_field_id = id_map.field_ids.get(&relation_field.id).unwrap();
+  let field = database.get_field(new_relation_field_id).unwrap();
+  let type_option = field
+    .get_type_option::<RelationTypeOption>(FieldType::Relation)
+    .unwrap();
+  assert_eq!(type_option.database_id, id_map.new_database_id);
+}
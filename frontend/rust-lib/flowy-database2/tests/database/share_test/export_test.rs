@@ -1,15 +1,25 @@
-use flowy_database2::entities::FieldType;
+use std::collections::HashMap;
+
+use collab_database::fields::Field;
+use collab_database::rows::RowId;
+
+use flowy_database2::entities::{FieldType, NumberFilterConditionPB, NumberFilterPB};
 use flowy_database2::services::cell::stringify_cell;
 use flowy_database2::services::field::CHECK;
-use flowy_database2::services::share::csv::CSVFormat;
+use flowy_database2::services::share::csv::{checksum_file_path, CSVExportOptions, CSVFormat};
+use lib_infra::box_any::BoxAny;
 
 use crate::database::database_editor::DatabaseEditorTest;
+use crate::database::filter_test::script::{DatabaseFilterTest, FilterScript::CreateDataFilter};
 
 #[tokio::test]
 async fn export_meta_csv_test() {
   let test = DatabaseEditorTest::new_grid().await;
   let database = test.editor.clone();
-  let s = database.export_csv(CSVFormat::META).await.unwrap();
+  let s = database
+    .export_csv(CSVFormat::META, CSVExportOptions::default())
+    .await
+    .unwrap();
   let mut reader = csv::Reader::from_reader(s.as_bytes());
   for header in reader.headers().unwrap() {
     dbg!(header);
@@ -27,7 +37,10 @@ async fn export_and_then_import_meta_csv_test() {
   let test = DatabaseEditorTest::new_grid().await;
   let database = test.editor.clone();
   let format = CSVFormat::META;
-  let csv_1 = database.export_csv(format).await.unwrap();
+  let csv_1 = database
+    .export_csv(format, CSVExportOptions::default())
+    .await
+    .unwrap();
 
   let result = test.import(csv_1.clone(), format).await;
   let database = test.get_database(&result.database_id).await.unwrap();
@@ -83,6 +96,8 @@ async fn export_and_then_import_meta_csv_test() {
           FieldType::CreatedTime => {},
           FieldType::Relation => {},
           FieldType::Summary => {},
+          FieldType::LastEditedBy => {},
+          FieldType::CreatedBy => {},
         }
       } else {
         panic!(
@@ -166,6 +181,8 @@ async fn history_database_import_test() {
           FieldType::CreatedTime => {},
           FieldType::Relation => {},
           FieldType::Summary => {},
+          FieldType::LastEditedBy => {},
+          FieldType::CreatedBy => {},
         }
       } else {
         panic!(
@@ -176,3 +193,227 @@ async fn history_database_import_test() {
     }
   }
 }
+
+#[tokio::test]
+async fn export_and_then_import_semicolon_csv_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let database = test.editor.clone();
+  let options = CSVExportOptions {
+    delimiter: ';',
+    ..Default::default()
+  };
+  let csv = database
+    .export_csv(CSVFormat::Original, options)
+    .await
+    .unwrap();
+  let header = csv.lines().next().unwrap();
+  assert!(header.contains(';'));
+
+  // The delimiter is detected from the header
+  let result = test.import(csv, CSVFormat::Original).await;
+  let imported_database = test.get_database(&result.database_id).await.unwrap();
+  let field_names = |fields: Vec<Field>| {
+    fields
+      .into_iter()
+      .map(|field| field.name)
+      .collect::<Vec<String>>()
+  };
+  assert_eq!(
+    field_names(imported_database.get_fields(&result.view_id, None)),
+    field_names(database.get_fields(&test.view_id, None))
+  );
+  let rows = imported_database.get_rows(&result.view_id).await.unwrap();
+  assert_eq!(rows.len(), test.row_details.len());
+}
+
+#[tokio::test]
+async fn export_csv_with_invalid_delimiter_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let options = CSVExportOptions {
+    delimiter: '"',
+    ..Default::default()
+  };
+  let result = test.editor.export_csv(CSVFormat::Original, options).await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn export_csv_with_checksum_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let csv = test
+    .editor
+    .export_csv(CSVFormat::Original, CSVExportOptions::default())
+    .await
+    .unwrap();
+  let (csv_with_checksum, checksum) = test
+    .editor
+    .export_csv_with_checksum(CSVFormat::Original, CSVExportOptions::default())
+    .await
+    .unwrap();
+  assert_eq!(csv_with_checksum, csv);
+  assert_eq!(checksum.len(), 64);
+
+  let path = std::env::temp_dir().join(format!("{}.csv", nanoid::nanoid!(10)));
+  let file_checksum = test
+    .editor
+    .export_csv_to_file(
+      CSVFormat::Original,
+      CSVExportOptions::default(),
+      path.clone(),
+      true,
+    )
+    .await
+    .unwrap();
+  assert_eq!(file_checksum, checksum);
+  assert_eq!(std::fs::read_to_string(&path).unwrap(), csv);
+
+  let checksum_path = checksum_file_path(&path);
+  let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+  assert_eq!(
+    std::fs::read_to_string(&checksum_path).unwrap(),
+    format!("{}  {}\n", checksum, file_name)
+  );
+  let _ = std::fs::remove_file(&path);
+  let _ = std::fs::remove_file(&checksum_path);
+}
+
+#[tokio::test]
+async fn export_view_csv_test() {
+  let mut test = DatabaseFilterTest::new().await;
+  let view_id = test.view_id.clone();
+  let text_field = test.get_first_field(FieldType::RichText);
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  let visibility = HashMap::from([(checkbox_field.id.clone(), false)]);
+  test
+    .editor
+    .set_fields_visibility(&view_id, visibility)
+    .await
+    .unwrap();
+  test
+    .run_scripts(vec![CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::Number,
+      data: BoxAny::new(NumberFilterPB {
+        condition: NumberFilterConditionPB::GreaterThan,
+        content: "2".to_string(),
+      }),
+      changed: None,
+    }])
+    .await;
+
+  // Only the rows that pass the filter and the visible fields are exported
+  let csv = test
+    .editor
+    .export_view_csv(&view_id, CSVFormat::Original)
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+  let headers = reader.headers().unwrap().clone();
+  assert!(headers.iter().any(|header| header == text_field.name));
+  assert!(!headers.iter().any(|header| header == checkbox_field.name));
+  let text_column = headers
+    .iter()
+    .position(|header| header == text_field.name)
+    .unwrap();
+  let names = reader
+    .records()
+    .map(|record| record.unwrap()[text_column].to_string())
+    .collect::<Vec<String>>();
+  let rows = test.editor.get_rows(&view_id).await.unwrap();
+  assert!(rows.len() < test.row_details.len());
+  let expected_names = rows
+    .iter()
+    .map(|row_detail| {
+      row_detail
+        .row
+        .cells
+        .get(&text_field.id)
+        .map(|cell| stringify_cell(cell, &text_field))
+        .unwrap_or_default()
+    })
+    .collect::<Vec<String>>();
+  assert_eq!(names, expected_names);
+
+  // The whole database export is unaffected
+  let csv = test
+    .editor
+    .export_csv(CSVFormat::Original, CSVExportOptions::default())
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+  assert_eq!(reader.records().count(), test.row_details.len());
+
+  // Only the header is exported if every row is filtered out
+  test
+    .run_scripts(vec![CreateDataFilter {
+      parent_filter_id: None,
+      field_type: FieldType::Number,
+      data: BoxAny::new(NumberFilterPB {
+        condition: NumberFilterConditionPB::GreaterThan,
+        content: "1000".to_string(),
+      }),
+      changed: None,
+    }])
+    .await;
+  let csv = test
+    .editor
+    .export_view_csv(&view_id, CSVFormat::Original)
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+  assert_eq!(reader.headers().unwrap(), &headers);
+  assert_eq!(reader.records().count(), 0);
+}
+
+#[tokio::test]
+async fn export_rows_csv_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let view_id = test.view_id.clone();
+  let text_field = test.get_first_field(FieldType::RichText);
+  let rows = test.editor.get_rows(&view_id).await.unwrap();
+
+  // The rows are exported in the order of the view and the unknown ids are skipped
+  let row_ids = vec![
+    rows[3].row.id.clone(),
+    RowId::from("unknown_row".to_string()),
+    rows[1].row.id.clone(),
+  ];
+  let csv = test
+    .editor
+    .export_rows_csv(&view_id, row_ids, CSVFormat::Original)
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+  let text_column = reader
+    .headers()
+    .unwrap()
+    .iter()
+    .position(|header| header == text_field.name)
+    .unwrap();
+  let names = reader
+    .records()
+    .map(|record| record.unwrap()[text_column].to_string())
+    .collect::<Vec<String>>();
+  let expected_names = [&rows[1], &rows[3]]
+    .iter()
+    .map(|row_detail| {
+      row_detail
+        .row
+        .cells
+        .get(&text_field.id)
+        .map(|cell| stringify_cell(cell, &text_field))
+        .unwrap_or_default()
+    })
+    .collect::<Vec<String>>();
+  assert_eq!(names, expected_names);
+
+  // Only the header is exported if no row is selected
+  let csv = test
+    .editor
+    .export_rows_csv(&view_id, vec![], CSVFormat::Original)
+    .await
+    .unwrap();
+  let mut reader = csv::Reader::from_reader(csv.as_bytes());
+  assert!(!reader.headers().unwrap().is_empty());
+  assert_eq!(reader.records().count(), 0);
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::cell::stringify_cell;
+use flowy_database2::services::share::csv::{
+  CSVExportOptions, CSVFormat, CSVStreamImportOptions, ROW_ID_COLUMN, ROW_METADATA_COLUMNS,
+};
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn import_csv_stream_into_grid_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let number_field = test.get_first_field(FieldType::Number);
+  // The text field is matched by its name, and the unknown column is skipped
+  let csv = "Name,Amount,Unknown\nApple,12,x\nPear,7,y,extra\nPlum,,z\n";
+  let mapping = HashMap::from([("Amount".to_string(), number_field.id.clone())]);
+  let options = CSVStreamImportOptions {
+    batch_size: 1,
+    ..Default::default()
+  };
+  let (tx, mut rx) = mpsc::channel(10);
+  let result = test
+    .editor
+    .import_csv_stream(
+      &test.view_id,
+      csv.as_bytes(),
+      mapping,
+      options,
+      tx,
+      CancellationToken::new(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(result.rows_imported, 2);
+  assert!(!result.is_cancelled);
+  // The line with an extra value is reported without aborting the import
+  assert_eq!(result.errors.len(), 1);
+  assert_eq!(result.errors[0].line, 3);
+
+  let mut last_progress = None;
+  while let Ok(progress) = rx.try_recv() {
+    last_progress = Some(progress);
+  }
+  let last_progress = last_progress.unwrap();
+  assert_eq!(last_progress.rows_imported, 2);
+  assert_eq!(last_progress.rows_total_estimate, 2);
+
+  let rows = test.get_rows().await;
+  assert_eq!(rows.len(), test.row_details.len() + 2);
+  let apple = &rows[rows.len() - 2].row;
+  assert_eq!(
+    stringify_cell(apple.cells.get(&text_field.id).unwrap(), &text_field),
+    "Apple"
+  );
+  assert_eq!(
+    stringify_cell(apple.cells.get(&number_field.id).unwrap(), &number_field),
+    "$12"
+  );
+  let plum = &rows[rows.len() - 1].row;
+  assert!(plum.cells.get(&number_field.id).is_none());
+}
+
+#[tokio::test]
+async fn import_csv_stream_cancelled_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let cancel_token = CancellationToken::new();
+  cancel_token.cancel();
+  let (tx, _rx) = mpsc::channel(10);
+  let result = test
+    .editor
+    .import_csv_stream(
+      &test.view_id,
+      "Name\nApple\nPear\n".as_bytes(),
+      HashMap::new(),
+      CSVStreamImportOptions::default(),
+      tx,
+      cancel_token,
+    )
+    .await
+    .unwrap();
+  assert!(result.is_cancelled);
+  assert_eq!(result.rows_imported, 0);
+  assert_eq!(test.get_rows().await.len(), test.row_details.len());
+}
+
+#[tokio::test]
+async fn export_and_import_csv_stream_with_row_ids_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let number_field = test.get_first_field(FieldType::Number);
+  let default_csv = test
+    .editor
+    .export_csv(CSVFormat::Original, CSVExportOptions::default())
+    .await
+    .unwrap();
+  assert!(!default_csv.starts_with(ROW_ID_COLUMN));
+
+  let options = CSVExportOptions {
+    include_row_metadata: true,
+    ..Default::default()
+  };
+  let csv = test
+    .editor
+    .export_csv(CSVFormat::Original, options)
+    .await
+    .unwrap();
+  let mut lines = csv.lines();
+  assert!(lines
+    .next()
+    .unwrap()
+    .starts_with(&ROW_METADATA_COLUMNS.join(",")));
+  let metadata = lines
+    .map(|line| line.split(',').take(3).collect::<Vec<_>>().join(","))
+    .collect::<Vec<String>>();
+  assert_eq!(metadata.len(), test.row_details.len());
+  assert!(metadata[0].starts_with(&test.row_details[0].row.id.to_string()));
+
+  // Rename the first three rows, one of which is locked, and add a new row
+  let locked_row_id = test.row_details[2].row.id.clone();
+  test
+    .editor
+    .set_row_locked(&locked_row_id, true)
+    .await
+    .unwrap();
+  let mut new_csv = format!("{},{}\n", ROW_METADATA_COLUMNS.join(","), text_field.name);
+  for (index, metadata) in metadata.iter().take(3).enumerate() {
+    new_csv.push_str(&format!("{},Renamed {}\n", metadata, index));
+  }
+  new_csv.push_str(",,,New row\n");
+
+  let (tx, _rx) = mpsc::channel(10);
+  let result = test
+    .editor
+    .import_csv_stream(
+      &test.view_id,
+      Cursor::new(new_csv.into_bytes()),
+      HashMap::new(),
+      CSVStreamImportOptions::default(),
+      tx,
+      CancellationToken::new(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(result.rows_imported, 3);
+  assert_eq!(result.rows_updated, 2);
+  assert_eq!(result.errors.len(), 1);
+  assert_eq!(result.errors[0].line, 4);
+
+  let rows = test.get_rows().await;
+  assert_eq!(rows.len(), test.row_details.len() + 1);
+  let name = |index: usize| {
+    rows[index]
+      .row
+      .cells
+      .get(&text_field.id)
+      .map(|cell| stringify_cell(cell, &text_field))
+      .unwrap_or_default()
+  };
+  assert_eq!(name(0), "Renamed 0");
+  assert_eq!(name(1), "Renamed 1");
+  assert_ne!(name(2), "Renamed 2");
+  assert_eq!(name(rows.len() - 1), "New row");
+  // The columns that aren't imported keep their cells
+  assert_eq!(
+    rows[0].row.cells.get(&number_field.id),
+    test.row_details[0].row.cells.get(&number_field.id)
+  );
+  assert_eq!(rows[0].row.created_at, test.row_details[0].row.created_at);
+}
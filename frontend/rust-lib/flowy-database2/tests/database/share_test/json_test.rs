@@ -0,0 +1,112 @@
+use flowy_database2::entities::FieldType;
+use flowy_database2::services::field::{
+  select_type_option_from_field, DateCellChangeset, DateCellData,
+};
+use lib_infra::box_any::BoxAny;
+use serde_json::{json, Value};
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn export_and_import_json_round_trip_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let json = test.editor.export_json(&test.view_id, false).await.unwrap();
+  let result = test.editor.import_json(&test.view_id, &json).await.unwrap();
+  assert_eq!(result.rows_imported, 7);
+  assert!(result.errors.is_empty(), "{:?}", result.errors);
+  assert_eq!(test.get_rows().await.len(), 14);
+
+  // The imported rows hold the same values as the exported ones
+  let json = test.editor.export_json(&test.view_id, false).await.unwrap();
+  let data = serde_json::from_str::<Value>(&json).unwrap();
+  let rows = data["rows"].as_array().unwrap();
+  assert_eq!(rows[..7], rows[7..]);
+}
+
+#[tokio::test]
+async fn export_and_import_json_date_range_test() {
+  let mut test = DatabaseEditorTest::new_grid().await;
+  let date_field = test.get_first_field(FieldType::DateTime);
+  let row_id = test.get_rows().await[0].row.id.clone();
+  test
+    .update_cell(
+      &date_field.id,
+      row_id,
+      BoxAny::new(DateCellChangeset {
+        date: Some(1678060800),
+        end_date: Some(1678320000),
+        include_time: Some(true),
+        is_range: Some(true),
+        ..Default::default()
+      }),
+    )
+    .await
+    .unwrap();
+
+  let json = test.editor.export_json(&test.view_id, false).await.unwrap();
+  let data = serde_json::from_str::<Value>(&json).unwrap();
+  assert_eq!(
+    data["rows"][0][&date_field.id],
+    json!({
+      "timestamp": 1678060800,
+      "end_timestamp": 1678320000,
+      "include_time": true,
+      "is_range": true,
+    })
+  );
+
+  // The range is kept when the rows are imported
+  test.editor.import_json(&test.view_id, &json).await.unwrap();
+  let rows = test.get_rows().await;
+  let cell = rows[7].row.cells.get(&date_field.id).unwrap();
+  let cell_data = DateCellData::from(cell);
+  assert_eq!(cell_data.timestamp, Some(1678060800));
+  assert_eq!(cell_data.end_timestamp, Some(1678320000));
+  assert!(cell_data.include_time);
+  assert!(cell_data.is_range);
+}
+
+#[tokio::test]
+async fn import_json_reports_mismatches_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let select_field = test.get_first_field(FieldType::SingleSelect);
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  let json = json!({
+    "fields": [
+      { "id": "unknown", "name": "Unknown", "field_type": FieldType::RichText },
+      { "id": text_field.id, "name": text_field.name, "field_type": FieldType::Number },
+      { "id": "other_id", "name": select_field.name, "field_type": FieldType::SingleSelect },
+      { "id": checkbox_field.id, "name": checkbox_field.name, "field_type": FieldType::Checkbox },
+    ],
+    "rows": [
+      {
+        "unknown": "a",
+        text_field.id.clone(): 1,
+        "other_id": "Imported",
+        checkbox_field.id.clone(): true,
+      },
+      { checkbox_field.id.clone(): "not a bool" },
+    ],
+  });
+  let result = test
+    .editor
+    .import_json(&test.view_id, &json.to_string())
+    .await
+    .unwrap();
+  assert_eq!(result.rows_imported, 2);
+  assert_eq!(result.warnings.len(), 1);
+  assert!(result.warnings[0].contains("Unknown"));
+  assert_eq!(result.errors.len(), 2);
+  assert!(result.errors[0].contains(&text_field.name));
+  assert!(result.errors[1].starts_with("Row 2"));
+
+  // The select option is created while importing, the field is matched by name
+  let select_field = test.get_field(&select_field.id, FieldType::SingleSelect);
+  let type_option = select_type_option_from_field(&select_field).unwrap();
+  assert!(type_option
+    .options()
+    .iter()
+    .any(|option| option.name == "Imported"));
+  assert_eq!(test.get_rows().await.len(), 9);
+}
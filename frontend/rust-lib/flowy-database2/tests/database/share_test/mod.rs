@@ -1 +1,5 @@
+mod clone_structure_test;
 mod export_test;
+mod import_stream_test;
+mod json_test;
+mod view_settings_test;
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use collab_database::database::gen_database_view_id;
+use collab_database::views::DatabaseLayout;
+use flowy_database2::entities::{
+  CheckboxFilterConditionPB, FieldType, FieldVisibility, UpdateSortPayloadPB,
+};
+use flowy_database2::services::filter::{FilterChangeset, FilterInner};
+use flowy_database2::services::sort::SortCondition;
+
+use crate::database::database_editor::DatabaseEditorTest;
+
+#[tokio::test]
+async fn import_view_settings_into_another_database_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let text_field = test.get_first_field(FieldType::RichText);
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  test
+    .editor
+    .create_or_update_sort(UpdateSortPayloadPB {
+      view_id: test.view_id.clone(),
+      field_id: text_field.id.clone(),
+      sort_id: None,
+      condition: SortCondition::Descending.into(),
+    })
+    .await
+    .unwrap();
+  test
+    .editor
+    .modify_view_filters(
+      &test.view_id,
+      FilterChangeset::Insert {
+        parent_filter_id: None,
+        data: FilterInner::new_data(
+          checkbox_field.id.clone(),
+          FieldType::Checkbox,
+          CheckboxFilterConditionPB::IsChecked as i64,
+          "".to_string(),
+        ),
+      },
+    )
+    .await
+    .unwrap();
+  let json = test
+    .editor
+    .export_view_settings(&test.view_id)
+    .await
+    .unwrap();
+
+  // Create a similar database whose fields have different ids, and remove its checkbox field
+  let params = test.editor.clone_structure().await.unwrap();
+  let new_view_id = params.params.inline_view_id.clone();
+  test
+    .sdk
+    .database_manager
+    .create_database_with_clone_params(params)
+    .await
+    .unwrap();
+  let database = test
+    .sdk
+    .database_manager
+    .get_database_with_view_id(&new_view_id)
+    .await
+    .unwrap();
+  database.delete_all_sorts(&new_view_id).await.unwrap();
+  let new_fields = database.get_fields(&new_view_id, None);
+  let new_checkbox_field = new_fields
+    .iter()
+    .find(|field| field.name == checkbox_field.name)
+    .unwrap();
+  database.delete_field(&new_checkbox_field.id).await.unwrap();
+
+  let result = test
+    .sdk
+    .database_manager
+    .import_view_settings(&new_view_id, &json)
+    .await
+    .unwrap();
+
+  // The sort is remapped to the text field of the new database by name
+  let new_text_field = new_fields
+    .iter()
+    .find(|field| field.name == text_field.name)
+    .unwrap();
+  let sorts = database.get_all_sorts(&new_view_id).await.items;
+  assert_eq!(sorts.len(), 1);
+  assert_eq!(sorts[0].field_id, new_text_field.id);
+  assert_eq!(sorts[0].condition, SortCondition::Descending.into());
+
+  // The filter on the removed checkbox field is dropped with a warning
+  assert!(database
+    .get_all_filters(&new_view_id)
+    .await
+    .items
+    .is_empty());
+  assert!(result
+    .warnings
+    .iter()
+    .any(|warning| warning.contains("filter") && warning.contains(&checkbox_field.name)));
+}
+
+#[tokio::test]
+async fn import_invalid_view_settings_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let result = test.editor.import_view_settings(&test.view_id, "{}").await;
+  assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn reset_view_test() {
+  let test = DatabaseEditorTest::new_grid().await;
+  let database_id = test
+    .sdk
+    .database_manager
+    .get_database_id_with_view_id(&test.view_id)
+    .await
+    .unwrap();
+  let view_id = gen_database_view_id();
+  test
+    .sdk
+    .database_manager
+    .create_linked_view(
+      "linked".to_string(),
+      DatabaseLayout::Grid,
+      database_id,
+      view_id.clone(),
+    )
+    .await
+    .unwrap();
+  let text_field = test.get_first_field(FieldType::RichText);
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+
+  // The last row is moved to the top of the linked view only
+  let row_ids = test
+    .editor
+    .get_rows(&view_id)
+    .await
+    .unwrap()
+    .iter()
+    .map(|row_detail| row_detail.row.id.clone())
+    .collect::<Vec<_>>();
+  test
+    .editor
+    .move_row(
+      &view_id,
+      row_ids.last().unwrap().clone(),
+      row_ids.first().unwrap().clone(),
+    )
+    .await
+    .unwrap();
+  test
+    .editor
+    .create_or_update_sort(UpdateSortPayloadPB {
+      view_id: view_id.clone(),
+      field_id: text_field.id.clone(),
+      sort_id: None,
+      condition: SortCondition::Descending.into(),
+    })
+    .await
+    .unwrap();
+  test
+    .editor
+    .modify_view_filters(
+      &view_id,
+      FilterChangeset::Insert {
+        parent_filter_id: None,
+        data: FilterInner::new_data(
+          checkbox_field.id.clone(),
+          FieldType::Checkbox,
+          CheckboxFilterConditionPB::IsChecked as i64,
+          "".to_string(),
+        ),
+      },
+    )
+    .await
+    .unwrap();
+  test
+    .editor
+    .set_fields_visibility(
+      &view_id,
+      HashMap::from([(checkbox_field.id.clone(), false)]),
+    )
+    .await
+    .unwrap();
+
+  // Resetting twice is the same as resetting once
+  for _ in 0..2 {
+    test.editor.reset_view(&view_id).await.unwrap();
+    assert!(test.editor.get_all_sorts(&view_id).await.items.is_empty());
+    assert!(test.editor.get_all_filters(&view_id).await.items.is_empty());
+    let field_settings = test
+      .editor
+      .get_field_settings(&view_id, vec![checkbox_field.id.clone()])
+      .await
+      .unwrap();
+    assert_eq!(field_settings[0].visibility, FieldVisibility::AlwaysShown);
+
+    let rows = test.editor.get_rows(&view_id).await.unwrap();
+    let reset_row_ids = rows
+      .iter()
+      .map(|row_detail| row_detail.row.id.clone())
+      .collect::<Vec<_>>();
+    assert_eq!(reset_row_ids, row_ids);
+    assert!(test.editor.get_field(&text_field.id).is_some());
+  }
+}
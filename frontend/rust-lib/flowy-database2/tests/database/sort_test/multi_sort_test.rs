@@ -99,3 +99,63 @@ async fn reorder_sort_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn set_sorts_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  let text_field = test.get_first_field(FieldType::RichText);
+  let scripts = vec![
+    InsertSort {
+      field: text_field.clone(),
+      condition: SortCondition::Descending,
+    },
+    // Replace the text sort with the same sorts as the test above
+    SetSorts {
+      sorts: vec![
+        (checkbox_field.clone(), SortCondition::Descending),
+        (text_field.clone(), SortCondition::Ascending),
+      ],
+      is_err: false,
+    },
+    AssertSortCount { expected: 2 },
+    AssertCellContentOrder {
+      field_id: checkbox_field.id.clone(),
+      orders: vec!["Yes", "Yes", "Yes", "No", "No", "", "No"],
+    },
+    AssertCellContentOrder {
+      field_id: text_field.id.clone(),
+      orders: vec!["A", "AE", "", "AE", "C", "CB", "DA"],
+    },
+    // Remove all the sorts
+    SetSorts {
+      sorts: vec![],
+      is_err: false,
+    },
+    AssertSortCount { expected: 0 },
+  ];
+  test.run_scripts(scripts).await;
+}
+
+#[tokio::test]
+async fn set_sorts_with_duplicate_field_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  let text_field = test.get_first_field(FieldType::RichText);
+  let scripts = vec![
+    InsertSort {
+      field: checkbox_field.clone(),
+      condition: SortCondition::Descending,
+    },
+    SetSorts {
+      sorts: vec![
+        (text_field.clone(), SortCondition::Ascending),
+        (text_field.clone(), SortCondition::Descending),
+      ],
+      is_err: true,
+    },
+    // The existing sorts are kept
+    AssertSortCount { expected: 1 },
+  ];
+  test.run_scripts(scripts).await;
+}
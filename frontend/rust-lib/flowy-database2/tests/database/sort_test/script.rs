@@ -8,7 +8,8 @@ use futures::stream::StreamExt;
 use tokio::sync::broadcast::Receiver;
 
 use flowy_database2::entities::{
-  CreateRowPayloadPB, DeleteSortPayloadPB, ReorderSortPayloadPB, UpdateSortPayloadPB,
+  CreateRowPayloadPB, DeleteSortPayloadPB, ReorderSortPayloadPB, SetSortsPayloadPB,
+  UpdateSortPayloadPB,
 };
 use flowy_database2::services::cell::stringify_cell;
 use flowy_database2::services::database_view::DatabaseViewChanged;
@@ -21,6 +22,13 @@ pub enum SortScript {
     field: Field,
     condition: SortCondition,
   },
+  SetSorts {
+    sorts: Vec<(Field, SortCondition)>,
+    is_err: bool,
+  },
+  AssertSortCount {
+    expected: usize,
+  },
   ReorderSort {
     from_sort_id: String,
     to_sort_id: String,
@@ -83,6 +91,33 @@ impl DatabaseSortTest {
         };
         let _ = self.editor.create_or_update_sort(params).await.unwrap();
       },
+      SortScript::SetSorts { sorts, is_err } => {
+        self.recv = Some(
+          self
+            .editor
+            .subscribe_view_changed(&self.view_id)
+            .await
+            .unwrap(),
+        );
+        let params = SetSortsPayloadPB {
+          view_id: self.view_id.clone(),
+          sorts: sorts
+            .into_iter()
+            .map(|(field, condition)| UpdateSortPayloadPB {
+              view_id: self.view_id.clone(),
+              field_id: field.id,
+              sort_id: None,
+              condition: condition.into(),
+            })
+            .collect(),
+        };
+        let result = self.editor.set_sorts(params).await;
+        assert_eq!(result.is_err(), is_err);
+      },
+      SortScript::AssertSortCount { expected } => {
+        let sorts = self.editor.get_all_sorts(&self.view_id).await;
+        assert_eq!(sorts.items.len(), expected);
+      },
       SortScript::ReorderSort {
         from_sort_id,
         to_sort_id,
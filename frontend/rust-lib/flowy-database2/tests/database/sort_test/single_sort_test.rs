@@ -1,5 +1,8 @@
+use collab_database::rows::RowId;
 use flowy_database2::entities::FieldType;
+use flowy_database2::services::field::{RelationCellChangeset, RelationTypeOption};
 use flowy_database2::services::sort::SortCondition;
+use lib_infra::box_any::BoxAny;
 
 use crate::database::sort_test::script::{DatabaseSortTest, SortScript::*};
 
@@ -107,6 +110,47 @@ async fn sort_after_new_row_test() {
   test.run_scripts(scripts).await;
 }
 
+#[tokio::test]
+async fn sort_equal_key_rows_keep_stable_order_after_reopen_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let checkbox_field = test.get_first_field(FieldType::Checkbox);
+  for _ in 0..20 {
+    test.run_script(AddNewRow).await;
+  }
+  let scripts = vec![
+    InsertSort {
+      field: checkbox_field.clone(),
+      condition: SortCondition::Ascending,
+    },
+    Wait { millis: 200 },
+  ];
+  test.run_scripts(scripts).await;
+
+  let first_load = test
+    .get_rows()
+    .await
+    .iter()
+    .map(|row_detail| row_detail.row.id.to_string())
+    .collect::<Vec<String>>();
+
+  // Reopen the view so the rows are loaded and sorted again from scratch
+  test.editor.close_view(&test.view_id).await;
+  let second_load = test
+    .get_rows()
+    .await
+    .iter()
+    .map(|row_detail| row_detail.row.id.to_string())
+    .collect::<Vec<String>>();
+  assert_eq!(first_load.len(), 27);
+  assert_eq!(first_load, second_load);
+
+  // The 21 rows without a checkbox value share the same sort key, so they're ordered by row id
+  let empty_rows = &first_load[3..24];
+  let mut sorted_empty_rows = empty_rows.to_vec();
+  sorted_empty_rows.sort();
+  assert_eq!(empty_rows, sorted_empty_rows.as_slice());
+}
+
 #[tokio::test]
 async fn sort_text_by_ascending_and_delete_sort_test() {
   let mut test = DatabaseSortTest::new().await;
@@ -481,3 +525,88 @@ async fn sort_checklist_by_descending_test() {
   ];
   test.run_scripts(scripts).await;
 }
+
+#[tokio::test]
+async fn sort_relation_by_related_row_title_test() {
+  let mut test = DatabaseSortTest::new().await;
+  let relation_field = test.get_first_field(FieldType::Relation);
+  let database_id = test
+    .editor
+    .get_database_data(&test.view_id)
+    .await
+    .unwrap()
+    .id;
+  let type_option = RelationTypeOption { database_id };
+  test
+    .editor
+    .update_field_type_option(
+      &relation_field.id,
+      type_option.into(),
+      relation_field.clone(),
+    )
+    .await
+    .unwrap();
+
+  // The rows link to the other rows of the same database, whose titles are "A", "", "C", "DA"
+  // and "AE"
+  let row_ids = test
+    .get_rows()
+    .await
+    .iter()
+    .map(|row_detail| row_detail.row.id.clone())
+    .collect::<Vec<RowId>>();
+  let links = vec![
+    (0, vec![3]),
+    (1, vec![2, 4]),
+    (3, vec![0]),
+    // The linked row has no title, so the cell is sorted like an empty one
+    (4, vec![1]),
+  ];
+  for (row_index, linked_row_indexes) in links {
+    let changeset = RelationCellChangeset {
+      inserted_row_ids: linked_row_indexes
+        .into_iter()
+        .map(|index| row_ids[index].clone())
+        .collect(),
+      ..Default::default()
+    };
+    test
+      .editor
+      .update_cell_with_changeset(
+        &test.view_id,
+        &row_ids[row_index],
+        &relation_field.id,
+        BoxAny::new(changeset),
+      )
+      .await
+      .unwrap();
+  }
+
+  for (condition, expected) in [
+    (SortCondition::Ascending, [3, 1, 0]),
+    (SortCondition::Descending, [0, 1, 3]),
+  ] {
+    test
+      .run_scripts(vec![InsertSort {
+        field: relation_field.clone(),
+        condition,
+      }])
+      .await;
+    let sorted_row_ids = test
+      .editor
+      .get_rows(&test.view_id)
+      .await
+      .unwrap()
+      .iter()
+      .map(|row_detail| row_detail.row.id.clone())
+      .collect::<Vec<RowId>>();
+    let expected = expected
+      .iter()
+      .map(|index| row_ids[*index].clone())
+      .collect::<Vec<RowId>>();
+    // The empty relations are placed at the end in both directions
+    assert_eq!(sorted_row_ids[..3], expected[..]);
+    assert_eq!(sorted_row_ids.len(), row_ids.len());
+    test.editor.delete_all_sorts(&test.view_id).await.unwrap();
+  }
+}
@@ -11,6 +11,7 @@ use collab_document::{
 
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::ErrorCode;
+use flowy_storage::{UploadProgress, UploadState};
 use lib_infra::validator_fn::{required_not_empty_str, required_valid_path};
 use validator::Validate;
 
@@ -95,6 +96,52 @@ pub struct UploadedFilePB {
   pub local_file_path: String,
 }
 
+#[derive(PartialEq, Eq, Debug, ProtoBuf_Enum, Clone, Copy, Default)]
+pub enum UploadStatePB {
+  #[default]
+  Uploading = 0,
+  Finished = 1,
+  Failed = 2,
+  Cancelled = 3,
+}
+
+impl From<UploadState> for UploadStatePB {
+  fn from(state: UploadState) -> Self {
+    match state {
+      UploadState::Uploading => UploadStatePB::Uploading,
+      UploadState::Finished => UploadStatePB::Finished,
+      UploadState::Failed => UploadStatePB::Failed,
+      UploadState::Cancelled => UploadStatePB::Cancelled,
+    }
+  }
+}
+
+#[derive(Default, ProtoBuf, Debug, Clone)]
+pub struct UploadProgressPB {
+  #[pb(index = 1)]
+  pub file_id: String,
+
+  #[pb(index = 2)]
+  pub bytes_uploaded: u64,
+
+  #[pb(index = 3)]
+  pub total_bytes: u64,
+
+  #[pb(index = 4)]
+  pub state: UploadStatePB,
+}
+
+impl From<&UploadProgress> for UploadProgressPB {
+  fn from(progress: &UploadProgress) -> Self {
+    Self {
+      file_id: progress.file_id.clone(),
+      bytes_uploaded: progress.bytes_uploaded,
+      total_bytes: progress.total_bytes,
+      state: progress.state.into(),
+    }
+  }
+}
+
 #[derive(Default, ProtoBuf)]
 pub struct CreateDocumentPayloadPB {
   #[pb(index = 1)]
@@ -11,4 +11,5 @@ pub mod deps;
 pub mod notification;
 mod parse;
 pub mod reminder;
+mod upload_progress;
 pub use collab_document::document::DocumentIndexContent;
@@ -16,13 +16,14 @@ use dashmap::DashMap;
 use flowy_storage::object_from_disk;
 use lib_infra::util::timestamp;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
 use tracing::{error, trace};
 use tracing::{event, instrument};
 
 use collab_integrate::collab_builder::{AppFlowyCollabBuilder, CollabBuilderConfig};
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
-use flowy_storage::ObjectStorageService;
+use flowy_storage::{ObjectStorageService, UploadProgress};
 use lib_dispatch::prelude::af_spawn;
 
 use crate::document::MutexDocument;
@@ -31,6 +32,7 @@ use crate::entities::{
   DocumentSnapshotData, DocumentSnapshotMeta, DocumentSnapshotMetaPB, DocumentSnapshotPB,
 };
 use crate::reminder::DocumentReminderAction;
+use crate::upload_progress::UploadProgressReporter;
 
 pub trait DocumentUserService: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
@@ -55,6 +57,7 @@ pub struct DocumentManager {
   cloud_service: Arc<dyn DocumentCloudService>,
   storage_service: Weak<dyn ObjectStorageService>,
   snapshot_service: Arc<dyn DocumentSnapshotService>,
+  upload_progress: broadcast::Sender<UploadProgress>,
 }
 
 impl DocumentManager {
@@ -65,6 +68,7 @@ impl DocumentManager {
     storage_service: Weak<dyn ObjectStorageService>,
     snapshot_service: Arc<dyn DocumentSnapshotService>,
   ) -> Self {
+    let (upload_progress, _) = broadcast::channel(100);
     Self {
       user_service,
       collab_builder,
@@ -73,6 +77,7 @@ impl DocumentManager {
       cloud_service,
       storage_service,
       snapshot_service,
+      upload_progress,
     }
   }
 
@@ -310,25 +315,46 @@ impl DocumentManager {
     is_async: bool,
   ) -> FlowyResult<String> {
     let (object_identity, object_value) = object_from_disk(&workspace_id, local_file_path).await?;
+    let file_id = object_identity.file_id.clone();
+    let total_bytes = object_value.raw.len() as u64;
     let storage_service = self.storage_service_upgrade()?;
     let url = storage_service.get_object_url(object_identity).await?;
 
     let clone_url = url.clone();
+    let reporter = UploadProgressReporter::start(
+      self.upload_progress.clone(),
+      &workspace_id,
+      &file_id,
+      total_bytes,
+    );
 
     match is_async {
-      false => storage_service.put_object(clone_url, object_value).await?,
+      false => {
+        let result = storage_service.put_object(clone_url, object_value).await;
+        reporter.finish(&result);
+        result?
+      },
       true => {
         // let the upload happen in the background
         af_spawn(async move {
-          if let Err(e) = storage_service.put_object(clone_url, object_value).await {
+          let result = storage_service.put_object(clone_url, object_value).await;
+          if let Err(e) = &result {
             error!("upload file failed: {}", e);
           }
+          reporter.finish(&result);
         });
       },
     }
     Ok(url)
   }
 
+  /// Subscribes to the progress of the file uploads. Each upload reports
+  /// [UploadState::Uploading](flowy_storage::UploadState::Uploading) when it starts, and ends with
+  /// a finished, failed or cancelled state.
+  pub fn subscribe_upload_progress(&self) -> broadcast::Receiver<UploadProgress> {
+    self.upload_progress.subscribe()
+  }
+
   pub async fn download_file(&self, local_file_path: String, url: String) -> FlowyResult<()> {
     // TODO(nathan): save file when the current target is wasm
     #[cfg(not(target_arch = "wasm32"))]
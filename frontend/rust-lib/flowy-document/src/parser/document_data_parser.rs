@@ -55,6 +55,14 @@ impl DocumentDataParser {
     self.to_text_with_json(&json)
   }
 
+  /// Converts the document data to Markdown.
+  pub fn to_markdown(&self) -> String {
+    self
+      .to_json()
+      .map(|json| json.convert_to_markdown(""))
+      .unwrap_or_default()
+  }
+
   /// Converts the document data to a nested JSON structure, considering the optional range.
   pub fn to_json(&self) -> Option<NestedBlock> {
     let root_id = &self.document_data.page_id;
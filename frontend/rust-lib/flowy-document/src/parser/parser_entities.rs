@@ -491,6 +491,78 @@ impl NestedBlock {
     };
     text
   }
+
+  /// Converts the block and its children to Markdown. The children of the list blocks are indented
+  /// with `indent`. The inline styles are not kept.
+  pub fn convert_to_markdown(&self, indent: &str) -> String {
+    let mut markdown = String::new();
+
+    let delta_text = self
+      .data
+      .get(DELTA)
+      .and_then(convert_insert_delta_from_json)
+      .map(|delta| delta_to_text(&delta))
+      .unwrap_or_default();
+    let str_value = |key: &str| {
+      self
+        .data
+        .get(key)
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
+    };
+
+    let list_marker = match self.ty.as_str() {
+      BULLETED_LIST | TOGGLE_LIST => Some("- ".to_string()),
+      NUMBERED_LIST => Some("1. ".to_string()),
+      TODO_LIST => {
+        let checked = self
+          .data
+          .get(CHECKED)
+          .and_then(|v| v.as_bool())
+          .unwrap_or_default();
+        Some(format!("- [{}] ", if checked { "x" } else { " " }))
+      },
+      _ => None,
+    };
+
+    match list_marker {
+      Some(marker) => {
+        markdown.push_str(&format!("{}{}{}\n", indent, marker, delta_text));
+        let child_indent = format!("{}  ", indent);
+        for child in &self.children {
+          markdown.push_str(&child.convert_to_markdown(&child_indent));
+        }
+      },
+      None => {
+        let line = match self.ty.as_str() {
+          HEADING => {
+            let level = self
+              .data
+              .get(LEVEL)
+              .and_then(|v| v.as_u64())
+              .unwrap_or(1)
+              .clamp(1, 6);
+            format!("{} {}", "#".repeat(level as usize), delta_text)
+          },
+          QUOTE => format!("> {}", delta_text),
+          CALLOUT => format!("> {}{}", str_value(ICON), delta_text),
+          IMAGE => format!("![]({})", str_value(URL)),
+          DIVIDER => "---".to_string(),
+          MATH_EQUATION => format!("$${}$$", str_value(FORMULA)),
+          CODE => format!("```{}\n{}\n```", str_value(LANGUAGE), delta_text),
+          _ => delta_text,
+        };
+        if !(self.ty == PAGE && line.is_empty()) {
+          markdown.push_str(&format!("{}{}\n\n", indent, line));
+        }
+        for child in &self.children {
+          markdown.push_str(&child.convert_to_markdown(indent));
+        }
+      },
+    }
+    markdown
+  }
 }
 
 pub struct ConvertBlockToHtmlParams {
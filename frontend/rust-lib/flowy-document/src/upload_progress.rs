@@ -0,0 +1,109 @@
+use flowy_error::FlowyResult;
+use flowy_storage::{UploadProgress, UploadState};
+use tokio::sync::broadcast;
+
+use crate::entities::UploadProgressPB;
+use crate::notification::{send_notification, DocumentNotification};
+
+/// Reports the progress of an upload to the subscribers of
+/// [crate::manager::DocumentManager::subscribe_upload_progress] and to the frontend, which
+/// observes it by the workspace id.
+///
+/// If it's dropped before the upload finishes, e.g. the task that uploads the file is cancelled,
+/// it reports [UploadState::Cancelled], so every upload ends with a terminal state.
+pub(crate) struct UploadProgressReporter {
+  workspace_id: String,
+  progress: UploadProgress,
+  tx: broadcast::Sender<UploadProgress>,
+}
+
+impl UploadProgressReporter {
+  pub(crate) fn start(
+    tx: broadcast::Sender<UploadProgress>,
+    workspace_id: &str,
+    file_id: &str,
+    total_bytes: u64,
+  ) -> Self {
+    let reporter = Self {
+      workspace_id: workspace_id.to_string(),
+      progress: UploadProgress {
+        file_id: file_id.to_string(),
+        bytes_uploaded: 0,
+        total_bytes,
+        state: UploadState::Uploading,
+      },
+      tx,
+    };
+    reporter.report();
+    reporter
+  }
+
+  pub(crate) fn finish<T>(mut self, result: &FlowyResult<T>) {
+    match result {
+      Ok(_) => {
+        self.progress.bytes_uploaded = self.progress.total_bytes;
+        self.progress.state = UploadState::Finished;
+      },
+      Err(_) => self.progress.state = UploadState::Failed,
+    }
+    self.report();
+  }
+
+  fn report(&self) {
+    // The send fails when there is no subscriber, which is fine
+    let _ = self.tx.send(self.progress.clone());
+    send_notification(
+      &self.workspace_id,
+      DocumentNotification::DidUpdateUploadProgress,
+    )
+    .payload(UploadProgressPB::from(&self.progress))
+    .send();
+  }
+}
+
+impl Drop for UploadProgressReporter {
+  fn drop(&mut self) {
+    if !self.progress.state.is_terminal() {
+      self.progress.state = UploadState::Cancelled;
+      self.report();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use flowy_error::FlowyError;
+  use flowy_storage::UploadState;
+  use tokio::sync::broadcast;
+
+  use super::UploadProgressReporter;
+
+  #[test]
+  fn upload_progress_reporter_test() {
+    let (tx, mut rx) = broadcast::channel(10);
+
+    let reporter = UploadProgressReporter::start(tx.clone(), "w1", "f1", 100);
+    reporter.finish(&Ok::<(), FlowyError>(()));
+    let started = rx.try_recv().unwrap();
+    assert_eq!(
+      (started.bytes_uploaded, started.state),
+      (0, UploadState::Uploading)
+    );
+    let finished = rx.try_recv().unwrap();
+    assert_eq!(
+      (finished.bytes_uploaded, finished.state),
+      (100, UploadState::Finished)
+    );
+    assert!(rx.try_recv().is_err());
+
+    let reporter = UploadProgressReporter::start(tx.clone(), "w1", "f2", 100);
+    reporter.finish(&Err::<(), FlowyError>(FlowyError::internal()));
+    assert_eq!(rx.try_recv().unwrap().state, UploadState::Uploading);
+    assert_eq!(rx.try_recv().unwrap().state, UploadState::Failed);
+
+    let reporter = UploadProgressReporter::start(tx, "w1", "f3", 100);
+    drop(reporter);
+    assert_eq!(rx.try_recv().unwrap().state, UploadState::Uploading);
+    assert_eq!(rx.try_recv().unwrap().state, UploadState::Cancelled);
+  }
+}
@@ -1,4 +1,6 @@
-use crate::parser::parse_to_html_text::utils::{assert_document_html_eq, assert_document_text_eq};
+use crate::parser::parse_to_html_text::utils::{
+  assert_document_html_eq, assert_document_markdown_eq, assert_document_text_eq,
+};
 
 macro_rules! generate_test_cases {
     ($($block_ty:ident),*) => {
@@ -35,3 +37,24 @@ async fn block_tests() {
     assert_document_text_eq(json_data, expect_text);
   }
 }
+
+#[tokio::test]
+async fn markdown_tests() {
+  let test_cases = [
+    (
+      include_str!("../../assets/json/heading.json"),
+      "# Heading1\n\n## Heading2\n\n### Heading3\n\n",
+    ),
+    (
+      include_str!("../../assets/json/todo_list.json"),
+      "- [x] Highlight\n  You can also\n\n  - [ ] nest\n",
+    ),
+    (
+      include_str!("../../assets/json/code.json"),
+      include_str!("../../assets/markdown/code.md"),
+    ),
+  ];
+  for (json_data, expect_markdown) in test_cases.iter() {
+    assert_document_markdown_eq(json_data, expect_markdown);
+  }
+}
@@ -280,6 +280,36 @@ pub enum ErrorCode {
 
   #[error("Workspace data not match")]
   WorkspaceDataNotMatch = 97,
+
+  #[error("The record is locked")]
+  RecordLocked = 98,
+
+  #[error("The number of the records to update exceeds the limit")]
+  BulkUpdateLimitExceeded = 99,
+
+  #[error("The operation is cancelled")]
+  Cancelled = 100,
+
+  #[error("The number of the records to scan exceeds the limit")]
+  ScanLimitExceeded = 101,
+
+  #[error("The request timed out")]
+  Timeout = 102,
+
+  #[error("The workspace belongs to a different type of account")]
+  WorkspaceAuthTypeMismatch = 103,
+
+  #[error("The workspace belongs to a different server")]
+  WorkspaceServerMismatch = 104,
+
+  #[error("The account doesn't sign in with a password")]
+  PasswordAuthNotSupported = 105,
+
+  #[error("The user is not logged in")]
+  UserNotLogin = 107,
+
+  #[error("The view can't be moved into itself or its child views")]
+  MoveViewIntoItself = 108,
 }
 
 impl ErrorCode {
@@ -80,6 +80,7 @@ impl FlowyError {
   static_flowy_error!(invalid_view_id, ErrorCode::ViewIdIsInvalid);
   static_flowy_error!(view_data, ErrorCode::ViewDataInvalid);
   static_flowy_error!(unauthorized, ErrorCode::UserUnauthorized);
+  static_flowy_error!(user_not_login, ErrorCode::UserNotLogin);
   static_flowy_error!(email_empty, ErrorCode::EmailIsEmpty);
   static_flowy_error!(email_format, ErrorCode::EmailFormatInvalid);
   static_flowy_error!(email_exist, ErrorCode::EmailAlreadyExists);
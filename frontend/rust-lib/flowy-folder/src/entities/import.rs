@@ -1,6 +1,6 @@
 use crate::entities::parser::empty_str::NotEmptyStr;
 use crate::entities::ViewLayoutPB;
-use crate::share::{ImportParams, ImportType};
+use crate::share::{ImportCSVOptions, ImportParams, ImportType};
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
 use flowy_error::FlowyError;
 
@@ -48,6 +48,14 @@ pub struct ImportPB {
 
   #[pb(index = 6)]
   pub import_type: ImportTypePB,
+
+  /// Orders the rows of an imported CSV by their creation time instead of the order of the CSV.
+  #[pb(index = 7)]
+  pub csv_sort_by_created_at: bool,
+
+  /// The delimiter of an imported CSV. It's detected from the header line if it's not set.
+  #[pb(index = 8, one_of)]
+  pub csv_delimiter: Option<String>,
 }
 
 impl TryInto<ImportParams> for ImportPB {
@@ -73,6 +81,21 @@ impl TryInto<ImportParams> for ImportPB {
       ),
     };
 
+    let delimiter = match self.csv_delimiter {
+      None => None,
+      Some(delimiter) => {
+        let mut chars = delimiter.chars();
+        match (chars.next(), chars.next()) {
+          (Some(delimiter), None) => Some(delimiter),
+          _ => {
+            return Err(
+              FlowyError::invalid_data().with_context("The CSV delimiter must be one character"),
+            )
+          },
+        }
+      },
+    };
+
     Ok(ImportParams {
       parent_view_id,
       name,
@@ -80,6 +103,10 @@ impl TryInto<ImportParams> for ImportPB {
       file_path,
       view_layout: self.view_layout.into(),
       import_type: self.import_type.into(),
+      csv_options: ImportCSVOptions {
+        sort_by_created_at: self.csv_sort_by_created_at,
+        delimiter,
+      },
     })
   }
 }
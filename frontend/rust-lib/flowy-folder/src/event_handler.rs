@@ -320,7 +320,7 @@ pub(crate) async fn putback_trash_handler(
   folder: AFPluginState<Weak<FolderManager>>,
 ) -> Result<(), FlowyError> {
   let folder = upgrade_folder(folder)?;
-  folder.restore_trash(&identifier.id).await;
+  folder.restore_from_trash(&identifier.id).await?;
   Ok(())
 }
 
@@ -332,7 +332,7 @@ pub(crate) async fn delete_trash_handler(
   let folder = upgrade_folder(folder)?;
   let trash_ids = identifiers.into_inner().items;
   for trash_id in trash_ids {
-    let _ = folder.delete_trash(&trash_id.id).await;
+    let _ = folder.permanently_delete(&trash_id.id).await;
   }
   Ok(())
 }
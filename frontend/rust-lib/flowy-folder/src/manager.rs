@@ -2,8 +2,8 @@ use crate::entities::icon::UpdateViewIconParams;
 use crate::entities::{
   view_pb_with_child_views, view_pb_without_child_views, view_pb_without_child_views_from_arc,
   CreateViewParams, CreateWorkspaceParams, DeletedViewPB, FolderSnapshotPB, MoveNestedViewParams,
-  RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB, UpdateViewParams, ViewPB, ViewSectionPB,
-  WorkspacePB, WorkspaceSettingPB,
+  RepeatedTrashPB, RepeatedViewIdPB, RepeatedViewPB, TrashPB, UpdateViewParams, ViewPB,
+  ViewSectionPB, WorkspacePB, WorkspaceSettingPB,
 };
 use crate::manager_observer::{
   notify_child_views_changed, notify_did_update_workspace, notify_parent_view_did_change,
@@ -34,12 +34,17 @@ use parking_lot::RwLock;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::sync::{Arc, Weak};
-use tracing::{error, info, instrument};
+use tracing::{error, info, instrument, warn};
 
 pub trait FolderUser: Send + Sync {
   fn user_id(&self) -> Result<i64, FlowyError>;
   fn workspace_id(&self) -> Result<String, FlowyError>;
   fn collab_db(&self, uid: i64) -> Result<Weak<CollabKVDB>, FlowyError>;
+  /// Returns the ids of the views in the quick-access list of the workspace, in order.
+  fn favorite_view_ids(&self, workspace_id: &str) -> Result<Vec<String>, FlowyError>;
+  fn add_favorite_view_id(&self, workspace_id: &str, view_id: &str) -> Result<(), FlowyError>;
+  /// Returns true if the view was in the quick-access list of the workspace.
+  fn remove_favorite_view_id(&self, workspace_id: &str, view_id: &str) -> Result<bool, FlowyError>;
 }
 
 pub struct FolderManager {
@@ -641,6 +646,125 @@ impl FolderManager {
     Ok(())
   }
 
+  /// Moves the view to the `index` of the child views of `new_parent_id`, which is either a view
+  /// or the current workspace. The index is clamped to the number of the child views. Only the
+  /// hierarchy changes, the data of the view, e.g. the rows of a database, is kept. The view and
+  /// its descendants move to the private or the public section of the new parent.
+  ///
+  /// Moving a view into itself or into one of its descendants fails with
+  /// [ErrorCode::MoveViewIntoItself].
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn move_view_to_parent(
+    &self,
+    view_id: &str,
+    new_parent_id: &str,
+    index: usize,
+  ) -> FlowyResult<()> {
+    let workspace_id = self.user.workspace_id()?;
+    let view = self.get_view_pb(view_id).await?;
+    let old_parent_id = view.parent_view_id;
+    {
+      // The checks and the move are made under the same lock, so the hierarchy can't change in
+      // between, e.g. by a concurrent move that would create a cycle.
+      let folder = self.mutex_folder.read();
+      let folder = folder.as_ref().ok_or_else(folder_not_init_error)?;
+      if self
+        .get_view_ids_should_be_filtered(folder)
+        .iter()
+        .any(|id| id == new_parent_id)
+      {
+        return Err(FlowyError::record_not_found().with_context(format!(
+          "The parent view: {} is in trash or other private sections",
+          new_parent_id
+        )));
+      }
+
+      let child_view_ids = match folder.views.get_view(new_parent_id) {
+        Some(parent_view) => {
+          let mut ancestor = Some(parent_view.clone());
+          while let Some(view) = ancestor {
+            if view.id == view_id {
+              return Err(FlowyError::new(
+                ErrorCode::MoveViewIntoItself,
+                format!("Can't move the view: {} into {}", view_id, new_parent_id),
+              ));
+            }
+            ancestor = folder.views.get_view(&view.parent_view_id);
+          }
+          parent_view
+            .children
+            .items
+            .iter()
+            .map(|view| view.id.clone())
+            .collect::<Vec<String>>()
+        },
+        None if new_parent_id == workspace_id => folder
+          .get_workspace_info(&workspace_id)
+          .map(|workspace| {
+            workspace
+              .child_views
+              .items
+              .into_iter()
+              .map(|view| view.id)
+              .collect::<Vec<String>>()
+          })
+          .unwrap_or_default(),
+        None => {
+          return Err(
+            FlowyError::record_not_found()
+              .with_context(format!("The parent view: {} doesn't exist", new_parent_id)),
+          )
+        },
+      };
+
+      // The view itself isn't counted when it's moved within the same parent
+      let siblings = child_view_ids
+        .into_iter()
+        .filter(|id| id != view_id)
+        .collect::<Vec<String>>();
+      let prev_view_id = match index.min(siblings.len()) {
+        0 => None,
+        index => Some(siblings[index - 1].clone()),
+      };
+      folder.move_nested_view(view_id, new_parent_id, prev_view_id);
+
+      // The view and its descendants join the section of the new parent. A view moved to the
+      // top level of the workspace keeps its section.
+      if new_parent_id != workspace_id {
+        let mut view_ids = vec![view_id.to_string()];
+        let mut index = 0;
+        while index < view_ids.len() {
+          let child_view_ids = folder
+            .views
+            .get_views_belong_to(&view_ids[index])
+            .into_iter()
+            .map(|view| view.id.clone())
+            .collect::<Vec<String>>();
+          view_ids.extend(child_view_ids);
+          index += 1;
+        }
+        if folder.is_view_in_section(Section::Private, new_parent_id) {
+          folder.add_private_view_ids(view_ids);
+        } else {
+          folder.delete_private_view_ids(view_ids);
+        }
+      }
+    }
+
+    notify_parent_view_did_change(
+      &workspace_id,
+      self.mutex_folder.clone(),
+      vec![new_parent_id.to_string(), old_parent_id],
+    );
+    // The favorites and the recent views are keyed by the view id, so they are still valid, but
+    // the frontend displays the path of the views in them.
+    self.send_update_recent_views_notification().await;
+    self
+      .send_update_favorite_views_notification(&workspace_id)
+      .await;
+    Ok(())
+  }
+
   /// Move the view with given id from one position to another position.
   /// The view will be moved to the new position in the same parent view.
   /// The passed in index is the index of the view that displayed in the UI.
@@ -852,6 +976,62 @@ impl FolderManager {
     Ok(())
   }
 
+  /// Adds the view to the end of the quick-access list of the current workspace. Unlike
+  /// [FolderManager::toggle_favorites], the list is stored in the user's local database instead of
+  /// the folder, so it isn't shared with the other members of the workspace.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn add_favorite_view(&self, view_id: &str) -> FlowyResult<()> {
+    // Make sure the view exists and isn't in the trash
+    let _ = self.get_view_pb(view_id).await?;
+    let workspace_id = self.user.workspace_id()?;
+    self.user.add_favorite_view_id(&workspace_id, view_id)?;
+    self
+      .send_update_favorite_views_notification(&workspace_id)
+      .await;
+    Ok(())
+  }
+
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn remove_favorite_view(&self, view_id: &str) -> FlowyResult<()> {
+    let workspace_id = self.user.workspace_id()?;
+    if self.user.remove_favorite_view_id(&workspace_id, view_id)? {
+      self
+        .send_update_favorite_views_notification(&workspace_id)
+        .await;
+    }
+    Ok(())
+  }
+
+  /// Returns the views in the quick-access list of the current workspace in the order they were
+  /// added. The views that were deleted or moved to the trash are skipped.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn list_favorite_views(&self) -> FlowyResult<Vec<ViewPB>> {
+    let workspace_id = self.user.workspace_id()?;
+    self.get_favorite_views(&workspace_id).await
+  }
+
+  async fn get_favorite_views(&self, workspace_id: &str) -> FlowyResult<Vec<ViewPB>> {
+    let view_ids = self.user.favorite_view_ids(workspace_id)?;
+    let mut views = Vec::with_capacity(view_ids.len());
+    for view_id in view_ids {
+      if let Ok(view) = self.get_view_pb(&view_id).await {
+        views.push(view);
+      }
+    }
+    Ok(views)
+  }
+
+  async fn send_update_favorite_views_notification(&self, workspace_id: &str) {
+    match self.get_favorite_views(workspace_id).await {
+      Ok(views) => {
+        send_notification(workspace_id, FolderNotification::DidUpdateQuickAccessViews)
+          .payload(RepeatedViewPB { items: views })
+          .send();
+      },
+      Err(err) => error!("Failed to get the quick-access views: {:?}", err),
+    }
+  }
+
   // Used by toggle_favorites to send notification to frontend, after the favorite status of view has been changed.It sends two distinct notifications: one to correctly update the concerned view's is_favorite status, and another to update the list of favorites that is to be displayed.
   async fn send_toggle_favorite_notification(&self, view_id: &str) {
     if let Ok(view) = self.get_view_pb(view_id).await {
@@ -909,14 +1089,83 @@ impl FolderManager {
       .send();
   }
 
-  #[tracing::instrument(level = "trace", skip(self))]
-  pub(crate) async fn restore_trash(&self, trash_id: &str) {
-    self.with_folder(
-      || (),
+  /// Returns the trash of the given workspace. Only the trash of the current workspace can be
+  /// read, because the folders of the other workspaces aren't opened.
+  #[tracing::instrument(level = "trace", skip(self), err)]
+  pub async fn list_trash(&self, workspace_id: &str) -> FlowyResult<Vec<TrashPB>> {
+    if self.user.workspace_id()? != workspace_id {
+      return Err(FlowyError::workspace_data_not_match());
+    }
+    let trash = self.get_my_trash_info().await;
+    Ok(trash.into_iter().map(TrashPB::from).collect())
+  }
+
+  /// Puts the view back to its parent. If the parent is in the trash too, or it was deleted, the
+  /// view is put back to the root of the workspace instead, otherwise it would stay hidden.
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn restore_from_trash(&self, trash_id: &str) -> FlowyResult<()> {
+    let workspace_id = self.user.workspace_id()?;
+    let old_parent_id = self.with_folder(
+      || Err(folder_not_init_error()),
       |folder| {
+        let is_in_trash = folder
+          .get_my_trash_info()
+          .iter()
+          .any(|trash| trash.id == trash_id);
+        if !is_in_trash {
+          return Err(
+            FlowyError::record_not_found()
+              .with_context(format!("View: {} isn't in the trash", trash_id)),
+          );
+        }
+
+        let parent_id = folder
+          .views
+          .get_view(trash_id)
+          .map(|view| view.parent_view_id.clone());
         folder.delete_trash_view_ids(vec![trash_id.to_string()]);
+
+        let parent_id = parent_id.filter(|parent_id| {
+          *parent_id != workspace_id
+            && (folder.views.get_view(parent_id).is_none()
+              || self.get_all_trash_ids(folder).contains(parent_id))
+        });
+        if let Some(parent_id) = parent_id.as_ref() {
+          warn!(
+            "The parent: {} of the view: {} is unavailable, put it back to the workspace root",
+            parent_id, trash_id
+          );
+          folder.move_nested_view(trash_id, &workspace_id, None);
+        }
+        Ok(parent_id)
       },
-    );
+    )?;
+
+    if let Some(old_parent_id) = old_parent_id {
+      notify_parent_view_did_change(
+        &workspace_id,
+        self.mutex_folder.clone(),
+        vec![workspace_id.clone(), old_parent_id],
+      );
+    }
+    Ok(())
+  }
+
+  /// Deletes the view in the trash permanently. See [Self::delete_trash].
+  #[tracing::instrument(level = "debug", skip(self), err)]
+  pub async fn permanently_delete(&self, trash_id: &str) -> FlowyResult<()> {
+    let is_in_trash = self
+      .get_my_trash_info()
+      .await
+      .iter()
+      .any(|trash| trash.id == trash_id);
+    if !is_in_trash {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("View: {} isn't in the trash", trash_id)),
+      );
+    }
+    self.delete_trash(trash_id).await
   }
 
   /// Delete all the trash permanently.
@@ -971,6 +1220,7 @@ impl FolderManager {
           &view_id,
           &import_data.name,
           import_data.import_type,
+          import_data.csv_options,
           data,
         )
         .await?;
@@ -38,6 +38,9 @@ pub enum FolderNotification {
 
   /// Trigger when the ROOT views (the first level) in section are updated
   DidUpdateSectionViews = 39,
+
+  /// Trigger when the quick-access list of the workspace is updated
+  DidUpdateQuickAccessViews = 40,
 }
 
 impl std::convert::From<FolderNotification> for i32 {
@@ -65,6 +68,7 @@ impl std::convert::From<i32> for FolderNotification {
       37 => FolderNotification::DidUnfavoriteView,
       38 => FolderNotification::DidUpdateRecentViews,
       39 => FolderNotification::DidUpdateSectionViews,
+      40 => FolderNotification::DidUpdateQuickAccessViews,
       _ => FolderNotification::Unknown,
     }
   }
@@ -8,6 +8,16 @@ pub enum ImportType {
   CSV = 3,
 }
 
+/// The options of the imports of a database from a CSV, i.e. the [ImportType::CSV],
+/// [ImportType::HistoryDatabase] and [ImportType::RawDatabase] imports.
+#[derive(Clone, Debug, Default)]
+pub struct ImportCSVOptions {
+  /// Orders the rows by their creation time instead of the order of the CSV.
+  pub sort_by_created_at: bool,
+  /// The delimiter of the values. It's detected from the header line if it's None.
+  pub delimiter: Option<char>,
+}
+
 #[derive(Clone, Debug)]
 pub struct ImportParams {
   pub parent_view_id: String,
@@ -16,4 +26,5 @@ pub struct ImportParams {
   pub file_path: Option<String>,
   pub view_layout: ViewLayout,
   pub import_type: ImportType,
+  pub csv_options: ImportCSVOptions,
 }
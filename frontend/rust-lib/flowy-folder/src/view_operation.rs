@@ -14,7 +14,7 @@ use lib_infra::future::FutureResult;
 use lib_infra::util::timestamp;
 
 use crate::entities::{CreateViewParams, ViewLayoutPB};
-use crate::share::ImportType;
+use crate::share::{ImportCSVOptions, ImportType};
 
 pub type ViewData = Bytes;
 
@@ -81,13 +81,14 @@ pub trait FolderOperationHandler {
     layout: ViewLayout,
   ) -> FutureResult<(), FlowyError>;
 
-  /// Create a view by importing data
+  /// Create a view by importing data. The `csv_options` only apply to the imports of a database.
   fn import_from_bytes(
     &self,
     uid: i64,
     view_id: &str,
     name: &str,
     import_type: ImportType,
+    csv_options: ImportCSVOptions,
     bytes: Vec<u8>,
   ) -> FutureResult<(), FlowyError>;
 
@@ -17,6 +17,13 @@ pub trait IndexManager: Send + Sync {
   fn set_index_content_receiver(&self, rx: IndexContentReceiver, workspace_id: String);
   fn add_index(&self, data: IndexableData) -> Result<(), FlowyError>;
   fn update_index(&self, data: IndexableData) -> Result<(), FlowyError>;
+  /// Updates the indices of many objects at once. The implementations should write them in one
+  /// commit.
+  fn update_indices(&self, data: Vec<IndexableData>) -> Result<(), FlowyError> {
+    data
+      .into_iter()
+      .try_for_each(|data| self.update_index(data))
+  }
   fn remove_indices(&self, ids: Vec<String>) -> Result<(), FlowyError>;
   fn is_indexed(&self) -> bool;
 
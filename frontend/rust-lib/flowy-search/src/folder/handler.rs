@@ -41,4 +41,8 @@ impl SearchHandler for FolderSearchHandler {
   fn index_count(&self) -> u64 {
     self.index_manager.num_docs()
   }
+
+  fn flush_pending_index(&self) -> FlowyResult<()> {
+    self.index_manager.flush_pending_index()
+  }
 }
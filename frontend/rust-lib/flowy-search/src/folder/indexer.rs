@@ -13,6 +13,7 @@ use crate::{
     FolderSchema, FOLDER_ICON_FIELD_NAME, FOLDER_ICON_TY_FIELD_NAME, FOLDER_ID_FIELD_NAME,
     FOLDER_TITLE_FIELD_NAME, FOLDER_WORKSPACE_ID_FIELD_NAME,
   },
+  services::index_batch::{IndexBatch, INDEX_BATCH_WINDOW},
 };
 use collab::core::collab::{IndexContent, IndexContentReceiver};
 use collab_folder::{View, ViewIcon, ViewIndexContent, ViewLayout};
@@ -34,6 +35,8 @@ pub struct FolderIndexManagerImpl {
   index: Option<Index>,
   index_reader: Option<IndexReader>,
   index_writer: Option<Arc<Mutex<IndexWriter>>>,
+  /// The changes of the views are written in batches, see [IndexBatch].
+  pending_index: Arc<IndexBatch>,
 }
 
 const FOLDER_INDEX_DIR: &str = "folder_index";
@@ -120,9 +123,25 @@ impl FolderIndexManagerImpl {
       index: Some(index),
       index_reader: Some(index_reader.unwrap()),
       index_writer: Some(Arc::new(Mutex::new(index_writer.unwrap()))),
+      pending_index: Default::default(),
     }
   }
 
+  /// Writes the changes of the views that are waiting to be batched.
+  pub fn flush_pending_index(&self) -> FlowyResult<()> {
+    self.pending_index.flush(self)
+  }
+
+  fn schedule_flush(&self) {
+    let indexer = self.clone();
+    af_spawn(async move {
+      tokio::time::sleep(INDEX_BATCH_WINDOW).await;
+      if let Err(e) = indexer.flush_pending_index() {
+        tracing::error!("FolderIndexManager failed to flush pending index: {:?}", e);
+      }
+    });
+  }
+
   fn index_all(&self, indexes: Vec<IndexableData>) -> Result<(), FlowyError> {
     if self.is_indexed() || indexes.is_empty() {
       return Ok(());
@@ -171,6 +190,7 @@ impl FolderIndexManagerImpl {
       index: None,
       index_reader: None,
       index_writer: None,
+      pending_index: Default::default(),
     }
   }
 
@@ -289,33 +309,27 @@ impl IndexManager for FolderIndexManagerImpl {
     af_spawn(async move {
       while let Ok(msg) = rx.recv().await {
         match msg {
-          IndexContent::Create(value) => match serde_json::from_value::<ViewIndexContent>(value) {
-            Ok(view) => {
-              let _ = indexer.add_index(IndexableData {
-                id: view.id,
-                data: view.name,
-                icon: view.icon,
-                layout: view.layout,
-                workspace_id: wid.clone(),
-              });
-            },
-            Err(err) => tracing::error!("FolderIndexManager error deserialize: {:?}", err),
-          },
-          IndexContent::Update(value) => match serde_json::from_value::<ViewIndexContent>(value) {
-            Ok(view) => {
-              let _ = indexer.update_index(IndexableData {
-                id: view.id,
-                data: view.name,
-                icon: view.icon,
-                layout: view.layout,
-                workspace_id: wid.clone(),
-              });
-            },
-            Err(err) => tracing::error!("FolderIndexManager error deserialize: {:?}", err),
+          // A created view is indexed the same way as an updated one
+          IndexContent::Create(value) | IndexContent::Update(value) => {
+            match serde_json::from_value::<ViewIndexContent>(value) {
+              Ok(view) => {
+                let should_schedule = indexer.pending_index.update(IndexableData {
+                  id: view.id,
+                  data: view.name,
+                  icon: view.icon,
+                  layout: view.layout,
+                  workspace_id: wid.clone(),
+                });
+                if should_schedule {
+                  indexer.schedule_flush();
+                }
+              },
+              Err(err) => tracing::error!("FolderIndexManager error deserialize: {:?}", err),
+            }
           },
           IndexContent::Delete(ids) => {
-            if let Err(e) = indexer.remove_indices(ids) {
-              tracing::error!("FolderIndexManager error deserialize: {:?}", e);
+            if indexer.pending_index.remove(ids) {
+              indexer.schedule_flush();
             }
           },
         }
@@ -324,6 +338,10 @@ impl IndexManager for FolderIndexManagerImpl {
   }
 
   fn update_index(&self, data: IndexableData) -> Result<(), FlowyError> {
+    self.update_indices(vec![data])
+  }
+
+  fn update_indices(&self, data: Vec<IndexableData>) -> Result<(), FlowyError> {
     let mut index_writer = self.get_index_writer()?;
 
     let folder_schema = self.get_folder_schema()?;
@@ -336,22 +354,25 @@ impl IndexManager for FolderIndexManagerImpl {
       .schema
       .get_field(FOLDER_WORKSPACE_ID_FIELD_NAME)?;
 
-    let delete_term = Term::from_field_text(id_field, &data.id.clone());
+    for data in data {
+      let delete_term = Term::from_field_text(id_field, &data.id.clone());
 
-    // Remove old index
-    index_writer.delete_term(delete_term);
+      // Remove old index
+      index_writer.delete_term(delete_term);
 
-    let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
+      let (icon, icon_ty) = self.extract_icon(data.icon, data.layout);
 
-    // Add new index
-    let _ = index_writer.add_document(doc![
-      id_field => data.id.clone(),
-      title_field => data.data,
-      icon_field => icon.unwrap_or_default(),
-      icon_ty_field => icon_ty,
-      workspace_id_field => data.workspace_id.clone(),
-    ]);
+      // Add new index
+      let _ = index_writer.add_document(doc![
+        id_field => data.id.clone(),
+        title_field => data.data,
+        icon_field => icon.unwrap_or_default(),
+        icon_ty_field => icon_ty,
+        workspace_id_field => data.workspace_id.clone(),
+      ]);
+    }
 
+    // All the indices are committed at once
     index_writer.commit()?;
 
     Ok(())
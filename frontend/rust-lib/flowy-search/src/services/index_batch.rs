@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use flowy_error::FlowyResult;
+use flowy_search_pub::entities::{IndexManager, IndexableData};
+
+/// How long the index requests are collected before they're written.
+pub const INDEX_BATCH_WINDOW: Duration = Duration::from_millis(300);
+
+enum PendingIndex {
+  Update(IndexableData),
+  Remove,
+}
+
+/// Collects the index requests that haven't been written yet. A request replaces the pending
+/// request of the same object, so a burst of requests, e.g. a bulk import or rename, is written
+/// in one pass with only the final state of each object.
+#[derive(Default)]
+pub struct IndexBatch {
+  pending: Mutex<HashMap<String, PendingIndex>>,
+  is_scheduled: AtomicBool,
+  /// Held while a pass is written, so the passes are written in the order they're taken.
+  flush_lock: Mutex<()>,
+}
+
+impl IndexBatch {
+  /// Returns true if a flush should be scheduled, i.e. none is scheduled since the last flush.
+  pub fn update(&self, data: IndexableData) -> bool {
+    self.push(data.id.clone(), PendingIndex::Update(data))
+  }
+
+  /// Returns true if a flush should be scheduled, i.e. none is scheduled since the last flush.
+  pub fn remove(&self, ids: Vec<String>) -> bool {
+    let mut is_first = false;
+    for id in ids {
+      is_first |= self.push(id, PendingIndex::Remove);
+    }
+    is_first
+  }
+
+  pub fn len(&self) -> usize {
+    self
+      .pending
+      .lock()
+      .map(|pending| pending.len())
+      .unwrap_or(0)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Writes the pending requests with at most one [IndexManager::remove_indices] call and one
+  /// [IndexManager::update_indices] call.
+  pub fn flush(&self, indexer: &dyn IndexManager) -> FlowyResult<()> {
+    let _flush_guard = self.flush_lock.lock();
+    // The requests that arrive from now on schedule the next flush
+    self.is_scheduled.store(false, Ordering::SeqCst);
+    let pending = match self.pending.lock() {
+      Ok(mut pending) => std::mem::take(&mut *pending),
+      Err(_) => return Ok(()),
+    };
+
+    let mut removed_ids = vec![];
+    let mut updated_data = vec![];
+    for (id, index) in pending {
+      match index {
+        PendingIndex::Update(data) => updated_data.push(data),
+        PendingIndex::Remove => removed_ids.push(id),
+      }
+    }
+    if !removed_ids.is_empty() {
+      indexer.remove_indices(removed_ids)?;
+    }
+    if !updated_data.is_empty() {
+      indexer.update_indices(updated_data)?;
+    }
+    Ok(())
+  }
+
+  fn push(&self, id: String, index: PendingIndex) -> bool {
+    if let Ok(mut pending) = self.pending.lock() {
+      pending.insert(id, index);
+    }
+    !self.is_scheduled.swap(true, Ordering::SeqCst)
+  }
+}
@@ -22,6 +22,10 @@ pub trait SearchHandler: Send + Sync + 'static {
   ) -> FlowyResult<Vec<SearchResultPB>>;
   /// returns the number of indexed objects
   fn index_count(&self) -> u64;
+  /// writes the index requests that are waiting to be batched
+  fn flush_pending_index(&self) -> FlowyResult<()> {
+    Ok(())
+  }
 }
 
 /// The [SearchManager] is used to inject multiple [SearchHandler]'s
@@ -51,6 +55,16 @@ impl SearchManager {
     self.handlers.get(&search_type)
   }
 
+  /// The index requests are batched, so the indices are updated shortly after the objects. Writes
+  /// the pending requests of all the handlers now, e.g. before an export or an assertion in a
+  /// test.
+  pub fn flush_pending_index(&self) -> FlowyResult<()> {
+    for handler in self.handlers.values() {
+      handler.flush_pending_index()?;
+    }
+    Ok(())
+  }
+
   pub fn perform_search(
     &self,
     query: String,
@@ -1,2 +1,3 @@
+pub mod index_batch;
 pub mod manager;
 pub mod notifier;
@@ -0,0 +1,120 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use collab::core::collab::IndexContentReceiver;
+use collab_folder::ViewLayout;
+use flowy_error::FlowyError;
+use flowy_search::services::index_batch::IndexBatch;
+use flowy_search_pub::entities::{IndexManager, IndexableData};
+
+/// Keeps the indexed names in memory and counts the calls that write the index.
+#[derive(Default)]
+struct MockIndexManager {
+  names: Mutex<HashMap<String, String>>,
+  num_of_calls: Mutex<usize>,
+}
+
+impl MockIndexManager {
+  fn num_of_calls(&self) -> usize {
+    *self.num_of_calls.lock().unwrap()
+  }
+
+  fn name(&self, id: &str) -> Option<String> {
+    self.names.lock().unwrap().get(id).cloned()
+  }
+}
+
+impl IndexManager for MockIndexManager {
+  fn set_index_content_receiver(&self, _rx: IndexContentReceiver, _workspace_id: String) {}
+
+  fn add_index(&self, data: IndexableData) -> Result<(), FlowyError> {
+    self.update_index(data)
+  }
+
+  fn update_index(&self, data: IndexableData) -> Result<(), FlowyError> {
+    *self.num_of_calls.lock().unwrap() += 1;
+    self.names.lock().unwrap().insert(data.id, data.data);
+    Ok(())
+  }
+
+  fn update_indices(&self, data: Vec<IndexableData>) -> Result<(), FlowyError> {
+    *self.num_of_calls.lock().unwrap() += 1;
+    let mut names = self.names.lock().unwrap();
+    for data in data {
+      names.insert(data.id, data.data);
+    }
+    Ok(())
+  }
+
+  fn remove_indices(&self, ids: Vec<String>) -> Result<(), FlowyError> {
+    *self.num_of_calls.lock().unwrap() += 1;
+    let mut names = self.names.lock().unwrap();
+    for id in ids {
+      names.remove(&id);
+    }
+    Ok(())
+  }
+
+  fn is_indexed(&self) -> bool {
+    !self.names.lock().unwrap().is_empty()
+  }
+
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+}
+
+fn indexable_data(id: &str, name: &str) -> IndexableData {
+  IndexableData {
+    id: id.to_string(),
+    data: name.to_string(),
+    icon: None,
+    layout: ViewLayout::Document,
+    workspace_id: "w1".to_string(),
+  }
+}
+
+#[test]
+fn index_batch_coalesces_burst_test() {
+  // Without batching, every request writes the index
+  let indexer = MockIndexManager::default();
+  for i in 0..100 {
+    indexer
+      .update_index(indexable_data(
+        &format!("v{}", i % 3),
+        &format!("name {}", i),
+      ))
+      .unwrap();
+  }
+  indexer.remove_indices(vec!["v2".to_string()]).unwrap();
+  assert_eq!(indexer.num_of_calls(), 101);
+
+  let indexer = MockIndexManager::default();
+  let batch = IndexBatch::default();
+  // Only the first request asks for a flush to be scheduled
+  assert!(batch.update(indexable_data("v0", "name 0")));
+  for i in 1..100 {
+    assert!(!batch.update(indexable_data(
+      &format!("v{}", i % 3),
+      &format!("name {}", i)
+    )));
+  }
+  assert!(!batch.remove(vec!["v2".to_string()]));
+  assert_eq!(batch.len(), 3);
+  assert_eq!(indexer.num_of_calls(), 0);
+
+  batch.flush(&indexer).unwrap();
+  assert!(batch.is_empty());
+  assert_eq!(indexer.num_of_calls(), 2);
+  // The final state of each object is indexed
+  assert_eq!(indexer.name("v0").unwrap(), "name 99");
+  assert_eq!(indexer.name("v1").unwrap(), "name 97");
+  assert!(indexer.name("v2").is_none());
+
+  // The requests after a flush schedule the next one
+  assert!(batch.update(indexable_data("v2", "name 100")));
+  batch.flush(&indexer).unwrap();
+  assert_eq!(indexer.num_of_calls(), 3);
+  assert_eq!(indexer.name("v2").unwrap(), "name 100");
+}
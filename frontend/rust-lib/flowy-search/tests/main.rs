@@ -1,3 +1,4 @@
 // mod search;
 
+mod index_batch_test;
 mod tantivy_test;
@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
@@ -7,12 +8,45 @@ use flowy_error::{ErrorCode, FlowyError};
 pub const APPFLOWY_CLOUD_BASE_URL: &str = "APPFLOWY_CLOUD_ENV_APPFLOWY_CLOUD_BASE_URL";
 pub const APPFLOWY_CLOUD_WS_BASE_URL: &str = "APPFLOWY_CLOUD_ENV_APPFLOWY_CLOUD_WS_BASE_URL";
 pub const APPFLOWY_CLOUD_GOTRUE_URL: &str = "APPFLOWY_CLOUD_ENV_APPFLOWY_CLOUD_GOTRUE_URL";
+pub const APPFLOWY_CLOUD_TOKEN_REFRESH_MARGIN: &str =
+  "APPFLOWY_CLOUD_ENV_APPFLOWY_CLOUD_TOKEN_REFRESH_MARGIN";
+pub const APPFLOWY_CLOUD_TOKEN_REFRESH_INTERVAL: &str =
+  "APPFLOWY_CLOUD_ENV_APPFLOWY_CLOUD_TOKEN_REFRESH_INTERVAL";
+pub const APPFLOWY_CLOUD_REQUEST_TIMEOUT: &str =
+  "APPFLOWY_CLOUD_ENV_APPFLOWY_CLOUD_REQUEST_TIMEOUT";
+
+/// The default timeout of the requests to the cloud, in seconds.
+const DEFAULT_REQUEST_TIMEOUT_IN_SECS: u64 = 30;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct AFCloudConfiguration {
   pub base_url: String,
   pub ws_base_url: String,
   pub gotrue_url: String,
+  #[serde(default)]
+  pub token_refresh: TokenRefreshConfiguration,
+  /// The requests to the cloud fail with [ErrorCode::Timeout] if they take longer than this many
+  /// seconds. Zero disables the timeout.
+  #[serde(default = "default_request_timeout_in_secs")]
+  pub request_timeout_in_secs: u64,
+}
+
+/// Controls the background task that refreshes the token before it expires.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TokenRefreshConfiguration {
+  /// The token is refreshed when it expires in less than this many seconds.
+  pub margin_in_secs: u64,
+  /// How often, in seconds, the expiration of the token is checked.
+  pub interval_in_secs: u64,
+}
+
+impl Default for TokenRefreshConfiguration {
+  fn default() -> Self {
+    Self {
+      margin_in_secs: 5 * 60,
+      interval_in_secs: 60,
+    }
+  }
 }
 
 impl Display for AFCloudConfiguration {
@@ -53,10 +87,24 @@ impl AFCloudConfiguration {
       );
     }
 
+    // The token refresh settings are optional, the defaults are used when they are missing
+    let mut token_refresh = TokenRefreshConfiguration::default();
+    if let Some(margin) = read_env_secs(APPFLOWY_CLOUD_TOKEN_REFRESH_MARGIN) {
+      token_refresh.margin_in_secs = margin;
+    }
+    if let Some(interval) = read_env_secs(APPFLOWY_CLOUD_TOKEN_REFRESH_INTERVAL) {
+      token_refresh.interval_in_secs = interval;
+    }
+
+    let request_timeout_in_secs =
+      read_env_secs(APPFLOWY_CLOUD_REQUEST_TIMEOUT).unwrap_or(DEFAULT_REQUEST_TIMEOUT_IN_SECS);
+
     Ok(Self {
       base_url,
       ws_base_url,
       gotrue_url,
+      token_refresh,
+      request_timeout_in_secs,
     })
   }
 
@@ -65,5 +113,30 @@ impl AFCloudConfiguration {
     std::env::set_var(APPFLOWY_CLOUD_BASE_URL, &self.base_url);
     std::env::set_var(APPFLOWY_CLOUD_WS_BASE_URL, &self.ws_base_url);
     std::env::set_var(APPFLOWY_CLOUD_GOTRUE_URL, &self.gotrue_url);
+    std::env::set_var(
+      APPFLOWY_CLOUD_TOKEN_REFRESH_MARGIN,
+      self.token_refresh.margin_in_secs.to_string(),
+    );
+    std::env::set_var(
+      APPFLOWY_CLOUD_TOKEN_REFRESH_INTERVAL,
+      self.token_refresh.interval_in_secs.to_string(),
+    );
+    std::env::set_var(
+      APPFLOWY_CLOUD_REQUEST_TIMEOUT,
+      self.request_timeout_in_secs.to_string(),
+    );
+  }
+
+  /// Returns None if the requests never time out.
+  pub fn request_timeout(&self) -> Option<Duration> {
+    (self.request_timeout_in_secs > 0).then(|| Duration::from_secs(self.request_timeout_in_secs))
   }
 }
+
+fn default_request_timeout_in_secs() -> u64 {
+  DEFAULT_REQUEST_TIMEOUT_IN_SECS
+}
+
+fn read_env_secs(key: &str) -> Option<u64> {
+  std::env::var(key).ok()?.parse::<u64>().ok()
+}
@@ -9,4 +9,9 @@ pub const USER_DEVICE_ID: &str = "device_id";
 pub trait ServerUser: Send + Sync {
   /// different user might return different workspace id.
   fn workspace_id(&self) -> FlowyResult<String>;
+
+  /// The workspace id is only available when a user is logged in.
+  fn is_logged_in(&self) -> bool {
+    self.workspace_id().is_ok()
+  }
 }
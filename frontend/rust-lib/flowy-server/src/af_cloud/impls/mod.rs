@@ -2,6 +2,7 @@ pub(crate) use database::*;
 pub(crate) use document::*;
 pub(crate) use file_storage::*;
 pub(crate) use folder::*;
+pub use user::UserCloudTransport;
 pub(crate) use user::*;
 
 mod database;
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use client_api::entity::workspace_dto::{
@@ -13,10 +15,12 @@ use client_api::entity::{QueryCollab, QueryCollabParams};
 use client_api::{Client, ClientConfiguration};
 use collab_entity::{CollabObject, CollabType};
 use parking_lot::RwLock;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
-use flowy_user_pub::cloud::{UserCloudService, UserCollabParams, UserUpdate, UserUpdateReceiver};
+use flowy_user_pub::cloud::{
+  BatchCreateReport, UserCloudService, UserCollabParams, UserUpdate, UserUpdateReceiver,
+};
 use flowy_user_pub::entities::{
   AFCloudOAuthParams, AuthResponse, Role, UpdateUserProfileParams, UserCredentials, UserProfile,
   UserWorkspace, WorkspaceInvitation, WorkspaceInvitationStatus, WorkspaceMember,
@@ -39,6 +43,7 @@ pub(crate) struct AFCloudUserAuthServiceImpl<T> {
   server: T,
   user_change_recv: RwLock<Option<tokio::sync::mpsc::Receiver<UserUpdate>>>,
   user: Arc<dyn ServerUser>,
+  request_timeout: Option<Duration>,
 }
 
 impl<T> AFCloudUserAuthServiceImpl<T> {
@@ -46,13 +51,48 @@ impl<T> AFCloudUserAuthServiceImpl<T> {
     server: T,
     user_change_recv: tokio::sync::mpsc::Receiver<UserUpdate>,
     user: Arc<dyn ServerUser>,
+    request_timeout: Option<Duration>,
   ) -> Self {
     Self {
       server,
       user_change_recv: RwLock::new(Some(user_change_recv)),
       user,
+      request_timeout,
+    }
+  }
+
+  /// Fails with [ErrorCode::UserNotLogin] if no user is logged in. The requests that need a
+  /// session check it first, so they don't reach the client and fail with a confusing error.
+  fn require_login(&self) -> FlowyResult<()> {
+    if self.user.is_logged_in() {
+      Ok(())
+    } else {
+      Err(FlowyError::user_not_login())
     }
   }
+
+  /// Runs the request, failing it with [ErrorCode::Timeout] if it doesn't finish within the
+  /// request timeout.
+  fn request<R, Fut>(&self, fut: Fut) -> FutureResult<R, FlowyError>
+  where
+    Fut: Future<Output = Result<R, FlowyError>> + Send + Sync + 'static,
+    R: Send + Sync + 'static,
+  {
+    let request_timeout = self.request_timeout;
+    FutureResult::new(with_timeout(request_timeout, fut))
+  }
+}
+
+async fn with_timeout<R, Fut>(request_timeout: Option<Duration>, fut: Fut) -> Result<R, FlowyError>
+where
+  Fut: Future<Output = Result<R, FlowyError>>,
+{
+  match request_timeout {
+    None => fut.await,
+    Some(request_timeout) => tokio::time::timeout(request_timeout, fut)
+      .await
+      .map_err(|_| FlowyError::new(ErrorCode::Timeout, "The request timed out"))?,
+  }
 }
 
 impl<T> UserCloudService for AFCloudUserAuthServiceImpl<T>
@@ -61,7 +101,7 @@ where
 {
   fn sign_up(&self, params: BoxAny) -> FutureResult<AuthResponse, FlowyError> {
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
       let params = oauth_params_from_box_any(params)?;
       let resp = user_sign_up_request(try_get_client?, params).await?;
       Ok(resp)
@@ -71,7 +111,7 @@ where
   // Zack: Not sure if this is needed anymore since sign_up handles both cases
   fn sign_in(&self, params: BoxAny) -> FutureResult<AuthResponse, FlowyError> {
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
       let client = try_get_client?;
       let params = oauth_params_from_box_any(params)?;
       let resp = user_sign_in_with_url(client, params).await?;
@@ -85,10 +125,21 @@ where
     FutureResult::new(async move { Ok(()) })
   }
 
+  fn delete_account(&self) -> FutureResult<(), FlowyError> {
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
+    self.request(async move {
+      require_login?;
+      let transport = try_get_transport?;
+      transport.delete_user().await?;
+      Ok(())
+    })
+  }
+
   fn generate_sign_in_url_with_email(&self, email: &str) -> FutureResult<String, FlowyError> {
     let email = email.to_string();
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
       let client = try_get_client?;
       let admin_client = get_admin_client(&client).await?;
       let action_link = admin_client.generate_sign_in_action_link(&email).await?;
@@ -101,7 +152,7 @@ where
     let password = password.to_string();
     let email = email.to_string();
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
       let client = try_get_client?;
       let admin_client = get_admin_client(&client).await?;
       admin_client
@@ -120,7 +171,7 @@ where
     let password = password.to_string();
     let email = email.to_string();
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
       let client = try_get_client?;
       client.sign_in_password(&email, &password).await?;
       let profile = client.get_profile().await?;
@@ -138,7 +189,7 @@ where
     let email = email.to_owned();
     let redirect_to = redirect_to.to_owned();
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
       let client = try_get_client?;
       client
         .sign_in_with_magic_link(&email, Some(redirect_to))
@@ -147,10 +198,45 @@ where
     })
   }
 
+  fn change_password(
+    &self,
+    email: &str,
+    old_password: &str,
+    new_password: &str,
+  ) -> FutureResult<String, FlowyError> {
+    let require_login = self.require_login();
+    let email = email.to_string();
+    let old_password = old_password.to_string();
+    let new_password = new_password.to_string();
+    let try_get_transport = self.server.try_get_user_transport();
+    self.request(async move {
+      require_login?;
+      let transport = try_get_transport?;
+      // The server can't tell a wrong password from an account that has no password, e.g. one
+      // that signs in with OAuth or a magic link.
+      transport
+        .sign_in_password(&email, &old_password)
+        .await
+        .map_err(|err| match err.code {
+          ErrorCode::UserUnauthorized | ErrorCode::InvalidParams => FlowyError::new(
+            ErrorCode::PasswordNotMatch,
+            "The current password is incorrect, or the account signs in with OAuth or a magic \
+             link and has no password",
+          ),
+          _ => err,
+        })?;
+      transport.update_password(&new_password).await?;
+
+      // Refresh the session with the new password
+      transport.sign_in_password(&email, &new_password).await?;
+      transport.get_token()
+    })
+  }
+
   fn generate_oauth_url_with_provider(&self, provider: &str) -> FutureResult<String, FlowyError> {
     let provider = AuthProvider::from(provider);
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
       let provider = provider.ok_or(anyhow!("invalid provider"))?;
       let url = try_get_client?
         .generate_oauth_url_with_provider(&provider)
@@ -164,8 +250,10 @@ where
     _credential: UserCredentials,
     params: UpdateUserProfileParams,
   ) -> FutureResult<(), FlowyError> {
+    let require_login = self.require_login();
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
+      require_login?;
       let client = try_get_client?;
       client
         .update_user(af_update_from_update_params(params))
@@ -179,13 +267,15 @@ where
     &self,
     _credential: UserCredentials,
   ) -> FutureResult<UserProfile, FlowyError> {
-    let try_get_client = self.server.try_get_client();
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
     let cloned_user = self.user.clone();
-    FutureResult::new(async move {
+    self.request(async move {
+      require_login?;
       let expected_workspace_id = cloned_user.workspace_id()?;
-      let client = try_get_client?;
-      let profile = client.get_profile().await?;
-      let token = client.get_token()?;
+      let transport = try_get_transport?;
+      let profile = transport.get_profile().await?;
+      let token = transport.get_token()?;
       let profile = user_profile_from_af_profile(token, profile)?;
 
       // Discard the response if the user has switched to a new workspace. This avoids updating the
@@ -200,20 +290,24 @@ where
   }
 
   fn open_workspace(&self, workspace_id: &str) -> FutureResult<UserWorkspace, FlowyError> {
-    let try_get_client = self.server.try_get_client();
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
     let workspace_id = workspace_id.to_string();
-    FutureResult::new(async move {
-      let client = try_get_client?;
-      let af_workspace = client.open_workspace(&workspace_id).await?;
+    self.request(async move {
+      require_login?;
+      let transport = try_get_transport?;
+      let af_workspace = transport.open_workspace(&workspace_id).await?;
       Ok(to_user_workspace(af_workspace))
     })
   }
 
   fn get_all_workspace(&self, _uid: i64) -> FutureResult<Vec<UserWorkspace>, FlowyError> {
-    let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
-      let workspaces = try_get_client?.get_workspaces().await?;
-      to_user_workspaces(workspaces.0)
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
+    self.request(async move {
+      require_login?;
+      let workspaces = try_get_transport?.get_workspaces().await?;
+      to_user_workspaces(workspaces)
     })
   }
 
@@ -223,8 +317,10 @@ where
     user_email: String,
     workspace_id: String,
   ) -> FutureResult<(), FlowyError> {
+    let require_login = self.require_login();
     let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    self.request(async move {
+      require_login?;
       // TODO(zack): add_workspace_members will be deprecated after finishing the invite logic. Don't forget to remove the #[allow(deprecated)]
       try_get_client?
         .add_workspace_members(
@@ -245,9 +341,11 @@ where
     workspace_id: String,
     role: Role,
   ) -> FutureResult<(), FlowyError> {
-    let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
-      try_get_client?
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
+    self.request(async move {
+      require_login?;
+      try_get_transport?
         .invite_workspace_members(
           &workspace_id,
           vec![WorkspaceMemberInvitation {
@@ -264,11 +362,13 @@ where
     &self,
     filter: Option<WorkspaceInvitationStatus>,
   ) -> FutureResult<Vec<WorkspaceInvitation>, FlowyError> {
-    let try_get_client = self.server.try_get_client();
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
     let filter = filter.map(to_workspace_invitation_status);
 
-    FutureResult::new(async move {
-      let r = try_get_client?
+    self.request(async move {
+      require_login?;
+      let r = try_get_transport?
         .list_workspace_invitations(filter)
         .await?
         .into_iter()
@@ -279,9 +379,11 @@ where
   }
 
   fn accept_workspace_invitations(&self, invite_id: String) -> FutureResult<(), FlowyError> {
-    let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
-      try_get_client?
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
+    self.request(async move {
+      require_login?;
+      try_get_transport?
         .accept_workspace_invitation(&invite_id)
         .await?;
       Ok(())
@@ -293,9 +395,11 @@ where
     user_email: String,
     workspace_id: String,
   ) -> FutureResult<(), FlowyError> {
-    let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
-      try_get_client?
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
+    self.request(async move {
+      require_login?;
+      try_get_transport?
         .remove_workspace_members(workspace_id, vec![user_email])
         .await?;
       Ok(())
@@ -308,10 +412,12 @@ where
     workspace_id: String,
     role: Role,
   ) -> FutureResult<(), FlowyError> {
-    let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
+    self.request(async move {
+      require_login?;
       let changeset = WorkspaceMemberChangeset::new(user_email).with_role(to_af_role(role));
-      try_get_client?
+      try_get_transport?
         .update_workspace_member(workspace_id, changeset)
         .await?;
       Ok(())
@@ -322,9 +428,11 @@ where
     &self,
     workspace_id: String,
   ) -> FutureResult<Vec<WorkspaceMember>, FlowyError> {
-    let try_get_client = self.server.try_get_client();
-    FutureResult::new(async move {
-      let members = try_get_client?
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
+    self.request(async move {
+      require_login?;
+      let members = try_get_transport?
         .get_workspace_members(&workspace_id)
         .await?
         .into_iter()
@@ -341,11 +449,13 @@ where
     workspace_id: &str,
     object_id: &str,
   ) -> FutureResult<Vec<u8>, FlowyError> {
+    let require_login = self.require_login();
     let workspace_id = workspace_id.to_string();
     let object_id = object_id.to_string();
     let try_get_client = self.server.try_get_client();
     let cloned_user = self.user.clone();
-    FutureResult::new(async move {
+    self.request(async move {
+      require_login?;
       let params = QueryCollabParams {
         workspace_id: workspace_id.clone(),
         inner: QueryCollab {
@@ -376,9 +486,11 @@ where
     collab_object: &CollabObject,
     data: Vec<u8>,
   ) -> FutureResult<(), FlowyError> {
+    let require_login = self.require_login();
     let try_get_client = self.server.try_get_client();
     let collab_object = collab_object.clone();
-    FutureResult::new(async move {
+    self.request(async move {
+      require_login?;
       let client = try_get_client?;
       let params = CreateCollabParams {
         workspace_id: collab_object.workspace_id.clone(),
@@ -395,30 +507,75 @@ where
     &self,
     workspace_id: &str,
     objects: Vec<UserCollabParams>,
-  ) -> FutureResult<(), FlowyError> {
+    all_or_nothing: bool,
+  ) -> FutureResult<BatchCreateReport, FlowyError> {
+    let require_login = self.require_login();
     let workspace_id = workspace_id.to_string();
     let try_get_client = self.server.try_get_client();
+    let request_timeout = self.request_timeout;
+    // The time to upload the objects grows with their size, so the request timeout only applies
+    // to the objects created one by one, not to the whole batch.
     FutureResult::new(async move {
-      let params = objects
+      require_login?;
+      let client = try_get_client?;
+      let mut report = BatchCreateReport::default();
+      let (objects, invalid_objects): (Vec<_>, Vec<_>) = objects
         .into_iter()
-        .map(|object| CollabParams {
+        .partition(|object| Uuid::parse_str(&object.object_id).is_ok());
+      for object in invalid_objects {
+        warn!(
+          "Skip creating collab object with invalid id: {}",
+          object.object_id
+        );
+        report.skipped_object_ids.push(object.object_id);
+      }
+      if objects.is_empty() {
+        return Ok(report);
+      }
+
+      if all_or_nothing {
+        let object_ids = objects
+          .iter()
+          .map(|object| object.object_id.clone())
+          .collect::<Vec<_>>();
+        let params = objects.into_iter().map(to_collab_params).collect();
+        client.create_collab_list(&workspace_id, params).await?;
+        report.created_object_ids = object_ids;
+        return Ok(report);
+      }
+
+      // The batch request takes the objects, so they would have to be copied to retry them after
+      // a rejected batch. Create them one by one instead.
+      for object in objects {
+        let object_id = object.object_id.clone();
+        let params = CreateCollabParams {
+          workspace_id: workspace_id.clone(),
           object_id: object.object_id,
           encoded_collab_v1: object.encoded_collab,
           collab_type: object.collab_type,
+        };
+        match with_timeout(request_timeout, async {
+          client.create_collab(params).await.map_err(FlowyError::from)
         })
-        .collect::<Vec<_>>();
-      try_get_client?
-        .create_collab_list(&workspace_id, params)
         .await
-        .map_err(FlowyError::from)?;
-      Ok(())
+        {
+          Ok(_) => report.created_object_ids.push(object_id),
+          Err(err) => {
+            warn!("Create collab object {} failed: {}", object_id, err);
+            report.rejected_object_ids.push(object_id);
+          },
+        }
+      }
+      Ok(report)
     })
   }
 
   fn create_workspace(&self, workspace_name: &str) -> FutureResult<UserWorkspace, FlowyError> {
+    let require_login = self.require_login();
     let try_get_client = self.server.try_get_client();
     let workspace_name_owned = workspace_name.to_owned();
-    FutureResult::new(async move {
+    self.request(async move {
+      require_login?;
       let client = try_get_client?;
       let new_workspace = client
         .create_workspace(CreateWorkspaceParam {
@@ -430,9 +587,11 @@ where
   }
 
   fn delete_workspace(&self, workspace_id: &str) -> FutureResult<(), FlowyError> {
+    let require_login = self.require_login();
     let try_get_client = self.server.try_get_client();
     let workspace_id_owned = workspace_id.to_owned();
-    FutureResult::new(async move {
+    self.request(async move {
+      require_login?;
       let client = try_get_client?;
       client.delete_workspace(&workspace_id_owned).await?;
       Ok(())
@@ -445,11 +604,13 @@ where
     new_workspace_name: Option<&str>,
     new_workspace_icon: Option<&str>,
   ) -> FutureResult<(), FlowyError> {
+    let require_login = self.require_login();
     let try_get_client = self.server.try_get_client();
     let owned_workspace_id = workspace_id.to_owned();
     let owned_workspace_name = new_workspace_name.map(|s| s.to_owned());
     let owned_workspace_icon = new_workspace_icon.map(|s| s.to_owned());
-    FutureResult::new(async move {
+    self.request(async move {
+      require_login?;
       let workspace_id: Uuid = owned_workspace_id
         .parse()
         .map_err(|_| ErrorCode::InvalidParams)?;
@@ -466,11 +627,13 @@ where
   }
 
   fn leave_workspace(&self, workspace_id: &str) -> FutureResult<(), FlowyError> {
-    let try_get_client = self.server.try_get_client();
+    let require_login = self.require_login();
+    let try_get_transport = self.server.try_get_user_transport();
     let workspace_id = workspace_id.to_string();
-    FutureResult::new(async move {
-      let client = try_get_client?;
-      client.leave_workspace(&workspace_id).await?;
+    self.request(async move {
+      require_login?;
+      let transport = try_get_transport?;
+      transport.leave_workspace(&workspace_id).await?;
       Ok(())
     })
   }
@@ -530,6 +693,14 @@ pub async fn user_sign_in_with_url(
   })
 }
 
+fn to_collab_params(object: UserCollabParams) -> CollabParams {
+  CollabParams {
+    object_id: object.object_id,
+    encoded_collab_v1: object.encoded_collab,
+    collab_type: object.collab_type,
+  }
+}
+
 fn to_user_workspace(af_workspace: AFWorkspace) -> UserWorkspace {
   UserWorkspace {
     id: af_workspace.workspace_id.to_string(),
@@ -537,6 +708,7 @@ fn to_user_workspace(af_workspace: AFWorkspace) -> UserWorkspace {
     created_at: af_workspace.created_at,
     database_indexer_id: af_workspace.database_storage_id.to_string(),
     icon: af_workspace.icon,
+    member_count: 0,
   }
 }
 
@@ -570,3 +742,340 @@ fn oauth_params_from_box_any(any: BoxAny) -> Result<AFCloudOAuthParams, FlowyErr
     sign_in_url: sign_in_url.to_string(),
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::time::Duration;
+
+  use anyhow::{anyhow, Error};
+  use client_api::entity::workspace_dto::{WorkspaceMemberChangeset, WorkspaceMemberInvitation};
+  use client_api::entity::{
+    AFUserProfile, AFWorkspace, AFWorkspaceInvitation, AFWorkspaceInvitationStatus,
+    AFWorkspaceMember,
+  };
+  use parking_lot::Mutex;
+
+  use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+  use flowy_user_pub::cloud::UserCloudService;
+  use flowy_user_pub::entities::Role;
+  use lib_infra::async_trait::async_trait;
+
+  use crate::af_cloud::define::ServerUser;
+  use crate::af_cloud::impls::user::{AFCloudUserAuthServiceImpl, UserCloudTransport};
+  use crate::af_cloud::{AFCloudClient, AFServer};
+
+  /// Records the requests and fails them with `error` if it's set. The requests that return a
+  /// list never finish if `hang` is true.
+  #[derive(Default)]
+  struct MockTransport {
+    error: Option<ErrorCode>,
+    hang: bool,
+    requests: Mutex<Vec<String>>,
+  }
+
+  impl MockTransport {
+    fn request(&self, request: String) -> Result<(), FlowyError> {
+      self.requests.lock().push(request);
+      match &self.error {
+        None => Ok(()),
+        Some(code) => Err(FlowyError::new(code.clone(), "mock error")),
+      }
+    }
+
+    async fn hang_if_needed(&self) {
+      if self.hang {
+        std::future::pending::<()>().await;
+      }
+    }
+  }
+
+  #[async_trait]
+  impl UserCloudTransport for MockTransport {
+    async fn get_profile(&self) -> Result<AFUserProfile, FlowyError> {
+      self.request("get_profile".to_string())?;
+      Err(FlowyError::not_support())
+    }
+
+    fn get_token(&self) -> Result<String, FlowyError> {
+      self.request("get_token".to_string())?;
+      Ok("token".to_string())
+    }
+
+    async fn sign_in_password(&self, email: &str, password: &str) -> Result<(), FlowyError> {
+      self.request(format!("sign_in_password {} {}", email, password))
+    }
+
+    async fn update_password(&self, new_password: &str) -> Result<(), FlowyError> {
+      self.request(format!("update_password {}", new_password))
+    }
+
+    async fn get_workspaces(&self) -> Result<Vec<AFWorkspace>, FlowyError> {
+      self.request("get_workspaces".to_string())?;
+      self.hang_if_needed().await;
+      Ok(vec![])
+    }
+
+    async fn open_workspace(&self, workspace_id: &str) -> Result<AFWorkspace, FlowyError> {
+      self.request(format!("open_workspace {}", workspace_id))?;
+      Err(FlowyError::not_support())
+    }
+
+    async fn get_workspace_members(
+      &self,
+      workspace_id: &str,
+    ) -> Result<Vec<AFWorkspaceMember>, FlowyError> {
+      self.request(format!("get_workspace_members {}", workspace_id))?;
+      self.hang_if_needed().await;
+      Ok(vec![])
+    }
+
+    async fn invite_workspace_members(
+      &self,
+      workspace_id: &str,
+      invitations: Vec<WorkspaceMemberInvitation>,
+    ) -> Result<(), FlowyError> {
+      let emails = invitations
+        .into_iter()
+        .map(|invitation| invitation.email)
+        .collect::<Vec<_>>();
+      self.request(format!(
+        "invite_workspace_members {} {:?}",
+        workspace_id, emails
+      ))
+    }
+
+    async fn list_workspace_invitations(
+      &self,
+      _status: Option<AFWorkspaceInvitationStatus>,
+    ) -> Result<Vec<AFWorkspaceInvitation>, FlowyError> {
+      self.request("list_workspace_invitations".to_string())?;
+      Ok(vec![])
+    }
+
+    async fn accept_workspace_invitation(&self, invite_id: &str) -> Result<(), FlowyError> {
+      self.request(format!("accept_workspace_invitation {}", invite_id))
+    }
+
+    async fn update_workspace_member(
+      &self,
+      workspace_id: String,
+      changeset: WorkspaceMemberChangeset,
+    ) -> Result<(), FlowyError> {
+      self.request(format!(
+        "update_workspace_member {} {}",
+        workspace_id, changeset.email
+      ))
+    }
+
+    async fn remove_workspace_members(
+      &self,
+      workspace_id: String,
+      emails: Vec<String>,
+    ) -> Result<(), FlowyError> {
+      self.request(format!(
+        "remove_workspace_members {} {:?}",
+        workspace_id, emails
+      ))
+    }
+
+    async fn leave_workspace(&self, workspace_id: &str) -> Result<(), FlowyError> {
+      self.request(format!("leave_workspace {}", workspace_id))
+    }
+
+    async fn delete_user(&self) -> Result<(), FlowyError> {
+      self.request("delete_user".to_string())
+    }
+  }
+
+  struct MockServer {
+    transport: Arc<MockTransport>,
+  }
+
+  impl AFServer for MockServer {
+    fn get_client(&self) -> Option<Arc<AFCloudClient>> {
+      None
+    }
+
+    fn try_get_client(&self) -> Result<Arc<AFCloudClient>, Error> {
+      Err(anyhow!("The mock server has no client"))
+    }
+
+    fn try_get_user_transport(&self) -> Result<Arc<dyn UserCloudTransport>, Error> {
+      Ok(self.transport.clone())
+    }
+  }
+
+  struct MockUser {
+    is_logged_in: bool,
+  }
+
+  impl ServerUser for MockUser {
+    fn workspace_id(&self) -> FlowyResult<String> {
+      if self.is_logged_in {
+        Ok("w1".to_string())
+      } else {
+        Err(FlowyError::record_not_found())
+      }
+    }
+  }
+
+  fn user_service(transport: Arc<MockTransport>) -> AFCloudUserAuthServiceImpl<MockServer> {
+    user_service_with_timeout(transport, Some(Duration::from_secs(30)))
+  }
+
+  fn user_service_with_timeout(
+    transport: Arc<MockTransport>,
+    request_timeout: Option<Duration>,
+  ) -> AFCloudUserAuthServiceImpl<MockServer> {
+    new_user_service(transport, request_timeout, true)
+  }
+
+  fn new_user_service(
+    transport: Arc<MockTransport>,
+    request_timeout: Option<Duration>,
+    is_logged_in: bool,
+  ) -> AFCloudUserAuthServiceImpl<MockServer> {
+    let (_tx, rx) = tokio::sync::mpsc::channel(1);
+    AFCloudUserAuthServiceImpl::new(
+      MockServer { transport },
+      rx,
+      Arc::new(MockUser { is_logged_in }),
+      request_timeout,
+    )
+  }
+
+  #[tokio::test]
+  async fn workspace_member_requests_use_transport_test() {
+    let transport = Arc::new(MockTransport::default());
+    let service = user_service(transport.clone());
+    let email = "nathan@appflowy.io".to_string();
+    service
+      .invite_workspace_member(email.clone(), "w1".to_string(), Role::Member)
+      .await
+      .unwrap();
+    service
+      .update_workspace_member(email.clone(), "w1".to_string(), Role::Guest)
+      .await
+      .unwrap();
+    service
+      .remove_workspace_member(email, "w1".to_string())
+      .await
+      .unwrap();
+    service.leave_workspace("w1").await.unwrap();
+    service.delete_account().await.unwrap();
+    let members = service
+      .get_workspace_members("w1".to_string())
+      .await
+      .unwrap();
+
+    assert!(members.is_empty());
+    assert_eq!(
+      *transport.requests.lock(),
+      vec![
+        "invite_workspace_members w1 [\"nathan@appflowy.io\"]",
+        "update_workspace_member w1 nathan@appflowy.io",
+        "remove_workspace_members w1 [\"nathan@appflowy.io\"]",
+        "leave_workspace w1",
+        "delete_user",
+        "get_workspace_members w1",
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn transport_error_is_returned_test() {
+    let transport = Arc::new(MockTransport {
+      error: Some(ErrorCode::UserUnauthorized),
+      ..Default::default()
+    });
+    let service = user_service(transport);
+    let err = service.get_all_workspace(1).await.unwrap_err();
+    assert_eq!(err.code, ErrorCode::UserUnauthorized);
+
+    let err = service
+      .accept_workspace_invitations("invite_1".to_string())
+      .await
+      .unwrap_err();
+    assert_eq!(err.code, ErrorCode::UserUnauthorized);
+  }
+
+  #[tokio::test]
+  async fn request_timeout_test() {
+    let transport = Arc::new(MockTransport {
+      hang: true,
+      ..Default::default()
+    });
+    let service = user_service_with_timeout(transport.clone(), Some(Duration::from_millis(10)));
+    let err = service.get_all_workspace(1).await.unwrap_err();
+    assert_eq!(err.code, ErrorCode::Timeout);
+
+    let err = service
+      .get_workspace_members("w1".to_string())
+      .await
+      .unwrap_err();
+    assert_eq!(err.code, ErrorCode::Timeout);
+
+    // The requests that finish in time are not affected
+    service.leave_workspace("w1").await.unwrap();
+    assert_eq!(transport.requests.lock().len(), 3);
+  }
+
+  #[tokio::test]
+  async fn change_password_test() {
+    let transport = Arc::new(MockTransport::default());
+    let service = user_service(transport.clone());
+    let token = service
+      .change_password("nathan@appflowy.io", "Old1234!", "New1234!")
+      .await
+      .unwrap();
+
+    assert_eq!(token, "token");
+    assert_eq!(
+      *transport.requests.lock(),
+      vec![
+        "sign_in_password nathan@appflowy.io Old1234!",
+        "update_password New1234!",
+        "sign_in_password nathan@appflowy.io New1234!",
+        "get_token",
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn change_password_with_wrong_password_test() {
+    let transport = Arc::new(MockTransport {
+      error: Some(ErrorCode::UserUnauthorized),
+      ..Default::default()
+    });
+    let service = user_service(transport.clone());
+    let err = service
+      .change_password("nathan@appflowy.io", "Wrong1234!", "New1234!")
+      .await
+      .unwrap_err();
+
+    assert_eq!(err.code, ErrorCode::PasswordNotMatch);
+    // The password is not updated
+    assert_eq!(transport.requests.lock().len(), 1);
+  }
+
+  #[tokio::test]
+  async fn request_without_login_test() {
+    let transport = Arc::new(MockTransport::default());
+    let service = new_user_service(transport.clone(), None, false);
+    let err = service
+      .get_workspace_members("w1".to_string())
+      .await
+      .unwrap_err();
+    assert_eq!(err.code, ErrorCode::UserNotLogin);
+
+    let err = service
+      .change_password("nathan@appflowy.io", "Old1234!", "New1234!")
+      .await
+      .unwrap_err();
+    assert_eq!(err.code, ErrorCode::UserNotLogin);
+
+    // The requests don't reach the server
+    assert!(transport.requests.lock().is_empty());
+  }
+}
@@ -1,5 +1,7 @@
 pub use cloud_service_impl::*;
+pub use transport::*;
 
 mod cloud_service_impl;
 mod dto;
+mod transport;
 mod util;
@@ -0,0 +1,151 @@
+use client_api::entity::auth_dto::UpdateUserParams;
+use client_api::entity::workspace_dto::{WorkspaceMemberChangeset, WorkspaceMemberInvitation};
+use client_api::entity::{
+  AFUserProfile, AFWorkspace, AFWorkspaceInvitation, AFWorkspaceInvitationStatus, AFWorkspaceMember,
+};
+use client_api::Client;
+
+use flowy_error::FlowyError;
+use lib_infra::async_trait::async_trait;
+
+/// The requests that the user service sends to the AppFlowy Cloud. [Client] is the real
+/// implementation, and tests can replace it with a mock to run the user service without a server.
+#[async_trait]
+pub trait UserCloudTransport: Send + Sync {
+  async fn get_profile(&self) -> Result<AFUserProfile, FlowyError>;
+
+  async fn sign_in_password(&self, email: &str, password: &str) -> Result<(), FlowyError>;
+
+  async fn update_password(&self, new_password: &str) -> Result<(), FlowyError>;
+
+  fn get_token(&self) -> Result<String, FlowyError>;
+
+  async fn get_workspaces(&self) -> Result<Vec<AFWorkspace>, FlowyError>;
+
+  async fn open_workspace(&self, workspace_id: &str) -> Result<AFWorkspace, FlowyError>;
+
+  async fn get_workspace_members(
+    &self,
+    workspace_id: &str,
+  ) -> Result<Vec<AFWorkspaceMember>, FlowyError>;
+
+  async fn invite_workspace_members(
+    &self,
+    workspace_id: &str,
+    invitations: Vec<WorkspaceMemberInvitation>,
+  ) -> Result<(), FlowyError>;
+
+  async fn list_workspace_invitations(
+    &self,
+    status: Option<AFWorkspaceInvitationStatus>,
+  ) -> Result<Vec<AFWorkspaceInvitation>, FlowyError>;
+
+  async fn accept_workspace_invitation(&self, invite_id: &str) -> Result<(), FlowyError>;
+
+  async fn update_workspace_member(
+    &self,
+    workspace_id: String,
+    changeset: WorkspaceMemberChangeset,
+  ) -> Result<(), FlowyError>;
+
+  async fn remove_workspace_members(
+    &self,
+    workspace_id: String,
+    emails: Vec<String>,
+  ) -> Result<(), FlowyError>;
+
+  async fn leave_workspace(&self, workspace_id: &str) -> Result<(), FlowyError>;
+
+  async fn delete_user(&self) -> Result<(), FlowyError>;
+}
+
+#[async_trait]
+impl UserCloudTransport for Client {
+  async fn get_profile(&self) -> Result<AFUserProfile, FlowyError> {
+    Ok(Client::get_profile(self).await?)
+  }
+
+  fn get_token(&self) -> Result<String, FlowyError> {
+    Ok(Client::get_token(self)?)
+  }
+
+  async fn sign_in_password(&self, email: &str, password: &str) -> Result<(), FlowyError> {
+    Client::sign_in_password(self, email, password).await?;
+    Ok(())
+  }
+
+  async fn update_password(&self, new_password: &str) -> Result<(), FlowyError> {
+    let params = UpdateUserParams {
+      name: None,
+      email: None,
+      password: Some(new_password.to_string()),
+      metadata: None,
+    };
+    Client::update_user(self, params).await?;
+    Ok(())
+  }
+
+  async fn get_workspaces(&self) -> Result<Vec<AFWorkspace>, FlowyError> {
+    Ok(Client::get_workspaces(self).await?.0)
+  }
+
+  async fn open_workspace(&self, workspace_id: &str) -> Result<AFWorkspace, FlowyError> {
+    Ok(Client::open_workspace(self, workspace_id).await?)
+  }
+
+  async fn get_workspace_members(
+    &self,
+    workspace_id: &str,
+  ) -> Result<Vec<AFWorkspaceMember>, FlowyError> {
+    Ok(Client::get_workspace_members(self, workspace_id).await?)
+  }
+
+  async fn invite_workspace_members(
+    &self,
+    workspace_id: &str,
+    invitations: Vec<WorkspaceMemberInvitation>,
+  ) -> Result<(), FlowyError> {
+    Client::invite_workspace_members(self, workspace_id, invitations).await?;
+    Ok(())
+  }
+
+  async fn list_workspace_invitations(
+    &self,
+    status: Option<AFWorkspaceInvitationStatus>,
+  ) -> Result<Vec<AFWorkspaceInvitation>, FlowyError> {
+    Ok(Client::list_workspace_invitations(self, status).await?)
+  }
+
+  async fn accept_workspace_invitation(&self, invite_id: &str) -> Result<(), FlowyError> {
+    Client::accept_workspace_invitation(self, invite_id).await?;
+    Ok(())
+  }
+
+  async fn update_workspace_member(
+    &self,
+    workspace_id: String,
+    changeset: WorkspaceMemberChangeset,
+  ) -> Result<(), FlowyError> {
+    Client::update_workspace_member(self, workspace_id, changeset).await?;
+    Ok(())
+  }
+
+  async fn remove_workspace_members(
+    &self,
+    workspace_id: String,
+    emails: Vec<String>,
+  ) -> Result<(), FlowyError> {
+    Client::remove_workspace_members(self, workspace_id, emails).await?;
+    Ok(())
+  }
+
+  async fn leave_workspace(&self, workspace_id: &str) -> Result<(), FlowyError> {
+    Client::leave_workspace(self, workspace_id).await?;
+    Ok(())
+  }
+
+  async fn delete_user(&self) -> Result<(), FlowyError> {
+    Client::delete_user(self).await?;
+    Ok(())
+  }
+}
@@ -3,3 +3,4 @@ pub use server::*;
 pub mod define;
 pub mod impls;
 mod server;
+mod token_refresh;
@@ -13,13 +13,14 @@ use client_api::{Client, ClientConfiguration};
 use flowy_storage::ObjectStorageService;
 use rand::Rng;
 use tokio::select;
-use tokio::sync::{watch, Mutex};
+use tokio::sync::{broadcast, watch, Mutex};
 use tokio_stream::wrappers::WatchStream;
 use tokio_util::sync::CancellationToken;
 use tracing::{error, event, info, warn};
 use uuid::Uuid;
 
 use crate::af_cloud::define::ServerUser;
+use crate::af_cloud::token_refresh::TokenRefresher;
 use flowy_database_pub::cloud::DatabaseCloudService;
 use flowy_document_pub::cloud::DocumentCloudService;
 use flowy_error::{ErrorCode, FlowyError};
@@ -31,7 +32,7 @@ use lib_dispatch::prelude::af_spawn;
 
 use crate::af_cloud::impls::{
   AFCloudDatabaseCloudServiceImpl, AFCloudDocumentCloudServiceImpl, AFCloudFileStorageServiceImpl,
-  AFCloudFolderCloudServiceImpl, AFCloudUserAuthServiceImpl,
+  AFCloudFolderCloudServiceImpl, AFCloudUserAuthServiceImpl, UserCloudTransport,
 };
 use crate::AppFlowyServer;
 
@@ -46,6 +47,7 @@ pub struct AppFlowyCloudServer {
   pub device_id: String,
   ws_client: Arc<WSClient>,
   user: Arc<dyn ServerUser>,
+  token_refresher: Arc<TokenRefresher>,
 }
 
 impl AppFlowyCloudServer {
@@ -84,12 +86,18 @@ impl AppFlowyCloudServer {
     let ws_client = Arc::new(ws_client);
     let api_client = Arc::new(api_client);
     let ws_connect_cancellation_token = Arc::new(Mutex::new(CancellationToken::new()));
+    let token_refresher = Arc::new(TokenRefresher::new(
+      &api_client,
+      config.token_refresh.clone(),
+    ));
+    token_refresher.spawn(enable_sync.clone());
 
     spawn_ws_conn(
       token_state_rx,
       &ws_client,
       ws_connect_cancellation_token,
       &api_client,
+      &token_refresher,
       &enable_sync,
     );
     Self {
@@ -100,6 +108,7 @@ impl AppFlowyCloudServer {
       device_id,
       ws_client,
       user,
+      token_refresher,
     }
   }
 
@@ -147,6 +156,10 @@ impl AppFlowyServer for AppFlowyCloudServer {
     Some(WatchStream::new(watch_rx))
   }
 
+  fn subscribe_token_refresh_failed(&self) -> Option<broadcast::Receiver<String>> {
+    Some(self.token_refresher.subscribe_refresh_failed())
+  }
+
   fn set_enable_sync(&self, uid: i64, enable: bool) {
     info!("{} cloud sync: {}", uid, enable);
     self.enable_sync.store(enable, Ordering::SeqCst);
@@ -156,6 +169,10 @@ impl AppFlowyServer for AppFlowyCloudServer {
     self.network_reachable.store(reachable, Ordering::SeqCst);
   }
 
+  fn base_url(&self) -> Option<String> {
+    Some(self.config.base_url.clone())
+  }
+
   fn user_service(&self) -> Arc<dyn UserCloudService> {
     let server = AFServerImpl {
       client: self.get_client(),
@@ -180,6 +197,7 @@ impl AppFlowyServer for AppFlowyCloudServer {
       server,
       rx,
       self.user.clone(),
+      self.config.request_timeout(),
     ))
   }
 
@@ -256,10 +274,12 @@ fn spawn_ws_conn(
   ws_client: &Arc<WSClient>,
   conn_cancellation_token: Arc<Mutex<CancellationToken>>,
   api_client: &Arc<Client>,
+  token_refresher: &Arc<TokenRefresher>,
   enable_sync: &Arc<AtomicBool>,
 ) {
   let weak_ws_client = Arc::downgrade(ws_client);
   let weak_api_client = Arc::downgrade(api_client);
+  let weak_token_refresher = Arc::downgrade(token_refresher);
   let enable_sync = enable_sync.clone();
   let cloned_conn_cancellation_token = conn_cancellation_token.clone();
 
@@ -276,8 +296,8 @@ fn spawn_ws_conn(
             }
           },
           ConnectState::Unauthorized => {
-            if let Some(api_client) = weak_api_client.upgrade() {
-              if let Err(err) = api_client
+            if let Some(token_refresher) = weak_token_refresher.upgrade() {
+              if let Err(err) = token_refresher
                 .refresh_token("websocket connect unauthorized")
                 .await
               {
@@ -354,6 +374,12 @@ async fn attempt_reconnect(
 pub trait AFServer: Send + Sync + 'static {
   fn get_client(&self) -> Option<Arc<AFCloudClient>>;
   fn try_get_client(&self) -> Result<Arc<AFCloudClient>, Error>;
+
+  /// Returns the transport used by the user service. It's the client unless it's replaced in tests.
+  fn try_get_user_transport(&self) -> Result<Arc<dyn UserCloudTransport>, Error> {
+    let client = self.try_get_client()?;
+    Ok(client)
+  }
 }
 
 #[derive(Clone)]
@@ -0,0 +1,146 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::sync::{broadcast, Mutex};
+use tracing::{error, info, trace};
+
+use flowy_error::FlowyError;
+use flowy_server_pub::af_cloud_config::TokenRefreshConfiguration;
+use lib_dispatch::prelude::af_spawn;
+
+use crate::af_cloud::AFCloudClient;
+
+/// Refreshes the token of the client before it expires, instead of waiting for a request to fail
+/// because of the expired token.
+///
+/// Only one refresh runs at a time. The callers that ask for a refresh while another one is
+/// running wait for it and reuse its result.
+///
+/// The background refresh is retried every [TokenRefreshConfiguration::interval_in_secs] while it
+/// fails, but the failure is only sent once until a refresh succeeds again.
+pub(crate) struct TokenRefresher {
+  client: Weak<AFCloudClient>,
+  config: TokenRefreshConfiguration,
+  refresh_lock: Mutex<()>,
+  refresh_failed_tx: broadcast::Sender<String>,
+  is_refresh_failed: AtomicBool,
+}
+
+impl TokenRefresher {
+  pub fn new(client: &Arc<AFCloudClient>, config: TokenRefreshConfiguration) -> Self {
+    let (refresh_failed_tx, _) = broadcast::channel(1);
+    Self {
+      client: Arc::downgrade(client),
+      config,
+      refresh_lock: Mutex::new(()),
+      refresh_failed_tx,
+      is_refresh_failed: AtomicBool::new(false),
+    }
+  }
+
+  /// Receives the reason of the background refreshes that failed.
+  pub fn subscribe_refresh_failed(&self) -> broadcast::Receiver<String> {
+    self.refresh_failed_tx.subscribe()
+  }
+
+  /// Refreshes the token. Does nothing if the token was refreshed by another caller while waiting
+  /// for the running refresh.
+  pub async fn refresh_token(&self, reason: &str) -> Result<(), FlowyError> {
+    let client = self
+      .client
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().with_context("The client is dropped"))?;
+    let expires_at = token_expires_at(&client);
+    let _guard = self.refresh_lock.lock().await;
+    if token_expires_at(&client) != expires_at {
+      trace!("The token was refreshed while waiting for the lock");
+      return Ok(());
+    }
+
+    client.refresh_token(reason).await?;
+    Ok(())
+  }
+
+  /// Checks the expiration of the token every [TokenRefreshConfiguration::interval_in_secs] and
+  /// refreshes it when it's about to expire. The task stops when the refresher is dropped.
+  pub fn spawn(self: &Arc<Self>, enable_sync: Arc<AtomicBool>) {
+    let weak_refresher = Arc::downgrade(self);
+    let interval = Duration::from_secs(self.config.interval_in_secs.max(1));
+    af_spawn(async move {
+      let mut interval = tokio::time::interval(interval);
+      loop {
+        interval.tick().await;
+        let refresher = match weak_refresher.upgrade() {
+          Some(refresher) => refresher,
+          None => break,
+        };
+        if !enable_sync.load(Ordering::SeqCst) {
+          continue;
+        }
+        let expires_at = match refresher
+          .client
+          .upgrade()
+          .and_then(|c| token_expires_at(&c))
+        {
+          Some(expires_at) => expires_at,
+          // Not signed in yet
+          None => continue,
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        if !is_about_to_expire(expires_at, refresher.config.margin_in_secs, now) {
+          continue;
+        }
+
+        info!(
+          "The token expires at {}, refresh it in the background",
+          expires_at
+        );
+        match refresher.refresh_token("token is about to expire").await {
+          Ok(_) => refresher.is_refresh_failed.store(false, Ordering::SeqCst),
+          Err(err) => {
+            error!("Failed to refresh the token in the background: {}", err);
+            if !refresher.is_refresh_failed.swap(true, Ordering::SeqCst) {
+              let _ = refresher.refresh_failed_tx.send(err.msg);
+            }
+          },
+        }
+      }
+    });
+  }
+}
+
+#[derive(Deserialize)]
+struct TokenExpiration {
+  expires_at: i64,
+}
+
+/// Returns the expiration of the client's token as a unix timestamp in seconds, or None if the
+/// client has no token.
+fn token_expires_at(client: &AFCloudClient) -> Option<i64> {
+  let token = client.get_token().ok()?;
+  serde_json::from_str::<TokenExpiration>(&token)
+    .ok()
+    .map(|token| token.expires_at)
+}
+
+fn is_about_to_expire(expires_at: i64, margin_in_secs: u64, now: i64) -> bool {
+  expires_at.saturating_sub(margin_in_secs as i64) <= now
+}
+
+#[cfg(test)]
+mod tests {
+  use super::is_about_to_expire;
+
+  #[test]
+  fn token_is_about_to_expire_test() {
+    let now = 1_700_000_000;
+    assert!(!is_about_to_expire(now + 3600, 300, now));
+    assert!(is_about_to_expire(now + 300, 300, now));
+    assert!(is_about_to_expire(now + 60, 300, now));
+    assert!(is_about_to_expire(now - 60, 300, now));
+    assert!(!is_about_to_expire(now + 60, 0, now));
+  }
+}
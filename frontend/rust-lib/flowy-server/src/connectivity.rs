@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use tracing::debug;
+
+/// Whether the server can be reached, as seen by the last connectivity probe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectivityState {
+  Online,
+  Offline,
+}
+
+impl ConnectivityState {
+  pub fn is_online(&self) -> bool {
+    matches!(self, ConnectivityState::Online)
+  }
+}
+
+/// Checks whether the server can be reached. The probes share one client, so they reuse its
+/// connections instead of opening new ones each time.
+pub struct ConnectivityProbe {
+  client: Client,
+  timeout: Duration,
+}
+
+impl ConnectivityProbe {
+  pub fn new(timeout: Duration) -> Self {
+    Self {
+      client: Client::new(),
+      timeout,
+    }
+  }
+
+  /// Sends a GET request to `endpoint`, a path relative to `base_url`. Any response that isn't a
+  /// server error counts as online, so a server that doesn't have the endpoint is still
+  /// reachable. A timeout, a connection error or a server error, e.g. the 502 of a proxy in front
+  /// of a server that's down, counts as offline.
+  pub async fn probe(&self, base_url: &str, endpoint: &str) -> ConnectivityState {
+    let url = format!(
+      "{}/{}",
+      base_url.trim_end_matches('/'),
+      endpoint.trim_start_matches('/')
+    );
+    match self.client.get(&url).timeout(self.timeout).send().await {
+      Ok(response) if !response.status().is_server_error() => ConnectivityState::Online,
+      Ok(response) => {
+        debug!("Connectivity probe {} failed: {}", url, response.status());
+        ConnectivityState::Offline
+      },
+      Err(err) => {
+        debug!("Connectivity probe {} failed: {}", url, err);
+        ConnectivityState::Offline
+      },
+    }
+  }
+}
@@ -1,6 +1,7 @@
 pub use server::*;
 
 pub mod af_cloud;
+pub mod connectivity;
 pub mod local_server;
 mod response;
 mod server;
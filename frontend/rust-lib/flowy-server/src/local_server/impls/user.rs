@@ -6,7 +6,7 @@ use parking_lot::Mutex;
 use uuid::Uuid;
 
 use flowy_error::FlowyError;
-use flowy_user_pub::cloud::{UserCloudService, UserCollabParams};
+use flowy_user_pub::cloud::{BatchCreateReport, UserCloudService, UserCollabParams};
 use flowy_user_pub::entities::*;
 use flowy_user_pub::DEFAULT_USER_NAME;
 use lib_infra::box_any::BoxAny;
@@ -82,6 +82,11 @@ impl UserCloudService for LocalServerUserAuthServiceImpl {
     FutureResult::new(async { Ok(()) })
   }
 
+  fn delete_account(&self) -> FutureResult<(), FlowyError> {
+    // The data of the local user is only stored on the device
+    FutureResult::new(async { Ok(()) })
+  }
+
   fn generate_sign_in_url_with_email(&self, _email: &str) -> FutureResult<String, FlowyError> {
     FutureResult::new(async {
       Err(
@@ -158,6 +163,14 @@ impl UserCloudService for LocalServerUserAuthServiceImpl {
     FutureResult::new(async { Ok(vec![]) })
   }
 
+  fn get_workspace_feature_flags(
+    &self,
+    _workspace_id: &str,
+  ) -> FutureResult<FeatureFlags, FlowyError> {
+    // The local workspace has no plan, so it only has the features that every plan has
+    FutureResult::new(async { Ok(FeatureFlags::conservative()) })
+  }
+
   fn get_user_awareness_doc_state(
     &self,
     _uid: i64,
@@ -184,7 +197,8 @@ impl UserCloudService for LocalServerUserAuthServiceImpl {
     &self,
     _workspace_id: &str,
     _objects: Vec<UserCollabParams>,
-  ) -> FutureResult<(), FlowyError> {
+    _all_or_nothing: bool,
+  ) -> FutureResult<BatchCreateReport, FlowyError> {
     FutureResult::new(async {
       Err(
         FlowyError::local_version_not_support()
@@ -233,5 +247,6 @@ fn make_user_workspace() -> UserWorkspace {
     created_at: Default::default(),
     database_indexer_id: uuid::Uuid::new_v4().to_string(),
     icon: "".to_string(),
+    member_count: 0,
   }
 }
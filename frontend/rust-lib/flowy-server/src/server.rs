@@ -7,6 +7,7 @@ use std::sync::Arc;
 use anyhow::Error;
 use client_api::collab_sync::ServerCollabMessage;
 use parking_lot::RwLock;
+use tokio::sync::broadcast;
 use tokio_stream::wrappers::WatchStream;
 #[cfg(feature = "enable_supabase")]
 use {collab_entity::CollabObject, collab_plugins::cloud_storage::RemoteCollabStorage};
@@ -46,6 +47,10 @@ pub trait AppFlowyServer: Send + Sync + 'static {
   fn subscribe_token_state(&self) -> Option<WatchStream<UserTokenState>> {
     None
   }
+
+  fn subscribe_token_refresh_failed(&self) -> Option<broadcast::Receiver<String>> {
+    None
+  }
   /// Enables or disables server sync.
   ///
   /// # Arguments
@@ -59,6 +64,12 @@ pub trait AppFlowyServer: Send + Sync + 'static {
   /// * `reachable`: A boolean indicating whether the network is reachable.
   fn set_network_reachable(&self, _reachable: bool) {}
 
+  /// The url that [crate::connectivity::probe_connectivity] checks. None for the servers that
+  /// don't need the network, e.g. the local server, which are never probed.
+  fn base_url(&self) -> Option<String> {
+    None
+  }
+
   /// Provides access to cloud-based user management functionalities. This includes operations
   /// such as user registration, authentication, profile management, and handling of user workspaces.
   /// The interface also offers methods for managing collaborative objects, subscribing to user updates,
@@ -368,7 +368,8 @@ where
     &self,
     _workspace_id: &str,
     _objects: Vec<UserCollabParams>,
-  ) -> FutureResult<(), FlowyError> {
+    _all_or_nothing: bool,
+  ) -> FutureResult<BatchCreateReport, FlowyError> {
     FutureResult::new(async {
       Err(
         FlowyError::local_version_not_support()
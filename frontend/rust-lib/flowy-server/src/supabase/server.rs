@@ -110,6 +110,10 @@ impl SupabaseServer {
 }
 
 impl AppFlowyServer for SupabaseServer {
+  fn base_url(&self) -> Option<String> {
+    Some(self.config.url.clone())
+  }
+
   fn set_enable_sync(&self, uid: i64, enable: bool) {
     tracing::info!("{} supabase sync: {}", uid, enable);
 
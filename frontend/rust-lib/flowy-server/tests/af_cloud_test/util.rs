@@ -42,6 +42,11 @@ impl ServerUser for FakeServerUserImpl {
   fn workspace_id(&self) -> FlowyResult<String> {
     todo!()
   }
+
+  // The tests sign in with the client directly
+  fn is_logged_in(&self) -> bool {
+    true
+  }
 }
 
 pub async fn generate_sign_in_url(user_email: &str, config: &AFCloudConfiguration) -> String {
@@ -0,0 +1,12 @@
+-- Your SQL goes here
+ALTER TABLE user_workspace_table ADD COLUMN order_index BIGINT;
+
+-- Backfill the order of the existing workspaces using their creation time
+UPDATE user_workspace_table
+SET order_index = (
+  SELECT COUNT(*)
+  FROM user_workspace_table AS other
+  WHERE other.uid = user_workspace_table.uid
+    AND (other.created_at < user_workspace_table.created_at
+      OR (other.created_at = user_workspace_table.created_at AND other.id < user_workspace_table.id))
+);
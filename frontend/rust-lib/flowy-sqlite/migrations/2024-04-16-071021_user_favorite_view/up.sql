@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE user_favorite_view_table (
+  workspace_id TEXT NOT NULL,
+  view_id TEXT NOT NULL,
+  order_index BIGINT NOT NULL,
+  PRIMARY KEY (workspace_id, view_id)
+);
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TABLE user_workspace_table ADD COLUMN member_count BIGINT NOT NULL DEFAULT 0;
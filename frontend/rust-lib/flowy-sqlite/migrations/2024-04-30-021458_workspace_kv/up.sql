@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE workspace_kv_table (
+  workspace_id TEXT NOT NULL,
+  key TEXT NOT NULL,
+  value TEXT NOT NULL,
+  PRIMARY KEY (workspace_id, key)
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE user_workspace_table DROP COLUMN auth_type;
+ALTER TABLE user_workspace_table DROP COLUMN service_url;
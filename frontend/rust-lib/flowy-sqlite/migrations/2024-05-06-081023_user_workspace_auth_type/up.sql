@@ -0,0 +1,3 @@
+-- Your SQL goes here
+ALTER TABLE user_workspace_table ADD COLUMN auth_type INTEGER;
+ALTER TABLE user_workspace_table ADD COLUMN service_url TEXT;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+CREATE TABLE user_data_migration_failure_table (
+  migration_name TEXT NOT NULL PRIMARY KEY,
+  error TEXT NOT NULL,
+  attempts INTEGER NOT NULL DEFAULT 0,
+  failed_at BIGINT NOT NULL DEFAULT 0
+);
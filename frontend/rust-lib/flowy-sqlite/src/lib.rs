@@ -11,7 +11,9 @@ pub use diesel::*;
 pub use diesel_derives::*;
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
 
-pub use crate::sqlite_impl::{ConnectionPool, DBConnection, Database, PoolConfig};
+pub use crate::sqlite_impl::{
+  ConnectionPool, DBConnection, Database, PoolConfig, PoolUtilization, DEFAULT_POOL_SIZE,
+};
 
 pub mod kv;
 mod sqlite_impl;
@@ -33,11 +35,19 @@ pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("../flowy-sqlite/mi
 pub const DB_NAME: &str = "flowy-database.db";
 
 pub fn init<P: AsRef<Path>>(storage_path: P) -> Result<Database, io::Error> {
+  init_with_pool_config(storage_path, PoolConfig::default())
+}
+
+/// Opens the database with the `pool_config`. The pool config isn't persisted, so it can be
+/// changed by reopening the database.
+pub fn init_with_pool_config<P: AsRef<Path>>(
+  storage_path: P,
+  pool_config: PoolConfig,
+) -> Result<Database, io::Error> {
   let storage_path = storage_path.as_ref().to_str().unwrap();
   if !Path::new(storage_path).exists() {
     std::fs::create_dir_all(storage_path)?;
   }
-  let pool_config = PoolConfig::default();
   let database = Database::new(storage_path, DB_NAME, pool_config).map_err(as_io_error)?;
   let mut conn = database.get_connection().map_err(as_io_error)?;
   (*conn)
@@ -20,6 +20,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_data_migration_failure_table (migration_name) {
+        migration_name -> Text,
+        error -> Text,
+        attempts -> Integer,
+        failed_at -> BigInt,
+    }
+}
+
+diesel::table! {
+    user_favorite_view_table (workspace_id, view_id) {
+        workspace_id -> Text,
+        view_id -> Text,
+        order_index -> BigInt,
+    }
+}
+
 diesel::table! {
     user_table (id) {
         id -> Text,
@@ -44,12 +61,27 @@ diesel::table! {
         created_at -> BigInt,
         database_storage_id -> Text,
         icon -> Text,
+        order_index -> Nullable<BigInt>,
+        member_count -> BigInt,
+        auth_type -> Nullable<Integer>,
+        service_url -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    workspace_kv_table (workspace_id, key) {
+        workspace_id -> Text,
+        key -> Text,
+        value -> Text,
     }
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
   collab_snapshot,
+  user_data_migration_failure_table,
   user_data_migration_records,
+  user_favorite_view_table,
   user_table,
   user_workspace_table,
+  workspace_kv_table,
 );
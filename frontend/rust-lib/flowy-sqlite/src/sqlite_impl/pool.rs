@@ -44,6 +44,30 @@ impl ConnectionPool {
       .build_unchecked(manager);
     Ok(ConnectionPool { inner: pool })
   }
+
+  /// Returns how many connections of the pool are in use. Used for diagnostics.
+  pub fn utilization(&self) -> PoolUtilization {
+    let state = self.inner.state();
+    PoolUtilization {
+      max_size: self.inner.max_size(),
+      connections: state.connections,
+      idle_connections: state.idle_connections,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PoolUtilization {
+  pub max_size: u32,
+  /// The number of the opened connections, including the idle ones.
+  pub connections: u32,
+  pub idle_connections: u32,
+}
+
+impl PoolUtilization {
+  pub fn in_use(&self) -> u32 {
+    self.connections - self.idle_connections
+  }
 }
 
 #[allow(dead_code)]
@@ -56,11 +80,14 @@ pub struct PoolConfig {
   idle_timeout: Duration,
 }
 
+/// The default max number of the connections in a pool.
+pub const DEFAULT_POOL_SIZE: u32 = 10;
+
 impl Default for PoolConfig {
   fn default() -> Self {
     Self {
       min_idle: 1,
-      max_size: 10,
+      max_size: DEFAULT_POOL_SIZE,
       connection_timeout: Duration::from_secs(10),
       idle_timeout: Duration::from_secs(5 * 60),
     }
@@ -74,9 +101,10 @@ impl PoolConfig {
     self
   }
 
-  #[allow(dead_code)]
+  /// Sets the max number of the connections, at least one. The min idle is capped by it.
   pub fn max_size(mut self, max_size: u32) -> Self {
-    self.max_size = max_size;
+    self.max_size = max_size.max(1);
+    self.min_idle = self.min_idle.min(self.max_size);
     self
   }
 }
@@ -153,3 +181,33 @@ impl CustomizeConnection<SqliteConnection, crate::sqlite_impl::Error> for Databa
     Ok(())
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::sqlite_impl::{Database, PoolConfig};
+
+  #[test]
+  fn pool_utilization_test() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().to_str().unwrap();
+    let database = Database::new(path, "test.db", PoolConfig::default().max_size(2)).unwrap();
+    let pool = database.get_pool();
+    assert_eq!(pool.utilization().max_size, 2);
+
+    let conn_1 = pool.get().unwrap();
+    let conn_2 = pool.get().unwrap();
+    assert_eq!(pool.utilization().in_use(), 2);
+
+    drop(conn_1);
+    drop(conn_2);
+    assert_eq!(pool.utilization().in_use(), 0);
+  }
+
+  #[test]
+  fn pool_size_is_at_least_one_test() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().to_str().unwrap();
+    let database = Database::new(path, "test.db", PoolConfig::default().max_size(0)).unwrap();
+    assert_eq!(database.get_pool().utilization().max_size, 1);
+  }
+}
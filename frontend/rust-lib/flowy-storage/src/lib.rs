@@ -26,6 +26,31 @@ pub struct ObjectValue {
   pub raw: Bytes,
   pub mime: Mime,
 }
+
+/// The state of an upload. [UploadState::Finished], [UploadState::Failed] and
+/// [UploadState::Cancelled] are terminal, no more progress is reported after them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadState {
+  Uploading,
+  Finished,
+  Failed,
+  Cancelled,
+}
+
+impl UploadState {
+  pub fn is_terminal(&self) -> bool {
+    !matches!(self, UploadState::Uploading)
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct UploadProgress {
+  pub file_id: String,
+  pub bytes_uploaded: u64,
+  pub total_bytes: u64,
+  pub state: UploadState,
+}
+
 conditional_send_sync_trait! {
   "Provides a service for object storage. The trait includes methods for CRUD operations on storage objects.";
   ObjectStorageService {
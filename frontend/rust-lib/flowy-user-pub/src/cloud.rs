@@ -9,12 +9,14 @@ use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use tokio_stream::wrappers::WatchStream;
 use uuid::Uuid;
 
 use crate::entities::{
-  AuthResponse, Authenticator, Role, UpdateUserProfileParams, UserCredentials, UserProfile,
-  UserTokenState, UserWorkspace, WorkspaceInvitation, WorkspaceInvitationStatus, WorkspaceMember,
+  AuthResponse, Authenticator, FeatureFlags, MemberChangeEvent, Role, UpdateUserProfileParams,
+  UserCredentials, UserProfile, UserTokenState, UserWorkspace, WorkspaceInvitation,
+  WorkspaceInvitationStatus, WorkspaceMember,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +76,15 @@ conditional_send_sync_trait! {
   /// The stream allows the caller to watch for changes in the token state.
   fn subscribe_token_state(&self) -> Option<WatchStream<UserTokenState>>;
 
+  /// Subscribes to the failures of the background refreshes of the authentication token. It's
+  /// separate from [Self::subscribe_token_state], so a failure never replaces a refreshed token
+  /// that isn't saved yet.
+  ///
+  /// # Returns
+  /// An `Option` containing a receiver of the reasons of the failures if available, or `None`
+  /// otherwise.
+  fn subscribe_token_refresh_failed(&self) -> Option<broadcast::Receiver<String>>;
+
   /// Sets the synchronization state for a user.
   ///
   /// # Arguments
@@ -130,6 +141,11 @@ pub trait UserCloudService: Send + Sync + 'static {
   /// Sign out an account
   fn sign_out(&self, token: Option<String>) -> FutureResult<(), FlowyError>;
 
+  /// Deletes the account of the current user and all of its data on the server
+  fn delete_account(&self) -> FutureResult<(), FlowyError> {
+    FutureResult::new(async { Err(FlowyError::not_support()) })
+  }
+
   /// Generate a sign in url for the user with the given email
   /// Currently, only use the admin client for testing
   fn generate_sign_in_url_with_email(&self, email: &str) -> FutureResult<String, FlowyError>;
@@ -145,6 +161,22 @@ pub trait UserCloudService: Send + Sync + 'static {
   fn sign_in_with_magic_link(&self, email: &str, redirect_to: &str)
     -> FutureResult<(), FlowyError>;
 
+  /// Changes the password of the account after signing in again with `old_password`. Returns
+  /// the token of the new session.
+  fn change_password(
+    &self,
+    email: &str,
+    old_password: &str,
+    new_password: &str,
+  ) -> FutureResult<String, FlowyError> {
+    FutureResult::new(async {
+      Err(FlowyError::new(
+        ErrorCode::PasswordAuthNotSupported,
+        "Only the AppFlowy Cloud accounts have a password",
+      ))
+    })
+  }
+
   /// When the user opens the OAuth URL, it redirects to the corresponding provider's OAuth web page.
   /// After the user is authenticated, the browser will open a deep link to the AppFlowy app (iOS, macOS, etc.),
   /// which will call [Client::sign_in_with_url]generate_sign_in_url_with_email to sign in.
@@ -249,6 +281,12 @@ pub trait UserCloudService: Send + Sync + 'static {
     None
   }
 
+  /// Returns the members joining or leaving the workspace pushed by the server, or None if the
+  /// server can't push them. The members are polled instead if it returns None.
+  fn subscribe_member_changes(&self, _workspace_id: &str) -> Option<MemberChangeReceiver> {
+    None
+  }
+
   fn reset_workspace(&self, collab_object: CollabObject) -> FutureResult<(), FlowyError>;
 
   fn create_collab_object(
@@ -257,19 +295,34 @@ pub trait UserCloudService: Send + Sync + 'static {
     data: Vec<u8>,
   ) -> FutureResult<(), FlowyError>;
 
+  /// Creates the collab objects. Objects with an invalid object id are skipped instead of failing
+  /// the whole batch, see [BatchCreateReport].
+  ///
+  /// If [all_or_nothing] is true, the objects are created in one request that fails as a whole.
+  /// Otherwise, they are created one by one and the rejected ones are reported.
   fn batch_create_collab_object(
     &self,
     workspace_id: &str,
     objects: Vec<UserCollabParams>,
-  ) -> FutureResult<(), FlowyError>;
+    all_or_nothing: bool,
+  ) -> FutureResult<BatchCreateReport, FlowyError>;
 
   fn leave_workspace(&self, workspace_id: &str) -> FutureResult<(), FlowyError> {
     FutureResult::new(async { Ok(()) })
   }
+
+  /// Returns the features of the workspace derived from its subscription plan and settings.
+  fn get_workspace_feature_flags(
+    &self,
+    _workspace_id: &str,
+  ) -> FutureResult<FeatureFlags, FlowyError> {
+    FutureResult::new(async { Err(FlowyError::not_support()) })
+  }
 }
 
 pub type UserUpdateReceiver = tokio::sync::mpsc::Receiver<UserUpdate>;
 pub type UserUpdateSender = tokio::sync::mpsc::Sender<UserUpdate>;
+pub type MemberChangeReceiver = tokio::sync::broadcast::Receiver<MemberChangeEvent>;
 #[derive(Debug, Clone)]
 pub struct UserUpdate {
   pub uid: i64,
@@ -292,3 +345,20 @@ pub struct UserCollabParams {
   pub encoded_collab: Vec<u8>,
   pub collab_type: CollabType,
 }
+
+/// The result of [UserCloudService::batch_create_collab_object].
+#[derive(Debug, Default, Clone)]
+pub struct BatchCreateReport {
+  pub created_object_ids: Vec<String>,
+  /// The objects that were not sent because their object id is invalid
+  pub skipped_object_ids: Vec<String>,
+  /// The objects that the server refused to create
+  pub rejected_object_ids: Vec<String>,
+}
+
+impl BatchCreateReport {
+  /// Returns true if all the objects were created
+  pub fn is_complete(&self) -> bool {
+    self.skipped_object_ids.is_empty() && self.rejected_object_ids.is_empty()
+  }
+}
@@ -142,6 +142,9 @@ pub struct UserWorkspace {
   pub database_indexer_id: String,
   #[serde(default)]
   pub icon: String,
+  /// The number of members in the workspace. It's zero if the members haven't been fetched yet.
+  #[serde(default)]
+  pub member_count: i64,
 }
 
 impl UserWorkspace {
@@ -152,6 +155,7 @@ impl UserWorkspace {
       created_at: Utc::now(),
       database_indexer_id: Uuid::new_v4().to_string(),
       icon: "".to_string(),
+      member_count: 0,
     }
   }
 }
@@ -391,12 +395,54 @@ pub enum Role {
   Guest,
 }
 
+#[derive(Clone, Debug)]
 pub struct WorkspaceMember {
   pub email: String,
   pub role: Role,
   pub name: String,
 }
 
+/// A member joining or leaving a workspace.
+#[derive(Clone, Debug)]
+pub enum MemberChangeEvent {
+  Added {
+    workspace_id: String,
+    member: WorkspaceMember,
+  },
+  Removed {
+    workspace_id: String,
+    member: WorkspaceMember,
+  },
+}
+
+/// The features that are available in a workspace. They are derived from the subscription plan
+/// and the settings of the workspace.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeatureFlags {
+  pub guest_sharing: bool,
+  pub ai: bool,
+  /// The maximum number of members of the workspace. None if it's unlimited.
+  pub member_limit: Option<i64>,
+}
+
+impl FeatureFlags {
+  /// The flags used when the actual ones can't be fetched, e.g. when offline. Only the features
+  /// that every plan has are available.
+  pub fn conservative() -> Self {
+    Self {
+      guest_sharing: false,
+      ai: false,
+      member_limit: Some(1),
+    }
+  }
+}
+
+impl Default for FeatureFlags {
+  fn default() -> Self {
+    Self::conservative()
+  }
+}
+
 /// represent the user awareness object id for the workspace.
 pub fn user_awareness_object_id(user_uuid: &Uuid, workspace_id: &str) -> Uuid {
   Uuid::new_v5(
@@ -75,6 +75,7 @@ impl<'de> Visitor<'de> for SessionVisitor {
           // For historical reasons, the database_storage_id is constructed by the user_id.
           database_indexer_id: STANDARD.encode(format!("{}:user:database", user_id)),
           icon: "".to_owned(),
+          member_count: 0,
         })
       }
     }
@@ -264,6 +264,29 @@ pub struct UserStatePB {
   pub auth_type: AuthenticatorPB,
 }
 
+/// The text that must be passed as [DeleteAccountPB::confirmation] to delete the account.
+pub const DELETE_ACCOUNT_CONFIRMATION: &str = "DELETE MY ACCOUNT";
+
+#[derive(ProtoBuf, Default)]
+pub struct DeleteAccountPB {
+  /// The id of the user that is deleted. It must be the current user.
+  #[pb(index = 1)]
+  pub uid: i64,
+
+  /// Must be [DELETE_ACCOUNT_CONFIRMATION], which the user is asked to type.
+  #[pb(index = 2)]
+  pub confirmation: String,
+}
+
+#[derive(ProtoBuf, Default)]
+pub struct ChangePasswordPB {
+  #[pb(index = 1)]
+  pub old_password: String,
+
+  #[pb(index = 2)]
+  pub new_password: String,
+}
+
 #[derive(ProtoBuf, Debug, Default, Clone)]
 pub struct AuthStateChangedPB {
   #[pb(index = 1)]
@@ -228,6 +228,13 @@ pub struct NetworkStatePB {
   pub ty: NetworkTypePB,
 }
 
+/// Whether the server can be reached, which is checked periodically in the background.
+#[derive(ProtoBuf, Debug, Default, Clone)]
+pub struct ServerConnectivityPB {
+  #[pb(index = 1)]
+  pub is_online: bool,
+}
+
 #[derive(ProtoBuf, Serialize, Deserialize, Debug, Clone)]
 pub struct DateTimeSettingsPB {
   #[pb(index = 1)]
@@ -276,3 +283,20 @@ pub struct AppFlowyCloudSettingPB {
   #[pb(index = 3)]
   pub gotrue_url: String,
 }
+
+/// A data migration that keeps failing, so it's skipped until it's cleared.
+#[derive(Debug, Default, Clone, ProtoBuf)]
+pub struct FailedMigrationPB {
+  #[pb(index = 1)]
+  pub migration_name: String,
+
+  #[pb(index = 2)]
+  pub error: String,
+
+  #[pb(index = 3)]
+  pub attempts: i32,
+
+  /// The time of the last attempt, in seconds since the Unix epoch.
+  #[pb(index = 4)]
+  pub failed_at: i64,
+}
@@ -1,7 +1,7 @@
 use validator::Validate;
 
 use flowy_derive::{ProtoBuf, ProtoBuf_Enum};
-use flowy_user_pub::entities::{Role, WorkspaceInvitation, WorkspaceMember};
+use flowy_user_pub::entities::{FeatureFlags, Role, WorkspaceInvitation, WorkspaceMember};
 use lib_infra::validator_fn::required_not_empty_str;
 
 #[derive(ProtoBuf, Default, Clone)]
@@ -170,11 +170,41 @@ pub struct UserWorkspaceIdPB {
   pub workspace_id: String,
 }
 
+/// Sent when a workspace that is cached locally no longer exists on the server, for example,
+/// because another admin deleted it or the user was removed from it.
+#[derive(ProtoBuf, Default, Clone, Debug)]
+pub struct RemovedUserWorkspacePB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub name: String,
+
+  /// Whether the removed workspace is the one the user is currently in.
+  #[pb(index = 3)]
+  pub is_current_workspace: bool,
+
+  /// The workspace the user should switch to. Empty if the removed workspace was the last one.
+  #[pb(index = 4)]
+  pub fallback_workspace_id: String,
+}
+
+#[derive(ProtoBuf, Default, Clone)]
+pub struct ReorderWorkspacesPB {
+  /// The workspace ids in the order they should be displayed
+  #[pb(index = 1)]
+  pub ordered_workspace_ids: Vec<String>,
+}
+
 #[derive(ProtoBuf, Default, Clone, Validate)]
 pub struct CreateWorkspacePB {
   #[pb(index = 1)]
   #[validate(custom = "required_not_empty_str")]
   pub name: String,
+
+  /// Adds a suffix to the name if another workspace already uses it, e.g. "Personal (2)"
+  #[pb(index = 2)]
+  pub unique_name: bool,
 }
 
 #[derive(ProtoBuf, Default, Clone, Validate)]
@@ -186,6 +216,11 @@ pub struct RenameWorkspacePB {
   #[pb(index = 2)]
   #[validate(custom = "required_not_empty_str")]
   pub new_name: String,
+
+  /// Only updates the local workspace after the server confirms the change. Otherwise, the local
+  /// workspace is updated right away and reverted if the server rejects the change.
+  #[pb(index = 3)]
+  pub strict: bool,
 }
 
 #[derive(ProtoBuf, Default, Clone, Validate)]
@@ -196,4 +231,36 @@ pub struct ChangeWorkspaceIconPB {
 
   #[pb(index = 2)]
   pub new_icon: String,
+
+  /// See [RenameWorkspacePB::strict].
+  #[pb(index = 3)]
+  pub strict: bool,
+}
+
+/// The features available in a workspace. The UI hides the features that are unavailable.
+#[derive(ProtoBuf, Default, Clone, Debug)]
+pub struct WorkspaceFeatureFlagsPB {
+  #[pb(index = 1)]
+  pub workspace_id: String,
+
+  #[pb(index = 2)]
+  pub guest_sharing: bool,
+
+  #[pb(index = 3)]
+  pub ai: bool,
+
+  /// The maximum number of members. Not set if it's unlimited.
+  #[pb(index = 4, one_of)]
+  pub member_limit: Option<i64>,
+}
+
+impl WorkspaceFeatureFlagsPB {
+  pub fn new(workspace_id: String, flags: FeatureFlags) -> Self {
+    Self {
+      workspace_id,
+      guest_sharing: flags.guest_sharing,
+      ai: flags.ai,
+      member_limit: flags.member_limit,
+    }
+  }
 }
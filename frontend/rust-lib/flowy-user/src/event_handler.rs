@@ -490,6 +490,14 @@ pub async fn get_all_workspace_handler(
   let manager = upgrade_manager(manager)?;
   let uid = manager.get_session()?.user_id;
   let user_workspaces = manager.get_all_user_workspaces(uid).await?;
+
+  // Refresh the workspaces in the background
+  let weak_manager = Arc::downgrade(&manager);
+  af_spawn(async move {
+    if let Some(manager) = weak_manager.upgrade() {
+      let _ = manager.refresh_user_workspaces(uid).await;
+    }
+  });
   data_result_ok(user_workspaces.into())
 }
 
@@ -511,12 +519,7 @@ pub async fn update_network_state_handler(
 ) -> Result<(), FlowyError> {
   let manager = upgrade_manager(manager)?;
   let reachable = data.into_inner().ty.is_reachable();
-  manager.cloud_services.set_network_reachable(reachable);
-  manager
-    .user_status_callback
-    .read()
-    .await
-    .did_update_network(reachable);
+  manager.did_update_network_reachability(reachable).await;
   Ok(())
 }
 
@@ -538,6 +541,15 @@ pub async fn open_anon_user_handler(
   Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn sign_in_anonymous_handler(
+  manager: AFPluginState<Weak<UserManager>>,
+) -> DataResult<UserProfilePB, FlowyError> {
+  let manager = upgrade_manager(manager)?;
+  let user_profile = manager.sign_in_anonymous().await?;
+  data_result_ok(UserProfilePB::from(user_profile))
+}
+
 pub async fn push_realtime_event_handler(
   payload: AFPluginData<RealtimePayloadPB>,
   manager: AFPluginState<Weak<UserManager>>,
@@ -685,7 +697,11 @@ pub async fn create_workspace_handler(
 ) -> DataResult<UserWorkspacePB, FlowyError> {
   let data = data.try_into_inner()?;
   let manager = upgrade_manager(manager)?;
-  let new_workspace = manager.add_workspace(&data.name).await?;
+  let new_workspace = if data.unique_name {
+    manager.create_workspace_unique(&data.name).await?
+  } else {
+    manager.add_workspace(&data.name).await?
+  };
   data_result_ok(new_workspace.into())
 }
 
@@ -708,7 +724,12 @@ pub async fn rename_workspace_handler(
   let params = rename_workspace_param.try_into_inner()?;
   let manager = upgrade_manager(manager)?;
   manager
-    .patch_workspace(&params.workspace_id, Some(&params.new_name), None)
+    .patch_workspace(
+      &params.workspace_id,
+      Some(&params.new_name),
+      None,
+      params.strict,
+    )
     .await?;
   Ok(())
 }
@@ -721,7 +742,12 @@ pub async fn change_workspace_icon_handler(
   let params = change_workspace_icon_param.try_into_inner()?;
   let manager = upgrade_manager(manager)?;
   manager
-    .patch_workspace(&params.workspace_id, None, Some(&params.new_icon))
+    .patch_workspace(
+      &params.workspace_id,
+      None,
+      Some(&params.new_icon),
+      params.strict,
+    )
     .await?;
   Ok(())
 }
@@ -774,3 +800,51 @@ pub async fn leave_workspace_handler(
   manager.leave_workspace(&workspace_id).await?;
   Ok(())
 }
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn reorder_workspaces_handler(
+  param: AFPluginData<ReorderWorkspacesPB>,
+  manager: AFPluginState<Weak<UserManager>>,
+) -> Result<(), FlowyError> {
+  let ordered_workspace_ids = param.into_inner().ordered_workspace_ids;
+  let manager = upgrade_manager(manager)?;
+  manager.reorder_workspaces(ordered_workspace_ids).await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn get_workspace_feature_flags_handler(
+  param: AFPluginData<UserWorkspaceIdPB>,
+  manager: AFPluginState<Weak<UserManager>>,
+) -> DataResult<WorkspaceFeatureFlagsPB, FlowyError> {
+  let workspace_id = param.try_into_inner()?.workspace_id;
+  let manager = upgrade_manager(manager)?;
+  let flags = manager.get_workspace_feature_flags(&workspace_id).await?;
+  data_result_ok(WorkspaceFeatureFlagsPB::new(workspace_id, flags))
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn change_password_handler(
+  param: AFPluginData<ChangePasswordPB>,
+  manager: AFPluginState<Weak<UserManager>>,
+) -> Result<(), FlowyError> {
+  let param = param.into_inner();
+  let manager = upgrade_manager(manager)?;
+  manager
+    .change_password(&param.old_password, &param.new_password)
+    .await?;
+  Ok(())
+}
+
+#[tracing::instrument(level = "debug", skip_all, err)]
+pub async fn delete_account_handler(
+  param: AFPluginData<DeleteAccountPB>,
+  manager: AFPluginState<Weak<UserManager>>,
+) -> Result<(), FlowyError> {
+  let param = param.into_inner();
+  let manager = upgrade_manager(manager)?;
+  manager
+    .delete_account(param.uid, &param.confirmation)
+    .await?;
+  Ok(())
+}
@@ -71,6 +71,11 @@ pub fn init(user_manager: Weak<UserManager>) -> AFPlugin {
     .event(UserEvent::InviteWorkspaceMember, invite_workspace_member_handler)
     .event(UserEvent::ListWorkspaceInvitations, list_workspace_invitations_handler)
     .event(UserEvent::AcceptWorkspaceInvitation, accept_workspace_invitations_handler)
+    .event(UserEvent::ReorderWorkspaces, reorder_workspaces_handler)
+    .event(UserEvent::GetWorkspaceFeatureFlags, get_workspace_feature_flags_handler)
+    .event(UserEvent::DeleteAccount, delete_account_handler)
+    .event(UserEvent::SignInAnonymous, sign_in_anonymous_handler)
+    .event(UserEvent::ChangePassword, change_password_handler)
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Display, Hash, ProtoBuf_Enum, Flowy_Event)]
@@ -230,6 +235,27 @@ pub enum UserEvent {
 
   #[event(input = "MagicLinkSignInPB", output = "UserProfilePB")]
   MagicLinkSignIn = 50,
+
+  /// Save the order of the workspaces in the user's workspace list
+  #[event(input = "ReorderWorkspacesPB")]
+  ReorderWorkspaces = 51,
+
+  /// Returns the features available in the workspace, so the UI can hide the unavailable ones
+  #[event(input = "UserWorkspaceIdPB", output = "WorkspaceFeatureFlagsPB")]
+  GetWorkspaceFeatureFlags = 52,
+
+  /// Deletes the account of the current user on the server and removes its data from the device
+  #[event(input = "DeleteAccountPB")]
+  DeleteAccount = 53,
+
+  /// Signs in as the anonymous user, who uses the app without an account. The existing anonymous
+  /// user is reused if there is one.
+  #[event(output = "UserProfilePB")]
+  SignInAnonymous = 54,
+
+  /// Changes the password of the current user, who must sign in with an email and a password
+  #[event(input = "ChangePasswordPB")]
+  ChangePassword = 55,
 }
 
 pub trait UserStatusCallback: Send + Sync + 'static {
@@ -265,6 +291,10 @@ pub trait UserStatusCallback: Send + Sync + 'static {
   fn did_expired(&self, token: &str, user_id: i64) -> Fut<FlowyResult<()>>;
   fn open_workspace(&self, user_id: i64, user_workspace: &UserWorkspace) -> Fut<FlowyResult<()>>;
   fn did_update_network(&self, _reachable: bool) {}
+  /// Called after the role of a member in the workspace is changed.
+  fn did_update_workspace_role(&self, _workspace_id: &str) -> Fut<FlowyResult<()>> {
+    to_fut(async { Ok(()) })
+  }
 }
 
 /// Acts as a placeholder [UserStatusCallback] for the user session, but does not perform any function
@@ -1,17 +1,25 @@
 use std::sync::Arc;
 
 use chrono::NaiveDateTime;
-use diesel::{RunQueryDsl, SqliteConnection};
+use diesel::{OptionalExtension, RunQueryDsl, SqliteConnection};
 use semver::Version;
+use tracing::warn;
 
 use collab_integrate::CollabKVDB;
 use flowy_error::FlowyResult;
-use flowy_sqlite::schema::user_data_migration_records;
-use flowy_sqlite::ConnectionPool;
+use flowy_sqlite::schema::{user_data_migration_failure_table, user_data_migration_records};
+use flowy_sqlite::{query_dsl::*, ConnectionPool, ExpressionMethods};
 use flowy_user_pub::entities::Authenticator;
 
 use flowy_user_pub::session::Session;
 
+use crate::entities::FailedMigrationPB;
+use crate::notification::{send_notification, UserNotification};
+
+/// The number of times a migration is run before it's skipped. Without the limit, a migration that
+/// keeps failing would be retried on every launch. See [clear_migration_failure].
+pub const MAX_MIGRATION_ATTEMPTS: i32 = 3;
+
 pub struct UserLocalDataMigration {
   session: Session,
   collab_db: Arc<CollabKVDB>,
@@ -40,6 +48,10 @@ impl UserLocalDataMigration {
   /// The function does not apply a migration if its name is already in the list of applied migrations.
   /// If a migration name is duplicated, the function logs an error message and continues with the next migration.
   ///
+  /// If a migration fails, the failure is recorded and the error is returned without running the
+  /// migrations after it. Once a migration has failed [MAX_MIGRATION_ATTEMPTS] times, it's skipped
+  /// and [UserNotification::DidFailMigration] is sent, so the launches aren't blocked by it.
+  ///
   /// # Arguments
   ///
   /// * `migrations` - A vector of boxed dynamic `UserDataMigration` objects representing the migrations to be applied.
@@ -53,6 +65,7 @@ impl UserLocalDataMigration {
     let mut applied_migrations = vec![];
     let mut conn = self.sqlite_pool.get()?;
     let record = get_all_records(&mut conn)?;
+    let failures = get_all_migration_failures(&mut conn)?;
     let mut duplicated_names = vec![];
     for migration in migrations {
       if !record
@@ -67,9 +80,33 @@ impl UserLocalDataMigration {
 
         let migration_name = migration.name().to_string();
         if !duplicated_names.contains(&migration_name) {
-          migration.run(&self.session, &self.collab_db, authenticator)?;
+          let failure = failures
+            .iter()
+            .find(|failure| failure.migration_name == migration_name);
+          if let Some(failure) = failure {
+            if failure.attempts >= MAX_MIGRATION_ATTEMPTS {
+              warn!(
+                "Skip the migration {} that failed {} times: {}",
+                migration_name, failure.attempts, failure.error
+              );
+              notify_failed_migration(self.session.user_id, failure);
+              duplicated_names.push(migration_name);
+              continue;
+            }
+          }
+
+          if let Err(err) = migration.run(&self.session, &self.collab_db, authenticator) {
+            let failure = save_migration_failure(&mut conn, &migration_name, &err.to_string())?;
+            if failure.attempts >= MAX_MIGRATION_ATTEMPTS {
+              notify_failed_migration(self.session.user_id, &failure);
+            }
+            return Err(err);
+          }
           applied_migrations.push(migration.name().to_string());
           save_migration_record(&mut conn, &migration_name);
+          if failure.is_some() {
+            clear_migration_failure(&mut conn, &migration_name)?;
+          }
           duplicated_names.push(migration_name);
         } else {
           tracing::error!("Duplicated migration name: {}", migration_name);
@@ -112,6 +149,58 @@ fn get_all_records(conn: &mut SqliteConnection) -> FlowyResult<Vec<UserDataMigra
   )
 }
 
+/// Records a failed run of the migration and returns the failure with the number of the attempts
+/// so far.
+fn save_migration_failure(
+  conn: &mut SqliteConnection,
+  migration_name: &str,
+  error: &str,
+) -> FlowyResult<UserDataMigrationFailure> {
+  let attempts = user_data_migration_failure_table::table
+    .filter(user_data_migration_failure_table::migration_name.eq(migration_name))
+    .select(user_data_migration_failure_table::attempts)
+    .first::<i32>(conn)
+    .optional()?
+    .unwrap_or(0);
+  let failure = UserDataMigrationFailure {
+    migration_name: migration_name.to_string(),
+    error: error.to_string(),
+    attempts: attempts + 1,
+    failed_at: chrono::Utc::now().timestamp(),
+  };
+  diesel::replace_into(user_data_migration_failure_table::table)
+    .values(&failure)
+    .execute(conn)?;
+  Ok(failure)
+}
+
+pub(crate) fn get_all_migration_failures(
+  conn: &mut SqliteConnection,
+) -> FlowyResult<Vec<UserDataMigrationFailure>> {
+  let failures = user_data_migration_failure_table::table.load::<UserDataMigrationFailure>(conn)?;
+  Ok(failures)
+}
+
+/// Forgets the failures of the migration, so it's run again on the next launch, e.g. after the
+/// cause of the failures is fixed. Returns true if the migration had failed.
+pub(crate) fn clear_migration_failure(
+  conn: &mut SqliteConnection,
+  migration_name: &str,
+) -> FlowyResult<bool> {
+  let num_of_deleted = diesel::delete(
+    user_data_migration_failure_table::table
+      .filter(user_data_migration_failure_table::migration_name.eq(migration_name)),
+  )
+  .execute(conn)?;
+  Ok(num_of_deleted > 0)
+}
+
+fn notify_failed_migration(uid: i64, failure: &UserDataMigrationFailure) {
+  send_notification(&uid.to_string(), UserNotification::DidFailMigration)
+    .payload(FailedMigrationPB::from(failure.clone()))
+    .send();
+}
+
 #[derive(Clone, Default, Queryable, Identifiable)]
 #[diesel(table_name = user_data_migration_records)]
 pub struct UserDataMigrationRecord {
@@ -125,3 +214,127 @@ pub struct UserDataMigrationRecord {
 pub struct NewUserDataMigrationRecord {
   pub migration_name: String,
 }
+
+#[derive(Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = user_data_migration_failure_table)]
+pub struct UserDataMigrationFailure {
+  pub migration_name: String,
+  pub error: String,
+  pub attempts: i32,
+  pub failed_at: i64,
+}
+
+impl From<UserDataMigrationFailure> for FailedMigrationPB {
+  fn from(failure: UserDataMigrationFailure) -> Self {
+    Self {
+      migration_name: failure.migration_name,
+      error: failure.error,
+      attempts: failure.attempts,
+      failed_at: failure.failed_at,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::Arc;
+
+  use semver::Version;
+  use uuid::Uuid;
+
+  use collab_integrate::CollabKVDB;
+  use flowy_error::{FlowyError, FlowyResult};
+  use flowy_user_pub::entities::{Authenticator, UserWorkspace};
+  use flowy_user_pub::session::Session;
+
+  use super::{
+    clear_migration_failure, get_all_migration_failures, UserDataMigration, UserLocalDataMigration,
+    MAX_MIGRATION_ATTEMPTS,
+  };
+
+  struct TestMigration {
+    name: &'static str,
+    is_failing: bool,
+    num_of_runs: Arc<AtomicUsize>,
+  }
+
+  impl UserDataMigration for TestMigration {
+    fn name(&self) -> &str {
+      self.name
+    }
+
+    fn applies_to_version(&self, _app_version: &Version) -> bool {
+      true
+    }
+
+    fn run(
+      &self,
+      _user: &Session,
+      _collab_db: &Arc<CollabKVDB>,
+      _authenticator: &Authenticator,
+    ) -> FlowyResult<()> {
+      self.num_of_runs.fetch_add(1, Ordering::SeqCst);
+      if self.is_failing {
+        return Err(FlowyError::internal().with_context("broken data"));
+      }
+      Ok(())
+    }
+  }
+
+  #[test]
+  fn skip_failing_migration_test() {
+    let dir = std::env::temp_dir().join(nanoid::nanoid!(10));
+    let pool = flowy_sqlite::init(dir.join("sqlite")).unwrap().get_pool();
+    let collab_db = Arc::new(CollabKVDB::open(dir.join("collab_db")).unwrap());
+    let session = Session {
+      user_id: 1,
+      user_uuid: Uuid::new_v4(),
+      user_workspace: UserWorkspace::new("workspace", 1),
+    };
+    let failing_runs = Arc::new(AtomicUsize::new(0));
+    let next_runs = Arc::new(AtomicUsize::new(0));
+    let run_migrations = || {
+      let migrations: Vec<Box<dyn UserDataMigration>> = vec![
+        Box::new(TestMigration {
+          name: "failing",
+          is_failing: true,
+          num_of_runs: failing_runs.clone(),
+        }),
+        Box::new(TestMigration {
+          name: "next",
+          is_failing: false,
+          num_of_runs: next_runs.clone(),
+        }),
+      ];
+      UserLocalDataMigration::new(session.clone(), collab_db.clone(), pool.clone()).run(
+        migrations,
+        &Authenticator::Local,
+        None,
+      )
+    };
+
+    // The migrations after the failing one wait until it's skipped
+    for _ in 0..MAX_MIGRATION_ATTEMPTS {
+      assert!(run_migrations().is_err());
+    }
+    assert_eq!(next_runs.load(Ordering::SeqCst), 0);
+    assert_eq!(run_migrations().unwrap(), vec!["next".to_string()]);
+    assert_eq!(
+      failing_runs.load(Ordering::SeqCst),
+      MAX_MIGRATION_ATTEMPTS as usize
+    );
+    let failures = get_all_migration_failures(&mut pool.get().unwrap()).unwrap();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].attempts, MAX_MIGRATION_ATTEMPTS);
+    assert!(failures[0].error.contains("broken data"));
+
+    // The migration is run again once its failures are cleared
+    assert!(clear_migration_failure(&mut pool.get().unwrap(), "failing").unwrap());
+    assert!(run_migrations().is_err());
+    assert_eq!(
+      failing_runs.load(Ordering::SeqCst),
+      MAX_MIGRATION_ATTEMPTS as usize + 1
+    );
+  }
+}
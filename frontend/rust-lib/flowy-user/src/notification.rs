@@ -5,6 +5,10 @@ use crate::entities::AuthStateChangedPB;
 
 const USER_OBSERVABLE_SOURCE: &str = "User";
 
+/// The id of [UserNotification::DidUpdateServerConnectivity], which is sent whether or not a user
+/// is signed in. Don't change it, it's also used in the frontend.
+pub(crate) const SERVER_CONNECTIVITY_NOTIFICATION_ID: &str = "server_connectivity";
+
 #[derive(ProtoBuf_Enum, Debug, Default)]
 pub(crate) enum UserNotification {
   #[default]
@@ -14,6 +18,15 @@ pub(crate) enum UserNotification {
   DidUpdateUserWorkspaces = 3,
   DidUpdateCloudConfig = 4,
   DidUpdateUserWorkspace = 5,
+  DidRemoveUserWorkspace = 6,
+  /// Sent when the token can't be refreshed before it expires
+  DidFailToRefreshToken = 7,
+  /// Sent when the features available in a workspace change
+  DidUpdateWorkspaceFeatureFlags = 8,
+  /// Sent when a data migration is skipped because it failed too many times
+  DidFailMigration = 9,
+  /// Sent when the server becomes reachable or unreachable
+  DidUpdateServerConnectivity = 11,
 }
 
 impl std::convert::From<UserNotification> for i32 {
@@ -1,13 +1,17 @@
 use crate::migrations::session_migration::migrate_session_with_user_uuid;
 use crate::services::db::UserDB;
 use crate::services::entities::{UserConfig, UserPaths};
-use crate::services::sqlite_sql::user_sql::vacuum_database;
+use crate::services::sqlite_sql::favorite_view_sql::{
+  delete_favorite_view_op, insert_favorite_view_op, select_favorite_view_ids_op,
+};
+use crate::services::sqlite_sql::user_sql::{select_user_profile, vacuum_database};
+use crate::user_manager::manager_user_workspace::delete_user_workspaces;
 use collab_integrate::CollabKVDB;
 
 use flowy_error::{internal_error, ErrorCode, FlowyError, FlowyResult};
 use flowy_sqlite::kv::StorePreferences;
-use flowy_sqlite::DBConnection;
-use flowy_user_pub::entities::UserWorkspace;
+use flowy_sqlite::{DBConnection, PoolUtilization};
+use flowy_user_pub::entities::{UserProfile, UserWorkspace};
 use flowy_user_pub::session::Session;
 use std::path::PathBuf;
 use std::sync::{Arc, Weak};
@@ -26,7 +30,7 @@ pub struct AuthenticateUser {
 impl AuthenticateUser {
   pub fn new(user_config: UserConfig, store_preferences: Arc<StorePreferences>) -> Self {
     let user_paths = UserPaths::new(user_config.storage_path.clone());
-    let database = Arc::new(UserDB::new(user_paths.clone()));
+    let database = Arc::new(UserDB::new(user_paths.clone(), user_config.db_pool_size));
     let session = Arc::new(parking_lot::RwLock::new(None));
     *session.write() =
       migrate_session_with_user_uuid(&user_config.session_cache_key, &store_preferences);
@@ -62,8 +66,13 @@ impl AuthenticateUser {
     Ok(self.user_config.device_id.to_string())
   }
 
+  /// Returns the id of the current workspace. Fails if the session has no workspace, see
+  /// [Self::clear_user_workspace].
   pub fn workspace_id(&self) -> FlowyResult<String> {
     let session = self.get_session()?;
+    if session.user_workspace.id.is_empty() {
+      return Err(FlowyError::record_not_found().with_context("No workspace is opened"));
+    }
     Ok(session.user_workspace.id)
   }
 
@@ -83,6 +92,36 @@ impl AuthenticateUser {
     self.database.get_connection(uid)
   }
 
+  /// Returns the utilization of the connection pool of the current user's sqlite database.
+  pub fn get_sqlite_pool_utilization(&self) -> FlowyResult<PoolUtilization> {
+    let uid = self.user_id()?;
+    Ok(self.database.get_pool(uid)?.utilization())
+  }
+
+  pub fn get_user_profile(&self) -> FlowyResult<UserProfile> {
+    let uid = self.user_id()?;
+    let conn = self.get_sqlite_connection(uid)?;
+    select_user_profile(uid, conn)
+  }
+
+  /// Returns the ids of the favorite views of the workspace in the order they were added. The
+  /// favorite views are stored per workspace in the user's sqlite database.
+  pub fn get_favorite_view_ids(&self, workspace_id: &str) -> FlowyResult<Vec<String>> {
+    let uid = self.user_id()?;
+    select_favorite_view_ids_op(workspace_id, self.get_sqlite_connection(uid)?)
+  }
+
+  pub fn add_favorite_view_id(&self, workspace_id: &str, view_id: &str) -> FlowyResult<()> {
+    let uid = self.user_id()?;
+    insert_favorite_view_op(workspace_id, view_id, self.get_sqlite_connection(uid)?)
+  }
+
+  /// Returns true if the view was in the favorite list of the workspace.
+  pub fn remove_favorite_view_id(&self, workspace_id: &str, view_id: &str) -> FlowyResult<bool> {
+    let uid = self.user_id()?;
+    delete_favorite_view_op(workspace_id, view_id, self.get_sqlite_connection(uid)?)
+  }
+
   pub fn get_index_path(&self) -> PathBuf {
     let uid = self.user_id().unwrap_or(0);
     PathBuf::from(self.user_paths.user_data_dir(uid)).join("indexes")
@@ -123,6 +162,20 @@ impl AuthenticateUser {
     self.set_session(Some(session))
   }
 
+  /// Leaves the session without a current workspace until one is opened, e.g. after the last
+  /// workspace of the user is removed.
+  pub fn clear_user_workspace(&self) -> FlowyResult<()> {
+    let mut session = self.get_session()?;
+    session.user_workspace = UserWorkspace::new("", session.user_id);
+    self.set_session(Some(session))
+  }
+
+  /// Removes the workspace from the locally cached workspace list of the user.
+  pub fn remove_user_workspace(&self, uid: i64, workspace_id: &str) -> FlowyResult<()> {
+    let conn = self.database.get_connection(uid)?;
+    delete_user_workspaces(conn, workspace_id)
+  }
+
   pub fn get_session(&self) -> FlowyResult<Session> {
     if let Some(session) = (self.session.read()).clone() {
       return Ok(session);
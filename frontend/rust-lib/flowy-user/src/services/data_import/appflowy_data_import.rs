@@ -27,7 +27,7 @@ use flowy_folder_pub::cloud::gen_view_id;
 use flowy_folder_pub::entities::{AppFlowyData, ImportData};
 use flowy_folder_pub::folder_builder::{ParentChildViews, ViewBuilder};
 use flowy_sqlite::kv::StorePreferences;
-use flowy_user_pub::cloud::{UserCloudService, UserCollabParams};
+use flowy_user_pub::cloud::{BatchCreateReport, UserCloudService, UserCollabParams};
 use flowy_user_pub::entities::{user_awareness_object_id, Authenticator};
 use flowy_user_pub::session::Session;
 use parking_lot::{Mutex, RwLock};
@@ -796,12 +796,13 @@ pub async fn upload_collab_objects_data(
   user_authenticator: &Authenticator,
   appflowy_data: AppFlowyData,
   user_cloud_service: Arc<dyn UserCloudService>,
-) -> Result<(), FlowyError> {
+) -> Result<BatchCreateReport, FlowyError> {
   // Only support uploading the collab data when the current server is AppFlowy Cloud server
   if !user_authenticator.is_appflowy_cloud() {
-    return Ok(());
+    return Ok(BatchCreateReport::default());
   }
 
+  let mut report = BatchCreateReport::default();
   match appflowy_data {
     AppFlowyData::Folder { .. } => {},
     AppFlowyData::CollabObject {
@@ -857,20 +858,20 @@ pub async fn upload_collab_objects_data(
       // upload fails, we will retry the upload later.
       // af_spawn(async move {
       if !objects.is_empty() {
-        batch_create(
+        report = batch_create(
           uid,
           workspace_id,
           &user_cloud_service,
           &size_counter,
           objects,
         )
-        .await;
+        .await?;
       }
       // });
     },
   }
 
-  Ok(())
+  Ok(report)
 }
 
 async fn batch_create(
@@ -879,27 +880,36 @@ async fn batch_create(
   user_cloud_service: &Arc<dyn UserCloudService>,
   size_counter: &usize,
   objects: Vec<UserCollabParams>,
-) {
+) -> Result<BatchCreateReport, FlowyError> {
   let ids = objects
     .iter()
     .map(|o| o.object_id.clone())
     .collect::<Vec<_>>()
     .join(", ");
   match user_cloud_service
-    .batch_create_collab_object(workspace_id, objects)
+    .batch_create_collab_object(workspace_id, objects, false)
     .await
   {
-    Ok(_) => {
-      info!(
-        "Batch creating collab objects success, origin payload size: {}",
-        size_counter
-      );
+    Ok(report) => {
+      if report.is_complete() {
+        info!(
+          "Batch creating collab objects success, origin payload size: {}",
+          size_counter
+        );
+      } else {
+        error!(
+          "Batch creating collab objects partially fail, skipped: {:?}, rejected: {:?}, workspace_id:{}, uid: {}",
+          report.skipped_object_ids, report.rejected_object_ids, workspace_id, uid
+        );
+      }
+      Ok(report)
     },
     Err(err) => {
       error!(
       "Batch creating collab objects fail:{}, origin payload size: {}, workspace_id:{}, uid: {}, error: {:?}",
        ids, size_counter, workspace_id, uid,err
       );
+      Err(err)
     },
   }
 }
@@ -6,12 +6,12 @@ use collab_integrate::{CollabKVAction, CollabKVDB, PersistenceError};
 use collab_plugins::local_storage::kv::KVTransactionDB;
 use flowy_error::FlowyError;
 use flowy_sqlite::schema::user_workspace_table;
-use flowy_sqlite::ConnectionPool;
 use flowy_sqlite::{
   query_dsl::*,
   schema::{user_table, user_table::dsl},
   DBConnection, Database, ExpressionMethods,
 };
+use flowy_sqlite::{ConnectionPool, PoolConfig};
 use flowy_user_pub::entities::{UserProfile, UserWorkspace};
 use lib_dispatch::prelude::af_spawn;
 use lib_infra::file_util::{unzip_and_replace, zip_folder};
@@ -29,14 +29,17 @@ pub trait UserDBPath: Send + Sync + 'static {
 
 pub struct UserDB {
   paths: Box<dyn UserDBPath>,
+  /// The max number of the connections of each sqlite database.
+  pool_size: u32,
   sqlite_map: RwLock<HashMap<i64, Database>>,
   collab_db_map: RwLock<HashMap<i64, Arc<CollabKVDB>>>,
 }
 
 impl UserDB {
-  pub fn new(paths: impl UserDBPath) -> Self {
+  pub fn new(paths: impl UserDBPath, pool_size: u32) -> Self {
     Self {
       paths: Box::new(paths),
+      pool_size,
       sqlite_map: Default::default(),
       collab_db_map: Default::default(),
     }
@@ -154,7 +157,8 @@ impl UserDB {
 
     let mut write_guard = self.sqlite_map.write();
     tracing::debug!("open sqlite db {} at path: {:?}", user_id, db_path.as_ref());
-    let db = flowy_sqlite::init(&db_path)
+    let pool_config = PoolConfig::default().max_size(self.pool_size);
+    let db = flowy_sqlite::init_with_pool_config(&db_path, pool_config)
       .map_err(|e| FlowyError::internal().with_context(format!("open user db failed, {:?}", e)))?;
     let pool = db.get_pool();
     write_guard.insert(user_id.to_owned(), db);
@@ -5,6 +5,7 @@ use std::path::PathBuf;
 use crate::services::db::UserDBPath;
 use base64::engine::general_purpose::PAD;
 use base64::engine::GeneralPurpose;
+use flowy_sqlite::DEFAULT_POOL_SIZE;
 use semver::Version;
 
 pub const URL_SAFE_ENGINE: GeneralPurpose = GeneralPurpose::new(&URL_SAFE, PAD);
@@ -21,6 +22,9 @@ pub struct UserConfig {
   /// Used as the key of `Session` when saving session information to KV.
   pub(crate) session_cache_key: String,
   pub app_version: Version,
+  /// The max number of the connections of the user's sqlite database. Changing it only requires
+  /// reopening the database.
+  pub db_pool_size: u32,
 }
 
 impl UserConfig {
@@ -40,9 +44,15 @@ impl UserConfig {
       session_cache_key,
       device_id: device_id.to_owned(),
       app_version,
+      db_pool_size: DEFAULT_POOL_SIZE,
     }
   }
 
+  pub fn with_db_pool_size(mut self, db_pool_size: u32) -> Self {
+    self.db_pool_size = db_pool_size;
+    self
+  }
+
   /// Returns bool whether the user choose a custom path for the user data.
   pub fn is_custom_storage_path(&self) -> bool {
     !self.storage_path.contains(&self.application_path)
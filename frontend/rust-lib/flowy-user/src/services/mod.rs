@@ -5,3 +5,4 @@ pub mod data_import;
 pub mod db;
 pub mod entities;
 pub mod sqlite_sql;
+pub mod workspace_kv_store;
@@ -0,0 +1,84 @@
+use diesel::{RunQueryDsl, SqliteConnection};
+use flowy_error::FlowyError;
+use flowy_sqlite::schema::user_favorite_view_table;
+use flowy_sqlite::DBConnection;
+use flowy_sqlite::{query_dsl::*, ExpressionMethods};
+
+#[derive(Clone, Default, Queryable, Insertable)]
+#[diesel(table_name = user_favorite_view_table)]
+pub struct UserFavoriteViewTable {
+  pub workspace_id: String,
+  pub view_id: String,
+  /// The position of the view in the favorite list of the workspace.
+  pub order_index: i64,
+}
+
+/// Returns the ids of the favorite views of the workspace in the order they were added.
+pub fn select_favorite_view_ids_op(
+  workspace_id: &str,
+  mut conn: DBConnection,
+) -> Result<Vec<String>, FlowyError> {
+  let view_ids = user_favorite_view_table::dsl::user_favorite_view_table
+    .filter(user_favorite_view_table::workspace_id.eq(workspace_id))
+    .order(user_favorite_view_table::order_index.asc())
+    .select(user_favorite_view_table::view_id)
+    .load::<String>(&mut *conn)?;
+  Ok(view_ids)
+}
+
+/// Appends the view to the end of the favorite list of the workspace. Does nothing if the view is
+/// already in the list.
+pub fn insert_favorite_view_op(
+  workspace_id: &str,
+  view_id: &str,
+  mut conn: DBConnection,
+) -> Result<(), FlowyError> {
+  conn.immediate_transaction(|conn| {
+    if is_favorite_view(workspace_id, view_id, conn)? {
+      return Ok::<(), FlowyError>(());
+    }
+
+    let order_index = user_favorite_view_table::dsl::user_favorite_view_table
+      .filter(user_favorite_view_table::workspace_id.eq(workspace_id))
+      .select(diesel::dsl::max(user_favorite_view_table::order_index))
+      .first::<Option<i64>>(conn)?
+      .map(|index| index + 1)
+      .unwrap_or(0);
+    diesel::insert_into(user_favorite_view_table::table)
+      .values(UserFavoriteViewTable {
+        workspace_id: workspace_id.to_string(),
+        view_id: view_id.to_string(),
+        order_index,
+      })
+      .execute(conn)?;
+    Ok(())
+  })
+}
+
+/// Returns true if the view was in the favorite list of the workspace.
+pub fn delete_favorite_view_op(
+  workspace_id: &str,
+  view_id: &str,
+  mut conn: DBConnection,
+) -> Result<bool, FlowyError> {
+  let num_of_deleted = diesel::delete(
+    user_favorite_view_table::dsl::user_favorite_view_table
+      .filter(user_favorite_view_table::workspace_id.eq(workspace_id))
+      .filter(user_favorite_view_table::view_id.eq(view_id)),
+  )
+  .execute(&mut *conn)?;
+  Ok(num_of_deleted > 0)
+}
+
+fn is_favorite_view(
+  workspace_id: &str,
+  view_id: &str,
+  conn: &mut SqliteConnection,
+) -> Result<bool, FlowyError> {
+  let count = user_favorite_view_table::dsl::user_favorite_view_table
+    .filter(user_favorite_view_table::workspace_id.eq(workspace_id))
+    .filter(user_favorite_view_table::view_id.eq(view_id))
+    .count()
+    .get_result::<i64>(conn)?;
+  Ok(count > 0)
+}
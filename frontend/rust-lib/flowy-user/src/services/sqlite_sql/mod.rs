@@ -1,2 +1,4 @@
+pub(crate) mod favorite_view_sql;
 pub(crate) mod user_sql;
+pub(crate) mod workspace_kv_sql;
 pub(crate) mod workspace_sql;
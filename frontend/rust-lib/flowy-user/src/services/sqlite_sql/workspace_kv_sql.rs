@@ -0,0 +1,72 @@
+use diesel::{OptionalExtension, RunQueryDsl};
+use flowy_error::FlowyError;
+use flowy_sqlite::schema::workspace_kv_table;
+use flowy_sqlite::DBConnection;
+use flowy_sqlite::{query_dsl::*, ExpressionMethods};
+
+#[derive(Clone, Default, Queryable, Insertable)]
+#[diesel(table_name = workspace_kv_table)]
+pub struct WorkspaceKVTable {
+  pub workspace_id: String,
+  pub key: String,
+  pub value: String,
+}
+
+/// Inserts the value of the key, or replaces it if the key exists in the workspace.
+pub fn upsert_workspace_kv_op(
+  workspace_id: &str,
+  key: &str,
+  value: &str,
+  mut conn: DBConnection,
+) -> Result<(), FlowyError> {
+  diesel::replace_into(workspace_kv_table::table)
+    .values(WorkspaceKVTable {
+      workspace_id: workspace_id.to_string(),
+      key: key.to_string(),
+      value: value.to_string(),
+    })
+    .execute(&mut *conn)?;
+  Ok(())
+}
+
+pub fn select_workspace_kv_op(
+  workspace_id: &str,
+  key: &str,
+  mut conn: DBConnection,
+) -> Result<Option<String>, FlowyError> {
+  let value = workspace_kv_table::dsl::workspace_kv_table
+    .filter(workspace_kv_table::workspace_id.eq(workspace_id))
+    .filter(workspace_kv_table::key.eq(key))
+    .select(workspace_kv_table::value)
+    .first::<String>(&mut *conn)
+    .optional()?;
+  Ok(value)
+}
+
+/// Returns true if the key existed in the workspace.
+pub fn delete_workspace_kv_op(
+  workspace_id: &str,
+  key: &str,
+  mut conn: DBConnection,
+) -> Result<bool, FlowyError> {
+  let num_of_deleted = diesel::delete(
+    workspace_kv_table::dsl::workspace_kv_table
+      .filter(workspace_kv_table::workspace_id.eq(workspace_id))
+      .filter(workspace_kv_table::key.eq(key)),
+  )
+  .execute(&mut *conn)?;
+  Ok(num_of_deleted > 0)
+}
+
+/// Returns the keys of the workspace in alphabetical order.
+pub fn select_workspace_kv_keys_op(
+  workspace_id: &str,
+  mut conn: DBConnection,
+) -> Result<Vec<String>, FlowyError> {
+  let keys = workspace_kv_table::dsl::workspace_kv_table
+    .filter(workspace_kv_table::workspace_id.eq(workspace_id))
+    .order(workspace_kv_table::key.asc())
+    .select(workspace_kv_table::key)
+    .load::<String>(&mut *conn)?;
+  Ok(keys)
+}
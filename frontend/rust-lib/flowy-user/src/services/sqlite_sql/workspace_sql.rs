@@ -4,7 +4,7 @@ use flowy_error::FlowyError;
 use flowy_sqlite::schema::user_workspace_table;
 use flowy_sqlite::DBConnection;
 use flowy_sqlite::{query_dsl::*, ExpressionMethods};
-use flowy_user_pub::entities::UserWorkspace;
+use flowy_user_pub::entities::{Authenticator, UserWorkspace};
 use std::convert::TryFrom;
 
 #[derive(Clone, Default, Queryable, Identifiable, Insertable)]
@@ -16,6 +16,15 @@ pub struct UserWorkspaceTable {
   pub created_at: i64,
   pub database_storage_id: String,
   pub icon: String,
+  /// The position of the workspace in the user's workspace list. Workspaces that haven't been
+  /// ordered by the user yet have no order index and are listed after the ordered ones.
+  pub order_index: Option<i64>,
+  pub member_count: i64,
+  /// The [Authenticator] of the session that the workspace was first saved with. It's None for
+  /// the workspaces saved before the type was recorded.
+  pub auth_type: Option<i32>,
+  /// The server that the workspace was first saved with. It's empty for the local workspaces.
+  pub service_url: Option<String>,
 }
 
 pub fn get_user_workspace_op(workspace_id: &str, mut conn: DBConnection) -> Option<UserWorkspace> {
@@ -30,12 +39,113 @@ pub fn get_all_user_workspace_op(
   user_id: i64,
   mut conn: DBConnection,
 ) -> Result<Vec<UserWorkspace>, FlowyError> {
-  let rows = user_workspace_table::dsl::user_workspace_table
+  let mut rows = user_workspace_table::dsl::user_workspace_table
     .filter(user_workspace_table::uid.eq(user_id))
     .load::<UserWorkspaceTable>(&mut *conn)?;
+  rows.sort_by_key(|row| (row.order_index.is_none(), row.order_index, row.created_at));
   Ok(rows.into_iter().map(UserWorkspace::from).collect())
 }
 
+/// Persists the order of the user's workspaces. The workspaces in [ordered_ids] are placed first,
+/// followed by the remaining workspaces in their current order.
+pub fn update_user_workspace_order_op(
+  uid: i64,
+  ordered_ids: &[String],
+  mut conn: DBConnection,
+) -> Result<(), FlowyError> {
+  conn.immediate_transaction(|conn| {
+    let mut rows = user_workspace_table::dsl::user_workspace_table
+      .filter(user_workspace_table::uid.eq(uid))
+      .load::<UserWorkspaceTable>(conn)?;
+    rows.sort_by_key(|row| (row.order_index.is_none(), row.order_index, row.created_at));
+
+    if let Some(unknown_id) = ordered_ids
+      .iter()
+      .find(|id| !rows.iter().any(|row| &row.id == *id))
+    {
+      return Err(
+        FlowyError::record_not_found()
+          .with_context(format!("The workspace:{} is not found", unknown_id)),
+      );
+    }
+
+    let remaining_ids = rows
+      .into_iter()
+      .map(|row| row.id)
+      .filter(|id| !ordered_ids.contains(id));
+    for (index, workspace_id) in ordered_ids.iter().cloned().chain(remaining_ids).enumerate() {
+      diesel::update(
+        user_workspace_table::dsl::user_workspace_table
+          .filter(user_workspace_table::id.eq(&workspace_id)),
+      )
+      .set(user_workspace_table::order_index.eq(Some(index as i64)))
+      .execute(conn)?;
+    }
+    Ok::<(), FlowyError>(())
+  })
+}
+
+pub fn update_user_workspace_member_count_op(
+  workspace_id: &str,
+  member_count: i64,
+  mut conn: DBConnection,
+) -> Result<(), FlowyError> {
+  diesel::update(
+    user_workspace_table::dsl::user_workspace_table
+      .filter(user_workspace_table::id.eq(workspace_id)),
+  )
+  .set(user_workspace_table::member_count.eq(member_count))
+  .execute(&mut *conn)?;
+  Ok(())
+}
+
+/// The type of the session that a workspace was saved with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceAuthType {
+  pub authenticator: Authenticator,
+  /// Empty for the local workspaces.
+  pub service_url: String,
+}
+
+/// Returns None if the workspace isn't found or its type isn't recorded.
+pub fn get_user_workspace_auth_type_op(
+  workspace_id: &str,
+  mut conn: DBConnection,
+) -> Option<WorkspaceAuthType> {
+  let (auth_type, service_url) = user_workspace_table::dsl::user_workspace_table
+    .filter(user_workspace_table::id.eq(workspace_id))
+    .select((
+      user_workspace_table::auth_type,
+      user_workspace_table::service_url,
+    ))
+    .first::<(Option<i32>, Option<String>)>(&mut *conn)
+    .ok()?;
+  Some(WorkspaceAuthType {
+    authenticator: Authenticator::from(auth_type?),
+    service_url: service_url.unwrap_or_default(),
+  })
+}
+
+/// Records [auth_type] on the workspaces of the user that don't have a type yet. The type of a
+/// workspace is never overwritten, so it keeps the session that it was first saved with.
+pub fn update_user_workspace_auth_type_op(
+  uid: i64,
+  auth_type: &WorkspaceAuthType,
+  mut conn: DBConnection,
+) -> Result<(), FlowyError> {
+  diesel::update(
+    user_workspace_table::dsl::user_workspace_table
+      .filter(user_workspace_table::uid.eq(uid))
+      .filter(user_workspace_table::auth_type.is_null()),
+  )
+  .set((
+    user_workspace_table::auth_type.eq(Some(auth_type.authenticator.clone() as i32)),
+    user_workspace_table::service_url.eq(Some(&auth_type.service_url)),
+  ))
+  .execute(&mut *conn)?;
+  Ok(())
+}
+
 /// Remove all existing workspaces for given user and insert the new ones.
 ///
 #[allow(dead_code)]
@@ -92,6 +202,10 @@ impl TryFrom<(i64, &UserWorkspace)> for UserWorkspaceTable {
       created_at: value.1.created_at.timestamp(),
       database_storage_id: value.1.database_indexer_id.clone(),
       icon: value.1.icon.clone(),
+      order_index: None,
+      member_count: value.1.member_count,
+      auth_type: None,
+      service_url: None,
     })
   }
 }
@@ -107,6 +221,7 @@ impl From<UserWorkspaceTable> for UserWorkspace {
         .unwrap_or_default(),
       database_indexer_id: value.database_storage_id,
       icon: value.icon,
+      member_count: value.member_count,
     }
   }
 }
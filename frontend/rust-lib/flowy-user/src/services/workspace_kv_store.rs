@@ -0,0 +1,60 @@
+use std::sync::Weak;
+
+use flowy_error::{FlowyError, FlowyResult};
+use flowy_sqlite::DBConnection;
+
+use crate::services::authenticate_user::AuthenticateUser;
+use crate::services::sqlite_sql::workspace_kv_sql::{
+  delete_workspace_kv_op, select_workspace_kv_keys_op, select_workspace_kv_op,
+  upsert_workspace_kv_op,
+};
+
+/// Stores the small settings of a workspace, e.g. the UI preferences or the last opened view, in
+/// the user's sqlite database. Each store is bound to one workspace, so the settings of the other
+/// workspaces are never read or overwritten after switching the workspace. The settings are
+/// deleted along with the workspace.
+#[derive(Clone)]
+pub struct WorkspaceKvStore {
+  workspace_id: String,
+  authenticate_user: Weak<AuthenticateUser>,
+}
+
+impl WorkspaceKvStore {
+  pub(crate) fn new(workspace_id: &str, authenticate_user: Weak<AuthenticateUser>) -> Self {
+    Self {
+      workspace_id: workspace_id.to_string(),
+      authenticate_user,
+    }
+  }
+
+  pub fn workspace_id(&self) -> &str {
+    &self.workspace_id
+  }
+
+  pub fn set(&self, key: &str, value: &str) -> FlowyResult<()> {
+    upsert_workspace_kv_op(&self.workspace_id, key, value, self.get_connection()?)
+  }
+
+  pub fn get(&self, key: &str) -> FlowyResult<Option<String>> {
+    select_workspace_kv_op(&self.workspace_id, key, self.get_connection()?)
+  }
+
+  /// Returns true if the key existed.
+  pub fn delete(&self, key: &str) -> FlowyResult<bool> {
+    delete_workspace_kv_op(&self.workspace_id, key, self.get_connection()?)
+  }
+
+  /// Returns the keys in alphabetical order.
+  pub fn list_keys(&self) -> FlowyResult<Vec<String>> {
+    select_workspace_kv_keys_op(&self.workspace_id, self.get_connection()?)
+  }
+
+  fn get_connection(&self) -> FlowyResult<DBConnection> {
+    let authenticate_user = self
+      .authenticate_user
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().with_context("The user is dropped"))?;
+    let uid = authenticate_user.user_id()?;
+    authenticate_user.get_sqlite_connection(uid)
+  }
+}
@@ -24,11 +24,16 @@ use lib_dispatch::prelude::af_spawn;
 use lib_infra::box_any::BoxAny;
 
 use crate::anon_user::{migration_anon_user_on_sign_up, sync_supabase_user_data_to_cloud};
-use crate::entities::{AuthStateChangedPB, AuthStatePB, UserProfilePB, UserSettingPB};
+use crate::entities::parser::UserPassword;
+use crate::entities::{
+  AuthStateChangedPB, AuthStatePB, FailedMigrationPB, ServerConnectivityPB, UserProfilePB,
+  UserSettingPB, DELETE_ACCOUNT_CONFIRMATION,
+};
 use crate::event_map::{DefaultUserStatusCallback, UserStatusCallback};
 use crate::migrations::document_empty_content::HistoricalEmptyDocumentMigration;
 use crate::migrations::migration::{
-  save_migration_record, UserDataMigration, UserLocalDataMigration,
+  clear_migration_failure, get_all_migration_failures, save_migration_record, UserDataMigration,
+  UserLocalDataMigration, MAX_MIGRATION_ATTEMPTS,
 };
 use crate::migrations::workspace_and_favorite_v1::FavoriteV1AndWorkspaceArrayMigration;
 use crate::migrations::workspace_trash_v1::WorkspaceTrashMapToSectionMigration;
@@ -39,7 +44,10 @@ use crate::services::collab_interact::{CollabInteract, DefaultCollabInteract};
 
 use crate::services::sqlite_sql::user_sql::{select_user_profile, UserTable, UserTableChangeset};
 use crate::user_manager::manager_user_encryption::validate_encryption_sign;
-use crate::user_manager::manager_user_workspace::save_all_user_workspaces;
+use crate::user_manager::manager_user_workspace::{
+  refresh_workspace_feature_flags, save_all_user_workspaces, WorkspaceFeatureFlagsCache,
+};
+use crate::user_manager::manager_user_workspace_member::MemberChangeSenders;
 use crate::user_manager::user_login_state::UserAuthProcess;
 use crate::{errors::FlowyError, notification::*};
 use flowy_user_pub::session::Session;
@@ -58,6 +66,12 @@ pub struct UserManager {
   pub(crate) authenticate_user: Arc<AuthenticateUser>,
   refresh_user_profile_since: AtomicI64,
   pub(crate) is_loading_awareness: Arc<AtomicBool>,
+  pub(crate) workspace_feature_flags: WorkspaceFeatureFlagsCache,
+  pub(crate) member_change_senders: MemberChangeSenders,
+  /// Whether the device has a network connection, as reported by the app.
+  is_network_reachable: AtomicBool,
+  /// Whether the server responds, as reported by the connectivity probe.
+  is_server_reachable: AtomicBool,
 }
 
 impl UserManager {
@@ -84,6 +98,10 @@ impl UserManager {
       refresh_user_profile_since,
       user_workspace_service,
       is_loading_awareness: Arc::new(AtomicBool::new(false)),
+      workspace_feature_flags: Default::default(),
+      member_change_senders: Default::default(),
+      is_network_reachable: AtomicBool::new(true),
+      is_server_reachable: AtomicBool::new(true),
     });
 
     let weak_user_manager = Arc::downgrade(&user_manager);
@@ -239,6 +257,20 @@ impl UserManager {
             }
           });
         }
+
+        if let Some(mut refresh_failed_rx) = self.cloud_services.subscribe_token_refresh_failed() {
+          let user_uid = user.uid;
+          af_spawn(async move {
+            while let Ok(reason) = refresh_failed_rx.recv().await {
+              warn!("Failed to refresh the token in the background: {}", reason);
+              send_notification(
+                &user_uid.to_string(),
+                UserNotification::DidFailToRefreshToken,
+              )
+              .send();
+            }
+          });
+        }
       }
 
       // Do the user data migration if needed
@@ -275,6 +307,15 @@ impl UserManager {
           &self.authenticate_user.user_config.device_id,
         )
         .await?;
+
+      // The app may be launched offline, so don't wait for the flags
+      if let Ok(user_service) = self.cloud_services.get_user_service() {
+        let cache = self.workspace_feature_flags.clone();
+        let workspace_id = session.user_workspace.id.clone();
+        af_spawn(async move {
+          refresh_workspace_feature_flags(user_service, &cache, &workspace_id).await;
+        });
+      }
     }
     Ok(())
   }
@@ -345,6 +386,9 @@ impl UserManager {
         &self.authenticate_user.user_config.device_id,
       )
       .await?;
+    let _ = self
+      .refresh_workspace_feature_flags(&latest_workspace.id)
+      .await;
     send_auth_state_notification(AuthStateChangedPB {
       state: AuthStatePB::AuthStateSignIn,
       message: "Sign in success".to_string(),
@@ -486,6 +530,89 @@ impl UserManager {
     Ok(())
   }
 
+  /// Changes the password of the current user. The user signs in again with `old_password`
+  /// before the password is changed, and the session is refreshed with the new token afterwards.
+  ///
+  /// Returns [ErrorCode::PasswordAuthNotSupported] if the user is not an AppFlowy Cloud user, and
+  /// [ErrorCode::PasswordNotMatch] if `old_password` is wrong or the account has no password,
+  /// e.g. when it signs in with OAuth or a magic link.
+  #[instrument(level = "info", skip_all, err)]
+  pub async fn change_password(
+    &self,
+    old_password: &str,
+    new_password: &str,
+  ) -> Result<(), FlowyError> {
+    let session = self.get_session()?;
+    let profile = self.get_user_profile_from_disk(session.user_id).await?;
+    if !profile.authenticator.is_appflowy_cloud() {
+      return Err(FlowyError::new(
+        ErrorCode::PasswordAuthNotSupported,
+        "Only the AppFlowy Cloud accounts have a password",
+      ));
+    }
+    let new_password = UserPassword::parse(new_password.to_string())?;
+
+    let token = self
+      .cloud_services
+      .get_user_service()?
+      .change_password(&profile.email, old_password, new_password.as_ref())
+      .await?;
+    self.cloud_services.set_token(&token)?;
+    save_user_token(session.user_id, self.db_connection(session.user_id)?, token)?;
+    Ok(())
+  }
+
+  /// Deletes the account of the current user on the server, then removes its data from the
+  /// device, including the databases and the search indexes. `confirmation` must be
+  /// [DELETE_ACCOUNT_CONFIRMATION] so the account can't be deleted by a mistaken call.
+  ///
+  /// The local data is kept if the server fails to delete the account, so the user can try again.
+  ///
+  /// The local cleanup is best-effort. The account is deleted even if some files can't be removed,
+  /// e.g. when they're locked by another process.
+  #[instrument(level = "info", skip(self, confirmation), err)]
+  pub async fn delete_account(&self, uid: i64, confirmation: &str) -> FlowyResult<()> {
+    let session = self.get_session()?;
+    if session.user_id != uid || confirmation != DELETE_ACCOUNT_CONFIRMATION {
+      return Err(
+        FlowyError::invalid_data().with_context("The deletion of the account isn't confirmed"),
+      );
+    }
+
+    self
+      .cloud_services
+      .get_user_service()?
+      .delete_account()
+      .await?;
+
+    info!(
+      "The account of user {} is deleted, remove the local data",
+      uid
+    );
+    if let Err(err) = self.authenticate_user.database.close(uid) {
+      error!(
+        "Failed to close the databases of the deleted user: {:?}",
+        err
+      );
+    }
+    if let Err(err) = self.authenticate_user.set_session(None) {
+      error!("Failed to clear the session of the deleted user: {:?}", err);
+    }
+    let user_dir = self.user_dir(uid);
+    if let Err(err) = std::fs::remove_dir_all(&user_dir) {
+      error!(
+        "Failed to remove the data directory {} of the deleted user: {}",
+        user_dir, err
+      );
+    }
+
+    send_auth_state_notification(AuthStateChangedPB {
+      state: AuthStatePB::AuthStateSignOut,
+      message: "The account is deleted".to_string(),
+    });
+    Ok(())
+  }
+
   /// Updates the user's profile with the given parameters.
   ///
   /// This function modifies the user's profile based on the provided update parameters. After updating, it
@@ -612,8 +739,27 @@ impl UserManager {
     Ok(self.get_session()?.user_id)
   }
 
+  /// Returns the data migrations of the current user that are skipped because they failed too
+  /// many times.
+  pub fn get_failed_migrations(&self) -> FlowyResult<Vec<FailedMigrationPB>> {
+    let mut conn = self.db_connection(self.user_id()?)?;
+    let failed_migrations = get_all_migration_failures(&mut conn)?
+      .into_iter()
+      .filter(|failure| failure.attempts >= MAX_MIGRATION_ATTEMPTS)
+      .map(FailedMigrationPB::from)
+      .collect();
+    Ok(failed_migrations)
+  }
+
+  /// Clears the failures of the data migration, so it's run again on the next launch, e.g. after
+  /// the cause of the failures is fixed. Returns true if the migration had failed.
+  pub fn clear_failed_migration(&self, migration_name: &str) -> FlowyResult<bool> {
+    let mut conn = self.db_connection(self.user_id()?)?;
+    clear_migration_failure(&mut conn, migration_name)
+  }
+
   pub fn workspace_id(&self) -> Result<String, FlowyError> {
-    Ok(self.get_session()?.user_workspace.id)
+    self.authenticate_user.workspace_id()
   }
 
   pub fn token(&self) -> Result<Option<String>, FlowyError> {
@@ -658,6 +804,40 @@ impl UserManager {
     }
   }
 
+  /// Called when the device gains or loses its network connection.
+  pub async fn did_update_network_reachability(&self, reachable: bool) {
+    self.is_network_reachable.store(reachable, Ordering::SeqCst);
+    self.update_reachability().await;
+  }
+
+  /// Called when the server becomes reachable or unreachable. The services switch to their local
+  /// behavior while the server can't be reached, as they do when the device loses the network, and
+  /// the UI is notified so it can show that the app is offline.
+  pub async fn did_update_server_connectivity(&self, is_online: bool) {
+    info!("Server is {}", if is_online { "online" } else { "offline" });
+    self.is_server_reachable.store(is_online, Ordering::SeqCst);
+    self.update_reachability().await;
+    send_notification(
+      SERVER_CONNECTIVITY_NOTIFICATION_ID,
+      UserNotification::DidUpdateServerConnectivity,
+    )
+    .payload(ServerConnectivityPB { is_online })
+    .send();
+  }
+
+  /// The services use the server only while both the network and the server are reachable, so
+  /// the probe finding the server doesn't override a lost network connection and vice versa.
+  async fn update_reachability(&self) {
+    let reachable = self.is_network_reachable.load(Ordering::SeqCst)
+      && self.is_server_reachable.load(Ordering::SeqCst);
+    self.cloud_services.set_network_reachable(reachable);
+    self
+      .user_status_callback
+      .read()
+      .await
+      .did_update_network(reachable);
+  }
+
   pub(crate) async fn generate_sign_in_url_with_email(
     &self,
     authenticator: &Authenticator,
@@ -711,6 +891,7 @@ impl UserManager {
     }
 
     save_all_user_workspaces(uid, self.db_connection(uid)?, response.user_workspaces())?;
+    self.save_workspace_auth_type(uid)?;
     info!(
       "Save new user profile to disk, authenticator: {:?}",
       authenticator
@@ -733,11 +914,24 @@ impl UserManager {
       }
 
       // Save the user profile change
+      let uid = user_update.uid;
       upsert_user_profile_change(
-        user_update.uid,
-        self.db_connection(user_update.uid)?,
+        uid,
+        self.db_connection(uid)?,
         UserTableChangeset::from(user_update),
       )?;
+
+      // The user may have been removed from a workspace or the workspace may have been deleted
+      // by another admin. Drop those workspaces from the local cache before the app keeps using
+      // a dead workspace.
+      if let Err(err) = self.refresh_user_workspaces(uid).await {
+        error!("Refresh user workspaces failed: {:?}", err);
+      }
+
+      // The plan or the settings of the workspace may have changed
+      let _ = self
+        .refresh_workspace_feature_flags(&session.user_workspace.id)
+        .await;
     }
 
     Ok(())
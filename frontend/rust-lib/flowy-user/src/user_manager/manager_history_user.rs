@@ -1,9 +1,11 @@
 use tracing::instrument;
 
-use crate::entities::UserProfilePB;
+use crate::entities::{AuthStateChangedPB, AuthStatePB, UserProfilePB};
+use crate::notification::send_auth_state_notification;
 use crate::user_manager::UserManager;
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
-use flowy_user_pub::entities::Authenticator;
+use flowy_user_pub::entities::{Authenticator, SignUpParams, UserProfile};
+use lib_infra::box_any::BoxAny;
 
 use crate::migrations::AnonUser;
 use flowy_user_pub::session::Session;
@@ -55,6 +57,58 @@ impl UserManager {
     Ok(UserProfilePB::from(profile))
   }
 
+  /// Starts a session of the anonymous user, whose data is only stored on the device. The existing
+  /// anonymous user is reopened if there is one, so repeated calls never create another user.
+  /// Otherwise, a new anonymous user is signed up with a local workspace.
+  #[instrument(level = "info", skip(self), err)]
+  pub async fn sign_in_anonymous(&self) -> FlowyResult<UserProfile> {
+    let authenticator = Authenticator::Local;
+    let device_id = self.authenticate_user.user_config.device_id.clone();
+    let anon_user = match self.store_preferences.get_object::<Session>(ANON_USER) {
+      None => None,
+      Some(anon_session) => self
+        .get_user_profile_from_disk(anon_session.user_id)
+        .await
+        .ok()
+        .map(|user_profile| (anon_session, user_profile)),
+    };
+    let (anon_session, user_profile) = match anon_user {
+      Some(anon_user) => anon_user,
+      None => {
+        let params = SignUpParams {
+          email: "".to_string(),
+          name: "".to_string(),
+          password: "".to_string(),
+          auth_type: authenticator.clone(),
+          device_id,
+        };
+        return self.sign_up(authenticator, BoxAny::new(params)).await;
+      },
+    };
+
+    let current_uid = self.get_session().ok().map(|session| session.user_id);
+    if current_uid == Some(anon_session.user_id) {
+      return Ok(user_profile);
+    }
+
+    self.cloud_services.set_user_authenticator(&authenticator);
+    self.prepare_user(&anon_session).await;
+    self
+      .authenticate_user
+      .set_session(Some(anon_session.clone()))?;
+    self
+      .user_status_callback
+      .read()
+      .await
+      .did_sign_in(user_profile.uid, &anon_session.user_workspace, &device_id)
+      .await?;
+    send_auth_state_notification(AuthStateChangedPB {
+      state: AuthStatePB::AuthStateSignIn,
+      message: "Sign in as the anonymous user".to_string(),
+    });
+    Ok(user_profile)
+  }
+
   /// Opens a historical user's session based on their user ID, device ID, and authentication type.
   ///
   /// This function facilitates the re-opening of a user's session from historical tracking.
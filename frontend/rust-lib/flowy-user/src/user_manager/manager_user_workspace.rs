@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -7,22 +8,29 @@ use tracing::{error, info, instrument, warn};
 
 use flowy_error::{ErrorCode, FlowyError, FlowyResult};
 use flowy_folder_pub::entities::{AppFlowyData, ImportData};
-use flowy_sqlite::schema::user_workspace_table;
+use flowy_sqlite::schema::{user_workspace_table, workspace_kv_table};
 use flowy_sqlite::{query_dsl::*, DBConnection, ExpressionMethods};
+use flowy_user_pub::cloud::UserCloudService;
 use flowy_user_pub::entities::{
-  Role, UserWorkspace, WorkspaceInvitation, WorkspaceInvitationStatus, WorkspaceMember,
+  FeatureFlags, Role, UserWorkspace, WorkspaceInvitation, WorkspaceInvitationStatus,
+  WorkspaceMember,
 };
 use lib_dispatch::prelude::af_spawn;
 
-use crate::entities::{RepeatedUserWorkspacePB, ResetWorkspacePB, UserWorkspacePB};
+use crate::entities::{
+  RemovedUserWorkspacePB, RepeatedUserWorkspacePB, ResetWorkspacePB, UserWorkspacePB,
+  WorkspaceFeatureFlagsPB,
+};
 use crate::migrations::AnonUser;
 use crate::notification::{send_notification, UserNotification};
 use crate::services::data_import::{
   generate_import_data, upload_collab_objects_data, ImportedFolder, ImportedSource,
 };
 use crate::services::sqlite_sql::workspace_sql::{
-  get_all_user_workspace_op, get_user_workspace_op, insert_new_workspaces_op, UserWorkspaceTable,
+  get_all_user_workspace_op, get_user_workspace_op, insert_new_workspaces_op,
+  update_user_workspace_auth_type_op, update_user_workspace_order_op, UserWorkspaceTable,
 };
+use crate::services::workspace_kv_store::WorkspaceKvStore;
 use crate::user_manager::UserManager;
 use flowy_user_pub::session::Session;
 
@@ -121,10 +129,14 @@ impl UserManager {
         )
         .await
         {
-          Ok(_) => info!(
+          Ok(report) if report.is_complete() => info!(
             "Successfully uploaded collab objects data for user:{}",
             user_id
           ),
+          Ok(report) => error!(
+            "Failed to upload some collab objects, skipped: {:?}, rejected: {:?} for user:{}",
+            report.skipped_object_ids, report.rejected_object_ids, user_id
+          ),
           Err(err) => {
             error!(
               "Failed to upload collab objects data: {:?} for user:{}",
@@ -156,6 +168,7 @@ impl UserManager {
   #[instrument(skip(self), err)]
   pub async fn open_workspace(&self, workspace_id: &str) -> FlowyResult<()> {
     info!("open workspace: {}", workspace_id);
+    self.validate_workspace_auth_type(workspace_id)?;
     let user_workspace = self
       .cloud_services
       .get_user_service()?
@@ -184,6 +197,14 @@ impl UserManager {
       error!("Open workspace failed: {:?}", err);
     }
 
+    // Opening the workspace doesn't depend on the feature flags, and the cached ones are used
+    // until they can be fetched again
+    if let Err(err) = self.refresh_workspace_feature_flags(workspace_id).await {
+      warn!(
+        "Failed to refresh the feature flags of workspace {}: {}",
+        workspace_id, err
+      );
+    }
     Ok(())
   }
 
@@ -204,25 +225,125 @@ impl UserManager {
     let uid = self.user_id()?;
     let mut conn = self.db_connection(uid)?;
     insert_new_workspaces_op(uid, &[new_workspace.clone()], &mut conn)?;
+    self.save_workspace_auth_type(uid)?;
     Ok(new_workspace)
   }
 
+  /// Creates a workspace whose name doesn't collide with the user's other workspaces. A duplicate
+  /// name gets a numeric suffix, e.g. "Personal (2)". The returned workspace has the final name.
+  ///
+  /// Another client may create a workspace with the same name at the same time. The name is
+  /// checked again after the workspace is created and, if it's taken, the new workspace is renamed
+  /// once with the next free suffix.
+  #[instrument(level = "info", skip(self), err)]
+  pub async fn create_workspace_unique(&self, workspace_name: &str) -> FlowyResult<UserWorkspace> {
+    let uid = self.user_id()?;
+    let existing_workspaces = self.fetch_all_workspaces(uid).await?;
+    let name = unique_workspace_name(
+      workspace_name,
+      existing_workspaces
+        .iter()
+        .map(|workspace| workspace.name.as_str()),
+    );
+    let mut new_workspace = self.add_workspace(&name).await?;
+
+    let workspaces = self.fetch_all_workspaces(uid).await?;
+    let other_names = workspaces
+      .iter()
+      .filter(|workspace| workspace.id != new_workspace.id)
+      .map(|workspace| workspace.name.as_str())
+      .collect::<Vec<_>>();
+    if other_names.contains(&new_workspace.name.as_str()) {
+      let name = unique_workspace_name(workspace_name, other_names);
+      info!(
+        "workspace name {} was taken concurrently, rename workspace {} to {}",
+        new_workspace.name, new_workspace.id, name
+      );
+      self
+        .patch_workspace(&new_workspace.id, Some(&name), None, true)
+        .await?;
+      new_workspace.name = name;
+    }
+    Ok(new_workspace)
+  }
+
+  /// Returns the workspaces from the server, or the local ones if the server can't be reached.
+  async fn fetch_all_workspaces(&self, uid: i64) -> FlowyResult<Vec<UserWorkspace>> {
+    let remote_workspaces = self
+      .cloud_services
+      .get_user_service()?
+      .get_all_workspace(uid)
+      .await;
+    match remote_workspaces {
+      Ok(workspaces) => Ok(workspaces),
+      Err(err) => {
+        warn!("Failed to fetch workspaces from the server: {}", err);
+        get_all_user_workspace_op(uid, self.db_connection(uid)?)
+      },
+    }
+  }
+
+  /// Updates the name and/or the icon of the workspace. Unless `strict` is true, the local
+  /// workspace is updated and notified before the server confirms the change, and it's reverted
+  /// and notified again if the server rejects it.
   pub async fn patch_workspace(
     &self,
     workspace_id: &str,
     new_workspace_name: Option<&str>,
     new_workspace_icon: Option<&str>,
+    strict: bool,
   ) -> FlowyResult<()> {
-    self
-      .cloud_services
-      .get_user_service()?
+    // The uid is captured before the request, so the change is applied to, or reverted from, the
+    // workspace of this user even if the user switches the workspace while it's in flight.
+    let uid = self.user_id()?;
+    let user_service = self.cloud_services.get_user_service()?;
+    if strict {
+      user_service
+        .patch_workspace(workspace_id, new_workspace_name, new_workspace_icon)
+        .await?;
+      self.patch_local_workspace(uid, workspace_id, new_workspace_name, new_workspace_icon)?;
+      return Ok(());
+    }
+
+    let old_workspace =
+      self.patch_local_workspace(uid, workspace_id, new_workspace_name, new_workspace_icon)?;
+    let result = user_service
       .patch_workspace(workspace_id, new_workspace_name, new_workspace_icon)
-      .await?;
+      .await;
+    if let Err(err) = result {
+      warn!(
+        "Failed to patch workspace {}, revert the local change: {}",
+        workspace_id, err
+      );
+      // Only the values that weren't changed again while the request was in flight are reverted
+      if let Some(current_workspace) = self.get_user_workspace(uid, workspace_id) {
+        let old_name = new_workspace_name
+          .filter(|name| current_workspace.name == *name)
+          .map(|_| old_workspace.name.as_str());
+        let old_icon = new_workspace_icon
+          .filter(|icon| current_workspace.icon == *icon)
+          .map(|_| old_workspace.icon.as_str());
+        if old_name.is_some() || old_icon.is_some() {
+          if let Err(err) = self.patch_local_workspace(uid, workspace_id, old_name, old_icon) {
+            error!("Failed to revert workspace {}: {}", workspace_id, err);
+          }
+        }
+      }
+      return Err(err);
+    }
+    Ok(())
+  }
 
-    // save the icon and name to sqlite db
-    let uid = self.user_id()?;
-    let conn = self.db_connection(uid)?;
-    let mut user_workspace = match self.get_user_workspace(uid, workspace_id) {
+  /// Saves the name and the icon of the workspace to the local db, and notifies the change.
+  /// Returns the workspace before the change.
+  fn patch_local_workspace(
+    &self,
+    uid: i64,
+    workspace_id: &str,
+    new_workspace_name: Option<&str>,
+    new_workspace_icon: Option<&str>,
+  ) -> FlowyResult<UserWorkspace> {
+    let old_workspace = match self.get_user_workspace(uid, workspace_id) {
       Some(user_workspace) => user_workspace,
       None => {
         return Err(FlowyError::record_not_found().with_context(format!(
@@ -232,6 +353,7 @@ impl UserManager {
       },
     };
 
+    let mut user_workspace = old_workspace.clone();
     if let Some(new_workspace_name) = new_workspace_name {
       user_workspace.name = new_workspace_name.to_string();
     }
@@ -239,14 +361,20 @@ impl UserManager {
       user_workspace.icon = new_workspace_icon.to_string();
     }
 
+    let conn = self.db_connection(uid)?;
     let _ = save_user_workspace(uid, conn, &user_workspace);
 
-    let payload: UserWorkspacePB = user_workspace.clone().into();
+    let payload: UserWorkspacePB = user_workspace.into();
     send_notification(&uid.to_string(), UserNotification::DidUpdateUserWorkspace)
       .payload(payload)
       .send();
 
-    Ok(())
+    Ok(old_workspace)
+  }
+
+  /// Returns the key/value store of the workspace. See [WorkspaceKvStore].
+  pub fn workspace_kv_store(&self, workspace_id: &str) -> WorkspaceKvStore {
+    WorkspaceKvStore::new(workspace_id, Arc::downgrade(&self.authenticate_user))
   }
 
   #[instrument(level = "info", skip(self), err)]
@@ -359,36 +487,155 @@ impl UserManager {
     self
       .cloud_services
       .get_user_service()?
-      .update_workspace_member(user_email, workspace_id, role)
+      .update_workspace_member(user_email, workspace_id.clone(), role)
       .await?;
+
+    if let Err(err) = self
+      .user_status_callback
+      .read()
+      .await
+      .did_update_workspace_role(&workspace_id)
+      .await
+    {
+      error!("Update workspace role failed: {:?}", err);
+    }
     Ok(())
   }
 
+  /// Returns the features available in the workspace. The flags are cached until they're
+  /// refreshed by [Self::refresh_workspace_feature_flags].
+  pub async fn get_workspace_feature_flags(&self, workspace_id: &str) -> FlowyResult<FeatureFlags> {
+    if let Some(flags) = self.workspace_feature_flags.lock().get(workspace_id) {
+      return Ok(flags.clone());
+    }
+    self.refresh_workspace_feature_flags(workspace_id).await
+  }
+
+  /// Fetches the features available in the workspace again, e.g. after its subscription plan or
+  /// settings changed.
+  pub async fn refresh_workspace_feature_flags(
+    &self,
+    workspace_id: &str,
+  ) -> FlowyResult<FeatureFlags> {
+    let user_service = self.cloud_services.get_user_service()?;
+    let flags =
+      refresh_workspace_feature_flags(user_service, &self.workspace_feature_flags, workspace_id)
+        .await;
+    Ok(flags)
+  }
+
   pub fn get_user_workspace(&self, uid: i64, workspace_id: &str) -> Option<UserWorkspace> {
     let conn = self.db_connection(uid).ok()?;
     get_user_workspace_op(workspace_id, conn)
   }
 
+  /// Returns the workspaces cached locally. Call [Self::refresh_user_workspaces] to fetch the
+  /// latest ones from the server.
   pub async fn get_all_user_workspaces(&self, uid: i64) -> FlowyResult<Vec<UserWorkspace>> {
     let conn = self.db_connection(uid)?;
     let workspaces = get_all_user_workspace_op(uid, conn)?;
+    Ok(workspaces)
+  }
 
-    if let Ok(service) = self.cloud_services.get_user_service() {
-      if let Ok(pool) = self.db_pool(uid) {
-        af_spawn(async move {
-          if let Ok(new_user_workspaces) = service.get_all_workspace(uid).await {
-            if let Ok(conn) = pool.get() {
-              let _ = save_all_user_workspaces(uid, conn, &new_user_workspaces);
-              let repeated_workspace_pbs = RepeatedUserWorkspacePB::from(new_user_workspaces);
-              send_notification(&uid.to_string(), UserNotification::DidUpdateUserWorkspaces)
-                .payload(repeated_workspace_pbs)
-                .send();
-            }
-          }
-        });
-      }
+  /// Fetches the workspaces of the user from the server, saves them locally and sends them with
+  /// [UserNotification::DidUpdateUserWorkspaces]. The workspaces that no longer exist on the
+  /// server are removed first, see [Self::remove_workspaces_missing_on_server].
+  #[instrument(level = "info", skip(self), err)]
+  pub(crate) async fn refresh_user_workspaces(&self, uid: i64) -> FlowyResult<()> {
+    let new_user_workspaces = self
+      .cloud_services
+      .get_user_service()?
+      .get_all_workspace(uid)
+      .await?;
+    // The local server doesn't keep a remote workspace list.
+    if !self.cloud_services.get_user_authenticator().is_local() {
+      self
+        .remove_workspaces_missing_on_server(uid, &new_user_workspaces)
+        .await?;
     }
-    Ok(workspaces)
+
+    let auth_type = self.current_workspace_auth_type();
+    save_all_user_workspaces(uid, self.db_connection(uid)?, &new_user_workspaces)?;
+    update_user_workspace_auth_type_op(uid, &auth_type, self.db_connection(uid)?)?;
+    // Return the workspaces in the order saved by the user. The new workspaces that haven't been
+    // ordered yet are appended at the end.
+    let workspaces = get_all_user_workspace_op(uid, self.db_connection(uid)?)?;
+    send_notification(&uid.to_string(), UserNotification::DidUpdateUserWorkspaces)
+      .payload(RepeatedUserWorkspacePB::from(workspaces))
+      .send();
+    Ok(())
+  }
+
+  /// Saves the order of the workspaces in the user's workspace list. The workspaces that are not
+  /// in [ordered_ids] keep their relative order and are placed after the ordered ones.
+  #[instrument(level = "info", skip(self), err)]
+  pub async fn reorder_workspaces(&self, ordered_ids: Vec<String>) -> FlowyResult<()> {
+    let uid = self.user_id()?;
+    update_user_workspace_order_op(uid, &ordered_ids, self.db_connection(uid)?)?;
+
+    let workspaces = get_all_user_workspace_op(uid, self.db_connection(uid)?)?;
+    send_notification(&uid.to_string(), UserNotification::DidUpdateUserWorkspaces)
+      .payload(RepeatedUserWorkspacePB::from(workspaces))
+      .send();
+    Ok(())
+  }
+
+  /// Removes the workspaces that no longer exist on the server from the local cache. A workspace
+  /// disappears when another admin deletes it or when the user is removed from it.
+  ///
+  /// If the current workspace was removed, the user is switched to the first remaining workspace.
+  /// When no workspace is left, the session is left without a current workspace and the
+  /// [UserNotification::DidRemoveUserWorkspace] notification is sent with an empty fallback
+  /// workspace id, so the frontend is expected to create a new one.
+  #[instrument(level = "info", skip(self, remote_workspaces), err)]
+  async fn remove_workspaces_missing_on_server(
+    &self,
+    uid: i64,
+    remote_workspaces: &[UserWorkspace],
+  ) -> FlowyResult<()> {
+    let local_workspaces = get_all_user_workspace_op(uid, self.db_connection(uid)?)?;
+    let removed_workspaces = find_removed_workspaces(&local_workspaces, remote_workspaces);
+    if removed_workspaces.is_empty() {
+      return Ok(());
+    }
+
+    // The session may have no workspace already, if its last workspace was removed before
+    let current_workspace_id = self.get_session()?.user_workspace.id;
+    let change = current_workspace_change(
+      &current_workspace_id,
+      &removed_workspaces,
+      remote_workspaces,
+    );
+    for removed_workspace in removed_workspaces {
+      info!(
+        "workspace:{} was removed on the server, remove it from local",
+        removed_workspace.id
+      );
+      self
+        .authenticate_user
+        .remove_user_workspace(uid, &removed_workspace.id)?;
+      notify_workspace_removed(
+        uid,
+        &current_workspace_id,
+        removed_workspace,
+        remote_workspaces,
+      );
+    }
+
+    match change {
+      CurrentWorkspaceChange::Unchanged => {},
+      CurrentWorkspaceChange::Open(fallback_workspace) => {
+        self.open_workspace(&fallback_workspace.id).await?
+      },
+      CurrentWorkspaceChange::Clear => {
+        warn!(
+          "The current workspace:{} was removed and it was the last workspace",
+          current_workspace_id
+        );
+        self.authenticate_user.clear_user_workspace()?;
+      },
+    }
+    Ok(())
   }
 
   /// Reset the remote workspace using local workspace data. This is useful when a user wishes to
@@ -410,6 +657,49 @@ impl UserManager {
   }
 }
 
+pub(crate) type WorkspaceFeatureFlagsCache = Arc<parking_lot::Mutex<HashMap<String, FeatureFlags>>>;
+
+/// Fetches the feature flags of the workspace and caches them. Sends
+/// [UserNotification::DidUpdateWorkspaceFeatureFlags] if they're different from the cached ones.
+///
+/// The cached flags are kept if they can't be fetched, e.g. when offline. The conservative flags
+/// are returned if there are no cached flags, and they're not cached so they're fetched again
+/// next time.
+pub(crate) async fn refresh_workspace_feature_flags(
+  user_service: Arc<dyn UserCloudService>,
+  cache: &WorkspaceFeatureFlagsCache,
+  workspace_id: &str,
+) -> FeatureFlags {
+  match user_service.get_workspace_feature_flags(workspace_id).await {
+    Ok(flags) => {
+      let old_flags = cache.lock().insert(workspace_id.to_string(), flags.clone());
+      if old_flags.as_ref() != Some(&flags) {
+        send_notification(
+          workspace_id,
+          UserNotification::DidUpdateWorkspaceFeatureFlags,
+        )
+        .payload(WorkspaceFeatureFlagsPB::new(
+          workspace_id.to_string(),
+          flags.clone(),
+        ))
+        .send();
+      }
+      flags
+    },
+    Err(err) => {
+      warn!(
+        "Failed to get the feature flags of workspace {}: {}",
+        workspace_id, err
+      );
+      cache
+        .lock()
+        .get(workspace_id)
+        .cloned()
+        .unwrap_or_else(FeatureFlags::conservative)
+    },
+  }
+}
+
 /// This method is used to save one user workspace to the SQLite database
 ///
 /// If the workspace is already persisted in the database, it will be overridden.
@@ -505,11 +795,95 @@ pub fn save_all_user_workspaces(
   })
 }
 
+/// Returns [name] if no workspace uses it. Otherwise, returns [name] with the smallest free suffix
+/// starting from 2, e.g. "Personal (2)".
+fn unique_workspace_name<'a>(
+  name: &str,
+  existing_names: impl IntoIterator<Item = &'a str>,
+) -> String {
+  let existing_names = existing_names.into_iter().collect::<HashSet<_>>();
+  if !existing_names.contains(name) {
+    return name.to_string();
+  }
+  (2..)
+    .map(|index| format!("{} ({})", name, index))
+    .find(|candidate| !existing_names.contains(candidate.as_str()))
+    .unwrap_or_else(|| name.to_string())
+}
+
+/// Returns the workspaces in [local_workspaces] that are not in [remote_workspaces].
+fn find_removed_workspaces<'a>(
+  local_workspaces: &'a [UserWorkspace],
+  remote_workspaces: &[UserWorkspace],
+) -> Vec<&'a UserWorkspace> {
+  local_workspaces
+    .iter()
+    .filter(|local| !remote_workspaces.iter().any(|remote| remote.id == local.id))
+    .collect()
+}
+
+/// What happens to the current workspace after the workspaces missing on the server are removed.
+#[derive(Debug, PartialEq)]
+enum CurrentWorkspaceChange<'a> {
+  Unchanged,
+  /// The current workspace was removed, so the user is switched to this one.
+  Open(&'a UserWorkspace),
+  /// The current workspace was the last workspace, so the session is left without one.
+  Clear,
+}
+
+fn current_workspace_change<'a>(
+  current_workspace_id: &str,
+  removed_workspaces: &[&UserWorkspace],
+  remote_workspaces: &'a [UserWorkspace],
+) -> CurrentWorkspaceChange<'a> {
+  if !removed_workspaces
+    .iter()
+    .any(|workspace| workspace.id == current_workspace_id)
+  {
+    return CurrentWorkspaceChange::Unchanged;
+  }
+  match remote_workspaces.first() {
+    Some(fallback_workspace) => CurrentWorkspaceChange::Open(fallback_workspace),
+    None => CurrentWorkspaceChange::Clear,
+  }
+}
+
+fn notify_workspace_removed(
+  uid: i64,
+  current_workspace_id: &str,
+  removed_workspace: &UserWorkspace,
+  remaining_workspaces: &[UserWorkspace],
+) {
+  let is_current_workspace = removed_workspace.id == current_workspace_id;
+  let fallback_workspace_id = if is_current_workspace {
+    remaining_workspaces
+      .first()
+      .map(|workspace| workspace.id.clone())
+      .unwrap_or_default()
+  } else {
+    current_workspace_id.to_string()
+  };
+
+  send_notification(&uid.to_string(), UserNotification::DidRemoveUserWorkspace)
+    .payload(RemovedUserWorkspacePB {
+      workspace_id: removed_workspace.id.clone(),
+      name: removed_workspace.name.clone(),
+      is_current_workspace,
+      fallback_workspace_id,
+    })
+    .send();
+}
+
 pub fn delete_user_workspaces(mut conn: DBConnection, workspace_id: &str) -> FlowyResult<()> {
   let n = conn.immediate_transaction(|conn| {
     let rows_affected: usize =
       diesel::delete(user_workspace_table::table.filter(user_workspace_table::id.eq(workspace_id)))
         .execute(conn)?;
+    diesel::delete(
+      workspace_kv_table::table.filter(workspace_kv_table::workspace_id.eq(workspace_id)),
+    )
+    .execute(conn)?;
     Ok::<usize, FlowyError>(rows_affected)
   })?;
   if n != 1 {
@@ -517,3 +891,66 @@ pub fn delete_user_workspaces(mut conn: DBConnection, workspace_id: &str) -> Flo
   }
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use flowy_user_pub::entities::UserWorkspace;
+
+  use super::{
+    current_workspace_change, find_removed_workspaces, unique_workspace_name,
+    CurrentWorkspaceChange,
+  };
+
+  #[test]
+  fn unique_workspace_name_test() {
+    assert_eq!(unique_workspace_name("Personal", []), "Personal");
+    assert_eq!(unique_workspace_name("Personal", ["Work"]), "Personal");
+    assert_eq!(
+      unique_workspace_name("Personal", ["Personal", "Work"]),
+      "Personal (2)"
+    );
+    assert_eq!(
+      unique_workspace_name("Personal", ["Personal", "Personal (2)", "Personal (4)"]),
+      "Personal (3)"
+    );
+  }
+
+  #[test]
+  fn current_workspace_change_test() {
+    let w1 = UserWorkspace::new("w1", 1);
+    let w2 = UserWorkspace::new("w2", 1);
+    let local_workspaces = vec![w1.clone(), w2.clone()];
+
+    // Another workspace is removed
+    let remote_workspaces = vec![w1.clone()];
+    let removed = find_removed_workspaces(&local_workspaces, &remote_workspaces);
+    assert_eq!(
+      current_workspace_change("w1", &removed, &remote_workspaces),
+      CurrentWorkspaceChange::Unchanged
+    );
+
+    // The current workspace is removed
+    let remote_workspaces = vec![w2.clone()];
+    let removed = find_removed_workspaces(&local_workspaces, &remote_workspaces);
+    assert!(matches!(
+      current_workspace_change("w1", &removed, &remote_workspaces),
+      CurrentWorkspaceChange::Open(workspace) if workspace.id == "w2"
+    ));
+  }
+
+  #[test]
+  fn last_workspace_removed_test() {
+    let local_workspaces = vec![UserWorkspace::new("w1", 1)];
+    let removed = find_removed_workspaces(&local_workspaces, &[]);
+    assert_eq!(
+      current_workspace_change("w1", &removed, &[]),
+      CurrentWorkspaceChange::Clear
+    );
+
+    // Nothing changes once the session has no workspace
+    assert_eq!(
+      current_workspace_change("", &removed, &[]),
+      CurrentWorkspaceChange::Unchanged
+    );
+  }
+}
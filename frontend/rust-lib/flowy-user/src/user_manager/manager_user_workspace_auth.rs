@@ -0,0 +1,117 @@
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+
+use crate::services::sqlite_sql::workspace_sql::{
+  get_user_workspace_auth_type_op, update_user_workspace_auth_type_op, WorkspaceAuthType,
+};
+use crate::user_manager::UserManager;
+
+impl UserManager {
+  /// Checks that the workspace can be opened with the current session. A workspace saved by a
+  /// local user can't be opened with a cloud session and vice versa, and a cloud workspace can
+  /// only be opened with the server that it was saved with.
+  ///
+  /// Returns [ErrorCode::WorkspaceAuthTypeMismatch] or [ErrorCode::WorkspaceServerMismatch] if
+  /// they conflict, so the app can ask the user to switch the account or the server before
+  /// syncing. The workspaces whose type isn't recorded yet are accepted.
+  pub fn validate_workspace_auth_type(&self, workspace_id: &str) -> FlowyResult<()> {
+    let uid = self.user_id()?;
+    match get_user_workspace_auth_type_op(workspace_id, self.db_connection(uid)?) {
+      Some(workspace_auth_type) => check_workspace_auth_type(
+        workspace_id,
+        &workspace_auth_type,
+        &self.current_workspace_auth_type(),
+      ),
+      None => Ok(()),
+    }
+  }
+
+  pub(crate) fn current_workspace_auth_type(&self) -> WorkspaceAuthType {
+    let authenticator = self.cloud_services.get_user_authenticator();
+    let service_url = if authenticator.is_local() {
+      "".to_string()
+    } else {
+      self.cloud_services.service_url()
+    };
+    WorkspaceAuthType {
+      authenticator,
+      service_url,
+    }
+  }
+
+  /// Records the type of the current session on the saved workspaces that don't have one yet.
+  pub(crate) fn save_workspace_auth_type(&self, uid: i64) -> FlowyResult<()> {
+    let auth_type = self.current_workspace_auth_type();
+    update_user_workspace_auth_type_op(uid, &auth_type, self.db_connection(uid)?)
+  }
+}
+
+fn check_workspace_auth_type(
+  workspace_id: &str,
+  workspace_auth_type: &WorkspaceAuthType,
+  current_auth_type: &WorkspaceAuthType,
+) -> FlowyResult<()> {
+  if workspace_auth_type.authenticator != current_auth_type.authenticator {
+    return Err(FlowyError::new(
+      ErrorCode::WorkspaceAuthTypeMismatch,
+      format!(
+        "The workspace:{} is saved by {:?} user, but the current user is {:?}",
+        workspace_id, workspace_auth_type.authenticator, current_auth_type.authenticator
+      ),
+    ));
+  }
+
+  // The server of a workspace is unknown if the url isn't configured when it's saved
+  let workspace_url = workspace_auth_type.service_url.trim_end_matches('/');
+  let current_url = current_auth_type.service_url.trim_end_matches('/');
+  if !workspace_url.is_empty() && !current_url.is_empty() && workspace_url != current_url {
+    return Err(FlowyError::new(
+      ErrorCode::WorkspaceServerMismatch,
+      format!(
+        "The workspace:{} is saved on {}, but the current server is {}",
+        workspace_id, workspace_url, current_url
+      ),
+    ));
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use flowy_error::ErrorCode;
+  use flowy_user_pub::entities::Authenticator;
+
+  use super::check_workspace_auth_type;
+  use crate::services::sqlite_sql::workspace_sql::WorkspaceAuthType;
+
+  fn auth_type(authenticator: Authenticator, service_url: &str) -> WorkspaceAuthType {
+    WorkspaceAuthType {
+      authenticator,
+      service_url: service_url.to_string(),
+    }
+  }
+
+  #[test]
+  fn local_workspace_with_cloud_session_test() {
+    let local = auth_type(Authenticator::Local, "");
+    let cloud = auth_type(Authenticator::AppFlowyCloud, "https://a.com");
+    let err = check_workspace_auth_type("w1", &local, &cloud).unwrap_err();
+    assert_eq!(err.code, ErrorCode::WorkspaceAuthTypeMismatch);
+    let err = check_workspace_auth_type("w1", &cloud, &local).unwrap_err();
+    assert_eq!(err.code, ErrorCode::WorkspaceAuthTypeMismatch);
+    assert!(check_workspace_auth_type("w1", &local, &local).is_ok());
+  }
+
+  #[test]
+  fn cloud_workspace_with_different_server_test() {
+    let workspace = auth_type(Authenticator::AppFlowyCloud, "https://a.com/");
+    let same_server = auth_type(Authenticator::AppFlowyCloud, "https://a.com");
+    let other_server = auth_type(Authenticator::AppFlowyCloud, "https://b.com");
+    assert!(check_workspace_auth_type("w1", &workspace, &same_server).is_ok());
+    let err = check_workspace_auth_type("w1", &workspace, &other_server).unwrap_err();
+    assert_eq!(err.code, ErrorCode::WorkspaceServerMismatch);
+
+    let supabase = auth_type(Authenticator::Supabase, "https://a.com");
+    let err = check_workspace_auth_type("w1", &workspace, &supabase).unwrap_err();
+    assert_eq!(err.code, ErrorCode::WorkspaceAuthTypeMismatch);
+  }
+}
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use futures::stream::{self, StreamExt};
+use tokio::sync::broadcast;
+use tracing::{error, trace, warn};
+
+use flowy_error::{ErrorCode, FlowyError, FlowyResult};
+use flowy_user_pub::cloud::{MemberChangeReceiver, UserCloudService};
+use flowy_user_pub::entities::{MemberChangeEvent, Role, WorkspaceMember};
+use lib_dispatch::prelude::af_spawn;
+
+use crate::entities::UserWorkspacePB;
+use crate::notification::{send_notification, UserNotification};
+use crate::services::authenticate_user::AuthenticateUser;
+use crate::services::sqlite_sql::workspace_sql::{
+  get_user_workspace_op, update_user_workspace_member_count_op,
+};
+use crate::user_manager::UserManager;
+
+/// How often the members of the current workspace are fetched when the server can't push the
+/// member changes.
+const MEMBER_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The number of the role updates that [UserManager::update_workspace_members_roles] sends at the
+/// same time.
+const MAX_CONCURRENT_ROLE_UPDATES: usize = 4;
+
+type MemberChangeSenderMap =
+  parking_lot::Mutex<HashMap<String, broadcast::Sender<MemberChangeEvent>>>;
+pub(crate) type MemberChangeSenders = Arc<MemberChangeSenderMap>;
+
+impl UserManager {
+  /// Subscribes to the members joining or leaving the workspace. The changes are pushed by the
+  /// server if it supports it. Otherwise, the members are fetched every [MEMBER_POLL_INTERVAL]
+  /// while the workspace is the current workspace, and compared with the previous ones.
+  ///
+  /// The cached member count of the workspace is updated along with the changes, and
+  /// [UserNotification::DidUpdateUserWorkspace] is sent when it changes. The observation stops
+  /// when all the receivers are dropped.
+  pub fn subscribe_member_changes(
+    &self,
+    workspace_id: &str,
+  ) -> FlowyResult<broadcast::Receiver<MemberChangeEvent>> {
+    let mut senders = self.member_change_senders.lock();
+    if let Some(sender) = senders.get(workspace_id) {
+      return Ok(sender.subscribe());
+    }
+
+    let uid = self.user_id()?;
+    let user_service = self.cloud_services.get_user_service()?;
+    let (tx, rx) = broadcast::channel(100);
+    senders.insert(workspace_id.to_string(), tx.clone());
+    drop(senders);
+
+    let watcher = MemberChangeWatcher {
+      uid,
+      workspace_id: workspace_id.to_string(),
+      tx,
+      senders: Arc::downgrade(&self.member_change_senders),
+      authenticate_user: Arc::downgrade(&self.authenticate_user),
+    };
+    match user_service.subscribe_member_changes(workspace_id) {
+      Some(member_change_rx) => af_spawn(watcher.forward(member_change_rx)),
+      None => af_spawn(watcher.poll(user_service)),
+    };
+    Ok(rx)
+  }
+
+  /// Updates the roles of several members of the workspace at once. The updates are sent
+  /// concurrently, at most [MAX_CONCURRENT_ROLE_UPDATES] at a time. A failed update doesn't stop
+  /// the others, so the result of each update is returned along with the email of the member, in
+  /// the order of `changes`.
+  ///
+  /// Nothing is sent if the current user isn't allowed to assign one of the roles, see
+  /// [can_assign_role]. [ErrorCode::NotEnoughPermissions] is returned instead.
+  pub async fn update_workspace_members_roles(
+    &self,
+    workspace_id: String,
+    changes: Vec<(String, Role)>,
+  ) -> FlowyResult<Vec<(String, FlowyResult<()>)>> {
+    let session = self.get_session()?;
+    let profile = self.get_user_profile_from_disk(session.user_id).await?;
+    let user_service = self.cloud_services.get_user_service()?;
+    let members = user_service
+      .get_workspace_members(workspace_id.clone())
+      .await?;
+    let find_role = |email: &str| {
+      members
+        .iter()
+        .find(|member| member.email == email)
+        .map(|member| member.role.clone())
+    };
+
+    let actor_role = find_role(&profile.email).ok_or_else(|| {
+      FlowyError::new(
+        ErrorCode::NotEnoughPermissions,
+        "The current user isn't a member of the workspace",
+      )
+    })?;
+    for (email, role) in changes.iter() {
+      if !can_assign_role(&actor_role, find_role(email).as_ref(), role) {
+        return Err(FlowyError::new(
+          ErrorCode::NotEnoughPermissions,
+          format!(
+            "{:?} can't assign the role {:?} to {}",
+            actor_role, role, email
+          ),
+        ));
+      }
+    }
+
+    let results = stream::iter(changes)
+      .map(|(email, role)| {
+        let user_service = user_service.clone();
+        let workspace_id = workspace_id.clone();
+        async move {
+          let result = user_service
+            .update_workspace_member(email.clone(), workspace_id, role)
+            .await;
+          (email, result)
+        }
+      })
+      .buffered(MAX_CONCURRENT_ROLE_UPDATES)
+      .collect::<Vec<_>>()
+      .await;
+
+    if results.iter().any(|(_, result)| result.is_ok()) {
+      if let Err(err) = self
+        .user_status_callback
+        .read()
+        .await
+        .did_update_workspace_role(&workspace_id)
+        .await
+      {
+        error!("Update workspace role failed: {:?}", err);
+      }
+    }
+    Ok(results)
+  }
+}
+
+/// Returns true if a member with the `actor` role can change the role of a member from `current`
+/// to `new`. The owners can assign any role, and the members can only assign the roles up to
+/// their own to the other non-owners. `current` is None if the member isn't in the workspace, in
+/// which case the server decides.
+fn can_assign_role(actor: &Role, current: Option<&Role>, new: &Role) -> bool {
+  match actor {
+    Role::Owner => true,
+    Role::Member => {
+      !matches!(current, Some(Role::Owner)) && matches!(new, Role::Member | Role::Guest)
+    },
+    Role::Guest => false,
+  }
+}
+
+struct MemberChangeWatcher {
+  uid: i64,
+  workspace_id: String,
+  tx: broadcast::Sender<MemberChangeEvent>,
+  senders: Weak<MemberChangeSenderMap>,
+  authenticate_user: Weak<AuthenticateUser>,
+}
+
+impl MemberChangeWatcher {
+  async fn forward(self, mut member_change_rx: MemberChangeReceiver) {
+    loop {
+      let event = match member_change_rx.recv().await {
+        Ok(event) => event,
+        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+        Err(broadcast::error::RecvError::Closed) => break,
+      };
+      if self.should_stop() {
+        return;
+      }
+
+      let delta = match &event {
+        MemberChangeEvent::Added { .. } => 1,
+        MemberChangeEvent::Removed { .. } => -1,
+      };
+      if let Some(member_count) = self.member_count() {
+        self.update_member_count((member_count + delta).max(0));
+      }
+      let _ = self.tx.send(event);
+    }
+    self.remove_sender();
+  }
+
+  async fn poll(self, user_service: Arc<dyn UserCloudService>) {
+    let mut members: Option<Vec<WorkspaceMember>> = None;
+    let mut interval = tokio::time::interval(MEMBER_POLL_INTERVAL);
+    loop {
+      interval.tick().await;
+      if self.should_stop() {
+        return;
+      }
+      let is_current_workspace = self
+        .authenticate_user
+        .upgrade()
+        .and_then(|authenticate_user| authenticate_user.get_session().ok())
+        .map(|session| session.user_workspace.id == self.workspace_id)
+        .unwrap_or(false);
+      if !is_current_workspace {
+        continue;
+      }
+
+      let new_members = match user_service
+        .get_workspace_members(self.workspace_id.clone())
+        .await
+      {
+        Ok(new_members) => new_members,
+        Err(err) => {
+          warn!(
+            "Failed to get the members of workspace {}: {}",
+            self.workspace_id, err
+          );
+          continue;
+        },
+      };
+      if let Some(old_members) = &members {
+        for event in diff_workspace_members(&self.workspace_id, old_members, &new_members) {
+          let _ = self.tx.send(event);
+        }
+      }
+      self.update_member_count(new_members.len() as i64);
+      members = Some(new_members);
+    }
+  }
+
+  /// Returns true if all the receivers are dropped, and removes the sender so the next
+  /// subscription starts a new watcher.
+  fn should_stop(&self) -> bool {
+    let senders = match self.senders.upgrade() {
+      Some(senders) => senders,
+      None => return true,
+    };
+    let mut senders = senders.lock();
+    if self.tx.receiver_count() > 0 {
+      return false;
+    }
+    trace!(
+      "Stop observing the members of workspace {}",
+      self.workspace_id
+    );
+    senders.remove(&self.workspace_id);
+    true
+  }
+
+  fn remove_sender(&self) {
+    if let Some(senders) = self.senders.upgrade() {
+      senders.lock().remove(&self.workspace_id);
+    }
+  }
+
+  fn member_count(&self) -> Option<i64> {
+    let authenticate_user = self.authenticate_user.upgrade()?;
+    let conn = authenticate_user.database.get_connection(self.uid).ok()?;
+    get_user_workspace_op(&self.workspace_id, conn).map(|workspace| workspace.member_count)
+  }
+
+  fn update_member_count(&self, member_count: i64) {
+    if let Err(err) = self.try_update_member_count(member_count) {
+      error!(
+        "Failed to update the member count of workspace {}: {}",
+        self.workspace_id, err
+      );
+    }
+  }
+
+  fn try_update_member_count(&self, member_count: i64) -> FlowyResult<()> {
+    let authenticate_user = self
+      .authenticate_user
+      .upgrade()
+      .ok_or_else(|| FlowyError::internal().with_context("The user is dropped"))?;
+    let conn = authenticate_user.database.get_connection(self.uid)?;
+    let mut user_workspace =
+      get_user_workspace_op(&self.workspace_id, conn).ok_or_else(FlowyError::record_not_found)?;
+    if user_workspace.member_count == member_count {
+      return Ok(());
+    }
+
+    let conn = authenticate_user.database.get_connection(self.uid)?;
+    update_user_workspace_member_count_op(&self.workspace_id, member_count, conn)?;
+    user_workspace.member_count = member_count;
+    send_notification(
+      &self.uid.to_string(),
+      UserNotification::DidUpdateUserWorkspace,
+    )
+    .payload(UserWorkspacePB::from(user_workspace))
+    .send();
+    Ok(())
+  }
+}
+
+/// Returns the members that joined or left the workspace. The members are identified by email.
+fn diff_workspace_members(
+  workspace_id: &str,
+  old_members: &[WorkspaceMember],
+  new_members: &[WorkspaceMember],
+) -> Vec<MemberChangeEvent> {
+  let contains = |members: &[WorkspaceMember], member: &WorkspaceMember| {
+    members.iter().any(|other| other.email == member.email)
+  };
+  let added = new_members
+    .iter()
+    .filter(|member| !contains(old_members, member))
+    .map(|member| MemberChangeEvent::Added {
+      workspace_id: workspace_id.to_string(),
+      member: member.clone(),
+    });
+  let removed = old_members
+    .iter()
+    .filter(|member| !contains(new_members, member))
+    .map(|member| MemberChangeEvent::Removed {
+      workspace_id: workspace_id.to_string(),
+      member: member.clone(),
+    });
+  added.chain(removed).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use flowy_user_pub::entities::{MemberChangeEvent, Role, WorkspaceMember};
+
+  use super::{can_assign_role, diff_workspace_members};
+
+  fn member(email: &str) -> WorkspaceMember {
+    WorkspaceMember {
+      email: email.to_string(),
+      role: Role::Member,
+      name: email.to_string(),
+    }
+  }
+
+  #[test]
+  fn diff_workspace_members_test() {
+    let old_members = vec![member("a"), member("b")];
+    let new_members = vec![member("b"), member("c"), member("d")];
+    let changes = diff_workspace_members("w1", &old_members, &new_members)
+      .into_iter()
+      .map(|event| match event {
+        MemberChangeEvent::Added { member, .. } => format!("+{}", member.email),
+        MemberChangeEvent::Removed { member, .. } => format!("-{}", member.email),
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(changes, vec!["+c", "+d", "-a"]);
+
+    assert!(diff_workspace_members("w1", &old_members, &old_members).is_empty());
+  }
+
+  #[test]
+  fn can_assign_role_test() {
+    let cases = vec![
+      (Role::Owner, Some(Role::Guest), Role::Owner, true),
+      (Role::Owner, Some(Role::Owner), Role::Member, true),
+      (Role::Member, Some(Role::Guest), Role::Member, true),
+      (Role::Member, None, Role::Guest, true),
+      // The members can't elevate anyone to owner, nor change the role of an owner
+      (Role::Member, Some(Role::Member), Role::Owner, false),
+      (Role::Member, Some(Role::Owner), Role::Guest, false),
+      (Role::Guest, Some(Role::Guest), Role::Guest, false),
+    ];
+    for (actor, current, new, expected) in cases {
+      assert_eq!(
+        can_assign_role(&actor, current.as_ref(), &new),
+        expected,
+        "{:?} assigns {:?} to {:?}",
+        actor,
+        new,
+        current
+      );
+    }
+  }
+}
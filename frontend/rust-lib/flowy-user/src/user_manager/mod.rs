@@ -3,6 +3,8 @@ pub(crate) mod manager_history_user;
 pub(crate) mod manager_user_awareness;
 pub(crate) mod manager_user_encryption;
 pub(crate) mod manager_user_workspace;
+pub(crate) mod manager_user_workspace_auth;
+pub(crate) mod manager_user_workspace_member;
 mod user_login_state;
 
 pub use manager::*;
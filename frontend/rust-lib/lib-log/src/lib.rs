@@ -14,9 +14,11 @@ use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::{layer::SubscriberExt, EnvFilter};
 
 use crate::layer::FlowyFormattingLayer;
+use crate::redact::RedactMakeWriter;
 use crate::stream_log::{StreamLog, StreamLogSender};
 
 mod layer;
+pub mod redact;
 pub mod stream_log;
 
 lazy_static! {
@@ -31,6 +33,7 @@ pub struct Builder {
   #[allow(dead_code)]
   platform: Platform,
   stream_log_sender: Option<Arc<dyn StreamLogSender>>,
+  redact_sensitive_info: bool,
 }
 
 impl Builder {
@@ -53,6 +56,7 @@ impl Builder {
       file_appender,
       platform: platform.clone(),
       stream_log_sender,
+      redact_sensitive_info: !cfg!(debug_assertions),
     }
   }
 
@@ -61,19 +65,30 @@ impl Builder {
     self
   }
 
+  /// Masks emails, tokens and user ids in the log output. See [redact::redact_sensitive_info].
+  /// Enabled by default in release builds.
+  pub fn redact_sensitive_info(mut self, enable: bool) -> Self {
+    self.redact_sensitive_info = enable;
+    self
+  }
+
   pub fn build(self) -> Result<(), String> {
     let env_filter = EnvFilter::new(self.env_filter);
+    let redact = self.redact_sensitive_info;
     let (non_blocking, guard) = tracing_appender::non_blocking(self.file_appender);
-    let file_layer = FlowyFormattingLayer::new(non_blocking);
+    let file_layer = FlowyFormattingLayer::new(RedactMakeWriter::new(non_blocking, redact));
 
     if let Some(stream_log_sender) = &self.stream_log_sender {
       let subscriber = tracing_subscriber::fmt()
         .with_timer(CustomTime)
         .with_max_level(tracing::Level::TRACE)
         .with_ansi(self.platform.is_not_ios())
-        .with_writer(StreamLog {
-          sender: stream_log_sender.clone(),
-        })
+        .with_writer(RedactMakeWriter::new(
+          StreamLog {
+            sender: stream_log_sender.clone(),
+          },
+          redact,
+        ))
         .with_thread_ids(false)
         .pretty()
         .with_env_filter(env_filter)
@@ -86,11 +101,15 @@ impl Builder {
         .with_timer(CustomTime)
         .with_max_level(tracing::Level::TRACE)
         .with_ansi(true)
+        .with_writer(RedactMakeWriter::new(io::stdout, redact))
         .with_thread_ids(false)
         .pretty()
         .with_env_filter(env_filter)
         .finish()
-        .with(FlowyFormattingLayer::new(DebugStdoutWriter))
+        .with(FlowyFormattingLayer::new(RedactMakeWriter::new(
+          DebugStdoutWriter,
+          redact,
+        )))
         .with(JsonStorageLayer)
         .with(file_layer);
       set_global_default(subscriber).map_err(|e| format!("{:?}", e))?;
@@ -0,0 +1,315 @@
+use std::io;
+use std::io::Write;
+
+use tracing_subscriber::fmt::MakeWriter;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// The value of these keys is replaced with [REDACTED] when it's written to the log.
+const SECRET_KEYS: [&str; 5] = [
+  "token",
+  "access_token",
+  "refresh_token",
+  "password",
+  "secret",
+];
+
+/// The value of these keys is truncated to [TRUNCATED_LEN] characters when it's written to the log.
+const TRUNCATED_KEYS: [&str; 2] = ["user_uuid", "uuid"];
+const TRUNCATED_LEN: usize = 8;
+
+/// Masks the personal or sensitive information in a log line:
+/// 1. Email addresses keep their first character and domain, e.g. `j***@appflowy.io`.
+/// 2. The values of [SECRET_KEYS], `Bearer` credentials and JWTs are replaced with [REDACTED].
+/// 3. The values of [TRUNCATED_KEYS] are truncated to their first [TRUNCATED_LEN] characters.
+pub fn redact_sensitive_info(line: &str) -> String {
+  let line = redact_emails(line);
+  let line = redact_key_values(&line, &SECRET_KEYS, |_| REDACTED.to_string());
+  let line = redact_key_values(&line, &TRUNCATED_KEYS, |value| {
+    let truncated = value.chars().take(TRUNCATED_LEN).collect::<String>();
+    if truncated.len() < value.len() {
+      format!("{}...", truncated)
+    } else {
+      truncated
+    }
+  });
+  let line = redact_bearer_tokens(&line);
+  redact_jwts(&line)
+}
+
+fn is_email_local_char(c: char) -> bool {
+  c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-')
+}
+
+fn is_email_domain_char(c: char) -> bool {
+  c.is_ascii_alphanumeric() || matches!(c, '.' | '-')
+}
+
+fn is_token_char(c: char) -> bool {
+  c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '+' | '/' | '=')
+}
+
+fn redact_emails(line: &str) -> String {
+  if !line.contains('@') {
+    return line.to_string();
+  }
+
+  let chars = line.chars().collect::<Vec<char>>();
+  let mut output = String::with_capacity(line.len());
+  let mut cursor = 0;
+  let mut i = 0;
+  while i < chars.len() {
+    if chars[i] != '@' {
+      i += 1;
+      continue;
+    }
+
+    let mut start = i;
+    while start > cursor && is_email_local_char(chars[start - 1]) {
+      start -= 1;
+    }
+    let mut end = i + 1;
+    while end < chars.len() && is_email_domain_char(chars[end]) {
+      end += 1;
+    }
+    // Trailing dots belong to the sentence rather than the domain
+    while end > i + 1 && chars[end - 1] == '.' {
+      end -= 1;
+    }
+
+    let domain = chars[i + 1..end].iter().collect::<String>();
+    if start == i || !domain.contains('.') {
+      i += 1;
+      continue;
+    }
+
+    output.extend(&chars[cursor..start]);
+    output.push(chars[start]);
+    output.push_str("***@");
+    output.push_str(&domain);
+    cursor = end;
+    i = end;
+  }
+  output.extend(&chars[cursor..]);
+  output
+}
+
+/// Finds `key` followed by `:` or `=`, optionally quoted, e.g. `token: "abc"`, `"token":"abc"` or
+/// `token=abc`, and replaces the value with the result of `replace`.
+fn redact_key_values<F>(line: &str, keys: &[&str], replace: F) -> String
+where
+  F: Fn(&str) -> String,
+{
+  let mut output = String::with_capacity(line.len());
+  let mut rest = line;
+  'outer: while !rest.is_empty() {
+    for key in keys {
+      if let Some(value_range) = find_value_of_key(rest, key) {
+        let (start, end) = value_range;
+        output.push_str(&rest[..start]);
+        output.push_str(&replace(&rest[start..end]));
+        rest = &rest[end..];
+        continue 'outer;
+      }
+    }
+
+    // No key matches at the start of `rest`, move to the next character
+    let next = rest.chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+    output.push_str(&rest[..next]);
+    rest = &rest[next..];
+  }
+  output
+}
+
+/// Returns the byte range of the value if `s` starts with `key`.
+fn find_value_of_key(s: &str, key: &str) -> Option<(usize, usize)> {
+  // Skip an optional quote that wraps the key
+  let after_quote = s.strip_prefix('"').unwrap_or(s);
+  let after_key = after_quote.strip_prefix(key)?;
+  let mut offset = s.len() - after_key.len();
+
+  let bytes = s.as_bytes();
+  if offset < bytes.len() && bytes[offset] == b'"' {
+    offset += 1;
+  }
+  while offset < bytes.len() && bytes[offset] == b' ' {
+    offset += 1;
+  }
+  if offset >= bytes.len() || !matches!(bytes[offset], b':' | b'=') {
+    return None;
+  }
+  offset += 1;
+  while offset < bytes.len() && bytes[offset] == b' ' {
+    offset += 1;
+  }
+
+  let quoted = offset < bytes.len() && bytes[offset] == b'"';
+  if quoted {
+    offset += 1;
+  }
+  let start = offset;
+  let end = s[start..]
+    .char_indices()
+    .find(|(_, c)| {
+      if quoted {
+        *c == '"'
+      } else {
+        c.is_whitespace() || matches!(c, ',' | '}' | ')' | ']' | '&')
+      }
+    })
+    .map(|(index, _)| start + index)
+    .unwrap_or(s.len());
+
+  if start == end {
+    None
+  } else {
+    Some((start, end))
+  }
+}
+
+fn redact_bearer_tokens(line: &str) -> String {
+  const BEARER: &str = "Bearer ";
+  let mut output = String::with_capacity(line.len());
+  let mut rest = line;
+  while let Some(index) = rest.find(BEARER) {
+    let token_start = index + BEARER.len();
+    let token_end = rest[token_start..]
+      .char_indices()
+      .find(|(_, c)| !is_token_char(*c))
+      .map(|(i, _)| token_start + i)
+      .unwrap_or(rest.len());
+    output.push_str(&rest[..token_start]);
+    if token_end > token_start {
+      output.push_str(REDACTED);
+    }
+    rest = &rest[token_end..];
+  }
+  output.push_str(rest);
+  output
+}
+
+/// JWTs always start with `eyJ`, the base64 encoding of `{"`.
+fn redact_jwts(line: &str) -> String {
+  const JWT_PREFIX: &str = "eyJ";
+  let mut output = String::with_capacity(line.len());
+  let mut rest = line;
+  while let Some(index) = rest.find(JWT_PREFIX) {
+    let token_end = rest[index..]
+      .char_indices()
+      .find(|(_, c)| !is_token_char(*c))
+      .map(|(i, _)| index + i)
+      .unwrap_or(rest.len());
+    output.push_str(&rest[..index]);
+    if rest[index..token_end].matches('.').count() >= 2 {
+      output.push_str(REDACTED);
+    } else {
+      output.push_str(&rest[index..token_end]);
+    }
+    rest = &rest[token_end..];
+  }
+  output.push_str(rest);
+  output
+}
+
+/// Wraps a [MakeWriter] and masks the sensitive information of each log line before it's written.
+/// The log lines are written as they are if `enable` is false.
+pub struct RedactMakeWriter<M> {
+  inner: M,
+  enable: bool,
+}
+
+impl<M> RedactMakeWriter<M> {
+  pub fn new(inner: M, enable: bool) -> Self {
+    Self { inner, enable }
+  }
+}
+
+impl<'a, M> MakeWriter<'a> for RedactMakeWriter<M>
+where
+  M: MakeWriter<'a>,
+{
+  type Writer = RedactWriter<M::Writer>;
+
+  fn make_writer(&'a self) -> Self::Writer {
+    RedactWriter {
+      inner: self.inner.make_writer(),
+      enable: self.enable,
+    }
+  }
+}
+
+pub struct RedactWriter<W> {
+  inner: W,
+  enable: bool,
+}
+
+impl<W: Write> Write for RedactWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    if !self.enable {
+      return self.inner.write(buf);
+    }
+
+    match std::str::from_utf8(buf) {
+      Ok(line) => {
+        self
+          .inner
+          .write_all(redact_sensitive_info(line).as_bytes())?;
+        Ok(buf.len())
+      },
+      Err(_) => self.inner.write(buf),
+    }
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::redact_sensitive_info;
+
+  #[test]
+  fn redact_email_test() {
+    assert_eq!(
+      redact_sensitive_info("sign in with nathan@appflowy.io."),
+      "sign in with n***@appflowy.io."
+    );
+    assert_eq!(
+      redact_sensitive_info(r#"{"email":"lucas.xu@appflowy.io","name":"lucas"}"#),
+      r#"{"email":"l***@appflowy.io","name":"lucas"}"#
+    );
+    assert_eq!(redact_sensitive_info("@appflowy"), "@appflowy");
+  }
+
+  #[test]
+  fn redact_token_test() {
+    assert_eq!(
+      redact_sensitive_info(r#"{"access_token":"abc123","expires_in":3600}"#),
+      r#"{"access_token":"[REDACTED]","expires_in":3600}"#
+    );
+    assert_eq!(
+      redact_sensitive_info("token: abc123, refresh_token=xyz"),
+      "token: [REDACTED], refresh_token=[REDACTED]"
+    );
+    assert_eq!(
+      redact_sensitive_info("Authorization: Bearer abc.def-123"),
+      "Authorization: Bearer [REDACTED]"
+    );
+    assert_eq!(
+      redact_sensitive_info("url: appflowy-flutter://#access_token=eyJhbGc.eyJzdWI.sig"),
+      "url: appflowy-flutter://#access_token=[REDACTED]"
+    );
+    assert_eq!(redact_sensitive_info("tokens: 5"), "tokens: 5");
+  }
+
+  #[test]
+  fn truncate_uuid_test() {
+    assert_eq!(
+      redact_sensitive_info(
+        r#"Session { user_id: 1, user_uuid: "a3b0e4c2-1234-4a5b-9c8d-0123456789ab" }"#
+      ),
+      r#"Session { user_id: 1, user_uuid: "a3b0e4c2..." }"#
+    );
+  }
+}